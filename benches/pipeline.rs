@@ -0,0 +1,95 @@
+//! Rust-level benchmarks of the stages of the Hinton pipeline. Run with `cargo bench`.
+//!
+//! The scanner, parser, and compiler are measured on a large generated program, since the
+//! Hinton benchmarks in this directory are too small to time those stages reliably. The VM
+//! is measured by running each of the Hinton benchmarks with the `hinton bench` runner.
+use hinton::bench_runner::{bench_file, BenchOptions, BenchResult};
+use hinton::built_in::BuiltIn;
+use hinton::compiler::{Compiler, OptLevel};
+use hinton::lexer::Lexer;
+use hinton::parser::Parser;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// The number of times each stage is measured.
+const RUNS: usize = 20;
+
+/// Generates a program with the given number of classes, each of which
+/// has a few fields and methods with loops, conditionals, and calls.
+fn generate_program(classes: usize) -> String {
+   let mut src = String::new();
+
+   for i in 0..classes {
+      src += &format!(
+         "class Shape{i} {{
+   pub var width = {i};
+   pub var height = {i}.5;
+
+   pub func area() {{
+      return self.width * self.height;
+   }}
+
+   pub func scale(by) {{
+      var total = 0;
+      for var k in 0..by {{
+         if k % 2 == 0 {{ total += self.area(); }} else {{ total -= k; }}
+      }}
+      return 'Shape{i}: ${{total}}';
+   }}
+}}
+
+var shape{i} = new Shape{i}();
+shape{i}.scale(3);
+",
+         i = i
+      );
+   }
+
+   src
+}
+
+/// Measures a function, and prints the median time of its runs.
+fn bench(name: &str, mut f: impl FnMut()) {
+   let mut times: Vec<Duration> = (0..RUNS)
+      .map(|_| {
+         let start = Instant::now();
+         f();
+         start.elapsed()
+      })
+      .collect();
+   times.sort();
+
+   println!("{:<24} {:>12}", name, format!("{:.2?}", times[RUNS / 2]));
+}
+
+fn main() {
+   let src = generate_program(500);
+   let ast = Parser::parse(&src).unwrap_or_else(|_| panic!("The generated program has errors."));
+
+   println!("{:<24} {:>12}", "Stage", "Median");
+   bench("scan", || {
+      Lexer::new(&src).tokens().count();
+   });
+   bench("parse", || {
+      Parser::parse(&src).ok();
+   });
+   bench("compile", || {
+      Compiler::compile_ast(&PathBuf::new(), &ast, &BuiltIn::default()).ok();
+   });
+
+   let options = BenchOptions {
+      runs: 5,
+      opt_level: OptLevel::default(),
+      module_dirs: vec![],
+   };
+
+   println!();
+   BenchResult::print_header();
+
+   let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("benches");
+   for file in hinton::test_runner::collect_test_files(&dir) {
+      if let Some(result) = bench_file(&file, &options) {
+         result.print();
+      }
+   }
+}
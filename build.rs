@@ -0,0 +1,19 @@
+//! Records the version of the compiler that builds the crate, which is part of the handshake
+//! between the interpreter and the native plugins it loads (see `src/built_in/plugins.rs`).
+
+use std::env;
+use std::process::Command;
+
+fn main() {
+   let rustc = env::var("RUSTC").unwrap_or_else(|_| String::from("rustc"));
+   let version = Command::new(rustc)
+      .arg("--version")
+      .output()
+      .ok()
+      .and_then(|output| String::from_utf8(output.stdout).ok())
+      .unwrap_or_default();
+
+   println!("cargo:rustc-env=HINTON_RUSTC_VERSION={}", version.trim());
+   println!("cargo:rerun-if-changed=build.rs");
+   println!("cargo:rerun-if-env-changed=RUSTC");
+}
@@ -0,0 +1,8 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+// Counts the allocated bytes, so that the heap limit of the sandboxed VM is enforced.
+#[global_allocator]
+static ALLOCATOR: hinton::virtual_machine::CountingAllocator = hinton::virtual_machine::CountingAllocator;
+
+fuzz_target!(|data: &[u8]| hinton::fuzz::fuzz_vm(data));
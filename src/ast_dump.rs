@@ -0,0 +1,1282 @@
+//! Dumps the abstract syntax tree of a Hinton program as indented text or as JSON, which is
+//! printed by the `hinton ast <file>` command. Each node of the dump has its kind (the name of
+//! the AST node, e.g., `Binary`), the range of bytes of the source code it was parsed from,
+//! its properties (e.g., the operator of a binary expression), and its children.
+
+use crate::core::ast::*;
+use crate::core::tokens::{Span, Token, TokenType};
+use crate::errors::ErrorReport;
+use crate::lexer::Lexer;
+use crate::lsp::json::Json;
+use crate::objects::Object;
+use crate::parser::Parser;
+use hashbrown::HashMap;
+
+/// The formats of the dumped syntax tree.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AstFormat {
+   /// Indented text, with a line for each node.
+   Text,
+   /// A JSON object for the root node, where each node has `kind`, `span`, and `children` members.
+   Json,
+}
+
+impl AstFormat {
+   /// Gets the format of the dump from its name (`text` or `json`).
+   ///
+   /// # Parameters
+   /// - `name`: The name of the format.
+   pub fn from_name(name: &str) -> Option<Self> {
+      match name.to_lowercase().as_str() {
+         "text" => Some(AstFormat::Text),
+         "json" => Some(AstFormat::Json),
+         _ => None,
+      }
+   }
+}
+
+/// A node of the dumped syntax tree.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SyntaxNode {
+   /// The kind of the node, which is the name of the AST node (e.g., `VariableDecl`).
+   pub kind: &'static str,
+   /// The range of bytes of the source code that the node was parsed from.
+   pub span: Span,
+   /// The properties of the node (e.g., the name of a variable), which are not nodes.
+   pub properties: Vec<(&'static str, Json)>,
+   /// The children of the node, along with their role in the node (e.g., `left` or `right`).
+   pub children: Vec<(&'static str, SyntaxNode)>,
+}
+
+impl SyntaxNode {
+   /// Gets the children of the node with the given role.
+   pub fn children_with_role<'a>(&'a self, role: &'a str) -> impl Iterator<Item = &'a SyntaxNode> {
+      self
+         .children
+         .iter()
+         .filter(move |(r, _)| *r == role)
+         .map(|(_, c)| c)
+   }
+
+   /// Prints the node and its descendants as indented text, with a line for each node.
+   pub fn to_text(&self) -> String {
+      let mut text = String::new();
+      self.write_text(&mut text, "", 0);
+      text
+   }
+
+   /// Writes the line of the node, followed by the lines of its children.
+   fn write_text(&self, text: &mut String, role: &str, depth: usize) {
+      text.push_str(&"  ".repeat(depth));
+
+      if !role.is_empty() {
+         text.push_str(&format!("{}: ", role));
+      }
+
+      text.push_str(&format!("{} [{}..{}]", self.kind, self.span.start, self.span.end));
+
+      for (name, value) in self.properties.iter() {
+         match value {
+            Json::String(s) if !s.is_empty() && !s.contains(char::is_whitespace) => {
+               text.push_str(&format!(" {}={}", name, s))
+            }
+            _ => text.push_str(&format!(" {}={}", name, value)),
+         }
+      }
+
+      text.push('\n');
+
+      for (role, child) in self.children.iter() {
+         child.write_text(text, role, depth + 1);
+      }
+   }
+
+   /// Converts the node and its descendants into a JSON object.
+   pub fn to_json(&self) -> Json {
+      let mut members = vec![
+         (String::from("kind"), Json::from(self.kind)),
+         (
+            String::from("span"),
+            Json::object(vec![
+               ("start", self.span.start.into()),
+               ("end", self.span.end.into()),
+            ]),
+         ),
+      ];
+
+      for (name, value) in self.properties.iter() {
+         members.push((name.to_string(), value.clone()));
+      }
+
+      let children = self
+         .children
+         .iter()
+         .map(|(role, child)| match child.to_json() {
+            Json::Object(mut members) => {
+               members.insert(0, (String::from("role"), Json::from(*role)));
+               Json::Object(members)
+            }
+            json => json,
+         })
+         .collect();
+
+      members.push((String::from("children"), Json::Array(children)));
+      Json::Object(members)
+   }
+}
+
+/// Parses the source code of a program, without compiling it, into its syntax tree.
+///
+/// # Parameters
+/// - `source`: The source code.
+///
+/// # Returns
+/// - `Result<SyntaxNode, Vec<ErrorReport>>`: The root node of the syntax tree, or the syntax
+///   errors of the program.
+pub fn syntax_tree(source: &str) -> Result<SyntaxNode, Vec<ErrorReport>> {
+   let ast = Parser::parse(source)?;
+   let builder = TreeBuilder::new(source);
+
+   let mut root = builder.node(&ast);
+   root.span = Span {
+      start: 0,
+      end: source.len(),
+   };
+
+   builder.extend_bodies(&mut root);
+   Ok(root)
+}
+
+/// Dumps the syntax tree of a program in the given format.
+///
+/// # Parameters
+/// - `source`: The source code.
+/// - `format`: The format of the dump.
+pub fn dump_ast(source: &str, format: AstFormat) -> Result<String, Vec<ErrorReport>> {
+   let tree = syntax_tree(source)?;
+
+   Ok(match format {
+      AstFormat::Text => tree.to_text(),
+      AstFormat::Json => format!("{}\n", tree.to_json()),
+   })
+}
+
+/// The roles of the children that are the statements of a body (or the members of a class),
+/// whose spans extend to the start of the next statement.
+const BODY_ROLES: [&str; 3] = ["body", "member", "method"];
+
+/// Converts the nodes of an AST into the nodes of the syntax tree. The AST does not store the
+/// position of every token of the code (e.g., of the parenthesis of a call), so the spans of
+/// the nodes are computed from the tokens of the source code.
+struct TreeBuilder<'a> {
+   /// The tokens of the source code, without the comments.
+   tokens: Vec<Token<'a>>,
+   /// The index of the token at each line and column of the source code.
+   positions: HashMap<(usize, usize), usize>,
+   /// The index of the matching closing bracket of each opening bracket.
+   closers: HashMap<usize, usize>,
+   /// The index of the matching opening bracket of each closing bracket.
+   openers: HashMap<usize, usize>,
+}
+
+impl<'a> TreeBuilder<'a> {
+   /// Creates a builder for the syntax tree of a source code, matching its brackets.
+   fn new(source: &'a str) -> Self {
+      let tokens: Vec<Token> = Lexer::new(source).tokens().collect();
+      let mut positions = HashMap::new();
+      let mut closers = HashMap::new();
+      let mut open = vec![];
+
+      for (idx, token) in tokens.iter().enumerate() {
+         positions.insert((token.line_num, token.column_start), idx);
+
+         match token.token_type {
+            TokenType::L_PAREN
+            | TokenType::L_BRACKET
+            | TokenType::L_CURLY
+            | TokenType::INTERPOLATION_START => open.push(idx),
+            TokenType::R_PARENTHESIS
+            | TokenType::R_BRACKET
+            | TokenType::R_CURLY
+            | TokenType::INTERPOLATION_END => {
+               if let Some(opener) = open.pop() {
+                  closers.insert(opener, idx);
+               }
+            }
+            _ => {}
+         }
+      }
+
+      let openers = closers.iter().map(|(o, c)| (*c, *o)).collect();
+
+      TreeBuilder {
+         tokens,
+         positions,
+         closers,
+         openers,
+      }
+   }
+
+   /// Gets the index of the first token that starts at or after a byte offset.
+   fn first_token_from(&self, offset: usize) -> usize {
+      self.tokens.partition_point(|t| t.span.start < offset)
+   }
+
+   /// Gets the index of the last token that starts before a byte offset.
+   fn last_token_before(&self, offset: usize) -> Option<usize> {
+      self.first_token_from(offset).checked_sub(1)
+   }
+
+   /// Creates a node, whose span covers the given tokens and the spans of its children.
+   fn make(
+      &self,
+      kind: &'static str,
+      tokens: &[&Token],
+      properties: Vec<(&'static str, Json)>,
+      children: Vec<(&'static str, SyntaxNode)>,
+   ) -> SyntaxNode {
+      let spans = tokens
+         .iter()
+         .map(|t| t.span)
+         .chain(children.iter().map(|(_, c)| c.span));
+
+      let span = spans.reduce(|a, b| Span {
+         start: a.start.min(b.start),
+         end: a.end.max(b.end),
+      });
+
+      SyntaxNode {
+         kind,
+         span: self.balance(span.unwrap_or_default()),
+         properties,
+         children,
+      }
+   }
+
+   /// Extends a span so that every bracket in it is matched by a bracket in the span
+   /// (e.g., the span of the arguments of a call is extended to the closing parenthesis).
+   fn balance(&self, span: Span) -> Span {
+      let mut first = self.first_token_from(span.start);
+      let mut last = match self.last_token_before(span.end) {
+         Some(idx) if idx >= first => idx,
+         _ => return span,
+      };
+
+      let mut idx = first;
+      while idx <= last {
+         if let Some(&closer) = self.closers.get(&idx) {
+            last = last.max(closer);
+         }
+
+         if let Some(&opener) = self.openers.get(&idx) {
+            first = first.min(opener);
+         }
+
+         idx += 1;
+      }
+
+      // Brackets found before the original start of the span may have their own brackets.
+      if first < self.first_token_from(span.start) {
+         return self.balance(Span {
+            start: self.tokens[first].span.start,
+            end: self.tokens[last].span.end,
+         });
+      }
+
+      Span {
+         start: self.tokens[first].span.start,
+         end: self.tokens[last].span.end,
+      }
+   }
+
+   /// Extends the start of a node's span over the tokens before it that satisfy a predicate
+   /// (e.g., the `var` keyword before the name of a variable).
+   fn extend_back(&self, node: &mut SyntaxNode, is_prefix: impl Fn(&Token) -> bool) {
+      let mut idx = self.first_token_from(node.span.start);
+
+      while idx > 0 && is_prefix(&self.tokens[idx - 1]) {
+         idx -= 1;
+      }
+
+      if let Some(token) = self.tokens.get(idx) {
+         node.span.start = node.span.start.min(token.span.start);
+      }
+   }
+
+   /// Extends the end of a node's span over the next token, if it satisfies a predicate, or over
+   /// the brackets that the next token opens (e.g., the empty arguments of a call).
+   fn extend_forward(&self, node: &mut SyntaxNode, is_next: impl Fn(&Token) -> bool) {
+      let idx = self.first_token_from(node.span.end);
+
+      if let Some(token) = self.tokens.get(idx).filter(|t| is_next(t)) {
+         let end = match self.closers.get(&idx) {
+            Some(&closer) => self.tokens[closer].span.end,
+            None => token.span.end,
+         };
+
+         node.span.end = node.span.end.max(end);
+      }
+   }
+
+   /// Extends the end of a node's span to the `}` that closes its body, which is the first `{`
+   /// after the given offset that is not nested in parenthesis or brackets.
+   fn extend_to_body(&self, node: &mut SyntaxNode, after: usize) {
+      let mut nesting = 0;
+
+      for idx in self.first_token_from(after)..self.tokens.len() {
+         match self.tokens[idx].token_type {
+            TokenType::L_PAREN | TokenType::L_BRACKET => nesting += 1,
+            TokenType::R_PARENTHESIS | TokenType::R_BRACKET => nesting -= 1,
+            TokenType::L_CURLY if nesting == 0 => {
+               if let Some(&closer) = self.closers.get(&idx) {
+                  node.span.end = node.span.end.max(self.tokens[closer].span.end);
+               }
+
+               return;
+            }
+            _ => {}
+         }
+      }
+   }
+
+   /// Extends the spans of the statements in the bodies of a node (and of its descendants) to
+   /// the last token before the next statement, or before the `}` that closes the body. Used
+   /// for the parts of the statements that are not stored in the AST (e.g., an empty body).
+   fn extend_bodies(&self, node: &mut SyntaxNode) {
+      let limit = match self.last_token_before(node.span.end) {
+         Some(idx) if node.kind != "Module" && matches!(self.tokens[idx].token_type, TokenType::R_CURLY) => {
+            self.tokens[idx].span.start
+         }
+         _ => node.span.end,
+      };
+
+      let statements: Vec<usize> = (0..node.children.len())
+         .filter(|i| BODY_ROLES.contains(&node.children[*i].0))
+         .collect();
+
+      for (n, &idx) in statements.iter().enumerate() {
+         let next_start = match statements.get(n + 1) {
+            Some(&next) => node.children[next].1.span.start,
+            None => limit,
+         };
+
+         if let Some(last) = self.last_token_before(next_start) {
+            let child = &mut node.children[idx].1;
+            child.span.end = child.span.end.max(self.tokens[last].span.end);
+         }
+      }
+
+      for (_, child) in node.children.iter_mut() {
+         self.extend_bodies(child);
+      }
+   }
+
+   /// Converts a list of AST nodes into children with the given role.
+   fn nodes(&self, role: &'static str, nodes: &[ASTNode]) -> Vec<(&'static str, SyntaxNode)> {
+      nodes.iter().map(|n| (role, self.node(n))).collect()
+   }
+
+   /// Converts an AST node into a node of the syntax tree.
+   fn node(&self, node: &ASTNode) -> SyntaxNode {
+      match node {
+         ASTNode::Module(module) => self.make("Module", &[], vec![], self.nodes("body", &module.body)),
+         ASTNode::Array(expr) => {
+            self.make("Array", &[&expr.token], vec![], self.nodes("value", &expr.values))
+         }
+         ASTNode::Binary(expr) => self.make(
+            "Binary",
+            &[&expr.opr_token],
+            vec![("operator", expr.opr_token.lexeme.as_ref().into())],
+            vec![("left", self.node(&expr.left)), ("right", self.node(&expr.right))],
+         ),
+         ASTNode::ComparisonChain(expr) => {
+            let operators: Vec<Json> = expr
+               .operators
+               .iter()
+               .map(|(token, _)| token.lexeme.as_ref().into())
+               .collect();
+            let tokens: Vec<&Token> = expr.operators.iter().map(|(token, _)| token).collect();
+
+            self.make(
+               "ComparisonChain",
+               &tokens,
+               vec![("operators", Json::Array(operators))],
+               self.nodes("operand", &expr.operands),
+            )
+         }
+         ASTNode::Comprehension(expr) => {
+            let mut children = vec![];
+            if let Some(key) = &expr.key {
+               children.push(("key", self.node(key)));
+            }
+
+            children.push(("value", self.node(&expr.value)));
+
+            for clause in expr.clauses.iter() {
+               let mut variables = vec![clause.id.token.lexeme.as_ref()];
+               if let Some(value_id) = &clause.value_id {
+                  variables.push(value_id.token.lexeme.as_ref());
+               }
+
+               let mut clause_children = vec![("iterator", self.node(&clause.iterator))];
+               if let Some(condition) = &clause.condition {
+                  clause_children.push(("condition", self.node(condition)));
+               }
+
+               let tokens = [&clause.token, &clause.id.token];
+               let properties = vec![("variables", variables.join(", ").into())];
+               children.push((
+                  "clause",
+                  self.make("ComprehensionClause", &tokens, properties, clause_children),
+               ));
+            }
+
+            self.make("Comprehension", &[&expr.token], vec![], children)
+         }
+         ASTNode::Dictionary(expr) => {
+            let entries = expr
+               .keys
+               .iter()
+               .zip(expr.values.iter())
+               .map(|(key, value)| {
+                  let entry = self.make(
+                     "Entry",
+                     &[key],
+                     vec![("key", key.lexeme.as_ref().into())],
+                     vec![("value", self.node(value))],
+                  );
+
+                  ("entry", entry)
+               })
+               .collect();
+
+            self.make("Dictionary", &[&expr.token], vec![], entries)
+         }
+         ASTNode::FunctionCall(call) => self.call("FunctionCall", call),
+         ASTNode::Instance(call) => {
+            let mut node = self.call("Instance", call);
+            self.extend_back(&mut node, |t| matches!(t.token_type, TokenType::NEW_KW));
+            node
+         }
+         ASTNode::Identifier(id) => self.identifier(&id.token),
+         ASTNode::Literal(lit) => self.literal(lit),
+         ASTNode::ObjectGetter(expr) => {
+            let mut properties = vec![("property", expr.getter.lexeme.as_ref().into())];
+
+            if expr.is_optional {
+               properties.push(("optional", true.into()));
+            }
+
+            self.make(
+               "ObjectGetter",
+               &[&expr.getter],
+               properties,
+               vec![("target", self.node(&expr.target))],
+            )
+         }
+         ASTNode::OptionalChain(expr) => self.make(
+            "OptionalChain",
+            &[&expr.token],
+            vec![],
+            vec![("chain", self.node(&expr.chain))],
+         ),
+         ASTNode::NonNullAssertion(expr) => self.make(
+            "NonNullAssertion",
+            &[&expr.token],
+            vec![],
+            vec![("operand", self.node(&expr.operand))],
+         ),
+         ASTNode::ObjectSetter(expr) => self.make(
+            "ObjectSetter",
+            &[&expr.setter],
+            vec![
+               ("property", expr.setter.lexeme.as_ref().into()),
+               ("operator", reassignment_operator(&expr.opr_type).into()),
+            ],
+            vec![
+               ("target", self.node(&expr.target)),
+               ("value", self.node(&expr.value)),
+            ],
+         ),
+         ASTNode::SelfExpr(expr) => self.make("SelfExpr", &[&expr.token], vec![], vec![]),
+         ASTNode::Slice(expr) => {
+            let mut children = vec![("target", self.node(&expr.target))];
+
+            for (role, part) in [("start", &expr.start), ("end", &expr.end), ("step", &expr.step)] {
+               if let Some(part) = part {
+                  children.push((role, self.node(part)));
+               }
+            }
+
+            let mut node = self.make("Slice", &[], vec![], children);
+
+            // A slice without bounds (e.g., `a[..]`) has no children inside its brackets.
+            if node.children.len() == 1 {
+               self.extend_forward(&mut node, |t| matches!(t.token_type, TokenType::L_BRACKET));
+            }
+
+            node
+         }
+         ASTNode::SuperExpr(expr) => self.make(
+            "SuperExpr",
+            &[&expr.token, &expr.method],
+            vec![("method", expr.method.lexeme.as_ref().into())],
+            vec![],
+         ),
+         ASTNode::StringInterpolation(expr) => self.make(
+            "StringInterpolation",
+            &[&expr.token],
+            vec![],
+            self.nodes("part", &expr.parts),
+         ),
+         ASTNode::Subscript(expr) => self.make(
+            "Subscript",
+            &[],
+            vec![],
+            vec![
+               ("target", self.node(&expr.target)),
+               ("index", self.node(&expr.index)),
+            ],
+         ),
+         ASTNode::SubscriptAssignment(expr) => self.make(
+            "SubscriptAssignment",
+            &[],
+            vec![("operator", reassignment_operator(&expr.opr_type).into())],
+            vec![
+               ("target", self.node(&expr.target)),
+               ("index", self.node(&expr.index)),
+               ("value", self.node(&expr.value)),
+            ],
+         ),
+         ASTNode::TernaryConditional(expr) => self.make(
+            if expr.is_if_expression {
+               "IfExpression"
+            } else {
+               "TernaryConditional"
+            },
+            &[&expr.true_branch_token, &expr.false_branch_token],
+            vec![],
+            vec![
+               ("condition", self.node(&expr.condition)),
+               ("then", self.node(&expr.branch_true)),
+               ("else", self.node(&expr.branch_false)),
+            ],
+         ),
+         ASTNode::Tuple(expr) => {
+            self.make("Tuple", &[&expr.token], vec![], self.nodes("value", &expr.values))
+         }
+         ASTNode::Unary(expr) => {
+            let operator = match expr.opr_type {
+               UnaryExprType::ArithmeticNeg => "-",
+               UnaryExprType::LogicNeg => "!",
+               UnaryExprType::BitwiseNeg => "~",
+               UnaryExprType::Await => "await",
+            };
+
+            let operator_token = self.positions.get(&expr.pos).map(|&idx| &self.tokens[idx]);
+
+            self.make(
+               "Unary",
+               &operator_token.into_iter().collect::<Vec<_>>(),
+               vec![("operator", operator.into())],
+               vec![("operand", self.node(&expr.operand))],
+            )
+         }
+         ASTNode::VarReassignment(expr) => self.make(
+            "VarReassignment",
+            &[&expr.target],
+            vec![
+               ("name", expr.target.lexeme.as_ref().into()),
+               ("operator", reassignment_operator(&expr.opr_type).into()),
+            ],
+            vec![("value", self.node(&expr.value))],
+         ),
+         ASTNode::Lambda(decl) => self.function("Lambda", decl),
+         ASTNode::LoopExpr(expr) => {
+            let children = vec![("body", self.node(&expr.body))];
+            let node = self.make("LoopExpr", &[&expr.token], label_property(&expr.label), children);
+            self.labeled(node, &expr.label)
+         }
+         ASTNode::ClassDecl(decl) => self.class_declaration(decl),
+         ASTNode::ConstantDecl(decl) => self.constant_declaration(decl),
+         ASTNode::ExportDecl(decl) => {
+            let node = self.make(
+               "ExportDecl",
+               &[&decl.token],
+               vec![],
+               vec![("decl", self.node(&decl.decl))],
+            );
+            self.statement(node)
+         }
+         ASTNode::FunctionDecl(decl) => {
+            let mut node = self.function("FunctionDecl", decl);
+            self.extend_back(&mut node, |t| {
+               matches!(t.token_type, TokenType::FUNC_KW | TokenType::ASYNC_KW)
+            });
+            node
+         }
+         ASTNode::ImportDecl(decl) => {
+            let node = self.make(
+               "ImportDecl",
+               &[&decl.token, &decl.name, &decl.path_token],
+               vec![
+                  ("name", decl.name.lexeme.as_ref().into()),
+                  ("path", decl.path.as_str().into()),
+               ],
+               vec![],
+            );
+
+            self.statement(node)
+         }
+         ASTNode::InterfaceDecl(decl) => self.interface_declaration(decl),
+         ASTNode::EnumDecl(decl) => self.enum_declaration(decl),
+         ASTNode::VariableDecl(decl) => self.variable_declaration(decl),
+         ASTNode::AssertStmt(stmt) => {
+            let mut children = vec![("condition", self.node(&stmt.condition))];
+
+            if let Some(message) = &stmt.message {
+               children.push(("message", self.node(message)));
+            }
+
+            self.statement(self.make("AssertStmt", &[&stmt.token], vec![], children))
+         }
+         ASTNode::BlockStmt(block) => {
+            let end = &block.end_of_block;
+            let start = self.tokens.iter().position(|t| t.span == end.span);
+            let opener = start
+               .and_then(|idx| self.openers.get(&idx))
+               .map(|&idx| &self.tokens[idx]);
+
+            let mut tokens = vec![end];
+            tokens.extend(opener);
+
+            self.make("BlockStmt", &tokens, vec![], self.nodes("body", &block.body))
+         }
+         ASTNode::DeferStmt(stmt) => {
+            let mut node = self.make(
+               "DeferStmt",
+               &[&stmt.token],
+               vec![],
+               self.nodes("body", &stmt.body.body),
+            );
+            self.extend_forward(&mut node, |t| matches!(t.token_type, TokenType::L_CURLY));
+            self.statement(node)
+         }
+         ASTNode::ExpressionStmt(stmt) => {
+            let node = self.make(
+               "ExpressionStmt",
+               &[],
+               vec![],
+               vec![("expression", self.node(&stmt.child))],
+            );
+            self.statement(node)
+         }
+         ASTNode::ForStmt(stmt) => {
+            let mut variables = vec![stmt.id.token.lexeme.as_ref()];
+            if let Some(value_id) = &stmt.value_id {
+               variables.push(value_id.token.lexeme.as_ref());
+            }
+
+            let mut children = vec![("iterator", self.node(&stmt.iterator))];
+            children.extend(self.nodes("body", &stmt.body));
+
+            let mut properties = vec![("variables", variables.join(", ").into())];
+            properties.extend(label_property(&stmt.label));
+
+            let mut node = self.make("ForStmt", &[&stmt.token, &stmt.id.token], properties, children);
+
+            let iterator_end = node.children[0].1.span.end;
+            self.extend_to_body(&mut node, iterator_end);
+            self.labeled(node, &stmt.label)
+         }
+         ASTNode::IfStmt(stmt) => {
+            let mut children = vec![
+               ("condition", self.node(&stmt.condition)),
+               ("then", self.node(&stmt.then_branch)),
+            ];
+
+            if let Some(else_branch) = &*stmt.else_branch {
+               children.push(("else", self.node(else_branch)));
+            }
+
+            self.make("IfStmt", &[&stmt.then_token], vec![], children)
+         }
+         ASTNode::LoopBranch(stmt) => {
+            let branch = if stmt.is_break { "break" } else { "continue" };
+            let mut properties = vec![("branch", branch.into())];
+            properties.extend(label_property(&stmt.label));
+
+            let mut tokens = vec![&stmt.token];
+            tokens.extend(stmt.label.iter());
+
+            let children = match &stmt.value {
+               Some(value) => vec![("value", self.node(value))],
+               None => vec![],
+            };
+
+            self.statement(self.make("LoopBranch", &tokens, properties, children))
+         }
+         ASTNode::MatchStmt(stmt) => self.match_statement(stmt),
+         ASTNode::ReturnStmt(stmt) => {
+            let children = match &stmt.value {
+               Some(value) => vec![("value", self.node(value))],
+               None => vec![],
+            };
+
+            self.statement(self.make("ReturnStmt", &[&stmt.token], vec![], children))
+         }
+         ASTNode::TryStmt(stmt) => {
+            let mut properties = vec![];
+            let mut children = vec![("body", self.node(&stmt.body))];
+
+            if let Some(id) = &stmt.catch_id {
+               properties.push(("error", id.lexeme.as_ref().into()));
+            }
+
+            if let Some(catch_body) = &stmt.catch_body {
+               children.push(("catch", self.node(catch_body)));
+            }
+
+            if let Some(finally_body) = &stmt.finally_body {
+               children.push(("finally", self.node(finally_body)));
+            }
+
+            // The body of the `try` statement is a block, not a list of statements.
+            children[0].0 = "try";
+
+            self.make("TryStmt", &[&stmt.token], properties, children)
+         }
+         ASTNode::WhileStmt(stmt) => {
+            let kind = match stmt.token.token_type {
+               TokenType::DO_KW => "DoWhileStmt",
+               TokenType::LOOP_KW => "LoopStmt",
+               _ => "WhileStmt",
+            };
+
+            let mut children = vec![];
+            if let Some(condition) = &stmt.condition {
+               children.push(("condition", self.node(condition)));
+            }
+            children.push(("body", self.node(&stmt.body)));
+
+            // The body of a `do-while` loop is a block written before its condition.
+            if let TokenType::DO_KW = stmt.token.token_type {
+               children.reverse();
+               children[0].0 = "do";
+            }
+
+            let node = self.make(kind, &[&stmt.token], label_property(&stmt.label), children);
+            self.labeled(node, &stmt.label)
+         }
+      }
+   }
+
+   /// Extends the span of a loop over its label (e.g., `outer:` in `outer: for ...`).
+   fn labeled(&self, mut node: SyntaxNode, label: &Option<Token>) -> SyntaxNode {
+      if let Some(label) = label {
+         node.span.start = label.span.start;
+      }
+
+      node
+   }
+
+   /// Extends the span of a statement over the `;` after it.
+   fn statement(&self, mut node: SyntaxNode) -> SyntaxNode {
+      self.extend_forward(&mut node, |t| matches!(t.token_type, TokenType::SEMICOLON));
+      node
+   }
+
+   /// Creates the node of an identifier.
+   fn identifier(&self, token: &Token) -> SyntaxNode {
+      self.make(
+         "Identifier",
+         &[token],
+         vec![("name", token.lexeme.as_ref().into())],
+         vec![],
+      )
+   }
+
+   /// Creates the node of a literal, with its source text and the type of its value.
+   fn literal(&self, lit: &LiteralExprNode) -> SyntaxNode {
+      let mut node = self.make(
+         "Literal",
+         &[&lit.token],
+         vec![
+            ("value", lit.token.lexeme.as_ref().into()),
+            ("type", lit.value.type_name().into()),
+         ],
+         vec![],
+      );
+
+      // The segments of an interpolated string are matched as brackets, but they span only
+      // their own token.
+      if matches!(
+         lit.token.token_type,
+         TokenType::INTERPOLATION_START | TokenType::INTERPOLATION_MID | TokenType::INTERPOLATION_END
+      ) {
+         node.span = lit.token.span;
+      }
+
+      node
+   }
+
+   /// Creates the node of a function call or of a class instantiation.
+   fn call(&self, kind: &'static str, call: &FunctionCallExprNode) -> SyntaxNode {
+      let mut children = vec![("target", self.node(&call.target))];
+
+      for arg in call.args.iter() {
+         let mut properties = vec![];
+         let mut tokens = vec![];
+
+         if let Some(name) = &arg.name {
+            properties.push(("name", name.lexeme.as_ref().into()));
+            tokens.push(name);
+         }
+
+         if arg.is_spread {
+            properties.push(("spread", true.into()));
+         }
+
+         let mut node = self.make(
+            "Argument",
+            &tokens,
+            properties,
+            vec![("value", self.node(&arg.value))],
+         );
+
+         if arg.is_spread {
+            self.extend_back(&mut node, |t| matches!(t.token_type, TokenType::ELLIPSIS));
+         }
+
+         children.push(("arg", node));
+      }
+
+      let mut node = self.make(kind, &[], vec![], children);
+
+      // A call without arguments has no children inside its parenthesis.
+      if call.args.is_empty() {
+         self.extend_forward(&mut node, |t| matches!(t.token_type, TokenType::L_PAREN));
+      }
+
+      node
+   }
+
+   /// Creates the node of a parameter of a function.
+   fn parameter(&self, param: &Parameter) -> SyntaxNode {
+      let mut properties = vec![("name", param.name.lexeme.as_ref().into())];
+
+      if let Some(annotation) = &param.annotation {
+         properties.push(("type", annotation.name.lexeme.as_ref().into()));
+      }
+
+      if param.is_optional && !param.is_variadic && param.default.is_none() {
+         properties.push(("optional", true.into()));
+      }
+
+      if param.is_variadic {
+         properties.push(("variadic", true.into()));
+      }
+
+      let children = match &param.default {
+         Some(default) => vec![("default", self.node(default))],
+         None => vec![],
+      };
+
+      let mut tokens = vec![&param.name];
+      tokens.extend(param.annotation.as_ref().map(|a| &a.name));
+
+      let mut node = self.make("Parameter", &tokens, properties, children);
+
+      if param.is_variadic {
+         self.extend_back(&mut node, |t| matches!(t.token_type, TokenType::ELLIPSIS));
+      }
+
+      node
+   }
+
+   /// Creates the node of a function declaration or of a lambda.
+   fn function(&self, kind: &'static str, decl: &FunctionDeclNode) -> SyntaxNode {
+      let mut properties = vec![];
+      let mut tokens = vec![&decl.name];
+
+      if kind != "Lambda" {
+         properties.push(("name", decl.name.lexeme.as_ref().into()));
+      }
+
+      if let Some(return_type) = &decl.return_type {
+         properties.push(("return_type", return_type.name.lexeme.as_ref().into()));
+         tokens.push(&return_type.name);
+      }
+
+      if let Some(doc) = &decl.doc {
+         properties.push(("doc", doc.as_str().into()));
+      }
+
+      if decl.is_async {
+         properties.push(("async", true.into()));
+      }
+
+      let mut children: Vec<_> = match decl.name.token_type {
+         // The `it` parameter of a trailing closure is not written in the source code.
+         TokenType::L_CURLY => vec![],
+         _ => decl.params.iter().map(|p| ("param", self.parameter(p))).collect(),
+      };
+
+      match (&decl.name.token_type, &decl.body[..]) {
+         (
+            TokenType::BIT_OR | TokenType::LOGIC_OR,
+            [ASTNode::ReturnStmt(ReturnStmtNode {
+               value: Some(value), ..
+            })],
+         ) => children.push(("value", self.node(value))),
+         _ => children.extend(self.nodes("body", &decl.body)),
+      }
+
+      let mut node = self.make(kind, &tokens, properties, children);
+
+      // The body of a shorthand lambda (e.g., `|x| x * 2`) is not wrapped in braces, and the
+      // name of a trailing closure is the `{` that opens its body.
+      match decl.name.token_type {
+         TokenType::BIT_OR | TokenType::LOGIC_OR => {}
+         TokenType::L_CURLY => self.extend_to_body(&mut node, decl.name.span.start),
+         _ => self.extend_to_body(&mut node, decl.name.span.end),
+      }
+
+      if decl.is_async {
+         self.extend_back(&mut node, |t| matches!(t.token_type, TokenType::ASYNC_KW));
+      }
+
+      node
+   }
+
+   /// Creates the node of a variable declaration.
+   fn variable_declaration(&self, decl: &VariableDeclNode) -> SyntaxNode {
+      let names: Vec<&str> = decl.identifiers.iter().map(|id| id.lexeme.as_ref()).collect();
+      let mut properties = vec![("names", names.join(", ").into())];
+
+      let types: Vec<&str> = decl
+         .annotations
+         .iter()
+         .map(|a| a.as_ref().map_or("", |a| a.name.lexeme.as_ref()))
+         .collect();
+
+      if types.iter().any(|t| !t.is_empty()) {
+         properties.push(("types", types.join(", ").into()));
+      }
+
+      if decl.destructure {
+         properties.push(("destructure", true.into()));
+      }
+
+      // Variables declared without a value are initialized with a `null` literal that is not
+      // in the source code (e.g., `var x;`), which is not part of the tree.
+      let children = match &*decl.value {
+         ASTNode::Literal(lit) if matches!(lit.value, Object::Null) && lit.token.lexeme != "null" => vec![],
+         value => vec![("value", self.node(value))],
+      };
+
+      let tokens: Vec<&Token> = decl.identifiers.iter().collect();
+      let mut node = self.make("VariableDecl", &tokens, properties, children);
+
+      self.extend_back(&mut node, |t| {
+         matches!(t.token_type, TokenType::VAR_KW)
+            || (decl.destructure && matches!(t.token_type, TokenType::L_PAREN))
+      });
+
+      self.statement(node)
+   }
+
+   /// Creates the node of a constant declaration.
+   fn constant_declaration(&self, decl: &ConstantDeclNode) -> SyntaxNode {
+      let mut properties = vec![("name", decl.name.lexeme.as_ref().into())];
+
+      if let Some(annotation) = &decl.annotation {
+         properties.push(("type", annotation.name.lexeme.as_ref().into()));
+      }
+
+      let mut node = self.make(
+         "ConstantDecl",
+         &[&decl.name],
+         properties,
+         vec![("value", self.node(&decl.value))],
+      );
+
+      self.extend_back(&mut node, |t| matches!(t.token_type, TokenType::CONST_KW));
+      self.statement(node)
+   }
+
+   /// Creates the node of a class declaration, along with its members.
+   fn class_declaration(&self, decl: &ClassDeclNode) -> SyntaxNode {
+      let mut properties = vec![("name", decl.name.lexeme.as_ref().into())];
+      let mut tokens = vec![&decl.name];
+
+      if let Some(superclass) = &decl.superclass {
+         properties.push(("superclass", superclass.token.lexeme.as_ref().into()));
+         tokens.push(&superclass.token);
+      }
+
+      if !decl.interfaces.is_empty() {
+         let names: Vec<&str> = decl.interfaces.iter().map(|i| i.token.lexeme.as_ref()).collect();
+         properties.push(("interfaces", names.join(", ").into()));
+         tokens.extend(decl.interfaces.iter().map(|i| &i.token));
+      }
+
+      if let Some(doc) = &decl.doc {
+         properties.push(("doc", doc.as_str().into()));
+      }
+
+      let members = decl
+         .members
+         .iter()
+         .map(|m| ("member", self.class_member(m)))
+         .collect();
+
+      let mut node = self.make("ClassDecl", &tokens, properties, members);
+      self.extend_back(&mut node, |t| matches!(t.token_type, TokenType::CLASS_KW));
+      self.extend_to_body(&mut node, decl.name.span.end);
+      node
+   }
+
+   /// Creates the node of a class member, with its modifiers.
+   fn class_member(&self, member: &ClassMemberDeclNode) -> SyntaxNode {
+      let mut properties = vec![];
+
+      for (bit, modifier) in [(0b_1000, "public"), (0b_0100, "static"), (0b_0010, "override")] {
+         if member.mode & bit != 0 {
+            properties.push((modifier, true.into()));
+         }
+      }
+
+      if member.mode & 0b_0001_0000 != 0 {
+         properties.push(("accessor", "get".into()));
+      } else if member.mode & 0b_0010_0000 != 0 {
+         properties.push(("accessor", "set".into()));
+      }
+
+      let decl = match &member.member_type {
+         ClassMemberDecl::Var(decl) => self.variable_declaration(decl),
+         ClassMemberDecl::Const(decl) => self.constant_declaration(decl),
+         ClassMemberDecl::Method(decl) => {
+            let mut node = self.function("FunctionDecl", decl);
+            self.extend_back(&mut node, |t| {
+               matches!(t.token_type, TokenType::FUNC_KW | TokenType::ASYNC_KW)
+                  || (matches!(t.token_type, TokenType::IDENTIFIER)
+                     && (t.lexeme == "get" || t.lexeme == "set"))
+            });
+            node
+         }
+      };
+
+      let mut node = self.make("ClassMember", &[], properties, vec![("decl", decl)]);
+      self.extend_back(&mut node, |t| {
+         matches!(
+            t.token_type,
+            TokenType::PUBLIC_KW | TokenType::STATIC_KW | TokenType::OVERRIDE_KW
+         )
+      });
+
+      node
+   }
+
+   /// Creates the node of an interface declaration, along with its method signatures.
+   fn interface_declaration(&self, decl: &InterfaceDeclNode) -> SyntaxNode {
+      let methods = decl
+         .methods
+         .iter()
+         .map(|method| {
+            let mut properties = vec![("name", method.name.lexeme.as_ref().into())];
+            let mut tokens = vec![&method.name];
+
+            if let Some(return_type) = &method.return_type {
+               properties.push(("return_type", return_type.name.lexeme.as_ref().into()));
+               tokens.push(&return_type.name);
+            }
+
+            let params = method
+               .params
+               .iter()
+               .map(|p| ("param", self.parameter(p)))
+               .collect();
+
+            let mut node = self.make("InterfaceMethod", &tokens, properties, params);
+            self.extend_back(&mut node, |t| matches!(t.token_type, TokenType::FUNC_KW));
+            ("method", self.statement(node))
+         })
+         .collect();
+
+      let mut node = self.make(
+         "InterfaceDecl",
+         &[&decl.name],
+         vec![("name", decl.name.lexeme.as_ref().into())],
+         methods,
+      );
+
+      self.extend_back(&mut node, |t| matches!(t.token_type, TokenType::INTERFACE_KW));
+      self.extend_to_body(&mut node, decl.name.span.end);
+      node
+   }
+
+   /// Creates the node of an enum declaration, along with its variants.
+   fn enum_declaration(&self, decl: &EnumDeclNode) -> SyntaxNode {
+      let variants = decl
+         .variants
+         .iter()
+         .map(|variant| {
+            let fields = variant.fields.iter().map(|f| f.lexeme.as_ref().into()).collect();
+            let mut tokens = vec![&variant.name];
+            tokens.extend(variant.fields.iter());
+
+            let mut node = self.make(
+               "EnumVariant",
+               &tokens,
+               vec![
+                  ("name", variant.name.lexeme.as_ref().into()),
+                  ("fields", Json::Array(fields)),
+               ],
+               vec![],
+            );
+
+            if !variant.fields.is_empty() {
+               self.extend_forward(&mut node, |t| matches!(t.token_type, TokenType::R_PARENTHESIS));
+            }
+
+            ("variant", node)
+         })
+         .collect();
+
+      let mut node = self.make(
+         "EnumDecl",
+         &[&decl.name],
+         vec![("name", decl.name.lexeme.as_ref().into())],
+         variants,
+      );
+
+      self.extend_back(&mut node, |t| matches!(t.token_type, TokenType::ENUM_KW));
+      self.extend_to_body(&mut node, decl.name.span.end);
+      node
+   }
+
+   /// Creates the node of a `match` statement, along with its arms.
+   fn match_statement(&self, stmt: &MatchStmtNode) -> SyntaxNode {
+      let mut children = vec![("subject", self.node(&stmt.subject))];
+
+      for arm in stmt.arms.iter() {
+         let mut patterns: Vec<(&'static str, SyntaxNode)> = arm
+            .patterns
+            .iter()
+            .map(|p| ("pattern", self.pattern(p)))
+            .collect();
+
+         if let Some(guard) = &arm.guard {
+            patterns.push(("guard", self.node(guard)));
+         }
+
+         patterns.push(("body", self.node(&arm.body)));
+         children.push(("arm", self.make("MatchArm", &[], vec![], patterns)));
+      }
+
+      if let Some(default) = &stmt.default {
+         children.push(("default", self.node(default)));
+      }
+
+      self.make("MatchStmt", &[&stmt.token], vec![], children)
+   }
+
+   /// Creates the node of a pattern of a `match` arm.
+   fn pattern(&self, pattern: &MatchPattern) -> SyntaxNode {
+      match pattern {
+         MatchPattern::Literal(lit) => self.literal(lit),
+         MatchPattern::Range(start, end) => self.make(
+            "RangePattern",
+            &[],
+            vec![],
+            vec![("start", self.literal(start)), ("end", self.literal(end))],
+         ),
+         MatchPattern::Wildcard(token) => self.make("WildcardPattern", &[token], vec![], vec![]),
+         MatchPattern::Binding(name) => self.make(
+            "BindingPattern",
+            &[name],
+            vec![("name", name.lexeme.as_ref().into())],
+            vec![],
+         ),
+         MatchPattern::Array(token, items) => {
+            let items = items.iter().map(|p| ("item", self.pattern(p))).collect();
+            self.make("ArrayPattern", &[token], vec![], items)
+         }
+         MatchPattern::Variant {
+            enum_name,
+            variant,
+            fields,
+         } => {
+            let patterns = fields
+               .iter()
+               .flat_map(|f| f.iter())
+               .map(|p| ("field", self.pattern(p)))
+               .collect();
+
+            let mut node = self.make(
+               "VariantPattern",
+               &[enum_name, variant],
+               vec![
+                  ("enum", enum_name.lexeme.as_ref().into()),
+                  ("variant", variant.lexeme.as_ref().into()),
+               ],
+               patterns,
+            );
+
+            // The parenthesis around the payload's patterns are part of the pattern.
+            if fields.is_some() {
+               self.extend_forward(&mut node, |t| {
+                  matches!(t.token_type, TokenType::L_PAREN | TokenType::R_PARENTHESIS)
+               });
+            }
+
+            node
+         }
+         MatchPattern::Dictionary(token, entries) => {
+            let entries = entries
+               .iter()
+               .map(|(key, value)| {
+                  let entry = self.make(
+                     "Entry",
+                     &[key],
+                     vec![("key", key.lexeme.as_ref().into())],
+                     vec![("value", self.pattern(value))],
+                  );
+
+                  ("entry", entry)
+               })
+               .collect();
+
+            self.make("DictionaryPattern", &[token], vec![], entries)
+         }
+      }
+   }
+}
+
+/// Gets the operator of a reassignment.
+fn reassignment_operator(opr_type: &ReassignmentType) -> &'static str {
+   match opr_type {
+      ReassignmentType::Plus => "+=",
+      ReassignmentType::Minus => "-=",
+      ReassignmentType::Mul => "*=",
+      ReassignmentType::Div => "/=",
+      ReassignmentType::FloorDiv => "~/=",
+      ReassignmentType::Expo => "**=",
+      ReassignmentType::Mod => "%=",
+      ReassignmentType::ShiftL => "<<=",
+      ReassignmentType::ShiftR => ">>=",
+      ReassignmentType::BitAnd => "&=",
+      ReassignmentType::Xor => "^=",
+      ReassignmentType::BitOr => "|=",
+      ReassignmentType::Assign => "=",
+   }
+}
+
+/// Gets the `label` property of a loop or of a `break` or `continue` statement, if it has a label.
+fn label_property(label: &Option<Token>) -> Vec<(&'static str, Json)> {
+   label
+      .iter()
+      .map(|l| ("label", l.lexeme.as_ref().into()))
+      .collect()
+}
@@ -0,0 +1,147 @@
+use crate::built_in::BuiltIn;
+use crate::compiler::{Compiler, OptLevel, WarningMode};
+use crate::errors::print_errors_list;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::virtual_machine::{InterpretResult, VM};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// The options used to benchmark a file.
+pub struct BenchOptions {
+   /// The number of times each stage of the file is measured.
+   pub runs: usize,
+   /// Determines which optimizations are applied to the benchmarked files.
+   pub opt_level: OptLevel,
+   /// The directories searched for the modules imported by the benchmarked files.
+   pub module_dirs: Vec<PathBuf>,
+}
+
+/// The time taken by each stage of a benchmarked file. Each duration is the median of
+/// the measurements made across the runs of the benchmark.
+pub struct BenchResult {
+   /// The path to the benchmarked file.
+   pub file: PathBuf,
+   /// The time taken to scan the file into tokens.
+   pub scan: Duration,
+   /// The time taken to parse the file into an AST, including the time taken to scan it.
+   pub parse: Duration,
+   /// The time taken to compile the file's AST into bytecode.
+   pub compile: Duration,
+   /// The time taken by the VM to execute the file's bytecode.
+   pub run: Duration,
+}
+
+impl BenchResult {
+   /// Prints the header of the table of benchmark results.
+   pub fn print_header() {
+      println!(
+         "{:<24} {:>12} {:>12} {:>12} {:>12}",
+         "Benchmark", "Scan", "Parse", "Compile", "Run"
+      );
+   }
+
+   /// Prints the benchmark result as a row of the table of benchmark results.
+   pub fn print(&self) {
+      let name = self.file.file_name().unwrap_or_default().to_string_lossy();
+
+      println!(
+         "{:<24} {:>12} {:>12} {:>12} {:>12}",
+         name,
+         format!("{:.2?}", self.scan),
+         format!("{:.2?}", self.parse),
+         format!("{:.2?}", self.compile),
+         format!("{:.2?}", self.run)
+      );
+   }
+}
+
+/// Gets the median of a list of measurements.
+///
+/// # Parameters
+/// - `times`: The measurements. Must not be empty.
+fn median(mut times: Vec<Duration>) -> Duration {
+   times.sort();
+   times[times.len() / 2]
+}
+
+/// Benchmarks a Hinton source file by measuring the time taken to scan, parse, compile, and
+/// execute the file, as many times as requested by the options. Each execution runs in a fresh
+/// VM, and the output of the program is discarded. Files that cannot be compiled, or whose
+/// execution ends in an error, are not benchmarked, and their errors are printed instead.
+///
+/// # Parameters
+/// - `filepath`: The canonical path to the file.
+/// - `options`: The options used to benchmark the file.
+///
+/// # Returns
+/// - `Option<BenchResult>`: The time taken by each stage, or `None` if the file had errors.
+pub fn bench_file(filepath: &Path, options: &BenchOptions) -> Option<BenchResult> {
+   let source = match fs::read_to_string(filepath) {
+      Ok(src) => src,
+      Err(_) => {
+         eprintln!("Could not read benchmark file '{}'.", filepath.display());
+         return None;
+      }
+   };
+
+   let runs = options.runs.max(1);
+   let (mut scan, mut parse, mut compile, mut run) = (vec![], vec![], vec![], vec![]);
+
+   for _ in 0..runs {
+      let start = Instant::now();
+      Lexer::new(&source).tokens().count();
+      scan.push(start.elapsed());
+
+      let start = Instant::now();
+      let ast = match Parser::parse(&source) {
+         Ok(ast) => ast,
+         Err(e) => {
+            let _ = print_errors_list(&mut io::stderr(), filepath, &e, &source);
+            return None;
+         }
+      };
+      parse.push(start.elapsed());
+
+      let start = Instant::now();
+      let module = match Compiler::compile_ast_with_warnings(
+         filepath,
+         &ast,
+         &BuiltIn::default(),
+         WarningMode::Silence,
+         options.opt_level,
+         &options.module_dirs,
+      ) {
+         Ok((module, _)) => module,
+         Err(e) => {
+            let _ = print_errors_list(&mut io::stderr(), filepath, &e, &source);
+            return None;
+         }
+      };
+      compile.push(start.elapsed());
+
+      // Only the errors of the program are shown.
+      let mut vm = VM::new(filepath.to_path_buf())
+         .with_stdout(Box::new(io::sink()))
+         .with_opt_level(options.opt_level)
+         .with_module_dirs(options.module_dirs.clone());
+
+      let start = Instant::now();
+      let result = vm.execute(module, &source);
+      run.push(start.elapsed());
+
+      if !matches!(result, InterpretResult::Ok) {
+         return None;
+      }
+   }
+
+   Some(BenchResult {
+      file: filepath.to_path_buf(),
+      scan: median(scan),
+      parse: median(parse),
+      compile: median(compile),
+      run: median(run),
+   })
+}
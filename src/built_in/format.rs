@@ -0,0 +1,210 @@
+//! The format strings of Hinton, used by the `format(...)` native function and the
+//! `String.format(...)` method. The `{}` placeholders of a format string are replaced by the
+//! arguments of the call, in order. A placeholder may contain the index of its argument (as in
+//! `{0}`), and a specification after a colon with the fill character and alignment, the minimum
+//! width, and the precision of the argument (as in `{:>8}`, `{0:*^10}`, `{:05}`, or `{:.2}`).
+//! Literal braces are written as `{{` and `}}`.
+
+use crate::errors::RuntimeErrorType;
+use crate::objects::Object;
+use crate::virtual_machine::{RuntimeResult, VM};
+
+/// The specification of how an argument is formatted, written after the colon of a placeholder
+/// as `[[fill]align][0][width][.precision]`.
+struct FormatSpec {
+   /// The character that pads the argument to the minimum width.
+   fill: char,
+   /// The alignment of the argument (`<`, `>`, or `^`). Numbers are aligned to the right by
+   /// default, and other values to the left.
+   align: Option<char>,
+   /// Whether numbers are padded with zeros after their sign.
+   zero: bool,
+   /// The minimum width of the formatted argument, in characters.
+   width: usize,
+   /// The number of decimal digits of numbers, or the maximum length of other values.
+   precision: Option<usize>,
+}
+
+/// Reads the decimal number that starts at the given position of a specification.
+fn read_number(chars: &[char], pos: &mut usize) -> Option<usize> {
+   let start = *pos;
+   while chars.get(*pos).is_some_and(|c| c.is_ascii_digit()) {
+      *pos += 1;
+   }
+
+   chars[start..*pos].iter().collect::<String>().parse().ok()
+}
+
+/// Parses the specification of a placeholder.
+///
+/// # Returns
+/// - `Option<FormatSpec>`: The specification, or `None` if it is not valid.
+fn parse_spec(spec: &str) -> Option<FormatSpec> {
+   let chars: Vec<char> = spec.chars().collect();
+   let is_align = |c: Option<&char>| matches!(c, Some('<' | '>' | '^'));
+
+   let mut format = FormatSpec {
+      fill: ' ',
+      align: None,
+      zero: false,
+      width: 0,
+      precision: None,
+   };
+
+   let mut pos = 0;
+   if is_align(chars.get(1)) {
+      format.fill = chars[0];
+      format.align = Some(chars[1]);
+      pos = 2;
+   } else if is_align(chars.first()) {
+      format.align = Some(chars[0]);
+      pos = 1;
+   }
+
+   if chars.get(pos) == Some(&'0') {
+      format.zero = true;
+      pos += 1;
+   }
+
+   format.width = read_number(&chars, &mut pos).unwrap_or(0);
+
+   if chars.get(pos) == Some(&'.') {
+      pos += 1;
+      format.precision = Some(read_number(&chars, &mut pos)?);
+   }
+
+   if pos == chars.len() {
+      Some(format)
+   } else {
+      None
+   }
+}
+
+/// Formats an argument of a format string with the specification of its placeholder.
+fn format_arg(vm: &mut VM, arg: &Object, spec: &FormatSpec) -> Result<String, RuntimeResult> {
+   let is_number = matches!(arg, Object::Int(_) | Object::Float(_) | Object::BigInt(_));
+
+   let mut text = match (spec.precision, arg) {
+      (Some(p), Object::Float(f)) => format!("{:.*}", p, f),
+      (Some(p), Object::Int(i)) => format!("{:.*}", p, *i as f64),
+      (Some(p), _) => vm.stringify(arg)?.chars().take(p).collect(),
+      (None, _) => vm.stringify(arg)?,
+   };
+
+   let len = text.chars().count();
+   if len >= spec.width {
+      return Ok(text);
+   }
+
+   let padding = spec.width - len;
+
+   // Zeros are inserted after the sign of a number, so that `-5` becomes `-005`.
+   if spec.zero && is_number {
+      let sign = if text.starts_with('-') { 1 } else { 0 };
+      text.insert_str(sign, &"0".repeat(padding));
+      return Ok(text);
+   }
+
+   let fill = if spec.zero { '0' } else { spec.fill };
+   let (left, right) = match spec.align.unwrap_or(if is_number { '>' } else { '<' }) {
+      '<' => (0, padding),
+      '>' => (padding, 0),
+      _ => (padding / 2, padding - padding / 2),
+   };
+
+   let fill_left: String = std::iter::repeat_n(fill, left).collect();
+   let fill_right: String = std::iter::repeat_n(fill, right).collect();
+   Ok(format!("{}{}{}", fill_left, text, fill_right))
+}
+
+/// Replaces the placeholders of a format string with the arguments of the call.
+///
+/// # Parameters
+/// - `vm`: The virtual machine, used to call the `__str__` method of instances.
+/// - `template`: The format string.
+/// - `args`: The arguments that replace the placeholders.
+///
+/// # Returns
+/// - `Result<String, RuntimeResult>`: The formatted string, or an `ArgumentError` if the
+///   format string is not valid.
+pub(crate) fn format_template(vm: &mut VM, template: &str, args: &[Object]) -> Result<String, RuntimeResult> {
+   let format_error = |message: String| RuntimeResult::Error {
+      error: RuntimeErrorType::ArgumentError,
+      message,
+   };
+
+   let mut result = String::with_capacity(template.len());
+   let mut next_arg = 0;
+   let mut chars = template.chars().peekable();
+
+   while let Some(c) = chars.next() {
+      match c {
+         '{' if chars.peek() == Some(&'{') => {
+            chars.next();
+            result.push('{');
+         }
+         '}' if chars.peek() == Some(&'}') => {
+            chars.next();
+            result.push('}');
+         }
+         '{' => {
+            let mut placeholder = String::new();
+
+            loop {
+               match chars.next() {
+                  Some('}') => break,
+                  Some(c) => placeholder.push(c),
+                  None => return Err(format_error(String::from("Unclosed '{' in format string."))),
+               }
+            }
+
+            let (index, spec) = match placeholder.split_once(':') {
+               Some((index, spec)) => (index, spec),
+               None => (placeholder.as_str(), ""),
+            };
+
+            let idx = if index.is_empty() {
+               next_arg += 1;
+               next_arg - 1
+            } else {
+               match index.trim().parse::<usize>() {
+                  Ok(idx) => idx,
+                  Err(_) => {
+                     return Err(format_error(format!(
+                        "Invalid placeholder '{{{}}}' in format string.",
+                        placeholder
+                     )))
+                  }
+               }
+            };
+
+            let spec = match parse_spec(spec) {
+               Some(spec) => spec,
+               None => {
+                  return Err(format_error(format!(
+                     "Invalid format specification '{}' in placeholder '{{{}}}'.",
+                     spec, placeholder
+                  )))
+               }
+            };
+
+            let arg = match args.get(idx) {
+               Some(arg) => arg,
+               None => {
+                  return Err(format_error(format!(
+                     "Format string refers to argument {}, but only {} argument(s) were given.",
+                     idx,
+                     args.len()
+                  )))
+               }
+            };
+
+            result.push_str(&format_arg(vm, arg, &spec)?);
+         }
+         '}' => return Err(format_error(String::from("Unmatched '}' in format string."))),
+         c => result.push(c),
+      }
+   }
+
+   Ok(result)
+}
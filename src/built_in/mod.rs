@@ -1,15 +1,30 @@
 use crate::built_in::natives::Natives;
 use crate::built_in::primitives::Primitives;
+use crate::errors::ObjectOprErrType;
 use crate::objects::{NativeFuncObj, NativeMethodObj, Object};
 use crate::virtual_machine::{RuntimeResult, VM};
+use std::rc::Rc;
 
 // Submodules
+pub(crate) mod format;
 pub mod natives;
+pub mod plugins;
 pub mod primitives;
 
 /// Represents the body of a Hinton native function object.
 pub type NativeFn = fn(&mut VM, Vec<Object>) -> RuntimeResult;
 
+/// Represents the body of a native function registered by a host Rust program.
+pub type HostFn = Rc<dyn Fn(&[Object]) -> Result<Object, ObjectOprErrType>>;
+
+/// The body of a Hinton native function object, which is either built into
+/// Hinton, or registered by a host Rust program that embeds the VM.
+#[derive(Clone)]
+pub enum NativeFnBody {
+   BuiltIn(NativeFn),
+   Host(HostFn),
+}
+
 /// Represents the body of a Hinton primitive-bound method object.
 pub type NativeBoundMethod = fn(&mut VM, Object, Vec<Object>) -> RuntimeResult;
 
@@ -18,6 +33,8 @@ pub type NativeBoundMethod = fn(&mut VM, Object, Vec<Object>) -> RuntimeResult;
 pub struct BuiltIn {
    pub natives: Natives,
    pub primitives: Primitives,
+   /// Whether programs can access the host system (e.g., read files, or import modules).
+   pub system_access: bool,
 }
 
 /// The default implementation for the `BuiltIn` struct.
@@ -26,11 +43,25 @@ impl Default for BuiltIn {
       Self {
          natives: Natives::default(),
          primitives: Primitives::default(),
+         system_access: true,
       }
    }
 }
 
 impl BuiltIn {
+   /// Removes the native functions and namespaces that give programs access to the host system
+   /// (e.g., the `File` namespace), and forbids imports, so that untrusted programs can be run.
+   /// Programs that use them fail to compile.
+   pub fn remove_system_access(&mut self) {
+      self.natives.0.remove("load_plugin");
+
+      for name in ["File", "Http", "Net", "Process"] {
+         self.primitives.0.remove(name);
+      }
+
+      self.system_access = false;
+   }
+
    /// Gets a property attached to a Hinton primitive class.
    ///
    /// # Arguments
@@ -73,9 +104,9 @@ impl BuiltIn {
    /// ```
    /// let fn_print = NativeFuncObj {
    ///    name: "print".to_string(),
-   ///    min_arity: 1,
-   ///    max_arity: 1,
-   ///    body: native_print as NativeFn,
+   ///    min_arity: 0,
+   ///    max_arity: 255,
+   ///    body: NativeFnBody::BuiltIn(native_print as NativeFn),
    /// };
    ///
    /// BuiltIn::call_native_fn(&mut vm, fn_print, vec![Object::Bool(true)])
@@ -97,7 +128,13 @@ impl BuiltIn {
       vm.pop_stack();
 
       // Calls the native function, and returns its result.
-      (func.body)(vm, args)
+      match func.body {
+         NativeFnBody::BuiltIn(body) => body(vm, args),
+         NativeFnBody::Host(body) => match body(&args) {
+            Ok(result) => vm.push_stack(result),
+            Err(e) => e.to_runtime_error(),
+         },
+      }
    }
 
    /// Executes the body of a method bound to primitive class.
@@ -0,0 +1,389 @@
+use crate::built_in::primitives::HTPrimitive;
+use crate::built_in::NativeBoundMethod;
+use crate::errors::RuntimeErrorType;
+use crate::objects::bytes_obj;
+use crate::objects::class_obj::{ClassField, ClassObject};
+use crate::objects::Object;
+use crate::virtual_machine::{RuntimeResult, VM};
+use hashbrown::HashMap;
+
+/// Represents the Hinton `Crypto` namespace, whose static members are the native functions
+/// that compute digests (e.g., checksums) and HMACs of strings and bytes.
+pub struct CryptoClass(ClassObject);
+
+/// Implements the `HTPrimitive` trait for the `Crypto` namespace.
+impl HTPrimitive for CryptoClass {
+   /// Gets the name class name of this Hinton primitive.
+   fn name(&self) -> String {
+      self.0.name.clone()
+   }
+
+   /// Gets the non-static members of this Hinton primitive.
+   fn members(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.members
+   }
+
+   /// Gets the static members of this Hinton primitive.
+   fn statics(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.statics
+   }
+
+   /// Gets the default class object stored in this Hinton primitive.
+   fn default() -> ClassObject {
+      let mut _self = CryptoClass(ClassObject::new("Crypto"));
+
+      // >>>>>>> Static fields for the "Crypto" namespace to be added after this line
+      _self.bind_static_method("hmac", (3, 3), hmac as NativeBoundMethod);
+      _self.bind_static_method("md5", (1, 1), md5 as NativeBoundMethod);
+      _self.bind_static_method("sha1", (1, 1), sha1 as NativeBoundMethod);
+      _self.bind_static_method("sha256", (1, 1), sha256 as NativeBoundMethod);
+      // <<<<<<< Static fields for the "Crypto" namespace to be added before this line
+
+      _self.0
+   }
+}
+
+/// The size, in bytes, of the blocks processed by the MD5, SHA-1, and SHA-256 algorithms.
+const BLOCK_SIZE: usize = 64;
+
+/// The signature of the functions that compute the digest of a message.
+type DigestFn = fn(&[u8]) -> Vec<u8>;
+
+/// Pads a message to a multiple of the block size, with a `1` bit, zeros, and the length of
+/// the message in bits.
+///
+/// # Arguments
+/// * `data`: The message.
+/// * `big_endian`: Whether the length is stored in big-endian (SHA) or little-endian (MD5) order.
+///
+/// # Returns:
+/// Vec<u8>
+fn pad_message(data: &[u8], big_endian: bool) -> Vec<u8> {
+   let bit_len = (data.len() as u64).wrapping_mul(8);
+
+   let mut message = data.to_vec();
+   message.push(0x80);
+   while message.len() % BLOCK_SIZE != BLOCK_SIZE - 8 {
+      message.push(0);
+   }
+
+   if big_endian {
+      message.extend_from_slice(&bit_len.to_be_bytes());
+   } else {
+      message.extend_from_slice(&bit_len.to_le_bytes());
+   }
+
+   message
+}
+
+/// The constants added in each round of the MD5 algorithm.
+const MD5_K: [u32; 64] = [
+   0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee, 0xf57c0faf, 0x4787c62a, 0xa8304613, 0xfd469501,
+   0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be, 0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821,
+   0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa, 0xd62f105d, 0x02441453, 0xd8a1e681, 0xe7d3fbc8,
+   0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed, 0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a,
+   0xfffa3942, 0x8771f681, 0x6d9d6122, 0xfde5380c, 0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70,
+   0x289b7ec6, 0xeaa127fa, 0xd4ef3085, 0x04881d05, 0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665,
+   0xf4292244, 0x432aff97, 0xab9423a7, 0xfc93a039, 0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
+   0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1, 0xf7537e82, 0xbd3af235, 0x2ad7d2bb, 0xeb86d391,
+];
+
+/// The number of bits each word is rotated by in the rounds of the MD5 algorithm.
+const MD5_SHIFTS: [u32; 16] = [7, 12, 17, 22, 5, 9, 14, 20, 4, 11, 16, 23, 6, 10, 15, 21];
+
+/// Computes the MD5 digest of a message.
+fn md5_digest(data: &[u8]) -> Vec<u8> {
+   let mut state: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+
+   for block in pad_message(data, false).chunks(BLOCK_SIZE) {
+      let words: Vec<u32> = block
+         .chunks(4)
+         .map(|w| u32::from_le_bytes([w[0], w[1], w[2], w[3]]))
+         .collect();
+
+      let [mut a, mut b, mut c, mut d] = state;
+      for i in 0..64 {
+         let (f, g) = match i / 16 {
+            0 => ((b & c) | (!b & d), i),
+            1 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+            2 => (b ^ c ^ d, (3 * i + 5) % 16),
+            _ => (c ^ (b | !d), (7 * i) % 16),
+         };
+
+         let rotated = a
+            .wrapping_add(f)
+            .wrapping_add(MD5_K[i])
+            .wrapping_add(words[g])
+            .rotate_left(MD5_SHIFTS[i / 16 * 4 + i % 4]);
+
+         a = d;
+         d = c;
+         c = b;
+         b = b.wrapping_add(rotated);
+      }
+
+      for (s, v) in state.iter_mut().zip([a, b, c, d]) {
+         *s = s.wrapping_add(v);
+      }
+   }
+
+   state.iter().flat_map(|s| s.to_le_bytes()).collect()
+}
+
+/// Computes the SHA-1 digest of a message.
+fn sha1_digest(data: &[u8]) -> Vec<u8> {
+   let mut state: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+   for block in pad_message(data, true).chunks(BLOCK_SIZE) {
+      let mut words = [0u32; 80];
+      for (i, w) in block.chunks(4).enumerate() {
+         words[i] = u32::from_be_bytes([w[0], w[1], w[2], w[3]]);
+      }
+      for i in 16..80 {
+         words[i] = (words[i - 3] ^ words[i - 8] ^ words[i - 14] ^ words[i - 16]).rotate_left(1);
+      }
+
+      let [mut a, mut b, mut c, mut d, mut e] = state;
+      for (i, word) in words.iter().enumerate() {
+         let (f, k) = match i / 20 {
+            0 => ((b & c) | (!b & d), 0x5a827999),
+            1 => (b ^ c ^ d, 0x6ed9eba1),
+            2 => ((b & c) | (b & d) | (c & d), 0x8f1bbcdc),
+            _ => (b ^ c ^ d, 0xca62c1d6),
+         };
+
+         let temp = a
+            .rotate_left(5)
+            .wrapping_add(f)
+            .wrapping_add(e)
+            .wrapping_add(k)
+            .wrapping_add(*word);
+
+         e = d;
+         d = c;
+         c = b.rotate_left(30);
+         b = a;
+         a = temp;
+      }
+
+      for (s, v) in state.iter_mut().zip([a, b, c, d, e]) {
+         *s = s.wrapping_add(v);
+      }
+   }
+
+   state.iter().flat_map(|s| s.to_be_bytes()).collect()
+}
+
+/// The constants added in each round of the SHA-256 algorithm.
+const SHA256_K: [u32; 64] = [
+   0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+   0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+   0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+   0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+   0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+   0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+   0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+   0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// Computes the SHA-256 digest of a message.
+fn sha256_digest(data: &[u8]) -> Vec<u8> {
+   let mut state: [u32; 8] = [
+      0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+   ];
+
+   for block in pad_message(data, true).chunks(BLOCK_SIZE) {
+      let mut words = [0u32; 64];
+      for (i, w) in block.chunks(4).enumerate() {
+         words[i] = u32::from_be_bytes([w[0], w[1], w[2], w[3]]);
+      }
+      for i in 16..64 {
+         let s0 = words[i - 15].rotate_right(7) ^ words[i - 15].rotate_right(18) ^ (words[i - 15] >> 3);
+         let s1 = words[i - 2].rotate_right(17) ^ words[i - 2].rotate_right(19) ^ (words[i - 2] >> 10);
+         words[i] = words[i - 16]
+            .wrapping_add(s0)
+            .wrapping_add(words[i - 7])
+            .wrapping_add(s1);
+      }
+
+      let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+      for (k, word) in SHA256_K.iter().zip(words.iter()) {
+         let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+         let choice = (e & f) ^ (!e & g);
+         let temp1 = h
+            .wrapping_add(s1)
+            .wrapping_add(choice)
+            .wrapping_add(*k)
+            .wrapping_add(*word);
+         let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+         let majority = (a & b) ^ (a & c) ^ (b & c);
+         let temp2 = s0.wrapping_add(majority);
+
+         h = g;
+         g = f;
+         f = e;
+         e = d.wrapping_add(temp1);
+         d = c;
+         c = b;
+         b = a;
+         a = temp1.wrapping_add(temp2);
+      }
+
+      for (s, v) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+         *s = s.wrapping_add(v);
+      }
+   }
+
+   state.iter().flat_map(|s| s.to_be_bytes()).collect()
+}
+
+/// Gets the digest function of a hash algorithm by its name.
+fn digest_function(algorithm: &str) -> Option<DigestFn> {
+   match algorithm.to_lowercase().replace('-', "").as_str() {
+      "md5" => Some(md5_digest),
+      "sha1" => Some(sha1_digest),
+      "sha256" => Some(sha256_digest),
+      _ => None,
+   }
+}
+
+/// Computes the HMAC of a message (RFC 2104) with the given digest function.
+fn hmac_digest(digest: DigestFn, key: &[u8], message: &[u8]) -> Vec<u8> {
+   // Keys longer than a block are hashed, and shorter keys are padded with zeros.
+   let mut key = if key.len() > BLOCK_SIZE {
+      digest(key)
+   } else {
+      key.to_vec()
+   };
+   key.resize(BLOCK_SIZE, 0);
+
+   let mut inner: Vec<u8> = key.iter().map(|k| k ^ 0x36).collect();
+   inner.extend_from_slice(message);
+
+   let mut outer: Vec<u8> = key.iter().map(|k| k ^ 0x5c).collect();
+   outer.extend_from_slice(&digest(&inner));
+
+   digest(&outer)
+}
+
+/// Converts the argument of a `Crypto` function into binary data.
+///
+/// # Arguments
+/// * `arg`: The argument passed to the function.
+/// * `fn_name`: The name of the function.
+///
+/// # Returns:
+/// Result<Vec<u8>, RuntimeResult>
+fn to_bytes(arg: &Object, fn_name: &str) -> Result<Vec<u8>, RuntimeResult> {
+   bytes_obj::from_object(arg).ok_or_else(|| RuntimeResult::Error {
+      error: RuntimeErrorType::TypeError,
+      message: format!(
+         "Function 'Crypto.{}' expected a String or Bytes. Found '{}' instead.",
+         fn_name,
+         arg.type_name()
+      ),
+   })
+}
+
+/// Computes a digest of a String or Bytes object, and pushes it as a string of hexadecimal digits.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `args`: The arguments of the call.
+/// * `fn_name`: The name of the function.
+/// * `digest`: The digest function.
+///
+/// # Returns:
+/// RuntimeResult
+fn push_digest(vm: &mut VM, args: &[Object], fn_name: &str, digest: DigestFn) -> RuntimeResult {
+   match to_bytes(&args[0], fn_name) {
+      Ok(data) => vm.push_stack(Object::from(bytes_obj::to_hex(&digest(&data)))),
+      Err(e) => e,
+   }
+}
+
+/// Computes the MD5 digest of a String or Bytes object. MD5 is only suitable for checksums,
+/// because it is not collision resistant.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Crypto` namespace (unused).
+/// * `args`: The data to hash.
+///
+/// # Returns:
+/// RuntimeResult
+fn md5(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   push_digest(vm, &args, "md5", md5_digest)
+}
+
+/// Computes the SHA-1 digest of a String or Bytes object. SHA-1 is only suitable for
+/// checksums, because it is not collision resistant.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Crypto` namespace (unused).
+/// * `args`: The data to hash.
+///
+/// # Returns:
+/// RuntimeResult
+fn sha1(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   push_digest(vm, &args, "sha1", sha1_digest)
+}
+
+/// Computes the SHA-256 digest of a String or Bytes object.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Crypto` namespace (unused).
+/// * `args`: The data to hash.
+///
+/// # Returns:
+/// RuntimeResult
+fn sha256(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   push_digest(vm, &args, "sha256", sha256_digest)
+}
+
+/// Computes the HMAC of a message with a secret key, using one of the digest algorithms
+/// of the `Crypto` namespace (`md5`, `sha1`, or `sha256`).
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Crypto` namespace (unused).
+/// * `args`: The name of the algorithm, the key, and the message.
+///
+/// # Returns:
+/// RuntimeResult
+fn hmac(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   let digest = match &args[0] {
+      Object::String(name) => match digest_function(name) {
+         Some(d) => d,
+         None => {
+            return RuntimeResult::Error {
+               error: RuntimeErrorType::ArgumentError,
+               message: format!(
+                  "Unknown hash algorithm '{}'. Expected one of: md5, sha1, sha256.",
+                  name
+               ),
+            }
+         }
+      },
+      obj => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::TypeError,
+            message: format!(
+               "Function 'Crypto.hmac' expected the name of an algorithm. Found '{}' instead.",
+               obj.type_name()
+            ),
+         }
+      }
+   };
+
+   let (key, message) = match (to_bytes(&args[1], "hmac"), to_bytes(&args[2], "hmac")) {
+      (Ok(k), Ok(m)) => (k, m),
+      (Err(e), _) | (_, Err(e)) => return e,
+   };
+
+   vm.push_stack(Object::from(bytes_obj::to_hex(&hmac_digest(
+      digest, &key, &message,
+   ))))
+}
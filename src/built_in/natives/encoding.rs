@@ -0,0 +1,154 @@
+use crate::built_in::primitives::HTPrimitive;
+use crate::built_in::NativeBoundMethod;
+use crate::errors::RuntimeErrorType;
+use crate::objects::bytes_obj;
+use crate::objects::class_obj::{ClassField, ClassObject};
+use crate::objects::Object;
+use crate::virtual_machine::{RuntimeResult, VM};
+use hashbrown::HashMap;
+
+/// Represents the Hinton `Encoding` namespace, whose static members are the native functions
+/// that convert binary data to and from text encodings like Base64 and hexadecimal.
+pub struct EncodingClass(ClassObject);
+
+/// Implements the `HTPrimitive` trait for the `Encoding` namespace.
+impl HTPrimitive for EncodingClass {
+   /// Gets the name class name of this Hinton primitive.
+   fn name(&self) -> String {
+      self.0.name.clone()
+   }
+
+   /// Gets the non-static members of this Hinton primitive.
+   fn members(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.members
+   }
+
+   /// Gets the static members of this Hinton primitive.
+   fn statics(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.statics
+   }
+
+   /// Gets the default class object stored in this Hinton primitive.
+   fn default() -> ClassObject {
+      let mut _self = EncodingClass(ClassObject::new("Encoding"));
+
+      // >>>>>>> Static fields for the "Encoding" namespace to be added after this line
+      _self.bind_static_method("base64_decode", (1, 1), base64_decode as NativeBoundMethod);
+      _self.bind_static_method("base64_encode", (1, 1), base64_encode as NativeBoundMethod);
+      _self.bind_static_method("hex_decode", (1, 1), hex_decode as NativeBoundMethod);
+      _self.bind_static_method("hex_encode", (1, 1), hex_encode as NativeBoundMethod);
+      // <<<<<<< Static fields for the "Encoding" namespace to be added before this line
+
+      _self.0
+   }
+}
+
+/// Encodes a String (as UTF-8) or Bytes object into text, and pushes the text onto the stack.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `arg`: The data to encode.
+/// * `fn_name`: The name of the function.
+/// * `encode`: The function that encodes the binary data.
+///
+/// # Returns:
+/// RuntimeResult
+fn push_encoded(vm: &mut VM, arg: &Object, fn_name: &str, encode: fn(&[u8]) -> String) -> RuntimeResult {
+   match bytes_obj::from_object(arg) {
+      Some(data) => vm.push_stack(Object::from(encode(&data))),
+      None => RuntimeResult::Error {
+         error: RuntimeErrorType::TypeError,
+         message: format!(
+            "Function 'Encoding.{}' expected a String or Bytes. Found '{}' instead.",
+            fn_name,
+            arg.type_name()
+         ),
+      },
+   }
+}
+
+/// Decodes text into a Bytes object, and pushes the object onto the stack.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `arg`: The text to decode.
+/// * `fn_name`: The name of the function.
+/// * `encoding`: The name of the encoding of the text.
+///
+/// # Returns:
+/// RuntimeResult
+fn push_decoded(vm: &mut VM, arg: &Object, fn_name: &str, encoding: &str) -> RuntimeResult {
+   let text = match arg {
+      Object::String(s) => s,
+      _ => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::TypeError,
+            message: format!(
+               "Function 'Encoding.{}' expected a String. Found '{}' instead.",
+               fn_name,
+               arg.type_name()
+            ),
+         }
+      }
+   };
+
+   match bytes_obj::encode(text, encoding) {
+      Ok(bytes) => vm.push_stack(Object::from(bytes)),
+      Err(message) => RuntimeResult::Error {
+         error: RuntimeErrorType::ArgumentError,
+         message,
+      },
+   }
+}
+
+/// Encodes a String or Bytes object as padded standard Base64.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Encoding` namespace (unused).
+/// * `args`: The data to encode.
+///
+/// # Returns:
+/// RuntimeResult
+fn base64_encode(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   push_encoded(vm, &args[0], "base64_encode", bytes_obj::to_base64)
+}
+
+/// Decodes a Base64 string (with the standard or the URL-safe alphabet) into Bytes.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Encoding` namespace (unused).
+/// * `args`: The Base64 string.
+///
+/// # Returns:
+/// RuntimeResult
+fn base64_decode(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   push_decoded(vm, &args[0], "base64_decode", "base64")
+}
+
+/// Encodes a String or Bytes object as lowercase hexadecimal digits.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Encoding` namespace (unused).
+/// * `args`: The data to encode.
+///
+/// # Returns:
+/// RuntimeResult
+fn hex_encode(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   push_encoded(vm, &args[0], "hex_encode", bytes_obj::to_hex)
+}
+
+/// Decodes a string of hexadecimal digits into Bytes.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Encoding` namespace (unused).
+/// * `args`: The hexadecimal string.
+///
+/// # Returns:
+/// RuntimeResult
+fn hex_decode(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   push_decoded(vm, &args[0], "hex_decode", "hex")
+}
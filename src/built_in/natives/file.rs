@@ -0,0 +1,260 @@
+use crate::built_in::primitives::HTPrimitive;
+use crate::built_in::NativeBoundMethod;
+use crate::errors::RuntimeErrorType;
+use crate::objects::bytes_obj;
+use crate::objects::class_obj::{ClassField, ClassObject};
+use crate::objects::Object;
+use crate::virtual_machine::{RuntimeResult, VM};
+use hashbrown::HashMap;
+use std::fs;
+use std::io;
+use std::io::Write;
+
+/// Represents the Hinton `File` namespace, whose static members are
+/// the native functions used to access the filesystem.
+pub struct FileClass(ClassObject);
+
+/// Implements the `HTPrimitive` trait for the `File` namespace.
+impl HTPrimitive for FileClass {
+   /// Gets the name class name of this Hinton primitive.
+   fn name(&self) -> String {
+      self.0.name.clone()
+   }
+
+   /// Gets the non-static members of this Hinton primitive.
+   fn members(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.members
+   }
+
+   /// Gets the static members of this Hinton primitive.
+   fn statics(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.statics
+   }
+
+   /// Gets the default class object stored in this Hinton primitive.
+   fn default() -> ClassObject {
+      let mut _self = FileClass(ClassObject::new("File"));
+
+      // >>>>>>> Static fields for the "File" namespace to be added after this line
+      _self.bind_static_method("append", (2, 2), append as NativeBoundMethod);
+      _self.bind_static_method("exists", (1, 1), exists as NativeBoundMethod);
+      _self.bind_static_method("lines", (1, 1), lines as NativeBoundMethod);
+      _self.bind_static_method("list_dir", (1, 1), list_dir as NativeBoundMethod);
+      _self.bind_static_method("read", (1, 1), read as NativeBoundMethod);
+      _self.bind_static_method("read_bytes", (1, 1), read_bytes as NativeBoundMethod);
+      _self.bind_static_method("write", (2, 2), write as NativeBoundMethod);
+      _self.bind_static_method("write_bytes", (2, 2), write_bytes as NativeBoundMethod);
+      // <<<<<<< Static fields for the "File" namespace to be added before this line
+
+      _self.0
+   }
+}
+
+macro_rules! string_arg {
+   ($arg: expr, $fn_name: expr) => {
+      match &$arg {
+         Object::String(s) => &**s,
+         _ => {
+            return RuntimeResult::Error {
+               error: RuntimeErrorType::TypeError,
+               message: format!(
+                  "Function 'File.{}' expected a String argument. Found '{}' instead.",
+                  $fn_name,
+                  $arg.type_name()
+               ),
+            }
+         }
+      }
+   };
+}
+
+/// Converts an OS error into a Hinton `IOError`.
+///
+/// # Arguments
+/// * `err`: The OS error.
+/// * `path`: The path of the file that caused the error.
+///
+/// # Returns:
+/// RuntimeResult
+fn io_error(err: io::Error, path: &str) -> RuntimeResult {
+   RuntimeResult::Error {
+      error: RuntimeErrorType::IOError,
+      message: format!("{} (path '{}').", err, path),
+   }
+}
+
+/// Reads the contents of a text file into a string.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `File` namespace (unused).
+/// * `args`: The path of the file.
+///
+/// # Returns:
+/// RuntimeResult
+fn read(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   let path = string_arg!(args[0], "read");
+
+   match fs::read_to_string(path) {
+      Ok(s) => vm.push_stack(Object::from(s)),
+      Err(e) => io_error(e, path),
+   }
+}
+
+/// Reads the lines of a text file into an array of strings, without their line terminators.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `File` namespace (unused).
+/// * `args`: The path of the file.
+///
+/// # Returns:
+/// RuntimeResult
+fn lines(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   let path = string_arg!(args[0], "lines");
+
+   match fs::read_to_string(path) {
+      Ok(s) => {
+         let lines = s.lines().map(Object::from).collect();
+         let arr = vm.alloc_array(lines);
+         vm.push_stack(arr)
+      }
+      Err(e) => io_error(e, path),
+   }
+}
+
+/// Reads the contents of a binary file into a Bytes object.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `File` namespace (unused).
+/// * `args`: The path of the file.
+///
+/// # Returns:
+/// RuntimeResult
+fn read_bytes(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   let path = string_arg!(args[0], "read_bytes");
+
+   match fs::read(path) {
+      Ok(bytes) => vm.push_stack(Object::from(bytes)),
+      Err(e) => io_error(e, path),
+   }
+}
+
+/// Writes a value to a text file, replacing the file's contents if it already exists.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `File` namespace (unused).
+/// * `args`: The path of the file, and the value to write.
+///
+/// # Returns:
+/// RuntimeResult
+fn write(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   let path = string_arg!(args[0], "write");
+
+   match fs::write(path, args[1].as_plain_string()) {
+      Ok(_) => vm.push_stack(Object::Null),
+      Err(e) => io_error(e, path),
+   }
+}
+
+/// Appends a value to the end of a text file, creating the file if it does not exist.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `File` namespace (unused).
+/// * `args`: The path of the file, and the value to append.
+///
+/// # Returns:
+/// RuntimeResult
+fn append(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   let path = string_arg!(args[0], "append");
+
+   let result = fs::OpenOptions::new()
+      .append(true)
+      .create(true)
+      .open(path)
+      .and_then(|mut f| f.write_all(args[1].as_plain_string().as_bytes()));
+
+   match result {
+      Ok(_) => vm.push_stack(Object::Null),
+      Err(e) => io_error(e, path),
+   }
+}
+
+/// Writes a Bytes object (or an array of integer bytes) to a binary file, replacing the
+/// file's contents if it already exists.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `File` namespace (unused).
+/// * `args`: The path of the file, and the bytes to write.
+///
+/// # Returns:
+/// RuntimeResult
+fn write_bytes(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   let path = string_arg!(args[0], "write_bytes");
+
+   let bytes = match &args[1] {
+      Object::Bytes(bytes) => Some(bytes.borrow().clone()),
+      Object::Array(arr) => bytes_obj::from_int_array(&arr.borrow()),
+      _ => None,
+   };
+
+   match bytes {
+      Some(bytes) => match fs::write(path, bytes) {
+         Ok(_) => vm.push_stack(Object::Null),
+         Err(e) => io_error(e, path),
+      },
+      None => RuntimeResult::Error {
+         error: RuntimeErrorType::TypeError,
+         message: String::from(
+            "Function 'File.write_bytes' expected a Bytes object or an Array of integers from 0 to 255.",
+         ),
+      },
+   }
+}
+
+/// Checks whether a file or directory exists at the given path.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `File` namespace (unused).
+/// * `args`: The path.
+///
+/// # Returns:
+/// RuntimeResult
+fn exists(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   let path = string_arg!(args[0], "exists");
+   vm.push_stack(Object::Bool(std::path::Path::new(path).exists()))
+}
+
+/// Lists the names of the entries in a directory, in alphabetical order.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `File` namespace (unused).
+/// * `args`: The path of the directory.
+///
+/// # Returns:
+/// RuntimeResult
+fn list_dir(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   let path = string_arg!(args[0], "list_dir");
+
+   let names = fs::read_dir(path).and_then(|entries| {
+      entries
+         .map(|e| e.map(|e| e.file_name().to_string_lossy().into_owned()))
+         .collect::<io::Result<Vec<String>>>()
+   });
+
+   match names {
+      Ok(mut names) => {
+         names.sort();
+         let names = names.into_iter().map(Object::from).collect();
+         let arr = vm.alloc_array(names);
+         vm.push_stack(arr)
+      }
+      Err(e) => io_error(e, path),
+   }
+}
@@ -0,0 +1,722 @@
+use crate::built_in::primitives::HTPrimitive;
+use crate::built_in::NativeBoundMethod;
+use crate::errors::RuntimeErrorType;
+use crate::objects::class_obj::{ClassField, ClassObject};
+use crate::objects::dict_obj::DictObject;
+use crate::objects::Object;
+use crate::virtual_machine::{RuntimeResult, VM};
+use hashbrown::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::Duration;
+
+/// How long the client waits for the server to accept a request or send a response, and how
+/// long the server waits for a client to send a request.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Represents the Hinton `Http` namespace, whose static members are the native functions
+/// used to make HTTP requests and to serve them. Only plain `http://` URLs are supported,
+/// since the interpreter does not implement TLS.
+pub struct HttpClass(ClassObject);
+
+/// Implements the `HTPrimitive` trait for the `Http` namespace.
+impl HTPrimitive for HttpClass {
+   /// Gets the name class name of this Hinton primitive.
+   fn name(&self) -> String {
+      self.0.name.clone()
+   }
+
+   /// Gets the non-static members of this Hinton primitive.
+   fn members(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.members
+   }
+
+   /// Gets the static members of this Hinton primitive.
+   fn statics(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.statics
+   }
+
+   /// Gets the default class object stored in this Hinton primitive.
+   fn default() -> ClassObject {
+      let mut _self = HttpClass(ClassObject::new("Http"));
+
+      // >>>>>>> Static fields for the "Http" namespace to be added after this line
+      _self.bind_static_method("get", (1, 2), get as NativeBoundMethod);
+      _self.bind_static_method("post", (2, 3), post as NativeBoundMethod);
+      _self.bind_static_method("request", (2, 4), request as NativeBoundMethod);
+      _self.bind_static_method("serve", (2, 3), serve as NativeBoundMethod);
+      // <<<<<<< Static fields for the "Http" namespace to be added before this line
+
+      _self.0
+   }
+}
+
+/// An HTTP request or response, as read from a connection.
+pub(crate) struct HttpMessage {
+   /// The first line of the message (e.g., `GET / HTTP/1.1`, or `HTTP/1.1 200 OK`).
+   pub(crate) start_line: String,
+   /// The header fields of the message, with lowercase names, in the order they were read.
+   pub(crate) headers: Vec<(String, String)>,
+   /// The body of the message.
+   pub(crate) body: Vec<u8>,
+}
+
+impl HttpMessage {
+   /// Gets the value of the first header field with the given (lowercase) name.
+   pub(crate) fn header(&self, name: &str) -> Option<&str> {
+      self
+         .headers
+         .iter()
+         .find(|(n, _)| n == name)
+         .map(|(_, v)| v.as_str())
+   }
+}
+
+/// Creates the error for a malformed HTTP message.
+fn invalid_data(message: &str) -> io::Error {
+   io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Reads a line of an HTTP message's head, without its line terminator.
+fn read_head_line(reader: &mut impl BufRead) -> io::Result<String> {
+   let mut line = Vec::new();
+
+   if reader.read_until(b'\n', &mut line)? == 0 {
+      return Err(invalid_data(
+         "The connection was closed in the middle of an HTTP message",
+      ));
+   }
+
+   while line.ends_with(b"\n") || line.ends_with(b"\r") {
+      line.pop();
+   }
+
+   Ok(String::from_utf8_lossy(&line).into_owned())
+}
+
+/// Reads an HTTP message from a connection. The length of the body is given by the message's
+/// `Content-Length` header, or by its chunks if it uses the `chunked` transfer encoding.
+///
+/// # Parameters
+/// - `reader`: The connection.
+/// - `until_eof`: Whether the body of a message without a known length extends until the
+///   connection is closed (as in responses), rather than being empty (as in requests).
+pub(crate) fn read_message(reader: &mut impl BufRead, until_eof: bool) -> io::Result<HttpMessage> {
+   let start_line = read_head_line(reader)?;
+   let mut headers = vec![];
+
+   loop {
+      let line = read_head_line(reader)?;
+
+      if line.is_empty() {
+         break;
+      }
+
+      match line.split_once(':') {
+         Some((name, value)) => headers.push((name.trim().to_lowercase(), value.trim().to_string())),
+         None => return Err(invalid_data("Malformed HTTP header")),
+      }
+   }
+
+   let mut message = HttpMessage {
+      start_line,
+      headers,
+      body: vec![],
+   };
+
+   let is_chunked = message
+      .header("transfer-encoding")
+      .is_some_and(|e| e.to_lowercase().contains("chunked"));
+
+   if is_chunked {
+      message.body = read_chunked_body(reader)?;
+   } else if let Some(length) = message.header("content-length") {
+      let length: usize = length
+         .parse()
+         .map_err(|_| invalid_data("Malformed 'Content-Length' header"))?;
+
+      message.body = vec![0; length];
+      reader.read_exact(&mut message.body)?;
+   } else if until_eof {
+      reader.read_to_end(&mut message.body)?;
+   }
+
+   Ok(message)
+}
+
+/// Reads the body of an HTTP message sent with the `chunked` transfer encoding.
+fn read_chunked_body(reader: &mut impl BufRead) -> io::Result<Vec<u8>> {
+   let mut body = vec![];
+
+   loop {
+      let line = read_head_line(reader)?;
+      let size = line.split(';').next().unwrap_or("").trim();
+      let size = usize::from_str_radix(size, 16).map_err(|_| invalid_data("Malformed chunk size"))?;
+
+      if size == 0 {
+         // Skips the trailer fields, up to the empty line that ends the message.
+         while !read_head_line(reader)?.is_empty() {}
+         return Ok(body);
+      }
+
+      let start = body.len();
+      body.resize(start + size, 0);
+      reader.read_exact(&mut body[start..])?;
+      read_head_line(reader)?;
+   }
+}
+
+/// The parts of an `http://` URL needed to send a request.
+struct Url {
+   host: String,
+   port: u16,
+   /// The path and query of the URL (e.g., `/search?q=hinton`).
+   target: String,
+}
+
+/// Splits an `http://` URL into its host, port, and target.
+fn parse_url(url: &str) -> Result<Url, String> {
+   let rest = match url.split_once("://") {
+      Some((scheme, rest)) if scheme.eq_ignore_ascii_case("http") => rest,
+      Some((scheme, _)) => {
+         return Err(format!(
+            "Unsupported URL scheme '{}'. Only 'http://' URLs can be requested.",
+            scheme
+         ))
+      }
+      None => {
+         return Err(format!(
+            "Invalid URL '{}'. Expected a URL like 'http://host/path'.",
+            url
+         ))
+      }
+   };
+
+   let (authority, target) = match rest.find(['/', '?']) {
+      Some(idx) if rest[idx..].starts_with('?') => (&rest[..idx], format!("/{}", &rest[idx..])),
+      Some(idx) => (&rest[..idx], rest[idx..].to_string()),
+      None => (rest, String::from("/")),
+   };
+
+   let (host, port) = match authority.rsplit_once(':') {
+      Some((host, port)) => match port.parse() {
+         Ok(port) => (host, port),
+         Err(_) => return Err(format!("Invalid port '{}' in URL '{}'.", port, url)),
+      },
+      None => (authority, 80),
+   };
+
+   if host.is_empty() {
+      return Err(format!("Invalid URL '{}'. The URL has no host.", url));
+   }
+
+   Ok(Url {
+      host: host.to_string(),
+      port,
+      target,
+   })
+}
+
+/// Sends an HTTP request, and reads the response. The connection is closed after the response.
+fn send_request(
+   method: &str,
+   url: &Url,
+   headers: &[(String, String)],
+   body: &[u8],
+) -> io::Result<HttpMessage> {
+   let stream = TcpStream::connect((&*url.host, url.port))?;
+   stream.set_read_timeout(Some(TIMEOUT))?;
+   stream.set_write_timeout(Some(TIMEOUT))?;
+
+   let mut head = format!("{} {} HTTP/1.1\r\n", method, url.target);
+
+   let has_header = |name: &str| headers.iter().any(|(n, _)| n.eq_ignore_ascii_case(name));
+   if !has_header("host") {
+      match url.port {
+         80 => head += &format!("Host: {}\r\n", url.host),
+         port => head += &format!("Host: {}:{}\r\n", url.host, port),
+      }
+   }
+   if !has_header("user-agent") {
+      head += "User-Agent: Hinton\r\n";
+   }
+   if !body.is_empty() && !has_header("content-length") {
+      head += &format!("Content-Length: {}\r\n", body.len());
+   }
+
+   for (name, value) in headers {
+      head += &format!("{}: {}\r\n", name, value);
+   }
+   head += "Connection: close\r\n\r\n";
+
+   let mut writer = &stream;
+   writer.write_all(head.as_bytes())?;
+   writer.write_all(body)?;
+   writer.flush()?;
+
+   read_message(&mut BufReader::new(&stream), true)
+}
+
+/// Converts the header fields of a message into a Hinton dictionary.
+fn headers_dict(headers: Vec<(String, String)>) -> DictObject {
+   headers
+      .into_iter()
+      .map(|(name, value)| (name, Object::from(value)))
+      .collect()
+}
+
+/// Converts the response to a request into a Hinton dictionary, with the `status` code and
+/// `reason` phrase of the response, its `headers` (a dictionary with lowercase names), and
+/// its `body`, both as a String and as a Bytes object (`bytes`).
+fn response_dict(vm: &mut VM, response: HttpMessage) -> io::Result<Object> {
+   let mut status_line = response.start_line.splitn(3, ' ');
+
+   let status = match (status_line.next(), status_line.next()) {
+      (Some(version), Some(code)) if version.starts_with("HTTP/") => code.parse::<i64>().ok(),
+      _ => None,
+   };
+   let status = status.ok_or_else(|| invalid_data("Malformed HTTP status line"))?;
+
+   let headers = headers_dict(response.headers);
+
+   let mut dict = DictObject::default();
+   dict.insert(String::from("status"), Object::Int(status));
+   dict.insert(
+      String::from("reason"),
+      Object::from(status_line.next().unwrap_or("").to_string()),
+   );
+   dict.insert(String::from("headers"), vm.alloc_dict(headers));
+   dict.insert(
+      String::from("body"),
+      Object::from(String::from_utf8_lossy(&response.body).into_owned()),
+   );
+   dict.insert(String::from("bytes"), Object::from(response.body));
+
+   Ok(vm.alloc_dict(dict))
+}
+
+/// Performs an HTTP request, and pushes the response onto the stack.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `fn_name`: The name of the native function that performs the request.
+/// * `method`: The request method (e.g., `GET`).
+/// * `url`: The URL of the request.
+/// * `body`: The body of the request (a String, a Bytes object, or `null`), if any.
+/// * `headers`: A dictionary with the header fields of the request, if any.
+///
+/// # Returns:
+/// RuntimeResult
+fn perform(
+   vm: &mut VM,
+   fn_name: &str,
+   method: &str,
+   url: &Object,
+   body: Option<&Object>,
+   headers: Option<&Object>,
+) -> RuntimeResult {
+   let url = match url {
+      Object::String(s) => s.clone(),
+      _ => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::TypeError,
+            message: format!(
+               "Function 'Http.{}' expected a String as the URL. Found '{}' instead.",
+               fn_name,
+               url.type_name()
+            ),
+         }
+      }
+   };
+
+   let body = match body {
+      None | Some(Object::Null) => vec![],
+      Some(Object::String(s)) => s.as_bytes().to_vec(),
+      Some(Object::Bytes(b)) => b.borrow().clone(),
+      Some(obj) => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::TypeError,
+            message: format!(
+               "Function 'Http.{}' expected a String or a Bytes object as the body. Found '{}' instead.",
+               fn_name,
+               obj.type_name()
+            ),
+         }
+      }
+   };
+
+   let headers: Vec<(String, String)> = match headers {
+      None | Some(Object::Null) => vec![],
+      Some(Object::Dict(d)) => d
+         .borrow()
+         .iter()
+         .map(|(k, v)| (k.as_plain_string(), v.as_plain_string()))
+         .collect(),
+      Some(obj) => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::TypeError,
+            message: format!(
+               "Function 'Http.{}' expected a Dict as the headers. Found '{}' instead.",
+               fn_name,
+               obj.type_name()
+            ),
+         }
+      }
+   };
+
+   let parsed = match parse_url(&url) {
+      Ok(u) => u,
+      Err(message) => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::IOError,
+            message,
+         }
+      }
+   };
+
+   let response = send_request(method, &parsed, &headers, &body).and_then(|r| response_dict(vm, r));
+
+   match response {
+      Ok(response) => vm.push_stack(response),
+      Err(e) => RuntimeResult::Error {
+         error: RuntimeErrorType::IOError,
+         message: format!("Request to '{}' failed. {}.", url, e),
+      },
+   }
+}
+
+/// Sends a `GET` request to a URL, with the given headers, and returns the response.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Http` namespace (unused).
+/// * `args`: The URL, and an optional dictionary of headers.
+///
+/// # Returns:
+/// RuntimeResult
+fn get(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   perform(vm, "get", "GET", &args[0], None, args.get(1))
+}
+
+/// Sends a `POST` request with a body to a URL, with the given headers, and returns the response.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Http` namespace (unused).
+/// * `args`: The URL, the body, and an optional dictionary of headers.
+///
+/// # Returns:
+/// RuntimeResult
+fn post(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   perform(vm, "post", "POST", &args[0], args.get(1), args.get(2))
+}
+
+/// Sends a request with any method (e.g., `PUT` or `DELETE`) to a URL, with an optional body
+/// and headers, and returns the response.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Http` namespace (unused).
+/// * `args`: The method, the URL, and the optional body and dictionary of headers.
+///
+/// # Returns:
+/// RuntimeResult
+fn request(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   let method = match &args[0] {
+      Object::String(s) if !s.is_empty() && s.chars().all(|c| c.is_ascii_alphabetic()) => s.to_uppercase(),
+      arg => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::ArgumentError,
+            message: format!("Invalid HTTP method '{}'.", arg),
+         }
+      }
+   };
+
+   perform(vm, "request", &method, &args[1], args.get(2), args.get(3))
+}
+
+/// Decodes a component of a URL's query, where `+` stands for a space, and `%XX` for the byte
+/// with the hexadecimal value `XX`.
+fn decode_query_component(component: &str) -> String {
+   let bytes = component.as_bytes();
+   let mut decoded = Vec::with_capacity(bytes.len());
+   let mut i = 0;
+
+   while i < bytes.len() {
+      let escaped = bytes
+         .get(i + 1..i + 3)
+         .and_then(|hex| std::str::from_utf8(hex).ok())
+         .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+      match (bytes[i], escaped) {
+         (b'+', _) => decoded.push(b' '),
+         (b'%', Some(byte)) => {
+            decoded.push(byte);
+            i += 2;
+         }
+         (byte, _) => decoded.push(byte),
+      }
+
+      i += 1;
+   }
+
+   String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Converts a request received by the server into the Hinton dictionary passed to the handler,
+/// with the request's `method`, its `path` and `query` (a dictionary with the decoded
+/// parameters of the query), its `headers` (a dictionary with lowercase names), and its `body`,
+/// both as a String and as a Bytes object (`bytes`).
+fn request_dict(vm: &mut VM, request: HttpMessage) -> io::Result<Object> {
+   let mut request_line = request.start_line.split(' ');
+
+   let (method, target) = match (request_line.next(), request_line.next(), request_line.next()) {
+      (Some(method), Some(target), Some(version)) if version.starts_with("HTTP/") => (method, target),
+      _ => return Err(invalid_data("Malformed HTTP request line")),
+   };
+
+   let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+   let query: DictObject = query
+      .split('&')
+      .filter(|param| !param.is_empty())
+      .map(|param| {
+         let (name, value) = param.split_once('=').unwrap_or((param, ""));
+         (
+            decode_query_component(name),
+            Object::from(decode_query_component(value)),
+         )
+      })
+      .collect();
+
+   let mut dict = DictObject::default();
+   dict.insert(String::from("method"), Object::from(method.to_string()));
+   dict.insert(String::from("path"), Object::from(decode_query_component(path)));
+   dict.insert(String::from("query"), vm.alloc_dict(query));
+   dict.insert(
+      String::from("headers"),
+      vm.alloc_dict(headers_dict(request.headers)),
+   );
+   dict.insert(
+      String::from("body"),
+      Object::from(String::from_utf8_lossy(&request.body).into_owned()),
+   );
+   dict.insert(String::from("bytes"), Object::from(request.body));
+
+   Ok(vm.alloc_dict(dict))
+}
+
+/// Gets the reason phrase of common HTTP status codes.
+fn reason_phrase(status: i64) -> &'static str {
+   match status {
+      200 => "OK",
+      201 => "Created",
+      204 => "No Content",
+      301 => "Moved Permanently",
+      302 => "Found",
+      304 => "Not Modified",
+      400 => "Bad Request",
+      401 => "Unauthorized",
+      403 => "Forbidden",
+      404 => "Not Found",
+      405 => "Method Not Allowed",
+      500 => "Internal Server Error",
+      503 => "Service Unavailable",
+      _ => "",
+   }
+}
+
+/// Converts the value returned by a request handler into an HTTP response. The handler can
+/// return a String, which is sent as a plain text body, or a dictionary with the `status` of
+/// the response (200 by default), its `headers` (a dictionary), and its `body` (a String, a
+/// Bytes object, or `null`).
+fn to_response(value: &Object) -> Result<Vec<u8>, String> {
+   let (status, mut headers, body) = match value {
+      Object::String(body) => (
+         200,
+         vec![(
+            String::from("Content-Type"),
+            String::from("text/plain; charset=utf-8"),
+         )],
+         body.as_bytes().to_vec(),
+      ),
+      Object::Dict(dict) => {
+         let dict = dict.borrow();
+
+         let status = match dict.get("status") {
+            None => 200,
+            Some(Object::Int(status)) if (100..1000).contains(status) => *status,
+            Some(status) => return Err(format!("Invalid response status '{}'.", status)),
+         };
+
+         let headers = match dict.get("headers") {
+            None | Some(Object::Null) => vec![],
+            Some(Object::Dict(headers)) => headers
+               .borrow()
+               .iter()
+               .map(|(k, v)| (k.as_plain_string(), v.as_plain_string()))
+               .collect(),
+            Some(obj) => {
+               return Err(format!(
+                  "Expected a Dict as the response headers. Found '{}' instead.",
+                  obj.type_name()
+               ))
+            }
+         };
+
+         let body = match dict.get("body") {
+            None | Some(Object::Null) => vec![],
+            Some(Object::String(s)) => s.as_bytes().to_vec(),
+            Some(Object::Bytes(b)) => b.borrow().clone(),
+            Some(obj) => {
+               return Err(format!(
+                  "Expected a String or a Bytes object as the response body. Found '{}' instead.",
+                  obj.type_name()
+               ))
+            }
+         };
+
+         (status, headers, body)
+      }
+      obj => {
+         return Err(format!(
+            "Expected the request handler to return a String or a Dict. Found '{}' instead.",
+            obj.type_name()
+         ))
+      }
+   };
+
+   headers
+      .retain(|(n, _)| !n.eq_ignore_ascii_case("content-length") && !n.eq_ignore_ascii_case("connection"));
+   headers.push((String::from("Content-Length"), body.len().to_string()));
+   headers.push((String::from("Connection"), String::from("close")));
+
+   let mut response = format!("HTTP/1.1 {} {}\r\n", status, reason_phrase(status));
+   for (name, value) in headers {
+      response += &format!("{}: {}\r\n", name, value);
+   }
+   response += "\r\n";
+
+   let mut response = response.into_bytes();
+   response.extend(body);
+   Ok(response)
+}
+
+/// Sends a response to a client, ignoring the errors of clients that have already gone away.
+fn respond(mut stream: &TcpStream, response: &[u8]) {
+   let _ = stream.write_all(response).and_then(|_| stream.flush());
+}
+
+/// Serves HTTP requests on a port of the local machine, calling a Hinton function to handle
+/// each request. The server handles one request at a time, and closes the connection after
+/// each response. An error raised by the handler (or an invalid response) is answered with a
+/// `500 Internal Server Error` response, and stops the server by raising the error again.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Http` namespace (unused).
+/// * `args`: The port, the handler (which receives a request dictionary, and returns the
+///   response), and the number of requests to serve before returning (if omitted, or
+///   `null`, the server runs forever).
+///
+/// # Returns:
+/// RuntimeResult
+fn serve(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   let port = match &args[0] {
+      Object::Int(port) if (0..=65535).contains(port) => *port as u16,
+      Object::Int(port) => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::ArgumentError,
+            message: format!("Port {} is out of range. Ports go from 0 to 65535.", port),
+         }
+      }
+      obj => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::TypeError,
+            message: format!(
+               "Function 'Http.serve' expected an Int as the port. Found '{}' instead.",
+               obj.type_name()
+            ),
+         }
+      }
+   };
+
+   let handler = args[1].clone();
+
+   let limit = match args.get(2) {
+      None | Some(Object::Null) => None,
+      Some(Object::Int(limit)) if *limit >= 0 => Some(*limit as usize),
+      Some(obj) => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::ArgumentError,
+            message: format!(
+               "Function 'Http.serve' expected a non-negative Int as the number of requests. Found '{}' instead.",
+               obj
+            ),
+         }
+      }
+   };
+
+   let listener = match TcpListener::bind(("0.0.0.0", port)) {
+      Ok(listener) => listener,
+      Err(e) => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::IOError,
+            message: format!("Could not listen on port {}. {}.", port, e),
+         }
+      }
+   };
+
+   let mut served = 0;
+
+   while limit.is_none_or(|limit| served < limit) {
+      let stream = match listener.accept() {
+         Ok((stream, _)) => stream,
+         Err(_) => continue,
+      };
+      let _ = stream.set_read_timeout(Some(TIMEOUT));
+      let _ = stream.set_write_timeout(Some(TIMEOUT));
+
+      // Requests that cannot be read are answered without calling the handler.
+      let request = read_message(&mut BufReader::new(&stream), false).and_then(|r| request_dict(vm, r));
+      let request = match request {
+         Ok(request) => request,
+         Err(_) => {
+            respond(
+               &stream,
+               b"HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            );
+            continue;
+         }
+      };
+
+      served += 1;
+
+      let error = match vm.call_value(handler.clone(), vec![request]) {
+         Ok(value) => match to_response(&value) {
+            Ok(response) => {
+               respond(&stream, &response);
+               None
+            }
+            Err(message) => Some(RuntimeResult::Error {
+               error: RuntimeErrorType::TypeError,
+               message,
+            }),
+         },
+         Err(error) => Some(error),
+      };
+
+      if let Some(error) = error {
+         respond(
+            &stream,
+            b"HTTP/1.1 500 Internal Server Error\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+         );
+         return error;
+      }
+
+      // The server may run forever, so the objects created by the handler are collected here.
+      vm.collect_garbage_from_native(std::slice::from_ref(&handler));
+   }
+
+   vm.push_stack(Object::Null)
+}
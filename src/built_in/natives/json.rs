@@ -0,0 +1,471 @@
+use crate::built_in::primitives::HTPrimitive;
+use crate::built_in::NativeBoundMethod;
+use crate::errors::RuntimeErrorType;
+use crate::objects::bigint::BigInt;
+use crate::objects::class_obj::{ClassField, ClassObject};
+use crate::objects::dict_obj::DictObject;
+use crate::objects::Object;
+use crate::virtual_machine::{RuntimeResult, VM};
+use hashbrown::HashMap;
+use std::iter::Peekable;
+use std::rc::Rc;
+use std::str::CharIndices;
+
+/// Represents the Hinton `Json` namespace, whose static members are the native
+/// functions used to parse and serialize JSON documents.
+pub struct JsonClass(ClassObject);
+
+/// Implements the `HTPrimitive` trait for the `Json` namespace.
+impl HTPrimitive for JsonClass {
+   /// Gets the name class name of this Hinton primitive.
+   fn name(&self) -> String {
+      self.0.name.clone()
+   }
+
+   /// Gets the non-static members of this Hinton primitive.
+   fn members(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.members
+   }
+
+   /// Gets the static members of this Hinton primitive.
+   fn statics(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.statics
+   }
+
+   /// Gets the default class object stored in this Hinton primitive.
+   fn default() -> ClassObject {
+      let mut _self = JsonClass(ClassObject::new("Json"));
+
+      // >>>>>>> Static fields for the "Json" namespace to be added after this line
+      _self.bind_static_method("parse", (1, 1), parse as NativeBoundMethod);
+      _self.bind_static_method("stringify", (1, 2), stringify as NativeBoundMethod);
+      // <<<<<<< Static fields for the "Json" namespace to be added before this line
+
+      _self.0
+   }
+}
+
+/// Parses a JSON document into nested Hinton dictionaries and arrays.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Json` namespace (unused).
+/// * `args`: The JSON document.
+///
+/// # Returns:
+/// RuntimeResult
+fn parse(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   let src = match &args[0] {
+      Object::String(s) => s,
+      _ => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::TypeError,
+            message: format!(
+               "Function 'Json.parse' expected a String argument. Found '{}' instead.",
+               args[0].type_name()
+            ),
+         }
+      }
+   };
+
+   let mut parser = JsonParser {
+      vm,
+      chars: src.char_indices().peekable(),
+   };
+
+   let result = parser.parse_value().and_then(|value| {
+      parser.skip_whitespace();
+
+      match parser.chars.next() {
+         Some((pos, c)) => Err(format!("Unexpected character '{}' at position {}.", c, pos)),
+         None => Ok(value),
+      }
+   });
+
+   match result {
+      Ok(value) => vm.push_stack(value),
+      Err(message) => RuntimeResult::Error {
+         error: RuntimeErrorType::ArgumentError,
+         message: format!("Invalid JSON document. {}", message),
+      },
+   }
+}
+
+/// A recursive-descent parser for JSON documents.
+struct JsonParser<'a> {
+   /// A mutable reference to the virtual machine, used to allocate arrays and dictionaries.
+   vm: &'a mut VM,
+   /// The characters of the document, and their position.
+   chars: Peekable<CharIndices<'a>>,
+}
+
+impl<'a> JsonParser<'a> {
+   /// Skips the whitespace characters before the next token.
+   fn skip_whitespace(&mut self) {
+      while matches!(self.chars.peek(), Some((_, ' ' | '\t' | '\n' | '\r'))) {
+         self.chars.next();
+      }
+   }
+
+   /// Consumes the given character, or fails with an error message.
+   fn expect(&mut self, expected: char) -> Result<(), String> {
+      match self.chars.next() {
+         Some((_, c)) if c == expected => Ok(()),
+         Some((pos, c)) => Err(format!(
+            "Expected '{}' at position {}, found '{}'.",
+            expected, pos, c
+         )),
+         None => Err(format!("Expected '{}' at the end of the document.", expected)),
+      }
+   }
+
+   /// Consumes the given keyword.
+   fn expect_keyword(&mut self, keyword: &str) -> Result<(), String> {
+      keyword.chars().try_for_each(|c| self.expect(c))
+   }
+
+   /// Parses a JSON value.
+   fn parse_value(&mut self) -> Result<Object, String> {
+      self.skip_whitespace();
+
+      match self.chars.peek().copied() {
+         Some((_, 'n')) => self.expect_keyword("null").map(|_| Object::Null),
+         Some((_, 't')) => self.expect_keyword("true").map(|_| Object::Bool(true)),
+         Some((_, 'f')) => self.expect_keyword("false").map(|_| Object::Bool(false)),
+         Some((_, '"')) => self.parse_string().map(Object::from),
+         Some((_, '[')) => self.parse_array(),
+         Some((_, '{')) => self.parse_object(),
+         Some((_, '-' | '0'..='9')) => self.parse_number(),
+         Some((pos, c)) => Err(format!("Unexpected character '{}' at position {}.", c, pos)),
+         None => Err(String::from("Unexpected end of the document.")),
+      }
+   }
+
+   /// Parses a JSON number. Numbers without a fraction or an exponent are parsed into
+   /// integers (if they fit in an integer), and every other number is parsed into a float.
+   fn parse_number(&mut self) -> Result<Object, String> {
+      let mut lexeme = String::new();
+
+      while let Some((_, c)) = self.chars.peek() {
+         if !matches!(c, '-' | '+' | '.' | 'e' | 'E' | '0'..='9') {
+            break;
+         }
+
+         lexeme.push(*c);
+         self.chars.next();
+      }
+
+      if !lexeme.contains(&['.', 'e', 'E'][..]) {
+         if let Ok(i) = lexeme.parse::<i64>() {
+            return Ok(Object::Int(i));
+         }
+
+         // Integers too large for an Int are parsed as BigInts, instead of losing precision.
+         if let Some(b) = BigInt::parse(&lexeme) {
+            return Ok(Object::from(b));
+         }
+      }
+
+      match lexeme.parse::<f64>() {
+         Ok(f) => Ok(Object::Float(f)),
+         Err(_) => Err(format!("Invalid number '{}'.", lexeme)),
+      }
+   }
+
+   /// Parses a JSON string, including its escape sequences.
+   fn parse_string(&mut self) -> Result<String, String> {
+      self.expect('"')?;
+      let mut string = String::new();
+
+      loop {
+         match self.chars.next() {
+            Some((_, '"')) => return Ok(string),
+            Some((_, '\\')) => match self.chars.next() {
+               Some((_, '"')) => string.push('"'),
+               Some((_, '\\')) => string.push('\\'),
+               Some((_, '/')) => string.push('/'),
+               Some((_, 'b')) => string.push('\u{8}'),
+               Some((_, 'f')) => string.push('\u{c}'),
+               Some((_, 'n')) => string.push('\n'),
+               Some((_, 'r')) => string.push('\r'),
+               Some((_, 't')) => string.push('\t'),
+               Some((_, 'u')) => string.push(self.parse_unicode_escape()?),
+               Some((pos, c)) => {
+                  return Err(format!("Invalid escape sequence '\\{}' at position {}.", c, pos))
+               }
+               None => break,
+            },
+            Some((_, c)) => string.push(c),
+            None => break,
+         }
+      }
+
+      Err(String::from("Unterminated string."))
+   }
+
+   /// Parses the four hexadecimal digits of a `\u` escape sequence, and the
+   /// second half of a surrogate pair if the first digits are a high surrogate.
+   fn parse_unicode_escape(&mut self) -> Result<char, String> {
+      let high = self.parse_hex_digits()?;
+
+      let code = if (0xD800..0xDC00).contains(&high) {
+         self.expect_keyword("\\u")?;
+         let low = self.parse_hex_digits()?;
+         0x10000 + ((high - 0xD800) << 10) + (low.wrapping_sub(0xDC00) & 0x3FF)
+      } else {
+         high
+      };
+
+      char::from_u32(code).ok_or_else(|| format!("Invalid unicode escape '{:X}'.", code))
+   }
+
+   /// Parses four hexadecimal digits.
+   fn parse_hex_digits(&mut self) -> Result<u32, String> {
+      let mut code = 0;
+
+      for _ in 0..4 {
+         match self.chars.next().and_then(|(_, c)| c.to_digit(16)) {
+            Some(d) => code = code * 16 + d,
+            None => return Err(String::from("Invalid unicode escape sequence.")),
+         }
+      }
+
+      Ok(code)
+   }
+
+   /// Parses a JSON array into a Hinton array.
+   fn parse_array(&mut self) -> Result<Object, String> {
+      self.expect('[')?;
+      let mut values = vec![];
+
+      self.skip_whitespace();
+      if let Some((_, ']')) = self.chars.peek() {
+         self.chars.next();
+         return Ok(self.vm.alloc_array(values));
+      }
+
+      loop {
+         values.push(self.parse_value()?);
+         self.skip_whitespace();
+
+         match self.chars.next() {
+            Some((_, ',')) => continue,
+            Some((_, ']')) => return Ok(self.vm.alloc_array(values)),
+            Some((pos, c)) => return Err(format!("Expected ',' or ']' at position {}, found '{}'.", pos, c)),
+            None => return Err(String::from("Unterminated array.")),
+         }
+      }
+   }
+
+   /// Parses a JSON object into a Hinton dictionary.
+   fn parse_object(&mut self) -> Result<Object, String> {
+      self.expect('{')?;
+      let mut entries = DictObject::default();
+
+      self.skip_whitespace();
+      if let Some((_, '}')) = self.chars.peek() {
+         self.chars.next();
+         return Ok(self.vm.alloc_dict(entries));
+      }
+
+      loop {
+         self.skip_whitespace();
+         let key = self.parse_string()?;
+         self.skip_whitespace();
+         self.expect(':')?;
+         entries.insert(key, self.parse_value()?);
+         self.skip_whitespace();
+
+         match self.chars.next() {
+            Some((_, ',')) => continue,
+            Some((_, '}')) => return Ok(self.vm.alloc_dict(entries)),
+            Some((pos, c)) => {
+               return Err(format!(
+                  "Expected ',' or '}}' at position {}, found '{}'.",
+                  pos, c
+               ))
+            }
+            None => return Err(String::from("Unterminated object.")),
+         }
+      }
+   }
+}
+
+/// Serializes a Hinton object into a JSON document. The document is indented with the given
+/// number of spaces per level, or written in a single line if no indentation is given.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Json` namespace (unused).
+/// * `args`: The object to serialize, and the optional indentation.
+///
+/// # Returns:
+/// RuntimeResult
+fn stringify(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   let indent = match args.get(1) {
+      None | Some(Object::Null) => None,
+      Some(Object::Int(i)) if *i >= 0 => Some(*i as usize),
+      Some(o) => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::TypeError,
+            message: format!(
+               "The indentation of 'Json.stringify' must be a non-negative Int. Found '{}' instead.",
+               o.type_name()
+            ),
+         }
+      }
+   };
+
+   let mut writer = JsonWriter {
+      out: String::new(),
+      indent,
+      visiting: vec![],
+   };
+
+   match writer.write_value(&args[0], 0) {
+      Ok(_) => vm.push_stack(Object::from(writer.out)),
+      Err(message) => RuntimeResult::Error {
+         error: RuntimeErrorType::TypeError,
+         message,
+      },
+   }
+}
+
+/// Writes Hinton objects as JSON.
+struct JsonWriter {
+   /// The JSON document.
+   out: String,
+   /// The number of spaces per indentation level, if the document should be indented.
+   indent: Option<usize>,
+   /// The addresses of the arrays and dictionaries currently being written, used to
+   /// detect reference cycles (which cannot be represented in JSON).
+   visiting: Vec<usize>,
+}
+
+impl JsonWriter {
+   /// Writes a Hinton object.
+   fn write_value(&mut self, value: &Object, depth: usize) -> Result<(), String> {
+      match value {
+         Object::Null => self.out.push_str("null"),
+         Object::Bool(b) => self.out.push_str(if *b { "true" } else { "false" }),
+         Object::Int(i) => self.out.push_str(&i.to_string()),
+         Object::BigInt(b) => self.out.push_str(&b.to_string()),
+         Object::Float(f) if f.is_finite() => self.out.push_str(&format!("{:?}", f)),
+         Object::String(s) => self.write_string(s),
+         Object::Char(c) => self.write_string(&c.to_string()),
+         Object::Array(arr) => {
+            self.enter(Rc::as_ptr(arr) as usize)?;
+            let values = arr.borrow().clone();
+            self.write_sequence('[', ']', values.len(), depth, |w, i, depth| {
+               w.write_value(&values[i], depth)
+            })?;
+            self.visiting.pop();
+         }
+         Object::Tuple(tup) => {
+            self.write_sequence('[', ']', tup.len(), depth, |w, i, depth| {
+               w.write_value(&tup[i], depth)
+            })?;
+         }
+         Object::Dict(dict) => {
+            self.enter(Rc::as_ptr(dict) as usize)?;
+
+            // Keys are sorted so that the output does not depend on the order of the dictionary.
+            let mut entries: Vec<(String, Object)> = dict
+               .borrow()
+               .iter()
+               .map(|(k, v)| match k {
+                  Object::String(k) => Ok((k.to_string(), v.clone())),
+                  _ => Err(format!(
+                     "Only dictionaries with String keys can be serialized into JSON. Found a '{}' key.",
+                     k.type_name()
+                  )),
+               })
+               .collect::<Result<_, String>>()?;
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+            self.write_sequence('{', '}', entries.len(), depth, |w, i, depth| {
+               w.write_string(&entries[i].0);
+               w.out.push_str(if w.indent.is_some() { ": " } else { ":" });
+               w.write_value(&entries[i].1, depth)
+            })?;
+            self.visiting.pop();
+         }
+         _ => {
+            return Err(format!(
+               "Objects of type '{}' cannot be serialized into JSON.",
+               value.type_name()
+            ))
+         }
+      }
+
+      Ok(())
+   }
+
+   /// Marks an array or dictionary as being written, failing if it is already being written.
+   fn enter(&mut self, address: usize) -> Result<(), String> {
+      if self.visiting.contains(&address) {
+         return Err(String::from("Cannot serialize a cyclic structure into JSON."));
+      }
+
+      self.visiting.push(address);
+      Ok(())
+   }
+
+   /// Writes the items of a JSON array or object between the given delimiters.
+   fn write_sequence<F>(
+      &mut self,
+      open: char,
+      close: char,
+      len: usize,
+      depth: usize,
+      mut item: F,
+   ) -> Result<(), String>
+   where
+      F: FnMut(&mut Self, usize, usize) -> Result<(), String>,
+   {
+      self.out.push(open);
+
+      for i in 0..len {
+         if i > 0 {
+            self.out.push(',');
+         }
+
+         self.write_newline(depth + 1);
+         item(self, i, depth + 1)?;
+      }
+
+      if len > 0 {
+         self.write_newline(depth);
+      }
+
+      self.out.push(close);
+      Ok(())
+   }
+
+   /// Writes a line break followed by the indentation of the given level, if the document is indented.
+   fn write_newline(&mut self, depth: usize) {
+      if let Some(indent) = self.indent {
+         self.out.push('\n');
+         self.out.push_str(&" ".repeat(indent * depth));
+      }
+   }
+
+   /// Writes a JSON string, escaping the characters that cannot appear in it.
+   fn write_string(&mut self, s: &str) {
+      self.out.push('"');
+
+      for c in s.chars() {
+         match c {
+            '"' => self.out.push_str("\\\""),
+            '\\' => self.out.push_str("\\\\"),
+            '\n' => self.out.push_str("\\n"),
+            '\r' => self.out.push_str("\\r"),
+            '\t' => self.out.push_str("\\t"),
+            c if (c as u32) < 0x20 => self.out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => self.out.push(c),
+         }
+      }
+
+      self.out.push('"');
+   }
+}
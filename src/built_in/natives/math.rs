@@ -0,0 +1,346 @@
+use crate::built_in::primitives::HTPrimitive;
+use crate::built_in::NativeBoundMethod;
+use crate::errors::RuntimeErrorType;
+use crate::objects::class_obj::{ClassField, ClassObject};
+use crate::objects::Object;
+use crate::virtual_machine::{RuntimeResult, VM};
+use hashbrown::HashMap;
+use std::cell::Cell;
+use std::time::SystemTime;
+
+/// Represents the Hinton `Math` namespace, whose static members are the mathematical
+/// constants and native functions available through a Hinton program.
+pub struct MathClass(ClassObject);
+
+/// Implements the `HTPrimitive` trait for the `Math` namespace.
+impl HTPrimitive for MathClass {
+   /// Gets the name class name of this Hinton primitive.
+   fn name(&self) -> String {
+      self.0.name.clone()
+   }
+
+   /// Gets the non-static members of this Hinton primitive.
+   fn members(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.members
+   }
+
+   /// Gets the static members of this Hinton primitive.
+   fn statics(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.statics
+   }
+
+   /// Gets the default class object stored in this Hinton primitive.
+   fn default() -> ClassObject {
+      let mut _self = MathClass(ClassObject::new("Math"));
+
+      // >>>>>>> Static fields for the "Math" namespace to be added after this line
+      _self.bind_field("e", true, true, false, true, Object::Float(std::f64::consts::E));
+      _self.bind_field("pi", true, true, false, true, Object::Float(std::f64::consts::PI));
+      _self.bind_static_method("abs", (1, 1), abs as NativeBoundMethod);
+      _self.bind_static_method("ceil", (1, 1), ceil as NativeBoundMethod);
+      _self.bind_static_method("clamp", (3, 3), clamp as NativeBoundMethod);
+      _self.bind_static_method("cos", (1, 1), cos as NativeBoundMethod);
+      _self.bind_static_method("exp", (1, 1), exp as NativeBoundMethod);
+      _self.bind_static_method("floor", (1, 1), floor as NativeBoundMethod);
+      _self.bind_static_method("log", (1, 2), log as NativeBoundMethod);
+      _self.bind_static_method("max", (2, 2), max as NativeBoundMethod);
+      _self.bind_static_method("min", (2, 2), min as NativeBoundMethod);
+      _self.bind_static_method("random", (0, 0), random as NativeBoundMethod);
+      _self.bind_static_method("sin", (1, 1), sin as NativeBoundMethod);
+      _self.bind_static_method("sqrt", (1, 1), sqrt as NativeBoundMethod);
+      _self.bind_static_method("tan", (1, 1), tan as NativeBoundMethod);
+      // <<<<<<< Static fields for the "Math" namespace to be added before this line
+
+      _self.0
+   }
+}
+
+/// Converts the argument of a `Math` function into a Rust f64 float.
+///
+/// # Arguments
+/// * `arg`: The argument passed to the function.
+/// * `fn_name`: The name of the function.
+///
+/// # Returns:
+/// Result<f64, RuntimeResult>
+fn to_float(arg: &Object, fn_name: &str) -> Result<f64, RuntimeResult> {
+   match arg {
+      Object::Float(f) => Ok(*f),
+      Object::Int(_) | Object::Bool(_) => Ok(arg.as_int().unwrap() as f64),
+      _ => Err(RuntimeResult::Error {
+         error: RuntimeErrorType::TypeError,
+         message: format!(
+            "Function 'Math.{}' expected numeric arguments. Found '{}' instead.",
+            fn_name,
+            arg.type_name()
+         ),
+      }),
+   }
+}
+
+macro_rules! float_arg {
+   ($arg: expr, $fn_name: expr) => {
+      match to_float(&$arg, $fn_name) {
+         Ok(f) => f,
+         Err(e) => return e,
+      }
+   };
+}
+
+/// Computes the square root of a number.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Math` namespace (unused).
+/// * `args`: The number.
+///
+/// # Returns:
+/// RuntimeResult
+fn sqrt(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   vm.push_stack(Object::Float(float_arg!(args[0], "sqrt").sqrt()))
+}
+
+/// Computes the sine of an angle, in radians.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Math` namespace (unused).
+/// * `args`: The angle.
+///
+/// # Returns:
+/// RuntimeResult
+fn sin(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   vm.push_stack(Object::Float(float_arg!(args[0], "sin").sin()))
+}
+
+/// Computes the cosine of an angle, in radians.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Math` namespace (unused).
+/// * `args`: The angle.
+///
+/// # Returns:
+/// RuntimeResult
+fn cos(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   vm.push_stack(Object::Float(float_arg!(args[0], "cos").cos()))
+}
+
+/// Computes the tangent of an angle, in radians.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Math` namespace (unused).
+/// * `args`: The angle.
+///
+/// # Returns:
+/// RuntimeResult
+fn tan(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   vm.push_stack(Object::Float(float_arg!(args[0], "tan").tan()))
+}
+
+/// Computes the logarithm of a number. The natural logarithm is computed
+/// unless a base is provided as the second argument.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Math` namespace (unused).
+/// * `args`: The number, and an optional base.
+///
+/// # Returns:
+/// RuntimeResult
+fn log(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   let x = float_arg!(args[0], "log");
+
+   if args.len() == 2 {
+      vm.push_stack(Object::Float(x.log(float_arg!(args[1], "log"))))
+   } else {
+      vm.push_stack(Object::Float(x.ln()))
+   }
+}
+
+/// Computes `e` raised to the power of a number.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Math` namespace (unused).
+/// * `args`: The exponent.
+///
+/// # Returns:
+/// RuntimeResult
+fn exp(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   vm.push_stack(Object::Float(float_arg!(args[0], "exp").exp()))
+}
+
+/// Converts the arguments of a `Math` function into Rust i64 integers,
+/// if none of the arguments are floats.
+///
+/// # Arguments
+/// * `args`: The arguments passed to the function.
+///
+/// # Returns:
+/// Option<Vec<i64>>
+fn to_ints(args: &[Object]) -> Option<Vec<i64>> {
+   args.iter().map(|a| a.as_int()).collect()
+}
+
+/// Rounds a number down to the nearest integer. Integers are returned unchanged.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Math` namespace (unused).
+/// * `args`: The number.
+///
+/// # Returns:
+/// RuntimeResult
+fn floor(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   match to_ints(&args) {
+      Some(i) => vm.push_stack(Object::Int(i[0])),
+      None => vm.push_stack(Object::Float(float_arg!(args[0], "floor").floor())),
+   }
+}
+
+/// Rounds a number up to the nearest integer. Integers are returned unchanged.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Math` namespace (unused).
+/// * `args`: The number.
+///
+/// # Returns:
+/// RuntimeResult
+fn ceil(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   match to_ints(&args) {
+      Some(i) => vm.push_stack(Object::Int(i[0])),
+      None => vm.push_stack(Object::Float(float_arg!(args[0], "ceil").ceil())),
+   }
+}
+
+/// Computes the absolute value of a number.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Math` namespace (unused).
+/// * `args`: The number.
+///
+/// # Returns:
+/// RuntimeResult
+fn abs(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   match to_ints(&args).and_then(|i| i[0].checked_abs()) {
+      Some(i) => vm.push_stack(Object::Int(i)),
+      None => vm.push_stack(Object::Float(float_arg!(args[0], "abs").abs())),
+   }
+}
+
+/// Gets the smallest of two numbers.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Math` namespace (unused).
+/// * `args`: The two numbers.
+///
+/// # Returns:
+/// RuntimeResult
+fn min(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   match to_ints(&args) {
+      Some(i) => vm.push_stack(Object::Int(i[0].min(i[1]))),
+      None => {
+         let a = float_arg!(args[0], "min");
+         vm.push_stack(Object::Float(a.min(float_arg!(args[1], "min"))))
+      }
+   }
+}
+
+/// Gets the largest of two numbers.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Math` namespace (unused).
+/// * `args`: The two numbers.
+///
+/// # Returns:
+/// RuntimeResult
+fn max(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   match to_ints(&args) {
+      Some(i) => vm.push_stack(Object::Int(i[0].max(i[1]))),
+      None => {
+         let a = float_arg!(args[0], "max");
+         vm.push_stack(Object::Float(a.max(float_arg!(args[1], "max"))))
+      }
+   }
+}
+
+/// Restricts a number to the given interval.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Math` namespace (unused).
+/// * `args`: The number, and the lower and upper bounds of the interval.
+///
+/// # Returns:
+/// RuntimeResult
+fn clamp(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   let (x, lower, upper) = (
+      float_arg!(args[0], "clamp"),
+      float_arg!(args[1], "clamp"),
+      float_arg!(args[2], "clamp"),
+   );
+
+   // The bounds are checked with the float values because `clamp(...)` panics
+   // when the lower bound is greater than the upper bound, or when either is NaN.
+   if lower.is_nan() || upper.is_nan() || lower > upper {
+      return RuntimeResult::Error {
+         error: RuntimeErrorType::ArgumentError,
+         message: String::from("The lower bound of 'Math.clamp' cannot be greater than its upper bound."),
+      };
+   }
+
+   match to_ints(&args) {
+      Some(i) => vm.push_stack(Object::Int(i[0].clamp(i[1], i[2]))),
+      None => vm.push_stack(Object::Float(x.clamp(lower, upper))),
+   }
+}
+
+thread_local! {
+   /// The state of the pseudo-random number generator used by `Math.random()`.
+   static RNG_STATE: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Generates a pseudo-random float in the interval `[0, 1)`, using an xorshift generator
+/// seeded from the system's time. The generator is not suitable for cryptographic purposes.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Math` namespace (unused).
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn random(vm: &mut VM, _: Object, _: Vec<Object>) -> RuntimeResult {
+   // The state of an xorshift generator must never be zero.
+   if RNG_STATE.with(|state| state.get() == 0) {
+      let seed = match vm.system_time() {
+         Ok(time) => match time.duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(t) => t.as_nanos() as u64,
+            Err(_) => 0,
+         },
+         Err(e) => return e,
+      };
+
+      RNG_STATE.with(|state| state.set(seed | 1));
+   }
+
+   let value = RNG_STATE.with(|state| {
+      let mut x = state.get();
+
+      x ^= x << 13;
+      x ^= x >> 7;
+      x ^= x << 17;
+      state.set(x);
+
+      x
+   });
+
+   // Uses the top 53 bits of the value, which is the precision of a 64-bit float.
+   vm.push_stack(Object::Float((value >> 11) as f64 / (1u64 << 53) as f64))
+}
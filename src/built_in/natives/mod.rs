@@ -0,0 +1,1058 @@
+use crate::built_in::format::format_template;
+use crate::built_in::{plugins, NativeFn, NativeFnBody};
+use crate::docs;
+use crate::errors::{colors_enabled, RuntimeErrorType};
+use crate::objects::bigint::BigInt;
+use crate::objects::bytes_obj;
+use crate::objects::class_obj::BoundMethod;
+use crate::objects::set_obj::SetObject;
+use crate::objects::{IterKind, IterObject, NativeFuncObj, Object, RangeObject};
+use crate::virtual_machine::{RuntimeResult, Timer, VM};
+use hashbrown::{hash_map, HashMap};
+use std::cell::RefCell;
+use std::io::{self, Write};
+use std::path::Path;
+use std::rc::Rc;
+use std::time::{Duration, SystemTime};
+
+// Submodules
+pub mod crypto;
+pub mod encoding;
+pub mod file;
+pub mod http;
+pub mod json;
+pub mod math;
+pub mod net;
+pub mod process;
+pub mod random;
+pub mod regex;
+pub mod time;
+pub mod uuid;
+
+/// Represents the list of native functions available through a Hinton program.
+pub struct Natives(pub(crate) HashMap<String, NativeFuncObj>);
+
+/// The default implementation of a native function list.
+impl Default for Natives {
+   fn default() -> Self {
+      let mut natives = Natives(Default::default());
+
+      // >>>>>>>>>>>>>>>> Native functions to be added after this line
+      natives.add_native_function("BigInt", 1, 1, native_big_int as NativeFn);
+      natives.add_native_function("Bytes", 0, 2, native_bytes as NativeFn);
+      natives.add_native_function("Channel", 0, 0, native_channel as NativeFn);
+      natives.add_native_function("Set", 0, 1, native_set as NativeFn);
+      natives.add_native_function("assert_eq", 2, 3, native_assert_eq as NativeFn);
+      natives.add_native_function("assert_ne", 2, 3, native_assert_ne as NativeFn);
+      natives.add_native_function("chain", 1, 255, native_chain as NativeFn);
+      natives.add_native_function("clock", 0, 0, native_clock as NativeFn);
+      natives.add_native_function("delay", 1, 1, native_delay as NativeFn);
+      natives.add_native_function("enumerate", 1, 1, native_enumerate as NativeFn);
+      natives.add_native_function("eprint", 0, 255, native_eprint as NativeFn);
+      natives.add_native_function("float", 1, 1, native_float as NativeFn);
+      natives.add_native_function("format", 1, 255, native_format as NativeFn);
+      natives.add_native_function("help", 1, 1, native_help as NativeFn);
+      natives.add_native_function("input", 0, 1, native_input as NativeFn);
+      natives.add_native_function("instance_of", 2, 2, native_instance_of as NativeFn);
+      natives.add_native_function("int", 1, 1, native_int as NativeFn);
+      natives.add_native_function("iter", 1, 1, native_iter as NativeFn);
+      natives.add_native_function("load_plugin", 1, 1, native_load_plugin as NativeFn);
+      natives.add_native_function("next", 1, 1, native_next as NativeFn);
+      natives.add_native_function("print", 0, 255, native_print as NativeFn);
+      natives.add_native_function("println", 0, 255, native_print as NativeFn);
+      natives.add_native_function("range", 2, 3, native_range as NativeFn);
+      natives.add_native_function("read_all", 0, 0, native_read_all as NativeFn);
+      natives.add_native_function("read_line", 0, 0, native_read_line as NativeFn);
+      natives.add_native_function("round", 1, 2, native_round as NativeFn);
+      natives.add_native_function("skip", 2, 2, native_skip as NativeFn);
+      natives.add_native_function("sleep", 1, 1, native_sleep as NativeFn);
+      natives.add_native_function("spawn", 1, 255, native_spawn as NativeFn);
+      natives.add_native_function("take", 2, 2, native_take as NativeFn);
+      natives.add_native_function("truncate", 1, 1, native_truncate as NativeFn);
+      natives.add_native_function("zip", 1, 255, native_zip as NativeFn);
+      // <<<<<<<<<<<<<<<< Native functions to be added before this line
+
+      natives
+   }
+}
+
+impl Natives {
+   /// Adds a native function definition to the native functions list.
+   fn add_native_function(&mut self, name: &str, min_arity: u8, max_arity: u8, body: NativeFn) {
+      self.add_function(name, min_arity, max_arity, NativeFnBody::BuiltIn(body));
+   }
+
+   /// Adds a native function definition with the given body to the native functions list.
+   pub(crate) fn add_function(&mut self, name: &str, min_arity: u8, max_arity: u8, body: NativeFnBody) {
+      let name = String::from(name);
+
+      if let hash_map::Entry::Vacant(e) = self.0.entry(name.clone()) {
+         let f = NativeFuncObj {
+            name,
+            min_arity,
+            max_arity,
+            body,
+         };
+
+         e.insert(f);
+      } else {
+         panic!("Cannot duplicate native function '{}'.", name);
+      }
+   }
+
+   /// Obtains the NativeFunctionObj associated with a native function name.
+   pub fn get_native_fn_object(&self, idx: usize) -> Result<NativeFuncObj, RuntimeResult> {
+      // Natives are indexed in alphabetical order, so that the indices emitted by
+      // the compiler remain valid in precompiled bytecode files.
+      let mut names = self.0.keys().collect::<Vec<&String>>();
+      names.sort();
+      let name = names[idx];
+
+      match self.0.get(name) {
+         Some(f) => Ok(f.clone()),
+         None => Err(RuntimeResult::Error {
+            error: RuntimeErrorType::ReferenceError,
+            message: format!("No native function named '{}'.", name),
+         }),
+      }
+   }
+
+   /// Obtains a list of the names of the native functions
+   pub fn get_names(&self) -> Vec<String> {
+      let mut names: Vec<String> = self.0.keys().cloned().collect();
+      names.sort();
+      names
+   }
+}
+
+// >>>>>>>>>>>>>>>>> =============================================== <<<<<<<<<<<<<<<<<<<
+// ================= Native Function Implementations After This Line ===================
+// >>>>>>>>>>>>>>>>> =============================================== <<<<<<<<<<<<<<<<<<<
+
+/// Prints values separated by spaces and followed by a line break, to the output or the error
+/// stream of the virtual machine.
+///
+/// # Parameters
+/// - `vm`: The virtual machine.
+/// - `args`: The values to print.
+/// - `to_stderr`: Whether the values are printed to the error stream.
+fn print_values(vm: &mut VM, args: &[Object], to_stderr: bool) -> RuntimeResult {
+   let mut texts = Vec::with_capacity(args.len());
+
+   for arg in args {
+      texts.push(match arg {
+         // Instances may define a `__str__` method to customize how they are printed.
+         Object::Instance(_) => match vm.stringify(arg) {
+            Ok(s) => s,
+            Err(e) => return e,
+         },
+         obj if colors_enabled() => obj.to_string(),
+         obj => obj.as_plain_string(),
+      });
+   }
+
+   let stream = if to_stderr { &mut vm.stderr } else { &mut vm.stdout };
+   if let Err(e) = writeln!(stream, "{}", texts.join(" ")) {
+      return RuntimeResult::Error {
+         error: RuntimeErrorType::IOError,
+         message: format!("Failed to print the value. {}", e),
+      };
+   }
+
+   vm.push_stack(Object::Null)
+}
+
+/// Implements the `print(...)` and `println(...)` native functions for Hinton, which print
+/// their arguments, separated by spaces, as a line of the output of the virtual machine.
+fn native_print(vm: &mut VM, args: Vec<Object>) -> RuntimeResult {
+   print_values(vm, &args, false)
+}
+
+/// Implements the `eprint(...)` native function for Hinton, which prints its arguments,
+/// separated by spaces, as a line of the error stream of the virtual machine.
+fn native_eprint(vm: &mut VM, args: Vec<Object>) -> RuntimeResult {
+   print_values(vm, &args, true)
+}
+
+/// Implements the `format(...)` native function for Hinton, which replaces the placeholders
+/// of a format string with the rest of the arguments (e.g., `format("{:>5}|{:.2}", "a", 3.14159)`).
+fn native_format(vm: &mut VM, args: Vec<Object>) -> RuntimeResult {
+   let template = match &args[0] {
+      Object::String(s) => s.clone(),
+      obj => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::TypeError,
+            message: format!(
+               "The first argument of 'format' must be a String. Found '{}' instead.",
+               obj.type_name()
+            ),
+         }
+      }
+   };
+
+   match format_template(vm, &template, &args[1..]) {
+      Ok(result) => vm.push_stack(Object::from(result)),
+      Err(e) => e,
+   }
+}
+
+/// Implements the `help(...)` native function for Hinton, which prints the signature and the
+/// doc comment of a function, or the documentation of the public methods of a class.
+fn native_help(vm: &mut VM, args: Vec<Object>) -> RuntimeResult {
+   let text = match docs::help_text(&args[0]) {
+      Some(text) => text,
+      None => format!("No documentation for values of type '{}'.", args[0].type_name()),
+   };
+
+   if let Err(e) = writeln!(vm.stdout, "{}", text) {
+      return RuntimeResult::Error {
+         error: RuntimeErrorType::IOError,
+         message: format!("Failed to print the documentation. {}", e),
+      };
+   }
+
+   vm.push_stack(Object::Null)
+}
+
+/// Implements the `instance_of(...)` native function for Hinton, which checks if a value
+/// is an instance of a class (or of its subclasses), or implements an interface.
+fn native_instance_of(vm: &mut VM, args: Vec<Object>) -> RuntimeResult {
+   match args[0].is_instance_of(&args[1]) {
+      Some(b) => vm.push_stack(Object::Bool(b)),
+      None => RuntimeResult::Error {
+         error: RuntimeErrorType::TypeError,
+         message: format!(
+            "Expected a class or an interface but got '{}' instead.",
+            args[1].type_name()
+         ),
+      },
+   }
+}
+
+/// Implements the `clock()` native function for Hinton, which
+/// retrieves the current time from the Unix Epoch time.
+fn native_clock(vm: &mut VM, _: Vec<Object>) -> RuntimeResult {
+   let now = match vm.system_time() {
+      Ok(time) => time.duration_since(SystemTime::UNIX_EPOCH),
+      Err(e) => return e,
+   };
+
+   match now {
+      Ok(t) => {
+         let time = t.as_millis();
+         vm.push_stack(Object::Int(time as i64))
+      }
+      Err(_) => RuntimeResult::Error {
+         error: RuntimeErrorType::Internal,
+         message: String::from("System's time before UNIX EPOCH."),
+      },
+   }
+}
+
+/// Implements the `iter(...)` native function for Hinton, which
+/// converts the give object to an iterable object.
+fn native_iter(vm: &mut VM, args: Vec<Object>) -> RuntimeResult {
+   match make_iter(args[0].clone()) {
+      Ok(o) => vm.push_stack(o),
+      Err(e) => e,
+   }
+}
+
+/// Converts a Hinton object into an Iterable object. Besides the built-in collections, any
+/// instance with a `next()` method can be iterated over, until the method returns `null`.
+pub fn make_iter(o: Object) -> Result<Object, RuntimeResult> {
+   let kind = match &o {
+      Object::String(_) => IterKind::Items,
+      Object::Array(_) => IterKind::Items,
+      Object::Range(_) => IterKind::Items,
+      Object::Tuple(_) => IterKind::Items,
+      Object::Dict(_) => IterKind::Items,
+      Object::Set(_) => IterKind::Items,
+      Object::Bytes(_) => IterKind::Items,
+      Object::Instance(inst) if BoundMethod::find(inst, "next").is_some() => IterKind::Protocol,
+      // If the object is already an iterable, return that same object.
+      Object::Iter(_) => return Ok(o),
+      // Object cannot be iterable.
+      _ => {
+         return Err(RuntimeResult::Error {
+            error: RuntimeErrorType::TypeError,
+            message: format!("Cannot create iterable from '{}'.", o.type_name()),
+         })
+      }
+   };
+
+   Ok(Object::Iter(Rc::new(RefCell::new(IterObject::new(o, kind)))))
+}
+
+/// Converts a Hinton object into an iterator, and gets the underlying `IterObject`.
+fn make_iter_object(o: Object) -> Result<Rc<RefCell<IterObject>>, RuntimeResult> {
+   match make_iter(o)? {
+      Object::Iter(iter) => Ok(iter),
+      _ => unreachable!("Expected an iterator object."),
+   }
+}
+
+/// Implements the `next(...)` native function for Hinton, which
+/// retrieves the next item in an iterable object.
+fn native_next(vm: &mut VM, args: Vec<Object>) -> RuntimeResult {
+   match &args[0] {
+      Object::Iter(iter) => match get_next_in_iter(vm, iter) {
+         Ok(o) => vm.push_stack(o),
+         Err(e) => e,
+      },
+      _ => RuntimeResult::Error {
+         error: RuntimeErrorType::TypeError,
+         message: format!("Object of type '{}' is not iterable.", args[0].type_name()),
+      },
+   }
+}
+
+/// Creates the error that signals that an iterator has no more items.
+pub fn end_of_iter() -> RuntimeResult {
+   RuntimeResult::Error {
+      error: RuntimeErrorType::StopIteration,
+      message: String::from("End of Iterator."),
+   }
+}
+
+/// Checks if the result of getting the next item in an iterator signals
+/// that the iterator has no more items, rather than a runtime error.
+pub fn is_end_of_iter(result: &RuntimeResult) -> bool {
+   matches!(
+      result,
+      RuntimeResult::Error {
+         error: RuntimeErrorType::StopIteration,
+         ..
+      }
+   )
+}
+
+/// Gets the next item in a Hinton iterator. The iterators composed by an adapter get their
+/// items as they are needed, so adapters never create intermediate collections.
+pub fn get_next_in_iter(vm: &mut VM, o: &Rc<RefCell<IterObject>>) -> Result<Object, RuntimeResult> {
+   // The iterator is not borrowed while getting the items of the composed iterators, since
+   // those items may come from a `next()` method that uses this same iterator.
+   let (source, index, kind) = {
+      let iter = o.borrow();
+      ((*iter.iter).clone(), iter.index, iter.kind)
+   };
+
+   let item = match (kind, &source) {
+      (IterKind::Items, _) => return get_next_item(o),
+      (IterKind::Protocol, Object::Instance(inst)) => {
+         let method = match BoundMethod::find(inst, "next") {
+            Some(method) => Object::BoundMethod(method),
+            None => return Err(end_of_iter()),
+         };
+
+         match vm.call_value(method, vec![])? {
+            Object::Null => return Err(end_of_iter()),
+            item => item,
+         }
+      }
+      (IterKind::Enumerate, Object::Iter(inner)) => {
+         let item = get_next_in_iter(vm, inner)?;
+         Object::Tuple(Rc::new(vec![Object::from(index), item]))
+      }
+      (IterKind::Take(count), Object::Iter(inner)) => match index < count {
+         true => get_next_in_iter(vm, inner)?,
+         false => return Err(end_of_iter()),
+      },
+      (IterKind::Skip(count), Object::Iter(inner)) => {
+         if index == 0 {
+            for _ in 0..count {
+               get_next_in_iter(vm, inner)?;
+            }
+         }
+
+         get_next_in_iter(vm, inner)?
+      }
+      (IterKind::Zip, Object::Tuple(iters)) => {
+         let mut items = Vec::with_capacity(iters.len());
+
+         for iter in iters.iter() {
+            match iter {
+               Object::Iter(iter) => items.push(get_next_in_iter(vm, iter)?),
+               _ => unreachable!("Expected an iterator object."),
+            }
+         }
+
+         Object::Tuple(Rc::new(items))
+      }
+      (IterKind::Chain, Object::Tuple(iters)) => {
+         // Moves on to the next iterator of the chain when the current one ends.
+         let mut position = index;
+
+         let item = loop {
+            match iters.get(position) {
+               Some(Object::Iter(iter)) => match get_next_in_iter(vm, iter) {
+                  Err(e) if is_end_of_iter(&e) => position += 1,
+                  result => break result,
+               },
+               _ => break Err(end_of_iter()),
+            }
+         };
+
+         o.borrow_mut().index = position;
+         return item;
+      }
+      _ => unreachable!("Expected the object of an iterator adapter."),
+   };
+
+   o.borrow_mut().index += 1;
+   Ok(item)
+}
+
+/// Gets the next item in an iterator over the items of a Hinton collection.
+fn get_next_item(o: &Rc<RefCell<IterObject>>) -> Result<Object, RuntimeResult> {
+   let mut iter = o.borrow_mut();
+   let current_index = Object::Int(iter.index as i64);
+
+   // Dictionaries yield their entries in insertion order, as `(key, value)` tuples.
+   if let Object::Dict(dict) = &*iter.iter {
+      let entry = dict
+         .borrow()
+         .get_entry(iter.index)
+         .map(|(k, v)| Object::Tuple(Rc::new(vec![k.clone(), v.clone()])));
+
+      return match entry {
+         Some(entry) => {
+            iter.index += 1;
+            Ok(entry)
+         }
+         None => Err(end_of_iter()),
+      };
+   }
+
+   // Sets yield their items in insertion order.
+   if let Object::Set(set) = &*iter.iter {
+      let item = set.borrow().get(iter.index).cloned();
+
+      return match item {
+         Some(item) => {
+            iter.index += 1;
+            Ok(item)
+         }
+         None => Err(end_of_iter()),
+      };
+   }
+
+   // Strings yield their characters as chars.
+   if let Object::String(string) = &*iter.iter {
+      return match string.chars().nth(iter.index) {
+         Some(c) => {
+            iter.index += 1;
+            Ok(Object::Char(c))
+         }
+         None => Err(end_of_iter()),
+      };
+   }
+
+   // Since we are passing an integer into the `Object.get(...)` method,
+   // the only error that can occur is an `IndexOutOfBounds` error, which
+   // in terms of iterators means there are no more items left to iterate.
+   let obj = match iter.iter.subscript(&current_index) {
+      Ok(o) => o,
+      Err(_) => return Err(end_of_iter()),
+   };
+
+   // Increment to the next position of the iterator.
+   iter.index += 1;
+
+   Ok(obj)
+}
+
+/// Implements the `enumerate(...)` native function for Hinton, which creates an iterator
+/// that pairs the items of an iterable object with their positions, as `(index, item)` tuples.
+fn native_enumerate(vm: &mut VM, args: Vec<Object>) -> RuntimeResult {
+   adapt_iter(vm, &args[0], IterKind::Enumerate)
+}
+
+/// Implements the `take(...)` native function for Hinton, which creates an iterator
+/// that produces, at most, the given number of items of an iterable object.
+fn native_take(vm: &mut VM, args: Vec<Object>) -> RuntimeResult {
+   match iter_count("take", &args[1]) {
+      Ok(count) => adapt_iter(vm, &args[0], IterKind::Take(count)),
+      Err(e) => e,
+   }
+}
+
+/// Implements the `skip(...)` native function for Hinton, which creates an iterator
+/// that skips the given number of items of an iterable object, and produces the rest.
+fn native_skip(vm: &mut VM, args: Vec<Object>) -> RuntimeResult {
+   match iter_count("skip", &args[1]) {
+      Ok(count) => adapt_iter(vm, &args[0], IterKind::Skip(count)),
+      Err(e) => e,
+   }
+}
+
+/// Implements the `zip(...)` native function for Hinton, which creates an iterator that
+/// produces tuples with the next item of each iterable object, until one of them ends.
+fn native_zip(vm: &mut VM, args: Vec<Object>) -> RuntimeResult {
+   adapt_iters(vm, args, IterKind::Zip)
+}
+
+/// Implements the `chain(...)` native function for Hinton, which creates an iterator
+/// that produces the items of each iterable object, one after the other.
+fn native_chain(vm: &mut VM, args: Vec<Object>) -> RuntimeResult {
+   adapt_iters(vm, args, IterKind::Chain)
+}
+
+/// Creates an iterator adapter over the iterator of an iterable object.
+fn adapt_iter(vm: &mut VM, iterable: &Object, kind: IterKind) -> RuntimeResult {
+   match make_iter(iterable.clone()) {
+      Ok(iter) => vm.push_stack(Object::Iter(Rc::new(RefCell::new(IterObject::new(iter, kind))))),
+      Err(e) => e,
+   }
+}
+
+/// Creates an iterator adapter over the iterators of several iterable objects.
+fn adapt_iters(vm: &mut VM, iterables: Vec<Object>, kind: IterKind) -> RuntimeResult {
+   let mut iters = Vec::with_capacity(iterables.len());
+
+   for iterable in iterables {
+      match make_iter(iterable) {
+         Ok(iter) => iters.push(iter),
+         Err(e) => return e,
+      }
+   }
+
+   let iters = Object::Tuple(Rc::new(iters));
+   vm.push_stack(Object::Iter(Rc::new(RefCell::new(IterObject::new(iters, kind)))))
+}
+
+/// Gets the number of items taken (or skipped) by an iterator adapter.
+fn iter_count(name: &str, count: &Object) -> Result<usize, RuntimeResult> {
+   match count {
+      Object::Int(i) if *i >= 0 => Ok(*i as usize),
+      Object::Int(i) => Err(RuntimeResult::Error {
+         error: RuntimeErrorType::ArgumentError,
+         message: format!(
+            "The count of '{}' cannot be negative. Found '{}' instead.",
+            name, i
+         ),
+      }),
+      _ => Err(RuntimeResult::Error {
+         error: RuntimeErrorType::TypeError,
+         message: format!(
+            "The count of '{}' must be an Int. Found '{}' instead.",
+            name,
+            count.type_name()
+         ),
+      }),
+   }
+}
+
+/// Implements the `range(...)` native function for Hinton, which creates a range from a start
+/// (inclusive) to an end (exclusive), with an optional positive step (e.g., `range(0, 10, 2)`).
+fn native_range(vm: &mut VM, args: Vec<Object>) -> RuntimeResult {
+   let mut bounds = [0i64, 0, 1];
+
+   for (bound, arg) in bounds.iter_mut().zip(args.iter()) {
+      *bound = match arg {
+         Object::Int(i) => *i,
+         _ => {
+            return RuntimeResult::Error {
+               error: RuntimeErrorType::TypeError,
+               message: format!(
+                  "Function 'range' expected Int arguments. Found '{}' instead.",
+                  arg.type_name()
+               ),
+            }
+         }
+      };
+   }
+
+   match RangeObject::new(bounds[0], bounds[1]).with_step(bounds[2]) {
+      Some(range) => vm.push_stack(Object::Range(range)),
+      None => RuntimeResult::Error {
+         error: RuntimeErrorType::ArgumentError,
+         message: format!(
+            "The step of a range must be positive. Found '{}' instead.",
+            bounds[2]
+         ),
+      },
+   }
+}
+
+/// Implements the `BigInt(...)` native function for Hinton, which converts an integer,
+/// a float with an integer value, or a decimal string into a BigInt.
+fn native_big_int(vm: &mut VM, args: Vec<Object>) -> RuntimeResult {
+   let value = match &args[0] {
+      Object::BigInt(b) => Some((**b).clone()),
+      Object::Int(_) | Object::Bool(_) => args[0].as_int().map(BigInt::from),
+      Object::Float(f) if f.fract() == 0.0 && f.abs() < i64::MAX as f64 => Some(BigInt::from(*f as i64)),
+      Object::String(s) => BigInt::parse(s.trim()),
+      _ => None,
+   };
+
+   match value {
+      Some(b) => vm.push_stack(Object::from(b)),
+      None => RuntimeResult::Error {
+         error: RuntimeErrorType::TypeError,
+         message: format!("Cannot convert '{}' to a BigInt.", args[0].as_plain_string()),
+      },
+   }
+}
+
+/// Converts a float with an integer value into an Int.
+///
+/// # Returns
+/// - `Option<i64>`: The integer, or `None` if the float is not finite or does not fit in an Int.
+fn float_to_int(f: f64) -> Option<i64> {
+   // `i64::MAX as f64` is 2^63, which is one more than the largest Int.
+   if f.is_finite() && f >= i64::MIN as f64 && f < i64::MAX as f64 {
+      Some(f as i64)
+   } else {
+      None
+   }
+}
+
+/// Creates the error of a numeric conversion native that received a value that is not a number.
+fn not_a_number_error(fn_name: &str, value: &Object) -> RuntimeResult {
+   RuntimeResult::Error {
+      error: RuntimeErrorType::TypeError,
+      message: format!(
+         "Function '{}' expected a numeric argument. Found '{}' instead.",
+         fn_name,
+         value.type_name()
+      ),
+   }
+}
+
+/// Implements the `int(...)` native function for Hinton, which converts a number, a boolean,
+/// or a decimal string into an Int. Floats are truncated towards zero.
+fn native_int(vm: &mut VM, args: Vec<Object>) -> RuntimeResult {
+   let value = match &args[0] {
+      Object::Int(_) | Object::Bool(_) => args[0].as_int(),
+      Object::Float(f) => float_to_int(f.trunc()),
+      Object::BigInt(b) => b.to_i64(),
+      Object::String(s) => s.trim().parse::<i64>().ok(),
+      _ => None,
+   };
+
+   match value {
+      Some(i) => vm.push_stack(Object::Int(i)),
+      None => RuntimeResult::Error {
+         error: RuntimeErrorType::TypeError,
+         message: format!("Cannot convert '{}' to an Int.", args[0].as_plain_string()),
+      },
+   }
+}
+
+/// Implements the `float(...)` native function for Hinton, which converts a number, a boolean,
+/// or a decimal string into a Float.
+fn native_float(vm: &mut VM, args: Vec<Object>) -> RuntimeResult {
+   let value = match &args[0] {
+      Object::Float(f) => Some(*f),
+      Object::Int(_) | Object::Bool(_) => args[0].as_int().map(|i| i as f64),
+      Object::BigInt(b) => Some(b.to_f64()),
+      Object::String(s) => s.trim().parse::<f64>().ok(),
+      _ => None,
+   };
+
+   match value {
+      Some(f) => vm.push_stack(Object::Float(f)),
+      None => RuntimeResult::Error {
+         error: RuntimeErrorType::TypeError,
+         message: format!("Cannot convert '{}' to a Float.", args[0].as_plain_string()),
+      },
+   }
+}
+
+/// Implements the `round(...)` native function for Hinton, which rounds a number to the nearest
+/// integer (with halfway values rounded away from zero) and returns it as an Int. With a number
+/// of digits, a float is rounded to that many decimal places and is returned as a Float, while
+/// Ints are only rounded when the number of digits is negative (e.g., `round(1250, -2)`).
+fn native_round(vm: &mut VM, args: Vec<Object>) -> RuntimeResult {
+   let digits = match args.get(1) {
+      None => None,
+      Some(Object::Int(d)) => Some(*d),
+      Some(d) => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::TypeError,
+            message: format!(
+               "The number of digits of 'round' must be an Int. Found '{}' instead.",
+               d.type_name()
+            ),
+         }
+      }
+   };
+
+   // Rounds a float to a number of decimal places, which may be negative.
+   let round_to = |f: f64, digits: i64| {
+      let scale = 10f64.powi(digits.clamp(-308, 308) as i32);
+      (f * scale).round() / scale
+   };
+
+   let result = match (&args[0], digits) {
+      (Object::Int(i), Some(d)) if d < 0 => float_to_int(round_to(*i as f64, d)).map(Object::Int),
+      (Object::Int(_) | Object::BigInt(_) | Object::Bool(_), _) => Some(args[0].clone()),
+      (Object::Float(f), None) => float_to_int(f.round()).map(Object::Int),
+      (Object::Float(f), Some(d)) => Some(Object::Float(round_to(*f, d))),
+      _ => return not_a_number_error("round", &args[0]),
+   };
+
+   match result {
+      Some(Object::Bool(b)) => vm.push_stack(Object::Int(b as i64)),
+      Some(value) => vm.push_stack(value),
+      None => RuntimeResult::Error {
+         error: RuntimeErrorType::TypeError,
+         message: format!("Cannot round '{}' to an Int.", args[0].as_plain_string()),
+      },
+   }
+}
+
+/// Implements the `truncate(...)` native function for Hinton, which removes the fractional part
+/// of a number (rounding it towards zero) and returns it as an Int. Integers are returned
+/// unchanged.
+fn native_truncate(vm: &mut VM, args: Vec<Object>) -> RuntimeResult {
+   let result = match &args[0] {
+      Object::Int(_) | Object::BigInt(_) => Some(args[0].clone()),
+      Object::Bool(b) => Some(Object::Int(*b as i64)),
+      Object::Float(f) => float_to_int(f.trunc()).map(Object::Int),
+      _ => return not_a_number_error("truncate", &args[0]),
+   };
+
+   match result {
+      Some(value) => vm.push_stack(value),
+      None => RuntimeResult::Error {
+         error: RuntimeErrorType::TypeError,
+         message: format!("Cannot truncate '{}' to an Int.", args[0].as_plain_string()),
+      },
+   }
+}
+
+/// Implements the `Bytes(...)` native function for Hinton, which creates a Bytes object from
+/// an array of integers from 0 to 255, from a string with an optional encoding (`utf-8` by
+/// default), or filled with a number of zero bytes (or an empty Bytes object, without arguments).
+fn native_bytes(vm: &mut VM, args: Vec<Object>) -> RuntimeResult {
+   let bytes = match (args.first(), args.get(1)) {
+      (None, _) => Ok(vec![]),
+      (Some(Object::String(s)), None) => bytes_obj::encode(s, "utf-8"),
+      (Some(Object::String(s)), Some(Object::String(encoding))) => bytes_obj::encode(s, encoding),
+      (Some(_), Some(_)) => Err(String::from(
+         "An encoding can only be given when creating Bytes from a String.",
+      )),
+      (Some(Object::Int(n)), None) if *n >= 0 => Ok(vec![0; *n as usize]),
+      (Some(Object::Bytes(b)), None) => Ok(b.borrow().clone()),
+      (Some(Object::Array(arr)), None) => bytes_obj::from_int_array(&arr.borrow())
+         .ok_or_else(|| String::from("Bytes can only be created from an Array of Ints from 0 to 255.")),
+      (Some(obj), None) => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::TypeError,
+            message: format!(
+               "Cannot create Bytes from an object of type '{}'.",
+               obj.type_name()
+            ),
+         }
+      }
+   };
+
+   match bytes {
+      Ok(bytes) => vm.push_stack(Object::from(bytes)),
+      Err(message) => RuntimeResult::Error {
+         error: RuntimeErrorType::ArgumentError,
+         message,
+      },
+   }
+}
+
+/// Implements the `Set(...)` native function for Hinton, which creates a set
+/// with the items of an iterable object (or an empty set, without arguments).
+fn native_set(vm: &mut VM, args: Vec<Object>) -> RuntimeResult {
+   let mut items = vec![];
+
+   if let Some(iterable) = args.first() {
+      let iter = match make_iter_object(iterable.clone()) {
+         Ok(iter) => iter,
+         Err(e) => return e,
+      };
+
+      loop {
+         match get_next_in_iter(vm, &iter) {
+            Ok(item) => items.push(item),
+            Err(e) if is_end_of_iter(&e) => break,
+            Err(e) => return e,
+         }
+      }
+   }
+
+   match SetObject::from_items(items) {
+      Ok(set) => vm.push_stack(Object::Set(Rc::new(RefCell::new(set)))),
+      Err(e) => e.to_runtime_error(),
+   }
+}
+
+/// Implements the `Channel()` native function for Hinton, which creates an empty channel
+/// through which green threads send values to each other.
+fn native_channel(vm: &mut VM, _: Vec<Object>) -> RuntimeResult {
+   let channel = vm.alloc_channel();
+   vm.push_stack(channel)
+}
+
+/// Implements the `spawn(...)` native function for Hinton, which starts a green thread that
+/// calls a function with the rest of the arguments. The thread runs once the running thread
+/// sleeps, waits for a value from a channel, or ends.
+fn native_spawn(vm: &mut VM, mut args: Vec<Object>) -> RuntimeResult {
+   let callee = args.remove(0);
+
+   if !matches!(
+      callee,
+      Object::Function(_)
+         | Object::Closure(_)
+         | Object::BoundMethod(_)
+         | Object::Native(_)
+         | Object::BoundNativeMethod(_)
+   ) {
+      return RuntimeResult::Error {
+         error: RuntimeErrorType::TypeError,
+         message: format!(
+            "Function 'spawn' expected a function argument. Found '{}' instead.",
+            callee.type_name()
+         ),
+      };
+   }
+
+   vm.spawn_thread(callee, args);
+   vm.push_stack(Object::Null)
+}
+
+/// Implements the `sleep(...)` native function for Hinton, which pauses the running green
+/// thread for the given number of milliseconds, so that other green threads can run.
+/// Sleeping for `0` milliseconds lets the other threads that are ready run first.
+fn native_sleep(vm: &mut VM, args: Vec<Object>) -> RuntimeResult {
+   match to_duration("sleep", &args[0]) {
+      Ok(duration) => vm.sleep_thread(duration),
+      Err(error) => error,
+   }
+}
+
+/// Implements the `load_plugin(...)` native function for Hinton, which loads a native plugin
+/// from a dynamic library, and returns a namespace with the functions of the plugin.
+fn native_load_plugin(vm: &mut VM, args: Vec<Object>) -> RuntimeResult {
+   let path = match &args[0] {
+      Object::String(path) => path.clone(),
+      arg => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::TypeError,
+            message: format!(
+               "Function 'load_plugin' expected a String as the path. Found '{}' instead.",
+               arg.type_name()
+            ),
+         }
+      }
+   };
+
+   match plugins::load_plugin(Path::new(&*path)) {
+      Ok(plugin) => vm.push_stack(Object::from(plugin)),
+      Err(message) => RuntimeResult::Error {
+         error: RuntimeErrorType::IOError,
+         message,
+      },
+   }
+}
+
+/// Implements the `delay(...)` native function for Hinton, which returns a promise that
+/// resolves with `null` once the given number of milliseconds have passed. Unlike `sleep`,
+/// the running green thread keeps running until it awaits the promise.
+fn native_delay(vm: &mut VM, args: Vec<Object>) -> RuntimeResult {
+   let duration = match to_duration("delay", &args[0]) {
+      Ok(d) => d,
+      Err(error) => return error,
+   };
+
+   let now = match vm.now() {
+      Ok(now) => now,
+      Err(e) => return e,
+   };
+
+   let promise = vm.promise_event(Box::new(Timer(now + duration)));
+   vm.push_stack(promise)
+}
+
+/// Converts an argument of a native function into a duration, in milliseconds.
+///
+/// # Parameters
+/// - `fn_name`: The name of the native function.
+/// - `ms`: The number of milliseconds.
+fn to_duration(fn_name: &str, ms: &Object) -> Result<Duration, RuntimeResult> {
+   let ms = match ms {
+      Object::Int(i) => *i as f64,
+      Object::Float(f) => *f,
+      _ => return Err(not_a_number_error(fn_name, ms)),
+   };
+
+   if !ms.is_finite() || ms < 0.0 {
+      return Err(RuntimeResult::Error {
+         error: RuntimeErrorType::ArgumentError,
+         message: format!(
+            "Function '{}' expected a non-negative number of milliseconds.",
+            fn_name
+         ),
+      });
+   }
+
+   Ok(Duration::from_secs_f64(ms / 1000.0))
+}
+
+/// Reads a line of text from the input of the virtual machine, without the line break.
+///
+/// # Returns
+/// - `io::Result<Option<String>>`: The line, or `None` at the end of the input.
+fn read_input_line(vm: &mut VM) -> io::Result<Option<String>> {
+   let mut line = String::new();
+
+   let read = match vm.input.as_mut() {
+      Some(input) => input.read_line(&mut line)?,
+      None => io::stdin().read_line(&mut line)?,
+   };
+
+   if read == 0 {
+      return Ok(None);
+   }
+
+   if line.ends_with('\n') {
+      line.pop();
+      if line.ends_with('\r') {
+         line.pop();
+      }
+   }
+
+   Ok(Some(line))
+}
+
+/// Pushes a line read from the input of the virtual machine onto the stack,
+/// or `null` at the end of the input.
+fn push_input_line(vm: &mut VM) -> RuntimeResult {
+   match read_input_line(vm) {
+      Ok(Some(line)) => vm.push_stack(Object::from(line)),
+      Ok(None) => vm.push_stack(Object::Null),
+      Err(e) => RuntimeResult::Error {
+         error: RuntimeErrorType::IOError,
+         message: format!("Failed to read input. {}", e),
+      },
+   }
+}
+
+/// Implements the `input(...)` native function for Hinton, which prints an optional prompt
+/// and reads a line of user input from the console. Returns `null` at the end of the input.
+fn native_input(vm: &mut VM, args: Vec<Object>) -> RuntimeResult {
+   if let Some(prompt) = args.first() {
+      let printed = write!(vm.stdout, "{}", prompt.as_plain_string()).and_then(|_| vm.stdout.flush());
+
+      if let Err(e) = printed {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::IOError,
+            message: format!("Failed to print the prompt. {}", e),
+         };
+      }
+   }
+
+   push_input_line(vm)
+}
+
+/// Implements the `read_line()` native function for Hinton, which reads a line
+/// of text from the standard input. Returns `null` at the end of the input.
+fn native_read_line(vm: &mut VM, _: Vec<Object>) -> RuntimeResult {
+   push_input_line(vm)
+}
+
+/// Implements the `read_all()` native function for Hinton, which reads the rest of the
+/// standard input into a single string. Returns `null` if the input had already ended.
+fn native_read_all(vm: &mut VM, _: Vec<Object>) -> RuntimeResult {
+   let mut text = String::new();
+
+   let read = match vm.input.as_mut() {
+      Some(input) => input.read_to_string(&mut text),
+      None => io::Read::read_to_string(&mut io::stdin(), &mut text),
+   };
+
+   match read {
+      Ok(0) => vm.push_stack(Object::Null),
+      Ok(_) => vm.push_stack(Object::from(text)),
+      Err(e) => RuntimeResult::Error {
+         error: RuntimeErrorType::IOError,
+         message: format!("Failed to read input. {}", e),
+      },
+   }
+}
+
+// Implements the `assert_eq(...)` native function for Hinton, which checks that
+// the first two arguments of the function call are equal, emitting a RuntimeError
+// (with an optional third parameter as its message) if the values are not equal.
+// The error message shows both values, and where they first differ.
+fn native_assert_eq(vm: &mut VM, args: Vec<Object>) -> RuntimeResult {
+   let value1 = args[0].clone();
+   let value2 = args[1].clone();
+
+   if value1 == value2 {
+      vm.push_stack(Object::Null)
+   } else {
+      let message = match args.get(2) {
+         Some(message) => message.as_plain_string(),
+         None => String::from("Assertion values are not equal."),
+      };
+
+      RuntimeResult::Error {
+         error: RuntimeErrorType::AssertionError,
+         message: format!("{}\n{}", message, assertion_diff(&value1, &value2)),
+      }
+   }
+}
+
+// Implements the `assert_ne(...)` native function for Hinton, which checks that
+// the first two arguments of the function call are not equal, emitting a RuntimeError
+// (with an optional third parameter as its message) if the values are equal.
+fn native_assert_ne(vm: &mut VM, args: Vec<Object>) -> RuntimeResult {
+   let value1 = args[0].clone();
+   let value2 = args[1].clone();
+
+   if value1 != value2 {
+      vm.push_stack(Object::Null)
+   } else {
+      let message = match args.get(2) {
+         Some(message) => message.as_plain_string(),
+         None => String::from("Assertion values are equal."),
+      };
+
+      RuntimeResult::Error {
+         error: RuntimeErrorType::AssertionError,
+         message: format!("{}\n  both: {}", message, value1.as_plain_string()),
+      }
+   }
+}
+
+/// Describes the difference between the two values of a failed `assert_eq(...)` call. When
+/// both values look the same when printed, their types are shown as well. For strings and
+/// arrays, the index of the first item that differs is also shown.
+fn assertion_diff(left: &Object, right: &Object) -> String {
+   let (mut left_str, mut right_str) = (left.as_plain_string(), right.as_plain_string());
+
+   if left_str == right_str {
+      left_str = format!("{} ({})", left_str, left.type_name());
+      right_str = format!("{} ({})", right_str, right.type_name());
+   }
+
+   let first_diff = match (left, right) {
+      (Object::String(a), Object::String(b)) => Some(first_difference(
+         &a.chars().collect::<Vec<_>>(),
+         &b.chars().collect::<Vec<_>>(),
+      )),
+      (Object::Array(a), Object::Array(b)) => Some(first_difference(&a.borrow(), &b.borrow())),
+      _ => None,
+   };
+
+   let mut diff = format!("  left:  {}\n  right: {}", left_str, right_str);
+
+   if let Some(idx) = first_diff {
+      diff.push_str(&format!("\n  first difference at index {}.", idx));
+   }
+
+   diff
+}
+
+/// Gets the index of the first item that differs between two sequences.
+fn first_difference<T: PartialEq>(a: &[T], b: &[T]) -> usize {
+   a.iter().zip(b.iter()).take_while(|(x, y)| x == y).count()
+}
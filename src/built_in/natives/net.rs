@@ -0,0 +1,98 @@
+use crate::built_in::primitives::HTPrimitive;
+use crate::built_in::NativeBoundMethod;
+use crate::errors::RuntimeErrorType;
+use crate::objects::class_obj::{ClassField, ClassObject};
+use crate::objects::socket_obj::SocketObject;
+use crate::objects::Object;
+use crate::virtual_machine::{RuntimeResult, VM};
+use hashbrown::HashMap;
+use std::cell::RefCell;
+use std::convert::TryFrom;
+use std::net::TcpStream;
+use std::rc::Rc;
+
+/// Represents the Hinton `Net` namespace, whose static members are the native
+/// functions used to open raw TCP connections.
+pub struct NetClass(ClassObject);
+
+/// Implements the `HTPrimitive` trait for the `Net` namespace.
+impl HTPrimitive for NetClass {
+   /// Gets the name class name of this Hinton primitive.
+   fn name(&self) -> String {
+      self.0.name.clone()
+   }
+
+   /// Gets the non-static members of this Hinton primitive.
+   fn members(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.members
+   }
+
+   /// Gets the static members of this Hinton primitive.
+   fn statics(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.statics
+   }
+
+   /// Gets the default class object stored in this Hinton primitive.
+   fn default() -> ClassObject {
+      let mut _self = NetClass(ClassObject::new("Net"));
+
+      // >>>>>>> Static fields for the "Net" namespace to be added after this line
+      _self.bind_static_method("connect", (2, 2), connect as NativeBoundMethod);
+      // <<<<<<< Static fields for the "Net" namespace to be added before this line
+
+      _self.0
+   }
+}
+
+/// Opens a TCP connection to the given host and port, and returns it as a Socket object.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Net` namespace (unused).
+/// * `args`: The host name (or IP address), and the port.
+///
+/// # Returns:
+/// RuntimeResult
+fn connect(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   let host = match &args[0] {
+      Object::String(s) => s.clone(),
+      arg => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::TypeError,
+            message: format!(
+               "Function 'Net.connect' expected a String as the host. Found '{}' instead.",
+               arg.type_name()
+            ),
+         }
+      }
+   };
+
+   let port = match &args[1] {
+      Object::Int(p) => match u16::try_from(*p) {
+         Ok(p) => p,
+         Err(_) => {
+            return RuntimeResult::Error {
+               error: RuntimeErrorType::ArgumentError,
+               message: format!("Port {} is out of range. Ports go from 0 to 65535.", p),
+            }
+         }
+      },
+      arg => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::TypeError,
+            message: format!(
+               "Function 'Net.connect' expected an Int as the port. Found '{}' instead.",
+               arg.type_name()
+            ),
+         }
+      }
+   };
+
+   match TcpStream::connect((&*host, port)) {
+      Ok(stream) => vm.push_stack(Object::Socket(Rc::new(RefCell::new(SocketObject::new(stream))))),
+      Err(e) => RuntimeResult::Error {
+         error: RuntimeErrorType::IOError,
+         message: format!("Could not connect to '{}:{}'. {}.", host, port, e),
+      },
+   }
+}
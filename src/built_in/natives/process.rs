@@ -0,0 +1,286 @@
+use crate::built_in::primitives::HTPrimitive;
+use crate::built_in::NativeBoundMethod;
+use crate::errors::RuntimeErrorType;
+use crate::objects::class_obj::{ClassField, ClassObject};
+use crate::objects::dict_obj::DictObject;
+use crate::objects::Object;
+use crate::virtual_machine::{RuntimeResult, VM};
+use hashbrown::HashMap;
+use std::convert::TryFrom;
+use std::env;
+use std::io::Write;
+use std::process::Command;
+
+/// Represents the Hinton `Process` namespace, whose static members are the native functions
+/// used to access the program's arguments and environment, and to spawn subprocesses.
+pub struct ProcessClass(ClassObject);
+
+/// Implements the `HTPrimitive` trait for the `Process` namespace.
+impl HTPrimitive for ProcessClass {
+   /// Gets the name class name of this Hinton primitive.
+   fn name(&self) -> String {
+      self.0.name.clone()
+   }
+
+   /// Gets the non-static members of this Hinton primitive.
+   fn members(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.members
+   }
+
+   /// Gets the static members of this Hinton primitive.
+   fn statics(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.statics
+   }
+
+   /// Gets the default class object stored in this Hinton primitive.
+   fn default() -> ClassObject {
+      let mut _self = ProcessClass(ClassObject::new("Process"));
+
+      // >>>>>>> Static fields for the "Process" namespace to be added after this line
+      _self.bind_static_method("args", (0, 0), args as NativeBoundMethod);
+      _self.bind_static_method("cwd", (0, 0), cwd as NativeBoundMethod);
+      _self.bind_static_method("env", (1, 1), env as NativeBoundMethod);
+      _self.bind_static_method("env_vars", (0, 0), env_vars as NativeBoundMethod);
+      _self.bind_static_method("exit", (0, 1), exit as NativeBoundMethod);
+      _self.bind_static_method("pid", (0, 0), pid as NativeBoundMethod);
+      _self.bind_static_method("run", (1, 2), run as NativeBoundMethod);
+      _self.bind_static_method("set_env", (2, 2), set_env as NativeBoundMethod);
+      // <<<<<<< Static fields for the "Process" namespace to be added before this line
+
+      _self.0
+   }
+}
+
+macro_rules! string_arg {
+   ($arg: expr, $fn_name: expr) => {
+      match &$arg {
+         Object::String(s) => &**s,
+         _ => {
+            return RuntimeResult::Error {
+               error: RuntimeErrorType::TypeError,
+               message: format!(
+                  "Function 'Process.{}' expected a String argument. Found '{}' instead.",
+                  $fn_name,
+                  $arg.type_name()
+               ),
+            }
+         }
+      }
+   };
+}
+
+/// Gets the command-line arguments passed to the program after the path of the script.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Process` namespace (unused).
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn args(vm: &mut VM, _: Object, _: Vec<Object>) -> RuntimeResult {
+   let args = vm.script_args.iter().map(|a| Object::from(a.as_str())).collect();
+   let args = vm.alloc_array(args);
+   vm.push_stack(args)
+}
+
+/// Gets the path of the current working directory.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Process` namespace (unused).
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn cwd(vm: &mut VM, _: Object, _: Vec<Object>) -> RuntimeResult {
+   match env::current_dir() {
+      Ok(dir) => vm.push_stack(Object::from(dir.to_string_lossy().to_string())),
+      Err(err) => RuntimeResult::Error {
+         error: RuntimeErrorType::IOError,
+         message: format!("Could not get the current working directory. {}", err),
+      },
+   }
+}
+
+/// Gets the value of an environment variable, or `null` if the variable is not set.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Process` namespace (unused).
+/// * `args`: The name of the environment variable.
+///
+/// # Returns:
+/// RuntimeResult
+fn env(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   match env::var(string_arg!(args[0], "env")) {
+      Ok(value) => vm.push_stack(Object::from(value)),
+      Err(_) => vm.push_stack(Object::Null),
+   }
+}
+
+/// Gets a dictionary with every environment variable of the process, sorted by name.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Process` namespace (unused).
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn env_vars(vm: &mut VM, _: Object, _: Vec<Object>) -> RuntimeResult {
+   let mut vars: Vec<(String, String)> = env::vars_os()
+      .map(|(k, v)| (k.to_string_lossy().to_string(), v.to_string_lossy().to_string()))
+      .collect();
+   vars.sort();
+
+   let vars: DictObject = vars.into_iter().map(|(k, v)| (k, Object::from(v))).collect();
+   let vars = vm.alloc_dict(vars);
+   vm.push_stack(vars)
+}
+
+/// Sets the value of an environment variable of the process, which
+/// is inherited by the subprocesses spawned with `Process.run`.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Process` namespace (unused).
+/// * `args`: The name and the value of the environment variable.
+///
+/// # Returns:
+/// RuntimeResult
+fn set_env(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   let name = string_arg!(args[0], "set_env");
+   let value = string_arg!(args[1], "set_env");
+
+   if name.is_empty() || name.contains('=') || name.contains('\0') || value.contains('\0') {
+      return RuntimeResult::Error {
+         error: RuntimeErrorType::ArgumentError,
+         message: format!("Invalid environment variable '{}'.", name),
+      };
+   }
+
+   env::set_var(name, value);
+   vm.push_stack(Object::Null)
+}
+
+/// Terminates the program with an exit code, which defaults to 0.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Process` namespace (unused).
+/// * `args`: The optional exit code.
+///
+/// # Returns:
+/// RuntimeResult
+fn exit(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   let code = match args.first() {
+      None => 0,
+      Some(Object::Int(code)) if i32::try_from(*code).is_ok() => *code as i32,
+      Some(obj) => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::TypeError,
+            message: format!(
+               "Function 'Process.exit' expected an Int exit code. Found '{}' instead.",
+               obj.type_name()
+            ),
+         }
+      }
+   };
+
+   // The output printed by the program is flushed, since exiting does not unwind the stack.
+   let _ = vm.stdout.flush();
+   let _ = vm.stderr.flush();
+   std::process::exit(code)
+}
+
+/// Gets the ID of the process.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Process` namespace (unused).
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn pid(vm: &mut VM, _: Object, _: Vec<Object>) -> RuntimeResult {
+   vm.push_stack(Object::Int(std::process::id() as i64))
+}
+
+/// Runs a command in a subprocess and waits for it to finish. The result is a dictionary
+/// with the exit `code` of the subprocess (or `null` if it was terminated by a signal),
+/// and the `stdout` and `stderr` output it produced.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Process` namespace (unused).
+/// * `args`: The command, and an optional array with the arguments of the command.
+///
+/// # Returns:
+/// RuntimeResult
+fn run(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   let program = string_arg!(args[0], "run");
+
+   let command_args: Vec<String> = match args.get(1) {
+      None => vec![],
+      Some(Object::Array(arr)) => {
+         let arr = arr.borrow();
+         let strings: Option<Vec<String>> = arr
+            .iter()
+            .map(|a| match a {
+               Object::String(s) => Some(s.to_string()),
+               _ => None,
+            })
+            .collect();
+
+         match strings {
+            Some(s) => s,
+            None => {
+               return RuntimeResult::Error {
+                  error: RuntimeErrorType::TypeError,
+                  message: String::from("The arguments of 'Process.run' must be an Array of Strings."),
+               }
+            }
+         }
+      }
+      Some(obj) => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::TypeError,
+            message: format!(
+               "The arguments of 'Process.run' must be an Array of Strings. Found '{}' instead.",
+               obj.type_name()
+            ),
+         }
+      }
+   };
+
+   let output = match Command::new(program).args(&command_args).output() {
+      Ok(output) => output,
+      Err(err) => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::IOError,
+            message: format!("Could not run '{}'. {}", program, err),
+         }
+      }
+   };
+
+   let mut result = DictObject::default();
+   result.insert(
+      String::from("code"),
+      match output.status.code() {
+         Some(code) => Object::Int(code as i64),
+         None => Object::Null,
+      },
+   );
+   result.insert(
+      String::from("stdout"),
+      Object::from(String::from_utf8_lossy(&output.stdout).to_string()),
+   );
+   result.insert(
+      String::from("stderr"),
+      Object::from(String::from_utf8_lossy(&output.stderr).to_string()),
+   );
+
+   let result = vm.alloc_dict(result);
+   vm.push_stack(result)
+}
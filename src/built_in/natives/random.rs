@@ -0,0 +1,350 @@
+use crate::built_in::primitives::HTPrimitive;
+use crate::built_in::NativeBoundMethod;
+use crate::errors::RuntimeErrorType;
+use crate::objects::bytes_obj;
+use crate::objects::class_obj::{ClassField, ClassObject};
+use crate::objects::Object;
+use crate::virtual_machine::{RuntimeResult, VM};
+use hashbrown::HashMap;
+use std::cell::{Cell, RefCell};
+#[cfg(unix)]
+use std::fs::File;
+use std::io;
+#[cfg(unix)]
+use std::io::Read;
+use std::rc::Rc;
+use std::time::SystemTime;
+
+/// Represents the Hinton `Random` namespace, whose static members are the native functions
+/// that generate pseudo-random numbers from a seedable generator, so that programs can
+/// reproduce their random sequences.
+pub struct RandomClass(ClassObject);
+
+/// Implements the `HTPrimitive` trait for the `Random` namespace.
+impl HTPrimitive for RandomClass {
+   /// Gets the name class name of this Hinton primitive.
+   fn name(&self) -> String {
+      self.0.name.clone()
+   }
+
+   /// Gets the non-static members of this Hinton primitive.
+   fn members(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.members
+   }
+
+   /// Gets the static members of this Hinton primitive.
+   fn statics(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.statics
+   }
+
+   /// Gets the default class object stored in this Hinton primitive.
+   fn default() -> ClassObject {
+      let mut _self = RandomClass(ClassObject::new("Random"));
+
+      // >>>>>>> Static fields for the "Random" namespace to be added after this line
+      _self.bind_static_method("choice", (1, 1), choice as NativeBoundMethod);
+      _self.bind_static_method("float", (0, 0), float as NativeBoundMethod);
+      _self.bind_static_method("int", (2, 2), int as NativeBoundMethod);
+      _self.bind_static_method("seed", (1, 1), seed as NativeBoundMethod);
+      _self.bind_static_method("shuffle", (1, 1), shuffle as NativeBoundMethod);
+      _self.bind_static_method("token", (1, 1), token as NativeBoundMethod);
+      // <<<<<<< Static fields for the "Random" namespace to be added before this line
+
+      _self.0
+   }
+}
+
+thread_local! {
+   /// The state of the generator used by the `Random` namespace, which is seeded from the
+   /// system's time when a program uses the generator without calling `Random.seed(...)`.
+   static STATE: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+/// The largest number of bytes in a token generated by `Random.token(...)`.
+const MAX_TOKEN_BYTES: i64 = 1024;
+
+/// Seeds the generator from the system's time, unless the program has already seeded it.
+/// Called by the functions of the namespace before they generate a value.
+///
+/// # Arguments
+/// * `vm`: A reference to the virtual machine, whose clock is read.
+///
+/// # Returns:
+/// Result<(), RuntimeResult>
+fn seed_from_clock(vm: &VM) -> Result<(), RuntimeResult> {
+   if STATE.with(|state| state.get().is_none()) {
+      let seed = match vm.system_time()?.duration_since(SystemTime::UNIX_EPOCH) {
+         Ok(t) => t.as_nanos() as u64,
+         Err(_) => 0,
+      };
+
+      STATE.with(|state| state.set(Some(seed)));
+   }
+
+   Ok(())
+}
+
+/// Generates the next pseudo-random 64-bit value, using a SplitMix64 generator. The generator
+/// produces the same sequence for the same seed on every platform, but it is not suitable for
+/// cryptographic purposes.
+fn next_u64() -> u64 {
+   STATE.with(|state| {
+      let seed = state.get().unwrap_or_default();
+      let next = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+      state.set(Some(next));
+
+      let mut z = next;
+      z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+      z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+      z ^ (z >> 31)
+   })
+}
+
+/// Reads random bytes from the operating system, which are unpredictable and independent
+/// of the seed of the `Random` namespace. Used to generate identifiers and secrets.
+///
+/// # Arguments
+/// * `len`: The number of bytes to read.
+///
+/// # Returns:
+/// io::Result<Vec<u8>>
+#[cfg(unix)]
+pub(crate) fn system_random_bytes(len: usize) -> io::Result<Vec<u8>> {
+   let mut bytes = vec![0; len];
+   File::open("/dev/urandom")?.read_exact(&mut bytes)?;
+   Ok(bytes)
+}
+
+/// Reads random bytes from the operating system, through the random keys that the standard
+/// library obtains from the operating system for its hash maps.
+///
+/// # Arguments
+/// * `len`: The number of bytes to read.
+///
+/// # Returns:
+/// io::Result<Vec<u8>>
+#[cfg(not(unix))]
+pub(crate) fn system_random_bytes(len: usize) -> io::Result<Vec<u8>> {
+   use std::collections::hash_map::RandomState;
+   use std::hash::{BuildHasher, Hasher};
+
+   let mut bytes = Vec::with_capacity(len + 8);
+   while bytes.len() < len {
+      let mut hasher = RandomState::new().build_hasher();
+      hasher.write_usize(bytes.len());
+      bytes.extend_from_slice(&hasher.finish().to_le_bytes());
+   }
+
+   bytes.truncate(len);
+   Ok(bytes)
+}
+
+/// Generates a pseudo-random index in the interval `[0, len)`.
+fn next_index(len: usize) -> usize {
+   ((next_u64() as u128 * len as u128) >> 64) as usize
+}
+
+/// Converts the argument of a `Random` function into a Rust i64 integer.
+///
+/// # Arguments
+/// * `arg`: The argument passed to the function.
+/// * `fn_name`: The name of the function.
+///
+/// # Returns:
+/// Result<i64, RuntimeResult>
+fn to_int(arg: &Object, fn_name: &str) -> Result<i64, RuntimeResult> {
+   match arg {
+      Object::Int(i) => Ok(*i),
+      _ => Err(RuntimeResult::Error {
+         error: RuntimeErrorType::TypeError,
+         message: format!(
+            "Function 'Random.{}' expected Int arguments. Found '{}' instead.",
+            fn_name,
+            arg.type_name()
+         ),
+      }),
+   }
+}
+
+/// Converts the argument of a `Random` function into a Hinton array.
+///
+/// # Arguments
+/// * `arg`: The argument passed to the function.
+/// * `fn_name`: The name of the function.
+///
+/// # Returns:
+/// Result<Rc<RefCell<Vec<Object>>>, RuntimeResult>
+fn to_array(arg: &Object, fn_name: &str) -> Result<Rc<RefCell<Vec<Object>>>, RuntimeResult> {
+   match arg {
+      Object::Array(a) => Ok(a.clone()),
+      _ => Err(RuntimeResult::Error {
+         error: RuntimeErrorType::TypeError,
+         message: format!(
+            "Function 'Random.{}' expected an Array. Found '{}' instead.",
+            fn_name,
+            arg.type_name()
+         ),
+      }),
+   }
+}
+
+/// Seeds the generator, so that the numbers generated after this call are the same every
+/// time the program runs.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Random` namespace (unused).
+/// * `args`: The seed.
+///
+/// # Returns:
+/// RuntimeResult
+fn seed(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   let seed = match to_int(&args[0], "seed") {
+      Ok(s) => s,
+      Err(e) => return e,
+   };
+
+   STATE.with(|state| state.set(Some(seed as u64)));
+   vm.push_stack(Object::Null)
+}
+
+/// Generates a pseudo-random integer in the interval `[a, b]`.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Random` namespace (unused).
+/// * `args`: The lower and upper bounds (both inclusive).
+///
+/// # Returns:
+/// RuntimeResult
+fn int(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   let (lower, upper) = match (to_int(&args[0], "int"), to_int(&args[1], "int")) {
+      (Ok(a), Ok(b)) => (a, b),
+      (Err(e), _) | (_, Err(e)) => return e,
+   };
+
+   if lower > upper {
+      return RuntimeResult::Error {
+         error: RuntimeErrorType::ArgumentError,
+         message: String::from("The lower bound of 'Random.int' cannot be greater than its upper bound."),
+      };
+   }
+
+   if let Err(e) = seed_from_clock(vm) {
+      return e;
+   }
+
+   // Scales the value to the size of the interval, which is at most 2^64.
+   let span = (upper as i128 - lower as i128 + 1) as u128;
+   let offset = (next_u64() as u128 * span) >> 64;
+   vm.push_stack(Object::Int((lower as i128 + offset as i128) as i64))
+}
+
+/// Generates a pseudo-random float in the interval `[0, 1)`.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Random` namespace (unused).
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn float(vm: &mut VM, _: Object, _: Vec<Object>) -> RuntimeResult {
+   if let Err(e) = seed_from_clock(vm) {
+      return e;
+   }
+
+   // Uses the top 53 bits of the value, which is the precision of a 64-bit float.
+   vm.push_stack(Object::Float((next_u64() >> 11) as f64 / (1u64 << 53) as f64))
+}
+
+/// Picks a pseudo-random element of an array.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Random` namespace (unused).
+/// * `args`: The array.
+///
+/// # Returns:
+/// RuntimeResult
+fn choice(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   let array = match to_array(&args[0], "choice") {
+      Ok(a) => a,
+      Err(e) => return e,
+   };
+
+   let len = array.borrow().len();
+   if len == 0 {
+      return RuntimeResult::Error {
+         error: RuntimeErrorType::IndexError,
+         message: String::from("Cannot choose an element from an empty array."),
+      };
+   }
+
+   if let Err(e) = seed_from_clock(vm) {
+      return e;
+   }
+
+   let element = array.borrow()[next_index(len)].clone();
+   vm.push_stack(element)
+}
+
+/// Shuffles the elements of an array in place, using the Fisher-Yates algorithm.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Random` namespace (unused).
+/// * `args`: The array.
+///
+/// # Returns:
+/// RuntimeResult
+fn shuffle(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   let array = match to_array(&args[0], "shuffle") {
+      Ok(a) => a,
+      Err(e) => return e,
+   };
+
+   if let Err(e) = seed_from_clock(vm) {
+      return e;
+   }
+
+   let mut elements = array.borrow_mut();
+   for i in (1..elements.len()).rev() {
+      elements.swap(i, next_index(i + 1));
+   }
+
+   drop(elements);
+   vm.push_stack(Object::Null)
+}
+
+/// Generates a random token of the given number of bytes, as a string of hexadecimal digits.
+/// Unlike the other functions of the namespace, the bytes come from the operating system, so
+/// tokens are unpredictable even after calling `Random.seed(...)`.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Random` namespace (unused).
+/// * `args`: The number of random bytes in the token.
+///
+/// # Returns:
+/// RuntimeResult
+fn token(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   let len = match to_int(&args[0], "token") {
+      Ok(n) if (1..=MAX_TOKEN_BYTES).contains(&n) => n as usize,
+      Ok(_) => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::ArgumentError,
+            message: format!("The size of a token must be from 1 to {} bytes.", MAX_TOKEN_BYTES),
+         }
+      }
+      Err(e) => return e,
+   };
+
+   match system_random_bytes(len) {
+      Ok(bytes) => vm.push_stack(Object::from(bytes_obj::to_hex(&bytes))),
+      Err(e) => RuntimeResult::Error {
+         error: RuntimeErrorType::IOError,
+         message: format!("Could not read random bytes from the system. {}.", e),
+      },
+   }
+}
@@ -0,0 +1,369 @@
+use crate::built_in::primitives::HTPrimitive;
+use crate::built_in::NativeBoundMethod;
+use crate::errors::RuntimeErrorType;
+use crate::objects::class_obj::{ClassField, ClassObject};
+use crate::objects::dict_obj::DictObject;
+use crate::objects::regex_obj::{Captures, RegexObject};
+use crate::objects::Object;
+use crate::virtual_machine::{RuntimeResult, VM};
+use hashbrown::HashMap;
+use std::rc::Rc;
+
+/// Represents the Hinton `Regex` namespace. Its static `compile` method creates regular
+/// expressions, and its non-static members are the methods of the compiled regular expressions.
+pub struct RegexClass(ClassObject);
+
+/// Implements the `HTPrimitive` trait for the `Regex` namespace.
+impl HTPrimitive for RegexClass {
+   /// Gets the name class name of this Hinton primitive.
+   fn name(&self) -> String {
+      self.0.name.clone()
+   }
+
+   /// Gets the non-static members of this Hinton primitive.
+   fn members(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.members
+   }
+
+   /// Gets the static members of this Hinton primitive.
+   fn statics(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.statics
+   }
+
+   /// Gets the default class object stored in this Hinton primitive.
+   fn default() -> ClassObject {
+      let mut _self = RegexClass(ClassObject::new("Regex"));
+
+      // >>>>>>> Static fields for the "Regex" namespace to be added after this line
+      _self.bind_static_method("compile", (1, 2), compile as NativeBoundMethod);
+      // <<<<<<< Static fields for the "Regex" namespace to be added before this line
+
+      // >>>>>>> Class fields for the "Regex" primitive type to be added after this line
+      _self.bind_non_static_method("find", (1, 1), find as NativeBoundMethod);
+      _self.bind_non_static_method("find_all", (1, 1), find_all as NativeBoundMethod);
+      _self.bind_non_static_method("replace", (2, 3), replace as NativeBoundMethod);
+      _self.bind_non_static_method("split", (1, 2), split as NativeBoundMethod);
+      _self.bind_non_static_method("test", (1, 1), test as NativeBoundMethod);
+      // <<<<<<< Class fields for the "Regex" primitive type to be added before this line
+
+      _self.0
+   }
+}
+
+macro_rules! verify_regex_object {
+   ($maybe_regex: expr, $prop_name: expr) => {
+      match $maybe_regex {
+         Object::Regex(r) => r,
+         _ => {
+            return RuntimeResult::Error {
+               error: RuntimeErrorType::TypeError,
+               message: format!(
+                  "Property 'Regex.{}' requires that 'self' be a Regex object. Found '{}' instead.",
+                  $prop_name,
+                  $maybe_regex.type_name()
+               ),
+            }
+         }
+      }
+   };
+}
+
+/// Gets the characters of a string argument, returning a `TypeError` from the
+/// enclosing method if the argument is not a string.
+macro_rules! text_arg {
+   ($arg: expr, $prop_name: expr) => {
+      match &$arg {
+         Object::String(s) => s.chars().collect::<Vec<char>>(),
+         obj => {
+            return RuntimeResult::Error {
+               error: RuntimeErrorType::TypeError,
+               message: format!(
+                  "Expected argument of type 'String' for call to 'Regex.{}'. Got '{}' instead.",
+                  $prop_name,
+                  obj.type_name()
+               ),
+            }
+         }
+      }
+   };
+}
+
+/// Gets the optional limit argument of a method, returning a `TypeError` from the
+/// enclosing method if the argument is not a non-negative integer.
+macro_rules! limit_arg {
+   ($arg: expr, $prop_name: expr) => {
+      match $arg {
+         None | Some(Object::Null) => None,
+         Some(Object::Int(i)) if *i >= 0 => Some(*i as usize),
+         Some(obj) => {
+            return RuntimeResult::Error {
+               error: RuntimeErrorType::TypeError,
+               message: format!(
+                  "Expected a non-negative Int as the limit of 'Regex.{}'. Got '{}' instead.",
+                  $prop_name,
+                  obj.type_name()
+               ),
+            }
+         }
+      }
+   };
+}
+
+/// Compiles a regular expression. The optional second argument is a string of flags:
+/// `i` (ignore case), `m` (`^` and `$` match at line breaks), and `s` (`.` matches line breaks).
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Regex` namespace (unused).
+/// * `args`: The pattern, and the optional flags.
+///
+/// # Returns:
+/// RuntimeResult
+fn compile(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   let (pattern, flags) = match (&args[0], args.get(1)) {
+      (Object::String(p), None) => (p.clone(), Rc::from("")),
+      (Object::String(p), Some(Object::String(f))) => (p.clone(), f.clone()),
+      (Object::String(_), Some(obj)) | (obj, _) => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::TypeError,
+            message: format!(
+               "Expected arguments of type 'String' for call to 'Regex.compile'. Got '{}' instead.",
+               obj.type_name()
+            ),
+         }
+      }
+   };
+
+   match RegexObject::new(&pattern, &flags) {
+      Ok(regex) => vm.push_stack(Object::Regex(Rc::new(regex))),
+      Err((pos, message)) => RuntimeResult::Error {
+         error: RuntimeErrorType::TypeError,
+         message: format!("Invalid regular expression at position {}: {}", pos, message),
+      },
+   }
+}
+
+/// Converts the positions of a match into a Hinton dictionary with the matched `text`, its
+/// `start` and `end` positions, the text of each capture group in `groups` (or `null` for
+/// the groups that did not participate in the match), and the named groups in `named`.
+fn match_to_dict(vm: &mut VM, regex: &RegexObject, text: &[char], captures: &Captures) -> Object {
+   let group_text = |group: &Option<(usize, usize)>| match group {
+      Some((start, end)) => Object::from(text[*start..*end].iter().collect::<String>()),
+      None => Object::Null,
+   };
+
+   let (start, end) = captures[0].unwrap();
+   let groups: Vec<Object> = captures.iter().skip(1).map(group_text).collect();
+
+   let named: DictObject = regex
+      .group_names
+      .iter()
+      .zip(captures.iter())
+      .filter_map(|(name, group)| name.as_ref().map(|name| (name.clone(), group_text(group))))
+      .collect();
+
+   let mut dict = DictObject::default();
+   dict.insert(String::from("text"), group_text(&captures[0]));
+   dict.insert(String::from("start"), Object::Int(start as i64));
+   dict.insert(String::from("end"), Object::Int(end as i64));
+   dict.insert(String::from("groups"), vm.alloc_array(groups));
+   dict.insert(String::from("named"), vm.alloc_dict(named));
+   vm.alloc_dict(dict)
+}
+
+/// Finds the first match of a regular expression in a string.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The Regex object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn find(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let regex = verify_regex_object!(this, "find");
+   let text = text_arg!(args[0], "find");
+
+   match regex.captures_at(&text, 0) {
+      Some(captures) => {
+         let m = match_to_dict(vm, &regex, &text, &captures);
+         vm.push_stack(m)
+      }
+      None => vm.push_stack(Object::Null),
+   }
+}
+
+/// Finds all the non-overlapping matches of a regular expression in a string.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The Regex object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn find_all(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let regex = verify_regex_object!(this, "find_all");
+   let text = text_arg!(args[0], "find_all");
+
+   let matches: Vec<Object> = regex
+      .all_captures(&text, None)
+      .iter()
+      .map(|captures| match_to_dict(vm, &regex, &text, captures))
+      .collect();
+
+   let matches = vm.alloc_array(matches);
+   vm.push_stack(matches)
+}
+
+/// Checks if a regular expression matches anywhere in a string.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The Regex object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn test(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let regex = verify_regex_object!(this, "test");
+   let text = text_arg!(args[0], "test");
+
+   vm.push_stack(Object::Bool(regex.captures_at(&text, 0).is_some()))
+}
+
+/// Expands the references to capture groups in a replacement string: `$n` is the
+/// text of the n-th group, `${name}` is the text of a named group, and `$$` is a `$`.
+fn expand_replacement(regex: &RegexObject, replacement: &str, text: &[char], captures: &Captures) -> String {
+   let group_text = |idx: Option<usize>| match idx.and_then(|i| captures.get(i).copied().flatten()) {
+      Some((start, end)) => text[start..end].iter().collect(),
+      None => String::new(),
+   };
+
+   let mut result = String::new();
+   let mut chars = replacement.chars().peekable();
+
+   while let Some(c) = chars.next() {
+      if c != '$' {
+         result.push(c);
+         continue;
+      }
+
+      match chars.peek() {
+         Some('$') => {
+            chars.next();
+            result.push('$');
+         }
+         Some('{') => {
+            chars.next();
+            let name: String = chars.by_ref().take_while(|c| *c != '}').collect();
+            let idx = name.parse().ok().or_else(|| {
+               regex
+                  .group_names
+                  .iter()
+                  .position(|n| n.as_deref() == Some(name.as_str()))
+            });
+            result += &group_text(idx);
+         }
+         Some(d) if d.is_ascii_digit() => {
+            let mut digits = String::new();
+            while let Some(d) = chars.peek().filter(|d| d.is_ascii_digit()) {
+               digits.push(*d);
+               chars.next();
+            }
+            result += &group_text(digits.parse().ok());
+         }
+         _ => result.push('$'),
+      }
+   }
+
+   result
+}
+
+/// Replaces the matches of a regular expression in a string. The replacement is either a string,
+/// which may refer to the capture groups with `$1` or `${name}`, or a function that is called
+/// with the match dictionary and returns the replacement string. The optional third argument
+/// limits the number of replacements.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The Regex object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn replace(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let regex = verify_regex_object!(this, "replace");
+   let text = text_arg!(args[0], "replace");
+   let limit = limit_arg!(args.get(2), "replace");
+
+   let mut result = String::new();
+   let mut last_end = 0;
+
+   for captures in regex.all_captures(&text, limit) {
+      let (start, end) = captures[0].unwrap();
+      result.extend(&text[last_end..start]);
+
+      match &args[1] {
+         Object::String(replacement) => result += &expand_replacement(&regex, replacement, &text, &captures),
+         callback => {
+            let m = match_to_dict(vm, &regex, &text, &captures);
+
+            match vm.call_value(callback.clone(), vec![m]) {
+               Ok(Object::String(s)) => result += &s,
+               Ok(obj) => {
+                  return RuntimeResult::Error {
+                     error: RuntimeErrorType::TypeError,
+                     message: format!(
+                        "The replacement function of 'Regex.replace' must return a String. Got '{}' instead.",
+                        obj.type_name()
+                     ),
+                  }
+               }
+               Err(e) => return e,
+            }
+         }
+      }
+
+      last_end = end;
+   }
+
+   result.extend(&text[last_end..]);
+   vm.push_stack(Object::from(result))
+}
+
+/// Splits a string at the matches of a regular expression. The optional second
+/// argument limits the number of splits.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The Regex object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn split(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let regex = verify_regex_object!(this, "split");
+   let text = text_arg!(args[0], "split");
+   let limit = limit_arg!(args.get(1), "split");
+
+   let mut parts = vec![];
+   let mut last_end = 0;
+
+   for captures in regex.all_captures(&text, limit) {
+      let (start, end) = captures[0].unwrap();
+
+      // Empty matches at the edges of the string would produce empty parts.
+      if end == start && (start == 0 || start == text.len()) {
+         continue;
+      }
+
+      parts.push(Object::from(text[last_end..start].iter().collect::<String>()));
+      last_end = end;
+   }
+
+   parts.push(Object::from(text[last_end..].iter().collect::<String>()));
+
+   let parts = vm.alloc_array(parts);
+   vm.push_stack(parts)
+}
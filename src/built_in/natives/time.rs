@@ -0,0 +1,216 @@
+use crate::built_in::primitives::HTPrimitive;
+use crate::built_in::NativeBoundMethod;
+use crate::errors::RuntimeErrorType;
+use crate::objects::class_obj::{ClassField, ClassObject};
+use crate::objects::datetime_obj::DateTimeObject;
+use crate::objects::Object;
+use crate::virtual_machine::{RuntimeResult, VM};
+use hashbrown::HashMap;
+use std::cell::Cell;
+use std::time::Instant;
+
+/// Represents the Hinton `Time` namespace, whose static members are the native
+/// functions that read the system's clocks and create DateTime objects.
+pub struct TimeClass(ClassObject);
+
+/// Implements the `HTPrimitive` trait for the `Time` namespace.
+impl HTPrimitive for TimeClass {
+   /// Gets the name class name of this Hinton primitive.
+   fn name(&self) -> String {
+      self.0.name.clone()
+   }
+
+   /// Gets the non-static members of this Hinton primitive.
+   fn members(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.members
+   }
+
+   /// Gets the static members of this Hinton primitive.
+   fn statics(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.statics
+   }
+
+   /// Gets the default class object stored in this Hinton primitive.
+   fn default() -> ClassObject {
+      let mut _self = TimeClass(ClassObject::new("Time"));
+
+      // >>>>>>> Static fields for the "Time" namespace to be added after this line
+      _self.bind_static_method("date", (3, 7), date as NativeBoundMethod);
+      _self.bind_static_method("from_timestamp", (1, 1), from_timestamp as NativeBoundMethod);
+      _self.bind_static_method("monotonic", (0, 0), monotonic as NativeBoundMethod);
+      _self.bind_static_method("now", (0, 0), now as NativeBoundMethod);
+      _self.bind_static_method("parse", (2, 2), parse as NativeBoundMethod);
+      _self.bind_static_method("timestamp", (0, 0), timestamp as NativeBoundMethod);
+      // <<<<<<< Static fields for the "Time" namespace to be added before this line
+
+      _self.0
+   }
+}
+
+thread_local! {
+   /// The instant from which `Time.monotonic()` measures time, set by its first call.
+   static CLOCK_START: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// Gets the current date and time, in UTC.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Time` namespace (unused).
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn now(vm: &mut VM, _: Object, _: Vec<Object>) -> RuntimeResult {
+   match vm.system_time() {
+      Ok(time) => vm.push_stack(Object::DateTime(DateTimeObject::from_system_time(time))),
+      Err(e) => e,
+   }
+}
+
+/// Gets the current Unix timestamp, in seconds.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Time` namespace (unused).
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn timestamp(vm: &mut VM, _: Object, _: Vec<Object>) -> RuntimeResult {
+   match vm.system_time() {
+      Ok(time) => vm.push_stack(Object::Float(DateTimeObject::from_system_time(time).timestamp())),
+      Err(e) => e,
+   }
+}
+
+/// Gets the number of seconds elapsed on a monotonic clock, which never goes backwards
+/// (unlike the system's time). Only the difference between two readings is meaningful,
+/// which makes this clock suitable for measuring the duration of a piece of code.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Time` namespace (unused).
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn monotonic(vm: &mut VM, _: Object, _: Vec<Object>) -> RuntimeResult {
+   let now = match vm.now() {
+      Ok(now) => now,
+      Err(e) => return e,
+   };
+
+   let elapsed = CLOCK_START.with(|start| {
+      let start_time = start.get().unwrap_or(now);
+      start.set(Some(start_time));
+      now - start_time
+   });
+   vm.push_stack(Object::Float(elapsed.as_secs_f64()))
+}
+
+/// Creates a DateTime object, in UTC, from its components: the year, month, and day,
+/// followed by the optional hour, minute, second, and millisecond.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Time` namespace (unused).
+/// * `args`: The components of the date and time.
+///
+/// # Returns:
+/// RuntimeResult
+fn date(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   let mut components = [0i64; 7];
+
+   for (idx, arg) in args.iter().enumerate() {
+      components[idx] = match arg {
+         Object::Int(i) => *i,
+         _ => {
+            return RuntimeResult::Error {
+               error: RuntimeErrorType::TypeError,
+               message: format!(
+                  "Function 'Time.date' expected Int arguments. Found '{}' instead.",
+                  arg.type_name()
+               ),
+            }
+         }
+      };
+   }
+
+   let [year, month, day, hour, minute, second, millisecond] = components;
+   match DateTimeObject::from_components((year, month, day), (hour, minute, second, millisecond)) {
+      Ok(d) => vm.push_stack(Object::DateTime(d)),
+      Err(message) => RuntimeResult::Error {
+         error: RuntimeErrorType::ArgumentError,
+         message,
+      },
+   }
+}
+
+/// Creates a DateTime object from a Unix timestamp, in seconds.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Time` namespace (unused).
+/// * `args`: The timestamp.
+///
+/// # Returns:
+/// RuntimeResult
+fn from_timestamp(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   let seconds = match &args[0] {
+      Object::Float(f) => *f,
+      Object::Int(i) => *i as f64,
+      obj => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::TypeError,
+            message: format!(
+               "Function 'Time.from_timestamp' expected a numeric argument. Found '{}' instead.",
+               obj.type_name()
+            ),
+         }
+      }
+   };
+
+   match DateTimeObject::from_timestamp(seconds) {
+      Some(d) => vm.push_stack(Object::DateTime(d)),
+      None => RuntimeResult::Error {
+         error: RuntimeErrorType::ArgumentError,
+         message: format!(
+            "The timestamp '{}' is outside the supported range of dates.",
+            seconds
+         ),
+      },
+   }
+}
+
+/// Parses a date and time with a format string (e.g., `'%Y-%m-%d %H:%M'`).
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Time` namespace (unused).
+/// * `args`: The text to parse, and the format string.
+///
+/// # Returns:
+/// RuntimeResult
+fn parse(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   let (text, format) = match (&args[0], &args[1]) {
+      (Object::String(t), Object::String(f)) => (t, f),
+      (Object::String(_), obj) | (obj, _) => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::TypeError,
+            message: format!(
+               "Function 'Time.parse' expected String arguments. Found '{}' instead.",
+               obj.type_name()
+            ),
+         }
+      }
+   };
+
+   match DateTimeObject::parse(text, format) {
+      Ok(d) => vm.push_stack(Object::DateTime(d)),
+      Err(message) => RuntimeResult::Error {
+         error: RuntimeErrorType::ArgumentError,
+         message: format!("Could not parse '{}' as a date: {}", text, message),
+      },
+   }
+}
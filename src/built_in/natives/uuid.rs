@@ -0,0 +1,81 @@
+use crate::built_in::natives::random::system_random_bytes;
+use crate::built_in::primitives::HTPrimitive;
+use crate::built_in::NativeBoundMethod;
+use crate::errors::RuntimeErrorType;
+use crate::objects::bytes_obj;
+use crate::objects::class_obj::{ClassField, ClassObject};
+use crate::objects::Object;
+use crate::virtual_machine::{RuntimeResult, VM};
+use hashbrown::HashMap;
+
+/// Represents the Hinton `Uuid` namespace, whose static members are the native functions
+/// that generate universally unique identifiers.
+pub struct UuidClass(ClassObject);
+
+/// Implements the `HTPrimitive` trait for the `Uuid` namespace.
+impl HTPrimitive for UuidClass {
+   /// Gets the name class name of this Hinton primitive.
+   fn name(&self) -> String {
+      self.0.name.clone()
+   }
+
+   /// Gets the non-static members of this Hinton primitive.
+   fn members(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.members
+   }
+
+   /// Gets the static members of this Hinton primitive.
+   fn statics(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.statics
+   }
+
+   /// Gets the default class object stored in this Hinton primitive.
+   fn default() -> ClassObject {
+      let mut _self = UuidClass(ClassObject::new("Uuid"));
+
+      // >>>>>>> Static fields for the "Uuid" namespace to be added after this line
+      _self.bind_static_method("v4", (0, 0), v4 as NativeBoundMethod);
+      // <<<<<<< Static fields for the "Uuid" namespace to be added before this line
+
+      _self.0
+   }
+}
+
+/// Generates a random (version 4) UUID, as a lowercase string in the hyphenated format
+/// (e.g., `"0b7e4a1c-5f2d-4c3a-9e8b-1a2b3c4d5e6f"`). The random bits come from the operating
+/// system, so the UUIDs do not depend on the seed of the `Random` namespace.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Uuid` namespace (unused).
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn v4(vm: &mut VM, _: Object, _: Vec<Object>) -> RuntimeResult {
+   let mut bytes = match system_random_bytes(16) {
+      Ok(b) => b,
+      Err(e) => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::IOError,
+            message: format!("Could not read random bytes from the system. {}.", e),
+         }
+      }
+   };
+
+   // Sets the version (4) and the variant (RFC 4122) of the UUID.
+   bytes[6] = (bytes[6] & 0x0F) | 0x40;
+   bytes[8] = (bytes[8] & 0x3F) | 0x80;
+
+   let hex = bytes_obj::to_hex(&bytes);
+   let uuid = format!(
+      "{}-{}-{}-{}-{}",
+      &hex[0..8],
+      &hex[8..12],
+      &hex[12..16],
+      &hex[16..20],
+      &hex[20..32]
+   );
+
+   vm.push_stack(Object::from(uuid))
+}
@@ -0,0 +1,258 @@
+//! Native plugins extend Hinton with functions written in Rust (e.g., bindings to a database
+//! or a cryptography library) without changes to the interpreter. A plugin is a dynamic
+//! library (a `cdylib` crate that depends on `hinton`) that exports its functions with the
+//! [`export_plugin!`](crate::export_plugin) macro:
+//!
+//! ```
+//! use hinton::built_in::plugins::Plugin;
+//! use hinton::Object;
+//!
+//! fn register(plugin: &mut Plugin) {
+//!    plugin.add_function("shout", 1, |args| Ok(Object::from(format!("{}!", args[0]).to_uppercase())));
+//! }
+//!
+//! hinton::export_plugin!(register);
+//! ```
+//!
+//! Hinton programs load the plugin with `var greeter = load_plugin("libgreeter.so")`, which
+//! returns a namespace with the functions of the plugin (e.g., `greeter.shout("hi")`). Because
+//! the functions are called with Rust's ABI, plugins must be built with the same version of
+//! the compiler and of the `hinton` crate as the interpreter that loads them. Both versions are
+//! checked through `extern "C"` functions before any Rust function of the plugin is called, so
+//! mismatched plugins are rejected instead of corrupting memory.
+//!
+//! Loaded libraries are never unloaded, since the functions of a plugin may be referenced until
+//! the interpreter exits. Loading a plugin again (or another copy of it) loads the same library.
+
+use crate::built_in::NativeFnBody;
+use crate::errors::ObjectOprErrType;
+use crate::objects::class_obj::{ClassField, ClassObject};
+use crate::objects::{NativeFuncObj, Object};
+use std::path::Path;
+use std::rc::Rc;
+
+/// The version of the plugin ABI, which changes whenever the types shared with plugins change.
+/// Plugins built for a different version are rejected when they are loaded.
+pub const PLUGIN_ABI_VERSION: u32 = 1;
+
+/// The versions of the compiler and of the `hinton` crate that the interpreter (or a plugin)
+/// was built with, as a null-terminated string. Rust does not have a stable ABI, so the types
+/// shared with plugins only have the same layout if both versions match.
+pub const PLUGIN_BUILD_ID: &str = concat!(
+   "hinton ",
+   env!("CARGO_PKG_VERSION"),
+   ", ",
+   env!("HINTON_RUSTC_VERSION"),
+   "\0"
+);
+
+/// The signature of the `hinton_plugin_abi_version` function exported by plugins.
+pub type AbiVersionFn = extern "C" fn() -> u32;
+
+/// The signature of the `hinton_plugin_build_id` function exported by plugins, which returns
+/// a pointer to the plugin's `PLUGIN_BUILD_ID`.
+pub type BuildIdFn = extern "C" fn() -> *const std::os::raw::c_char;
+
+/// The signature of the `hinton_plugin_register` function exported by plugins, which adds the
+/// functions of the plugin to its namespace.
+pub type RegisterFn = fn(&mut Plugin);
+
+/// Represents the namespace that holds the functions of a native plugin.
+pub struct Plugin(ClassObject);
+
+impl Plugin {
+   /// Creates an empty plugin namespace.
+   pub fn new(name: &str) -> Self {
+      Plugin(ClassObject::new(name))
+   }
+
+   /// Adds a function to the plugin's namespace. Like the functions registered with
+   /// `VM::register_native(...)`, objects kept by the function in between calls are not seen
+   /// by the garbage collector.
+   ///
+   /// # Parameters
+   /// - `name`: The name of the function in the plugin's namespace.
+   /// - `arity`: The number of arguments the function takes.
+   /// - `body`: The function's body, which receives the arguments of the call.
+   pub fn add_function<F>(&mut self, name: &str, arity: u8, body: F)
+   where
+      F: Fn(&[Object]) -> Result<Object, ObjectOprErrType> + 'static,
+   {
+      let function = NativeFuncObj {
+         name: format!("{}.{}", self.0.name, name),
+         min_arity: arity,
+         max_arity: arity,
+         body: NativeFnBody::Host(Rc::new(body)),
+      };
+
+      // The functions are public and constant static fields.
+      self.0.statics.insert(
+         name.to_string(),
+         ClassField {
+            value: Box::new(Object::from(function)),
+            mode: 0b_0000_0101,
+         },
+      );
+   }
+}
+
+/// Exports the functions a native plugin needs to be loaded by Hinton's `load_plugin(...)`
+/// native function. The argument is the path of the function that registers the plugin.
+#[macro_export]
+macro_rules! export_plugin {
+   ($register:path) => {
+      #[no_mangle]
+      pub extern "C" fn hinton_plugin_abi_version() -> u32 {
+         $crate::built_in::plugins::PLUGIN_ABI_VERSION
+      }
+
+      #[no_mangle]
+      pub extern "C" fn hinton_plugin_build_id() -> *const ::std::os::raw::c_char {
+         $crate::built_in::plugins::PLUGIN_BUILD_ID.as_ptr().cast()
+      }
+
+      #[no_mangle]
+      pub fn hinton_plugin_register(plugin: &mut $crate::built_in::plugins::Plugin) {
+         let register: $crate::built_in::plugins::RegisterFn = $register;
+         register(plugin)
+      }
+   };
+}
+
+/// Gets the name of the namespace of a plugin from the file name of its library (e.g.,
+/// `libgreeter.so` is the `greeter` plugin).
+#[cfg(unix)]
+fn plugin_name(path: &Path) -> String {
+   let file_name = path
+      .file_name()
+      .map_or(String::new(), |n| n.to_string_lossy().into_owned());
+   let stem = file_name.split('.').next().unwrap_or("");
+   stem.strip_prefix("lib").unwrap_or(stem).to_string()
+}
+
+/// Checks the ABI version and the build of a plugin, and creates the namespace with its functions.
+///
+/// # Parameters
+/// - `name`: The name of the plugin.
+/// - `abi_version`: The ABI version the plugin was built for.
+/// - `build_id`: The versions of the compiler and of the `hinton` crate the plugin was built with.
+/// - `register`: The function that binds the functions of the plugin.
+///
+/// # Returns
+/// - `Result<ClassObject, String>`: The namespace of the plugin, or the reason it could not
+///   be loaded.
+#[cfg_attr(not(unix), allow(dead_code))]
+pub(crate) fn register_plugin(
+   name: &str,
+   abi_version: u32,
+   build_id: &str,
+   register: RegisterFn,
+) -> Result<ClassObject, String> {
+   if abi_version != PLUGIN_ABI_VERSION {
+      return Err(format!(
+         "Plugin '{}' was built for version {} of the plugin ABI, but this interpreter supports version {}.",
+         name, abi_version, PLUGIN_ABI_VERSION
+      ));
+   }
+
+   let own_build_id = PLUGIN_BUILD_ID.trim_end_matches('\0');
+   if build_id != own_build_id {
+      return Err(format!(
+         "Plugin '{}' was built with {}, but this interpreter was built with {}.",
+         name, build_id, own_build_id
+      ));
+   }
+
+   let mut plugin = Plugin::new(name);
+   register(&mut plugin);
+   Ok(plugin.0)
+}
+
+/// Gets the last error reported by the dynamic loader.
+#[cfg(unix)]
+fn dl_error() -> String {
+   // SAFETY: `dlerror` returns either null, or a valid C string owned by the loader.
+   unsafe {
+      let error = libc::dlerror();
+
+      if error.is_null() {
+         String::from("Unknown error")
+      } else {
+         std::ffi::CStr::from_ptr(error).to_string_lossy().into_owned()
+      }
+   }
+}
+
+/// Loads a native plugin from a dynamic library. The library is never unloaded (not even when
+/// the plugin is rejected), because the functions of the plugin may be referenced until the
+/// program ends.
+///
+/// # Parameters
+/// - `path`: The path of the library.
+///
+/// # Returns
+/// - `Result<ClassObject, String>`: The namespace of the plugin, or the reason it could not
+///   be loaded.
+#[cfg(unix)]
+pub(crate) fn load_plugin(path: &Path) -> Result<ClassObject, String> {
+   use std::os::unix::ffi::OsStrExt;
+
+   let c_path = match std::ffi::CString::new(path.as_os_str().as_bytes()) {
+      Ok(p) => p,
+      Err(_) => return Err(format!("Invalid plugin path '{}'.", path.display())),
+   };
+
+   // SAFETY: The path is a valid C string. Loading a library runs its initializers, which is
+   // the reason programs can only load the plugins they trust.
+   let handle = unsafe { libc::dlopen(c_path.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL) };
+   if handle.is_null() {
+      return Err(format!(
+         "Could not load plugin '{}'. {}.",
+         path.display(),
+         dl_error()
+      ));
+   }
+
+   let symbol = |name: &str| {
+      let c_name = std::ffi::CString::new(name).unwrap();
+
+      // SAFETY: The handle was returned by a successful call to `dlopen`.
+      match unsafe { libc::dlsym(handle, c_name.as_ptr()) } {
+         s if s.is_null() => Err(format!(
+            "'{}' is not a Hinton plugin. The library does not export '{}'.",
+            path.display(),
+            name
+         )),
+         s => Ok(s),
+      }
+   };
+
+   let abi_version = symbol("hinton_plugin_abi_version")?;
+   let build_id = symbol("hinton_plugin_build_id")?;
+   let register = symbol("hinton_plugin_register")?;
+
+   // SAFETY: The symbols are the functions defined by the `export_plugin!` macro, which have
+   // these signatures. The handshake functions use the C ABI, so they can be called whatever
+   // the plugin was built with, and the register function is only called once they match.
+   let (abi_version, build_id, register) = unsafe {
+      (
+         std::mem::transmute::<*mut libc::c_void, AbiVersionFn>(abi_version),
+         std::mem::transmute::<*mut libc::c_void, BuildIdFn>(build_id),
+         std::mem::transmute::<*mut libc::c_void, RegisterFn>(register),
+      )
+   };
+
+   // SAFETY: The build ID is a null-terminated string in the static memory of the plugin,
+   // which is never unloaded.
+   let build_id = unsafe { std::ffi::CStr::from_ptr(build_id()) }.to_string_lossy();
+   register_plugin(&plugin_name(path), abi_version(), &build_id, register)
+}
+
+/// Loads a native plugin from a dynamic library, which is not supported on this platform.
+#[cfg(not(unix))]
+pub(crate) fn load_plugin(path: &Path) -> Result<ClassObject, String> {
+   Err(format!(
+      "Could not load plugin '{}'. Native plugins are not supported on this platform.",
+      path.display()
+   ))
+}
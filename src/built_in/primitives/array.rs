@@ -1,10 +1,13 @@
 use crate::built_in::primitives::HTPrimitive;
 use crate::built_in::NativeBoundMethod;
+use crate::core::ast::BinaryExprType;
 use crate::errors::RuntimeErrorType;
 use crate::objects::class_obj::{ClassField, ClassObject};
 use crate::objects::Object;
 use crate::virtual_machine::{RuntimeResult, VM};
 use hashbrown::HashMap;
+use std::cell::RefCell;
+use std::rc::Rc;
 
 /// Represents the Hinton `Array` primitive class.
 pub struct ArrayClass(ClassObject);
@@ -31,10 +34,18 @@ impl HTPrimitive for ArrayClass {
       let mut _self = ArrayClass(ClassObject::new("Array"));
 
       // >>>>>>> Class fields for the "Array" primitive type to be added after this line
+      _self.bind_non_static_method("all", (1, 1), all as NativeBoundMethod);
+      _self.bind_non_static_method("any", (1, 1), any as NativeBoundMethod);
+      _self.bind_non_static_method("each", (1, 1), each as NativeBoundMethod);
+      _self.bind_non_static_method("filter", (1, 1), filter as NativeBoundMethod);
+      _self.bind_non_static_method("find", (1, 1), find as NativeBoundMethod);
       _self.bind_non_static_method("index_of", (1, 1), index_of as NativeBoundMethod);
       _self.bind_non_static_method("len", (0, 0), len as NativeBoundMethod);
+      _self.bind_non_static_method("map", (1, 1), map as NativeBoundMethod);
       _self.bind_non_static_method("pop", (0, 0), pop as NativeBoundMethod);
       _self.bind_non_static_method("push", (1, 1), push as NativeBoundMethod);
+      _self.bind_non_static_method("reduce", (1, 2), reduce as NativeBoundMethod);
+      _self.bind_non_static_method("sort", (0, 1), sort as NativeBoundMethod);
       // <<<<<<< Class fields for the "Array" primitive type to be added before this line
 
       _self.0
@@ -128,3 +139,275 @@ fn index_of(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
       None => vm.push_stack(Object::Null),
    }
 }
+
+/// Gets a copy of the items in a Hinton array, so that the array can be modified by the
+/// callbacks of a higher-order method while the method iterates over the items.
+macro_rules! array_items {
+   ($maybe_array: expr, $prop_name: expr) => {
+      verify_array_object!(&$maybe_array, $prop_name).borrow().clone()
+   };
+}
+
+/// Calls a Hinton callback, returning from the enclosing method if the call fails.
+macro_rules! call_or_return {
+   ($vm: expr, $callback: expr, $args: expr) => {
+      match $vm.call_value($callback.clone(), $args) {
+         Ok(o) => o,
+         Err(e) => return e,
+      }
+   };
+}
+
+/// Calls a function on every item of this Hinton array.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The array object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn each(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let items = array_items!(this, "each");
+
+   for item in items {
+      call_or_return!(vm, args[0], vec![item]);
+   }
+
+   vm.push_stack(Object::Null)
+}
+
+/// Creates a new array with the results of calling a function on every item of this Hinton array.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The array object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn map(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let mut result = vec![];
+
+   let items = array_items!(this, "map");
+
+   for item in items {
+      result.push(call_or_return!(vm, args[0], vec![item]));
+   }
+
+   vm.push_stack(Object::Array(Rc::new(RefCell::new(result))))
+}
+
+/// Creates a new array with the items of this Hinton array for which a function returns a
+/// truthy value.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The array object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn filter(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let mut result = vec![];
+
+   let items = array_items!(this, "filter");
+
+   for item in items {
+      if !call_or_return!(vm, args[0], vec![item.clone()]).is_falsey() {
+         result.push(item);
+      }
+   }
+
+   vm.push_stack(Object::Array(Rc::new(RefCell::new(result))))
+}
+
+/// Combines the items of this Hinton array into a single value, by calling a function with the
+/// accumulated value and each item. The accumulated value starts as the given initial value,
+/// or as the first item of the array if no initial value is given.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The array object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn reduce(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let mut items = array_items!(this, "reduce").into_iter();
+
+   let mut acc = match args.get(1) {
+      Some(initial) => initial.clone(),
+      None => match items.next() {
+         Some(first) => first,
+         None => {
+            return RuntimeResult::Error {
+               error: RuntimeErrorType::TypeError,
+               message: String::from("Cannot reduce an empty array without an initial value."),
+            }
+         }
+      },
+   };
+
+   for item in items {
+      acc = call_or_return!(vm, args[0], vec![acc, item]);
+   }
+
+   vm.push_stack(acc)
+}
+
+/// Gets the first item of this Hinton array for which a function returns a truthy value, or
+/// `null` if there is no such item.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The array object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn find(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let items = array_items!(this, "find");
+
+   for item in items {
+      if !call_or_return!(vm, args[0], vec![item.clone()]).is_falsey() {
+         return vm.push_stack(item);
+      }
+   }
+
+   vm.push_stack(Object::Null)
+}
+
+/// Checks if a function returns a truthy value for any item of this Hinton array.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The array object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn any(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let items = array_items!(this, "any");
+
+   for item in items {
+      if !call_or_return!(vm, args[0], vec![item]).is_falsey() {
+         return vm.push_stack(Object::Bool(true));
+      }
+   }
+
+   vm.push_stack(Object::Bool(false))
+}
+
+/// Checks if a function returns a truthy value for every item of this Hinton array.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The array object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn all(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let items = array_items!(this, "all");
+
+   for item in items {
+      if call_or_return!(vm, args[0], vec![item]).is_falsey() {
+         return vm.push_stack(Object::Bool(false));
+      }
+   }
+
+   vm.push_stack(Object::Bool(true))
+}
+
+/// Sorts this Hinton array in place, and returns the array. The sort is stable. Without
+/// arguments, numbers are compared with the `<` operator and strings are compared
+/// lexicographically. Otherwise, the items are compared with a function that returns a negative
+/// number if its first argument goes before its second argument, a positive number if it goes
+/// after it, or zero if their order does not matter.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The array object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn sort(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let items = array_items!(this, "sort");
+
+   // Checks if the first item must go after the second item in the sorted array.
+   let mut goes_after = |a: &Object, b: &Object| -> Result<bool, RuntimeResult> {
+      match args.first() {
+         Some(compare) => match vm.call_value(compare.clone(), vec![a.clone(), b.clone()])? {
+            Object::Int(i) => Ok(i > 0),
+            Object::Float(f) => Ok(f > 0.0),
+            obj => Err(RuntimeResult::Error {
+               error: RuntimeErrorType::TypeError,
+               message: format!(
+                  "The comparison function of 'Array.sort' must return a number. Found '{}' instead.",
+                  obj.type_name()
+               ),
+            }),
+         },
+         None => match (a, b) {
+            (Object::String(a), Object::String(b)) => Ok(a > b),
+            _ => match b
+               .clone()
+               .binary_operation(&BinaryExprType::LogicLessThan, a.clone())
+            {
+               Ok(result) => Ok(!result.is_falsey()),
+               Err(e) => Err(e.to_runtime_error()),
+            },
+         },
+      }
+   };
+
+   match merge_sort(items, &mut goes_after) {
+      Ok(sorted) => {
+         if let Object::Array(arr) = &this {
+            *arr.borrow_mut() = sorted;
+         }
+
+         vm.push_stack(this)
+      }
+      Err(e) => e,
+   }
+}
+
+/// Sorts a list of objects with a stable merge sort, which (unlike the sorting methods of the
+/// standard library) tolerates comparison functions that fail or are inconsistent.
+///
+/// # Arguments
+/// * `items`: The objects to sort.
+/// * `goes_after`: Checks if its first argument must go after its second argument.
+///
+/// # Returns:
+/// Result<Vec<Object>, RuntimeResult>
+fn merge_sort<F>(mut items: Vec<Object>, goes_after: &mut F) -> Result<Vec<Object>, RuntimeResult>
+where
+   F: FnMut(&Object, &Object) -> Result<bool, RuntimeResult>,
+{
+   if items.len() <= 1 {
+      return Ok(items);
+   }
+
+   let right = merge_sort(items.split_off(items.len() / 2), goes_after)?;
+   let left = merge_sort(items, goes_after)?;
+
+   let mut merged = Vec::with_capacity(left.len() + right.len());
+   let mut left = left.into_iter().peekable();
+   let mut right = right.into_iter().peekable();
+
+   while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+      if goes_after(l, r)? {
+         merged.push(right.next().unwrap());
+      } else {
+         merged.push(left.next().unwrap());
+      }
+   }
+
+   merged.extend(left);
+   merged.extend(right);
+   Ok(merged)
+}
@@ -0,0 +1,202 @@
+use crate::built_in::primitives::HTPrimitive;
+use crate::built_in::NativeBoundMethod;
+use crate::errors::RuntimeErrorType;
+use crate::objects::bytes_obj;
+use crate::objects::class_obj::{ClassField, ClassObject};
+use crate::objects::Object;
+use crate::virtual_machine::{RuntimeResult, VM};
+use hashbrown::HashMap;
+
+/// Represents the Hinton `Bytes` primitive class. Bytes are created with the `Bytes(...)` native
+/// function, which shadows the name of this class in Hinton programs.
+pub struct BytesClass(ClassObject);
+
+/// Implements the `HTPrimitive` trait for the `BytesClass` primitive Hinton class.
+impl HTPrimitive for BytesClass {
+   /// Gets the name class name of this Hinton primitive.
+   fn name(&self) -> String {
+      self.0.name.clone()
+   }
+
+   /// Gets the non-static members of this Hinton primitive.
+   fn members(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.members
+   }
+
+   /// Gets the static members of this Hinton primitive.
+   fn statics(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.statics
+   }
+
+   /// Gets the default class object stored in this Hinton primitive.
+   fn default() -> ClassObject {
+      let mut _self = BytesClass(ClassObject::new("Bytes"));
+
+      // >>>>>>> Class fields for the "Bytes" primitive type to be added after this line
+      _self.bind_non_static_method("decode", (0, 1), decode as NativeBoundMethod);
+      _self.bind_non_static_method("extend", (1, 1), extend as NativeBoundMethod);
+      _self.bind_non_static_method("hex", (0, 0), hex as NativeBoundMethod);
+      _self.bind_non_static_method("len", (0, 0), len as NativeBoundMethod);
+      _self.bind_non_static_method("push", (1, 1), push as NativeBoundMethod);
+      _self.bind_non_static_method("to_array", (0, 0), to_array as NativeBoundMethod);
+      // <<<<<<< Class fields for the "Bytes" primitive type to be added before this line
+
+      _self.0
+   }
+}
+
+macro_rules! verify_bytes_object {
+   ($maybe_bytes: expr, $prop_name: expr) => {
+      match $maybe_bytes {
+         Object::Bytes(b) => b,
+         _ => {
+            return RuntimeResult::Error {
+               error: RuntimeErrorType::TypeError,
+               message: format!(
+                  "Property 'Bytes.{}' requires that 'self' be a Bytes object. Found '{}' instead.",
+                  $prop_name,
+                  $maybe_bytes.type_name()
+               ),
+            }
+         }
+      }
+   };
+}
+
+/// Decodes the contents of a Hinton Bytes object into a string. The encoding
+/// defaults to `utf-8`, and can also be `ascii`, `latin-1`, or `hex`.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The Bytes object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn decode(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let encoding = match args.first() {
+      Some(Object::String(e)) => e.to_string(),
+      Some(obj) => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::TypeError,
+            message: format!(
+               "Expected argument of type 'String' for call to 'Bytes.decode'. Got '{}' instead.",
+               obj.type_name()
+            ),
+         }
+      }
+      None => String::from("utf-8"),
+   };
+
+   let decoded = bytes_obj::decode(&verify_bytes_object!(this, "decode").borrow(), &encoding);
+
+   match decoded {
+      Ok(s) => vm.push_stack(Object::from(s)),
+      Err(message) => RuntimeResult::Error {
+         error: RuntimeErrorType::ArgumentError,
+         message,
+      },
+   }
+}
+
+/// Appends the contents of another Bytes object (or an array of integers from 0 to 255)
+/// to the end of a Hinton Bytes object.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The Bytes object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn extend(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let other = match &args[0] {
+      Object::Bytes(b) => Some(b.borrow().clone()),
+      Object::Array(arr) => bytes_obj::from_int_array(&arr.borrow()),
+      _ => None,
+   };
+
+   match other {
+      Some(other) => {
+         verify_bytes_object!(this, "extend").borrow_mut().extend(other);
+         vm.push_stack(Object::Null)
+      }
+      None => RuntimeResult::Error {
+         error: RuntimeErrorType::TypeError,
+         message: String::from("'Bytes.extend' expected a Bytes object or an Array of Ints from 0 to 255."),
+      },
+   }
+}
+
+/// Converts the contents of a Hinton Bytes object into a string of hexadecimal digits.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The Bytes object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn hex(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   let hex = bytes_obj::to_hex(&verify_bytes_object!(this, "hex").borrow());
+   vm.push_stack(Object::from(hex))
+}
+
+/// Gets the number of bytes in a Hinton Bytes object.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The Bytes object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn len(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   let len = verify_bytes_object!(this, "len").borrow().len();
+   vm.push_stack(Object::from(len))
+}
+
+/// Appends a byte (an integer from 0 to 255) to the end of a Hinton Bytes object.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The Bytes object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn push(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   match args[0] {
+      Object::Int(b) if (0..=255).contains(&b) => {
+         verify_bytes_object!(this, "push").borrow_mut().push(b as u8);
+         vm.push_stack(Object::Null)
+      }
+      _ => RuntimeResult::Error {
+         error: RuntimeErrorType::TypeError,
+         message: format!(
+            "Cannot push '{}' to Bytes. Expected an Int from 0 to 255.",
+            args[0].as_plain_string()
+         ),
+      },
+   }
+}
+
+/// Creates an array with the bytes of a Hinton Bytes object, as integers.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The Bytes object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn to_array(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   let items = verify_bytes_object!(this, "to_array")
+      .borrow()
+      .iter()
+      .map(|b| Object::Int(*b as i64))
+      .collect();
+
+   let arr = vm.alloc_array(items);
+   vm.push_stack(arr)
+}
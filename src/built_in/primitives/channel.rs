@@ -0,0 +1,122 @@
+use crate::built_in::primitives::HTPrimitive;
+use crate::built_in::NativeBoundMethod;
+use crate::errors::RuntimeErrorType;
+use crate::objects::class_obj::{ClassField, ClassObject};
+use crate::objects::Object;
+use crate::virtual_machine::{RuntimeResult, VM};
+use hashbrown::HashMap;
+
+/// Represents the Hinton `Channel` primitive class. Channels are created with the `Channel()`
+/// native function, which shadows the name of this class in Hinton programs.
+pub struct ChannelClass(ClassObject);
+
+/// Implements the `HTPrimitive` trait for the `ChannelClass` primitive Hinton class.
+impl HTPrimitive for ChannelClass {
+   /// Gets the name class name of this Hinton primitive.
+   fn name(&self) -> String {
+      self.0.name.clone()
+   }
+
+   /// Gets the non-static members of this Hinton primitive.
+   fn members(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.members
+   }
+
+   /// Gets the static members of this Hinton primitive.
+   fn statics(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.statics
+   }
+
+   /// Gets the default class object stored in this Hinton primitive.
+   fn default() -> ClassObject {
+      let mut _self = ChannelClass(ClassObject::new("Channel"));
+
+      // >>>>>>> Class fields for the "Channel" primitive type to be added after this line
+      _self.bind_non_static_method("len", (0, 0), len as NativeBoundMethod);
+      _self.bind_non_static_method("recv", (0, 0), recv as NativeBoundMethod);
+      _self.bind_non_static_method("send", (1, 1), send as NativeBoundMethod);
+      _self.bind_non_static_method("try_recv", (0, 0), try_recv as NativeBoundMethod);
+      // <<<<<<< Class fields for the "Channel" primitive type to be added before this line
+
+      _self.0
+   }
+}
+
+macro_rules! verify_channel_object {
+   ($maybe_channel: expr, $prop_name: expr) => {
+      match $maybe_channel {
+         Object::Channel(c) => c,
+         _ => {
+            return RuntimeResult::Error {
+               error: RuntimeErrorType::TypeError,
+               message: format!(
+                  "Property 'Channel.{}' requires that 'self' be a Channel. Found '{}' instead.",
+                  $prop_name,
+                  $maybe_channel.type_name()
+               ),
+            }
+         }
+      }
+   };
+}
+
+/// Gets the number of values waiting to be received from a Hinton channel.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The channel object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn len(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   let len = verify_channel_object!(this, "len").borrow().len();
+   vm.push_stack(Object::from(len))
+}
+
+/// Receives the oldest value sent through a Hinton channel. If the channel is empty, the
+/// running green thread waits until another green thread sends a value through the channel.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The channel object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn recv(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   let channel = verify_channel_object!(this, "recv");
+   vm.recv_from_channel(channel)
+}
+
+/// Sends a value through a Hinton channel. Channels are unbounded, so sending never waits.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The channel object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn send(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   verify_channel_object!(this, "send")
+      .borrow_mut()
+      .send(args[0].clone());
+
+   vm.push_stack(Object::Null)
+}
+
+/// Receives the oldest value sent through a Hinton channel, or `null` if the channel is
+/// empty, without waiting for other green threads.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The channel object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn try_recv(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   let value = verify_channel_object!(this, "try_recv").borrow_mut().recv();
+   vm.push_stack(value.unwrap_or(Object::Null))
+}
@@ -0,0 +1,238 @@
+use crate::built_in::primitives::HTPrimitive;
+use crate::built_in::NativeBoundMethod;
+use crate::errors::RuntimeErrorType;
+use crate::objects::class_obj::{ClassField, ClassObject};
+use crate::objects::Object;
+use crate::virtual_machine::{RuntimeResult, VM};
+use hashbrown::HashMap;
+use std::convert::TryFrom;
+
+/// Represents the Hinton `Char` primitive class.
+///
+/// A char is a single Unicode scalar value, written as a char literal (e.g., `c'a'`) or
+/// produced by iterating over a string. Chars can be compared with each other, and can be
+/// converted to and from their Unicode code points.
+pub struct CharClass(ClassObject);
+
+/// Implements the `HTPrimitive` trait for the `CharClass` primitive Hinton class.
+impl HTPrimitive for CharClass {
+   /// Gets the name class name of this Hinton primitive.
+   fn name(&self) -> String {
+      self.0.name.clone()
+   }
+
+   /// Gets the non-static members of this Hinton primitive.
+   fn members(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.members
+   }
+
+   /// Gets the static members of this Hinton primitive.
+   fn statics(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.statics
+   }
+
+   /// Gets the default class object stored in this Hinton primitive.
+   fn default() -> ClassObject {
+      let mut _self = CharClass(ClassObject::new("Char"));
+
+      // >>>>>>> Class fields for the "Char" primitive type to be added after this line
+      _self.bind_static_method("from_code", (1, 1), from_code as NativeBoundMethod);
+      _self.bind_non_static_method("code", (0, 0), code as NativeBoundMethod);
+      _self.bind_non_static_method("is_alphabetic", (0, 0), is_alphabetic as NativeBoundMethod);
+      _self.bind_non_static_method("is_digit", (0, 0), is_digit as NativeBoundMethod);
+      _self.bind_non_static_method("is_lower", (0, 0), is_lower as NativeBoundMethod);
+      _self.bind_non_static_method("is_upper", (0, 0), is_upper as NativeBoundMethod);
+      _self.bind_non_static_method("is_whitespace", (0, 0), is_whitespace as NativeBoundMethod);
+      _self.bind_non_static_method("to_lower", (0, 0), to_lower as NativeBoundMethod);
+      _self.bind_non_static_method("to_string", (0, 0), to_string as NativeBoundMethod);
+      _self.bind_non_static_method("to_upper", (0, 0), to_upper as NativeBoundMethod);
+      // <<<<<<< Class fields for the "Char" primitive type to be added before this line
+
+      _self.0
+   }
+}
+
+macro_rules! verify_char_object {
+   ($o: expr, $prop_name: expr) => {
+      match $o {
+         Object::Char(c) => c,
+         _ => {
+            return RuntimeResult::Error {
+               error: RuntimeErrorType::TypeError,
+               message: format!(
+                  "Property 'Char.{}' requires that 'self' be a Char. Found '{}' instead.",
+                  $prop_name,
+                  $o.type_name()
+               ),
+            }
+         }
+      }
+   };
+}
+
+/// Creates a Hinton char from its Unicode code point.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `_`: The `Char` class object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn from_code(vm: &mut VM, _: Object, args: Vec<Object>) -> RuntimeResult {
+   let code = match args[0] {
+      Object::Int(i) => i,
+      _ => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::TypeError,
+            message: format!(
+               "Function 'Char.from_code' expected an Int argument. Found '{}' instead.",
+               args[0].type_name()
+            ),
+         }
+      }
+   };
+
+   match u32::try_from(code).ok().and_then(char::from_u32) {
+      Some(c) => vm.push_stack(Object::Char(c)),
+      None => RuntimeResult::Error {
+         error: RuntimeErrorType::ArgumentError,
+         message: format!("'{}' is not a valid Unicode code point.", code),
+      },
+   }
+}
+
+/// Gets the Unicode code point of a Hinton char.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The char object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn code(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   vm.push_stack(Object::Int(verify_char_object!(this, "code") as i64))
+}
+
+/// Checks if a Hinton char is alphabetic.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The char object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn is_alphabetic(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   vm.push_stack(Object::Bool(
+      verify_char_object!(this, "is_alphabetic").is_alphabetic(),
+   ))
+}
+
+/// Checks if a Hinton char is a decimal digit.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The char object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn is_digit(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   vm.push_stack(Object::Bool(
+      verify_char_object!(this, "is_digit").is_ascii_digit(),
+   ))
+}
+
+/// Checks if a Hinton char is lowercase.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The char object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn is_lower(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   vm.push_stack(Object::Bool(verify_char_object!(this, "is_lower").is_lowercase()))
+}
+
+/// Checks if a Hinton char is uppercase.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The char object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn is_upper(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   vm.push_stack(Object::Bool(verify_char_object!(this, "is_upper").is_uppercase()))
+}
+
+/// Checks if a Hinton char is whitespace.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The char object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn is_whitespace(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   vm.push_stack(Object::Bool(
+      verify_char_object!(this, "is_whitespace").is_whitespace(),
+   ))
+}
+
+/// Converts a Hinton char to lowercase. Chars whose lowercase form
+/// has more than one character are returned unchanged.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The char object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn to_lower(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   let c = verify_char_object!(this, "to_lower");
+   vm.push_stack(Object::Char(single_char(c.to_lowercase()).unwrap_or(c)))
+}
+
+/// Converts a Hinton char to uppercase. Chars whose uppercase form
+/// has more than one character (e.g., 'ß') are returned unchanged.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The char object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn to_upper(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   let c = verify_char_object!(this, "to_upper");
+   vm.push_stack(Object::Char(single_char(c.to_uppercase()).unwrap_or(c)))
+}
+
+/// Converts a Hinton char into a Hinton string.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The char object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn to_string(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   vm.push_stack(Object::from(verify_char_object!(this, "to_string").to_string()))
+}
+
+/// Gets the only character produced by an iterator, or `None` if it produces more than one.
+fn single_char(mut chars: impl Iterator<Item = char>) -> Option<char> {
+   match (chars.next(), chars.next()) {
+      (Some(c), None) => Some(c),
+      _ => None,
+   }
+}
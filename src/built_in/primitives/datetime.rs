@@ -0,0 +1,261 @@
+use crate::built_in::primitives::HTPrimitive;
+use crate::built_in::NativeBoundMethod;
+use crate::errors::RuntimeErrorType;
+use crate::objects::class_obj::{ClassField, ClassObject};
+use crate::objects::datetime_obj::{DateComponents, DateTimeObject};
+use crate::objects::Object;
+use crate::virtual_machine::{RuntimeResult, VM};
+use hashbrown::HashMap;
+
+/// Represents the Hinton `DateTime` primitive class. DateTimes are created
+/// with the functions of the `Time` namespace (e.g., `Time.now()`).
+pub struct DateTimeClass(ClassObject);
+
+/// Implements the `HTPrimitive` trait for the `DateTimeClass` primitive Hinton class.
+impl HTPrimitive for DateTimeClass {
+   /// Gets the name class name of this Hinton primitive.
+   fn name(&self) -> String {
+      self.0.name.clone()
+   }
+
+   /// Gets the non-static members of this Hinton primitive.
+   fn members(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.members
+   }
+
+   /// Gets the static members of this Hinton primitive.
+   fn statics(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.statics
+   }
+
+   /// Gets the default class object stored in this Hinton primitive.
+   fn default() -> ClassObject {
+      let mut _self = DateTimeClass(ClassObject::new("DateTime"));
+
+      // >>>>>>> Class fields for the "DateTime" primitive type to be added after this line
+      _self.bind_non_static_method("add_days", (1, 1), add_days as NativeBoundMethod);
+      _self.bind_non_static_method("add_months", (1, 1), add_months as NativeBoundMethod);
+      _self.bind_non_static_method("day", (0, 0), day as NativeBoundMethod);
+      _self.bind_non_static_method("day_of_year", (0, 0), day_of_year as NativeBoundMethod);
+      _self.bind_non_static_method("format", (0, 1), format as NativeBoundMethod);
+      _self.bind_non_static_method("hour", (0, 0), hour as NativeBoundMethod);
+      _self.bind_non_static_method("millisecond", (0, 0), millisecond as NativeBoundMethod);
+      _self.bind_non_static_method("minute", (0, 0), minute as NativeBoundMethod);
+      _self.bind_non_static_method("month", (0, 0), month as NativeBoundMethod);
+      _self.bind_non_static_method("second", (0, 0), second as NativeBoundMethod);
+      _self.bind_non_static_method("timestamp", (0, 0), timestamp as NativeBoundMethod);
+      _self.bind_non_static_method("weekday", (0, 0), weekday as NativeBoundMethod);
+      _self.bind_non_static_method("year", (0, 0), year as NativeBoundMethod);
+      // <<<<<<< Class fields for the "DateTime" primitive type to be added before this line
+
+      _self.0
+   }
+}
+
+macro_rules! verify_date_time_object {
+   ($maybe_date: expr, $prop_name: expr) => {
+      match $maybe_date {
+         Object::DateTime(d) => d,
+         _ => {
+            return RuntimeResult::Error {
+               error: RuntimeErrorType::TypeError,
+               message: format!(
+                  "Property 'DateTime.{}' requires that 'self' be a DateTime object. Found '{}' instead.",
+                  $prop_name,
+                  $maybe_date.type_name()
+               ),
+            }
+         }
+      }
+   };
+}
+
+/// Pushes one of the components of a Hinton DateTime object onto the stack.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The DateTime object.
+/// * `prop_name`: The name of the method that gets the component.
+/// * `component`: Selects the component.
+///
+/// # Returns:
+/// RuntimeResult
+fn push_component(
+   vm: &mut VM,
+   this: Object,
+   prop_name: &str,
+   component: fn(&DateComponents) -> i64,
+) -> RuntimeResult {
+   let date = verify_date_time_object!(this, prop_name);
+   vm.push_stack(Object::Int(component(&date.components())))
+}
+
+/// Gets the year of a Hinton DateTime object.
+fn year(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   push_component(vm, this, "year", |c| c.year)
+}
+
+/// Gets the month of a Hinton DateTime object, from 1 (January) to 12 (December).
+fn month(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   push_component(vm, this, "month", |c| c.month)
+}
+
+/// Gets the day of the month of a Hinton DateTime object.
+fn day(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   push_component(vm, this, "day", |c| c.day)
+}
+
+/// Gets the hour of a Hinton DateTime object, from 0 to 23.
+fn hour(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   push_component(vm, this, "hour", |c| c.hour)
+}
+
+/// Gets the minute of a Hinton DateTime object.
+fn minute(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   push_component(vm, this, "minute", |c| c.minute)
+}
+
+/// Gets the second of a Hinton DateTime object.
+fn second(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   push_component(vm, this, "second", |c| c.second)
+}
+
+/// Gets the millisecond of a Hinton DateTime object.
+fn millisecond(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   push_component(vm, this, "millisecond", |c| c.millisecond)
+}
+
+/// Gets the day of the week of a Hinton DateTime object, from 1 (Monday) to 7 (Sunday).
+fn weekday(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   push_component(vm, this, "weekday", |c| c.weekday)
+}
+
+/// Gets the day of the year of a Hinton DateTime object, from 1 to 366.
+fn day_of_year(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   push_component(vm, this, "day_of_year", |c| c.day_of_year)
+}
+
+/// Gets the Unix timestamp of a Hinton DateTime object, in seconds.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The DateTime object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn timestamp(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   let date = verify_date_time_object!(this, "timestamp");
+   vm.push_stack(Object::Float(date.timestamp()))
+}
+
+/// Formats a Hinton DateTime object with a format string (e.g., `'%Y-%m-%d %H:%M'`).
+/// Without a format string, the date is formatted in the ISO 8601 format.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The DateTime object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn format(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let date = verify_date_time_object!(this, "format");
+
+   let formatted = match args.first() {
+      Some(Object::String(f)) => date.format(f),
+      Some(obj) => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::TypeError,
+            message: format!(
+               "Expected argument of type 'String' for call to 'DateTime.format'. Got '{}' instead.",
+               obj.type_name()
+            ),
+         }
+      }
+      None => Ok(date.to_string()),
+   };
+
+   match formatted {
+      Ok(s) => vm.push_stack(Object::from(s)),
+      Err(message) => RuntimeResult::Error {
+         error: RuntimeErrorType::ArgumentError,
+         message,
+      },
+   }
+}
+
+/// Shifts a Hinton DateTime object with a function that takes an integer argument.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The DateTime object.
+/// * `args`: The arguments of the method call.
+/// * `prop_name`: The name of the method.
+/// * `shift`: Computes the shifted date, or `None` if it is outside the supported range.
+///
+/// # Returns:
+/// RuntimeResult
+fn shift(
+   vm: &mut VM,
+   this: Object,
+   args: Vec<Object>,
+   prop_name: &str,
+   shift: fn(&DateTimeObject, i64) -> Option<DateTimeObject>,
+) -> RuntimeResult {
+   let date = verify_date_time_object!(this, prop_name);
+
+   let amount = match args[0] {
+      Object::Int(i) => i,
+      _ => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::TypeError,
+            message: format!(
+               "Expected argument of type 'Int' for call to 'DateTime.{}'. Got '{}' instead.",
+               prop_name,
+               args[0].type_name()
+            ),
+         }
+      }
+   };
+
+   match shift(&date, amount) {
+      Some(d) => vm.push_stack(Object::DateTime(d)),
+      None => RuntimeResult::Error {
+         error: RuntimeErrorType::ArgumentError,
+         message: format!(
+            "The result of 'DateTime.{}' is outside the supported range of dates.",
+            prop_name
+         ),
+      },
+   }
+}
+
+/// Creates a new Hinton DateTime object that is a number of days after (or before) this one.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The DateTime object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn add_days(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   shift(vm, this, args, "add_days", |d, days| {
+      d.add_millis(days.checked_mul(86_400_000)?)
+   })
+}
+
+/// Creates a new Hinton DateTime object that is a number of calendar months after (or before)
+/// this one. If the day does not exist in the resulting month, the last day of the month is used.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The DateTime object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn add_months(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   shift(vm, this, args, "add_months", DateTimeObject::add_months)
+}
@@ -0,0 +1,234 @@
+use crate::built_in::primitives::HTPrimitive;
+use crate::built_in::NativeBoundMethod;
+use crate::errors::RuntimeErrorType;
+use crate::objects::class_obj::{ClassField, ClassObject};
+use crate::objects::dict_obj::DictObject;
+use crate::objects::Object;
+use crate::virtual_machine::{RuntimeResult, VM};
+use hashbrown::HashMap;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Represents the Hinton `Dict` primitive class.
+///
+/// Because the entries of a dictionary can also be accessed with the dot notation,
+/// an entry whose key is the name of a method hides that method.
+pub struct DictClass(ClassObject);
+
+/// Implements the `HTPrimitive` trait for the `DictClass` primitive Hinton class.
+impl HTPrimitive for DictClass {
+   /// Gets the name class name of this Hinton primitive.
+   fn name(&self) -> String {
+      self.0.name.clone()
+   }
+
+   /// Gets the non-static members of this Hinton primitive.
+   fn members(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.members
+   }
+
+   /// Gets the static members of this Hinton primitive.
+   fn statics(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.statics
+   }
+
+   /// Gets the default class object stored in this Hinton primitive.
+   fn default() -> ClassObject {
+      let mut _self = DictClass(ClassObject::new("Dict"));
+
+      // >>>>>>> Class fields for the "Dict" primitive type to be added after this line
+      _self.bind_non_static_method("contains", (1, 1), contains as NativeBoundMethod);
+      _self.bind_non_static_method("entries", (0, 0), entries as NativeBoundMethod);
+      _self.bind_non_static_method("get", (1, 2), get as NativeBoundMethod);
+      _self.bind_non_static_method("keys", (0, 0), keys as NativeBoundMethod);
+      _self.bind_non_static_method("len", (0, 0), len as NativeBoundMethod);
+      _self.bind_non_static_method("merge", (1, 1), merge as NativeBoundMethod);
+      _self.bind_non_static_method("remove", (1, 1), remove as NativeBoundMethod);
+      _self.bind_non_static_method("values", (0, 0), values as NativeBoundMethod);
+      // <<<<<<< Class fields for the "Dict" primitive type to be added before this line
+
+      _self.0
+   }
+}
+
+macro_rules! verify_dict_object {
+   ($maybe_dict: expr, $prop_name: expr) => {
+      match $maybe_dict {
+         Object::Dict(d) => d,
+         _ => {
+            return RuntimeResult::Error {
+               error: RuntimeErrorType::TypeError,
+               message: format!(
+                  "Property 'Dict.{}' requires that 'self' be a Dict. Found '{}' instead.",
+                  $prop_name,
+                  $maybe_dict.type_name()
+               ),
+            }
+         }
+      }
+   };
+}
+
+/// Checks if a Hinton dictionary has an entry with the given key.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The dictionary object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn contains(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let has_key = verify_dict_object!(this, "contains").borrow().contains(&args[0]);
+   vm.push_stack(Object::Bool(has_key))
+}
+
+/// Creates an array with the `(key, value)` tuples of a Hinton dictionary, in insertion order.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The dictionary object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn entries(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   let entries = verify_dict_object!(this, "entries")
+      .borrow()
+      .iter()
+      .map(|(k, v)| Object::Tuple(Rc::new(vec![k.clone(), v.clone()])))
+      .collect();
+
+   vm.push_stack(Object::Array(Rc::new(RefCell::new(entries))))
+}
+
+/// Gets the value associated with a key in a Hinton dictionary. If the dictionary has no entry
+/// with the given key, returns the default value (or `null`) instead of raising a `KeyError`.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The dictionary object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn get(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let value = verify_dict_object!(this, "get")
+      .borrow()
+      .get_key(&args[0])
+      .cloned();
+   vm.push_stack(value.unwrap_or_else(|| args.get(1).cloned().unwrap_or(Object::Null)))
+}
+
+/// Creates an array with the keys of a Hinton dictionary, in insertion order.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The dictionary object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn keys(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   let keys = verify_dict_object!(this, "keys")
+      .borrow()
+      .keys()
+      .cloned()
+      .collect();
+
+   vm.push_stack(Object::Array(Rc::new(RefCell::new(keys))))
+}
+
+/// Gets the number of entries in a Hinton dictionary.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The dictionary object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn len(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   let len = verify_dict_object!(this, "len").borrow().len();
+   vm.push_stack(Object::from(len))
+}
+
+/// Adds the entries of another dictionary to a Hinton dictionary, and returns the dictionary.
+/// When both dictionaries have an entry with the same key, the value from the other dictionary
+/// overwrites the existing value, but the entry keeps its position.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The dictionary object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn merge(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   // The entries are copied first, so that a dictionary can be merged with itself.
+   let other: DictObject = match &args[0] {
+      Object::Dict(d) => d.borrow().clone(),
+      obj => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::TypeError,
+            message: format!(
+               "Expected argument of type 'Dict' for call to 'Dict.merge'. Got '{}' instead.",
+               obj.type_name()
+            ),
+         }
+      }
+   };
+
+   {
+      let mut dict = verify_dict_object!(&this, "merge").borrow_mut();
+
+      for (key, value) in other.iter() {
+         // The keys of the other dictionary are valid keys, so the insertion cannot fail.
+         let _ = dict.insert_key(key.clone(), value.clone());
+      }
+   }
+
+   vm.push_stack(this)
+}
+
+/// Removes the entry with the given key from a Hinton dictionary, and returns its value.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The dictionary object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn remove(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let removed = verify_dict_object!(this, "remove").borrow_mut().remove(&args[0]);
+
+   match removed {
+      Some(value) => vm.push_stack(value),
+      None => RuntimeResult::Error {
+         error: RuntimeErrorType::KeyError,
+         message: format!(
+            "Entry with key '{}' not found in the dictionary.",
+            args[0].as_plain_string()
+         ),
+      },
+   }
+}
+
+/// Creates an array with the values of a Hinton dictionary, in insertion order.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The dictionary object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn values(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   let values = verify_dict_object!(this, "values")
+      .borrow()
+      .values()
+      .cloned()
+      .collect();
+   vm.push_stack(Object::Array(Rc::new(RefCell::new(values))))
+}
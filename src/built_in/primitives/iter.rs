@@ -0,0 +1,82 @@
+use crate::built_in::natives::{get_next_in_iter, is_end_of_iter};
+use crate::built_in::primitives::HTPrimitive;
+use crate::built_in::NativeBoundMethod;
+use crate::errors::RuntimeErrorType;
+use crate::objects::class_obj::{ClassField, ClassObject};
+use crate::objects::Object;
+use crate::virtual_machine::{RuntimeResult, VM};
+use hashbrown::HashMap;
+
+/// Represents the Hinton `Iter` primitive class.
+///
+/// Iterators are created with the `iter(...)` native function, or with one of the iterator
+/// adapters (e.g., `zip(...)` or `take(...)`), and produce their items only as they are needed.
+pub struct IterClass(ClassObject);
+
+/// Implements the `HTPrimitive` trait for the `IterClass` primitive Hinton class.
+impl HTPrimitive for IterClass {
+   /// Gets the name class name of this Hinton primitive.
+   fn name(&self) -> String {
+      self.0.name.clone()
+   }
+
+   /// Gets the non-static members of this Hinton primitive.
+   fn members(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.members
+   }
+
+   /// Gets the static members of this Hinton primitive.
+   fn statics(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.statics
+   }
+
+   /// Gets the default class object stored in this Hinton primitive.
+   fn default() -> ClassObject {
+      let mut _self = IterClass(ClassObject::new("Iter"));
+
+      // >>>>>>> Class fields for the "Iter" primitive type to be added after this line
+      _self.bind_non_static_method("next", (0, 0), next as NativeBoundMethod);
+      // <<<<<<< Class fields for the "Iter" primitive type to be added before this line
+
+      _self.0
+   }
+}
+
+macro_rules! verify_iter_object {
+   ($o: expr, $prop_name: expr) => {
+      match $o {
+         Object::Iter(i) => i,
+         _ => {
+            return RuntimeResult::Error {
+               error: RuntimeErrorType::TypeError,
+               message: format!(
+                  "Property 'Iter.{}' requires that 'self' be an Iter. Found '{}' instead.",
+                  $prop_name,
+                  $o.type_name()
+               ),
+            }
+         }
+      }
+   };
+}
+
+/// Gets the next item produced by a Hinton iterator, or `null` if the
+/// iterator has no more items. This follows the same protocol as the
+/// `next()` method of user-defined iterators.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The iterator object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn next(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   let iter = verify_iter_object!(this, "next");
+
+   match get_next_in_iter(vm, &iter) {
+      Ok(item) => vm.push_stack(item),
+      Err(e) if is_end_of_iter(&e) => vm.push_stack(Object::Null),
+      Err(e) => e,
+   }
+}
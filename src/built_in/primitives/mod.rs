@@ -1,5 +1,27 @@
+use crate::built_in::natives::crypto::CryptoClass;
+use crate::built_in::natives::encoding::EncodingClass;
+use crate::built_in::natives::file::FileClass;
+use crate::built_in::natives::http::HttpClass;
+use crate::built_in::natives::json::JsonClass;
+use crate::built_in::natives::math::MathClass;
+use crate::built_in::natives::net::NetClass;
+use crate::built_in::natives::process::ProcessClass;
+use crate::built_in::natives::random::RandomClass;
+use crate::built_in::natives::regex::RegexClass;
+use crate::built_in::natives::time::TimeClass;
+use crate::built_in::natives::uuid::UuidClass;
 use crate::built_in::primitives::array::ArrayClass;
+use crate::built_in::primitives::bytes::BytesClass;
+use crate::built_in::primitives::channel::ChannelClass;
+use crate::built_in::primitives::char::CharClass;
+use crate::built_in::primitives::datetime::DateTimeClass;
+use crate::built_in::primitives::dict::DictClass;
 use crate::built_in::primitives::int::IntClass;
+use crate::built_in::primitives::iter::IterClass;
+use crate::built_in::primitives::promise::PromiseClass;
+use crate::built_in::primitives::range::RangeClass;
+use crate::built_in::primitives::set::SetClass;
+use crate::built_in::primitives::socket::SocketClass;
 use crate::built_in::primitives::string::StringClass;
 use crate::built_in::NativeBoundMethod;
 use crate::errors::RuntimeErrorType;
@@ -12,7 +34,17 @@ use std::rc::Rc;
 
 // Submodules
 mod array;
+mod bytes;
+mod channel;
+mod char;
+mod datetime;
+mod dict;
 mod int;
+mod iter;
+mod promise;
+mod range;
+mod set;
+mod socket;
 mod string;
 
 /// Represents the list of primitive classes available through a Hinton program.
@@ -26,10 +58,56 @@ impl Default for Primitives {
       // >>>>>> Primitive class definitions to be added after this line
       primitives.insert("Int".to_string(), Rc::new(RefCell::new(IntClass::default())));
       primitives.insert("Array".to_string(), Rc::new(RefCell::new(ArrayClass::default())));
+      primitives.insert("Bytes".to_string(), Rc::new(RefCell::new(BytesClass::default())));
+      primitives.insert(
+         "Channel".to_string(),
+         Rc::new(RefCell::new(ChannelClass::default())),
+      );
+      primitives.insert("Char".to_string(), Rc::new(RefCell::new(CharClass::default())));
+      primitives.insert(
+         "Crypto".to_string(),
+         Rc::new(RefCell::new(CryptoClass::default())),
+      );
+      primitives.insert(
+         "DateTime".to_string(),
+         Rc::new(RefCell::new(DateTimeClass::default())),
+      );
+      primitives.insert("Dict".to_string(), Rc::new(RefCell::new(DictClass::default())));
+      primitives.insert(
+         "Encoding".to_string(),
+         Rc::new(RefCell::new(EncodingClass::default())),
+      );
+      primitives.insert("File".to_string(), Rc::new(RefCell::new(FileClass::default())));
+      primitives.insert("Http".to_string(), Rc::new(RefCell::new(HttpClass::default())));
+      primitives.insert("Iter".to_string(), Rc::new(RefCell::new(IterClass::default())));
+      primitives.insert("Json".to_string(), Rc::new(RefCell::new(JsonClass::default())));
+      primitives.insert("Math".to_string(), Rc::new(RefCell::new(MathClass::default())));
+      primitives.insert("Net".to_string(), Rc::new(RefCell::new(NetClass::default())));
+      primitives.insert(
+         "Process".to_string(),
+         Rc::new(RefCell::new(ProcessClass::default())),
+      );
+      primitives.insert(
+         "Promise".to_string(),
+         Rc::new(RefCell::new(PromiseClass::default())),
+      );
+      primitives.insert(
+         "Random".to_string(),
+         Rc::new(RefCell::new(RandomClass::default())),
+      );
+      primitives.insert("Range".to_string(), Rc::new(RefCell::new(RangeClass::default())));
+      primitives.insert("Regex".to_string(), Rc::new(RefCell::new(RegexClass::default())));
+      primitives.insert("Set".to_string(), Rc::new(RefCell::new(SetClass::default())));
+      primitives.insert(
+         "Socket".to_string(),
+         Rc::new(RefCell::new(SocketClass::default())),
+      );
       primitives.insert(
          "String".to_string(),
          Rc::new(RefCell::new(StringClass::default())),
       );
+      primitives.insert("Time".to_string(), Rc::new(RefCell::new(TimeClass::default())));
+      primitives.insert("Uuid".to_string(), Rc::new(RefCell::new(UuidClass::default())));
       // <<<<<< Primitive class definitions to be added before this line
 
       Primitives(primitives)
@@ -0,0 +1,66 @@
+use crate::built_in::primitives::HTPrimitive;
+use crate::built_in::NativeBoundMethod;
+use crate::errors::RuntimeErrorType;
+use crate::objects::class_obj::{ClassField, ClassObject};
+use crate::objects::Object;
+use crate::virtual_machine::{RuntimeResult, VM};
+use hashbrown::HashMap;
+
+/// Represents the Hinton `Promise` primitive class. Promises are returned by calls to `async`
+/// functions and by the `delay(...)` native function, and their values are read with `await`.
+pub struct PromiseClass(ClassObject);
+
+/// Implements the `HTPrimitive` trait for the `PromiseClass` primitive Hinton class.
+impl HTPrimitive for PromiseClass {
+   /// Gets the name class name of this Hinton primitive.
+   fn name(&self) -> String {
+      self.0.name.clone()
+   }
+
+   /// Gets the non-static members of this Hinton primitive.
+   fn members(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.members
+   }
+
+   /// Gets the static members of this Hinton primitive.
+   fn statics(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.statics
+   }
+
+   /// Gets the default class object stored in this Hinton primitive.
+   fn default() -> ClassObject {
+      let mut _self = PromiseClass(ClassObject::new("Promise"));
+
+      // >>>>>>> Class fields for the "Promise" primitive type to be added after this line
+      _self.bind_non_static_method("is_settled", (0, 0), is_settled as NativeBoundMethod);
+      // <<<<<<< Class fields for the "Promise" primitive type to be added before this line
+
+      _self.0
+   }
+}
+
+/// Checks if a Hinton promise has been resolved or rejected, in which case awaiting
+/// the promise does not suspend the running green thread.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The promise object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn is_settled(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   match this {
+      Object::Promise(p) => {
+         let settled = p.borrow().is_settled();
+         vm.push_stack(Object::Bool(settled))
+      }
+      _ => RuntimeResult::Error {
+         error: RuntimeErrorType::TypeError,
+         message: format!(
+            "Property 'Promise.is_settled' requires that 'self' be a Promise. Found '{}' instead.",
+            this.type_name()
+         ),
+      },
+   }
+}
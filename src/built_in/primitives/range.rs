@@ -0,0 +1,126 @@
+use crate::built_in::primitives::HTPrimitive;
+use crate::built_in::NativeBoundMethod;
+use crate::errors::RuntimeErrorType;
+use crate::objects::class_obj::{ClassField, ClassObject};
+use crate::objects::Object;
+use crate::virtual_machine::{RuntimeResult, VM};
+use hashbrown::HashMap;
+
+/// Represents the Hinton `Range` primitive class.
+///
+/// Ranges are created with the `..` and `..=` operators (e.g., `0..10` or `0..=10`), or with
+/// the `range(...)` native function. A range whose end is less than its start counts down.
+pub struct RangeClass(ClassObject);
+
+/// Implements the `HTPrimitive` trait for the `RangeClass` primitive Hinton class.
+impl HTPrimitive for RangeClass {
+   /// Gets the name class name of this Hinton primitive.
+   fn name(&self) -> String {
+      self.0.name.clone()
+   }
+
+   /// Gets the non-static members of this Hinton primitive.
+   fn members(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.members
+   }
+
+   /// Gets the static members of this Hinton primitive.
+   fn statics(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.statics
+   }
+
+   /// Gets the default class object stored in this Hinton primitive.
+   fn default() -> ClassObject {
+      let mut _self = RangeClass(ClassObject::new("Range"));
+
+      // >>>>>>> Class fields for the "Range" primitive type to be added after this line
+      _self.bind_non_static_method("len", (0, 0), len as NativeBoundMethod);
+      _self.bind_non_static_method("reverse", (0, 0), reverse as NativeBoundMethod);
+      _self.bind_non_static_method("step", (1, 1), step as NativeBoundMethod);
+      // <<<<<<< Class fields for the "Range" primitive type to be added before this line
+
+      _self.0
+   }
+}
+
+macro_rules! verify_range_object {
+   ($o: expr, $prop_name: expr) => {
+      match $o {
+         Object::Range(r) => r,
+         _ => {
+            return RuntimeResult::Error {
+               error: RuntimeErrorType::TypeError,
+               message: format!(
+                  "Property 'Range.{}' requires that 'self' be a Range. Found '{}' instead.",
+                  $prop_name,
+                  $o.type_name()
+               ),
+            }
+         }
+      }
+   };
+}
+
+/// Gets the number of items produced by a Hinton range.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The range object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn len(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   vm.push_stack(Object::from(verify_range_object!(this, "len").len()))
+}
+
+/// Gets a Hinton range that produces the items of a range in reverse order
+/// (e.g., `(0..10..3).reverse()` produces 9, 6, 3, and 0).
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The range object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn reverse(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   match verify_range_object!(this, "reverse").reversed() {
+      Some(range) => vm.push_stack(Object::Range(range)),
+      None => RuntimeResult::Error {
+         error: RuntimeErrorType::ArgumentError,
+         message: String::from("Cannot reverse a range that starts at the smallest or largest Int."),
+      },
+   }
+}
+
+/// Gets a copy of a Hinton range that skips over items with the given step
+/// (e.g., `(0..10).step(3)` produces 0, 3, 6, and 9).
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The range object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn step(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let range = verify_range_object!(this, "step");
+
+   match args[0] {
+      Object::Int(step) => match range.with_step(step) {
+         Some(range) => vm.push_stack(Object::Range(range)),
+         None => RuntimeResult::Error {
+            error: RuntimeErrorType::ArgumentError,
+            message: format!("The step of a range must be positive. Found '{}' instead.", step),
+         },
+      },
+      _ => RuntimeResult::Error {
+         error: RuntimeErrorType::TypeError,
+         message: format!(
+            "Function 'Range.step' expected an Int argument. Found '{}' instead.",
+            args[0].type_name()
+         ),
+      },
+   }
+}
@@ -0,0 +1,234 @@
+use crate::built_in::primitives::HTPrimitive;
+use crate::built_in::NativeBoundMethod;
+use crate::errors::RuntimeErrorType;
+use crate::objects::class_obj::{ClassField, ClassObject};
+use crate::objects::set_obj::SetObject;
+use crate::objects::Object;
+use crate::virtual_machine::{RuntimeResult, VM};
+use hashbrown::HashMap;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Represents the Hinton `Set` primitive class. Sets are created with the `Set(...)` native
+/// function, which shadows the name of this class in Hinton programs.
+pub struct SetClass(ClassObject);
+
+/// Implements the `HTPrimitive` trait for the `SetClass` primitive Hinton class.
+impl HTPrimitive for SetClass {
+   /// Gets the name class name of this Hinton primitive.
+   fn name(&self) -> String {
+      self.0.name.clone()
+   }
+
+   /// Gets the non-static members of this Hinton primitive.
+   fn members(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.members
+   }
+
+   /// Gets the static members of this Hinton primitive.
+   fn statics(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.statics
+   }
+
+   /// Gets the default class object stored in this Hinton primitive.
+   fn default() -> ClassObject {
+      let mut _self = SetClass(ClassObject::new("Set"));
+
+      // >>>>>>> Class fields for the "Set" primitive type to be added after this line
+      _self.bind_non_static_method("add", (1, 1), add as NativeBoundMethod);
+      _self.bind_non_static_method("contains", (1, 1), contains as NativeBoundMethod);
+      _self.bind_non_static_method("difference", (1, 1), difference as NativeBoundMethod);
+      _self.bind_non_static_method("intersection", (1, 1), intersection as NativeBoundMethod);
+      _self.bind_non_static_method("len", (0, 0), len as NativeBoundMethod);
+      _self.bind_non_static_method("remove", (1, 1), remove as NativeBoundMethod);
+      _self.bind_non_static_method("to_array", (0, 0), to_array as NativeBoundMethod);
+      _self.bind_non_static_method("union", (1, 1), union as NativeBoundMethod);
+      // <<<<<<< Class fields for the "Set" primitive type to be added before this line
+
+      _self.0
+   }
+}
+
+macro_rules! verify_set_object {
+   ($maybe_set: expr, $prop_name: expr) => {
+      match $maybe_set {
+         Object::Set(s) => s,
+         _ => {
+            return RuntimeResult::Error {
+               error: RuntimeErrorType::TypeError,
+               message: format!(
+                  "Property 'Set.{}' requires that 'self' be a Set. Found '{}' instead.",
+                  $prop_name,
+                  $maybe_set.type_name()
+               ),
+            }
+         }
+      }
+   };
+}
+
+macro_rules! verify_set_arg {
+   ($arg: expr, $prop_name: expr) => {
+      match &$arg {
+         Object::Set(s) => s.borrow().clone(),
+         obj => {
+            return RuntimeResult::Error {
+               error: RuntimeErrorType::TypeError,
+               message: format!(
+                  "Expected argument of type 'Set' for call to 'Set.{}'. Got '{}' instead.",
+                  $prop_name,
+                  obj.type_name()
+               ),
+            }
+         }
+      }
+   };
+}
+
+/// Creates a new Hinton set object from a set of items.
+fn new_set(items: SetObject) -> Object {
+   Object::Set(Rc::new(RefCell::new(items)))
+}
+
+/// Adds an item to a Hinton set, if the set does not already contain it.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The set object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn add(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let inserted = verify_set_object!(this, "add")
+      .borrow_mut()
+      .insert(args[0].clone());
+
+   match inserted {
+      Ok(_) => vm.push_stack(Object::Null),
+      Err(e) => e.to_runtime_error(),
+   }
+}
+
+/// Checks if a Hinton set contains an item.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The set object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn contains(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let contains = verify_set_object!(this, "contains").borrow().contains(&args[0]);
+   vm.push_stack(Object::Bool(contains))
+}
+
+/// Creates a new set with the items of a Hinton set that are not in another set.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The set object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn difference(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let other = verify_set_arg!(args[0], "difference");
+   let set = verify_set_object!(this, "difference").borrow().clone();
+
+   match SetObject::from_items(set.iter().filter(|item| !other.contains(item)).cloned()) {
+      Ok(result) => vm.push_stack(new_set(result)),
+      Err(e) => e.to_runtime_error(),
+   }
+}
+
+/// Creates a new set with the items of a Hinton set that are also in another set.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The set object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn intersection(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let other = verify_set_arg!(args[0], "intersection");
+   let set = verify_set_object!(this, "intersection").borrow().clone();
+
+   match SetObject::from_items(set.iter().filter(|item| other.contains(item)).cloned()) {
+      Ok(result) => vm.push_stack(new_set(result)),
+      Err(e) => e.to_runtime_error(),
+   }
+}
+
+/// Gets the number of items in a Hinton set.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The set object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn len(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   let len = verify_set_object!(this, "len").borrow().len();
+   vm.push_stack(Object::from(len))
+}
+
+/// Removes an item from a Hinton set.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The set object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn remove(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   if verify_set_object!(this, "remove").borrow_mut().remove(&args[0]) {
+      vm.push_stack(Object::Null)
+   } else {
+      RuntimeResult::Error {
+         error: RuntimeErrorType::KeyError,
+         message: format!("Item '{}' not found in the set.", args[0].as_plain_string()),
+      }
+   }
+}
+
+/// Creates an array with the items of a Hinton set, in insertion order.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The set object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn to_array(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   let items = verify_set_object!(this, "to_array")
+      .borrow()
+      .iter()
+      .cloned()
+      .collect();
+   vm.push_stack(Object::Array(Rc::new(RefCell::new(items))))
+}
+
+/// Creates a new set with the items of a Hinton set followed by the items of another set.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The set object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn union(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let other = verify_set_arg!(args[0], "union");
+   let set = verify_set_object!(this, "union").borrow().clone();
+
+   match SetObject::from_items(set.iter().chain(other.iter()).cloned()) {
+      Ok(result) => vm.push_stack(new_set(result)),
+      Err(e) => e.to_runtime_error(),
+   }
+}
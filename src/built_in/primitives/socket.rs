@@ -0,0 +1,198 @@
+use crate::built_in::primitives::HTPrimitive;
+use crate::built_in::NativeBoundMethod;
+use crate::errors::RuntimeErrorType;
+use crate::objects::bytes_obj;
+use crate::objects::class_obj::{ClassField, ClassObject};
+use crate::objects::Object;
+use crate::virtual_machine::{RuntimeResult, VM};
+use hashbrown::HashMap;
+use std::io;
+
+/// Represents the Hinton `Socket` primitive class. Sockets are TCP connections opened with
+/// the `Net.connect(...)` native function.
+pub struct SocketClass(ClassObject);
+
+/// Implements the `HTPrimitive` trait for the `SocketClass` primitive Hinton class.
+impl HTPrimitive for SocketClass {
+   /// Gets the name class name of this Hinton primitive.
+   fn name(&self) -> String {
+      self.0.name.clone()
+   }
+
+   /// Gets the non-static members of this Hinton primitive.
+   fn members(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.members
+   }
+
+   /// Gets the static members of this Hinton primitive.
+   fn statics(&mut self) -> &mut HashMap<String, ClassField> {
+      &mut self.0.statics
+   }
+
+   /// Gets the default class object stored in this Hinton primitive.
+   fn default() -> ClassObject {
+      let mut _self = SocketClass(ClassObject::new("Socket"));
+
+      // >>>>>>> Class fields for the "Socket" primitive type to be added after this line
+      _self.bind_non_static_method("close", (0, 0), close as NativeBoundMethod);
+      _self.bind_non_static_method("is_closed", (0, 0), is_closed as NativeBoundMethod);
+      _self.bind_non_static_method("read", (0, 1), read as NativeBoundMethod);
+      _self.bind_non_static_method("read_line", (0, 0), read_line as NativeBoundMethod);
+      _self.bind_non_static_method("write", (1, 1), write as NativeBoundMethod);
+      // <<<<<<< Class fields for the "Socket" primitive type to be added before this line
+
+      _self.0
+   }
+}
+
+macro_rules! verify_socket_object {
+   ($maybe_socket: expr, $prop_name: expr) => {
+      match $maybe_socket {
+         Object::Socket(s) => s,
+         _ => {
+            return RuntimeResult::Error {
+               error: RuntimeErrorType::TypeError,
+               message: format!(
+                  "Property 'Socket.{}' requires that 'self' be a Socket. Found '{}' instead.",
+                  $prop_name,
+                  $maybe_socket.type_name()
+               ),
+            }
+         }
+      }
+   };
+}
+
+/// Converts a network error into a Hinton `IOError`.
+///
+/// # Arguments
+/// * `err`: The network error.
+/// * `peer`: The address of the other end of the connection.
+///
+/// # Returns:
+/// RuntimeResult
+fn io_error(err: io::Error, peer: &str) -> RuntimeResult {
+   RuntimeResult::Error {
+      error: RuntimeErrorType::IOError,
+      message: format!("{} (socket to '{}').", err, peer),
+   }
+}
+
+/// Closes a Hinton socket. Closing a socket that is already closed does nothing.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The socket object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn close(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   verify_socket_object!(this, "close").borrow_mut().close();
+   vm.push_stack(Object::Null)
+}
+
+/// Checks if a Hinton socket has been closed.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The socket object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn is_closed(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   let closed = verify_socket_object!(this, "is_closed").borrow().is_closed();
+   vm.push_stack(Object::Bool(closed))
+}
+
+/// Reads up to the given number of bytes (4096 by default) from a Hinton socket, into a
+/// Bytes object. The Bytes object is empty once the other end has closed the connection.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The socket object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn read(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let socket = verify_socket_object!(this, "read");
+
+   let max = match args.first() {
+      None => 4096,
+      Some(Object::Int(n)) if *n > 0 => *n as usize,
+      Some(arg) => {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::ArgumentError,
+            message: format!(
+               "Method 'Socket.read' expected a positive Int as the number of bytes. Found '{}' instead.",
+               arg
+            ),
+         }
+      }
+   };
+
+   let result = socket.borrow_mut().read(max);
+   match result {
+      Ok(bytes) => vm.push_stack(Object::from(bytes)),
+      Err(e) => io_error(e, &socket.borrow().peer),
+   }
+}
+
+/// Reads a line of text from a Hinton socket, without its line terminator, or `null`
+/// once the other end has closed the connection.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The socket object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn read_line(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   let socket = verify_socket_object!(this, "read_line");
+
+   let result = socket.borrow_mut().read_line();
+   match result {
+      Ok(Some(line)) => vm.push_stack(Object::from(line)),
+      Ok(None) => vm.push_stack(Object::Null),
+      Err(e) => io_error(e, &socket.borrow().peer),
+   }
+}
+
+/// Writes a String (as UTF-8), a Bytes object, or an Array of integer bytes to a Hinton socket.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The socket object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn write(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let socket = verify_socket_object!(this, "write");
+
+   let bytes = match &args[0] {
+      Object::String(s) => Some(s.as_bytes().to_vec()),
+      Object::Bytes(bytes) => Some(bytes.borrow().clone()),
+      Object::Array(arr) => bytes_obj::from_int_array(&arr.borrow()),
+      _ => None,
+   };
+
+   let bytes = match bytes {
+      Some(b) => b,
+      None => return RuntimeResult::Error {
+         error: RuntimeErrorType::TypeError,
+         message: String::from(
+            "Method 'Socket.write' expected a String, a Bytes object, or an Array of integers from 0 to 255.",
+         ),
+      },
+   };
+
+   let result = socket.borrow_mut().write(&bytes);
+   match result {
+      Ok(_) => vm.push_stack(Object::Null),
+      Err(e) => io_error(e, &socket.borrow().peer),
+   }
+}
@@ -1,12 +1,21 @@
+use crate::built_in::format::format_template;
 use crate::built_in::primitives::HTPrimitive;
 use crate::built_in::NativeBoundMethod;
 use crate::errors::RuntimeErrorType;
+use crate::objects::bytes_obj;
 use crate::objects::class_obj::{ClassField, ClassObject};
 use crate::objects::Object;
 use crate::virtual_machine::{RuntimeResult, VM};
 use hashbrown::HashMap;
+use std::cell::RefCell;
+use std::rc::Rc;
 
 /// Represents the Hinton `String` primitive class.
+///
+/// Strings are stored as UTF-8, but the lengths and indices used by string methods count
+/// characters (Unicode scalar values) rather than bytes, just like string subscripts and slices.
+/// This way, an index returned by `index_of(...)` can always be used to subscript the string.
+/// The number of bytes in the encoded string can be obtained with `byte_len()`.
 pub struct StringClass(ClassObject);
 
 /// Implements the `HTPrimitive` trait for the `StringClass` primitive Hinton class.
@@ -31,10 +40,20 @@ impl HTPrimitive for StringClass {
       let mut _self = StringClass(ClassObject::new("String"));
 
       // >>>>>>> Class fields for the "String" primitive type to be added after this line
+      _self.bind_non_static_method("byte_len", (0, 0), byte_len as NativeBoundMethod);
+      _self.bind_non_static_method("chars", (0, 0), chars as NativeBoundMethod);
+      _self.bind_non_static_method("contains", (1, 1), contains as NativeBoundMethod);
+      _self.bind_non_static_method("encode", (0, 1), encode as NativeBoundMethod);
       _self.bind_non_static_method("ends_with", (1, 1), ends_with as NativeBoundMethod);
+      _self.bind_non_static_method("format", (0, 255), format as NativeBoundMethod);
+      _self.bind_non_static_method("index_of", (1, 1), index_of as NativeBoundMethod);
       _self.bind_non_static_method("len", (0, 0), len as NativeBoundMethod);
+      _self.bind_non_static_method("replace", (2, 2), replace as NativeBoundMethod);
+      _self.bind_non_static_method("split", (0, 1), split as NativeBoundMethod);
       _self.bind_non_static_method("starts_with", (1, 1), starts_with as NativeBoundMethod);
       _self.bind_non_static_method("to_lower", (0, 0), to_lower as NativeBoundMethod);
+      _self.bind_non_static_method("to_upper", (0, 0), to_upper as NativeBoundMethod);
+      _self.bind_non_static_method("trim", (0, 0), trim as NativeBoundMethod);
       // <<<<<<< Class fields for the "String" primitive type to be added before this line
 
       _self.0
@@ -59,24 +78,24 @@ macro_rules! verify_string_object {
    };
 }
 
-/// Gets the length of a Hinton string.
+/// Gets the length of a Hinton string, in characters.
 ///
 /// # Arguments
 /// * `vm`: A mutable reference to the virtual machine.
-/// * `this`: The array object.
+/// * `this`: The string object.
 /// * `_`: A vector of objects that will serve as arguments to this method call.
 ///
 /// # Returns:
 /// RuntimeResult
 fn len(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
-   vm.push_stack(Object::from(verify_string_object!(this, "len").len()))
+   vm.push_stack(Object::from(verify_string_object!(this, "len").chars().count()))
 }
 
 /// Creates a copy of a string and lowercase it.
 ///
 /// # Arguments
 /// * `vm`: A mutable reference to the virtual machine.
-/// * `this`: The array object.
+/// * `this`: The string object.
 /// * `_`: A vector of objects that will serve as arguments to this method call.
 ///
 /// # Returns:
@@ -91,7 +110,7 @@ fn to_lower(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
 ///
 /// # Arguments
 /// * `vm`: A mutable reference to the virtual machine.
-/// * `this`: The array object.
+/// * `this`: The string object.
 /// * `args`: A vector of objects that will serve as arguments to this method call.
 ///
 /// # Returns:
@@ -113,7 +132,7 @@ fn ends_with(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
    };
 
    vm.push_stack(Object::Bool(
-      verify_string_object!(this, "ends_with").ends_with(&suffix),
+      verify_string_object!(this, "ends_with").ends_with(&*suffix),
    ))
 }
 
@@ -121,7 +140,7 @@ fn ends_with(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
 ///
 /// # Arguments
 /// * `vm`: A mutable reference to the virtual machine.
-/// * `this`: The array object.
+/// * `this`: The string object.
 /// * `args`: A vector of objects that will serve as arguments to this method call.
 ///
 /// # Returns:
@@ -144,6 +163,209 @@ fn starts_with(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
    };
 
    vm.push_stack(Object::Bool(
-      verify_string_object!(this, "starts_with").starts_with(&suffix),
+      verify_string_object!(this, "starts_with").starts_with(&*suffix),
+   ))
+}
+
+macro_rules! verify_string_arg {
+   ($arg: expr, $prop_name: expr) => {
+      match &$arg {
+         Object::String(s) => s.clone(),
+         obj => {
+            return RuntimeResult::Error {
+               error: RuntimeErrorType::TypeError,
+               message: format!(
+                  "Expected argument of type 'String' for call to 'String.{}'. Got '{}' instead.",
+                  $prop_name,
+                  obj.type_name()
+               ),
+            }
+         }
+      }
+   };
+}
+
+/// Gets the number of bytes in the UTF-8 encoding of a Hinton string.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The string object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn byte_len(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   vm.push_stack(Object::from(verify_string_object!(this, "byte_len").len()))
+}
+
+/// Creates an array with the characters of a Hinton string.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The string object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn chars(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   let chars = verify_string_object!(this, "chars")
+      .chars()
+      .map(|c| Object::from(c.to_string()))
+      .collect();
+
+   vm.push_stack(Object::Array(Rc::new(RefCell::new(chars))))
+}
+
+/// Checks if a string contains another string.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The string object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn contains(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let pattern = verify_string_arg!(args[0], "contains");
+   vm.push_stack(Object::Bool(
+      verify_string_object!(this, "contains").contains(&*pattern),
+   ))
+}
+
+/// Encodes a Hinton string into a Bytes object. The encoding defaults
+/// to `utf-8`, and can also be `ascii`, `latin-1`, or `hex`.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The string object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn encode(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let encoding = match args.first() {
+      Some(arg) => verify_string_arg!(arg, "encode"),
+      None => "utf-8".into(),
+   };
+
+   match bytes_obj::encode(&verify_string_object!(this, "encode"), &encoding) {
+      Ok(bytes) => vm.push_stack(Object::from(bytes)),
+      Err(message) => RuntimeResult::Error {
+         error: RuntimeErrorType::ArgumentError,
+         message,
+      },
+   }
+}
+
+/// Gets the character index of the first occurrence of another string in a string,
+/// or `null` if the other string does not occur in the string.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The string object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn index_of(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let pattern = verify_string_arg!(args[0], "index_of");
+   let string = verify_string_object!(this, "index_of");
+
+   match string.find(&*pattern) {
+      Some(byte_idx) => vm.push_stack(Object::from(string[..byte_idx].chars().count())),
+      None => vm.push_stack(Object::Null),
+   }
+}
+
+/// Creates a copy of a string where every occurrence of a pattern is replaced by another string.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The string object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn replace(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let pattern = verify_string_arg!(args[0], "replace");
+   let replacement = verify_string_arg!(args[1], "replace");
+
+   vm.push_stack(Object::from(
+      verify_string_object!(this, "replace").replace(&*pattern, &replacement),
    ))
 }
+
+/// Splits a string into an array of strings. Without arguments, the string is split at runs of
+/// whitespace. Otherwise, it is split at every occurrence of the given separator. An empty
+/// separator splits the string into its characters.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The string object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn split(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let separator = match args.first() {
+      Some(arg) => Some(verify_string_arg!(arg, "split")),
+      None => None,
+   };
+
+   let string = verify_string_object!(this, "split");
+
+   let parts: Vec<Object> = match separator {
+      None => string.split_whitespace().map(Object::from).collect(),
+      Some(sep) if sep.is_empty() => string.chars().map(|c| Object::from(c.to_string())).collect(),
+      Some(sep) => string.split(&*sep).map(Object::from).collect(),
+   };
+
+   vm.push_stack(Object::Array(Rc::new(RefCell::new(parts))))
+}
+
+/// Creates a copy of a string and uppercase it.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The string object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn to_upper(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   vm.push_stack(Object::from(
+      verify_string_object!(this, "to_upper").to_uppercase(),
+   ))
+}
+
+/// Creates a copy of a string without leading and trailing whitespace.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The string object.
+/// * `_`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn trim(vm: &mut VM, this: Object, _: Vec<Object>) -> RuntimeResult {
+   vm.push_stack(Object::from(verify_string_object!(this, "trim").trim()))
+}
+
+/// Creates a copy of a string where the `{}` placeholders are replaced by the arguments of the
+/// call, with the mini-language described in the `built_in::format` module.
+///
+/// # Arguments
+/// * `vm`: A mutable reference to the virtual machine.
+/// * `this`: The string object.
+/// * `args`: A vector of objects that will serve as arguments to this method call.
+///
+/// # Returns:
+/// RuntimeResult
+fn format(vm: &mut VM, this: Object, args: Vec<Object>) -> RuntimeResult {
+   let template = verify_string_object!(this, "format");
+
+   match format_template(vm, &template, &args) {
+      Ok(result) => vm.push_stack(Object::from(result)),
+      Err(e) => e,
+   }
+}
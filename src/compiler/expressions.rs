@@ -1,10 +1,13 @@
 use crate::compiler::symbols::SL;
+use crate::compiler::type_checker::BUILT_IN_TYPES;
 use crate::compiler::Compiler;
 use crate::core::ast::*;
 use crate::core::bytecode::OpCode;
-use crate::core::tokens::Token;
+use crate::core::tokens::{Token, TokenType};
 use crate::errors::CompilerErrorType;
+use crate::lexer::string_literal_value;
 use crate::objects::Object;
+use std::borrow::Cow;
 
 impl Compiler {
    /// Compiles a literal expression.
@@ -49,6 +52,7 @@ impl Compiler {
          UnaryExprType::ArithmeticNeg => OpCode::Negate,
          UnaryExprType::LogicNeg => OpCode::LogicNot,
          UnaryExprType::BitwiseNeg => OpCode::BitwiseNot,
+         UnaryExprType::Await => OpCode::Await,
       };
 
       self.emit_op_code(expression_op_code, expr.pos);
@@ -62,6 +66,10 @@ impl Compiler {
          return self.compile_logic_and_or_expr(expr);
       }
 
+      if let BinaryExprType::LogicIs = expr.opr_type {
+         return self.compile_is_expr(expr);
+      }
+
       // Compiles the binary operators.
       self.compile_node(&expr.left);
       self.compile_node(&expr.right);
@@ -74,10 +82,13 @@ impl Compiler {
          BinaryExprType::BitwiseXOR => OpCode::BitwiseXor,
          BinaryExprType::Division => OpCode::Divide,
          BinaryExprType::Expo => OpCode::Expo,
+         BinaryExprType::FloorDivision => OpCode::FloorDivide,
          BinaryExprType::LogicAND => unreachable!("'AND' expressions not compiled here."),
          BinaryExprType::LogicEQ => OpCode::Equals,
          BinaryExprType::LogicGreaterThan => OpCode::GreaterThan,
          BinaryExprType::LogicGreaterThanEQ => OpCode::GreaterThanEq,
+         BinaryExprType::LogicIn => OpCode::Contains,
+         BinaryExprType::LogicIs => unreachable!("'is' expressions not compiled here."),
          BinaryExprType::LogicLessThan => OpCode::LessThan,
          BinaryExprType::LogicLessThanEQ => OpCode::LessThanEq,
          BinaryExprType::LogicNotEQ => OpCode::NotEq,
@@ -88,6 +99,7 @@ impl Compiler {
          BinaryExprType::Nullish => OpCode::NullishCoalescing,
          BinaryExprType::Addition => OpCode::Add,
          BinaryExprType::Range => OpCode::MakeRange,
+         BinaryExprType::RangeInclusive => OpCode::MakeInclusiveRange,
       };
 
       self.emit_op_code(
@@ -96,6 +108,39 @@ impl Compiler {
       );
    }
 
+   /// Compiles an `is` expression, which tests the runtime type of a value. The rhs of the
+   /// expression is either the name of a built-in type (which, like in type annotations, cannot
+   /// be shadowed), or an expression that evaluates to a class or an interface.
+   fn compile_is_expr(&mut self, expr: &BinaryExprNode) {
+      self.compile_node(&expr.left);
+
+      match &*expr.right {
+         ASTNode::Identifier(id) if BUILT_IN_TYPES.contains(&id.token.lexeme.as_ref()) => {
+            // The `Void` type annotation refers to the `Null` type.
+            let name = match id.token.lexeme.as_ref() {
+               "Void" => "Null",
+               name => name,
+            };
+
+            self.add_literal_to_pool(Object::from(name), &id.token, true);
+         }
+         ASTNode::Identifier(_) | ASTNode::ObjectGetter(_) => self.compile_node(&expr.right),
+         _ => {
+            self.error_at_token(
+               &expr.opr_token,
+               CompilerErrorType::Syntax,
+               "Expected a type, class, or interface name after 'is'.",
+            );
+            return;
+         }
+      }
+
+      self.emit_op_code(
+         OpCode::InstanceOf,
+         (expr.opr_token.line_num, expr.opr_token.column_start),
+      );
+   }
+
    /// Compiles a ternary conditional expression.
    /// This is compiled similarly to how if statements are compiled.
    pub(super) fn compile_ternary_conditional_expr(&mut self, expr: &TernaryConditionalNode) {
@@ -131,8 +176,63 @@ impl Compiler {
       self.patch_jump(end_jump, &expr.opr_token);
    }
 
+   /// Compiles a chain of ordering comparisons (e.g., `a < b < c`), which is short-circuited
+   /// like a logic 'AND' expression and evaluates each operand only once.
+   pub(super) fn compile_comparison_chain(&mut self, expr: &ComparisonChainNode) {
+      self.compile_node(&expr.operands[0]);
+
+      let last = expr.operators.len() - 1;
+      let mut cleanup_jumps = vec![];
+
+      for (idx, (token, opr_type)) in expr.operators.iter().enumerate() {
+         let pos = (token.line_num, token.column_start);
+         let op_code = match opr_type {
+            BinaryExprType::LogicGreaterThan => OpCode::GreaterThan,
+            BinaryExprType::LogicGreaterThanEQ => OpCode::GreaterThanEq,
+            BinaryExprType::LogicLessThan => OpCode::LessThan,
+            BinaryExprType::LogicLessThanEQ => OpCode::LessThanEq,
+            _ => unreachable!("Can only chain ordering comparisons."),
+         };
+
+         self.compile_node(&expr.operands[idx + 1]);
+
+         if idx == last {
+            self.emit_op_code(op_code, pos);
+            break;
+         }
+
+         // Keeps a copy of the rhs below the lhs, so that it becomes the lhs of the next comparison.
+         self.emit_op_code(OpCode::DupTop, pos);
+         self.emit_op_code(OpCode::RotThree, pos);
+         self.emit_op_code(op_code, pos);
+         cleanup_jumps.push(self.emit_jump(OpCode::JumpIfFalseOrPop, token));
+      }
+
+      let token = &expr.operators[last].0;
+      let end_jump = self.emit_jump(OpCode::JumpForward, token);
+
+      // If a comparison is false, the copy of its rhs is popped from below the result.
+      for jump in cleanup_jumps {
+         self.patch_jump(jump, token);
+      }
+
+      let pos = (token.line_num, token.column_start);
+      self.emit_op_code(OpCode::RotTwo, pos);
+      self.emit_op_code(OpCode::PopStackTop, pos);
+
+      self.patch_jump(end_jump, token);
+   }
+
    /// Compiles an identifier expression.
    pub(super) fn compile_identifier_expr(&mut self, expr: &IdentifierExprNode) {
+      // A constant that is referenced before its declaration is replaced by its value.
+      if let Some(value) = self.forward_const(&expr.token) {
+         return self.compile_literal_expr(&LiteralExprNode {
+            value,
+            token: expr.token.clone(),
+         });
+      }
+
       if let Ok(res) = self.resolve_symbol(&expr.token, false) {
          self.named_variable(&res, &expr.token, false);
       }
@@ -150,11 +250,81 @@ impl Compiler {
          return;
       }
 
+      if self.current_class_scope().is_some_and(|c| c.in_static_method) {
+         self.error_at_token(
+            &expr.token,
+            CompilerErrorType::Reference,
+            "Cannot use 'self' in a static method.",
+         );
+
+         return;
+      }
+
       if let Ok(res) = self.resolve_symbol(&expr.token, false) {
          self.named_variable(&res, &expr.token, false);
       }
    }
 
+   /// Compiles a `super` method access expression. The method is searched for in the
+   /// superclass of the class where the expression appears, and bound to `self`.
+   pub(super) fn compile_super_expr(&mut self, expr: &SuperExprNode) {
+      let class = match self.classes.last() {
+         Some(c) => c,
+         None => {
+            self.error_at_token(
+               &expr.token,
+               CompilerErrorType::Reference,
+               "Cannot use 'super' outside of a class.",
+            );
+            return;
+         }
+      };
+
+      if class.in_static_method {
+         self.error_at_token(
+            &expr.token,
+            CompilerErrorType::Reference,
+            "Cannot use 'super' in a static method.",
+         );
+         return;
+      }
+
+      if !class.has_superclass {
+         self.error_at_token(
+            &expr.token,
+            CompilerErrorType::Reference,
+            "Cannot use 'super' in a class with no superclass.",
+         );
+         return;
+      }
+
+      let class_name = IdentifierExprNode {
+         token: class.name.clone(),
+      };
+
+      // The receiver of the method.
+      self.compile_self_expr(&SelfExprNode {
+         token: Token {
+            lexeme: Cow::Borrowed("self"),
+            ..expr.token.clone()
+         },
+      });
+
+      // The class where the expression appears, whose superclass has the method.
+      self.compile_identifier_expr(&class_name);
+
+      let method_name = Object::from(expr.method.lexeme.to_string());
+      let method_line_info = (expr.method.line_num, expr.method.column_start);
+
+      if let Some(pos) = self.add_literal_to_pool(method_name, &expr.method, false) {
+         if pos < 256 {
+            self.emit_op_code_with_byte(OpCode::GetSuper, pos as u8, method_line_info);
+         } else {
+            self.emit_op_code_with_short(OpCode::GetSuperLong, pos, method_line_info);
+         }
+      }
+   }
+
    /// Emits the appropriate opcode to either get or set a local or global variable.
    ///
    /// # Parameters
@@ -224,6 +394,7 @@ impl Compiler {
          ReassignmentType::Plus => self.emit_op_code(OpCode::Add, line_info),
          ReassignmentType::Minus => self.emit_op_code(OpCode::Subtract, line_info),
          ReassignmentType::Div => self.emit_op_code(OpCode::Divide, line_info),
+         ReassignmentType::FloorDiv => self.emit_op_code(OpCode::FloorDivide, line_info),
          ReassignmentType::Mul => self.emit_op_code(OpCode::Multiply, line_info),
          ReassignmentType::Expo => self.emit_op_code(OpCode::Expo, line_info),
          ReassignmentType::Mod => self.emit_op_code(OpCode::Modulus, line_info),
@@ -273,7 +444,7 @@ impl Compiler {
    pub(super) fn compile_object_setter_expr(&mut self, expr: &ObjectSetExprNode) {
       self.compile_node(&expr.target);
 
-      let prop_name = Object::from(expr.setter.lexeme.clone());
+      let prop_name = Object::from(expr.setter.lexeme.to_string());
       let prop_line_info = (expr.setter.line_num, expr.setter.column_start);
 
       if let Some(pos) = self.add_literal_to_pool(prop_name, &expr.setter, false) {
@@ -281,12 +452,18 @@ impl Compiler {
             // Proceed to directly reassign the variable.
             self.compile_node(&expr.value);
          } else {
-            // The expression `a.prop /= 2` expands to `a.prop = a.prop / 2`, so we
-            // must get the property's value onto the stack first.
-            self.compile_object_getter_expr(&ObjectGetExprNode {
-               target: expr.target.clone(),
-               getter: expr.setter.clone(),
-            });
+            // The expression `a.prop /= 2` expands to `a.prop = a.prop / 2`, so we must get
+            // the property's value onto the stack first. The object is duplicated instead of
+            // being evaluated again, so that its expression is only evaluated once.
+            self.emit_op_code(OpCode::DupTop, prop_line_info);
+
+            if pos < 256 {
+               self.emit_op_code_with_byte(OpCode::GetProp, pos as u8, prop_line_info);
+            } else {
+               self.emit_op_code_with_short(OpCode::GetPropLong, pos, prop_line_info);
+            }
+
+            self.emit_inline_cache(&expr.setter);
 
             // Then we push the other operand's value onto the stack
             self.compile_node(&expr.value);
@@ -300,22 +477,25 @@ impl Compiler {
          } else {
             self.emit_op_code_with_short(OpCode::SetPropLong, pos, prop_line_info);
          }
+
+         self.emit_inline_cache(&expr.setter);
       }
    }
 
    /// Compiles a subscripted assignment expression.
    pub(super) fn compile_subscript_assignment(&mut self, expr: &SubscriptAssignExprNode) {
+      self.compile_node(&expr.target);
+      self.compile_node(&expr.index);
+
       if let ReassignmentType::Assign = expr.opr_type {
          // Proceed to directly reassign the variable.
          self.compile_node(&expr.value);
       } else {
-         // The expression `a[1] /= 2` expands to `a[1] = a[1] / 2`, so we
-         // must get the array's value at the index onto the stack first.
-         self.compile_subscript_expr(&SubscriptExprNode {
-            target: expr.target.clone(),
-            index: expr.index.clone(),
-            pos: expr.pos,
-         });
+         // The expression `a[1] /= 2` expands to `a[1] = a[1] / 2`, so we must get the
+         // array's value at the index onto the stack first. The array and the index are
+         // duplicated instead of being evaluated again, so that they are only evaluated once.
+         self.emit_op_code(OpCode::DupTopTwo, expr.pos);
+         self.emit_op_code(OpCode::Subscript, expr.pos);
 
          // Then we push the other operand's value onto the stack
          self.compile_node(&expr.value);
@@ -324,8 +504,6 @@ impl Compiler {
          self.emit_compound_reassignment_opr(&expr.opr_type, expr.pos);
       }
 
-      self.compile_node(&expr.index);
-      self.compile_node(&expr.target);
       self.emit_op_code(OpCode::SubscriptAssign, expr.pos);
    }
 
@@ -333,7 +511,16 @@ impl Compiler {
    pub(super) fn compile_object_getter_expr(&mut self, expr: &ObjectGetExprNode) {
       self.compile_node(&expr.target);
 
-      let prop_name = Object::from(expr.getter.lexeme.clone());
+      // An optional access on `null` skips the rest of the chain, leaving `null` on the stack.
+      if expr.is_optional {
+         let jump = self.emit_jump(OpCode::JumpIfNull, &expr.getter);
+
+         if let Some(chain) = self.optional_chains.last_mut() {
+            chain.push(jump);
+         }
+      }
+
+      let prop_name = Object::from(expr.getter.lexeme.to_string());
       let prop_line_info = (expr.getter.line_num, expr.getter.column_start);
 
       if let Some(pos) = self.add_literal_to_pool(prop_name, &expr.getter, false) {
@@ -342,6 +529,32 @@ impl Compiler {
          } else {
             self.emit_op_code_with_short(OpCode::GetPropLong, pos, prop_line_info);
          }
+
+         self.emit_inline_cache(&expr.getter);
+      }
+   }
+
+   /// Compiles a chain of property accesses, calls, and subscripts with optional property
+   /// accesses, whose jumps for `null` targets land at the end of the chain.
+   pub(super) fn compile_optional_chain(&mut self, expr: &OptionalChainNode) {
+      self.optional_chains.push(vec![]);
+      self.compile_node(&expr.chain);
+
+      for jump in self.optional_chains.pop().unwrap_or_default() {
+         self.patch_jump(jump, &expr.token);
+      }
+   }
+
+   /// Compiles a non-null assertion expression.
+   pub(super) fn compile_non_null_assertion(&mut self, expr: &NonNullAssertionNode) {
+      self.compile_node(&expr.operand);
+
+      if let Some(pos) = self.add_literal_to_pool(Object::from(expr.source), &expr.token, false) {
+         self.emit_op_code_with_short(
+            OpCode::AssertNonNull,
+            pos,
+            (expr.token.line_num, expr.token.column_start),
+         );
       }
    }
 
@@ -371,6 +584,25 @@ impl Compiler {
       }
    }
 
+   /// Compiles a string interpolation expression.
+   pub(super) fn compile_string_interpolation(&mut self, expr: &StringInterpolationNode) {
+      if expr.parts.len() > (u8::MAX as usize) {
+         self.error_at_token(
+            &expr.token,
+            CompilerErrorType::MaxCapacity,
+            "Too many interpolated segments in the string.",
+         );
+         return;
+      }
+
+      for part in expr.parts.iter() {
+         self.compile_node(part);
+      }
+
+      let line_info = (expr.token.line_num, expr.token.column_start);
+      self.emit_op_code_with_byte(OpCode::BuildString, expr.parts.len() as u8, line_info);
+   }
+
    /// Compiles a tuple literal expression.
    pub(super) fn compile_tuple_expr(&mut self, expr: &TupleExprNode) {
       if expr.values.len() <= (u16::MAX as usize) {
@@ -418,21 +650,10 @@ impl Compiler {
       // Compile the key-value pairs and leave them on the stack
       for (key, value) in expr.keys.iter().zip(expr.values.iter()) {
          // Get the key name from the token
-         let name = if key.lexeme.starts_with('\"') {
-            let lexeme = key.lexeme.clone();
-
-            // Remove outer quotes from the source string
-            let lexeme = &lexeme[1..(lexeme.len() - 1)];
-
-            // Replace escaped characters with the actual representations
-            lexeme
-               .replace("\\n", "\n")
-               .replace("\\t", "\t")
-               .replace("\\r", "\r")
-               .replace("\\\\", "\\")
-               .replace("\\\"", "\"")
+         let name = if let TokenType::STRING = key.token_type {
+            string_literal_value(&key.lexeme)
          } else {
-            key.lexeme.clone()
+            key.lexeme.to_string()
          };
 
          self.add_literal_to_pool(Object::from(name), key, true);
@@ -454,18 +675,61 @@ impl Compiler {
       self.emit_op_code(OpCode::Subscript, expr.pos);
    }
 
+   /// Compiles a slicing expression. Omitted bounds and steps are compiled as `null`,
+   /// and replaced with their default values at runtime.
+   pub(super) fn compile_slice_expr(&mut self, expr: &SliceExprNode) {
+      self.compile_node(&expr.target);
+
+      for part in [&expr.start, &expr.end, &expr.step] {
+         match part {
+            Some(node) => self.compile_node(node),
+            None => self.emit_op_code(OpCode::LoadImmNull, expr.pos),
+         }
+      }
+
+      self.emit_op_code(OpCode::Slice, expr.pos);
+   }
+
    /// Compiles a function call or new instance expression.
    pub(super) fn compile_inst_or_func_call_expr(&mut self, expr: &FunctionCallExprNode, inst: bool) {
       // Compile the call's identifier
       self.compile_node(&expr.target);
 
-      // Compile call's arguments
+      // Compile call's arguments. Each named argument is preceded by its name,
+      // so that the VM can bind the argument to the matching parameter.
+      let mut named_count = 0u8;
       for arg in expr.args.iter() {
+         if let Some(name) = &arg.name {
+            self.add_literal_to_pool(Object::from(name.lexeme.to_string()), name, true);
+            named_count += 1;
+         }
+
          self.compile_node(&arg.value);
       }
 
       // Call the function or create an instance at runtime
-      if inst {
+      if expr.args.iter().any(|a| a.is_spread) {
+         let op_code = if inst {
+            OpCode::MakeInstanceSpread
+         } else {
+            OpCode::FuncCallSpread
+         };
+
+         self.emit_op_code_with_byte(op_code, expr.args.len() as u8, expr.pos);
+
+         for arg in expr.args.iter() {
+            self.emit_raw_byte(arg.is_spread as u8, expr.pos);
+         }
+      } else if named_count > 0 {
+         let op_code = if inst {
+            OpCode::MakeInstanceNamed
+         } else {
+            OpCode::FuncCallNamed
+         };
+
+         self.emit_op_code_with_byte(op_code, expr.args.len() as u8, expr.pos);
+         self.emit_raw_byte(named_count, expr.pos);
+      } else if inst {
          self.emit_op_code_with_byte(OpCode::MakeInstance, expr.args.len() as u8, expr.pos);
       } else {
          self.emit_op_code_with_byte(OpCode::FuncCall, expr.args.len() as u8, expr.pos);
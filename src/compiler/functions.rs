@@ -1,9 +1,9 @@
 use crate::compiler::symbols::{Symbol, SymbolTable, SymbolType};
-use crate::compiler::{Compiler, CompilerCtx, FunctionScope, UpValue};
+use crate::compiler::{Compiler, CompilerCtx, FunctionScope, PendingExit, UpValue};
 use crate::core::ast::*;
 use crate::core::bytecode::OpCode;
 use crate::core::chunk::Chunk;
-use crate::core::tokens::Token;
+use crate::core::tokens::{Token, TokenType};
 use crate::errors::CompilerErrorType;
 use crate::objects::{FuncObject, Object};
 
@@ -45,7 +45,7 @@ impl Compiler {
             is_captured: false,
          },
          _ => Symbol {
-            name: decl.name.lexeme.clone(),
+            name: decl.name.lexeme.to_string(),
             s_type: SymbolType::Func,
             is_initialized: true,
             depth: 0,
@@ -62,38 +62,65 @@ impl Compiler {
             min_arity: decl.arity.0,
             max_arity: decl.arity.1,
             chunk: Chunk::new(),
-            name: decl.name.lexeme.clone(),
-            up_val_count: 0,
+            name: match decl.name.token_type {
+               // The shorthand lambdas and trailing closures are named like the `fn` lambdas.
+               TokenType::BIT_OR | TokenType::LOGIC_OR | TokenType::L_CURLY => String::from("fn"),
+               _ => decl.name.lexeme.to_string(),
+            },
+            params: decl.params.iter().map(|p| p.name.lexeme.to_string()).collect(),
+            is_variadic: decl.params.last().is_some_and(|p| p.is_variadic),
+            up_val_names: vec![],
+            filepath: self.import_chain.last().cloned().unwrap_or_default(),
+            locals: vec![],
+            doc: decl.doc.clone(),
+            globals: vec![],
          },
          s_table: symbols,
          scope_depth: 0,
          loops: vec![],
          breaks: vec![],
+         try_depth: 0,
+         finally_scopes: vec![],
          up_values: vec![],
       });
 
       // compiles the parameters so that the compiler knows about their stack position.
       self.compile_parameters(&decl.params);
 
+      // An async function moves its call frame into a new green thread as soon as it is
+      // called, so the caller receives a promise while the body runs in the thread.
+      if decl.is_async {
+         self.emit_op_code(OpCode::Async, func_pos);
+      }
+
       // Compile the function's body
       if decl.body.is_empty() {
          self.emit_return(&None, func_pos, matches!(t, CompilerCtx::Init))
       } else {
-         for (index, node) in decl.body.iter().enumerate() {
-            self.compile_node(node);
+         self.warn_unreachable_code(&decl.body);
+
+         // A trailing closure returns the value of its last expression statement.
+         let (body, implicit_value) = match (&decl.name.token_type, decl.body.split_last()) {
+            (TokenType::L_CURLY, Some((ASTNode::ExpressionStmt(last), rest))) => (rest, Some(last)),
+            _ => (&decl.body[..], None),
+         };
+
+         let ends_in_return = self.compile_statements(body);
 
+         if let Some(last) = implicit_value {
+            self.compile_node(&last.child);
+            self.emit_return_instr(last.pos);
+         } else if !ends_in_return {
             // Emit an implicit `return` if the body does not end with a return.
-            if index == decl.body.len() - 1 {
-               match *node {
-                  ASTNode::ReturnStmt(_) => {}
-                  _ => self.emit_return(&None, func_pos, matches!(t, CompilerCtx::Init)),
-               }
-            };
+            self.emit_return(&None, func_pos, matches!(t, CompilerCtx::Init));
          }
       }
 
       // Pop all the symbols from the function's symbol table.
-      self.current_s_table_mut().pop_scope(0, true, true);
+      self.warn_unused_symbols(0);
+      self.current_s_table_mut().pop_scope(0, true);
+      self.end_local_ranges();
+      self.fuse_superinstructions();
 
       // Print the compiled function's chunk when the appropriate flag is on.
       #[cfg(feature = "show_bytecode")]
@@ -119,7 +146,7 @@ impl Compiler {
       if !matches!(t, CompilerCtx::Lambda) {
          if let CompilerCtx::Class = self.compiler_type {
             if self
-               .add_literal_to_pool(Object::from(decl.name.lexeme.clone()), &decl.name, true)
+               .add_literal_to_pool(Object::from(decl.name.lexeme.to_string()), &decl.name, true)
                .is_some()
             {
                self.emit_op_code(OpCode::AppendClassField, func_pos);
@@ -196,10 +223,13 @@ impl Compiler {
    fn bind_default_params(&mut self, decl: &FunctionDeclNode) {
       // Compiles the named parameters so that they can be on top
       // of the stack when the function gets composed at runtime.
-      for param in decl.params.iter() {
+      let mut defaults_count = 0u8;
+
+      for param in decl.params.iter().filter(|p| !p.is_variadic) {
          match &param.default {
             Some(expr) => {
                self.compile_node(&expr);
+               defaults_count += 1;
             }
             None => {
                if param.is_optional {
@@ -207,6 +237,7 @@ impl Compiler {
                      OpCode::LoadImmNull,
                      (param.name.line_num, param.name.column_start),
                   );
+                  defaults_count += 1;
                }
             }
          }
@@ -216,7 +247,7 @@ impl Compiler {
       // each of the named parameters to the function
       self.emit_op_code_with_byte(
          OpCode::BindDefaults,
-         (decl.arity.1 - decl.arity.0) as u8,
+         defaults_count,
          (decl.name.line_num, decl.name.column_start),
       );
    }
@@ -253,7 +284,36 @@ impl Compiler {
          return;
       }
 
-      self.emit_return(&stmt.value, (stmt.token.line_num, stmt.token.column_start), false)
+      // A `return` statement inside a `try` statement with a `finally` block runs the block first.
+      if self.current_func_scope().finally_scopes.is_empty() {
+         self.emit_return(&stmt.value, (stmt.token.line_num, stmt.token.column_start), false)
+      } else {
+         match &stmt.value {
+            Some(value) => self.compile_node(value),
+            None => self.emit_op_code(
+               OpCode::LoadImmNull,
+               (stmt.token.line_num, stmt.token.column_start),
+            ),
+         }
+
+         self.emit_finally_exit(PendingExit::Return, &stmt.token);
+      }
+   }
+
+   /// Compiles a `defer` statement. The deferred body is compiled as a lambda, which the
+   /// VM adds to the list of deferred calls of the current call frame.
+   pub(super) fn compile_defer_stmt(&mut self, stmt: &DeferStmtNode) {
+      if let CompilerCtx::Script = self.compiler_type {
+         self.error_at_token(
+            &stmt.token,
+            CompilerErrorType::Syntax,
+            "Cannot use 'defer' outside of a function.",
+         );
+         return;
+      }
+
+      self.compile_function_decl(&stmt.body, CompilerCtx::Lambda);
+      self.emit_op_code(OpCode::Defer, (stmt.token.line_num, stmt.token.column_start));
    }
 
    /// Emits bytecode to return out of a function at runtime.
@@ -265,13 +325,43 @@ impl Compiler {
       if init {
          self.emit_op_code_with_byte(OpCode::GetLocal, 0u8, token_pos);
       } else if let Some(node) = value {
-         self.compile_node(node);
+         match &**node {
+            ASTNode::FunctionCall(call) if self.is_tail_call(call) => {
+               self.compile_node(&call.target);
+               call.args.iter().for_each(|arg| self.compile_node(&arg.value));
+
+               // If the callee is not a Hinton function, the `TailCall` instruction performs
+               // a regular call, so the return instructions are still needed afterwards.
+               self.emit_op_code_with_byte(OpCode::TailCall, call.args.len() as u8, call.pos);
+            }
+            _ => self.compile_node(node),
+         }
       } else {
          self.emit_op_code(OpCode::LoadImmNull, token_pos);
       }
 
+      self.emit_return_instr(token_pos);
+   }
+
+   /// Checks if a call in a return statement can be compiled as a tail call, which reuses the
+   /// call frame of the current function. Calls with named or spread arguments, calls in the
+   /// main script, and calls inside a `try` statement are compiled as regular calls.
+   fn is_tail_call(&self, call: &FunctionCallExprNode) -> bool {
+      matches!(
+         self.compiler_type,
+         CompilerCtx::Function | CompilerCtx::Method | CompilerCtx::Lambda
+      ) && self.current_func_scope().try_depth == 0
+         && call.args.iter().all(|a| !a.is_named && !a.is_spread)
+   }
+
+   /// Emits the bytecode to close the captured locals of the current function, and return
+   /// the object at the top of the stack.
+   ///
+   /// # Parameters
+   /// - `token_pos`: The position of the return statement in the source code.
+   pub(super) fn emit_return_instr(&mut self, token_pos: (usize, usize)) {
       let depth = self.relative_scope_depth();
-      let symbols = self.current_s_table_mut().pop_scope(depth, false, false);
+      let symbols = self.current_s_table_mut().pop_scope(depth, false);
 
       for (i, is_captured) in symbols.iter().rev().enumerate() {
          if *is_captured {
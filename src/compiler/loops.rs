@@ -1,34 +1,47 @@
 use crate::compiler::symbols::SymbolType;
-use crate::compiler::{BreakScope, Compiler, LoopScope, LoopType};
+use crate::compiler::{BreakScope, Compiler, CompilerCtx, LoopScope, LoopType, PendingExit};
 use crate::core::ast::*;
 use crate::core::bytecode::OpCode;
-use crate::core::tokens::Token;
+use crate::core::tokens::{Token, TokenType};
 use crate::errors::CompilerErrorType;
+use std::borrow::Cow;
+
+/// The name of the closures that comprehensions are compiled into, as shown in tracebacks.
+const COMPREHENSION_FUNC_NAME: &str = "<comprehension>";
 
 impl Compiler {
-   /// Compiles a `while` statement.
+   /// Compiles a `while` statement, or an infinite `loop` statement.
    pub(super) fn compile_while_stmt(&mut self, stmt: &WhileStmtNode) {
+      if let TokenType::DO_KW = stmt.token.token_type {
+         return self.compile_do_while_stmt(stmt);
+      }
+
+      if let Some(condition) = &stmt.condition {
+         self.warn_constant_condition(condition, true);
+      }
+
       // We don't need to compile the loop if the condition is a
       // `false` literal because it will never execute.
-      if stmt.condition.is_false_literal() {
+      let constant_condition = match &stmt.condition {
+         Some(condition) => self.constant_condition(condition),
+         None => Some(true),
+      };
+
+      if constant_condition == Some(false) {
          return;
       }
 
-      let condition_is_truthy_lit = stmt.condition.is_truthy_literal();
+      let condition_is_truthy_lit = constant_condition == Some(true);
 
       let loop_start = self.current_chunk().len();
       // starts this loop's break scope
       let depth = self.relative_scope_depth() + 1;
-      self.current_func_scope_mut().loops.push(LoopScope {
-         position: loop_start,
-         loop_type: LoopType::While,
-         scope_depth: depth,
-      });
+      self.start_loop(loop_start, LoopType::While, depth, &stmt.label, None);
 
       // Only compile the condition if it is not a truthy literal or equivalent.
       let mut exit_jump = 0;
-      if !condition_is_truthy_lit {
-         self.compile_node(&stmt.condition);
+      if let (false, Some(condition)) = (condition_is_truthy_lit, &stmt.condition) {
+         self.compile_node(condition);
          exit_jump = self.emit_jump(OpCode::PopJumpIfFalse, &stmt.token);
       }
 
@@ -42,21 +55,251 @@ impl Compiler {
          self.patch_jump(exit_jump, &stmt.token);
       }
 
-      self.close_breaks(loop_start, &stmt.token);
-      self.current_func_scope_mut().loops.pop(); // ends this loop's break scope
+      self.end_loop(&stmt.token); // ends this loop's break scope
+   }
+
+   /// Compiles a `loop` expression, whose value is given by the `break` statements in its body.
+   pub(super) fn compile_loop_expr(&mut self, expr: &WhileStmtNode) {
+      let line_info = (expr.token.line_num, expr.token.column_start);
+
+      if let CompilerCtx::Class = self.compiler_type {
+         self.error_at_token(
+            &expr.token,
+            CompilerErrorType::Syntax,
+            "A 'loop' expression cannot be the value of a class field.",
+         );
+         return;
+      }
+
+      // The value of the loop lives in a stack slot that holds `null` until a `break`
+      // statement sets it.
+      self.emit_op_code(OpCode::LoadImmNull, line_info);
+
+      let (value_slot, is_hidden) = match self.start_value_slot("loop", &expr.token) {
+         Some(slot) => slot,
+         None => return,
+      };
+
+      let loop_start = self.current_chunk().len();
+      let depth = self.relative_scope_depth() + 1;
+      self.start_loop(loop_start, LoopType::While, depth, &expr.label, Some(value_slot));
+
+      self.compile_node(&expr.body);
+      self.emit_loop(loop_start, &expr.token);
+
+      self.end_loop(&expr.token);
+      self.end_value_slot(is_hidden);
+   }
+
+   /// Compiles an array or dictionary comprehension into the loops that build its collection.
+   pub(super) fn compile_comprehension(&mut self, expr: &ComprehensionExprNode) {
+      let line_info = (expr.token.line_num, expr.token.column_start);
+
+      // A comprehension is compiled into a closure that is called right away, so that the
+      // locals of its loops get their own call frame, and are not offset by the temporary
+      // objects on the stack around the comprehension (e.g., the callee of `f([x for ...])`).
+      let in_own_closure = self.current_func_scope().function.name == COMPREHENSION_FUNC_NAME
+         && self.current_chunk().is_empty();
+
+      if !in_own_closure {
+         let name = Token {
+            lexeme: Cow::Borrowed(COMPREHENSION_FUNC_NAME),
+            token_type: TokenType::IDENTIFIER,
+            ..expr.token.clone()
+         };
+
+         let closure = FunctionDeclNode {
+            name,
+            params: vec![].into_boxed_slice(),
+            arity: (0, 0),
+            body: vec![ASTNode::ReturnStmt(ReturnStmtNode {
+               token: expr.token.clone(),
+               value: Some(Box::new(ASTNode::Comprehension(expr.clone()))),
+            })]
+            .into_boxed_slice(),
+            return_type: None,
+            doc: None,
+            is_async: false,
+         };
+
+         self.compile_function_decl(&closure, CompilerCtx::Lambda);
+         self.emit_op_code_with_byte(OpCode::FuncCall, 0, line_info);
+         return;
+      }
+
+      // The collection starts empty, and lives in a stack slot while the loops add items to it.
+      let make_op = if expr.key.is_some() {
+         OpCode::MakeDict
+      } else {
+         OpCode::MakeArray
+      };
+      self.emit_op_code_with_byte(make_op, 0, line_info);
+
+      let (slot, is_hidden) = match self.start_value_slot("comprehension", &expr.token) {
+         Some(slot) => slot,
+         None => return,
+      };
+
+      self.compile_comprehension_clauses(expr, 0, slot);
+      self.end_value_slot(is_hidden);
+   }
+
+   /// Compiles the `for` clauses of a comprehension, starting at the given clause. The
+   /// innermost clause adds the value (or entry) of the comprehension to its collection.
+   ///
+   /// # Parameters
+   /// - `expr`: The comprehension expression.
+   /// - `idx`: The position of the clause to compile.
+   /// - `slot`: The stack slot that holds the collection of the comprehension.
+   fn compile_comprehension_clauses(&mut self, expr: &ComprehensionExprNode, idx: usize, slot: usize) {
+      let clause = match expr.clauses.get(idx) {
+         Some(clause) => clause,
+         None => {
+            let line_info = (expr.token.line_num, expr.token.column_start);
+            self.emit_get_local(slot, line_info);
+
+            match &expr.key {
+               Some(key) => {
+                  self.compile_node(key);
+                  self.compile_node(&expr.value);
+                  self.emit_op_code(OpCode::SubscriptAssign, line_info);
+                  self.emit_op_code(OpCode::PopStackTop, line_info);
+               }
+               None => {
+                  self.compile_node(&expr.value);
+                  self.emit_op_code(OpCode::ArrayPush, line_info);
+               }
+            }
+
+            return;
+         }
+      };
+
+      let ids = (&clause.id, &clause.value_id);
+      self.compile_for_loop(&clause.token, ids, &clause.iterator, &None, |c| {
+         let skip_jump = clause.condition.as_ref().map(|condition| {
+            c.compile_node(condition);
+            c.emit_jump(OpCode::PopJumpIfFalse, &clause.token)
+         });
+
+         c.compile_comprehension_clauses(expr, idx + 1, slot);
+
+         if let Some(jump) = skip_jump {
+            c.patch_jump(jump, &clause.token);
+         }
+      });
+   }
+
+   /// Reserves the stack slot that holds the value of an expression while it is built by a
+   /// loop. The value of a local declaration is kept in the slot of its (not yet initialized)
+   /// variable, while any other value is kept in the slot of a hidden local, which is removed
+   /// after the loop so that its value is left on the stack.
+   ///
+   /// # Parameters
+   /// - `kind`: The kind of expression, used in the name of the hidden local.
+   /// - `token`: A reference to the token associated with the expression.
+   ///
+   /// # Returns
+   /// - `Option<(usize, bool)>`: The slot, and whether it belongs to a hidden local.
+   fn start_value_slot(&mut self, kind: &str, token: &Token) -> Option<(usize, bool)> {
+      let in_local_decl = !self.is_global_scope()
+         && self
+            .current_s_table()
+            .symbols
+            .last()
+            .is_some_and(|s| !s.is_initialized && matches!(s.s_type, SymbolType::Var | SymbolType::Const));
+
+      if in_local_decl {
+         return Some((self.current_s_table().len() - 1, false));
+      }
+
+      self.current_func_scope_mut().scope_depth += 1;
+
+      let name = format!("<{} at #{}>", kind, self.current_chunk().len());
+      match self.emit_symbol(&name, token, SymbolType::Const) {
+         Ok(symbol_pos) => {
+            self.current_s_table_mut().mark_initialized(symbol_pos);
+            Some((symbol_pos, true))
+         }
+         Err(_) => None,
+      }
+   }
+
+   /// Removes the hidden local reserved by `start_value_slot(...)`, if any.
+   fn end_value_slot(&mut self, is_hidden: bool) {
+      if is_hidden {
+         self.current_func_scope_mut().s_table.pop();
+         self.end_local_ranges();
+         self.current_func_scope_mut().scope_depth -= 1;
+      }
+   }
+
+   /// Compiles a `do-while` statement, whose body runs before its condition is checked.
+   fn compile_do_while_stmt(&mut self, stmt: &WhileStmtNode) {
+      let condition = match &stmt.condition {
+         Some(condition) => condition,
+         None => unreachable!("A 'do-while' loop always has a condition."),
+      };
+
+      // Constant conditions are not warned about, because `do { ... } while false;` runs its
+      // body once (and can be exited early with `break`), while `while true` is a `loop`.
+      let loop_start = self.current_chunk().len();
+      let depth = self.relative_scope_depth() + 1;
+      self.start_loop(loop_start, LoopType::DoWhile, depth, &stmt.label, None);
+
+      self.compile_node(&stmt.body);
+
+      // The `continue` statements in the body jump forward to the condition.
+      self.patch_loop_jumps(true, &stmt.token);
+
+      match self.constant_condition(condition) {
+         Some(true) => self.emit_loop(loop_start, &stmt.token),
+         Some(false) => {}
+         None => {
+            self.compile_node(condition);
+            let exit_jump = self.emit_jump(OpCode::PopJumpIfFalse, &stmt.token);
+            self.emit_loop(loop_start, &stmt.token);
+            self.patch_jump(exit_jump, &stmt.token);
+         }
+      }
+
+      self.end_loop(&stmt.token);
    }
 
    /// Compiles a `for` statement.
    pub(super) fn compile_for_stmt(&mut self, stmt: &ForStmtNode) {
-      let loop_line_info = (stmt.token.line_num, stmt.token.column_start);
+      let ids = (&stmt.id, &stmt.value_id);
+      self.compile_for_loop(&stmt.token, ids, &stmt.iterator, &stmt.label, |c| {
+         c.warn_unreachable_code(&stmt.body);
+         c.compile_statements(&stmt.body);
+      });
+   }
+
+   /// Compiles a `for-in` loop over the items of an iterator.
+   ///
+   /// # Parameters
+   /// - `token`: A reference to the token associated with the loop.
+   /// - `ids`: The loop's variable, and its second variable, if it has one.
+   /// - `iterator`: The expression whose items are iterated over.
+   /// - `label`: The label of the loop, if it has one.
+   /// - `compile_body`: Compiles the body of the loop.
+   fn compile_for_loop(
+      &mut self,
+      token: &Token,
+      ids: (&IdentifierExprNode, &Option<IdentifierExprNode>),
+      iterator: &ASTNode,
+      label: &Option<Token>,
+      compile_body: impl FnOnce(&mut Self),
+   ) {
+      let loop_line_info = (token.line_num, token.column_start);
 
       // Create the iterator at runtime
-      self.compile_node(&stmt.iterator);
+      self.compile_node(iterator);
       self.emit_op_code(OpCode::MakeIter, loop_line_info);
 
       // Begin the loop
       let loop_start = self.current_chunk().len();
-      let exit_jump = self.emit_jump(OpCode::ForIterNextOrJump, &stmt.token);
+      let exit_jump = self.emit_jump(OpCode::ForIterNextOrJump, token);
 
       // Increment the scope for the loop's iterator
       self.current_func_scope_mut().scope_depth += 1;
@@ -66,7 +309,7 @@ impl Compiler {
       // symbol's value directly because of the format of its name.
       match self.emit_symbol(
          &format!("<for-loop at #{}>", loop_start),
-         &stmt.token,
+         token,
          SymbolType::Const,
       ) {
          Ok(symbol_pos) => self.current_s_table_mut().mark_initialized(symbol_pos),
@@ -79,42 +322,41 @@ impl Compiler {
 
       // Starts this loop's break scope
       let depth = self.relative_scope_depth();
-      self.current_func_scope_mut().loops.push(LoopScope {
-         position: loop_start,
-         loop_type: LoopType::ForIn,
-         scope_depth: depth,
-      });
+      self.start_loop(loop_start, LoopType::ForIn, depth, label, None);
 
-      // Declares the loop's identifier.
-      match self.declare_symbol(&stmt.id.token, SymbolType::Var) {
-         Ok(symbol_pos) => self.current_s_table_mut().mark_initialized(symbol_pos),
-         Err(_) => return,
+      // With two loop variables, the current item is unpacked into a pair.
+      if ids.1.is_some() {
+         self.emit_op_code_with_byte(OpCode::Unpack, 2, loop_line_info);
       }
 
-      // Compiles the loop's body
-      for node in stmt.body.iter() {
-         self.compile_node(node);
+      // Declares the loop's identifiers.
+      for id in std::iter::once(ids.0).chain(ids.1.iter()) {
+         match self.declare_symbol(&id.token, SymbolType::Var) {
+            Ok(symbol_pos) => self.current_s_table_mut().mark_initialized(symbol_pos),
+            Err(_) => return,
+         }
       }
 
+      // Compiles the loop's body
+      compile_body(self);
+
       // Ends the scope for the loop's body.
-      self.end_scope(&stmt.token);
+      self.end_scope(token);
 
       // Jump to the start of the loop
-      self.emit_loop(loop_start, &stmt.token);
-      self.patch_jump(exit_jump, &stmt.token);
+      self.emit_loop(loop_start, token);
+      self.patch_jump(exit_jump, token);
 
-      // Closes & patches all breaks associated with this loop
-      self.close_breaks(loop_start, &stmt.token);
-
-      // Ends this loop's break scope
-      self.current_func_scope_mut().loops.pop();
+      // Patches all breaks associated with this loop, and ends this loop's break scope
+      self.end_loop(token);
 
       // Removes the loop's iterator and ends the iterator scope.
       self.current_func_scope_mut().s_table.pop();
+      self.end_local_ranges();
       self.current_func_scope_mut().scope_depth -= 1;
    }
 
-   /// Compiles a `break` statement.
+   /// Compiles a `break` or `continue` statement.
    pub(super) fn compile_loop_branching_stmt(&mut self, stmt: &LoopBranchStmtNode) {
       if self.current_func_scope().loops.is_empty() {
          self.error_at_token(
@@ -128,60 +370,183 @@ impl Compiler {
          return;
       }
 
-      let current_loop = *self.current_func_scope().loops.last().unwrap();
-      let mut popped_scope =
-         self
-            .current_func_scope_mut()
-            .s_table
-            .pop_scope(current_loop.scope_depth, false, false);
+      // A labeled statement branches out of the innermost loop with that label, while an
+      // unlabeled statement branches out of the innermost loop.
+      let loops = &self.current_func_scope().loops;
+      let loop_idx = match &stmt.label {
+         Some(label) => match loops
+            .iter()
+            .rposition(|l| l.label.as_deref() == Some(label.lexeme.as_ref()))
+         {
+            Some(idx) => idx,
+            None => {
+               self.error_at_token(
+                  label,
+                  CompilerErrorType::Reference,
+                  &format!("Use of undeclared loop label '{}'.", label.lexeme),
+               );
+               return;
+            }
+         },
+         None => loops.len() - 1,
+      };
+
+      let target_loop = loops[loop_idx].clone();
+
+      // The value of a `break` statement is stored in the value slot of its `loop` expression.
+      if let Some(value) = &stmt.value {
+         let slot = match target_loop.value_slot {
+            Some(slot) => slot,
+            None => {
+               self.error_at_token(
+                  &stmt.token,
+                  CompilerErrorType::Syntax,
+                  "Only a 'loop' expression can 'break' with a value.",
+               );
+               return;
+            }
+         };
+
+         let line_info = (stmt.token.line_num, stmt.token.column_start);
+         self.compile_node(value);
+
+         if slot < 256 {
+            self.emit_op_code_with_byte(OpCode::SetLocal, slot as u8, line_info);
+         } else {
+            self.emit_op_code_with_short(OpCode::SetLocalLong, slot as u16, line_info);
+         }
+
+         self.emit_op_code(OpCode::PopStackTop, line_info);
+      }
+
+      self.emit_loop_branch(loop_idx, stmt.is_break, &stmt.token);
+   }
+
+   /// Emits the bytecode to branch out of a loop, or to its next iteration. A branch that
+   /// leaves a `try` statement with a `finally` block runs the block first.
+   ///
+   /// # Parameters
+   /// - `loop_idx`: The index of the target loop in the stack of loops.
+   /// - `is_break`: Whether the branch breaks out of the loop, or continues it.
+   /// - `token`: The token associated with the branch.
+   pub(super) fn emit_loop_branch(&mut self, loop_idx: usize, is_break: bool, token: &Token) {
+      let leaves_finally = self
+         .current_func_scope()
+         .finally_scopes
+         .last()
+         .is_some_and(|f| loop_idx < f.loop_depth);
+
+      if leaves_finally {
+         let exit = if is_break {
+            PendingExit::Break(loop_idx)
+         } else {
+            PendingExit::Continue(loop_idx)
+         };
+
+         return self.emit_finally_exit(exit, token);
+      }
+
+      let target_loop = self.current_func_scope().loops[loop_idx].clone();
+      let mut popped_scope = self
+         .current_func_scope_mut()
+         .s_table
+         .pop_scope(target_loop.scope_depth, false);
 
-      // If we are branching inside a for-in loop, also pop the loop's
+      // If we are breaking out of a for-in loop, also pop the loop's
       // iterator off the stack before exiting the loop.
-      if let super::LoopType::ForIn = current_loop.loop_type {
-         if stmt.is_break {
+      if let LoopType::ForIn = target_loop.loop_type {
+         if is_break {
             popped_scope.append(&mut vec![false]);
          }
       }
 
       // Emit the pop instructions
-      self.emit_stack_pops(popped_scope, &stmt.token);
+      self.emit_stack_pops(popped_scope, token);
 
-      if stmt.is_break {
-         // Jump out of the loop
-         let break_pos = self.emit_jump(OpCode::JumpForward, &stmt.token);
+      if is_break || matches!(target_loop.loop_type, LoopType::DoWhile) {
+         // Jump out of the loop (or to the condition of a do-while loop).
+         let break_pos = self.emit_jump(OpCode::JumpForward, token);
 
-         // Adds this break to the breaks list associated with the current loop so that it can
+         // Adds this jump to the breaks list associated with the target loop so that it can
          // be patched later.
          self.current_func_scope_mut().breaks.push(BreakScope {
-            parent_loop: current_loop,
+            loop_idx,
             chunk_pos: break_pos,
+            is_continue: !is_break,
          })
       } else {
-         self.emit_loop(current_loop.position, &stmt.token);
+         self.emit_loop(target_loop.position, token);
       }
    }
 
-   /// Patches all breaks associated with the current loop.
+   /// Starts the break scope of a loop.
    ///
    /// # Parameters
-   /// - `loop_start`: The position in the chunk of the loop instruction associated with this
-   /// break statement.
-   /// - `token`: A reference to the token associated with this break statement's loop
-   fn close_breaks(&mut self, loop_start: usize, token: &Token) {
-      // Looks for any break statements associated with this loop
-      let mut breaks: Vec<usize> = vec![];
-      for b in self
-         .current_func_scope()
-         .breaks
-         .iter()
-         .filter(|br| br.parent_loop.position == loop_start)
-      {
-         breaks.push(b.chunk_pos);
+   /// - `position`: The position of the loop's start in the chunk.
+   /// - `loop_type`: The type of the loop.
+   /// - `scope_depth`: The scope depth of the body of the loop.
+   /// - `label`: The label of the loop, if it has one.
+   /// - `value_slot`: The stack slot that holds the value of a `loop` expression.
+   fn start_loop(
+      &mut self,
+      position: usize,
+      loop_type: LoopType,
+      scope_depth: usize,
+      label: &Option<Token>,
+      value_slot: Option<usize>,
+   ) {
+      if let Some(label) = label {
+         let is_duplicate = self
+            .current_func_scope()
+            .loops
+            .iter()
+            .any(|l| l.label.as_deref() == Some(label.lexeme.as_ref()));
+
+         if is_duplicate {
+            self.error_at_token(
+               label,
+               CompilerErrorType::Duplication,
+               &format!("A loop labeled '{}' is already in scope.", label.lexeme),
+            );
+         }
       }
 
-      // Patches all the breaks associated with this loop
-      for b in breaks {
-         self.patch_jump(b, token);
+      self.current_func_scope_mut().loops.push(LoopScope {
+         position,
+         scope_depth,
+         loop_type,
+         label: label.as_ref().map(|l| l.lexeme.to_string()),
+         value_slot,
+      });
+   }
+
+   /// Patches all breaks associated with the current loop, and ends its break scope.
+   ///
+   /// # Parameters
+   /// - `token`: A reference to the token associated with the loop.
+   fn end_loop(&mut self, token: &Token) {
+      self.patch_loop_jumps(false, token);
+      self.current_func_scope_mut().loops.pop();
+   }
+
+   /// Patches the jumps of the `break` statements associated with the current loop so that
+   /// they jump to the current position in the chunk. The jumps are removed from the list of
+   /// breaks, so that a loop that starts at the same position is not affected by them.
+   ///
+   /// # Parameters
+   /// - `continues`: Whether to patch the forward jumps of the `continue` statements
+   /// of a `do-while` loop instead of its `break` statements.
+   /// - `token`: A reference to the token associated with the loop.
+   fn patch_loop_jumps(&mut self, continues: bool, token: &Token) {
+      let loop_idx = self.current_func_scope().loops.len() - 1;
+
+      let (jumps, rest) = std::mem::take(&mut self.current_func_scope_mut().breaks)
+         .into_iter()
+         .partition::<Vec<_>, _>(|b| b.loop_idx == loop_idx && b.is_continue == continues);
+      self.current_func_scope_mut().breaks = rest;
+
+      for b in jumps {
+         self.patch_jump(b.chunk_pos, token);
       }
    }
 }
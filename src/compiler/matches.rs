@@ -0,0 +1,611 @@
+use crate::compiler::symbols::SymbolType;
+use crate::compiler::Compiler;
+use crate::core::ast::*;
+use crate::core::bytecode::OpCode;
+use crate::core::tokens::{Token, TokenType};
+use crate::errors::CompilerErrorType;
+use crate::lexer::string_literal_value;
+use crate::objects::Object;
+use std::convert::TryFrom;
+
+/// The minimum number of integer keys covered by the arms of a `match`
+/// statement for it to be compiled into a jump table.
+const MIN_JUMP_TABLE_KEYS: usize = 4;
+
+/// The maximum number of entries in a jump table.
+const MAX_JUMP_TABLE_SIZE: usize = 1024;
+
+/// A step in the path from the subject of a `match` statement to a value nested in it.
+#[derive(Clone)]
+enum PathStep {
+   /// The item at an index of an array or a tuple.
+   Index(i64),
+   /// The value of a key in a dictionary.
+   Key(String),
+}
+
+/// A jump table for the arms of a `match` statement.
+struct JumpTable {
+   /// The lowest key in the table.
+   min: i64,
+   /// The index of the arm associated with each key in the table, starting from the lowest key.
+   entries: Vec<Option<usize>>,
+}
+
+impl Compiler {
+   /// Compiles a `match` statement.
+   pub(super) fn compile_match_stmt(&mut self, stmt: &MatchStmtNode) {
+      let line_info = (stmt.token.line_num, stmt.token.column_start);
+      let match_start = self.current_chunk().len();
+
+      self.compile_node(&stmt.subject);
+
+      self.warn_non_exhaustive_match(stmt);
+
+      // Increment the scope for the match's subject
+      self.current_func_scope_mut().scope_depth += 1;
+
+      // Emits a placeholder symbol for the match's subject, which lives on the stack until the
+      // end of the statement, so that the arms can compare against it (and so that branching
+      // out of an arm pops it off the stack).
+      let subject_pos = match self.emit_symbol(
+         &format!("<match at #{}>", match_start),
+         &stmt.token,
+         SymbolType::Const,
+      ) {
+         Ok(symbol_pos) => {
+            self.current_s_table_mut().mark_initialized(symbol_pos);
+            symbol_pos
+         }
+         Err(_) => return,
+      };
+
+      // Arms with dense integer keys are dispatched with a jump table. Other statements
+      // test the patterns of each arm in order, with a decision tree for nested patterns.
+      match Compiler::build_jump_table(stmt) {
+         Some(table) => self.compile_match_arms_with_table(stmt, &table, subject_pos),
+         None => self.compile_match_arms(stmt, subject_pos),
+      }
+
+      // Removes the match's subject and ends the subject's scope.
+      self.current_func_scope_mut().s_table.pop();
+      self.end_local_ranges();
+      self.emit_op_code(OpCode::PopStackTop, line_info);
+      self.current_func_scope_mut().scope_depth -= 1;
+   }
+
+   /// Compiles the arms of a `match` statement whose integer patterns are dispatched with a
+   /// jump table, which sends the numbers that are not keys in the table to the comparisons
+   /// below. Because the table already dispatches integers, only range patterns are compared
+   /// afterwards (to match floats).
+   ///
+   /// # Parameters
+   /// - `stmt`: The `match` statement.
+   /// - `table`: The jump table for the arms of the statement.
+   /// - `subject_pos`: The position of the match's subject in the symbol table.
+   fn compile_match_arms_with_table(&mut self, stmt: &MatchStmtNode, table: &JumpTable, subject_pos: usize) {
+      let table_pos = self.emit_jump_table(table, &stmt.token);
+
+      // The jumps from each arm's comparisons into the arm's body.
+      let mut arm_jumps: Vec<Vec<usize>> = vec![vec![]; stmt.arms.len()];
+
+      for (arm, jumps) in stmt.arms.iter().zip(arm_jumps.iter_mut()) {
+         for pattern in arm.patterns.iter() {
+            if matches!(pattern, MatchPattern::Literal(_)) {
+               continue;
+            }
+
+            let mut next_pattern = vec![];
+            self.emit_pattern_test(pattern, subject_pos, &mut vec![], &mut next_pattern);
+            jumps.push(self.emit_jump(OpCode::JumpForward, pattern.token()));
+
+            for jump in next_pattern {
+               self.patch_jump(jump, pattern.token());
+            }
+         }
+      }
+
+      // If no arm matched, jump to the default arm.
+      let default_jump = self.emit_jump(OpCode::JumpForward, &stmt.token);
+
+      let mut arm_starts = vec![];
+      let mut end_jumps = vec![];
+
+      for (i, (arm, jumps)) in stmt.arms.iter().zip(arm_jumps).enumerate() {
+         arm_starts.push(self.current_chunk().len());
+
+         for jump in jumps {
+            self.patch_jump(jump, &stmt.token);
+         }
+
+         self.compile_node(&arm.body);
+
+         // The last arm does not need to jump over the default arm if there is none.
+         if i < stmt.arms.len() - 1 || stmt.default.is_some() {
+            end_jumps.push(self.emit_jump(OpCode::JumpForward, &stmt.token));
+         }
+      }
+
+      let default_start = self.current_chunk().len();
+      self.patch_jump(default_jump, &stmt.token);
+
+      if let Some(default) = &stmt.default {
+         self.compile_node(default);
+      }
+
+      for jump in end_jumps {
+         self.patch_jump(jump, &stmt.token);
+      }
+
+      self.patch_jump_table(table, table_pos, &arm_starts, default_start, &stmt.token);
+   }
+
+   /// Compiles the arms of a `match` statement by testing the patterns of each arm in order.
+   /// When a pattern matches, the variables it binds are pushed onto the stack, and the guard
+   /// of the arm is evaluated before running its body.
+   ///
+   /// # Parameters
+   /// - `stmt`: The `match` statement.
+   /// - `subject_pos`: The position of the match's subject in the symbol table.
+   fn compile_match_arms(&mut self, stmt: &MatchStmtNode, subject_pos: usize) {
+      let mut end_jumps = vec![];
+
+      for arm in stmt.arms.iter() {
+         let token = arm.patterns[0].token();
+
+         // The jumps from each matching pattern into the arm's body.
+         let mut body_jumps = vec![];
+
+         for pattern in arm.patterns.iter() {
+            let mut next_pattern = vec![];
+            self.emit_pattern_test(pattern, subject_pos, &mut vec![], &mut next_pattern);
+            body_jumps.push(self.emit_jump(OpCode::JumpForward, pattern.token()));
+
+            for jump in next_pattern {
+               self.patch_jump(jump, pattern.token());
+            }
+         }
+
+         let next_arm = self.emit_jump(OpCode::JumpForward, token);
+
+         for jump in body_jumps {
+            self.patch_jump(jump, token);
+         }
+
+         // The variables bound by the pattern live in their own scope, around the guard and body.
+         self.current_func_scope_mut().scope_depth += 1;
+
+         let mut bindings = vec![];
+         self.collect_bindings(&arm.patterns[0], &mut vec![], &mut bindings);
+
+         for (name, path) in bindings {
+            self.emit_get_path(subject_pos, &path, name);
+
+            if let Ok(symbol_pos) = self.emit_symbol(&name.lexeme, name, SymbolType::Var) {
+               self.current_s_table_mut().mark_initialized(symbol_pos);
+            }
+         }
+
+         let guard_jump = arm.guard.as_ref().map(|guard| {
+            self.compile_node(guard);
+            self.emit_jump(OpCode::PopJumpIfFalse, token)
+         });
+
+         self.compile_node(&arm.body);
+
+         // If the guard is false, the bound variables are popped before testing the next arm.
+         let depth = self.relative_scope_depth();
+         let bound = self.current_s_table_mut().pop_scope(depth, false);
+         self.end_scope(token);
+         end_jumps.push(self.emit_jump(OpCode::JumpForward, token));
+
+         if let Some(guard_jump) = guard_jump {
+            self.patch_jump(guard_jump, token);
+            self.emit_stack_pops(bound, token);
+         }
+
+         self.patch_jump(next_arm, token);
+      }
+
+      if let Some(default) = &stmt.default {
+         self.compile_node(default);
+      }
+
+      for jump in end_jumps {
+         self.patch_jump(jump, &stmt.token);
+      }
+   }
+
+   /// Emits the instructions to test whether a value nested in the subject of a `match`
+   /// statement matches a pattern. The tests do not leave any values on the stack.
+   ///
+   /// # Parameters
+   /// - `pattern`: The pattern to be tested.
+   /// - `subject_pos`: The position of the match's subject in the symbol table.
+   /// - `path`: The path from the subject to the tested value.
+   /// - `fail_jumps`: The jumps emitted to skip the rest of the tests when the value does not
+   ///   match the pattern. These jumps should be patched to the next pattern's test by calling
+   ///   the `patch_jump(...)` function.
+   fn emit_pattern_test(
+      &mut self,
+      pattern: &MatchPattern,
+      subject_pos: usize,
+      path: &mut Vec<PathStep>,
+      fail_jumps: &mut Vec<usize>,
+   ) {
+      let token = pattern.token().clone();
+      let pos = (token.line_num, token.column_start);
+
+      match pattern {
+         MatchPattern::Wildcard(_) | MatchPattern::Binding(_) => {}
+         MatchPattern::Literal(lit) => {
+            self.emit_get_path(subject_pos, path, &token);
+            self.compile_literal_expr(lit);
+            self.emit_op_code(OpCode::Equals, pos);
+            fail_jumps.push(self.emit_jump(OpCode::PopJumpIfFalse, &token));
+         }
+         MatchPattern::Range(start, end) => {
+            // Range patterns only match numbers. An empty jump table sends every number to
+            // the comparisons below, and jumps over them for every other value.
+            self.emit_get_path(subject_pos, path, &token);
+            let guard = self.emit_jump_table(
+               &JumpTable {
+                  min: 0,
+                  entries: vec![],
+               },
+               &token,
+            );
+            self.emit_op_code(OpCode::PopStackTop, pos);
+
+            self.emit_get_path(subject_pos, path, &token);
+            self.compile_literal_expr(start);
+            self.emit_op_code(OpCode::GreaterThanEq, pos);
+            fail_jumps.push(self.emit_jump(OpCode::PopJumpIfFalse, &token));
+
+            self.emit_get_path(subject_pos, path, &token);
+            self.compile_literal_expr(end);
+            self.emit_op_code(OpCode::LessThan, pos);
+            fail_jumps.push(self.emit_jump(OpCode::PopJumpIfFalse, &token));
+            let matched = self.emit_jump(OpCode::JumpForward, &token);
+
+            // Values that are not numbers are popped off the stack before failing the test.
+            self.patch_jump(guard, &token);
+            self.emit_op_code(OpCode::PopStackTop, pos);
+            fail_jumps.push(self.emit_jump(OpCode::JumpForward, &token));
+            self.patch_jump(matched, &token);
+         }
+         MatchPattern::Array(_, items) => {
+            self.emit_get_path(subject_pos, path, &token);
+            self.add_literal_to_pool(Object::Int(items.len() as i64), &token, true);
+            self.emit_op_code(OpCode::MatchSequence, pos);
+            fail_jumps.push(self.emit_jump(OpCode::PopJumpIfFalse, &token));
+
+            for (idx, item) in items.iter().enumerate() {
+               path.push(PathStep::Index(idx as i64));
+               self.emit_pattern_test(item, subject_pos, path, fail_jumps);
+               path.pop();
+            }
+         }
+         MatchPattern::Dictionary(_, entries) => {
+            self.emit_get_path(subject_pos, path, &token);
+            self.add_literal_to_pool(Object::from("Dict"), &token, true);
+            self.emit_op_code(OpCode::InstanceOf, pos);
+            fail_jumps.push(self.emit_jump(OpCode::PopJumpIfFalse, &token));
+
+            for (key, value) in entries.iter() {
+               let key_pos = (key.line_num, key.column_start);
+
+               self.add_literal_to_pool(Object::from(dictionary_key(key)), key, true);
+               self.emit_get_path(subject_pos, path, key);
+               self.emit_op_code(OpCode::Contains, key_pos);
+               fail_jumps.push(self.emit_jump(OpCode::PopJumpIfFalse, key));
+
+               path.push(PathStep::Key(dictionary_key(key)));
+               self.emit_pattern_test(value, subject_pos, path, fail_jumps);
+               path.pop();
+            }
+         }
+         MatchPattern::Variant {
+            enum_name,
+            variant,
+            fields,
+         } => {
+            self.check_variant_pattern(enum_name, variant, fields);
+
+            self.emit_get_path(subject_pos, path, &token);
+            self.compile_identifier_expr(&IdentifierExprNode {
+               token: enum_name.clone(),
+            });
+            self.add_literal_to_pool(Object::from(variant.lexeme.as_ref()), variant, true);
+
+            // The length of the payload is only checked if the pattern lists its fields.
+            match fields {
+               Some(fields) => {
+                  self.add_literal_to_pool(Object::Int(fields.len() as i64), &token, true);
+               }
+               None => self.emit_op_code(OpCode::LoadImmNull, pos),
+            }
+
+            self.emit_op_code(OpCode::MatchVariant, pos);
+            fail_jumps.push(self.emit_jump(OpCode::PopJumpIfFalse, &token));
+
+            for (idx, field) in fields.iter().flat_map(|f| f.iter()).enumerate() {
+               path.push(PathStep::Index(idx as i64));
+               self.emit_pattern_test(field, subject_pos, path, fail_jumps);
+               path.pop();
+            }
+         }
+      }
+   }
+
+   /// Checks that the variant of a variant pattern exists in its enum, and that the pattern
+   /// lists as many fields as the variant's payload. Only the enums declared in the program
+   /// being compiled are checked.
+   ///
+   /// # Parameters
+   /// - `enum_name`: The name of the enum in the pattern.
+   /// - `variant`: The name of the variant in the pattern.
+   /// - `fields`: The patterns of the variant's payload.
+   fn check_variant_pattern(
+      &mut self,
+      enum_name: &Token,
+      variant: &Token,
+      fields: &Option<Box<[MatchPattern]>>,
+   ) {
+      let enum_obj = match self.enums.get(enum_name.lexeme.as_ref()) {
+         Some(e) => e.clone(),
+         None => return,
+      };
+
+      let expected = match enum_obj.variant_index(&variant.lexeme) {
+         Some(idx) => enum_obj.variants[idx].1.len(),
+         None => {
+            return self.error_at_token(
+               variant,
+               CompilerErrorType::Reference,
+               &format!("Enum '{}' has no variant '{}'.", enum_obj.name, variant.lexeme),
+            )
+         }
+      };
+
+      match fields {
+         Some(fields) if fields.len() != expected => self.error_at_token(
+            variant,
+            CompilerErrorType::Syntax,
+            &format!(
+               "Variant '{}.{}' has {} fields, but the pattern has {}.",
+               enum_obj.name,
+               variant.lexeme,
+               expected,
+               fields.len()
+            ),
+         ),
+         _ => {}
+      }
+   }
+
+   /// Collects the variables bound by a pattern, along with the path from the
+   /// subject of the `match` statement to the value bound to each variable.
+   fn collect_bindings<'a>(
+      &self,
+      pattern: &'a MatchPattern<'a>,
+      path: &mut Vec<PathStep>,
+      bindings: &mut Vec<(&'a Token<'a>, Vec<PathStep>)>,
+   ) {
+      match pattern {
+         MatchPattern::Binding(name) => bindings.push((name, path.clone())),
+         MatchPattern::Array(_, items) => {
+            for (idx, item) in items.iter().enumerate() {
+               path.push(PathStep::Index(idx as i64));
+               self.collect_bindings(item, path, bindings);
+               path.pop();
+            }
+         }
+         MatchPattern::Dictionary(_, entries) => {
+            for (key, value) in entries.iter() {
+               path.push(PathStep::Key(dictionary_key(key)));
+               self.collect_bindings(value, path, bindings);
+               path.pop();
+            }
+         }
+         MatchPattern::Variant {
+            fields: Some(fields), ..
+         } => {
+            for (idx, field) in fields.iter().enumerate() {
+               path.push(PathStep::Index(idx as i64));
+               self.collect_bindings(field, path, bindings);
+               path.pop();
+            }
+         }
+         _ => {}
+      }
+   }
+
+   /// Emits the instructions to load a value nested in the subject of a `match` statement.
+   ///
+   /// # Parameters
+   /// - `subject_pos`: The position of the match's subject in the symbol table.
+   /// - `path`: The path from the subject to the value.
+   /// - `token`: The token associated with these instructions.
+   fn emit_get_path(&mut self, subject_pos: usize, path: &[PathStep], token: &Token) {
+      let pos = (token.line_num, token.column_start);
+      self.emit_get_local(subject_pos, pos);
+
+      for step in path {
+         let key = match step {
+            PathStep::Index(idx) => Object::Int(*idx),
+            PathStep::Key(key) => Object::from(key.as_str()),
+         };
+
+         self.add_literal_to_pool(key, token, true);
+
+         self.emit_op_code(OpCode::Subscript, pos);
+      }
+   }
+
+   /// Emits a `GetLocal` or `GetLocalLong` instruction.
+   ///
+   /// # Parameters
+   /// - `idx`: The position of the local symbol in the symbol table.
+   /// - `pos`: The source line and column associated with this instruction.
+   pub(super) fn emit_get_local(&mut self, idx: usize, pos: (usize, usize)) {
+      if idx < 256 {
+         self.emit_op_code_with_byte(OpCode::GetLocal, idx as u8, pos);
+      } else {
+         self.emit_op_code_with_short(OpCode::GetLocalLong, idx as u16, pos);
+      }
+   }
+
+   /// Emits a `SetLocal` or `SetLocalLong` instruction.
+   ///
+   /// # Parameters
+   /// - `idx`: The position of the local symbol in the symbol table.
+   /// - `pos`: The source line and column associated with this instruction.
+   pub(super) fn emit_set_local(&mut self, idx: usize, pos: (usize, usize)) {
+      if idx < 256 {
+         self.emit_op_code_with_byte(OpCode::SetLocal, idx as u8, pos);
+      } else {
+         self.emit_op_code_with_short(OpCode::SetLocalLong, idx as u16, pos);
+      }
+   }
+
+   /// Builds a jump table for the arms of a `match` statement, if all of its patterns are
+   /// integers, and the integer keys they cover are dense enough for a table to be worth it.
+   ///
+   /// # Parameters
+   /// - `stmt`: The `match` statement.
+   ///
+   /// # Returns
+   /// `Option<JumpTable>`: The jump table, or `None` if the statement should be
+   /// compiled into cascaded comparisons instead.
+   fn build_jump_table(stmt: &MatchStmtNode) -> Option<JumpTable> {
+      // The range of keys (start inclusive, end exclusive) covered by each pattern.
+      let mut intervals: Vec<(i64, i64, usize)> = vec![];
+
+      for (i, arm) in stmt.arms.iter().enumerate() {
+         if arm.guard.is_some() {
+            return None;
+         }
+
+         for pattern in arm.patterns.iter() {
+            let (start, end) = match pattern {
+               MatchPattern::Literal(LiteralExprNode {
+                  value: Object::Int(x),
+                  ..
+               }) => (*x, x.checked_add(1)?),
+               MatchPattern::Range(start, end) => (start.value.as_int()?, end.value.as_int()?),
+               _ => return None,
+            };
+
+            // Empty ranges never match.
+            if start < end {
+               intervals.push((start, end, i));
+            }
+         }
+      }
+
+      let min = intervals.iter().map(|i| i.0).min()?;
+      let max = intervals.iter().map(|i| i.1).max()?;
+      let size = max
+         .checked_sub(min)
+         .filter(|s| *s as usize <= MAX_JUMP_TABLE_SIZE)? as usize;
+
+      // Patterns are matched in order, so keys covered by more than one arm belong to the first.
+      let mut entries = vec![None; size];
+      for (start, end, arm) in intervals {
+         for entry in entries[(start - min) as usize..(end - min) as usize].iter_mut() {
+            entry.get_or_insert(arm);
+         }
+      }
+
+      // Only use the table if at least half of its entries are covered by the arms.
+      let keys = entries.iter().filter(|e| e.is_some()).count();
+      if keys < MIN_JUMP_TABLE_KEYS || keys * 2 < size {
+         return None;
+      }
+
+      Some(JumpTable { min, entries })
+   }
+
+   /// Emits a `JumpTable` instruction with placeholder jump offsets. These offsets
+   /// should be later replaced by calling the `patch_jump_table(...)` function.
+   ///
+   /// # Parameters
+   /// - `table`: The jump table.
+   /// - `token`: The token associated with the `match` statement.
+   ///
+   /// # Returns
+   /// `usize`: The position in the chunk of the table's jump offsets.
+   fn emit_jump_table(&mut self, table: &JumpTable, token: &Token) -> usize {
+      let pos = (token.line_num, token.column_start);
+      let min_idx = self
+         .add_literal_to_pool(Object::Int(table.min), token, false)
+         .unwrap_or(0);
+
+      self.emit_op_code(OpCode::JumpTable, pos);
+      self.emit_raw_short(min_idx, pos);
+      self.emit_raw_short(table.entries.len() as u16, pos);
+
+      let table_pos = self.current_chunk().len();
+
+      // One offset for values that are not numbers, and one offset for each entry.
+      for _ in 0..=table.entries.len() {
+         self.emit_raw_short(0xffff, pos);
+      }
+
+      table_pos
+   }
+
+   /// Patches the jump offsets of a `JumpTable` instruction. Values that are not numbers, and
+   /// the entries not covered by any arm jump to the default arm, while the other entries
+   /// jump to the body of their arm.
+   ///
+   /// # Parameters
+   /// - `table`: The jump table.
+   /// - `table_pos`: The position in the chunk of the table's jump offsets.
+   /// - `arm_starts`: The position in the chunk of the body of each arm.
+   /// - `default_start`: The position in the chunk of the default arm's body.
+   /// - `token`: The token associated with the `match` statement.
+   fn patch_jump_table(
+      &mut self,
+      table: &JumpTable,
+      table_pos: usize,
+      arm_starts: &[usize],
+      default_start: usize,
+      token: &Token,
+   ) {
+      let table_end = table_pos + (table.entries.len() + 1) * 2;
+
+      let targets = std::iter::once(default_start).chain(table.entries.iter().map(|e| match e {
+         Some(arm) => arm_starts[*arm],
+         None => default_start,
+      }));
+
+      for (i, target) in targets.enumerate() {
+         match u16::try_from(target - table_end) {
+            Ok(offset) => {
+               let offset = offset.to_be_bytes();
+               self.current_chunk_mut().modify_byte(table_pos + i * 2, offset[0]);
+               self
+                  .current_chunk_mut()
+                  .modify_byte(table_pos + i * 2 + 1, offset[1]);
+            }
+            Err(_) => {
+               return self.error_at_token(
+                  token,
+                  CompilerErrorType::MaxCapacity,
+                  "Too much code to jump over.",
+               )
+            }
+         }
+      }
+   }
+}
+
+/// Gets the key of a dictionary pattern's entry, which is either an identifier or a string.
+fn dictionary_key(key: &Token) -> String {
+   match key.token_type {
+      TokenType::STRING => string_literal_value(&key.lexeme),
+      _ => key.lexeme.to_string(),
+   }
+}
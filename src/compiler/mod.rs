@@ -1,33 +1,61 @@
 use crate::built_in::BuiltIn;
-use crate::compiler::symbols::{Symbol, SymbolTable, SymbolType};
+use crate::compiler::symbols::{Symbol, SymbolTable};
+use crate::compiler::type_checker::TypeChecker;
 use crate::core::ast::{ASTNode, ModuleNode};
 use crate::core::bytecode::OpCode;
 use crate::core::chunk::Chunk;
 use crate::core::tokens::Token;
-use crate::errors::{CompilerErrorType, ErrorReport};
+use crate::errors::{CompilerErrorType, ErrorReport, WarningReport};
+use crate::objects::class_obj::InterfaceObject;
+use crate::objects::enum_obj::EnumObject;
 use crate::objects::{FuncObject, Object};
+use hashbrown::HashMap;
 use std::convert::TryFrom;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
 
 // Submodules
 mod expressions;
 mod functions;
 mod loops;
+mod matches;
+mod modules;
+mod optimizer;
 mod resolver;
 mod statements;
 mod symbols;
+mod type_checker;
+mod warnings;
+
+pub use optimizer::OptLevel;
+pub use symbols::{Declaration, Reference, SymbolIndex, SymbolType};
+pub use warnings::WarningMode;
+
+/// The result of analyzing a program for editor tooling, which compiles the
+/// program to find its errors and warnings, and to resolve its symbols.
+pub struct Analysis {
+   /// The compile-time errors found in the program.
+   pub errors: Vec<ErrorReport>,
+   /// The compiler warnings generated for the program.
+   pub warnings: Vec<WarningReport>,
+   /// The declarations made in the program and the identifiers that refer to them.
+   pub symbols: SymbolIndex,
+}
 
 /// Represents a break statement, which is associated with a loop.
 struct BreakScope {
-   /// The loop scope associated with this break statement.
-   parent_loop: LoopScope,
+   /// The index of the loop associated with this break statement in the stack of loops.
+   loop_idx: usize,
    /// The position of the break's instruction in the chunk.
    chunk_pos: usize,
+   /// Whether this is the jump of a `continue` statement in a `do-while` loop, which goes
+   /// forward to the loop's condition rather than out of the loop.
+   is_continue: bool,
 }
 
 /// Represents a loop statement at compile time. Used primarily by
 /// break statements to know which loops to break.
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 struct LoopScope {
    /// The position of the loop's start in the chunk.
    position: usize,
@@ -35,6 +63,11 @@ struct LoopScope {
    scope_depth: usize,
    /// The type of loop this represents.
    loop_type: LoopType,
+   /// The label of the loop, if it has one.
+   label: Option<String>,
+   /// The stack slot that holds the value of a `loop` expression, which is
+   /// set by its `break` statements. This is `None` for loop statements.
+   value_slot: Option<usize>,
 }
 
 /// The types of loops available in Hinton. This is useful when \
@@ -42,6 +75,7 @@ struct LoopScope {
 /// based on the loop type.
 #[derive(Clone, Copy)]
 enum LoopType {
+   DoWhile,
    ForIn,
    While,
 }
@@ -75,6 +109,42 @@ pub struct FunctionScope {
    loops: Vec<LoopScope>,
    /// A list of break statements made inside a loop.
    breaks: Vec<BreakScope>,
+   /// The number of `try` statements that enclose the code being compiled. Calls
+   /// inside a `try` statement are never compiled as tail calls, because their
+   /// errors must be caught by the handlers of the current call frame.
+   try_depth: usize,
+   /// The `try` statements with a `finally` block whose `try` or `catch` blocks enclose
+   /// the code being compiled, where the last element is the inner-most statement.
+   finally_scopes: Vec<FinallyScope>,
+}
+
+/// Represents a `try` statement with a `finally` block at compile time. The `return`, `break`,
+/// and `continue` statements that leave its `try` or `catch` blocks jump into the `finally`
+/// block, which completes the pending exit after it runs.
+struct FinallyScope {
+   /// The number of loops that enclose the `try` statement. Branching statements that target
+   /// one of these loops leave the `try` statement.
+   loop_depth: usize,
+   /// The number of locals on the stack when the `try` block starts.
+   stack_depth: usize,
+   /// The stack slot that holds the code of the pending exit, or `0` if there is none.
+   action_slot: usize,
+   /// The stack slot that holds the value of a pending `return` statement.
+   value_slot: usize,
+   /// The pending exits, where the code of each exit is its position in the list plus one.
+   exits: Vec<PendingExit>,
+   /// The positions in the chunk of the jumps into the `finally` block.
+   jumps: Vec<usize>,
+}
+
+/// An exit from a `try` statement that runs after its `finally` block.
+#[derive(Clone, Copy, PartialEq)]
+enum PendingExit {
+   Return,
+   /// A `break` statement, with the index of its target loop in the stack of loops.
+   Break(usize),
+   /// A `continue` statement, with the index of its target loop in the stack of loops.
+   Continue(usize),
 }
 
 #[derive(Clone)]
@@ -92,6 +162,12 @@ pub struct UpValue {
 
 pub struct ClassScope {
    members: SymbolTable,
+   /// The name of the class.
+   name: Token<'static>,
+   /// Whether the class inherits from a superclass.
+   has_superclass: bool,
+   /// Whether the compiler is currently compiling a static method of the class.
+   in_static_method: bool,
 }
 
 /// Represents the compiler and its internal state.
@@ -108,16 +184,52 @@ pub struct Compiler {
    primitives: Vec<String>,
    /// A list of compiler errors generated while compiling the program.
    errors: Vec<ErrorReport>,
+   /// A list of compiler warnings generated while compiling the program.
+   warnings: Vec<WarningReport>,
+   /// Determines how the warnings generated while compiling the program are handled.
+   warning_mode: WarningMode,
+   /// Determines which optimizations are applied to the program.
+   opt_level: OptLevel,
+   /// The directories searched for imported modules that cannot
+   /// be found relative to the importing file.
+   module_dirs: Vec<PathBuf>,
+   /// Whether the program can import modules, which are read from the host's filesystem.
+   system_access: bool,
    /// The type of chunk currently being compiled.
    compiler_type: CompilerCtx,
    /// A list of class declarations made inside the program, where
    /// the last element of this vector represents the inner-most
    /// class declaration currently being compiled.
    classes: Vec<ClassScope>,
+   /// The jumps to the end of each optional chain currently being compiled, emitted by the
+   /// optional property accesses (`?.`) that find `null`. The last element of this vector
+   /// holds the jumps of the inner-most chain.
+   optional_chains: Vec<Vec<usize>>,
+   /// The interfaces declared in the program, by name. Used to check at compile time
+   /// that the classes which implement an interface define its methods.
+   interfaces: HashMap<String, Rc<InterfaceObject>>,
+   /// The enums declared in the program, by name. Used to check the variant patterns of
+   /// `match` arms, and whether a `match` statement covers every variant of an enum.
+   enums: HashMap<String, Rc<EnumObject>>,
+   /// The values of the top-level constants of the module whose initializers are constant
+   /// expressions, by name. These constants can be referenced before their declaration.
+   consts: HashMap<String, Object>,
+   /// The paths of the modules currently being compiled, where the
+   /// last element is the path of the file this compiler is compiling.
+   /// Used to detect circular imports.
+   import_chain: Vec<PathBuf>,
+   /// Whether the compiler is compiling an imported module (whose
+   /// top-level declarations are local to the module) or the main script.
+   is_module: bool,
+   /// The top-level declarations exported by the module.
+   exports: Vec<Token<'static>>,
+   /// The declarations and references resolved while compiling the program,
+   /// which are only recorded when the program is being analyzed.
+   symbol_index: Option<SymbolIndex>,
 }
 
 impl Compiler {
-   /// Compiles an Abstract Syntax Tree into ByteCode.
+   /// Compiles an Abstract Syntax Tree into ByteCode, discarding the compiler warnings.
    ///
    /// # Parameters
    /// - `filepath`: The program's filepath.
@@ -133,10 +245,245 @@ impl Compiler {
       program: &ASTNode,
       built_in: &BuiltIn,
    ) -> Result<FuncObject, Vec<ErrorReport>> {
+      Compiler::compile_ast_with_warnings(
+         filepath,
+         program,
+         built_in,
+         WarningMode::Silence,
+         OptLevel::default(),
+         &[],
+      )
+      .map(|(f, _)| f)
+   }
+
+   /// Compiles an Abstract Syntax Tree into ByteCode, and collects the compiler warnings.
+   ///
+   /// # Parameters
+   /// - `filepath`: The program's filepath.
+   /// - `program`: The root node of the AST for a particular program.
+   /// - `built_in`: The built-in functions and primitives of Hinton.
+   /// - `warning_mode`: Determines how the warnings are handled. When warnings are denied,
+   ///   they are returned as errors.
+   /// - `opt_level`: Determines which optimizations are applied to the program.
+   /// - `module_dirs`: The directories searched for imported modules.
+   ///
+   /// # Returns
+   /// `Result<(FuncObject, Vec<WarningReport>), Vec<ErrorReport>>`: If the program had no
+   /// compile-time errors, returns the main function and the list of warnings. Otherwise,
+   /// returns the list of compile-time errors.
+   pub fn compile_ast_with_warnings(
+      filepath: &Path,
+      program: &ASTNode,
+      built_in: &BuiltIn,
+      warning_mode: WarningMode,
+      opt_level: OptLevel,
+      module_dirs: &[PathBuf],
+   ) -> Result<(FuncObject, Vec<WarningReport>), Vec<ErrorReport>> {
+      let mut _self = Compiler::new(
+         format!("<File '{}'>", filepath.to_str().unwrap()),
+         vec![filepath.to_path_buf()],
+         built_in.natives.get_names(),
+         built_in.primitives.get_names(),
+      );
+      _self.warning_mode = warning_mode;
+      _self.opt_level = opt_level;
+      _self.module_dirs = module_dirs.to_vec();
+      _self.system_access = built_in.system_access;
+
+      // Compile the function body
+      _self.check_types(program);
+      _self.compile_node(&program);
+      _self.emit_op_code(OpCode::EndVirtualMachine, (0, 0));
+      _self.fuse_superinstructions();
+
+      // Print the bytecode for the main function when the appropriate flag is present.
+      #[cfg(feature = "show_bytecode")]
+      _self.print_pretty_bytecode();
+      #[cfg(feature = "show_raw_bytecode")]
+      _self.print_raw_bytecode();
+
+      if _self.errors.is_empty() {
+         // Unused symbols are reported at the end of their scope, so the warnings are sorted
+         // to be reported in the order they appear in the source.
+         _self.warnings.sort_by_key(|w| (w.line, w.column));
+
+         let mut function = std::mem::take(&mut _self.current_func_scope_mut().function);
+         function.globals = _self.global_names();
+         Ok((function, _self.warnings))
+      } else {
+         Err(_self.errors)
+      }
+   }
+
+   /// Compiles an Abstract Syntax Tree to analyze it, collecting its errors and warnings,
+   /// as well as the declarations made in it and the identifiers that refer to them.
+   ///
+   /// # Parameters
+   /// - `filepath`: The program's filepath.
+   /// - `program`: The root node of the AST for a particular program.
+   /// - `built_in`: The built-in functions and primitives of Hinton.
+   pub fn analyze(filepath: &Path, program: &ASTNode, built_in: &BuiltIn) -> Analysis {
+      let mut _self = Compiler::new(
+         format!("<File '{}'>", filepath.to_str().unwrap_or_default()),
+         vec![filepath.to_path_buf()],
+         built_in.natives.get_names(),
+         built_in.primitives.get_names(),
+      );
+      _self.symbol_index = Some(SymbolIndex::default());
+      _self.system_access = built_in.system_access;
+
+      let (type_errors, descriptions) = TypeChecker::check_and_describe(program);
+      for (token, message) in type_errors {
+         _self.error_at_token(&token, CompilerErrorType::Type, &message);
+      }
+
+      _self.compile_node(program);
+      _self.warnings.sort_by_key(|w| (w.line, w.column));
+
+      let mut symbols = _self.symbol_index.take().unwrap_or_default();
+      for declaration in symbols.declarations.iter_mut() {
+         if let Some(description) = descriptions.get(&declaration.line_info) {
+            declaration.description = description.clone();
+         }
+      }
+
+      Analysis {
+         errors: _self.errors,
+         warnings: _self.warnings,
+         symbols,
+      }
+   }
+
+   /// Creates a compiler for the REPL. The compiler is reused for every input entered in the
+   /// REPL, so that the global declarations made by previous inputs can be resolved.
+   ///
+   /// # Parameters
+   /// - `filepath`: The path used to resolve the modules imported from the REPL.
+   /// - `built_in`: The built-in functions and primitives of Hinton.
+   pub fn new_repl(filepath: &Path, built_in: &BuiltIn) -> Self {
+      let mut _self = Compiler::new(
+         String::from("<REPL>"),
+         vec![filepath.to_path_buf()],
+         built_in.natives.get_names(),
+         built_in.primitives.get_names(),
+      );
+      _self.system_access = built_in.system_access;
+      _self
+   }
+
+   /// Compiles an input entered in the REPL into ByteCode. If the input ends with an expression
+   /// statement, the value of the expression is left on the stack so the REPL can print it.
+   ///
+   /// # Parameters
+   /// - `program`: The root node of the AST for the input.
+   ///
+   /// # Returns
+   /// `Result<FuncObject, Vec<ErrorReport>>`: If the input had no compile-time errors, returns
+   /// the main function for the input. Otherwise, returns the list of compile-time errors.
+   pub fn compile_repl_input(&mut self, program: &ASTNode) -> Result<FuncObject, Vec<ErrorReport>> {
+      let globals_count = self.globals.len();
+      self.check_types(program);
+
+      match program {
+         ASTNode::Module(module) => {
+            if let Some((last, rest)) = module.body.split_last() {
+               for node in rest.iter() {
+                  self.compile_node(node);
+               }
+
+               match last {
+                  ASTNode::ExpressionStmt(x) => self.compile_node(&x.child),
+                  _ => self.compile_node(last),
+               }
+            }
+         }
+         _ => self.compile_node(program),
+      }
+
+      self.emit_op_code(OpCode::EndVirtualMachine, (0, 0));
+      self.fuse_superinstructions();
+
+      // Print the bytecode for the input when the appropriate flag is present.
+      #[cfg(feature = "show_bytecode")]
+      self.print_pretty_bytecode();
+      #[cfg(feature = "show_raw_bytecode")]
+      self.print_raw_bytecode();
+
+      let name = self.current_func_scope().function.name.clone();
+      let filepath = self.current_func_scope().function.filepath.clone();
+      let mut function = std::mem::replace(
+         &mut self.current_func_scope_mut().function,
+         FuncObject {
+            name,
+            filepath,
+            ..Default::default()
+         },
+      );
+      function.globals = self.global_names();
+
+      if self.errors.is_empty() {
+         Ok(function)
+      } else {
+         // The global declarations made by an input with errors are discarded.
+         while self.globals.len() > globals_count {
+            self.globals.pop();
+         }
+
+         self.warnings.clear();
+
+         Err(std::mem::take(&mut self.errors))
+      }
+   }
+
+   /// Takes the warnings generated by the inputs compiled since the last call to this function.
+   pub fn take_warnings(&mut self) -> Vec<WarningReport> {
+      std::mem::take(&mut self.warnings)
+   }
+
+   /// Declares a global variable defined by the host program, so that the
+   /// inputs compiled after this call can refer to it.
+   ///
+   /// # Parameters
+   /// - `name`: The name of the global variable.
+   ///
+   /// # Returns
+   /// `usize`: The slot of the global variable.
+   pub fn declare_host_global(&mut self, name: &str) -> usize {
+      if let Some((_, slot)) = self.globals.resolve(name, false, None) {
+         return slot;
+      }
+
+      self.globals.push(Symbol {
+         name: name.to_string(),
+         depth: 0,
+         s_type: SymbolType::Var,
+         is_initialized: true,
+         is_used: true,
+         line_info: (0, 0),
+         is_captured: false,
+      });
+
+      self.globals.len() - 1
+   }
+
+   /// Gets the names of the global declarations made by the compiled programs, in the order
+   /// of their slots.
+   pub fn global_names(&self) -> Vec<String> {
+      self.globals.symbols.iter().map(|s| s.name.clone()).collect()
+   }
+
+   /// Creates a new compiler whose base function has the given name.
+   ///
+   /// # Parameters
+   /// - `name`: The name of the base function.
+   /// - `import_chain`: The paths of the modules currently being compiled.
+   /// - `natives`: A list of string names of Hinton native functions.
+   /// - `primitives`: A list of string names of Hinton primitive classes.
+   fn new(name: String, import_chain: Vec<PathBuf>, natives: Vec<String>, primitives: Vec<String>) -> Self {
       // The first element in a symbol table is always the symbol representing
       // the function to which the symbol table belongs.
       let symbols = SymbolTable::new(vec![Symbol {
-         name: format!("<File '{}'>", filepath.to_str().unwrap()),
+         name: name.clone(),
          s_type: SymbolType::Func,
          is_initialized: true,
          depth: 0,
@@ -151,70 +498,94 @@ impl Compiler {
             min_arity: 0,
             max_arity: 0,
             chunk: Chunk::new(),
-            name: format!("<File '{}'>", filepath.to_str().unwrap()),
-            up_val_count: 0,
+            name,
+            params: vec![],
+            is_variadic: false,
+            up_val_names: vec![],
+            filepath: import_chain.last().cloned().unwrap_or_default(),
+            locals: vec![],
+            doc: None,
+            globals: vec![],
          },
          s_table: symbols,
          scope_depth: 0,
          loops: vec![],
          breaks: vec![],
+         try_depth: 0,
+         finally_scopes: vec![],
          up_values: vec![],
       };
 
-      let mut _self = Compiler {
+      Compiler {
          compiler_type: CompilerCtx::Script,
          functions: vec![base_fn],
          errors: vec![],
+         warnings: vec![],
+         warning_mode: WarningMode::default(),
+         opt_level: OptLevel::default(),
+         module_dirs: vec![],
+         system_access: true,
          globals: SymbolTable::new(vec![]),
-         natives: built_in.natives.get_names(),
-         primitives: built_in.primitives.get_names(),
+         natives,
+         primitives,
          classes: vec![],
-      };
-
-      // Compile the function body
-      _self.compile_node(&program);
-      _self.emit_op_code(OpCode::EndVirtualMachine, (0, 0));
-
-      // Print the bytecode for the main function when the appropriate flag is present.
-      #[cfg(feature = "show_bytecode")]
-      _self.print_pretty_bytecode();
-      #[cfg(feature = "show_raw_bytecode")]
-      _self.print_raw_bytecode();
-
-      if _self.errors.is_empty() {
-         Ok(std::mem::take(&mut _self.current_func_scope_mut().function))
-      } else {
-         Err(_self.errors)
+         optional_chains: vec![],
+         interfaces: HashMap::new(),
+         enums: HashMap::new(),
+         consts: HashMap::new(),
+         import_chain,
+         is_module: false,
+         exports: vec![],
+         symbol_index: None,
       }
    }
 
    /// Compiles an AST node.
    fn compile_node(&mut self, node: &ASTNode) {
       match node {
+         // Constant expressions are compiled as literals when optimizations are enabled.
+         ASTNode::Binary(_) | ASTNode::Unary(_) | ASTNode::TernaryConditional(_)
+            if self.compile_folded_expr(node) => {}
          ASTNode::Array(x) => self.compile_array_expr(x),
+         ASTNode::AssertStmt(x) => self.compile_assert_stmt(x),
          ASTNode::Binary(x) => self.compile_binary_expr(x),
          ASTNode::BlockStmt(x) => self.compile_block_stmt(x),
          ASTNode::ClassDecl(x) => self.compile_class_declaration(x),
+         ASTNode::ComparisonChain(x) => self.compile_comparison_chain(x),
+         ASTNode::Comprehension(x) => self.compile_comprehension(x),
+         ASTNode::InterfaceDecl(x) => self.compile_interface_declaration(x),
          ASTNode::ConstantDecl(x) => self.compile_constant_decl(x),
+         ASTNode::DeferStmt(x) => self.compile_defer_stmt(x),
          ASTNode::Dictionary(x) => self.compile_dictionary(x),
+         ASTNode::EnumDecl(x) => self.compile_enum_declaration(x),
+         ASTNode::ExportDecl(x) => self.compile_export_decl(x),
          ASTNode::ExpressionStmt(x) => self.compile_expression_stmt(x),
          ASTNode::ForStmt(x) => self.compile_for_stmt(x),
          ASTNode::FunctionCall(x) => self.compile_inst_or_func_call_expr(x, false),
          ASTNode::FunctionDecl(x) => self.compile_function_decl(x, CompilerCtx::Function),
          ASTNode::Identifier(x) => self.compile_identifier_expr(x),
          ASTNode::IfStmt(x) => self.compile_if_stmt(x),
+         ASTNode::ImportDecl(x) => self.compile_import_decl(x),
          ASTNode::Instance(x) => self.compile_inst_or_func_call_expr(x, true),
          ASTNode::Lambda(x) => self.compile_function_decl(x, CompilerCtx::Lambda),
          ASTNode::Literal(x) => self.compile_literal_expr(x),
          ASTNode::LoopBranch(x) => self.compile_loop_branching_stmt(x),
+         ASTNode::LoopExpr(x) => self.compile_loop_expr(x),
+         ASTNode::MatchStmt(x) => self.compile_match_stmt(x),
          ASTNode::Module(x) => self.compile_module_node(x),
+         ASTNode::NonNullAssertion(x) => self.compile_non_null_assertion(x),
          ASTNode::ObjectGetter(x) => self.compile_object_getter_expr(x),
          ASTNode::ObjectSetter(x) => self.compile_object_setter_expr(x),
+         ASTNode::OptionalChain(x) => self.compile_optional_chain(x),
          ASTNode::ReturnStmt(x) => self.compile_return_stmt(x),
+         ASTNode::StringInterpolation(x) => self.compile_string_interpolation(x),
          ASTNode::SelfExpr(x) => self.compile_self_expr(x),
+         ASTNode::SuperExpr(x) => self.compile_super_expr(x),
+         ASTNode::Slice(x) => self.compile_slice_expr(x),
          ASTNode::Subscript(x) => self.compile_subscript_expr(x),
          ASTNode::SubscriptAssignment(x) => self.compile_subscript_assignment(x),
          ASTNode::TernaryConditional(x) => self.compile_ternary_conditional_expr(x),
+         ASTNode::TryStmt(x) => self.compile_try_stmt(x),
          ASTNode::Tuple(x) => self.compile_tuple_expr(x),
          ASTNode::Unary(x) => self.compile_unary_expr(x),
          ASTNode::VarReassignment(x) => self.compile_var_reassignment_expr(x),
@@ -223,15 +594,54 @@ impl Compiler {
       }
    }
 
+   /// Runs the type checker on a program, and reports the type errors it finds.
+   ///
+   /// # Parameters
+   /// - `program`: The root node of the program's AST.
+   fn check_types(&mut self, program: &ASTNode) {
+      for (token, message) in TypeChecker::check(program) {
+         self.error_at_token(&token, CompilerErrorType::Type, &message);
+      }
+   }
+
    /// Compiles an AST module node.
    fn compile_module_node(&mut self, module: &ModuleNode) {
+      self.fold_module_consts(&module.body);
+
       for node in module.body.iter() {
          self.compile_node(node);
       }
    }
 
+   /// Records the local variables of the current function that have gone out of scope (those
+   /// whose stack slot is no longer occupied by a symbol) as ending at the current instruction.
+   fn end_local_ranges(&mut self) {
+      let live_slots = self.current_s_table().len();
+      let function = &mut self.current_func_scope_mut().function;
+      let end = function.chunk.len();
+
+      for local in function.locals.iter_mut().rev() {
+         if local.end == usize::MAX && local.slot >= live_slots {
+            local.end = end;
+         }
+      }
+   }
+
+   /// Checks that the compiler is currently in the top-level scope of the module (or the
+   /// main script), where the declarations of the module's constants are made.
+   fn is_module_scope(&self) -> bool {
+      matches!(self.compiler_type, CompilerCtx::Script)
+         && self.functions.len() == 1
+         && self.relative_scope_depth() == 0
+   }
+
    /// Checks that the compiler is currently in the global scope.
    fn is_global_scope(&self) -> bool {
+      // The top-level declarations of imported modules are local to the module.
+      if self.is_module {
+         return false;
+      }
+
       if let CompilerCtx::Script = self.compiler_type {
          if self.functions.len() == 1 && self.relative_scope_depth() == 0 {
             return true;
@@ -305,7 +715,7 @@ impl Compiler {
    /// Pretty-prints the compiled chunk of bytecode fot the current function.
    #[cfg(feature = "show_bytecode")]
    fn print_pretty_bytecode(&self) {
-      crate::core::bytecode::disassemble_func_scope(
+      crate::core::disassembler::disassemble_chunk(
          &self.current_func_scope().function.chunk,
          &self.natives,
          &self.primitives,
@@ -376,6 +786,24 @@ impl Compiler {
       self.emit_raw_short(short, pos);
    }
 
+   /// Adds an inline cache to the current chunk, and emits its position as the
+   /// operand of the property access instruction that was just emitted.
+   ///
+   /// # Parameters
+   /// - `token`: The token of the property being accessed.
+   fn emit_inline_cache(&mut self, token: &Token) {
+      let pos = (token.line_num, token.column_start);
+
+      match self.current_chunk_mut().add_cache() {
+         Ok(idx) => self.emit_raw_short(idx, pos),
+         Err(_) => self.error_at_token(
+            token,
+            CompilerErrorType::MaxCapacity,
+            "Too many property accesses in one chunk.",
+         ),
+      }
+   }
+
    /// Emits a jump instructions with a dummy jump offset. This offset should be
    /// later replaced by calling the `patch_jump(...)` function.
    ///
@@ -486,24 +914,15 @@ impl Compiler {
    /// - `err_type`: The type of error to be emitted.
    /// - `message`: The error message to display.
    fn error_at_token(&mut self, token: &Token, err_type: CompilerErrorType, message: &str) {
-      let err_name = match err_type {
-         CompilerErrorType::MaxCapacity => "MaxCapacityError",
-         CompilerErrorType::Reassignment => "ReassignmentError",
-         CompilerErrorType::Reference => "ReferenceError",
-         CompilerErrorType::Syntax => "SyntaxError",
-         CompilerErrorType::Duplication => "DuplicationError",
-      };
-
       let msg = format!(
-         "\x1b[31;1m{}\x1b[0m\x1b[1m at [{}:{}]: {}\x1b[0m",
-         err_name, token.line_num, token.column_start, message
+         "\x1b[31;1m{}[{}]\x1b[0m\x1b[1m at [{}:{}]: {}\x1b[0m",
+         err_type.name(),
+         err_type.code(),
+         token.line_num,
+         token.column_start,
+         message
       );
 
-      self.errors.push(ErrorReport {
-         line: token.line_num,
-         column: token.column_start,
-         lexeme_len: token.lexeme.len(),
-         message: msg,
-      });
+      self.errors.push(ErrorReport::at_token(token, msg));
    }
 }
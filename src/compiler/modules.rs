@@ -0,0 +1,238 @@
+use crate::compiler::symbols::SymbolType;
+use crate::compiler::{Compiler, CompilerCtx};
+use crate::core::ast::*;
+use crate::core::bytecode::OpCode;
+use crate::errors::{print_errors_list, print_warnings_list, CompilerErrorType, ErrorReport, WarningReport};
+use crate::objects::{FuncObject, Object};
+use crate::parser::Parser;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+impl Compiler {
+   /// Compiles an `import` declaration.
+   pub(super) fn compile_import_decl(&mut self, decl: &ImportDeclNode) {
+      if !self.system_access {
+         self.error_at_token(
+            &decl.path_token,
+            CompilerErrorType::Import,
+            "Programs without access to the host system cannot import modules.",
+         );
+         return;
+      }
+
+      let path = match self.resolve_module_path(&decl.path) {
+         Some(p) => p,
+         None => {
+            self.error_at_token(
+               &decl.path_token,
+               CompilerErrorType::Import,
+               &format!("Could not find module '{}'.", decl.path),
+            );
+            return;
+         }
+      };
+
+      // Prevent modules from importing themselves, directly or indirectly.
+      if self.import_chain.contains(&path) {
+         self.error_at_token(
+            &decl.path_token,
+            CompilerErrorType::CircularImport,
+            &format!("Circular import of module '{}'.", decl.path),
+         );
+         return;
+      }
+
+      let source = match fs::read_to_string(&path) {
+         Ok(src) => src,
+         Err(_) => {
+            self.error_at_token(
+               &decl.path_token,
+               CompilerErrorType::Import,
+               &format!("Could not read module '{}'.", decl.path),
+            );
+            return;
+         }
+      };
+
+      // Compiles the module into its own function object. The errors found in the module
+      // are reported immediately because they are relative to the module's source.
+      let module = match Parser::parse(&source) {
+         Ok(ast) => self.compile_module(&path, &ast),
+         Err(e) => Err(e),
+      };
+
+      let symbol_pos = match self.declare_symbol(&decl.name, SymbolType::Const) {
+         Ok(p) => p,
+         Err(_) => return,
+      };
+
+      match module {
+         Ok((m, warnings)) => {
+            // Like errors, the warnings found in the module are reported immediately.
+            let _ = print_warnings_list(&mut io::stderr(), &path, &warnings, &source);
+
+            let pos = (decl.token.line_num, decl.token.column_start);
+
+            if let Some(idx) = self.add_literal_to_pool(Object::from(m), &decl.token, false) {
+               if idx < 256 {
+                  self.emit_op_code_with_byte(OpCode::ImportModule, idx as u8, pos);
+               } else {
+                  self.emit_op_code_with_short(OpCode::ImportModuleLong, idx, pos);
+               }
+            }
+
+            if self.is_global_scope() {
+               self.define_as_global(&decl.name);
+            }
+         }
+         Err(e) => {
+            let _ = print_errors_list(&mut io::stderr(), &path, &e, &source);
+            self.error_at_token(
+               &decl.path_token,
+               CompilerErrorType::Import,
+               &format!("Could not compile module '{}'.", decl.path),
+            );
+         }
+      }
+
+      // The module's name is declared even if the module had errors to
+      // prevent cascading errors when its exports are referenced.
+      self.current_s_table_mut().mark_initialized(symbol_pos);
+   }
+
+   /// Compiles an `export` declaration.
+   pub(super) fn compile_export_decl(&mut self, decl: &ExportDeclNode) {
+      let is_top_level = self.functions.len() == 1 && self.relative_scope_depth() == 0;
+
+      if !is_top_level || !matches!(self.compiler_type, CompilerCtx::Script) {
+         self.error_at_token(
+            &decl.token,
+            CompilerErrorType::Syntax,
+            "Exports can only be declared at the top-level of a module.",
+         );
+         return;
+      }
+
+      self.compile_node(&decl.decl);
+
+      match &*decl.decl {
+         ASTNode::VariableDecl(v) => self
+            .exports
+            .extend(v.identifiers.iter().map(|t| t.clone().into_owned())),
+         ASTNode::ConstantDecl(c) => self.exports.push(c.name.clone().into_owned()),
+         ASTNode::FunctionDecl(f) => self.exports.push(f.name.clone().into_owned()),
+         ASTNode::ClassDecl(c) => self.exports.push(c.name.clone().into_owned()),
+         ASTNode::InterfaceDecl(i) => self.exports.push(i.name.clone().into_owned()),
+         ASTNode::EnumDecl(e) => self.exports.push(e.name.clone().into_owned()),
+         _ => unreachable!("Should have parsed an exportable declaration."),
+      }
+   }
+
+   /// Resolves the path of an imported module relative to the directory of the current file,
+   /// or else relative to each of the module directories (in order). If the path has no
+   /// extension, the `.ht` extension is assumed.
+   ///
+   /// # Parameters
+   /// - `path`: The path to the module, as written in the `import` declaration.
+   ///
+   /// # Returns
+   /// `Option<PathBuf>`: The canonical path to the module, if the module exists.
+   fn resolve_module_path(&self, path: &str) -> Option<PathBuf> {
+      let current_dir = self
+         .import_chain
+         .last()
+         .and_then(|p| p.parent())
+         .unwrap_or_else(|| Path::new(""));
+
+      std::iter::once(current_dir)
+         .chain(self.module_dirs.iter().map(|d| d.as_path()))
+         .find_map(|dir| {
+            let mut module_path = dir.join(path);
+
+            if module_path.extension().is_none() {
+               module_path.set_extension("ht");
+            }
+
+            fs::canonicalize(module_path).ok()
+         })
+   }
+
+   /// Compiles the AST of an imported module into a function object. When called, the function
+   /// executes the module's body, and returns a dictionary with the module's exports.
+   ///
+   /// # Parameters
+   /// - `filepath`: The canonical path to the module.
+   /// - `program`: The root node of the module's AST.
+   ///
+   /// # Returns
+   /// `Result<(FuncObject, Vec<WarningReport>), Vec<ErrorReport>>`: The module's function object
+   /// and the list of warnings found in the module, or the list of errors found while compiling it.
+   fn compile_module(
+      &self,
+      filepath: &Path,
+      program: &ASTNode,
+   ) -> Result<(FuncObject, Vec<WarningReport>), Vec<ErrorReport>> {
+      let mut import_chain = self.import_chain.clone();
+      import_chain.push(filepath.to_path_buf());
+
+      let mut module = Compiler::new(
+         format!("<Module '{}'>", filepath.to_str().unwrap()),
+         import_chain,
+         self.natives.clone(),
+         self.primitives.clone(),
+      );
+      module.is_module = true;
+      module.warning_mode = self.warning_mode;
+      module.opt_level = self.opt_level;
+      module.module_dirs = self.module_dirs.clone();
+
+      module.check_types(program);
+      module.compile_node(program);
+      module.emit_module_exports();
+      module.current_s_table_mut().pop_scope(0, true);
+      module.end_local_ranges();
+      module.fuse_superinstructions();
+
+      // Print the bytecode for the module when the appropriate flag is present.
+      #[cfg(feature = "show_bytecode")]
+      module.print_pretty_bytecode();
+      #[cfg(feature = "show_raw_bytecode")]
+      module.print_raw_bytecode();
+
+      if module.errors.is_empty() {
+         let function = std::mem::take(&mut module.current_func_scope_mut().function);
+         Ok((function, module.warnings))
+      } else {
+         Err(module.errors)
+      }
+   }
+
+   /// Emits the bytecode to compose the module's exports table into a dictionary, cache it
+   /// in the VM, and return it to the importing module.
+   fn emit_module_exports(&mut self) {
+      let exports = std::mem::take(&mut self.exports);
+      let pos = match exports.first() {
+         Some(t) => (t.line_num, t.column_start),
+         None => (0, 0),
+      };
+
+      if exports.is_empty() {
+         self.emit_op_code_with_byte(OpCode::MakeDict, 0, pos);
+      } else {
+         let values: Vec<ASTNode> = exports
+            .iter()
+            .map(|t| ASTNode::Identifier(IdentifierExprNode { token: t.clone() }))
+            .collect();
+
+         self.compile_dictionary(&DictionaryExprNode {
+            token: exports[0].clone(),
+            keys: exports.into_boxed_slice(),
+            values: values.into_boxed_slice(),
+         });
+      }
+
+      self.emit_op_code(OpCode::ExportModule, pos);
+      self.emit_return_instr(pos);
+   }
+}
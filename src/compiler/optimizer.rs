@@ -0,0 +1,291 @@
+use crate::compiler::Compiler;
+use crate::core::ast::*;
+use crate::core::bytecode::OpCode;
+use crate::core::chunk::Chunk;
+use crate::core::tokens::Token;
+use crate::objects::Object;
+use hashbrown::HashMap;
+
+/// The maximum length of a string created by folding a constant expression. Longer strings
+/// are created at runtime instead, to keep the constant pool small.
+const MAX_FOLDED_STR_LEN: usize = 256;
+
+/// Determines which optimizations the compiler applies to a program.
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum OptLevel {
+   /// The program is compiled as written.
+   O0,
+   /// Constant expressions are folded, branches with constant conditions are
+   /// removed, the code after a `return`, `break`, or `continue` is not compiled,
+   /// and common sequences of instructions are fused into superinstructions.
+   #[default]
+   O1,
+}
+
+impl Compiler {
+   /// Fuses the common sequences of instructions in the current chunk into superinstructions,
+   /// when optimizations are enabled. Only the first instruction of a sequence is replaced, so
+   /// the positions of the instructions in the chunk (and the jumps, exception handlers, and
+   /// line information that refer to them) are not affected.
+   pub(super) fn fuse_superinstructions(&mut self) {
+      if self.opt_level == OptLevel::O0 {
+         return;
+      }
+
+      let chunk = self.current_chunk_mut();
+      let mut idx = 0;
+
+      while idx < chunk.len() {
+         let next = idx + chunk.instruction_len(idx);
+
+         if let Some(op) = superinstruction_at(chunk, idx) {
+            chunk.modify_byte(idx, op as u8);
+         }
+
+         idx = next;
+      }
+   }
+
+   /// Compiles a list of statements. When optimizations are enabled, the statements that
+   /// follow a `return`, `break`, or `continue` statement are not compiled.
+   ///
+   /// # Parameters
+   /// - `body`: The statements to compile.
+   ///
+   /// # Returns
+   /// - `bool`: True if the last compiled statement was a `return` statement.
+   pub(super) fn compile_statements(&mut self, body: &[ASTNode]) -> bool {
+      let mut ends_in_return = false;
+
+      for node in body.iter() {
+         self.compile_node(node);
+         ends_in_return = matches!(node, ASTNode::ReturnStmt(_));
+
+         if self.opt_level == OptLevel::O1 && matches!(node, ASTNode::ReturnStmt(_) | ASTNode::LoopBranch(_))
+         {
+            break;
+         }
+      }
+
+      ends_in_return
+   }
+
+   /// Checks if a condition is always truthy or always falsey. Without optimizations, only
+   /// truthy literals and the `false` literal are considered constant conditions.
+   ///
+   /// # Parameters
+   /// - `condition`: The condition.
+   ///
+   /// # Returns
+   /// - `Option<bool>`: The truthiness of the condition, or `None` if it is not constant.
+   pub(super) fn constant_condition(&self, condition: &ASTNode) -> Option<bool> {
+      match self.opt_level {
+         OptLevel::O0 if condition.is_truthy_literal() => Some(true),
+         OptLevel::O0 if condition.is_false_literal() => Some(false),
+         OptLevel::O0 => None,
+         OptLevel::O1 => fold_constant(condition, None).map(|lit| !lit.value.is_falsey()),
+      }
+   }
+
+   /// Compiles an expression as a literal value if it can be evaluated at compile time.
+   ///
+   /// # Parameters
+   /// - `expr`: The expression.
+   ///
+   /// # Returns
+   /// - `bool`: True if the expression was folded into a literal, false otherwise.
+   pub(super) fn compile_folded_expr(&mut self, expr: &ASTNode) -> bool {
+      if self.opt_level == OptLevel::O0 {
+         return false;
+      }
+
+      match fold_constant(expr, None) {
+         Some(lit) => {
+            self.compile_literal_expr(&lit);
+            true
+         }
+         None => false,
+      }
+   }
+
+   /// Evaluates the initializers of the top-level constants of a module at compile time.
+   /// An initializer is evaluated if it is a constant expression, which may reference other
+   /// constants of the module, even if they are declared after it.
+   ///
+   /// # Parameters
+   /// - `body`: The top-level statements of the module.
+   pub(super) fn fold_module_consts(&mut self, body: &[ASTNode]) {
+      let mut pending: Vec<&ConstantDeclNode> = body
+         .iter()
+         .filter_map(|node| match node {
+            ASTNode::ConstantDecl(decl) => Some(decl),
+            ASTNode::ExportDecl(export) => match &*export.decl {
+               ASTNode::ConstantDecl(decl) => Some(decl),
+               _ => None,
+            },
+            _ => None,
+         })
+         .collect();
+
+      // Each pass evaluates the constants whose initializers only reference constants that
+      // have already been evaluated, until a pass makes no progress.
+      loop {
+         let count = pending.len();
+
+         pending.retain(|decl| match fold_constant(&decl.value, Some(&self.consts)) {
+            Some(lit) if !self.consts.contains_key(decl.name.lexeme.as_ref()) => {
+               self.consts.insert(decl.name.lexeme.to_string(), lit.value);
+               false
+            }
+            _ => true,
+         });
+
+         if pending.len() == count {
+            break;
+         }
+      }
+   }
+
+   /// Gets the value of a top-level constant that is referenced before its declaration. The
+   /// reference must not be shadowed by (or refer to) a symbol that has already been declared.
+   ///
+   /// # Parameters
+   /// - `token`: The identifier's token.
+   ///
+   /// # Returns
+   /// - `Option<Object>`: The value of the constant, if the identifier refers to one.
+   pub(super) fn forward_const(&self, token: &Token) -> Option<Object> {
+      let value = self.consts.get(token.lexeme.as_ref())?;
+
+      let is_declared = self
+         .functions
+         .iter()
+         .map(|f| &f.s_table)
+         .chain(std::iter::once(&self.globals))
+         .any(|table| table.symbols.iter().any(|s| s.name == token.lexeme));
+
+      if is_declared {
+         None
+      } else {
+         Some(value.clone())
+      }
+   }
+}
+
+/// Evaluates an expression at compile time, if it only operates on literal values. Operations
+/// that would fail at runtime (e.g., a division by zero) are not folded, so that the error is
+/// still reported when the program runs.
+///
+/// # Parameters
+/// - `node`: The expression.
+/// - `consts`: The values of the constants that the expression may reference, if any.
+///
+/// # Returns
+/// - `Option<LiteralExprNode>`: The value of the expression, or `None` if it cannot be folded.
+fn fold_constant<'a>(
+   node: &ASTNode<'a>,
+   consts: Option<&HashMap<String, Object>>,
+) -> Option<LiteralExprNode<'a>> {
+   match node {
+      ASTNode::Literal(lit) => Some(lit.clone()),
+      ASTNode::Identifier(id) => Some(LiteralExprNode {
+         value: consts?.get(id.token.lexeme.as_ref())?.clone(),
+         token: id.token.clone(),
+      }),
+      ASTNode::Unary(expr) => {
+         let operand = fold_constant(&expr.operand, consts)?;
+
+         Some(LiteralExprNode {
+            value: operand.value.unary_operation(&expr.opr_type).ok()?,
+            token: operand.token,
+         })
+      }
+      ASTNode::Binary(expr) => fold_binary_expr(expr, consts),
+      ASTNode::TernaryConditional(expr) => {
+         if fold_constant(&expr.condition, consts)?.value.is_falsey() {
+            fold_constant(&expr.branch_false, consts)
+         } else {
+            fold_constant(&expr.branch_true, consts)
+         }
+      }
+      _ => None,
+   }
+}
+
+/// Evaluates a binary expression at compile time, if both of its operands are constant.
+fn fold_binary_expr<'a>(
+   expr: &BinaryExprNode<'a>,
+   consts: Option<&HashMap<String, Object>>,
+) -> Option<LiteralExprNode<'a>> {
+   let left = fold_constant(&expr.left, consts)?;
+
+   let value = match expr.opr_type {
+      // The rhs of a short-circuited expression only matters if the lhs does not decide it.
+      BinaryExprType::LogicAND if left.value.is_falsey() => left.value,
+      BinaryExprType::LogicOR if !left.value.is_falsey() => left.value,
+      BinaryExprType::LogicAND | BinaryExprType::LogicOR => fold_constant(&expr.right, consts)?.value,
+      // Ranges are created, and types are tested, at runtime.
+      BinaryExprType::Range | BinaryExprType::RangeInclusive | BinaryExprType::LogicIs => return None,
+      _ => {
+         let right = fold_constant(&expr.right, consts)?;
+
+         // String repetitions can create very large strings, so they are left to the runtime.
+         if let BinaryExprType::Multiplication = expr.opr_type {
+            if matches!(left.value, Object::String(_)) || matches!(right.value, Object::String(_)) {
+               return None;
+            }
+         }
+
+         match left.value.binary_operation(&expr.opr_type, right.value).ok()? {
+            Object::String(s) if s.len() > MAX_FOLDED_STR_LEN => return None,
+            value => value,
+         }
+      }
+   };
+
+   Some(LiteralExprNode {
+      value,
+      token: expr.opr_token.clone(),
+   })
+}
+
+/// Finds the superinstruction that fuses the sequence of instructions starting at the given
+/// index, if any. The instructions of a fused sequence must be on the same line, so that the
+/// debugger and the profiler still see every line that is executed.
+///
+/// # Parameters
+/// - `chunk`: The chunk with the instructions.
+/// - `idx`: The index of the first instruction of the sequence. The offsets passed to `op_at`
+///   are the positions of the following instructions, relative to the first one.
+fn superinstruction_at(chunk: &Chunk, idx: usize) -> Option<OpCode> {
+   let op_at = |offset: usize| {
+      let pos = idx + offset;
+
+      if pos < chunk.len() && chunk.get_line_info(pos).0 == chunk.get_line_info(idx).0 {
+         Some(chunk.get_op_code(pos))
+      } else {
+         None
+      }
+   };
+
+   match chunk.get_op_code(idx) {
+      OpCode::LoadConstant if op_at(2) == Some(OpCode::Add) => Some(OpCode::LoadConstantAdd),
+      OpCode::LoadImmN if op_at(2) == Some(OpCode::Add) => Some(OpCode::LoadImmNAdd),
+      OpCode::LoadImm1I if op_at(1) == Some(OpCode::Add) => Some(OpCode::LoadImm1IAdd),
+      // The comparison has no operands, so the jump is only decoded after the comparison is.
+      OpCode::GetLocal if op_at(2) == Some(OpCode::GetLocal) => match op_at(4)? {
+         OpCode::Equals
+         | OpCode::GreaterThan
+         | OpCode::GreaterThanEq
+         | OpCode::LessThan
+         | OpCode::LessThanEq
+         | OpCode::NotEq
+            if op_at(5) == Some(OpCode::PopJumpIfFalse) =>
+         {
+            Some(OpCode::GetLocalsCompareJump)
+         }
+         _ => None,
+      },
+      _ => None,
+   }
+}
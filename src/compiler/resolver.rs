@@ -1,4 +1,4 @@
-use crate::compiler::symbols::{Symbol, SymbolType, SL};
+use crate::compiler::symbols::{Reference, Symbol, SymbolType, SL};
 use crate::compiler::{Compiler, UpValue};
 use crate::core::bytecode::OpCode;
 use crate::core::tokens::Token;
@@ -17,6 +17,7 @@ impl Compiler {
    pub(super) fn resolve_symbol(&mut self, token: &Token, reassign: bool) -> Result<SL, ()> {
       // Look for the symbol in the local scope of the current function
       if let Some(s) = self.resolve_local(self.functions.len() - 1, token, reassign, None) {
+         self.record_reference(token, &s);
          return Ok(s);
       }
 
@@ -24,12 +25,14 @@ impl Compiler {
       // in the scope of the parent function to create upValues & closures.
       if self.functions.len() > 1 {
          if let Some(s) = self.resolve_up_value(token, reassign, self.functions.len() - 2) {
+            self.record_reference(token, &s);
             return Ok(s);
          }
       }
 
       // Looks for the symbol in the global scope of the current script
       if let Some(s) = self.resolve_global(token, reassign) {
+         self.record_reference(token, &s);
          return Ok(s);
       }
 
@@ -55,7 +58,7 @@ impl Compiler {
       }
 
       // Look for the identifier in the primitives
-      if self.primitives.contains(&token.lexeme) {
+      if self.primitives.iter().any(|p| *p == token.lexeme) {
          if reassign {
             self.error_at_token(
                token,
@@ -66,7 +69,7 @@ impl Compiler {
             return Ok(SL::Error);
          }
 
-         match self.add_literal_to_pool(Object::from(token.lexeme.as_str()), &token, false) {
+         match self.add_literal_to_pool(Object::from(token.lexeme.as_ref()), &token, false) {
             None => return Ok(SL::Error),
             Some(index) => self.emit_op_code_with_byte(
                OpCode::LoadPrimitive,
@@ -78,12 +81,39 @@ impl Compiler {
          return Ok(SL::Primitive);
       }
 
+      // Constants cannot be reassigned, even before their declaration.
+      if reassign && self.consts.contains_key(token.lexeme.as_ref()) {
+         self.error_at_token(token, CompilerErrorType::Reassignment, "Constants are immutable.");
+         return Err(());
+      }
+
       // The symbol doesn't exist
       let error_msg = &format!("Use of undeclared identifier '{}'.", token.lexeme);
       self.error_at_token(token, CompilerErrorType::Reference, error_msg);
       Err(())
    }
 
+   /// Records that an identifier refers to the declaration of a resolved symbol,
+   /// if the symbols of the program are being indexed.
+   ///
+   /// # Parameters
+   /// - `token`: The identifier's token.
+   /// - `location`: The location of the resolved symbol.
+   fn record_reference(&mut self, token: &Token, location: &SL) {
+      let declaration = match location {
+         SL::Local(symbol, _) | SL::Global(symbol, _) => symbol.line_info,
+         SL::UpValue(up_value, _) => up_value.symbol.line_info,
+         _ => return,
+      };
+
+      if let Some(index) = &mut self.symbol_index {
+         index.references.push(Reference {
+            span: token.span,
+            declaration,
+         });
+      }
+   }
+
    /// Looks for a symbol with the given token name in the local scope of the provided function index.
    ///
    /// # Parameters
@@ -147,10 +177,7 @@ impl Compiler {
             .check_reassignment(&resolution, token, for_reassignment)
             .is_ok()
          {
-            let name = Object::from(token.lexeme.clone());
-            if let Some(idx) = self.add_literal_to_pool(name, &token, false) {
-               return Some(SL::Global(resolution.0, idx as usize));
-            }
+            return Some(SL::Global(resolution.0, resolution.1));
          }
 
          return Some(SL::Error);
@@ -196,6 +223,8 @@ impl Compiler {
          SymbolType::Const => "Constants are immutable.",
          SymbolType::Func => "Functions are immutable.",
          SymbolType::Class => "Classes are immutable.",
+         SymbolType::Interface => "Interfaces are immutable.",
+         SymbolType::Enum => "Enums are immutable.",
          SymbolType::ConstField => "Constant class fields are immutable.",
          SymbolType::Method => "Class methods are immutable.",
          // Only variables & parameters are re-assignable
@@ -219,7 +248,7 @@ impl Compiler {
    /// # Returns
    /// - `Option<SL>`: The location (if found) and resolution type of the symbol.
    fn resolve_up_value(&mut self, token: &Token, reassign: bool, func_idx: usize) -> Option<SL> {
-      if func_idx == 0 && self.functions[0].scope_depth == 0 {
+      if func_idx == 0 && self.functions[0].scope_depth == 0 && !self.is_module {
          return None;
       }
 
@@ -233,6 +262,8 @@ impl Compiler {
       if let Some(s) = self.resolve_local(func_idx, token, reassign, Some(true)) {
          return match s {
             SL::Local(s, p) => self.add_up_value(token, func_idx + 1, s, p, true),
+            // The symbol was found, but could not be used (e.g., it is not initialized yet).
+            SL::Error => Some(SL::Error),
             _ => unreachable!("SymbolLoc should have been a local symbol."),
          };
       }
@@ -242,6 +273,7 @@ impl Compiler {
          if let Some(s) = self.resolve_up_value(token, reassign, func_idx - 1) {
             return match s {
                SL::UpValue(u, p) => self.add_up_value(token, func_idx + 1, u.symbol, p, false),
+               SL::Error => Some(SL::Error),
                _ => unreachable!("SymbolLoc should have been an up_value symbol."),
             };
          }
@@ -260,9 +292,9 @@ impl Compiler {
       is_local: bool,
    ) -> Option<SL> {
       // Prevent creating repeated up_values
-      for (index, up_val) in self.functions[func_idx].up_values.iter().enumerate() {
+      for (i, up_val) in self.functions[func_idx].up_values.iter().enumerate() {
          if up_val.index == index && up_val.is_local == is_local && up_val.symbol.name == symbol.name {
-            return Some(SL::UpValue(up_val.clone(), index));
+            return Some(SL::UpValue(up_val.clone(), i));
          }
       }
 
@@ -282,7 +314,10 @@ impl Compiler {
       };
 
       self.functions[func_idx].up_values.push(up_value.clone());
-      self.functions[func_idx].function.up_val_count += 1;
+      self.functions[func_idx]
+         .function
+         .up_val_names
+         .push(up_value.symbol.name.clone());
 
       Some(SL::UpValue(
          up_value,
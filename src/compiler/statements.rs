@@ -1,11 +1,15 @@
-use crate::compiler::symbols::{Symbol, SymbolTable, SymbolType};
-use crate::compiler::{ClassScope, Compiler, CompilerCtx};
+use crate::compiler::symbols::{Declaration, Symbol, SymbolTable, SymbolType};
+use crate::compiler::{ClassScope, Compiler, CompilerCtx, FinallyScope, PendingExit};
 use crate::core::ast::*;
 use crate::core::bytecode::OpCode;
+use crate::core::chunk::ExceptionHandler;
 use crate::core::tokens::Token;
 use crate::errors::CompilerErrorType;
-use crate::objects::Object;
+use crate::objects::class_obj::InterfaceObject;
+use crate::objects::enum_obj::EnumObject;
+use crate::objects::{LocalVariable, Object};
 use std::borrow::Borrow;
+use std::rc::Rc;
 
 impl Compiler {
    /// Compiles an expression statement.
@@ -23,6 +27,10 @@ impl Compiler {
          SymbolType::Var
       };
 
+      if decl.destructure {
+         return self.compile_destructuring_decl(decl);
+      }
+
       for id in decl.identifiers.iter() {
          // If we are currently compiling a variable within a class, the constant's name cannot
          // be 'init', since a class initializer must always be a method.
@@ -41,7 +49,7 @@ impl Compiler {
             // If the compiler is currently compiling a class, append the variable to the class.
             if let CompilerCtx::Class = self.compiler_type {
                if self
-                  .add_literal_to_pool(Object::from(id.lexeme.clone()), id, true)
+                  .add_literal_to_pool(Object::from(id.lexeme.to_string()), id, true)
                   .is_some()
                {
                   self.emit_op_code(OpCode::AppendClassField, (id.line_num, id.column_start));
@@ -58,6 +66,54 @@ impl Compiler {
       }
    }
 
+   /// Compiles a variable declaration that unpacks a tuple (or an array) into its variables.
+   fn compile_destructuring_decl(&mut self, decl: &VariableDeclNode) {
+      let first = &decl.identifiers[0];
+      let pos = (first.line_num, first.column_start);
+
+      if let CompilerCtx::Class = self.compiler_type {
+         self.error_at_token(
+            first,
+            CompilerErrorType::Syntax,
+            "Class fields cannot be destructured.",
+         );
+         return;
+      }
+
+      if decl.identifiers.len() > 255 {
+         self.error_at_token(
+            first,
+            CompilerErrorType::MaxCapacity,
+            "Cannot destructure more than 255 variables.",
+         );
+         return;
+      }
+
+      self.compile_node(&decl.value);
+      self.emit_op_code_with_byte(OpCode::Unpack, decl.identifiers.len() as u8, pos);
+
+      let mut symbols = vec![];
+      for id in decl.identifiers.iter() {
+         match self.declare_symbol(id, SymbolType::Var) {
+            Ok(symbol_pos) => symbols.push(symbol_pos),
+            Err(_) => return,
+         }
+      }
+
+      // The last item is on top of the stack, so the globals are defined in reverse order.
+      if self.is_global_scope() {
+         decl
+            .identifiers
+            .iter()
+            .rev()
+            .for_each(|id| self.define_as_global(id));
+      }
+
+      for symbol_pos in symbols {
+         self.current_s_table_mut().mark_initialized(symbol_pos);
+      }
+   }
+
    /// Compiles a constant declaration.
    pub(super) fn compile_constant_decl(&mut self, decl: &ConstantDeclNode) {
       // Get the symbol type for the function declaration.
@@ -78,13 +134,26 @@ impl Compiler {
          return;
       }
 
-      if self.declare_symbol(&decl.name, s_type).is_ok() {
-         self.compile_node(&decl.value);
+      // The value of a top-level constant whose initializer is a constant
+      // expression was evaluated at compile time, before the module was compiled.
+      let folded = match self.is_module_scope() {
+         true => self.consts.get(decl.name.lexeme.as_ref()).cloned(),
+         false => None,
+      };
+
+      if let Ok(symbol_pos) = self.declare_symbol(&decl.name, s_type) {
+         match folded {
+            Some(value) => self.compile_literal_expr(&LiteralExprNode {
+               value,
+               token: decl.name.clone(),
+            }),
+            None => self.compile_node(&decl.value),
+         }
 
          // If the compiler is currently compiling a class, append the variable to the class.
          if let CompilerCtx::Class = self.compiler_type {
             if self
-               .add_literal_to_pool(Object::from(decl.name.lexeme.clone()), &decl.name, true)
+               .add_literal_to_pool(Object::from(decl.name.lexeme.to_string()), &decl.name, true)
                .is_some()
             {
                self.emit_op_code(
@@ -97,18 +166,21 @@ impl Compiler {
          if self.is_global_scope() {
             self.define_as_global(&decl.name);
          }
+
+         self.current_s_table_mut().mark_initialized(symbol_pos)
       }
    }
 
-   /// Defines a declaration as global by emitting a `DEFINE_GLOBAL` instructions.
+   /// Defines a declaration as global by emitting a `DEFINE_GLOBAL` instruction
+   /// with the slot of the declaration's symbol in the table of globals.
    pub(super) fn define_as_global(&mut self, token: &Token) {
-      if let Some(idx) = self.add_literal_to_pool(Object::from(token.lexeme.clone()), token, false) {
+      if let Some((_, slot)) = self.globals.resolve(&token.lexeme, false, None) {
          let pos = (token.line_num, token.column_start);
 
-         if idx < 256 {
-            self.emit_op_code_with_byte(OpCode::DefineGlobal, idx as u8, pos);
+         if slot < 256 {
+            self.emit_op_code_with_byte(OpCode::DefineGlobal, slot as u8, pos);
          } else {
-            self.emit_op_code_with_short(OpCode::DefineGlobalLong, idx, pos);
+            self.emit_op_code_with_short(OpCode::DefineGlobalLong, slot as u16, pos);
          }
       }
    }
@@ -141,6 +213,41 @@ impl Compiler {
          return Err(());
       }
 
+      if matches!(
+         symbol_type,
+         SymbolType::Var
+            | SymbolType::Const
+            | SymbolType::Func
+            | SymbolType::Class
+            | SymbolType::Interface
+            | SymbolType::Enum
+            | SymbolType::Param
+      ) {
+         self.warn_shadowed_symbol(token);
+      }
+
+      let is_global = self.is_global_scope();
+      if let Some(index) = &mut self.symbol_index {
+         let keyword = match symbol_type {
+            SymbolType::Class => "class",
+            SymbolType::Const | SymbolType::ConstField => "const",
+            SymbolType::Enum => "enum",
+            SymbolType::Func | SymbolType::Method => "func",
+            SymbolType::Interface => "interface",
+            SymbolType::Param => "param",
+            SymbolType::Var | SymbolType::VarField => "var",
+         };
+
+         index.declarations.push(Declaration {
+            name: token.lexeme.to_string(),
+            s_type: symbol_type.clone(),
+            span: token.span,
+            line_info: (token.line_num, token.column_start),
+            is_global,
+            description: format!("{} {}", keyword, token.lexeme),
+         });
+      }
+
       self.emit_symbol(&token.lexeme, &token, symbol_type)
    }
 
@@ -174,6 +281,15 @@ impl Compiler {
       };
 
       if self.is_global_scope() {
+         if self.globals.len() >= (u16::MAX as usize) {
+            self.error_at_token(
+               token,
+               CompilerErrorType::MaxCapacity,
+               "Too many global variables in this program.",
+            );
+            return Err(());
+         }
+
          self.globals.push(symbol);
          Ok(self.globals.len() - 1)
       } else {
@@ -187,18 +303,45 @@ impl Compiler {
          }
 
          self.current_s_table_mut().push(symbol);
-         Ok(self.current_s_table().len() - 1)
+         let slot = self.current_s_table().len() - 1;
+
+         let function = &mut self.current_func_scope_mut().function;
+         function.locals.push(LocalVariable {
+            name: name.to_string(),
+            slot,
+            start: function.chunk.len(),
+            end: usize::MAX,
+         });
+
+         Ok(slot)
       }
    }
 
+   /// Compiles an `assert` statement. The message of the assertion
+   /// is only evaluated if the condition is falsey.
+   pub(super) fn compile_assert_stmt(&mut self, stmt: &AssertStmtNode) {
+      let pos = (stmt.token.line_num, stmt.token.column_start);
+
+      self.compile_node(&stmt.condition);
+      self.emit_op_code(OpCode::LogicNot, pos);
+      let end_jump = self.emit_jump(OpCode::PopJumpIfFalse, &stmt.token);
+
+      self.add_literal_to_pool(Object::from(stmt.source), &stmt.token, true);
+      match &stmt.message {
+         Some(message) => self.compile_node(message),
+         None => self.emit_op_code(OpCode::LoadImmNull, pos),
+      }
+      self.emit_op_code(OpCode::FailAssertion, pos);
+
+      self.patch_jump(end_jump, &stmt.token);
+   }
+
    /// Compiles a block statement.
    pub(super) fn compile_block_stmt(&mut self, block: &BlockNode) {
       self.current_func_scope_mut().scope_depth += 1;
+      self.warn_unreachable_code(&block.body);
 
-      for node in block.body.iter() {
-         self.compile_node(node);
-      }
-
+      self.compile_statements(&block.body);
       self.end_scope(&block.end_of_block);
    }
 
@@ -208,7 +351,9 @@ impl Compiler {
    /// - `token`: The token associated with the end of the scope.
    pub(super) fn end_scope(&mut self, token: &Token) {
       let scope = self.relative_scope_depth();
-      let popped_scope = self.current_func_scope_mut().s_table.pop_scope(scope, true, true);
+      self.warn_unused_symbols(scope);
+      let popped_scope = self.current_func_scope_mut().s_table.pop_scope(scope, true);
+      self.end_local_ranges();
 
       self.emit_stack_pops(popped_scope, token);
       self.current_func_scope_mut().scope_depth -= 1;
@@ -237,8 +382,11 @@ impl Compiler {
 
    /// Compiles an if statement.
    pub(super) fn compile_if_stmt(&mut self, stmt: &IfStmtNode) {
-      let condition_is_lit_true = stmt.condition.is_truthy_literal();
-      let condition_is_lit_false = stmt.condition.is_false_literal();
+      self.warn_constant_condition(&stmt.condition, false);
+
+      let constant_condition = self.constant_condition(&stmt.condition);
+      let condition_is_lit_true = constant_condition == Some(true);
+      let condition_is_lit_false = constant_condition == Some(false);
 
       let mut then_jump = 0;
       // Only execute the condition if it is not a boolean literal (or equivalent).
@@ -295,15 +443,249 @@ impl Compiler {
       }
    }
 
+   /// Compiles a `try-catch-finally` statement.
+   pub(super) fn compile_try_stmt(&mut self, stmt: &TryStmtNode) {
+      if let Some(finally_token) = &stmt.finally_token {
+         if self.start_finally_scope(finally_token).is_err() {
+            return;
+         }
+      }
+
+      // When an error is caught, the VM removes all the temporary objects from the stack,
+      // keeping only the local declarations that were made before the `try` statement.
+      let stack_depth = self.current_func_scope().s_table.len();
+
+      // Compiles the `try` block
+      self.current_func_scope_mut().try_depth += 1;
+      let try_start = self.current_chunk().len();
+      self.compile_node(&stmt.body);
+      let try_end = self.current_chunk().len();
+      let try_exit = self.emit_jump(OpCode::JumpForward, &stmt.token);
+
+      // The range of instructions whose errors should execute the `finally` block.
+      let mut protected_range = (try_start, try_end);
+
+      // Compiles the `catch` block. At runtime, the caught error lives at the top of the stack.
+      if let Some(catch_body) = &stmt.catch_body {
+         let catch_token = stmt.catch_token.as_ref().unwrap();
+         let catch_start = self.current_chunk().len();
+
+         self.current_chunk_mut().add_handler(ExceptionHandler {
+            start: try_start,
+            end: try_end,
+            target: catch_start,
+            stack_depth,
+         });
+
+         match &stmt.catch_id {
+            Some(id) => {
+               self.current_func_scope_mut().scope_depth += 1;
+
+               match self.declare_symbol(id, SymbolType::Var) {
+                  Ok(symbol_pos) => self.current_s_table_mut().mark_initialized(symbol_pos),
+                  Err(_) => return,
+               }
+
+               self.compile_node(catch_body);
+               self.end_scope(catch_token);
+            }
+            None => {
+               self.emit_op_code(
+                  OpCode::PopStackTop,
+                  (catch_token.line_num, catch_token.column_start),
+               );
+               self.compile_node(catch_body);
+            }
+         }
+
+         protected_range = (catch_start, self.current_chunk().len());
+      }
+
+      self.current_func_scope_mut().try_depth -= 1;
+
+      let finally_body = match &stmt.finally_body {
+         Some(f) => f,
+         None => {
+            self.patch_jump(try_exit, &stmt.token);
+            return;
+         }
+      };
+
+      let finally_token = stmt.finally_token.as_ref().unwrap();
+      let finally_pos = (finally_token.line_num, finally_token.column_start);
+      let scope = self.current_func_scope_mut().finally_scopes.pop().unwrap();
+
+      // If the `finally` block is reached without errors, we push a `null` onto the stack
+      // so that the `Rethrow` instruction at the end of the block does nothing. The pending
+      // exits of the `try` and `catch` blocks also enter the `finally` block through here.
+      self.patch_jump(try_exit, &stmt.token);
+      for jump in scope.jumps.iter() {
+         self.patch_jump(*jump, finally_token);
+      }
+      self.emit_op_code(OpCode::LoadImmNull, finally_pos);
+
+      // Errors not handled by the `catch` block jump straight into the `finally` block with
+      // the error at the top of the stack, which gets re-thrown at the end of the block.
+      let finally_start = self.current_chunk().len();
+      self.current_chunk_mut().add_handler(ExceptionHandler {
+         start: protected_range.0,
+         end: protected_range.1,
+         target: finally_start,
+         stack_depth,
+      });
+
+      // Emits a placeholder symbol for the pending error, which lives on the stack until the
+      // end of the `finally` block. The programmer will never be able to access this symbol's
+      // value directly because of the format of its name.
+      self.current_func_scope_mut().scope_depth += 1;
+      match self.emit_symbol(
+         &format!("<finally at #{}>", finally_start),
+         finally_token,
+         SymbolType::Const,
+      ) {
+         Ok(symbol_pos) => self.current_s_table_mut().mark_initialized(symbol_pos),
+         Err(_) => return,
+      }
+
+      self.compile_node(finally_body);
+      self.emit_op_code(OpCode::Rethrow, finally_pos);
+
+      // Removes the pending error's placeholder symbol. The `Rethrow` instruction
+      // already pops the pending error off the stack.
+      self.current_func_scope_mut().s_table.pop();
+      self.end_local_ranges();
+      self.current_func_scope_mut().scope_depth -= 1;
+
+      // Completes the pending exit (if any), and removes the locals of the `finally` scope.
+      self.emit_pending_exits(&scope, finally_token);
+      self.end_scope(finally_token);
+   }
+
+   /// Starts the scope of a `try` statement with a `finally` block, declaring the hidden
+   /// locals that hold the pending exit of the statement and the value of a pending `return`.
+   ///
+   /// # Parameters
+   /// - `token`: The `finally` token of the statement.
+   fn start_finally_scope(&mut self, token: &Token) -> Result<(), ()> {
+      let pos = (token.line_num, token.column_start);
+      self.current_func_scope_mut().scope_depth += 1;
+
+      self.emit_op_code(OpCode::LoadImm0I, pos);
+      let name = format!("<finally exit at #{}>", self.current_chunk().len());
+      let action_slot = self.emit_symbol(&name, token, SymbolType::Const)?;
+      self.current_s_table_mut().mark_initialized(action_slot);
+
+      self.emit_op_code(OpCode::LoadImmNull, pos);
+      let name = format!("<finally return at #{}>", self.current_chunk().len());
+      let value_slot = self.emit_symbol(&name, token, SymbolType::Const)?;
+      self.current_s_table_mut().mark_initialized(value_slot);
+
+      let func_scope = self.current_func_scope_mut();
+      func_scope.finally_scopes.push(FinallyScope {
+         loop_depth: func_scope.loops.len(),
+         stack_depth: func_scope.s_table.len(),
+         action_slot,
+         value_slot,
+         exits: vec![],
+         jumps: vec![],
+      });
+
+      Ok(())
+   }
+
+   /// Emits the bytecode of a `return`, `break`, or `continue` statement that leaves the `try`
+   /// or `catch` block of the inner-most `try` statement with a `finally` block. The exit is
+   /// recorded in the hidden locals of the statement, and completed after its `finally` block.
+   /// The value of a `return` statement must be at the top of the stack.
+   ///
+   /// # Parameters
+   /// - `exit`: The pending exit.
+   /// - `token`: The token associated with the exit.
+   pub(super) fn emit_finally_exit(&mut self, exit: PendingExit, token: &Token) {
+      let pos = (token.line_num, token.column_start);
+      let scope = self.current_func_scope_mut().finally_scopes.last_mut().unwrap();
+
+      let code = match scope.exits.iter().position(|e| *e == exit) {
+         Some(idx) => idx + 1,
+         None => {
+            scope.exits.push(exit);
+            scope.exits.len()
+         }
+      };
+      let (action_slot, value_slot, stack_depth) = (scope.action_slot, scope.value_slot, scope.stack_depth);
+
+      if let PendingExit::Return = exit {
+         self.emit_set_local(value_slot, pos);
+         self.emit_op_code(OpCode::PopStackTop, pos);
+      }
+
+      self.emit_op_code_with_byte(OpCode::LoadImmN, code as u8, pos);
+      self.emit_set_local(action_slot, pos);
+      self.emit_op_code(OpCode::PopStackTop, pos);
+
+      // Removes the locals declared inside the `try` statement before entering the `finally` block.
+      let popped_scope: Vec<bool> = self.current_s_table().symbols[stack_depth..]
+         .iter()
+         .rev()
+         .map(|s| s.is_captured)
+         .collect();
+      self.emit_stack_pops(popped_scope, token);
+
+      let jump = self.emit_jump(OpCode::JumpForward, token);
+      self
+         .current_func_scope_mut()
+         .finally_scopes
+         .last_mut()
+         .unwrap()
+         .jumps
+         .push(jump);
+   }
+
+   /// Emits the bytecode that completes the pending exit of a `try` statement after its
+   /// `finally` block runs. The exit leaves any enclosing `finally` block through it as well.
+   ///
+   /// # Parameters
+   /// - `scope`: The finally scope of the statement.
+   /// - `token`: The `finally` token of the statement.
+   fn emit_pending_exits(&mut self, scope: &FinallyScope, token: &Token) {
+      let pos = (token.line_num, token.column_start);
+
+      for (idx, exit) in scope.exits.iter().enumerate() {
+         self.emit_get_local(scope.action_slot, pos);
+         self.emit_op_code_with_byte(OpCode::LoadImmN, (idx + 1) as u8, pos);
+         self.emit_op_code(OpCode::Equals, pos);
+         let skip_jump = self.emit_jump(OpCode::PopJumpIfFalse, token);
+
+         match exit {
+            PendingExit::Return => {
+               self.emit_get_local(scope.value_slot, pos);
+
+               if self.current_func_scope().finally_scopes.is_empty() {
+                  self.emit_return_instr(pos);
+               } else {
+                  self.emit_finally_exit(PendingExit::Return, token);
+               }
+            }
+            PendingExit::Break(loop_idx) => self.emit_loop_branch(*loop_idx, true, token),
+            PendingExit::Continue(loop_idx) => self.emit_loop_branch(*loop_idx, false, token),
+         }
+
+         self.patch_jump(skip_jump, token);
+      }
+   }
+
    /// Compiles a class declaration statement.
    pub(super) fn compile_class_declaration(&mut self, decl: &ClassDeclNode) {
       if self.declare_symbol(&decl.name, SymbolType::Class).is_ok() {
-         let str_name = Object::from(decl.name.lexeme.clone());
+         let str_name = Object::from(decl.name.lexeme.to_string());
          let name_line_info = (decl.name.line_num, decl.name.column_start);
 
          // Adds this class to the list of class scopes
          self.classes.push(ClassScope {
             members: SymbolTable::new(vec![]),
+            name: decl.name.clone().into_owned(),
+            has_superclass: decl.superclass.is_some(),
+            in_static_method: false,
          });
 
          // Adds the class's name to the pool
@@ -319,7 +701,23 @@ impl Compiler {
          if name_pool_pos < 256 {
             self.emit_op_code_with_byte(OpCode::MakeClass, name_pool_pos as u8, name_line_info)
          } else {
-            self.emit_op_code_with_short(OpCode::MakeClass, name_pool_pos, name_line_info)
+            self.emit_op_code_with_short(OpCode::MakeClassLong, name_pool_pos, name_line_info)
+         }
+
+         // Copies the members of the superclass into the class. The members
+         // declared by the class itself are appended afterwards, overriding them.
+         if let Some(superclass) = &decl.superclass {
+            if superclass.token.lexeme == decl.name.lexeme {
+               self.error_at_token(
+                  &superclass.token,
+                  CompilerErrorType::Reference,
+                  "A class cannot inherit from itself.",
+               );
+            }
+
+            self.compile_identifier_expr(superclass);
+            let super_line_info = (superclass.token.line_num, superclass.token.column_start);
+            self.emit_op_code(OpCode::Inherit, super_line_info);
          }
 
          // Emits the class members
@@ -333,17 +731,45 @@ impl Compiler {
                   self.emit_raw_byte(member.mode, (c.name.line_num, c.name.column_start));
                }
                ClassMemberDecl::Method(m) => {
-                  if m.name.lexeme == "init" {
+                  // Static methods are called without an instance, so they cannot use `self`.
+                  let is_static = (member.mode & 0b_0000_0100) == 4;
+                  self.current_class_scope_mut().unwrap().in_static_method = is_static;
+
+                  // Property accessors are stored as methods named `get <prop>` or `set <prop>`,
+                  // which cannot clash with other members, nor be called directly.
+                  let accessor = match member.mode & 0b_0011_0000 {
+                     0b_0001_0000 => Some("get"),
+                     0b_0010_0000 => Some("set"),
+                     _ => None,
+                  };
+
+                  if let Some(kind) = accessor {
+                     let mut accessor_decl = m.clone();
+                     accessor_decl.name.lexeme = format!("{} {}", kind, m.name.lexeme).into();
+                     self.compile_function_decl(&accessor_decl, CompilerCtx::Method)
+                  } else if m.name.lexeme == "init" {
                      self.compile_function_decl(m, CompilerCtx::Init)
                   } else {
                      self.compile_function_decl(m, CompilerCtx::Method)
                   }
 
-                  self.emit_raw_byte(member.mode, (m.name.line_num, m.name.column_start));
+                  self.current_class_scope_mut().unwrap().in_static_method = false;
+
+                  let mode = member.mode & 0b_0000_1111;
+                  self.emit_raw_byte(mode, (m.name.line_num, m.name.column_start));
                }
             }
          }
 
+         // Checks that the class defines the methods of the interfaces it implements. The check
+         // is also performed at runtime, once the members of the superclass are known.
+         for interface in decl.interfaces.iter() {
+            self.check_implemented_interface(decl, interface);
+            self.compile_identifier_expr(interface);
+            let interface_line_info = (interface.token.line_num, interface.token.column_start);
+            self.emit_op_code(OpCode::Implement, interface_line_info);
+         }
+
          // Return the compiler to its previous context
          self.compiler_type = prev_compiler_type;
 
@@ -356,4 +782,118 @@ impl Compiler {
          self.classes.pop();
       }
    }
+
+   /// Checks at compile time that a class defines the methods required by an interface it
+   /// implements. Only interfaces declared in the same program can be checked, and methods
+   /// missing from a class with a superclass are left for the runtime check, since they
+   /// could be inherited.
+   ///
+   /// # Parameters
+   /// - `decl`: The class declaration.
+   /// - `interface`: The name of the implemented interface.
+   fn check_implemented_interface(&mut self, decl: &ClassDeclNode, interface: &IdentifierExprNode) {
+      let signatures = match self.interfaces.get(interface.token.lexeme.as_ref()) {
+         Some(i) => i.clone(),
+         None => return,
+      };
+
+      for (name, arity) in signatures.methods.iter() {
+         let method = decl.members.iter().find_map(|member| match &member.member_type {
+            ClassMemberDecl::Method(m) if m.name.lexeme == *name && member.mode & 0b_0011_0000 == 0 => {
+               Some((m, member.mode))
+            }
+            _ => None,
+         });
+
+         let message = match method {
+            None if decl.superclass.is_some() => continue,
+            Some((m, mode)) if mode & 0b_0000_1000 == 8 => {
+               let is_variadic = m.params.last().is_some_and(|p| p.is_variadic);
+
+               if *arity >= m.arity.0 && (is_variadic || *arity <= m.arity.1) {
+                  continue;
+               }
+
+               format!(
+                  "Method '{}' of class '{}' must accept {} argument(s) to implement interface '{}'.",
+                  name, decl.name.lexeme, arity, signatures.name
+               )
+            }
+            _ => format!(
+               "Class '{}' does not implement the public method '{}' of interface '{}'.",
+               decl.name.lexeme, name, signatures.name
+            ),
+         };
+
+         self.error_at_token(&interface.token, CompilerErrorType::Reference, &message);
+         return;
+      }
+   }
+
+   /// Compiles an interface declaration. Interfaces are compiled as constant
+   /// objects holding the names and number of parameters of their methods.
+   pub(super) fn compile_interface_declaration(&mut self, decl: &InterfaceDeclNode) {
+      if let Ok(symbol_pos) = self.declare_symbol(&decl.name, SymbolType::Interface) {
+         let interface = Rc::new(InterfaceObject {
+            name: decl.name.lexeme.to_string(),
+            methods: decl
+               .methods
+               .iter()
+               .map(|m| (m.name.lexeme.to_string(), m.params.len() as u8))
+               .collect(),
+         });
+
+         self
+            .interfaces
+            .insert(decl.name.lexeme.to_string(), interface.clone());
+
+         if self
+            .add_literal_to_pool(Object::Interface(interface), &decl.name, true)
+            .is_none()
+         {
+            return;
+         }
+
+         if self.is_global_scope() {
+            self.define_as_global(&decl.name);
+         }
+
+         self.current_s_table_mut().mark_initialized(symbol_pos)
+      }
+   }
+
+   /// Compiles an enum declaration. The enum is stored as a constant, because its variants
+   /// are known at compile time.
+   pub(super) fn compile_enum_declaration(&mut self, decl: &EnumDeclNode) {
+      if let Ok(symbol_pos) = self.declare_symbol(&decl.name, SymbolType::Enum) {
+         let enum_obj = Rc::new(EnumObject {
+            name: decl.name.lexeme.to_string(),
+            variants: decl
+               .variants
+               .iter()
+               .map(|v| {
+                  (
+                     v.name.lexeme.to_string(),
+                     v.fields.iter().map(|f| f.lexeme.to_string()).collect(),
+                  )
+               })
+               .collect(),
+         });
+
+         self.enums.insert(decl.name.lexeme.to_string(), enum_obj.clone());
+
+         if self
+            .add_literal_to_pool(Object::Enum(enum_obj), &decl.name, true)
+            .is_none()
+         {
+            return;
+         }
+
+         if self.is_global_scope() {
+            self.define_as_global(&decl.name);
+         }
+
+         self.current_s_table_mut().mark_initialized(symbol_pos)
+      }
+   }
 }
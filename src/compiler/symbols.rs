@@ -1,12 +1,15 @@
 use super::UpValue;
+use crate::core::tokens::Span;
 
 /// Types of symbols available in Hinton.
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum SymbolType {
    Class,
    Const,
    ConstField,
+   Enum,
    Func,
+   Interface,
    Method,
    Param,
    Var,
@@ -130,12 +133,11 @@ impl SymbolTable {
    /// # Parameters
    /// - `min_depth`: The minimum scope depth to pop the symbols.
    /// - `pop_symbols`: Whether the symbols should actually be popped from the symbol table or not.
-   ///- `show_warning`: Whether to show a warning message for unused symbols or not.
    ///
    /// # Returns
    /// - `Vev<bool>`: A vector with a boolean entry for each popped symbol, where `true` means that
    /// the symbol was captured by a closure, and `false` means it was not captured by a closure.
-   pub fn pop_scope(&mut self, min_depth: usize, pop_symbols: bool, show_warning: bool) -> Vec<bool> {
+   pub fn pop_scope(&mut self, min_depth: usize, pop_symbols: bool) -> Vec<bool> {
       // We get the ith symbol (from the back) instead of getting the `.last()` because when
       // the `pop_symbol` parameter is false, the loop may become infinite (because we are not
       // popping the symbol off the table).
@@ -147,13 +149,6 @@ impl SymbolTable {
             break;
          }
 
-         if !symbol.is_used && show_warning {
-            println!(
-               "\x1b[33;1mWarning\x1b[0m at [{}:{}] – Variable '\x1b[1m{}\x1b[0m' is never used.",
-               symbol.line_info.0, symbol.line_info.1, symbol.name
-            );
-         }
-
          popped_symbols.push(symbol.is_captured);
 
          if pop_symbols {
@@ -170,3 +165,55 @@ impl SymbolTable {
       popped_symbols
    }
 }
+
+/// A declaration made in a program, as recorded in the program's symbol index.
+#[derive(Clone, Debug)]
+pub struct Declaration {
+   /// The declared name.
+   pub name: String,
+   /// The declaration type.
+   pub s_type: SymbolType,
+   /// The range of bytes of the declared name in the source text.
+   pub span: Span,
+   /// The line and column positions of the declared name in the source code.
+   pub line_info: (usize, usize),
+   /// Whether the declaration was made in the global scope.
+   pub is_global: bool,
+   /// A description of the declaration, with its type or signature (e.g., `var x: Int`).
+   pub description: String,
+}
+
+/// An identifier that refers to a declaration.
+#[derive(Clone, Debug)]
+pub struct Reference {
+   /// The range of bytes of the identifier in the source text.
+   pub span: Span,
+   /// The line and column positions of the name of the declaration it refers to.
+   pub declaration: (usize, usize),
+}
+
+/// The declarations made in a program and the identifiers that refer to them, as resolved by
+/// the compiler. Used by editor tooling (e.g., to go to the definition of an identifier).
+#[derive(Clone, Debug, Default)]
+pub struct SymbolIndex {
+   pub declarations: Vec<Declaration>,
+   pub references: Vec<Reference>,
+}
+
+impl SymbolIndex {
+   /// Finds the declaration of the identifier at a position of the source text, which is
+   /// either a reference to the declaration or the declared name itself.
+   ///
+   /// # Parameters
+   /// - `offset`: The byte offset in the source text.
+   pub fn declaration_at(&self, offset: usize) -> Option<&Declaration> {
+      let contains = |span: &Span| span.start <= offset && offset <= span.end;
+
+      let line_info = match self.references.iter().find(|r| contains(&r.span)) {
+         Some(reference) => reference.declaration,
+         None => return self.declarations.iter().find(|d| contains(&d.span)),
+      };
+
+      self.declarations.iter().find(|d| d.line_info == line_info)
+   }
+}
@@ -0,0 +1,742 @@
+use crate::core::ast::*;
+use crate::core::tokens::Token;
+use crate::objects::Object;
+use hashbrown::{HashMap, HashSet};
+
+/// The names of the built-in types that can be used in type annotations.
+pub(super) const BUILT_IN_TYPES: [&str; 18] = [
+   "Any", "Array", "BigInt", "Bool", "Bytes", "Char", "DateTime", "Dict", "Float", "Function", "Int", "Null",
+   "Range", "Regex", "Set", "String", "Tuple", "Void",
+];
+
+/// The descriptions of the declarations in a program, by the line and column of the declared name.
+pub type Descriptions = HashMap<(usize, usize), String>;
+
+/// Represents the static type of an expression, as far as the type checker can tell.
+#[derive(Clone, PartialEq)]
+enum Type {
+   /// The type of the expression is unknown, so it is compatible with every type.
+   Any,
+   /// A built-in type (e.g., `Int`), or the name of a class whose instances have this type.
+   Named(String),
+}
+
+impl Type {
+   /// Creates the type with the given name.
+   fn named(name: &str) -> Self {
+      Type::Named(name.to_string())
+   }
+
+   /// Checks if a value of this type can be used where a value of the `expected` type is
+   /// expected. Integers can be used where floats or BigInts are expected.
+   fn is_assignable_to(&self, expected: &Type) -> bool {
+      match (self, expected) {
+         (Type::Any, _) | (_, Type::Any) => true,
+         (Type::Named(actual), Type::Named(expected)) => {
+            actual == expected || (actual == "Int" && (expected == "Float" || expected == "BigInt"))
+         }
+      }
+   }
+
+   /// Gets the name of this type, as displayed in error messages.
+   fn name(&self) -> &str {
+      match self {
+         Type::Any => "Any",
+         Type::Named(name) => name,
+      }
+   }
+
+   /// Checks if this type is a numeric type.
+   fn is_numeric(&self) -> bool {
+      matches!(self, Type::Named(n) if n == "Int" || n == "BigInt" || n == "Float")
+   }
+}
+
+/// Represents a declaration known to the type checker.
+#[derive(Clone)]
+enum Binding {
+   /// A variable, constant, or parameter, and its declared type.
+   Value(Type),
+   /// A function, and the declared types of its parameters and return value.
+   Function(Vec<Type>, Type),
+   /// A class or an interface.
+   Class,
+}
+
+/// A best-effort static type checker, which runs before a program is compiled to find the type
+/// errors that can be detected from the program's type annotations. Declarations without type
+/// annotations have the `Any` type, so programs without annotations never have type errors.
+pub struct TypeChecker {
+   /// The declarations in each lexical scope, where the last element is the inner-most scope.
+   scopes: Vec<HashMap<String, Binding>>,
+   /// The declared return type of each function being checked.
+   return_types: Vec<Type>,
+   /// The names of the classes declared in the program, which can be used as types.
+   classes: HashSet<String>,
+   /// The type errors found in the program, as the token that caused the error and a message.
+   errors: Vec<(Token<'static>, String)>,
+   /// The descriptions of the declarations in the program, with their declared or inferred types.
+   descriptions: Descriptions,
+}
+
+impl TypeChecker {
+   /// Type-checks a program.
+   ///
+   /// # Parameters
+   /// - `program`: The root node of the program's AST.
+   ///
+   /// # Returns
+   /// - `Vec<(Token<'static>, String)>`: The type errors found in the program, as the token that caused
+   ///   each error and the error message.
+   pub fn check(program: &ASTNode) -> Vec<(Token<'static>, String)> {
+      TypeChecker::check_and_describe(program).0
+   }
+
+   /// Type-checks a program, and describes the declarations made in it (e.g., `var x: Int`, or
+   /// the signature and arity of a function), which are shown by editor tooling.
+   ///
+   /// # Parameters
+   /// - `program`: The root node of the program's AST.
+   ///
+   /// # Returns
+   /// - `(Vec<(Token<'static>, String)>, Descriptions)`: The type errors found in the program, and the
+   ///   descriptions of the declarations by the line and column of their names.
+   pub fn check_and_describe(program: &ASTNode) -> (Vec<(Token<'static>, String)>, Descriptions) {
+      let mut checker = TypeChecker {
+         scopes: vec![HashMap::new()],
+         return_types: vec![],
+         classes: HashSet::new(),
+         errors: vec![],
+         descriptions: HashMap::new(),
+      };
+
+      checker.collect_classes(program);
+      checker.check_node(program);
+      (checker.errors, checker.descriptions)
+   }
+
+   /// Collects the names of the classes declared in the program, so that they can be used
+   /// as types before their declaration.
+   fn collect_classes(&mut self, node: &ASTNode) {
+      match node {
+         ASTNode::Module(m) => m.body.iter().for_each(|n| self.collect_classes(n)),
+         ASTNode::BlockStmt(b) => b.body.iter().for_each(|n| self.collect_classes(n)),
+         ASTNode::ExportDecl(e) => self.collect_classes(&e.decl),
+         ASTNode::ClassDecl(c) => {
+            self.classes.insert(c.name.lexeme.to_string());
+         }
+         ASTNode::EnumDecl(e) => {
+            self.classes.insert(e.name.lexeme.to_string());
+         }
+         _ => {}
+      }
+   }
+
+   /// Resolves a type annotation into a type. Unknown type names are reported as errors.
+   fn resolve_annotation(&mut self, annotation: &Option<TypeAnnotation>) -> Type {
+      let token = match annotation {
+         Some(a) => &a.name,
+         None => return Type::Any,
+      };
+
+      match token.lexeme.as_ref() {
+         "Any" => Type::Any,
+         "Void" => Type::named("Null"),
+         name if BUILT_IN_TYPES.contains(&name) || self.classes.contains(name) => Type::named(name),
+         name => {
+            self.error(token, format!("Unknown type '{}'.", name));
+            Type::Any
+         }
+      }
+   }
+
+   /// Declares a name in the current scope.
+   fn declare(&mut self, name: &str, binding: Binding) {
+      self.scopes.last_mut().unwrap().insert(name.to_string(), binding);
+   }
+
+   /// Finds the inner-most declaration with the given name.
+   fn lookup(&self, name: &str) -> Option<&Binding> {
+      self.scopes.iter().rev().find_map(|s| s.get(name))
+   }
+
+   /// Records the description of a declaration.
+   fn describe(&mut self, token: &Token, description: String) {
+      self
+         .descriptions
+         .insert((token.line_num, token.column_start), description);
+   }
+
+   /// Records a type error.
+   fn error(&mut self, token: &Token, message: String) {
+      self.errors.push((token.clone().into_owned(), message));
+   }
+
+   /// Checks that a value of type `actual` can be assigned to a declaration of type `expected`.
+   fn check_assignment(&mut self, token: &Token, actual: &Type, expected: &Type) {
+      if !actual.is_assignable_to(expected) {
+         self.error(
+            token,
+            format!(
+               "Cannot assign a value of type '{}' to '{}', which is declared as '{}'.",
+               actual.name(),
+               token.lexeme,
+               expected.name()
+            ),
+         );
+      }
+   }
+
+   /// Type-checks a statement or declaration.
+   fn check_node(&mut self, node: &ASTNode) {
+      match node {
+         ASTNode::Module(m) => m.body.iter().for_each(|n| self.check_node(n)),
+         ASTNode::BlockStmt(b) => self.check_scoped(&b.body),
+         ASTNode::ExpressionStmt(e) => {
+            self.infer(&e.child);
+         }
+         ASTNode::VariableDecl(v) => self.check_variable_decl(v),
+         ASTNode::ConstantDecl(c) => self.check_constant_decl(c),
+         ASTNode::FunctionDecl(f) => {
+            let signature = self.function_signature(f);
+            self.declare(&f.name.lexeme, signature);
+            self.check_function_body(f);
+         }
+         ASTNode::ClassDecl(c) => self.check_class_decl(c),
+         ASTNode::InterfaceDecl(i) => self.check_interface_decl(i),
+         ASTNode::EnumDecl(e) => {
+            self.declare(&e.name.lexeme, Binding::Class);
+            self.describe(&e.name, format!("enum {}", e.name.lexeme));
+         }
+         ASTNode::ExportDecl(e) => self.check_node(&e.decl),
+         ASTNode::ImportDecl(i) => {
+            self.describe(&i.name, format!("const {}", i.name.lexeme));
+            self.declare(&i.name.lexeme, Binding::Value(Type::Any))
+         }
+         ASTNode::IfStmt(i) => {
+            self.infer(&i.condition);
+            self.check_node(&i.then_branch);
+
+            if let Some(else_branch) = &*i.else_branch {
+               self.check_node(else_branch);
+            }
+         }
+         ASTNode::WhileStmt(w) => {
+            if let Some(condition) = &w.condition {
+               self.infer(condition);
+            }
+            self.check_node(&w.body);
+         }
+         ASTNode::ForStmt(f) => {
+            self.infer(&f.iterator);
+            self.scopes.push(HashMap::new());
+            self.declare(&f.id.token.lexeme, Binding::Value(Type::Any));
+            self.describe(&f.id.token, format!("var {}", f.id.token.lexeme));
+            if let Some(value_id) = &f.value_id {
+               self.declare(&value_id.token.lexeme, Binding::Value(Type::Any));
+               self.describe(&value_id.token, format!("var {}", value_id.token.lexeme));
+            }
+            f.body.iter().for_each(|n| self.check_node(n));
+            self.scopes.pop();
+         }
+         ASTNode::MatchStmt(m) => {
+            self.infer(&m.subject);
+            for arm in m.arms.iter() {
+               self.scopes.push(HashMap::new());
+
+               for name in arm.patterns.iter().flat_map(|p| p.bindings()) {
+                  self.declare(&name.lexeme, Binding::Value(Type::Any));
+                  self.describe(name, format!("var {}", name.lexeme));
+               }
+
+               if let Some(guard) = &arm.guard {
+                  self.infer(guard);
+               }
+
+               self.check_node(&arm.body);
+               self.scopes.pop();
+            }
+
+            if let Some(default) = &m.default {
+               self.check_node(default);
+            }
+         }
+         ASTNode::TryStmt(t) => {
+            self.check_node(&t.body);
+
+            if let Some(catch_body) = &t.catch_body {
+               self.scopes.push(HashMap::new());
+               if let Some(id) = &t.catch_id {
+                  self.declare(&id.lexeme, Binding::Value(Type::named("Dict")));
+                  self.describe(id, format!("var {}: Dict", id.lexeme));
+               }
+               self.check_node(catch_body);
+               self.scopes.pop();
+            }
+
+            if let Some(finally_body) = &t.finally_body {
+               self.check_node(finally_body);
+            }
+         }
+         ASTNode::ReturnStmt(r) => self.check_return_stmt(r),
+         ASTNode::DeferStmt(d) => self.check_function_body(&d.body),
+         ASTNode::AssertStmt(a) => {
+            self.infer(&a.condition);
+
+            if let Some(message) = &a.message {
+               self.infer(message);
+            }
+         }
+         ASTNode::LoopBranch(b) => {
+            if let Some(value) = &b.value {
+               self.infer(value);
+            }
+         }
+         _ => {
+            self.infer(node);
+         }
+      }
+   }
+
+   /// Type-checks a list of statements in a new scope.
+   fn check_scoped(&mut self, body: &[ASTNode]) {
+      self.scopes.push(HashMap::new());
+      body.iter().for_each(|n| self.check_node(n));
+      self.scopes.pop();
+   }
+
+   /// Type-checks a variable declaration.
+   fn check_variable_decl(&mut self, decl: &VariableDeclNode) {
+      // Variables declared without a value are initialized to `null`, which is allowed for
+      // every type so that variables can be declared before being assigned.
+      let has_value = !matches!(&*decl.value, ASTNode::Literal(l) if matches!(l.value, Object::Null) && l.token.lexeme != "null");
+      let value_type = self.infer(&decl.value);
+
+      // The types of the items of a destructured value are not known.
+      let value_type = if decl.destructure { Type::Any } else { value_type };
+
+      for (id, annotation) in decl.identifiers.iter().zip(decl.annotations.iter()) {
+         let expected = self.resolve_annotation(annotation);
+
+         if has_value {
+            self.check_assignment(id, &value_type, &expected);
+         }
+
+         // Variables declared without a type annotation are described with the type of their value.
+         let shown = if expected == Type::Any && has_value {
+            &value_type
+         } else {
+            &expected
+         };
+         self.describe(id, format!("var {}: {}", id.lexeme, shown.name()));
+
+         self.declare(&id.lexeme, Binding::Value(expected));
+      }
+   }
+
+   /// Type-checks a constant declaration.
+   fn check_constant_decl(&mut self, decl: &ConstantDeclNode) {
+      let value_type = self.infer(&decl.value);
+      let expected = self.resolve_annotation(&decl.annotation);
+
+      self.check_assignment(&decl.name, &value_type, &expected);
+
+      let shown = if expected == Type::Any {
+         &value_type
+      } else {
+         &expected
+      };
+      self.describe(
+         &decl.name,
+         format!("const {}: {}", decl.name.lexeme, shown.name()),
+      );
+
+      self.declare(&decl.name.lexeme, Binding::Value(expected));
+   }
+
+   /// Type-checks a class declaration, including the bodies of its methods.
+   fn check_class_decl(&mut self, decl: &ClassDeclNode) {
+      self.declare(&decl.name.lexeme, Binding::Class);
+      let description = match &decl.doc {
+         Some(doc) => format!("class {}\n\n{}", decl.name.lexeme, doc),
+         None => format!("class {}", decl.name.lexeme),
+      };
+      self.describe(&decl.name, description);
+      self.scopes.push(HashMap::new());
+
+      for member in decl.members.iter() {
+         match &member.member_type {
+            ClassMemberDecl::Var(v) => self.check_variable_decl(v),
+            ClassMemberDecl::Const(c) => self.check_constant_decl(c),
+            ClassMemberDecl::Method(m) => self.check_function_body(m),
+         }
+      }
+
+      self.scopes.pop();
+   }
+
+   /// Type-checks an interface declaration, which only checks that
+   /// the type annotations in its method signatures are known types.
+   fn check_interface_decl(&mut self, decl: &InterfaceDeclNode) {
+      self.declare(&decl.name.lexeme, Binding::Class);
+      self.describe(&decl.name, format!("interface {}", decl.name.lexeme));
+
+      for method in decl.methods.iter() {
+         for param in method.params.iter() {
+            self.resolve_annotation(&param.annotation);
+         }
+
+         self.resolve_annotation(&method.return_type);
+      }
+   }
+
+   /// Gets the signature of a function from its parameter and return type annotations.
+   fn function_signature(&mut self, decl: &FunctionDeclNode) -> Binding {
+      let params = decl
+         .params
+         .iter()
+         .filter(|p| !p.is_variadic)
+         .map(|p| self.resolve_annotation(&p.annotation))
+         .collect();
+      let return_type = self.resolve_annotation(&decl.return_type);
+      Binding::Function(params, return_type)
+   }
+
+   /// Type-checks the parameters and body of a function.
+   fn check_function_body(&mut self, decl: &FunctionDeclNode) {
+      let return_type = self.resolve_annotation(&decl.return_type);
+      self.describe_function(decl, &return_type);
+      self.scopes.push(HashMap::new());
+
+      for param in decl.params.iter() {
+         let expected = self.resolve_annotation(&param.annotation);
+         self.describe(
+            &param.name,
+            format!("param {}: {}", param.name.lexeme, expected.name()),
+         );
+
+         if let Some(default) = &param.default {
+            let default_type = self.infer(default);
+            self.check_assignment(&param.name, &default_type, &expected);
+         }
+
+         // The rest parameter is an array of the arguments, each annotated with the given type.
+         if param.is_variadic {
+            self.declare(&param.name.lexeme, Binding::Value(Type::named("Array")));
+         } else {
+            self.declare(&param.name.lexeme, Binding::Value(expected));
+         }
+      }
+
+      self.return_types.push(return_type);
+      decl.body.iter().for_each(|n| self.check_node(n));
+      self.return_types.pop();
+      self.scopes.pop();
+   }
+
+   /// Describes a function with its signature and the number of arguments it takes
+   /// (e.g., `func add(a: Int, b = ...) -> Int`, which takes 1 to 2 arguments).
+   fn describe_function(&mut self, decl: &FunctionDeclNode, return_type: &Type) {
+      let (min, max) = decl.arity;
+      let arguments = |n: u8| format!("{} argument{}", n, if n == 1 { "" } else { "s" });
+      let arity = if decl.params.iter().any(|p| p.is_variadic) {
+         format!("Takes at least {}.", arguments(min))
+      } else if min == max {
+         format!("Takes {}.", arguments(min))
+      } else {
+         format!("Takes {} to {} arguments.", min, max)
+      };
+
+      let mut description = format!(
+         "func {}({}) -> {}\n\n{}",
+         decl.name.lexeme,
+         decl.parameter_list(),
+         return_type.name(),
+         arity
+      );
+
+      if let Some(doc) = &decl.doc {
+         description.push_str(&format!("\n\n{}", doc));
+      }
+
+      self.describe(&decl.name, description);
+   }
+
+   /// Type-checks a `return` statement against the return type of the enclosing function.
+   fn check_return_stmt(&mut self, stmt: &ReturnStmtNode) {
+      let actual = match &stmt.value {
+         Some(value) => self.infer(value),
+         None => Type::named("Null"),
+      };
+
+      let expected = match self.return_types.last() {
+         Some(t) => t.clone(),
+         None => return,
+      };
+
+      if !actual.is_assignable_to(&expected) {
+         self.error(
+            &stmt.token,
+            format!(
+               "Cannot return a value of type '{}' from a function declared to return '{}'.",
+               actual.name(),
+               expected.name()
+            ),
+         );
+      }
+   }
+
+   /// Type-checks a function call, and infers the type of its return value.
+   fn check_call(&mut self, call: &FunctionCallExprNode) -> Type {
+      let arg_types: Vec<Type> = call.args.iter().map(|a| self.infer(&a.value)).collect();
+
+      let token = match &*call.target {
+         ASTNode::Identifier(id) => &id.token,
+         target => {
+            self.infer(target);
+            return Type::Any;
+         }
+      };
+
+      let (params, return_type) = match self.lookup(&token.lexeme) {
+         Some(Binding::Function(params, return_type)) => (params.clone(), return_type.clone()),
+         _ => return Type::Any,
+      };
+
+      // Named and spread arguments are matched to their parameters at runtime,
+      // so only the positional arguments before them are checked.
+      let positional = call
+         .args
+         .iter()
+         .take_while(|a| !a.is_named && !a.is_spread)
+         .count();
+
+      for (idx, (actual, expected)) in arg_types.iter().zip(params.iter()).take(positional).enumerate() {
+         if !actual.is_assignable_to(expected) {
+            self.error(
+               token,
+               format!(
+                  "Argument {} of '{}' expected a value of type '{}', but got '{}'.",
+                  idx + 1,
+                  token.lexeme,
+                  expected.name(),
+                  actual.name()
+               ),
+            );
+         }
+      }
+
+      return_type
+   }
+
+   /// Infers the type of an expression, type-checking its sub-expressions along the way.
+   fn infer(&mut self, node: &ASTNode) -> Type {
+      match node {
+         ASTNode::Literal(l) => Type::Named(l.value.type_name()),
+         ASTNode::Array(a) => {
+            a.values.iter().for_each(|v| {
+               self.infer(v);
+            });
+            Type::named("Array")
+         }
+         ASTNode::Tuple(t) => {
+            t.values.iter().for_each(|v| {
+               self.infer(v);
+            });
+            Type::named("Tuple")
+         }
+         ASTNode::Dictionary(d) => {
+            d.values.iter().for_each(|v| {
+               self.infer(v);
+            });
+            Type::named("Dict")
+         }
+         ASTNode::Comprehension(c) => {
+            self.scopes.push(HashMap::new());
+
+            for clause in c.clauses.iter() {
+               self.infer(&clause.iterator);
+
+               for id in std::iter::once(&clause.id).chain(clause.value_id.iter()) {
+                  self.declare(&id.token.lexeme, Binding::Value(Type::Any));
+                  self.describe(&id.token, format!("var {}", id.token.lexeme));
+               }
+
+               if let Some(condition) = &clause.condition {
+                  self.infer(condition);
+               }
+            }
+
+            if let Some(key) = &c.key {
+               self.infer(key);
+            }
+
+            self.infer(&c.value);
+            self.scopes.pop();
+
+            match c.key {
+               Some(_) => Type::named("Dict"),
+               None => Type::named("Array"),
+            }
+         }
+         ASTNode::StringInterpolation(s) => {
+            s.parts.iter().for_each(|p| {
+               self.infer(p);
+            });
+            Type::named("String")
+         }
+         ASTNode::Identifier(id) => match self.lookup(&id.token.lexeme) {
+            Some(Binding::Value(t)) => t.clone(),
+            Some(Binding::Function(..)) => Type::named("Function"),
+            _ => Type::Any,
+         },
+         ASTNode::Lambda(f) => {
+            self.check_function_body(f);
+            Type::named("Function")
+         }
+         ASTNode::LoopExpr(l) => {
+            self.check_node(&l.body);
+            Type::Any
+         }
+         ASTNode::FunctionCall(call) => self.check_call(call),
+         ASTNode::Instance(call) => {
+            self.check_call(call);
+
+            match &*call.target {
+               ASTNode::Identifier(id) if self.classes.contains(id.token.lexeme.as_ref()) => {
+                  Type::Named(id.token.lexeme.to_string())
+               }
+               _ => Type::Any,
+            }
+         }
+         ASTNode::Unary(u) => {
+            let operand = self.infer(&u.operand);
+
+            match u.opr_type {
+               UnaryExprType::LogicNeg => Type::named("Bool"),
+               UnaryExprType::ArithmeticNeg if operand.is_numeric() => operand,
+               UnaryExprType::BitwiseNeg if operand == Type::named("Int") => operand,
+               _ => Type::Any,
+            }
+         }
+         ASTNode::Binary(b) => self.infer_binary(b),
+         ASTNode::ComparisonChain(c) => {
+            for operand in c.operands.iter() {
+               self.infer(operand);
+            }
+
+            Type::named("Bool")
+         }
+         ASTNode::TernaryConditional(t) => {
+            self.infer(&t.condition);
+            let true_type = self.infer(&t.branch_true);
+            let false_type = self.infer(&t.branch_false);
+
+            if true_type == false_type {
+               true_type
+            } else {
+               Type::Any
+            }
+         }
+         ASTNode::VarReassignment(r) => {
+            let value_type = self.infer(&r.value);
+
+            if let ReassignmentType::Assign = r.opr_type {
+               if let Some(Binding::Value(expected)) = self.lookup(&r.target.lexeme) {
+                  let expected = expected.clone();
+                  self.check_assignment(&r.target, &value_type, &expected);
+               }
+            }
+
+            value_type
+         }
+         ASTNode::Subscript(s) => {
+            self.infer(&s.target);
+            self.infer(&s.index);
+            Type::Any
+         }
+         ASTNode::Slice(s) => {
+            let target = self.infer(&s.target);
+
+            for part in [&s.start, &s.end, &s.step].iter().filter_map(|p| p.as_ref()) {
+               self.infer(part);
+            }
+
+            // Slicing an array, string, tuple, or Bytes produces a value of the same type.
+            match target.name() {
+               "Array" | "Bytes" | "String" | "Tuple" => target,
+               _ => Type::Any,
+            }
+         }
+         ASTNode::SubscriptAssignment(s) => {
+            self.infer(&s.target);
+            self.infer(&s.index);
+            self.infer(&s.value)
+         }
+         ASTNode::ObjectGetter(g) => {
+            self.infer(&g.target);
+            Type::Any
+         }
+         ASTNode::OptionalChain(c) => {
+            self.infer(&c.chain);
+            Type::Any
+         }
+         ASTNode::NonNullAssertion(n) => self.infer(&n.operand),
+         ASTNode::ObjectSetter(s) => {
+            self.infer(&s.target);
+            self.infer(&s.value)
+         }
+         _ => Type::Any,
+      }
+   }
+
+   /// Infers the type of a binary expression.
+   fn infer_binary(&mut self, expr: &BinaryExprNode) -> Type {
+      let left = self.infer(&expr.left);
+      let right = self.infer(&expr.right);
+      let is_string = |t: &Type| *t == Type::named("String");
+      let is_int = |t: &Type| *t == Type::named("Int");
+      let is_integer = |t: &Type| is_int(t) || *t == Type::named("BigInt");
+
+      match expr.opr_type {
+         BinaryExprType::LogicEQ
+         | BinaryExprType::LogicNotEQ
+         | BinaryExprType::LogicGreaterThan
+         | BinaryExprType::LogicGreaterThanEQ
+         | BinaryExprType::LogicIn
+         | BinaryExprType::LogicIs
+         | BinaryExprType::LogicLessThan
+         | BinaryExprType::LogicLessThanEQ => Type::named("Bool"),
+         BinaryExprType::Range | BinaryExprType::RangeInclusive => Type::named("Range"),
+         BinaryExprType::Addition if is_string(&left) || is_string(&right) => Type::named("String"),
+         BinaryExprType::Addition
+         | BinaryExprType::Minus
+         | BinaryExprType::Multiplication
+         | BinaryExprType::Modulus
+         | BinaryExprType::FloorDivision
+         | BinaryExprType::Expo => {
+            if is_int(&left) && is_int(&right) {
+               Type::named("Int")
+            } else if is_integer(&left) && is_integer(&right) {
+               Type::named("BigInt")
+            } else if left.is_numeric() && right.is_numeric() {
+               Type::named("Float")
+            } else {
+               Type::Any
+            }
+         }
+         // Division always produces a float, even if both operands are integers.
+         BinaryExprType::Division if left.is_numeric() && right.is_numeric() => Type::named("Float"),
+         BinaryExprType::BitwiseAND
+         | BinaryExprType::BitwiseOR
+         | BinaryExprType::BitwiseXOR
+         | BinaryExprType::BitwiseShiftLeft
+         | BinaryExprType::BitwiseShiftRight
+            if is_int(&left) && is_int(&right) =>
+         {
+            Type::named("Int")
+         }
+         _ => Type::Any,
+      }
+   }
+}
@@ -0,0 +1,242 @@
+use crate::compiler::symbols::SymbolType;
+use crate::compiler::Compiler;
+use crate::core::ast::{ASTNode, MatchPattern, MatchStmtNode};
+use crate::core::tokens::Token;
+use crate::errors::{CompilerWarningType, ErrorReport, WarningReport};
+
+/// Determines how the warnings generated by the compiler are handled.
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum WarningMode {
+   /// Warnings are reported without aborting the compilation.
+   #[default]
+   Show,
+   /// Warnings are not reported.
+   Silence,
+   /// Warnings are reported as errors, which aborts the compilation.
+   Deny,
+}
+
+impl Compiler {
+   /// Warns about the symbols declared in the current scope (or in the scopes nested in it)
+   /// that are never used. Unused parameters are not reported.
+   ///
+   /// # Parameters
+   /// - `min_depth`: The minimum scope depth of the symbols to check.
+   pub(super) fn warn_unused_symbols(&mut self, min_depth: usize) {
+      let unused: Vec<(String, (usize, usize))> = self
+         .current_s_table()
+         .symbols
+         .iter()
+         .filter(|s| s.depth >= min_depth && !s.is_used)
+         .filter(|s| !matches!(s.s_type, SymbolType::Param))
+         .filter(|s| !s.name.starts_with('<'))
+         .map(|s| (s.name.clone(), s.line_info))
+         .collect();
+
+      for (name, line_info) in unused {
+         self.warning_at(
+            line_info,
+            name.len(),
+            CompilerWarningType::UnusedSymbol,
+            &format!("'{}' is declared but never used.", name),
+         );
+      }
+   }
+
+   /// Warns about the statements in a body that follow a `return`,
+   /// `break`, or `continue` statement, which can never be executed.
+   ///
+   /// # Parameters
+   /// - `body`: The statements in the body.
+   pub(super) fn warn_unreachable_code(&mut self, body: &[ASTNode]) {
+      for node in body.iter().take(body.len().saturating_sub(1)) {
+         let token = match node {
+            ASTNode::ReturnStmt(x) => &x.token,
+            ASTNode::LoopBranch(x) => &x.token,
+            _ => continue,
+         };
+
+         return self.warning_at_token(
+            token,
+            CompilerWarningType::UnreachableCode,
+            &format!("The code after this '{}' statement is unreachable.", token.lexeme),
+         );
+      }
+   }
+
+   /// Warns about a condition that is a literal value, and so is always truthy or always falsey.
+   ///
+   /// # Parameters
+   /// - `condition`: The condition.
+   /// - `allow_truthy`: Whether truthy literals are allowed (e.g., for `while true` loops).
+   pub(super) fn warn_constant_condition(&mut self, condition: &ASTNode, allow_truthy: bool) {
+      if let ASTNode::Literal(lit) = condition {
+         let is_truthy = !lit.value.is_falsey();
+
+         if !(allow_truthy && is_truthy) {
+            self.warning_at_token(
+               &lit.token,
+               CompilerWarningType::ConstantCondition,
+               &format!(
+                  "This condition is always {}.",
+                  if is_truthy { "truthy" } else { "falsey" }
+               ),
+            );
+         }
+      }
+   }
+
+   /// Warns about a local declaration that shadows a declaration made in an enclosing scope.
+   ///
+   /// # Parameters
+   /// - `token`: The token of the declaration's name.
+   pub(super) fn warn_shadowed_symbol(&mut self, token: &Token) {
+      let name = &token.lexeme;
+      if self.is_global_scope() {
+         return;
+      }
+
+      let depth = self.relative_scope_depth();
+      let shadows_local = self
+         .current_s_table()
+         .symbols
+         .iter()
+         .any(|s| s.depth < depth && &s.name == name);
+      let shadows_global = self.functions.len() == 1 && self.globals.lookup(name, 0).is_some();
+
+      if shadows_local || shadows_global {
+         self.warning_at_token(
+            token,
+            CompilerWarningType::ShadowedSymbol,
+            &format!(
+               "The declaration of '{}' shadows a declaration in an outer scope.",
+               name
+            ),
+         );
+      }
+   }
+
+   /// Warns about a `match` statement without an `else` arm whose arms match the variants of
+   /// an enum, but do not cover every variant. A variant is covered by an arm without a guard
+   /// whose pattern matches any payload of the variant.
+   ///
+   /// # Parameters
+   /// - `stmt`: The `match` statement.
+   pub(super) fn warn_non_exhaustive_match(&mut self, stmt: &MatchStmtNode) {
+      if stmt.default.is_some() {
+         return;
+      }
+
+      let patterns = || {
+         stmt
+            .arms
+            .iter()
+            .flat_map(|arm| arm.patterns.iter().map(move |p| (arm, p)))
+      };
+
+      let enum_obj = match patterns().find_map(|(_, p)| match p {
+         MatchPattern::Variant { enum_name, .. } => self.enums.get(enum_name.lexeme.as_ref()),
+         _ => None,
+      }) {
+         Some(e) => e.clone(),
+         None => return,
+      };
+
+      let mut covered = vec![];
+      for (_, pattern) in patterns().filter(|(arm, _)| arm.guard.is_none()) {
+         match pattern {
+            MatchPattern::Wildcard(_) | MatchPattern::Binding(_) => return,
+            MatchPattern::Variant {
+               enum_name,
+               variant,
+               fields,
+            } if enum_name.lexeme == enum_obj.name => {
+               let is_irrefutable = fields
+                  .iter()
+                  .flat_map(|f| f.iter())
+                  .all(|f| matches!(f, MatchPattern::Wildcard(_) | MatchPattern::Binding(_)));
+
+               if is_irrefutable {
+                  covered.push(variant.lexeme.as_ref());
+               }
+            }
+            _ => {}
+         }
+      }
+
+      let missing: Vec<String> = enum_obj
+         .variants
+         .iter()
+         .filter(|(name, _)| !covered.contains(&name.as_str()))
+         .map(|(name, _)| format!("'{}.{}'", enum_obj.name, name))
+         .collect();
+
+      if !missing.is_empty() {
+         self.warning_at_token(
+            &stmt.token,
+            CompilerWarningType::NonExhaustiveMatch,
+            &format!(
+               "This 'match' statement does not cover the variants {} of the enum '{}'.",
+               missing.join(", "),
+               enum_obj.name
+            ),
+         );
+      }
+   }
+
+   /// Emits a compiler warning from the given token.
+   ///
+   /// # Parameters
+   /// - `token`: The token that caused the warning.
+   /// - `warn_type`: The type of warning to be emitted.
+   /// - `message`: The warning message to display.
+   fn warning_at_token(&mut self, token: &Token, warn_type: CompilerWarningType, message: &str) {
+      self.warning_at(
+         (token.line_num, token.column_start),
+         token.lexeme.len(),
+         warn_type,
+         message,
+      );
+   }
+
+   /// Emits a compiler warning at the given source position. The warning is reported as an
+   /// error when warnings are denied, and is discarded when warnings are silenced.
+   ///
+   /// # Parameters
+   /// - `pos`: The source line and column of the warning.
+   /// - `len`: The number of characters that caused the warning.
+   /// - `warn_type`: The type of warning to be emitted.
+   /// - `message`: The warning message to display.
+   fn warning_at(&mut self, pos: (usize, usize), len: usize, warn_type: CompilerWarningType, message: &str) {
+      let warn_name = match warn_type {
+         CompilerWarningType::ConstantCondition => "ConstantConditionWarning",
+         CompilerWarningType::NonExhaustiveMatch => "NonExhaustiveMatchWarning",
+         CompilerWarningType::ShadowedSymbol => "ShadowedSymbolWarning",
+         CompilerWarningType::UnreachableCode => "UnreachableCodeWarning",
+         CompilerWarningType::UnusedSymbol => "UnusedSymbolWarning",
+      };
+
+      match self.warning_mode {
+         WarningMode::Silence => {}
+         WarningMode::Show => self.warnings.push(WarningReport {
+            line: pos.0,
+            column: pos.1,
+            lexeme_len: len,
+            message: format!(
+               "\x1b[33;1m{}\x1b[0m\x1b[1m at [{}:{}]: {}\x1b[0m",
+               warn_name, pos.0, pos.1, message
+            ),
+         }),
+         WarningMode::Deny => self.errors.push(ErrorReport {
+            line: pos.0,
+            column: pos.1,
+            end_line: pos.0,
+            end_column: pos.1 + len,
+            message: format!(
+               "\x1b[31;1m{}\x1b[0m\x1b[1m at [{}:{}]: {} (warnings are denied)\x1b[0m",
+               warn_name, pos.0, pos.1, message
+            ),
+         }),
+      }
+   }
+}
@@ -2,45 +2,61 @@ use crate::core::tokens::Token;
 use crate::objects::Object;
 
 #[derive(Clone)]
-pub enum ASTNode {
-   Module(ModuleNode),
+pub enum ASTNode<'a> {
+   Module(ModuleNode<'a>),
 
    // Expressions
-   Array(ArrayExprNode),
-   Binary(BinaryExprNode),
-   Dictionary(DictionaryExprNode),
-   FunctionCall(FunctionCallExprNode),
-   Identifier(IdentifierExprNode),
-   Instance(FunctionCallExprNode),
-   Literal(LiteralExprNode),
-   ObjectGetter(ObjectGetExprNode),
-   ObjectSetter(ObjectSetExprNode),
-   SelfExpr(SelfExprNode),
-   Subscript(SubscriptExprNode),
-   SubscriptAssignment(SubscriptAssignExprNode),
-   TernaryConditional(TernaryConditionalNode),
-   Tuple(TupleExprNode),
-   Unary(UnaryExprNode),
-   VarReassignment(VarReassignmentExprNode),
-   Lambda(FunctionDeclNode),
+   Array(ArrayExprNode<'a>),
+   Binary(BinaryExprNode<'a>),
+   ComparisonChain(ComparisonChainNode<'a>),
+   Comprehension(ComprehensionExprNode<'a>),
+   Dictionary(DictionaryExprNode<'a>),
+   FunctionCall(FunctionCallExprNode<'a>),
+   Identifier(IdentifierExprNode<'a>),
+   Instance(FunctionCallExprNode<'a>),
+   Literal(LiteralExprNode<'a>),
+   NonNullAssertion(NonNullAssertionNode<'a>),
+   ObjectGetter(ObjectGetExprNode<'a>),
+   ObjectSetter(ObjectSetExprNode<'a>),
+   OptionalChain(OptionalChainNode<'a>),
+   SelfExpr(SelfExprNode<'a>),
+   Slice(SliceExprNode<'a>),
+   SuperExpr(SuperExprNode<'a>),
+   StringInterpolation(StringInterpolationNode<'a>),
+   Subscript(SubscriptExprNode<'a>),
+   SubscriptAssignment(SubscriptAssignExprNode<'a>),
+   TernaryConditional(TernaryConditionalNode<'a>),
+   Tuple(TupleExprNode<'a>),
+   Unary(UnaryExprNode<'a>),
+   VarReassignment(VarReassignmentExprNode<'a>),
+   Lambda(FunctionDeclNode<'a>),
+   LoopExpr(WhileStmtNode<'a>),
 
    // Declarations
-   ClassDecl(ClassDeclNode),
-   ConstantDecl(ConstantDeclNode),
-   FunctionDecl(FunctionDeclNode),
-   VariableDecl(VariableDeclNode),
+   ClassDecl(ClassDeclNode<'a>),
+   ConstantDecl(ConstantDeclNode<'a>),
+   EnumDecl(EnumDeclNode<'a>),
+   ExportDecl(ExportDeclNode<'a>),
+   FunctionDecl(FunctionDeclNode<'a>),
+   ImportDecl(ImportDeclNode<'a>),
+   InterfaceDecl(InterfaceDeclNode<'a>),
+   VariableDecl(VariableDeclNode<'a>),
 
    // Statements
-   BlockStmt(BlockNode),
-   ExpressionStmt(ExpressionStmtNode),
-   ForStmt(ForStmtNode),
-   IfStmt(IfStmtNode),
-   LoopBranch(LoopBranchStmtNode),
-   ReturnStmt(ReturnStmtNode),
-   WhileStmt(WhileStmtNode),
-}
-
-impl ASTNode {
+   AssertStmt(AssertStmtNode<'a>),
+   BlockStmt(BlockNode<'a>),
+   DeferStmt(DeferStmtNode<'a>),
+   ExpressionStmt(ExpressionStmtNode<'a>),
+   ForStmt(ForStmtNode<'a>),
+   IfStmt(IfStmtNode<'a>),
+   LoopBranch(LoopBranchStmtNode<'a>),
+   MatchStmt(MatchStmtNode<'a>),
+   ReturnStmt(ReturnStmtNode<'a>),
+   TryStmt(TryStmtNode<'a>),
+   WhileStmt(WhileStmtNode<'a>),
+}
+
+impl<'a> ASTNode<'a> {
    pub fn is_truthy_literal(&self) -> bool {
       match self {
          ASTNode::Literal(x) => !x.value.is_falsey(),
@@ -60,38 +76,66 @@ impl ASTNode {
 }
 
 #[derive(Clone)]
-pub struct ModuleNode {
-   pub body: Box<[ASTNode]>,
+pub struct ModuleNode<'a> {
+   pub body: Box<[ASTNode<'a>]>,
 }
 
 #[derive(Clone)]
-pub struct LiteralExprNode {
+pub struct LiteralExprNode<'a> {
    pub value: Object,
-   pub token: Token,
+   pub token: Token<'a>,
+}
+
+#[derive(Clone)]
+pub struct ArrayExprNode<'a> {
+   pub values: Box<[ASTNode<'a>]>,
+   pub token: Token<'a>,
+}
+
+#[derive(Clone)]
+pub struct StringInterpolationNode<'a> {
+   pub parts: Box<[ASTNode<'a>]>,
+   pub token: Token<'a>,
+}
+
+#[derive(Clone)]
+pub struct TupleExprNode<'a> {
+   pub values: Box<[ASTNode<'a>]>,
+   pub token: Token<'a>,
 }
 
+/// An array comprehension (e.g., `[x * x for x in 0..10 if x mod 2 equals 0]`), or a
+/// dictionary comprehension (e.g., `{k: v for k, v in pairs}`).
 #[derive(Clone)]
-pub struct ArrayExprNode {
-   pub values: Box<[ASTNode]>,
-   pub token: Token,
+pub struct ComprehensionExprNode<'a> {
+   pub token: Token<'a>,
+   /// The key of each entry of a dictionary comprehension, or `None` for an array comprehension.
+   pub key: Option<Box<ASTNode<'a>>>,
+   pub value: Box<ASTNode<'a>>,
+   /// The `for` clauses of the comprehension, from the outermost loop to the innermost loop.
+   pub clauses: Box<[ComprehensionClause<'a>]>,
 }
 
+/// A `for x in iter if cond` clause of a comprehension.
 #[derive(Clone)]
-pub struct TupleExprNode {
-   pub values: Box<[ASTNode]>,
-   pub token: Token,
+pub struct ComprehensionClause<'a> {
+   pub token: Token<'a>,
+   pub id: IdentifierExprNode<'a>,
+   pub value_id: Option<IdentifierExprNode<'a>>,
+   pub iterator: Box<ASTNode<'a>>,
+   pub condition: Option<Box<ASTNode<'a>>>,
 }
 
 #[derive(Clone)]
-pub struct DictionaryExprNode {
-   pub keys: Box<[Token]>,
-   pub values: Box<[ASTNode]>,
-   pub token: Token,
+pub struct DictionaryExprNode<'a> {
+   pub keys: Box<[Token<'a>]>,
+   pub values: Box<[ASTNode<'a>]>,
+   pub token: Token<'a>,
 }
 
 #[derive(Clone)]
-pub struct UnaryExprNode {
-   pub operand: Box<ASTNode>,
+pub struct UnaryExprNode<'a> {
+   pub operand: Box<ASTNode<'a>>,
    pub opr_type: UnaryExprType,
    pub pos: (usize, usize),
 }
@@ -101,13 +145,14 @@ pub enum UnaryExprType {
    ArithmeticNeg,
    LogicNeg,
    BitwiseNeg,
+   Await,
 }
 
 #[derive(Clone)]
-pub struct BinaryExprNode {
-   pub left: Box<ASTNode>,
-   pub right: Box<ASTNode>,
-   pub opr_token: Token,
+pub struct BinaryExprNode<'a> {
+   pub left: Box<ASTNode<'a>>,
+   pub right: Box<ASTNode<'a>>,
+   pub opr_token: Token<'a>,
    pub opr_type: BinaryExprType,
 }
 
@@ -121,10 +166,13 @@ pub enum BinaryExprType {
    BitwiseXOR,
    Division,
    Expo,
+   FloorDivision,
    LogicAND,
    LogicEQ,
    LogicGreaterThan,
    LogicGreaterThanEQ,
+   LogicIn,
+   LogicIs,
    LogicLessThan,
    LogicLessThanEQ,
    LogicNotEQ,
@@ -134,185 +182,442 @@ pub enum BinaryExprType {
    Multiplication,
    Nullish,
    Range,
+   RangeInclusive,
 }
 
+/// A chain of ordering comparisons (e.g., `a < b <= c`), which is true if every comparison
+/// between two consecutive operands is true. Each operand is evaluated at most once.
 #[derive(Clone)]
-pub struct TernaryConditionalNode {
-   pub condition: Box<ASTNode>,
-   pub true_branch_token: Token,
-   pub branch_true: Box<ASTNode>,
-   pub false_branch_token: Token,
-   pub branch_false: Box<ASTNode>,
+pub struct ComparisonChainNode<'a> {
+   /// The compared expressions, from left to right.
+   pub operands: Box<[ASTNode<'a>]>,
+   /// The comparison operators between each pair of consecutive operands.
+   pub operators: Box<[(Token<'a>, BinaryExprType)]>,
 }
 
 #[derive(Clone)]
-pub struct IdentifierExprNode {
-   pub token: Token,
+pub struct TernaryConditionalNode<'a> {
+   pub condition: Box<ASTNode<'a>>,
+   pub true_branch_token: Token<'a>,
+   pub branch_true: Box<ASTNode<'a>>,
+   pub false_branch_token: Token<'a>,
+   pub branch_false: Box<ASTNode<'a>>,
+   /// Whether the conditional was written as an `if` expression (e.g., `if x { a } else { b }`)
+   /// instead of with the `?` and `:` operators.
+   pub is_if_expression: bool,
 }
 
 #[derive(Clone)]
-pub struct ExpressionStmtNode {
-   pub child: Box<ASTNode>,
+pub struct IdentifierExprNode<'a> {
+   pub token: Token<'a>,
+}
+
+#[derive(Clone)]
+pub struct ExpressionStmtNode<'a> {
+   pub child: Box<ASTNode<'a>>,
    pub pos: (usize, usize),
 }
 
 #[derive(Clone)]
-pub struct VariableDeclNode {
-   pub identifiers: Box<[Token]>,
-   pub value: Box<ASTNode>,
+pub struct VariableDeclNode<'a> {
+   pub identifiers: Box<[Token<'a>]>,
+   pub annotations: Box<[Option<TypeAnnotation<'a>>]>,
+   pub value: Box<ASTNode<'a>>,
+   /// Whether the value is unpacked into the variables, as in `var (a, b) = tuple;`.
+   pub destructure: bool,
+}
+
+/// A type annotation, like the `Int` in `var x: Int = 5;`.
+#[derive(Clone)]
+pub struct TypeAnnotation<'a> {
+   pub name: Token<'a>,
 }
 
 #[derive(Clone)]
 pub enum ReassignmentType {
-   Plus,   // a += b
-   Minus,  // a -= b
-   Mul,    // a *= b
-   Div,    // a /= b
-   Expo,   // a **= b
-   Mod,    // a %= b
-   ShiftL, // a <<= b
-   ShiftR, // a =>> b
-   BitAnd, // a &= b
-   Xor,    // a ^= b
-   BitOr,  // a |= b
-   Assign, // a = b
-}
-
-#[derive(Clone)]
-pub struct VarReassignmentExprNode {
-   pub target: Token,
-   pub value: Box<ASTNode>,
+   Plus,     // a += b
+   Minus,    // a -= b
+   Mul,      // a *= b
+   Div,      // a /= b
+   FloorDiv, // a ~/= b
+   Expo,     // a **= b
+   Mod,      // a %= b
+   ShiftL,   // a <<= b
+   ShiftR,   // a =>> b
+   BitAnd,   // a &= b
+   Xor,      // a ^= b
+   BitOr,    // a |= b
+   Assign,   // a = b
+}
+
+#[derive(Clone)]
+pub struct VarReassignmentExprNode<'a> {
+   pub target: Token<'a>,
+   pub value: Box<ASTNode<'a>>,
    pub opr_type: ReassignmentType,
    pub pos: (usize, usize),
 }
 
 #[derive(Clone)]
-pub struct SubscriptExprNode {
-   pub target: Box<ASTNode>,
-   pub index: Box<ASTNode>,
+pub struct SubscriptExprNode<'a> {
+   pub target: Box<ASTNode<'a>>,
+   pub index: Box<ASTNode<'a>>,
    pub pos: (usize, usize),
 }
 
+/// A slicing expression (e.g., `arr[1..4]`, `arr[..-1]`, or `arr[0..10..2]`),
+/// where any of the bounds and the step can be omitted.
 #[derive(Clone)]
-pub struct ConstantDeclNode {
-   pub name: Token,
-   pub value: Box<ASTNode>,
+pub struct SliceExprNode<'a> {
+   pub target: Box<ASTNode<'a>>,
+   pub start: Option<Box<ASTNode<'a>>>,
+   pub end: Option<Box<ASTNode<'a>>>,
+   pub step: Option<Box<ASTNode<'a>>>,
+   pub pos: (usize, usize),
 }
 
 #[derive(Clone)]
-pub struct BlockNode {
-   pub body: Box<[ASTNode]>,
-   pub end_of_block: Token,
+pub struct ConstantDeclNode<'a> {
+   pub name: Token<'a>,
+   pub annotation: Option<TypeAnnotation<'a>>,
+   pub value: Box<ASTNode<'a>>,
 }
 
 #[derive(Clone)]
-pub struct IfStmtNode {
-   pub condition: Box<ASTNode>,
-   pub then_token: Token,
-   pub then_branch: Box<ASTNode>,
-   pub else_branch: Box<Option<ASTNode>>,
-   pub else_token: Option<Token>,
+pub struct ImportDeclNode<'a> {
+   pub token: Token<'a>,
+   pub name: Token<'a>,
+   pub path: String,
+   pub path_token: Token<'a>,
 }
 
 #[derive(Clone)]
-pub struct WhileStmtNode {
-   pub token: Token,
-   pub condition: Box<ASTNode>,
-   pub body: Box<ASTNode>,
+pub struct ExportDeclNode<'a> {
+   pub token: Token<'a>,
+   pub decl: Box<ASTNode<'a>>,
 }
 
 #[derive(Clone)]
-pub struct ForStmtNode {
-   pub token: Token,
-   pub id: IdentifierExprNode,
-   pub iterator: Box<ASTNode>,
-   pub body: Box<[ASTNode]>,
+pub struct BlockNode<'a> {
+   pub body: Box<[ASTNode<'a>]>,
+   pub end_of_block: Token<'a>,
+}
+
+#[derive(Clone)]
+pub struct IfStmtNode<'a> {
+   pub condition: Box<ASTNode<'a>>,
+   pub then_token: Token<'a>,
+   pub then_branch: Box<ASTNode<'a>>,
+   pub else_branch: Box<Option<ASTNode<'a>>>,
+   pub else_token: Option<Token<'a>>,
+}
+
+#[derive(Clone)]
+pub struct MatchStmtNode<'a> {
+   pub token: Token<'a>,
+   pub subject: Box<ASTNode<'a>>,
+   pub arms: Box<[MatchArm<'a>]>,
+   pub default: Option<Box<ASTNode<'a>>>,
+}
+
+#[derive(Clone)]
+pub struct MatchArm<'a> {
+   pub patterns: Box<[MatchPattern<'a>]>,
+   /// A condition that must also be true for the arm to be selected (e.g., `n if n > 0 => ...`).
+   pub guard: Option<Box<ASTNode<'a>>>,
+   pub body: Box<ASTNode<'a>>,
+}
+
+#[derive(Clone)]
+pub enum MatchPattern<'a> {
+   Literal(LiteralExprNode<'a>),
+   /// A range pattern, which matches integers from the start (inclusive) to the end (exclusive).
+   Range(LiteralExprNode<'a>, LiteralExprNode<'a>),
+   /// The wildcard pattern (`_`), which matches any value.
+   Wildcard(Token<'a>),
+   /// A binding pattern, which matches any value and assigns it to a variable
+   /// that is visible in the guard and body of the arm.
+   Binding(Token<'a>),
+   /// An array pattern (e.g., `[x, 0]`), which matches the arrays and tuples with as many items
+   /// as the pattern, if each item matches its pattern. The token is the opening bracket.
+   Array(Token<'a>, Box<[MatchPattern<'a>]>),
+   /// A variant pattern (e.g., `Shape.Rect(w, h)`), which matches the values of a variant of an
+   /// enum, if each value in the payload matches its pattern. The patterns of the payload are
+   /// `None` when the pattern of a variant without payload has no parenthesis.
+   Variant {
+      enum_name: Token<'a>,
+      variant: Token<'a>,
+      fields: Option<Box<[MatchPattern<'a>]>>,
+   },
+   /// A dictionary pattern (e.g., `{name, age: 18..65}`), which matches the dictionaries that have
+   /// every key in the pattern, if the value of each key matches its pattern. A key without a
+   /// pattern binds the value to a variable with the same name. The token is the opening curly.
+   Dictionary(Token<'a>, Box<[(Token<'a>, MatchPattern<'a>)]>),
+}
+
+impl<'a> MatchPattern<'a> {
+   /// Gets the first token of the pattern.
+   pub fn token(&self) -> &Token<'a> {
+      match self {
+         MatchPattern::Literal(lit) | MatchPattern::Range(lit, _) => &lit.token,
+         MatchPattern::Wildcard(token)
+         | MatchPattern::Binding(token)
+         | MatchPattern::Array(token, _)
+         | MatchPattern::Dictionary(token, _) => token,
+         MatchPattern::Variant { enum_name, .. } => enum_name,
+      }
+   }
+
+   /// Gets the names bound by the pattern, in the order they appear in the pattern.
+   pub fn bindings(&self) -> Vec<&Token<'a>> {
+      match self {
+         MatchPattern::Binding(name) => vec![name],
+         MatchPattern::Array(_, items) => items.iter().flat_map(|p| p.bindings()).collect(),
+         MatchPattern::Dictionary(_, entries) => entries.iter().flat_map(|(_, p)| p.bindings()).collect(),
+         MatchPattern::Variant {
+            fields: Some(fields), ..
+         } => fields.iter().flat_map(|p| p.bindings()).collect(),
+         _ => vec![],
+      }
+   }
 }
 
 #[derive(Clone)]
-pub struct LoopBranchStmtNode {
-   pub token: Token,
+pub struct WhileStmtNode<'a> {
+   /// The `while`, `do`, or `loop` keyword, which tells the kind of loop apart.
+   pub token: Token<'a>,
+   /// The condition of the loop, or `None` for an infinite `loop`.
+   pub condition: Option<Box<ASTNode<'a>>>,
+   pub body: Box<ASTNode<'a>>,
+   pub label: Option<Token<'a>>,
+}
+
+#[derive(Clone)]
+pub struct ForStmtNode<'a> {
+   pub token: Token<'a>,
+   pub id: IdentifierExprNode<'a>,
+   /// The second loop variable of a `for var key, value in ...` loop.
+   pub value_id: Option<IdentifierExprNode<'a>>,
+   pub iterator: Box<ASTNode<'a>>,
+   pub body: Box<[ASTNode<'a>]>,
+   pub label: Option<Token<'a>>,
+}
+
+#[derive(Clone)]
+pub struct TryStmtNode<'a> {
+   pub token: Token<'a>,
+   pub body: Box<ASTNode<'a>>,
+   pub catch_token: Option<Token<'a>>,
+   pub catch_id: Option<Token<'a>>,
+   pub catch_body: Option<Box<ASTNode<'a>>>,
+   pub finally_token: Option<Token<'a>>,
+   pub finally_body: Option<Box<ASTNode<'a>>>,
+}
+
+#[derive(Clone)]
+pub struct LoopBranchStmtNode<'a> {
+   pub token: Token<'a>,
    pub is_break: bool,
+   /// The label of the loop to break out of or continue (e.g., `break outer;`).
+   pub label: Option<Token<'a>>,
+   /// The value that a `break` statement gives to a `loop` expression (e.g., `break 42;`).
+   pub value: Option<Box<ASTNode<'a>>>,
 }
 
 #[derive(Clone)]
-pub struct FunctionDeclNode {
-   pub name: Token,
-   pub params: Box<[Parameter]>,
+pub struct FunctionDeclNode<'a> {
+   pub name: Token<'a>,
+   pub params: Box<[Parameter<'a>]>,
    pub arity: (u8, u8),
-   pub body: Box<[ASTNode]>,
+   pub body: Box<[ASTNode<'a>]>,
+   pub return_type: Option<TypeAnnotation<'a>>,
+   /// The doc comment written before the declaration of the function, if there is one.
+   pub doc: Option<String>,
+   /// Whether the function was declared with the `async` keyword, in which case calling the
+   /// function runs its body in a new green thread and returns a promise of its result.
+   pub is_async: bool,
+}
+
+impl<'a> FunctionDeclNode<'a> {
+   /// Lists the parameters of the function as they are written in its signature, with the
+   /// values of default parameters elided (e.g., `a: Int, b = ..., ...rest`).
+   pub fn parameter_list(&self) -> String {
+      let params: Vec<String> = self
+         .params
+         .iter()
+         .map(|p| {
+            let mut param = match p.is_variadic {
+               true => format!("...{}", p.name.lexeme),
+               false => p.name.lexeme.to_string(),
+            };
+
+            if p.is_optional && !p.is_variadic && p.default.is_none() {
+               param.push('?');
+            }
+
+            if let Some(annotation) = &p.annotation {
+               param.push_str(&format!(": {}", annotation.name.lexeme));
+            }
+
+            if p.default.is_some() {
+               param.push_str(" = ...");
+            }
+
+            param
+         })
+         .collect();
+
+      params.join(", ")
+   }
 }
 
 #[derive(Clone)]
-pub struct Parameter {
-   pub name: Token,
+pub struct Parameter<'a> {
+   pub name: Token<'a>,
    pub is_optional: bool,
-   pub default: Option<Box<ASTNode>>,
+   pub is_variadic: bool,
+   pub default: Option<Box<ASTNode<'a>>>,
+   pub annotation: Option<TypeAnnotation<'a>>,
 }
 
 #[derive(Clone)]
-pub struct FunctionCallExprNode {
-   pub target: Box<ASTNode>,
-   pub args: Box<[Argument]>,
+pub struct FunctionCallExprNode<'a> {
+   pub target: Box<ASTNode<'a>>,
+   pub args: Box<[Argument<'a>]>,
    pub pos: (usize, usize),
 }
 
 #[derive(Clone)]
-pub struct Argument {
-   pub name: Option<ASTNode>,
+pub struct Argument<'a> {
+   pub name: Option<Token<'a>>,
    pub is_named: bool,
-   pub value: Box<ASTNode>,
+   pub is_spread: bool,
+   pub value: Box<ASTNode<'a>>,
+}
+
+#[derive(Clone)]
+pub struct ReturnStmtNode<'a> {
+   pub token: Token<'a>,
+   pub value: Option<Box<ASTNode<'a>>>,
+}
+
+#[derive(Clone)]
+pub struct AssertStmtNode<'a> {
+   pub token: Token<'a>,
+   pub condition: Box<ASTNode<'a>>,
+   pub message: Option<Box<ASTNode<'a>>>,
+   /// The source text of the condition, which is reported when the assertion fails.
+   pub source: &'a str,
+}
+
+#[derive(Clone)]
+pub struct DeferStmtNode<'a> {
+   pub token: Token<'a>,
+   pub body: FunctionDeclNode<'a>,
 }
 
 #[derive(Clone)]
-pub struct ReturnStmtNode {
-   pub token: Token,
-   pub value: Option<Box<ASTNode>>,
+pub struct ClassDeclNode<'a> {
+   pub name: Token<'a>,
+   pub superclass: Option<IdentifierExprNode<'a>>,
+   pub interfaces: Box<[IdentifierExprNode<'a>]>,
+   pub members: Box<[ClassMemberDeclNode<'a>]>,
+   /// The doc comment written before the declaration of the class, if there is one.
+   pub doc: Option<String>,
 }
 
 #[derive(Clone)]
-pub struct ClassDeclNode {
-   pub name: Token,
-   pub members: Box<[ClassMemberDeclNode]>,
+pub struct InterfaceDeclNode<'a> {
+   pub name: Token<'a>,
+   pub methods: Box<[InterfaceMethodNode<'a>]>,
 }
 
 #[derive(Clone)]
-pub enum ClassMemberDecl {
-   Var(VariableDeclNode),
-   Const(ConstantDeclNode),
-   Method(FunctionDeclNode),
+pub struct EnumDeclNode<'a> {
+   pub name: Token<'a>,
+   pub variants: Box<[EnumVariantNode<'a>]>,
 }
 
+/// A variant of an enum, along with the names of the fields of its payload (if it has one).
 #[derive(Clone)]
-pub struct ClassMemberDeclNode {
-   pub member_type: ClassMemberDecl,
+pub struct EnumVariantNode<'a> {
+   pub name: Token<'a>,
+   pub fields: Box<[Token<'a>]>,
+}
+
+/// The signature of a method that the classes implementing an interface must define.
+#[derive(Clone)]
+pub struct InterfaceMethodNode<'a> {
+   pub name: Token<'a>,
+   pub params: Box<[Parameter<'a>]>,
+   pub return_type: Option<TypeAnnotation<'a>>,
+}
+
+#[derive(Clone)]
+pub enum ClassMemberDecl<'a> {
+   Var(VariableDeclNode<'a>),
+   Const(ConstantDeclNode<'a>),
+   Method(FunctionDeclNode<'a>),
+}
+
+#[derive(Clone)]
+pub struct ClassMemberDeclNode<'a> {
+   pub member_type: ClassMemberDecl<'a>,
    pub mode: u8,
 }
 
 #[derive(Clone)]
-pub struct SelfExprNode {
-   pub token: Token,
+pub struct SelfExprNode<'a> {
+   pub token: Token<'a>,
+}
+
+#[derive(Clone)]
+pub struct SuperExprNode<'a> {
+   pub token: Token<'a>,
+   pub method: Token<'a>,
+}
+
+#[derive(Clone)]
+pub struct ObjectGetExprNode<'a> {
+   pub target: Box<ASTNode<'a>>,
+   pub getter: Token<'a>,
+   /// Whether the property is accessed with the optional chaining operator (`?.`), which
+   /// short-circuits the rest of the chain to `null` when the target is `null`.
+   pub is_optional: bool,
+}
+
+/// A chain of property accesses, calls, and subscripts with at least one optional property
+/// access (e.g., `user?.address.city`). Optional accesses that find `null` jump to the end of
+/// the chain, which evaluates to `null`.
+#[derive(Clone)]
+pub struct OptionalChainNode<'a> {
+   pub chain: Box<ASTNode<'a>>,
+   /// The first optional chaining operator (`?.`) in the chain.
+   pub token: Token<'a>,
 }
 
+/// A non-null assertion (e.g., `user!!`), which throws a `NullError` if its operand is `null`.
 #[derive(Clone)]
-pub struct ObjectGetExprNode {
-   pub target: Box<ASTNode>,
-   pub getter: Token,
+pub struct NonNullAssertionNode<'a> {
+   pub operand: Box<ASTNode<'a>>,
+   pub token: Token<'a>,
+   /// The source text of the operand, which is reported when the operand is `null`.
+   pub source: &'a str,
 }
 
 #[derive(Clone)]
-pub struct ObjectSetExprNode {
-   pub target: Box<ASTNode>,
-   pub setter: Token,
-   pub value: Box<ASTNode>,
+pub struct ObjectSetExprNode<'a> {
+   pub target: Box<ASTNode<'a>>,
+   pub setter: Token<'a>,
+   pub value: Box<ASTNode<'a>>,
    pub opr_type: ReassignmentType,
 }
 
 #[derive(Clone)]
-pub struct SubscriptAssignExprNode {
-   pub target: Box<ASTNode>,
-   pub index: Box<ASTNode>,
-   pub value: Box<ASTNode>,
+pub struct SubscriptAssignExprNode<'a> {
+   pub target: Box<ASTNode<'a>>,
+   pub index: Box<ASTNode<'a>>,
+   pub value: Box<ASTNode<'a>>,
    pub pos: (usize, usize),
    pub opr_type: ReassignmentType,
 }
@@ -1,26 +1,84 @@
 use crate::core::bytecode::OpCode;
+use crate::objects::class_obj::InstanceLayout;
 use crate::objects::Object;
 use num_traits::FromPrimitive;
+use std::rc::Rc;
+
+/// Represents an entry in a chunk's exception handler table. When a runtime error
+/// occurs while executing an instruction within the handler's range, the VM unwinds
+/// the stack and jumps to the handler's target instruction.
+#[derive(Clone)]
+pub struct ExceptionHandler {
+   /// The position of the first instruction covered by this handler.
+   pub start: usize,
+   /// The position after the last instruction covered by this handler.
+   pub end: usize,
+   /// The position of the instruction to jump to when an error is caught.
+   pub target: usize,
+   /// The number of stack slots, relative to the frame's base, that remain
+   /// on the stack when the handler is entered.
+   pub stack_depth: usize,
+}
+
+/// The inline cache of a property access instruction. It remembers the layout of the last
+/// instance whose property was accessed by the instruction, and the slot of the property in that
+/// layout, so that accessing the property of an instance with the same layout skips the lookup
+/// of the property by name.
+#[derive(Clone, Default)]
+pub struct InlineCache {
+   /// The layout of the last instance whose property was accessed.
+   pub layout: Option<Rc<InstanceLayout>>,
+   /// The slot of the property in the layout.
+   pub slot: usize,
+}
 
 /// Contains all the necessary information about
 /// the instructions to be executed.
 #[derive(Clone)]
 pub struct Chunk {
-   instructions: Vec<u8>,
-   locations: Vec<(usize, usize)>,
-   constants: Vec<Object>,
+   pub(super) instructions: Vec<u8>,
+   pub(super) locations: Vec<(usize, usize)>,
+   pub(super) constants: Vec<Object>,
+   pub(super) handlers: Vec<ExceptionHandler>,
+   pub(super) caches: Vec<InlineCache>,
 }
 
 impl Chunk {
    /// Creates a new chunk.
-   pub fn new() -> Self {
+   pub(crate) fn new() -> Self {
       Self {
          instructions: vec![],
          locations: vec![],
          constants: vec![],
+         handlers: vec![],
+         caches: vec![],
       }
    }
 
+   /// Adds an empty inline cache to this chunk.
+   ///
+   /// # Returns
+   /// - `Result<u16, ()>`: If the cache was added, returns the position of the cache. If the
+   ///   cache could not be added because the chunk has too many caches, returns error.
+   pub(crate) fn add_cache(&mut self) -> Result<u16, ()> {
+      if self.caches.len() < (u16::MAX as usize) {
+         self.caches.push(InlineCache::default());
+         Ok((self.caches.len() - 1) as u16)
+      } else {
+         Err(())
+      }
+   }
+
+   /// Gets the inline cache at the given position.
+   pub fn get_cache(&self, idx: usize) -> &InlineCache {
+      &self.caches[idx]
+   }
+
+   /// Replaces the inline cache at the given position.
+   pub fn set_cache(&mut self, idx: usize, cache: InlineCache) {
+      self.caches[idx] = cache;
+   }
+
    /// Adds a constant to this chunk's constants pool
    ///
    /// # Parameters
@@ -30,13 +88,21 @@ impl Chunk {
    /// - `Result<u16, ()>`: If the object was successfully added to the pool,
    /// returns the position of the object in the pool. If the item could not be
    /// added because the pool is full, returns error.
-   pub fn add_constant(&mut self, obj: Object) -> Result<u16, ()> {
+   pub(crate) fn add_constant(&mut self, obj: Object) -> Result<u16, ()> {
       return if self.constants.len() < (u16::MAX as usize) {
          // Having to create an iterator, then enumerating that iterator, to finally
          // look for the object seems very expensive. Can we do better? Do the benefits
          // of storing a single object in the heap outweigh the cost of these operations?
          // TODO: Find a better way of doing this, or do research to see if the benefits outweigh the cost.
-         match self.constants.iter().enumerate().find(|x| x.1 == &obj) {
+         // Numbers of different types can be equal (e.g., `2 == 2.0`), so the types of
+         // the constants are also compared to keep Ints and Floats apart.
+         let same_type = |x: &Object| std::mem::discriminant(x) == std::mem::discriminant(&obj);
+         match self
+            .constants
+            .iter()
+            .enumerate()
+            .find(|x| x.1 == &obj && same_type(x.1))
+         {
             Some(x) => Ok(x.0 as u16),
             None => {
                self.constants.push(obj);
@@ -59,11 +125,54 @@ impl Chunk {
       &self.constants[idx]
    }
 
+   /// Gets the list of objects in this chunk's constants pool.
+   pub fn get_constants(&self) -> &[Object] {
+      &self.constants
+   }
+
+   /// Gets a mutable reference to the objects in this chunk's constant pool.
+   pub fn get_constants_mut(&mut self) -> &mut [Object] {
+      &mut self.constants
+   }
+
    /// Gets the OpCode associated with a byte instruction in the instructions list.
    pub fn get_op_code(&self, idx: usize) -> OpCode {
       FromPrimitive::from_u8(self.instructions[idx]).unwrap()
    }
 
+   /// Gets the number of bytes taken by the instruction at the given index, including its operands.
+   pub fn instruction_len(&self, idx: usize) -> usize {
+      let code = self.get_byte(idx);
+
+      // Instructions with zero, one, and two operands are declared in that order.
+      if code <= OpCode::Subtract as u8 {
+         return 1;
+      } else if code <= OpCode::Unpack as u8 {
+         return 2;
+      } else if code <= OpCode::SetUpValLong as u8 {
+         return 3;
+      }
+
+      let up_value_count = |pos: usize| match &self.constants[pos] {
+         Object::Function(f) => f.borrow().up_val_names.len(),
+         _ => unreachable!("Expected a function object for the closure."),
+      };
+
+      match self.get_op_code(idx) {
+         OpCode::FuncCallSpread | OpCode::MakeInstanceSpread => 2 + self.get_byte(idx + 1) as usize,
+         OpCode::GetProp | OpCode::SetProp => 4,
+         OpCode::GetPropLong | OpCode::SetPropLong => 5,
+         OpCode::JumpTable => 7 + self.get_short(idx + 3) as usize * 2,
+         OpCode::MakeClosure => 2 + up_value_count(self.get_byte(idx + 1) as usize) * 2,
+         OpCode::MakeClosureLong => 3 + up_value_count(self.get_short(idx + 1) as usize) * 2,
+         OpCode::MakeClosureLarge => 2 + up_value_count(self.get_byte(idx + 1) as usize) * 3,
+         OpCode::MakeClosureLongLarge => 3 + up_value_count(self.get_short(idx + 1) as usize) * 3,
+         OpCode::GetLocalsCompareJump | OpCode::LoadConstantAdd | OpCode::LoadImmNAdd => 2,
+         OpCode::LoadImm1IAdd => 1,
+         op => unreachable!("The length of '{:?}' should be known by its operands.", op),
+      }
+   }
+
    /// Adds a raw byte instruction from a given OpCode into the instructions list.
    pub fn push_op_code(&mut self, val: OpCode) {
       self.instructions.push(val as u8);
@@ -102,9 +211,13 @@ impl Chunk {
       self.instructions[idx] = new_byte;
    }
 
-   /// Gets the line info associated with the byte at the given instruction index.
-   pub fn get_line_info(&self, idx: usize) -> &(usize, usize) {
-      &self.locations[idx]
+   /// Gets the line info associated with the byte at the given instruction index. Indices past
+   /// the end of the chunk get the line info of the last byte, or `(0, 0)` if the chunk is empty.
+   pub fn get_line_info(&self, idx: usize) -> (usize, usize) {
+      match self.locations.get(idx).or_else(|| self.locations.last()) {
+         Some(location) => *location,
+         None => (0, 0),
+      }
    }
 
    /// Pushes the line info associated with the last byte in the instruction list.
@@ -117,6 +230,27 @@ impl Chunk {
       self.instructions.len()
    }
 
+   /// Checks if the instructions list is empty.
+   pub fn is_empty(&self) -> bool {
+      self.instructions.is_empty()
+   }
+
+   /// Adds an entry to this chunk's exception handler table. Inner handlers must be added
+   /// before the handlers that enclose them.
+   pub fn add_handler(&mut self, handler: ExceptionHandler) {
+      self.handlers.push(handler);
+   }
+
+   /// Finds the inner-most exception handler that covers the instruction at the given index.
+   pub fn find_handler(&self, idx: usize) -> Option<&ExceptionHandler> {
+      self.handlers.iter().find(|h| idx >= h.start && idx < h.end)
+   }
+
+   /// Gets the exception handler table for this chunk.
+   pub fn get_handlers(&self) -> &[ExceptionHandler] {
+      &self.handlers
+   }
+
    /// Gets the size of the constants pool list.
    #[cfg(test)]
    pub fn get_pool_size(&self) -> usize {
@@ -0,0 +1,596 @@
+use crate::core::bytecode::OpCode;
+use crate::core::chunk::Chunk;
+use crate::errors::paint;
+use crate::objects::{FuncObject, Object};
+use hashbrown::HashSet;
+use num_traits::FromPrimitive;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Disassembles a function and every function nested inside of it (which are stored in the
+/// constant pool of their parent function), printing the bytecode of each of their chunks.
+///
+/// # Parameters
+/// - `func`: The function to disassemble.
+/// - `natives`: The names of the native functions, in the order they are indexed.
+/// - `primitives`: The names of the primitive classes, in the order they are indexed.
+pub fn disassemble_function(func: &FuncObject, natives: &[String], primitives: &[String]) {
+   disassemble_chunk(&func.chunk, natives, primitives, &func.name);
+
+   // Functions can be shared between constant pools (e.g., a module imported by two other
+   // modules), so each nested function is only printed once.
+   let mut printed: HashSet<*const RefCell<FuncObject>> = HashSet::new();
+   let mut pending: Vec<Rc<RefCell<FuncObject>>> = nested_functions(func);
+   pending.reverse();
+
+   while let Some(f) = pending.pop() {
+      if !printed.insert(Rc::as_ptr(&f)) {
+         continue;
+      }
+
+      let f = f.borrow();
+      disassemble_chunk(&f.chunk, natives, primitives, &f.name);
+      pending.extend(nested_functions(&f).into_iter().rev());
+   }
+}
+
+/// Gets the functions stored in the constant pool of a function.
+fn nested_functions(func: &FuncObject) -> Vec<Rc<RefCell<FuncObject>>> {
+   func
+      .chunk
+      .get_constants()
+      .iter()
+      .filter_map(|obj| obj.as_function().cloned())
+      .collect()
+}
+
+/// Disassembles a chunk, printing each instruction with its offset, operands, constant values,
+/// and source line, followed by the chunk's exception handlers.
+///
+/// # Parameters
+/// - `chunk`: The chunk to disassemble.
+/// - `natives`: The names of the native functions, in the order they are indexed.
+/// - `primitives`: The names of the primitive classes, in the order they are indexed.
+/// - `name`: The name of the function that owns the chunk.
+pub fn disassemble_chunk(chunk: &Chunk, natives: &[String], primitives: &[String], name: &str) {
+   // prints this chunk's name
+   println!("==== {} ====", name);
+
+   let mut current_line = 0;
+
+   let mut idx = 0;
+   while idx < chunk.len() {
+      let line_info = chunk.get_line_info(idx);
+
+      // Prints a line number or a vertical bar indicating that the
+      // current instruction is in the same line as the previous one.
+      if line_info.0 != current_line {
+         print!("{:>05}\t", line_info.0);
+         current_line = line_info.0;
+      } else {
+         print!("  |\t")
+      }
+
+      // Prints the index of the current instruction
+      print!("{:>05} ", idx);
+
+      // Prints the instruction code, name, and operands
+      let (text, next_idx) = disassemble_instruction(chunk, idx, natives, primitives);
+      println!("{}", text);
+
+      idx = next_idx;
+   }
+
+   // Prints the exception handler table
+   for h in chunk.get_handlers() {
+      println!(
+         "{}",
+         paint(&format!(
+            "  \x1b[33mhandler\x1b[0m [{:>05}, {:>05}) -> {:>05} (stack depth {})",
+            h.start, h.end, h.target, h.stack_depth
+         ))
+      );
+   }
+
+   println!();
+}
+
+/// Disassembles a single instruction of a chunk, with its operands and constant values.
+///
+/// # Parameters
+/// - `chunk`: The chunk that contains the instruction.
+/// - `offset`: The offset of the instruction in the chunk.
+/// - `natives`: The names of the native functions, in the order they are indexed.
+/// - `primitives`: The names of the primitive classes, in the order they are indexed.
+///
+/// # Returns
+/// - `(String, usize)`: The disassembled instruction, and the offset of the next instruction.
+pub fn disassemble_instruction(
+   chunk: &Chunk,
+   offset: usize,
+   natives: &[String],
+   primitives: &[String],
+) -> (String, usize) {
+   let mut idx = offset;
+   let code = chunk.get_byte(idx);
+   let mut operand_val = String::from("");
+
+   // Reads two bytes as the index of a constant
+   let const_val = |idx: usize, is_long: bool| -> &Object {
+      let pos = if is_long {
+         chunk.get_short(idx) as usize
+      } else {
+         chunk.get_byte(idx) as usize
+      };
+
+      chunk.get_constant(pos)
+   };
+
+   // Gets the operand value
+   let mut get_operand = |operand_count: usize| {
+      idx += operand_count;
+
+      operand_val = if operand_count == 1 {
+         format!("{}", chunk.get_byte(idx))
+      } else {
+         format!("{}", chunk.get_short(idx - 1))
+      }
+   };
+
+   let op_code_name = match FromPrimitive::from_u8(code).unwrap() {
+      OpCode::Add => "ADD",
+      OpCode::ArrayPush => "ARRAY_PUSH",
+      OpCode::Async => "ASYNC",
+      OpCode::Await => "AWAIT",
+      OpCode::BitwiseAnd => "BIT_AND",
+      OpCode::BitwiseNot => "BIT_NOT",
+      OpCode::BitwiseOr => "BIT_OR",
+      OpCode::BitwiseShiftLeft => "BIT_SHIFT_L",
+      OpCode::BitwiseShiftRight => "BIT_SHIFT_R",
+      OpCode::BitwiseXor => "BIT_XOR",
+      OpCode::Contains => "CONTAINS",
+      OpCode::Defer => "DEFER",
+      OpCode::Divide => "DIVIDE",
+      OpCode::DupTop => "DUP_TOP",
+      OpCode::DupTopTwo => "DUP_TOP_TWO",
+      OpCode::EndVirtualMachine => "END_VIRTUAL_MACHINE",
+      OpCode::Equals => "EQUALS",
+      OpCode::Expo => "EXPO",
+      OpCode::ExportModule => "EXPORT_MODULE",
+      OpCode::FailAssertion => "FAIL_ASSERTION",
+      OpCode::FloorDivide => "FLOOR_DIVIDE",
+      OpCode::GreaterThan => "GREATER_THAN",
+      OpCode::GreaterThanEq => "GREATER_THAN_EQ",
+      OpCode::Implement => "IMPLEMENT",
+      OpCode::Inherit => "INHERIT",
+      OpCode::InstanceOf => "INSTANCE_OF",
+      OpCode::LessThan => "LESS_THAN",
+      OpCode::LessThanEq => "LESS_THAN_EQ",
+      OpCode::LoadImm0F => "LOAD_IMM_0F",
+      OpCode::LoadImm0I => "LOAD_IMM_0I",
+      OpCode::LoadImm1F => "LOAD_IMM_1F",
+      OpCode::LoadImm1I => "LOAD_IMM_1I",
+      OpCode::LoadImmFalse => "LOAD_IMM_FALSE",
+      OpCode::LoadImmNull => "LOAD_IMM_NULL",
+      OpCode::LoadImmTrue => "LOAD_IMM_TRUE",
+      OpCode::LogicNot => "LOGIC_NOT",
+      OpCode::MakeIter => "MAKE_ITER",
+      OpCode::MakeInclusiveRange => "MAKE_INCLUSIVE_RANGE",
+      OpCode::MakeRange => "MAKE_RANGE",
+      OpCode::MatchSequence => "MATCH_SEQUENCE",
+      OpCode::MatchVariant => "MATCH_VARIANT",
+      OpCode::Modulus => "MODULUS",
+      OpCode::Multiply => "MULTIPLY",
+      OpCode::Negate => "NEGATE",
+      OpCode::NotEq => "NOT_EQ",
+      OpCode::NullishCoalescing => "NULLISH",
+      OpCode::PopCloseUpVal => "POP_CLOSE_UP_VAL",
+      OpCode::PopStackTop => "POP_STACK_TOP",
+      OpCode::Rethrow => "RETHROW",
+      OpCode::Return => "RETURN",
+      OpCode::RotThree => "ROT_THREE",
+      OpCode::RotTwo => "ROT_TWO",
+      OpCode::Slice => "SLICE",
+      OpCode::Subscript => "SUBSCRIPT",
+      OpCode::SubscriptAssign => "SUBSCRIPT_ASSIGN",
+      OpCode::Subtract => "SUBTRACT",
+
+      // OpCodes with 1 operand
+      OpCode::AppendClassField => {
+         get_operand(1);
+         "APPEND_CLASS_FIELD"
+      }
+      OpCode::BindDefaults => {
+         get_operand(1);
+         "BIND_DEFAULTS"
+      }
+      OpCode::BuildString => {
+         get_operand(1);
+         "BUILD_STRING"
+      }
+      OpCode::FuncCall => {
+         get_operand(1);
+         "FUNC_CALL"
+      }
+      OpCode::TailCall => {
+         get_operand(1);
+         "TAIL_CALL"
+      }
+      OpCode::Unpack => {
+         get_operand(1);
+         "UNPACK"
+      }
+      OpCode::GetLocal => {
+         get_operand(1);
+         "GET_LOCAL"
+      }
+      OpCode::LoadConstant => {
+         get_operand(1);
+         operand_val += &format!(" -> ({})", const_val(idx, false));
+         "LOAD_CONSTANT"
+      }
+      OpCode::DefineGlobal => {
+         get_operand(1);
+         "DEFINE_GLOBAL"
+      }
+      OpCode::GetGlobal => {
+         get_operand(1);
+         "GET_GLOBAL"
+      }
+      OpCode::MakeClass => {
+         get_operand(1);
+         operand_val += &format!(" -> '{}'", const_val(idx, false));
+         "MAKE_CLASS"
+      }
+      OpCode::MakeInstance => {
+         get_operand(1);
+         "MAKE_INSTANCE"
+      }
+      OpCode::GetSuper => {
+         get_operand(1);
+         operand_val += &format!(" -> '{}'", const_val(idx, false));
+         "GET_SUPER"
+      }
+      OpCode::SetGlobal => {
+         get_operand(1);
+         "SET_GLOBAL"
+      }
+      OpCode::LoadImmN => {
+         get_operand(1);
+         "LOAD_IMM_N"
+      }
+      OpCode::LoopJump => {
+         idx += 1;
+         // `idx + 1` because at runtime, the IP points to the next instruction
+         operand_val = format!("{}", (idx + 1) - chunk.get_byte(idx) as usize);
+         operand_val += &format!(" (sub {} from IP)", chunk.get_byte(idx));
+         "LOOP_JUMP"
+      }
+      OpCode::MakeArray => {
+         get_operand(1);
+         "MAKE_ARRAY"
+      }
+      OpCode::MakeTuple => {
+         get_operand(1);
+         "MAKE_TUPLE"
+      }
+      OpCode::MakeDict => {
+         get_operand(1);
+         "MAKE_DICT"
+      }
+      OpCode::SetLocal => {
+         get_operand(1);
+         "SET_LOCAL"
+      }
+      OpCode::GetUpVal => {
+         get_operand(1);
+         "GET_UP_VAL"
+      }
+      OpCode::SetUpVal => {
+         get_operand(1);
+         "SET_UP_VAL"
+      }
+      OpCode::CloseUpVal => {
+         get_operand(1);
+         "CLOSE_UP_VAL"
+      }
+      OpCode::ImportModule => {
+         get_operand(1);
+         operand_val += &format!(" -> ({})", const_val(idx, false));
+         "IMPORT_MODULE"
+      }
+      OpCode::LoadNative => {
+         get_operand(1);
+         operand_val += &format!(" -> '{}'", natives[chunk.get_byte(idx) as usize]);
+         "LOAD_NATIVE"
+      }
+      OpCode::LoadPrimitive => {
+         get_operand(1);
+         operand_val += &format!(" -> '{}'", primitives[chunk.get_byte(idx) as usize]);
+         "LOAD_PRIMITIVE"
+      }
+
+      // OpCode with 2 operands
+      OpCode::GetLocalLong => {
+         get_operand(2);
+         "GET_LOCAL_LONG"
+      }
+      OpCode::JumpForward => {
+         idx += 2;
+         let offset = chunk.get_short(idx - 1) as usize;
+         // `idx + 1` because at runtime, the IP points to the next instruction
+         operand_val = format!("{} (add {} to IP)", (idx + 1) + offset, offset);
+         "JUMP_FORWARD"
+      }
+      OpCode::ForIterNextOrJump => {
+         idx += 2;
+         let offset = chunk.get_short(idx - 1) as usize;
+         // `idx + 1` because at runtime, the IP points to the next instruction
+         operand_val = format!("{} (add {} to IP)", (idx + 1) + offset, offset);
+         "FOR_ITER_NEXT_OR_JUMP"
+      }
+      OpCode::JumpIfFalseOrPop => {
+         get_operand(2);
+         "JUMP_IF_FALSE_OR_POP"
+      }
+      OpCode::JumpIfNull => {
+         get_operand(2);
+         "JUMP_IF_NULL"
+      }
+      OpCode::JumpIfTrueOrPop => {
+         get_operand(2);
+         "JUMP_IF_TRUE_OR_POP"
+      }
+      OpCode::LoadConstantLong => {
+         get_operand(2);
+         operand_val += &format!(" -> ({})", const_val(idx - 1, true));
+         "LOAD_CONSTANT_LONG"
+      }
+      OpCode::AssertNonNull => {
+         get_operand(2);
+         operand_val += &format!(" -> ({})", const_val(idx - 1, true));
+         "ASSERT_NON_NULL"
+      }
+      OpCode::DefineGlobalLong => {
+         get_operand(2);
+         "DEFINE_GLOBAL_LONG"
+      }
+      OpCode::GetGlobalLong => {
+         get_operand(2);
+         "GET_GLOBAL_LONG"
+      }
+      OpCode::SetGlobalLong => {
+         get_operand(2);
+         "SET_GLOBAL_LONG"
+      }
+      OpCode::ImportModuleLong => {
+         get_operand(2);
+         operand_val += &format!(" -> ({})", const_val(idx - 1, true));
+         "IMPORT_MODULE_LONG"
+      }
+      OpCode::LoadImmNLong => {
+         get_operand(2);
+         "LOAD_IMM_N_LONG"
+      }
+      OpCode::LoopJumpLong => {
+         idx += 2;
+         let offset = chunk.get_short(idx - 1) as usize;
+         // `idx + 1` because at runtime, the IP points to the next instruction
+         operand_val = format!("{} (sub {} from IP)", (idx + 1) - offset, offset);
+         "LOOP_JUMP_LONG"
+      }
+      OpCode::MakeArrayLong => {
+         get_operand(2);
+         "MAKE_ARRAY_LONG"
+      }
+      OpCode::FuncCallNamed => {
+         idx += 2;
+         operand_val = format!("{} (with {} named)", chunk.get_byte(idx - 1), chunk.get_byte(idx));
+         "FUNC_CALL_NAMED"
+      }
+      OpCode::MakeInstanceNamed => {
+         idx += 2;
+         operand_val = format!("{} (with {} named)", chunk.get_byte(idx - 1), chunk.get_byte(idx));
+         "MAKE_INSTANCE_NAMED"
+      }
+      OpCode::MakeTupleLong => {
+         get_operand(2);
+         "MAKE_TUPLE_LONG"
+      }
+      OpCode::MakeDictLong => {
+         get_operand(2);
+         "MAKE_DICT_LONG"
+      }
+      OpCode::PopJumpIfFalse => {
+         idx += 2;
+         let offset = chunk.get_short(idx - 1) as usize;
+         // `idx + 1` because at runtime, the IP points to the next instruction
+         operand_val = format!("{} (add {} to IP)", (idx + 1) + offset, offset);
+         "POP_JUMP_IF_FALSE"
+      }
+      OpCode::SetLocalLong => {
+         get_operand(2);
+         "SET_LOCAL_LONG"
+      }
+      OpCode::GetUpValLong => {
+         get_operand(2);
+         "GET_UP_VAL_LONG"
+      }
+      OpCode::SetUpValLong => {
+         get_operand(2);
+         "SET_UP_VAL_LONG"
+      }
+      OpCode::CloseUpValLong => {
+         get_operand(2);
+         "CLOSE_UP_VAL_LONG"
+      }
+      OpCode::MakeClassLong => {
+         get_operand(2);
+         operand_val += &format!(" -> ({})", const_val(idx - 1, true));
+         "MAKE_CLASS_LONG"
+      }
+      OpCode::GetSuperLong => {
+         get_operand(2);
+         operand_val += &format!(" -> '{}'", const_val(idx, true));
+         "GET_SUPER_LONG"
+      }
+
+      OpCode::FuncCallSpread | OpCode::MakeInstanceSpread => {
+         let op_name = if let OpCode::FuncCallSpread = FromPrimitive::from_u8(code).unwrap() {
+            "FUNC_CALL_SPREAD"
+         } else {
+            "MAKE_INSTANCE_SPREAD"
+         };
+
+         get_operand(1);
+         let arg_count = chunk.get_byte(idx) as usize;
+         let spread: Vec<String> = (0..arg_count)
+            .filter(|i| chunk.get_byte(idx + 1 + i) == 1)
+            .map(|i| i.to_string())
+            .collect();
+
+         operand_val += &format!(" (spread: {})", spread.join(", "));
+         idx += arg_count;
+         op_name
+      }
+      OpCode::GetProp | OpCode::GetPropLong | OpCode::SetProp | OpCode::SetPropLong => {
+         let op_code = FromPrimitive::from_u8(code).unwrap();
+         let is_long = matches!(op_code, OpCode::GetPropLong | OpCode::SetPropLong);
+
+         get_operand(if is_long { 2 } else { 1 });
+         let name = const_val(if is_long { idx - 1 } else { idx }, is_long);
+         operand_val += &format!(" -> '{}' (cache {})", name, chunk.get_short(idx + 1));
+         idx += 2;
+
+         match op_code {
+            OpCode::GetProp => "GET_PROPERTY",
+            OpCode::GetPropLong => "GET_PROPERTY_LONG",
+            OpCode::SetProp => "SET_PROPERTY",
+            _ => "SET_PROPERTY_LONG",
+         }
+      }
+      OpCode::GetLocalsCompareJump => {
+         get_operand(1);
+         "GET_LOCALS_COMPARE_JUMP"
+      }
+      OpCode::LoadConstantAdd => {
+         get_operand(1);
+         operand_val += &format!(" -> ({})", const_val(idx, false));
+         "LOAD_CONSTANT_ADD"
+      }
+      OpCode::LoadImm1IAdd => "LOAD_IMM_1I_ADD",
+      OpCode::LoadImmNAdd => {
+         get_operand(1);
+         "LOAD_IMM_N_ADD"
+      }
+      OpCode::JumpTable => {
+         let min = const_val(idx + 1, true).as_int().unwrap();
+         let size = chunk.get_short(idx + 3) as usize;
+         // The jump offsets are relative to the end of the table.
+         let table_end = idx + 7 + size * 2;
+
+         operand_val = format!("{}", size);
+         if size > 0 {
+            operand_val += &format!(" (keys {} to {})", min, min + size as i64 - 1);
+         }
+
+         operand_val += &format!(
+            "\n  |\t{:>05}      | non-numbers -> {}",
+            idx + 5,
+            table_end + chunk.get_short(idx + 5) as usize
+         );
+
+         for i in 0..size {
+            let entry = idx + 7 + i * 2;
+            operand_val += &format!(
+               "\n  |\t{:>05}      | {} -> {}",
+               entry,
+               min + i as i64,
+               table_end + chunk.get_short(entry) as usize
+            );
+         }
+
+         idx = table_end - 1;
+         "JUMP_TABLE"
+      }
+
+      OpCode::MakeClosure | OpCode::MakeClosureLong => {
+         let up_value_count;
+         let op_name = if let OpCode::MakeClosure = FromPrimitive::from_u8(code).unwrap() {
+            get_operand(1);
+
+            let obj = const_val(idx, false);
+            up_value_count = obj.as_function().unwrap().borrow().up_val_names.len();
+            operand_val += &format!(" -> '{}'", obj);
+            "MAKE_CLOSURE"
+         } else {
+            get_operand(2);
+
+            let obj = const_val(idx, true);
+            up_value_count = obj.as_function().unwrap().borrow().up_val_names.len();
+            operand_val += &format!(" -> '{}'", obj);
+            "MAKE_CLOSURE_LONG"
+         };
+
+         for i in 0..up_value_count {
+            if i <= (up_value_count - 1) {
+               operand_val += "\n";
+            }
+
+            operand_val += &format!("  |\t{:>05}      | {}", idx + 1, i);
+
+            let is_local = chunk.get_byte(idx + 1) == 1u8;
+            let index = chunk.get_byte(idx + 2);
+            let up_val_type = if is_local { "Local" } else { "UpVal" };
+            operand_val += &format!(" {} idx={}", up_val_type, index);
+
+            idx += 2;
+         }
+
+         op_name
+      }
+
+      OpCode::MakeClosureLarge | OpCode::MakeClosureLongLarge => {
+         let up_value_count;
+         let op_name = if let OpCode::MakeClosureLarge = FromPrimitive::from_u8(code).unwrap() {
+            get_operand(1);
+
+            let obj = const_val(idx, false);
+            up_value_count = obj.as_function().unwrap().borrow().up_val_names.len();
+            operand_val += &format!(" -> '{}'", obj);
+            "MAKE_CLOSURE_LARGE"
+         } else {
+            get_operand(2);
+
+            let obj = const_val(idx, true);
+            up_value_count = obj.as_function().unwrap().borrow().up_val_names.len();
+            operand_val += &format!(" -> '{}'", obj);
+            "MAKE_CLOSURE_LONG_LARGE"
+         };
+
+         for i in 0..up_value_count {
+            if i <= (up_value_count - 1) {
+               operand_val += "\n";
+            }
+
+            operand_val += &format!("  |\t{:>05}      | {}", idx + 1, i);
+
+            let is_local = chunk.get_byte(idx + 1) == 1u8;
+            let index = chunk.get_short(idx + 2);
+            let up_val_type = if is_local { "Local" } else { "UpVal" };
+            operand_val += &format!(" {} idx={}", up_val_type, index);
+
+            idx += 3;
+         }
+
+         op_name
+      }
+   };
+
+   let text = format!(
+      "\x1b[32m{:#04X}\x1b[0m – \x1b[36m{:<26}\x1b[0m {}",
+      code, op_code_name, operand_val
+   );
+
+   (paint(&text).into_owned(), idx + 1)
+}
@@ -1,4 +1,6 @@
 pub mod ast;
 pub mod bytecode;
 pub mod chunk;
+pub mod disassembler;
+pub mod serialization;
 pub mod tokens;
@@ -0,0 +1,417 @@
+use crate::core::chunk::{Chunk, ExceptionHandler, InlineCache};
+use crate::objects::bigint::BigInt;
+use crate::objects::class_obj::InterfaceObject;
+use crate::objects::enum_obj::EnumObject;
+use crate::objects::{FuncObject, LocalVariable, Object};
+use std::convert::TryInto;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+/// The magic bytes at the start of every Hinton bytecode (`.hbc`) file.
+pub const HBC_MAGIC: [u8; 4] = *b"HBC\0";
+
+/// The version of the bytecode format. Must be incremented every time the
+/// format or the instruction set changes in a backwards-incompatible way.
+pub const HBC_VERSION: u16 = 42;
+
+/// The size, in bytes, of the header of a bytecode file (magic, version, and checksum).
+const HEADER_SIZE: usize = 10;
+
+// Tags that identify the type of the objects stored in a constant pool.
+const TAG_NULL: u8 = 0;
+const TAG_BOOL: u8 = 1;
+const TAG_INT: u8 = 2;
+const TAG_FLOAT: u8 = 3;
+const TAG_STRING: u8 = 4;
+const TAG_FUNCTION: u8 = 5;
+const TAG_INTERFACE: u8 = 6;
+const TAG_BIG_INT: u8 = 7;
+const TAG_ENUM: u8 = 8;
+const TAG_CHAR: u8 = 9;
+
+/// Serializes the main function of a compiled program into the bytes of a `.hbc` file.
+///
+/// # Parameters
+/// - `module`: The main function of the program.
+///
+/// # Returns
+/// `Result<Vec<u8>, String>`: The bytes of the file, or an error message if the
+/// program contains a constant that cannot be serialized.
+pub fn serialize(module: &FuncObject) -> Result<Vec<u8>, String> {
+   let mut payload = vec![];
+   write_function(&mut payload, module)?;
+
+   let mut bytes = Vec::with_capacity(HEADER_SIZE + payload.len());
+   bytes.extend_from_slice(&HBC_MAGIC);
+   bytes.extend_from_slice(&HBC_VERSION.to_be_bytes());
+   bytes.extend_from_slice(&checksum(&payload).to_be_bytes());
+   bytes.extend_from_slice(&payload);
+
+   Ok(bytes)
+}
+
+/// Deserializes the bytes of a `.hbc` file into the main function of a compiled program.
+///
+/// # Parameters
+/// - `bytes`: The bytes of the file.
+///
+/// # Returns
+/// `Result<FuncObject, String>`: The main function of the program, or an error message
+/// if the file is not a valid bytecode file for this version of Hinton.
+pub fn deserialize(bytes: &[u8]) -> Result<FuncObject, String> {
+   if bytes.len() < HEADER_SIZE || bytes[0..4] != HBC_MAGIC {
+      return Err(String::from("Not a Hinton bytecode file."));
+   }
+
+   let version = u16::from_be_bytes([bytes[4], bytes[5]]);
+   if version != HBC_VERSION {
+      return Err(format!(
+         "Unsupported bytecode version {} (expected version {}).",
+         version, HBC_VERSION
+      ));
+   }
+
+   let payload = &bytes[HEADER_SIZE..];
+   if u32::from_be_bytes([bytes[6], bytes[7], bytes[8], bytes[9]]) != checksum(payload) {
+      return Err(String::from("Checksum mismatch. The bytecode file is corrupted."));
+   }
+
+   let mut reader = Reader {
+      bytes: payload,
+      pos: 0,
+   };
+   let module = read_function(&mut reader)?;
+
+   if reader.pos != payload.len() {
+      return Err(String::from("Unexpected data at the end of the bytecode file."));
+   }
+
+   Ok(module)
+}
+
+/// Replaces the checksum in the header of a bytecode file with the checksum of its contents,
+/// so that corrupted files can reach the rest of the deserializer (e.g., while fuzzing).
+///
+/// # Parameters
+/// - `bytes`: The bytes of the file.
+pub fn with_valid_checksum(bytes: &[u8]) -> Vec<u8> {
+   let mut bytes = bytes.to_vec();
+
+   if bytes.len() >= HEADER_SIZE {
+      let sum = checksum(&bytes[HEADER_SIZE..]);
+      bytes[6..10].copy_from_slice(&sum.to_be_bytes());
+   }
+
+   bytes
+}
+
+/// Computes the Adler-32 checksum of the given bytes.
+fn checksum(bytes: &[u8]) -> u32 {
+   let mut a: u32 = 1;
+   let mut b: u32 = 0;
+
+   for byte in bytes {
+      a = (a + *byte as u32) % 65521;
+      b = (b + a) % 65521;
+   }
+
+   (b << 16) | a
+}
+
+/// Writes a length or index as an unsigned 32-bit integer.
+fn write_u32(out: &mut Vec<u8>, val: usize) {
+   out.extend_from_slice(&(val as u32).to_be_bytes());
+}
+
+/// Writes a string, prefixed by its length in bytes.
+fn write_string(out: &mut Vec<u8>, val: &str) {
+   write_u32(out, val.len());
+   out.extend_from_slice(val.as_bytes());
+}
+
+/// Writes a function object and its chunk.
+fn write_function(out: &mut Vec<u8>, func: &FuncObject) -> Result<(), String> {
+   write_string(out, &func.name);
+   out.push(func.min_arity);
+   out.push(func.max_arity);
+   write_u32(out, func.params.len());
+   for name in func.params.iter() {
+      write_string(out, name);
+   }
+   out.push(func.is_variadic as u8);
+   write_u32(out, func.up_val_names.len());
+   for name in func.up_val_names.iter() {
+      write_string(out, name);
+   }
+   write_string(out, &func.filepath.to_string_lossy());
+   write_u32(out, func.locals.len());
+   for local in func.locals.iter() {
+      write_string(out, &local.name);
+      write_u32(out, local.slot);
+      write_u32(out, local.start);
+      write_u32(out, local.end);
+   }
+   match &func.doc {
+      Some(doc) => {
+         out.push(1);
+         write_string(out, doc);
+      }
+      None => out.push(0),
+   }
+   write_u32(out, func.globals.len());
+   for name in func.globals.iter() {
+      write_string(out, name);
+   }
+   write_chunk(out, &func.chunk)
+}
+
+/// Writes a chunk's instructions, line information, constant pool, exception handlers, and
+/// the number of its inline caches (which are empty when the chunk is loaded).
+fn write_chunk(out: &mut Vec<u8>, chunk: &Chunk) -> Result<(), String> {
+   write_u32(out, chunk.instructions.len());
+   out.extend_from_slice(&chunk.instructions);
+
+   write_u32(out, chunk.locations.len());
+   for (line, column) in chunk.locations.iter() {
+      write_u32(out, *line);
+      write_u32(out, *column);
+   }
+
+   write_u32(out, chunk.constants.len());
+   for obj in chunk.constants.iter() {
+      write_object(out, obj)?;
+   }
+
+   write_u32(out, chunk.handlers.len());
+   for handler in chunk.handlers.iter() {
+      write_u32(out, handler.start);
+      write_u32(out, handler.end);
+      write_u32(out, handler.target);
+      write_u32(out, handler.stack_depth);
+   }
+
+   write_u32(out, chunk.caches.len());
+
+   Ok(())
+}
+
+/// Writes an object from a constant pool, prefixed by its type tag.
+fn write_object(out: &mut Vec<u8>, obj: &Object) -> Result<(), String> {
+   match obj {
+      Object::Null => out.push(TAG_NULL),
+      Object::Bool(b) => {
+         out.push(TAG_BOOL);
+         out.push(*b as u8);
+      }
+      Object::Int(i) => {
+         out.push(TAG_INT);
+         out.extend_from_slice(&i.to_be_bytes());
+      }
+      Object::BigInt(b) => {
+         out.push(TAG_BIG_INT);
+         write_string(out, &b.to_string());
+      }
+      Object::Float(f) => {
+         out.push(TAG_FLOAT);
+         out.extend_from_slice(&f.to_bits().to_be_bytes());
+      }
+      Object::String(s) => {
+         out.push(TAG_STRING);
+         write_string(out, s);
+      }
+      Object::Char(c) => {
+         out.push(TAG_CHAR);
+         out.extend_from_slice(&(*c as u32).to_be_bytes());
+      }
+      Object::Function(f) => {
+         out.push(TAG_FUNCTION);
+         write_function(out, &f.borrow())?;
+      }
+      Object::Interface(i) => {
+         out.push(TAG_INTERFACE);
+         write_string(out, &i.name);
+         write_u32(out, i.methods.len());
+         for (name, arity) in i.methods.iter() {
+            write_string(out, name);
+            out.push(*arity);
+         }
+      }
+      Object::Enum(e) => {
+         out.push(TAG_ENUM);
+         write_string(out, &e.name);
+         write_u32(out, e.variants.len());
+         for (name, fields) in e.variants.iter() {
+            write_string(out, name);
+            write_u32(out, fields.len());
+            for field in fields.iter() {
+               write_string(out, field);
+            }
+         }
+      }
+      _ => {
+         return Err(format!(
+            "Cannot serialize constant of type '{}'.",
+            obj.type_name()
+         ))
+      }
+   }
+
+   Ok(())
+}
+
+/// Reads the payload of a bytecode file.
+struct Reader<'a> {
+   bytes: &'a [u8],
+   pos: usize,
+}
+
+impl<'a> Reader<'a> {
+   /// Reads the given number of raw bytes.
+   fn read_bytes(&mut self, count: usize) -> Result<&'a [u8], String> {
+      if self.bytes.len() - self.pos < count {
+         return Err(String::from("Unexpected end of the bytecode file."));
+      }
+
+      self.pos += count;
+      Ok(&self.bytes[(self.pos - count)..self.pos])
+   }
+
+   /// Reads a single byte.
+   fn read_u8(&mut self) -> Result<u8, String> {
+      Ok(self.read_bytes(1)?[0])
+   }
+
+   /// Reads a length or index written as an unsigned 32-bit integer.
+   fn read_u32(&mut self) -> Result<usize, String> {
+      Ok(u32::from_be_bytes(self.read_bytes(4)?.try_into().unwrap()) as usize)
+   }
+
+   /// Reads an 8-byte value (used for integers and floats).
+   fn read_u64(&mut self) -> Result<u64, String> {
+      Ok(u64::from_be_bytes(self.read_bytes(8)?.try_into().unwrap()))
+   }
+
+   /// Reads a string prefixed by its length in bytes.
+   fn read_string(&mut self) -> Result<String, String> {
+      let len = self.read_u32()?;
+
+      match String::from_utf8(self.read_bytes(len)?.to_vec()) {
+         Ok(s) => Ok(s),
+         Err(_) => Err(String::from("Invalid string in the bytecode file.")),
+      }
+   }
+}
+
+/// Reads a function object and its chunk.
+fn read_function(reader: &mut Reader) -> Result<FuncObject, String> {
+   Ok(FuncObject {
+      name: reader.read_string()?,
+      min_arity: reader.read_u8()?,
+      max_arity: reader.read_u8()?,
+      params: (0..reader.read_u32()?)
+         .map(|_| reader.read_string())
+         .collect::<Result<Vec<String>, String>>()?,
+      is_variadic: reader.read_u8()? == 1,
+      up_val_names: (0..reader.read_u32()?)
+         .map(|_| reader.read_string())
+         .collect::<Result<Vec<String>, String>>()?,
+      filepath: PathBuf::from(reader.read_string()?),
+      locals: (0..reader.read_u32()?)
+         .map(|_| {
+            Ok(LocalVariable {
+               name: reader.read_string()?,
+               slot: reader.read_u32()?,
+               start: reader.read_u32()?,
+               end: reader.read_u32()?,
+            })
+         })
+         .collect::<Result<Vec<LocalVariable>, String>>()?,
+      doc: match reader.read_u8()? {
+         1 => Some(reader.read_string()?),
+         _ => None,
+      },
+      globals: (0..reader.read_u32()?)
+         .map(|_| reader.read_string())
+         .collect::<Result<Vec<String>, String>>()?,
+      chunk: read_chunk(reader)?,
+      defaults: vec![],
+   })
+}
+
+/// Reads a chunk's instructions, line information, constant pool, exception handlers, and
+/// inline caches.
+fn read_chunk(reader: &mut Reader) -> Result<Chunk, String> {
+   let mut chunk = Chunk::new();
+
+   let len = reader.read_u32()?;
+   chunk.instructions = reader.read_bytes(len)?.to_vec();
+
+   for _ in 0..reader.read_u32()? {
+      let line = reader.read_u32()?;
+      let column = reader.read_u32()?;
+      chunk.locations.push((line, column));
+   }
+
+   for _ in 0..reader.read_u32()? {
+      let obj = read_object(reader)?;
+      chunk.constants.push(obj);
+   }
+
+   for _ in 0..reader.read_u32()? {
+      chunk.handlers.push(ExceptionHandler {
+         start: reader.read_u32()?,
+         end: reader.read_u32()?,
+         target: reader.read_u32()?,
+         stack_depth: reader.read_u32()?,
+      });
+   }
+
+   let cache_count = reader.read_u32()?;
+   if cache_count > u16::MAX as usize {
+      return Err(String::from(
+         "Invalid number of inline caches in the bytecode file.",
+      ));
+   }
+   chunk.caches = vec![InlineCache::default(); cache_count];
+
+   Ok(chunk)
+}
+
+/// Reads an object from a constant pool.
+fn read_object(reader: &mut Reader) -> Result<Object, String> {
+   Ok(match reader.read_u8()? {
+      TAG_NULL => Object::Null,
+      TAG_BOOL => Object::Bool(reader.read_u8()? != 0),
+      TAG_INT => Object::Int(reader.read_u64()? as i64),
+      TAG_FLOAT => Object::Float(f64::from_bits(reader.read_u64()?)),
+      TAG_STRING => Object::from(reader.read_string()?),
+      TAG_FUNCTION => Object::from(read_function(reader)?),
+      TAG_INTERFACE => Object::Interface(Rc::new(InterfaceObject {
+         name: reader.read_string()?,
+         methods: (0..reader.read_u32()?)
+            .map(|_| Ok((reader.read_string()?, reader.read_u8()?)))
+            .collect::<Result<Vec<(String, u8)>, String>>()?,
+      })),
+      TAG_ENUM => Object::Enum(Rc::new(EnumObject {
+         name: reader.read_string()?,
+         variants: (0..reader.read_u32()?)
+            .map(|_| {
+               let name = reader.read_string()?;
+               let fields = (0..reader.read_u32()?)
+                  .map(|_| reader.read_string())
+                  .collect::<Result<Vec<String>, String>>()?;
+               Ok((name, fields))
+            })
+            .collect::<Result<Vec<(String, Vec<String>)>, String>>()?,
+      })),
+      TAG_BIG_INT => match BigInt::parse(&reader.read_string()?) {
+         Some(b) => Object::from(b),
+         None => return Err(String::from("Invalid BigInt constant in the bytecode file.")),
+      },
+      TAG_CHAR => match char::from_u32(reader.read_u32()? as u32) {
+         Some(c) => Object::Char(c),
+         None => return Err(String::from("Invalid Char constant in the bytecode file.")),
+      },
+      tag => return Err(format!("Unknown constant type '{}' in the bytecode file.", tag)),
+   })
+}
@@ -1,16 +1,46 @@
+use std::borrow::Cow;
+
 // A token that represents a single unit of Hinton code.
-#[derive(Clone)]
-pub struct Token {
+#[derive(Clone, Debug)]
+pub struct Token<'a> {
    /// The token's line number
    pub line_num: usize,
    /// The token's column start
    pub column_start: usize,
    /// The token's column end
    pub column_end: usize,
+   /// The line and column after the last character of the token, which
+   /// may be on a later line than the token's first character.
+   pub span_end: (usize, usize),
+   /// The range of bytes of the source text that the token was scanned from.
+   pub span: Span,
    /// The token's type
    pub token_type: TokenType,
-   /// The token's lexeme
-   pub lexeme: String,
+   /// The token's lexeme, borrowed from the source text. The lexeme of an error token
+   /// is its error message.
+   pub lexeme: Cow<'a, str>,
+}
+
+impl Token<'_> {
+   /// Copies the lexeme of the token, so that the token can outlive the source text
+   /// (e.g., to be stored along with a compiler error).
+   pub fn into_owned(self) -> Token<'static> {
+      Token {
+         lexeme: Cow::Owned(self.lexeme.into_owned()),
+         ..self
+      }
+   }
+}
+
+/// A range of bytes in the source text of a program, from the first byte of a token (`start`)
+/// to the byte after its last character (`end`), so that `&source[span.start..span.end]` is
+/// the text of the token.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Span {
+   /// The offset of the first byte.
+   pub start: usize,
+   /// The offset after the last byte.
+   pub end: usize,
 }
 
 /// The types of tokens in a Hinton program.
@@ -19,6 +49,10 @@ pub struct Token {
 #[repr(u8)]
 pub enum TokenType {
    AS_OPERATOR,
+   ASSERT_KW,
+   ASYNC_KW,
+   AWAIT_KW,
+   BIG_INTEGER,
    BINARY,
    BIT_AND,
    BIT_AND_EQ,
@@ -32,31 +66,51 @@ pub enum TokenType {
    BIT_XOR,
    BIT_XOR_EQ,
    BREAK_KW,
+   CATCH_KW,
+   CHAR_LITERAL,
    CLASS_KW,
    COLON,
    COLON_EQUALS,
    COMMA,
+   COMMENT,
    CONST_KW,
    CONTINUE_KW,
+   DEFER_KW,
+   DO_KW,
+   DOC_COMMENT,
    DOT,
+   ELLIPSIS,
    ELSE_KW,
    ENUM_KW,
    EOF,
    EQUALS,
    ERROR,
    EXPO,
+   EXPORT_KW,
    EXPO_EQUALS,
+   EXTENDS_KW,
    FALSE,
+   FAT_ARROW,
+   FINALLY_KW,
    FLOAT,
+   FLOOR_DIV,
+   FLOOR_DIV_EQ,
    FN_LAMBDA_KW,
    FOR_KW,
+   FROM_KW,
    FUNC_KW,
    GREATER_THAN,
    GREATER_THAN_EQ,
    HEXADECIMAL,
    IDENTIFIER,
    IF_KW,
+   IMPLEMENTS_KW,
+   IMPORT_KW,
    INTEGER,
+   INTERFACE_KW,
+   INTERPOLATION_END,
+   INTERPOLATION_MID,
+   INTERPOLATION_START,
    IN_KW,
    LESS_THAN,
    LESS_THAN_EQ,
@@ -66,23 +120,28 @@ pub enum TokenType {
    LOGIC_NOT,
    LOGIC_NOT_EQ,
    LOGIC_OR,
+   LOOP_KW,
    L_BRACKET,
    L_CURLY,
    L_PAREN,
+   MATCH_KW,
    MINUS,
    MINUS_EQ,
    MODULUS,
    MOD_EQ,
    NEW_KW,
+   NON_NULL_ASSERT,
    NULL,
    NULLISH,
    OCTAL,
+   OPTIONAL_CHAIN,
    OVERRIDE_KW,
    PLUS,
    PLUS_EQ,
    PRIVATE_KW,
    PUBLIC_KW,
    QUESTION,
+   RANGE_INCLUSIVE_OPR,
    RANGE_OPR,
    RETURN_KW,
    R_BRACKET,
@@ -99,31 +158,22 @@ pub enum TokenType {
    SUPER_KW,
    THIN_ARROW,
    TRUE,
+   TRY_KW,
    VAR_KW,
    WHILE_KW,
 
    // ***** To be implemented/considered
    // ABSTRACT_KEYWORD,
-   // ASYNC_KEYWORD,
-   // AWAIT_KEYWORD,
-   // EXPORT_KEYWORD,
-   // EXTENDS_KEYWORD,
    // FLEX_KEYWORD,
-   // FROM_KEYWORD,
-   // IMPLEMENTS_KEYWORD,
    // INSTANCE_OF_KEYWORD,
-   // INTERFACE_KEYWORD,
    // IN_OPERATOR,
-   // LOOP_KEYWORD,
    // OPTIONAL_KEYWORD,
    // ANY_TYPE,
    // BAD_CHARACTER,
    // BOOLEAN_TYPE,
-   // CHAR_LITERAL,
    // DICTIONARY_TYPE,
    // FLOAT_TYPE,
    // FUNCTION_TYPE,
-   // IMPORT_KEYWORD,
    // INTEGER_TYPE,
    // NULL_TYPE,
    // STRING_TYPE,
@@ -156,20 +206,35 @@ pub fn make_identifier_type(id: &str) -> TokenType {
    match id {
       "and" => TokenType::LOGIC_AND,
       "as" => TokenType::AS_OPERATOR,
+      "assert" => TokenType::ASSERT_KW,
+      "async" => TokenType::ASYNC_KW,
+      "await" => TokenType::AWAIT_KW,
       "break" => TokenType::BREAK_KW,
+      "catch" => TokenType::CATCH_KW,
       "class" => TokenType::CLASS_KW,
       "const" => TokenType::CONST_KW,
       "continue" => TokenType::CONTINUE_KW,
+      "defer" => TokenType::DEFER_KW,
+      "do" => TokenType::DO_KW,
       "else" => TokenType::ELSE_KW,
       "enum" => TokenType::ENUM_KW,
       "equals" => TokenType::LOGIC_EQ,
+      "export" => TokenType::EXPORT_KW,
+      "extends" => TokenType::EXTENDS_KW,
       "false" => TokenType::FALSE,
+      "finally" => TokenType::FINALLY_KW,
       "fn" => TokenType::FN_LAMBDA_KW,
       "for" => TokenType::FOR_KW,
+      "from" => TokenType::FROM_KW,
       "func" => TokenType::FUNC_KW,
       "if" => TokenType::IF_KW,
+      "implements" => TokenType::IMPLEMENTS_KW,
+      "import" => TokenType::IMPORT_KW,
       "in" => TokenType::IN_KW,
+      "interface" => TokenType::INTERFACE_KW,
       "is" => TokenType::LOGIC_IS,
+      "loop" => TokenType::LOOP_KW,
+      "match" => TokenType::MATCH_KW,
       "mod" => TokenType::MODULUS,
       "new" => TokenType::NEW_KW,
       "not" => TokenType::LOGIC_NOT,
@@ -183,6 +248,7 @@ pub fn make_identifier_type(id: &str) -> TokenType {
       "static" => TokenType::STATIC_KW,
       "super" => TokenType::SUPER_KW,
       "true" => TokenType::TRUE,
+      "try" => TokenType::TRY_KW,
       "var" => TokenType::VAR_KW,
       "while" => TokenType::WHILE_KW,
 
@@ -200,16 +266,8 @@ pub fn make_identifier_type(id: &str) -> TokenType {
       // "Void"      => TokenType::VOID_TYPE,
 
       // "abstract"  => TokenType::ABSTRACT_KEYWORD,
-      // "async"  => TokenType::ASYNC_KEYWORD,
-      // "await"  => TokenType::AWAIT_KEYWORD,
-      // "export"    => TokenType::EXPORT_KEYWORD,
-      // "extends"   => TokenType::EXTENDS_KEYWORD,
       // "final"     => TokenType::FINAL_KEYWORD,
-      // "from"      => TokenType::FROM_KEYWORD,
-      // "implements"    => TokenType::IMPLEMENTS_KEYWORD,
-      // "import"     => TokenType::IMPORT_KEYWORD,
       // "instanceOf"    => TokenType::INSTANCE_OF_KEYWORD,
-      // "interface"  => TokenType::INTERFACE_KEYWORD,
       // "optional"  => TokenType::OPTIONAL_KEYWORD,
       // "struct"     => TokenType::STRUCT_KEYWORD,
       // "yield"      => TokenType::YIELD_KEYWORD,
@@ -0,0 +1,185 @@
+use crate::core::disassembler;
+use crate::errors::{colors_enabled, paint};
+use crate::objects::Object;
+use crate::virtual_machine::{Breakpoints, DebugCommand, DebugHook, PauseReason, SourceLocation, VM};
+use hashbrown::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// The help message for the commands of the CLI debugger.
+const HELP: &str = "Commands:
+   c, continue          Continue running until the next breakpoint.
+   s, step              Step to the next line, entering function calls.
+   n, next              Step to the next line, stepping over function calls.
+   o, out               Step out of the current function.
+   b, break [file:]line Set a breakpoint.
+   d, delete [file:]line Remove a breakpoint.
+   breakpoints          List the breakpoints.
+   l, where             Show the current source line.
+   bt, backtrace        Show the call stack.
+   locals [frame]       Show the local variables of a call frame (defaults to the current frame).
+   globals              Show the global variables.
+   stack                Show the values stack.
+   disasm               Show the bytecode of the current function.
+   q, quit              Exit the program.";
+
+/// A command-line front-end for the Hinton debugger, used by the `--debug` flag.
+#[derive(Default)]
+pub struct CliDebugger {
+   /// The lines of the source files shown by the debugger, keyed by their path.
+   sources: HashMap<PathBuf, Vec<String>>,
+}
+
+impl DebugHook for CliDebugger {
+   fn on_pause(
+      &mut self,
+      vm: &VM,
+      location: &SourceLocation,
+      reason: PauseReason,
+      breakpoints: &mut Breakpoints,
+   ) -> DebugCommand {
+      if let PauseReason::Breakpoint = reason {
+         println!("{}", paint("\x1b[1mBreakpoint hit.\x1b[0m"));
+      }
+
+      self.print_location(location);
+
+      loop {
+         print!("(hdb) ");
+         std::io::stdout().flush().unwrap_or_default();
+
+         let mut input = String::new();
+         match std::io::stdin().read_line(&mut input) {
+            Ok(0) | Err(_) => return DebugCommand::Continue,
+            Ok(_) => {}
+         }
+
+         let mut parts = input.split_whitespace();
+         let command = parts.next().unwrap_or("");
+         let arg = parts.next();
+
+         match command {
+            "c" | "continue" => return DebugCommand::Continue,
+            "s" | "step" => return DebugCommand::StepInto,
+            "n" | "next" => return DebugCommand::StepOver,
+            "o" | "out" => return DebugCommand::StepOut,
+            "b" | "break" => match parse_breakpoint(arg, location) {
+               Some((path, line)) => {
+                  breakpoints.add(&path, line);
+                  println!("Breakpoint set at {}:{}.", path.display(), line);
+               }
+               None => println!("Expected a breakpoint as '[file:]line'."),
+            },
+            "d" | "delete" => match parse_breakpoint(arg, location) {
+               Some((path, line)) if breakpoints.remove(&path, line) => {
+                  println!("Breakpoint removed from {}:{}.", path.display(), line)
+               }
+               Some(_) => println!("There is no breakpoint at that line."),
+               None => println!("Expected a breakpoint as '[file:]line'."),
+            },
+            "breakpoints" => {
+               for (path, line) in breakpoints.list() {
+                  println!("{}:{}", path.display(), line);
+               }
+            }
+            "l" | "where" => self.print_location(location),
+            "bt" | "backtrace" => print_backtrace(vm),
+            "locals" => {
+               let frame_idx = match arg.map(|a| a.parse::<usize>()) {
+                  Some(Ok(idx)) => idx,
+                  Some(Err(_)) => {
+                     println!("Expected a frame number.");
+                     continue;
+                  }
+                  None => vm.frames_stack().len() - 1,
+               };
+
+               print_variables(&vm.frame_locals(frame_idx));
+            }
+            "globals" => print_variables(&vm.global_values()),
+            "stack" => {
+               for (idx, value) in vm.value_stack().iter().enumerate() {
+                  println!("{:>4}: {}", idx, display_value(value));
+               }
+            }
+            "disasm" => {
+               let function = vm.current_frame().closure.function.borrow();
+               disassembler::disassemble_chunk(
+                  &function.chunk,
+                  &vm.built_in.natives.get_names(),
+                  &vm.built_in.primitives.get_names(),
+                  &function.name,
+               );
+            }
+            "q" | "quit" => std::process::exit(0),
+            "h" | "help" => println!("{}", HELP),
+            "" => {}
+            _ => println!(
+               "Unknown command '{}'. Type 'help' for a list of commands.",
+               command
+            ),
+         }
+      }
+   }
+}
+
+impl CliDebugger {
+   /// Prints the source location where the program is paused, including the source line.
+   fn print_location(&mut self, location: &SourceLocation) {
+      println!(
+         "{}",
+         paint(&format!(
+            "\x1b[1mat [{}:{}] in {}\x1b[0m",
+            location.line, location.column, location.function
+         ))
+      );
+
+      let lines = self.sources.entry(location.filepath.clone()).or_insert_with(|| {
+         match fs::read_to_string(&location.filepath) {
+            Ok(src) => src.lines().map(String::from).collect(),
+            Err(_) => vec![],
+         }
+      });
+
+      if let Some(src_line) = lines.get(location.line - 1) {
+         println!("{:>4} | {}", location.line, src_line);
+      }
+   }
+}
+
+/// Parses a breakpoint in the form `[file:]line`. When no file is provided, the breakpoint
+/// is set in the file where the program is paused.
+fn parse_breakpoint(arg: Option<&str>, location: &SourceLocation) -> Option<(PathBuf, usize)> {
+   let arg = arg?;
+
+   match arg.rsplit_once(':') {
+      Some((file, line)) => Some((Path::new(file).to_path_buf(), line.parse().ok()?)),
+      None => Some((location.filepath.clone(), arg.parse().ok()?)),
+   }
+}
+
+/// Prints the call stack of the VM, from the main function to the current function.
+fn print_backtrace(vm: &VM) {
+   for idx in 0..vm.frames_stack().len() {
+      if let Some(loc) = vm.frame_location(idx) {
+         println!("{:>4}: {} at [{}:{}]", idx, loc.function, loc.line, loc.column);
+      }
+   }
+}
+
+/// Prints a list of named values.
+fn print_variables(variables: &[(String, Object)]) {
+   for (name, value) in variables {
+      println!("   {} = {}", name, display_value(value));
+   }
+}
+
+/// Formats a value for the output of the debugger, colored only if the colors are enabled.
+fn display_value(value: &Object) -> String {
+   if colors_enabled() {
+      value.to_string()
+   } else {
+      value.as_plain_string()
+   }
+}
@@ -0,0 +1,164 @@
+//! Renders the documentation of Hinton programs from their doc comments (single-line comments
+//! that start with `///`), which are attached by the parser to the function and class
+//! declarations that follow them. The documentation of a module is printed by the
+//! `hinton doc <file>` command, and the documentation of a value by the `help(...)` native.
+
+use crate::core::ast::*;
+use crate::errors::ErrorReport;
+use crate::objects::{FuncObject, Object};
+use crate::parser::Parser;
+
+/// Renders the documentation of the public (exported) functions and classes of a module as
+/// Markdown, including the public methods of the exported classes.
+///
+/// # Parameters
+/// - `source`: The source code of the module.
+/// - `module_name`: The name of the module, which is used as the title of the documentation.
+///
+/// # Returns
+/// - `Result<String, Vec<ErrorReport>>`: The documentation of the module, or the syntax errors
+///   of the module.
+pub fn render_module_docs(source: &str, module_name: &str) -> Result<String, Vec<ErrorReport>> {
+   let body = match Parser::parse(source)? {
+      ASTNode::Module(module) => module.body,
+      _ => unreachable!("Should have parsed a module."),
+   };
+
+   let mut docs = format!("# Module `{}`\n", module_name);
+
+   for node in body.iter() {
+      let decl = match node {
+         ASTNode::ExportDecl(export) => &*export.decl,
+         _ => continue,
+      };
+
+      match decl {
+         ASTNode::FunctionDecl(func) => {
+            write_section(&mut docs, "##", &function_signature(func), &func.doc);
+         }
+         ASTNode::ClassDecl(class) => {
+            write_section(&mut docs, "##", &class_signature(class), &class.doc);
+
+            for member in class.members.iter() {
+               let method = match &member.member_type {
+                  ClassMemberDecl::Method(m) if (member.mode & 0b_0000_1000) == 8 => m,
+                  _ => continue,
+               };
+
+               let signature = match member.mode {
+                  m if (m & 0b_0001_0000) != 0 => format!("get {}", function_signature(method)),
+                  m if (m & 0b_0010_0000) != 0 => format!("set {}", function_signature(method)),
+                  m if (m & 0b_0000_0100) != 0 => format!("static {}", function_signature(method)),
+                  _ => function_signature(method),
+               };
+
+               write_section(&mut docs, "###", &signature, &method.doc);
+            }
+         }
+         _ => {}
+      }
+   }
+
+   Ok(docs)
+}
+
+/// Writes the heading of a documented declaration, followed by its doc comment.
+fn write_section(docs: &mut String, heading: &str, signature: &str, doc: &Option<String>) {
+   docs.push_str(&format!("\n{} `{}`\n", heading, signature));
+
+   if let Some(doc) = doc {
+      docs.push_str(&format!("\n{}\n", doc));
+   }
+}
+
+/// Gets the signature of a function declaration (e.g., `func add(a: Int, b = ...) -> Int`).
+fn function_signature(decl: &FunctionDeclNode) -> String {
+   let mut signature = format!("func {}({})", decl.name.lexeme, decl.parameter_list());
+
+   if let Some(return_type) = &decl.return_type {
+      signature.push_str(&format!(" -> {}", return_type.name.lexeme));
+   }
+
+   signature
+}
+
+/// Gets the signature of a class declaration (e.g., `class Circle extends Shape`).
+fn class_signature(decl: &ClassDeclNode) -> String {
+   let mut signature = format!("class {}", decl.name.lexeme);
+
+   if let Some(superclass) = &decl.superclass {
+      signature.push_str(&format!(" extends {}", superclass.token.lexeme));
+   }
+
+   if !decl.interfaces.is_empty() {
+      let names: Vec<&str> = decl.interfaces.iter().map(|i| i.token.lexeme.as_ref()).collect();
+      signature.push_str(&format!(" implements {}", names.join(", ")));
+   }
+
+   signature
+}
+
+/// Renders the documentation of a value, as printed by the `help(...)` native: the signature of a
+/// function followed by its doc comment, or the documentation of the public methods of a class.
+///
+/// # Parameters
+/// - `value`: The documented value.
+///
+/// # Returns
+/// - `Option<String>`: The documentation of the value, or `None` if the value is not a function
+///   or a class.
+pub fn help_text(value: &Object) -> Option<String> {
+   match value {
+      Object::Function(f) => Some(function_help(&f.borrow())),
+      Object::Closure(c) => Some(function_help(&c.function.borrow())),
+      Object::BoundMethod(m) => Some(function_help(&m.method.function.borrow())),
+      Object::Native(n) => Some(format!("func {}(...)", n.name)),
+      Object::Class(class) => {
+         let class = class.borrow();
+         let mut methods: Vec<String> = class
+            .members
+            .iter()
+            .chain(class.statics.iter())
+            .filter(|(_, field)| field.is_public())
+            .filter_map(|(_, field)| match &*field.value {
+               Object::Function(f) => Some(function_help(&f.borrow())),
+               Object::Closure(c) => Some(function_help(&c.function.borrow())),
+               _ => None,
+            })
+            .collect();
+
+         // The members of a class are not stored in the order they were declared.
+         methods.sort();
+
+         let mut help = format!("class {}", class.name);
+         for method in methods {
+            help.push_str(&format!("\n\n{}", method));
+         }
+
+         Some(help)
+      }
+      _ => None,
+   }
+}
+
+/// Renders the signature of a compiled function followed by its doc comment. The values of the
+/// default parameters are not known after the function is compiled, so they are elided.
+fn function_help(func: &FuncObject) -> String {
+   let params: Vec<String> = func
+      .params
+      .iter()
+      .enumerate()
+      .map(|(idx, name)| match idx {
+         _ if func.is_variadic && idx + 1 == func.params.len() => format!("...{}", name),
+         _ if idx >= func.min_arity as usize => format!("{} = ...", name),
+         _ => name.clone(),
+      })
+      .collect();
+
+   let signature = format!("func {}({})", func.name, params.join(", "));
+
+   match &func.doc {
+      Some(doc) => format!("{}\n\n{}", signature, doc),
+      None => signature,
+   }
+}
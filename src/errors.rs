@@ -1,46 +1,331 @@
+use crate::core::tokens::Token;
 use crate::virtual_machine::{RuntimeResult, VM};
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::fmt;
+use std::io::{self, Write};
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether the diagnostics printed to the console are colored with ANSI escape sequences.
+/// Disabled with the `--no-color` flag, and on WebAssembly, where there is no terminal.
+static COLORS_ENABLED: AtomicBool = AtomicBool::new(!cfg!(target_arch = "wasm32"));
+
+thread_local! {
+   /// Whether the text written by the current thread may be colored. Cleared while a VM
+   /// without colors (e.g., the VM of `run_captured(...)`) runs a program on the thread.
+   static THREAD_COLORS: Cell<bool> = const { Cell::new(true) };
+}
 
 /// Represents an error generated by the parser or the compiler.
+#[derive(Clone, Debug)]
 pub struct ErrorReport {
    /// The source line of the error.
    pub line: usize,
    /// The source column of the error.
    pub column: usize,
-   /// The number of characters in the token(s) lexeme(s) that caused the error.
-   pub lexeme_len: usize,
+   /// The source line where the code that caused the error ends.
+   pub end_line: usize,
+   /// The source column after the last character of the code that caused the error.
+   pub end_column: usize,
    /// The error message to display for this error report.
    pub message: String,
 }
 
+impl ErrorReport {
+   /// Creates an error report that spans the source text of a token.
+   ///
+   /// # Parameters
+   /// - `token`: The token that caused the error.
+   /// - `message`: The error message to display.
+   pub fn at_token(token: &Token, message: String) -> Self {
+      ErrorReport {
+         line: token.line_num,
+         column: token.column_start,
+         end_line: token.span_end.0,
+         end_column: token.span_end.1,
+         message,
+      }
+   }
+}
+
+/// Represents an error that stopped a program executed through the library API of
+/// the crate (e.g., `hinton::interpret`), instead of being printed to the console.
+#[derive(Debug)]
+pub enum HintonError {
+   /// The program has syntax errors.
+   Parse(Vec<ErrorReport>),
+   /// The program could not be compiled into bytecode.
+   Compile(Vec<ErrorReport>),
+   /// The program threw an error that was not caught while it was executed.
+   Runtime(RuntimeError),
+}
+
+impl fmt::Display for HintonError {
+   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      match self {
+         HintonError::Parse(errors) | HintonError::Compile(errors) => {
+            let messages: Vec<String> = errors.iter().map(|e| strip_ansi_codes(&e.message)).collect();
+            write!(f, "{}", messages.join("\n"))
+         }
+         HintonError::Runtime(error) => write!(f, "{}", error),
+      }
+   }
+}
+
+impl std::error::Error for HintonError {}
+
+/// Represents an uncaught error thrown by a program while it was executed.
+#[derive(Clone, Debug)]
+pub struct RuntimeError {
+   /// The type of the error.
+   pub error_type: RuntimeErrorType,
+   /// The error message.
+   pub message: String,
+   /// The source line of the instruction that threw the error.
+   pub line: usize,
+   /// The source column of the instruction that threw the error.
+   pub column: usize,
+}
+
+impl RuntimeError {
+   /// Creates a runtime error thrown by the instruction that the VM is currently executing.
+   ///
+   /// # Parameters
+   /// - `vm`: The virtual machine that threw the error.
+   /// - `error_type`: The type of the error.
+   /// - `message`: The error message.
+   pub fn new(vm: &VM, error_type: RuntimeErrorType, message: String) -> Self {
+      let (line, column) = match vm.traceback().last() {
+         Some(frame) => frame
+            .function
+            .borrow()
+            .chunk
+            .get_line_info(frame.ip.saturating_sub(1)),
+         None => (0, 0),
+      };
+
+      RuntimeError {
+         error_type,
+         message,
+         line,
+         column,
+      }
+   }
+}
+
+impl fmt::Display for RuntimeError {
+   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      write!(
+         f,
+         "{}[{}] at [{}:{}]: {}",
+         self.error_type.name(),
+         self.error_type.code(),
+         self.line,
+         self.column,
+         self.message
+      )
+   }
+}
+
+/// Represents a warning generated by the compiler.
+pub struct WarningReport {
+   /// The source line of the warning.
+   pub line: usize,
+   /// The source column of the warning.
+   pub column: usize,
+   /// The number of characters in the token(s) lexeme(s) that caused the warning.
+   pub lexeme_len: usize,
+   /// The warning message to display for this warning report.
+   pub message: String,
+}
+
 /// Represents the types of errors that can occur during
 /// execution of the compiled bytecode.
+#[derive(Clone, PartialEq, Debug)]
 pub enum RuntimeErrorType {
    ArgumentError,
    AssertionError,
+   DeadlockError,
    IndexError,
    InstanceError,
    Internal,
+   IOError,
+   KeyboardInterrupt,
    KeyError,
+   MemoryError,
+   NullError,
    RecursionError,
    ReferenceError,
    StopIteration,
+   TimeoutError,
    TypeError,
    ZeroDivision,
 }
 
+impl RuntimeErrorType {
+   /// Every runtime error type, in the order of their error codes.
+   pub const ALL: [RuntimeErrorType; 17] = [
+      RuntimeErrorType::ArgumentError,
+      RuntimeErrorType::AssertionError,
+      RuntimeErrorType::IndexError,
+      RuntimeErrorType::InstanceError,
+      RuntimeErrorType::Internal,
+      RuntimeErrorType::IOError,
+      RuntimeErrorType::KeyboardInterrupt,
+      RuntimeErrorType::KeyError,
+      RuntimeErrorType::RecursionError,
+      RuntimeErrorType::ReferenceError,
+      RuntimeErrorType::StopIteration,
+      RuntimeErrorType::TypeError,
+      RuntimeErrorType::ZeroDivision,
+      RuntimeErrorType::MemoryError,
+      RuntimeErrorType::TimeoutError,
+      RuntimeErrorType::NullError,
+      RuntimeErrorType::DeadlockError,
+   ];
+
+   /// Gets the stable error code of this runtime error type (e.g., `E0021`), which
+   /// can be passed to the `hinton explain` command for a longer description.
+   pub fn code(&self) -> &'static str {
+      match self {
+         RuntimeErrorType::ArgumentError => "E0009",
+         RuntimeErrorType::AssertionError => "E0010",
+         RuntimeErrorType::IndexError => "E0011",
+         RuntimeErrorType::InstanceError => "E0012",
+         RuntimeErrorType::Internal => "E0013",
+         RuntimeErrorType::IOError => "E0014",
+         RuntimeErrorType::KeyboardInterrupt => "E0015",
+         RuntimeErrorType::KeyError => "E0016",
+         RuntimeErrorType::RecursionError => "E0017",
+         RuntimeErrorType::ReferenceError => "E0018",
+         RuntimeErrorType::StopIteration => "E0019",
+         RuntimeErrorType::TypeError => "E0020",
+         RuntimeErrorType::ZeroDivision => "E0021",
+         RuntimeErrorType::MemoryError => "E0022",
+         RuntimeErrorType::TimeoutError => "E0023",
+         RuntimeErrorType::NullError => "E0024",
+         RuntimeErrorType::DeadlockError => "E0025",
+      }
+   }
+
+   /// Gets the name of this runtime error type as displayed to the programmer.
+   pub fn name(&self) -> &'static str {
+      match self {
+         RuntimeErrorType::ArgumentError => "ArgumentError",
+         RuntimeErrorType::AssertionError => "AssertionError",
+         RuntimeErrorType::DeadlockError => "DeadlockError",
+         RuntimeErrorType::IndexError => "IndexError",
+         RuntimeErrorType::InstanceError => "InstanceError",
+         RuntimeErrorType::Internal => "InternalError",
+         RuntimeErrorType::IOError => "IOError",
+         RuntimeErrorType::KeyboardInterrupt => "KeyboardInterrupt",
+         RuntimeErrorType::KeyError => "KeyError",
+         RuntimeErrorType::MemoryError => "MemoryError",
+         RuntimeErrorType::NullError => "NullError",
+         RuntimeErrorType::RecursionError => "RecursionError",
+         RuntimeErrorType::ReferenceError => "ReferenceError",
+         RuntimeErrorType::StopIteration => "EndOfIterationError",
+         RuntimeErrorType::TimeoutError => "TimeoutError",
+         RuntimeErrorType::TypeError => "TypeError",
+         RuntimeErrorType::ZeroDivision => "ZeroDivisionError",
+      }
+   }
+
+   /// Gets the runtime error type associated with the given error name.
+   pub fn from_name(name: &str) -> Option<Self> {
+      Some(match name {
+         "ArgumentError" => RuntimeErrorType::ArgumentError,
+         "AssertionError" => RuntimeErrorType::AssertionError,
+         "DeadlockError" => RuntimeErrorType::DeadlockError,
+         "IndexError" => RuntimeErrorType::IndexError,
+         "InstanceError" => RuntimeErrorType::InstanceError,
+         "InternalError" => RuntimeErrorType::Internal,
+         "IOError" => RuntimeErrorType::IOError,
+         "KeyboardInterrupt" => RuntimeErrorType::KeyboardInterrupt,
+         "KeyError" => RuntimeErrorType::KeyError,
+         "MemoryError" => RuntimeErrorType::MemoryError,
+         "NullError" => RuntimeErrorType::NullError,
+         "RecursionError" => RuntimeErrorType::RecursionError,
+         "ReferenceError" => RuntimeErrorType::ReferenceError,
+         "EndOfIterationError" => RuntimeErrorType::StopIteration,
+         "TimeoutError" => RuntimeErrorType::TimeoutError,
+         "TypeError" => RuntimeErrorType::TypeError,
+         "ZeroDivisionError" => RuntimeErrorType::ZeroDivision,
+         _ => return None,
+      })
+   }
+}
+
 /// Represents the types of errors that can occur during compilation
 /// of the abstract syntax tree into bytecode.
 pub enum CompilerErrorType {
+   CircularImport,
+   Import,
    MaxCapacity,
    Reassignment,
    Reference,
    Syntax,
    Duplication,
+   Type,
+}
+
+impl CompilerErrorType {
+   /// Every compiler error type, in the order of their error codes.
+   pub const ALL: [CompilerErrorType; 8] = [
+      CompilerErrorType::Syntax,
+      CompilerErrorType::Reference,
+      CompilerErrorType::Reassignment,
+      CompilerErrorType::Duplication,
+      CompilerErrorType::Type,
+      CompilerErrorType::Import,
+      CompilerErrorType::CircularImport,
+      CompilerErrorType::MaxCapacity,
+   ];
+
+   /// Gets the name of this compiler error type as displayed to the programmer.
+   pub fn name(&self) -> &'static str {
+      match self {
+         CompilerErrorType::CircularImport => "CircularImportError",
+         CompilerErrorType::Import => "ImportError",
+         CompilerErrorType::MaxCapacity => "MaxCapacityError",
+         CompilerErrorType::Reassignment => "ReassignmentError",
+         CompilerErrorType::Reference => "ReferenceError",
+         CompilerErrorType::Syntax => "SyntaxError",
+         CompilerErrorType::Duplication => "DuplicationError",
+         CompilerErrorType::Type => "TypeError",
+      }
+   }
+
+   /// Gets the stable error code of this compiler error type (e.g., `E0001`), which
+   /// can be passed to the `hinton explain` command for a longer description.
+   pub fn code(&self) -> &'static str {
+      match self {
+         CompilerErrorType::Syntax => "E0001",
+         CompilerErrorType::Reference => "E0002",
+         CompilerErrorType::Reassignment => "E0003",
+         CompilerErrorType::Duplication => "E0004",
+         CompilerErrorType::Type => "E0005",
+         CompilerErrorType::Import => "E0006",
+         CompilerErrorType::CircularImport => "E0007",
+         CompilerErrorType::MaxCapacity => "E0008",
+      }
+   }
+}
+
+/// Represents the types of warnings that can be generated during
+/// compilation of the abstract syntax tree into bytecode.
+pub enum CompilerWarningType {
+   ConstantCondition,
+   NonExhaustiveMatch,
+   ShadowedSymbol,
+   UnreachableCode,
+   UnusedSymbol,
 }
 
 /// Represents the types of errors that can occur while performing
 /// some operation between Hinton objects.
+#[derive(Debug)]
 pub enum ObjectOprErrType {
    TypeError(String),
    IndexError(String),
@@ -72,128 +357,461 @@ impl ObjectOprErrType {
    }
 }
 
+/// Enables or disables the colors (ANSI escape sequences) of the diagnostics printed to the console.
+///
+/// # Parameters
+/// - `enabled`: Whether the diagnostics should be colored.
+pub fn set_colors_enabled(enabled: bool) {
+   COLORS_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Checks if the diagnostics printed to the console are colored.
+pub fn colors_enabled() -> bool {
+   COLORS_ENABLED.load(Ordering::Relaxed) && THREAD_COLORS.with(Cell::get)
+}
+
+/// Disables the colors of the current thread for as long as it is alive (if the colors are not
+/// enabled for it), and restores them once it is dropped.
+pub(crate) struct ColorsScope(bool);
+
+impl ColorsScope {
+   /// Starts a scope where the colors of the current thread are only kept if they are enabled.
+   ///
+   /// # Parameters
+   /// - `enabled`: Whether the text written in the scope may be colored.
+   pub(crate) fn new(enabled: bool) -> Self {
+      ColorsScope(THREAD_COLORS.with(|colors| colors.replace(colors.get() && enabled)))
+   }
+}
+
+impl Drop for ColorsScope {
+   fn drop(&mut self) {
+      THREAD_COLORS.with(|colors| colors.set(self.0));
+   }
+}
+
+/// Removes the ANSI control sequences (e.g., `\x1b[38;5;81m`) from a string. A control
+/// sequence is made of `ESC [`, its parameter and intermediate bytes, and a final byte.
+///
+/// # Parameters
+/// - `text`: The text with ANSI escape sequences.
+pub fn strip_ansi_codes(text: &str) -> String {
+   let mut plain = String::with_capacity(text.len());
+   let mut chars = text.chars().peekable();
+
+   while let Some(c) = chars.next() {
+      if c != '\x1b' || chars.peek() != Some(&'[') {
+         plain.push(c);
+         continue;
+      }
+
+      chars.next();
+      while chars.next_if(|c| ('\x30'..='\x3f').contains(c)).is_some() {}
+      while chars.next_if(|c| ('\x20'..='\x2f').contains(c)).is_some() {}
+      chars.next_if(|c| ('\x40'..='\x7e').contains(c));
+   }
+
+   plain
+}
+
+/// Prepares a string with ANSI escape sequences to be printed to the console,
+/// removing its escape sequences if colors are disabled.
+///
+/// # Parameters
+/// - `text`: The text to print.
+pub fn paint(text: &str) -> Cow<'_, str> {
+   if colors_enabled() {
+      Cow::Borrowed(text)
+   } else {
+      Cow::Owned(strip_ansi_codes(text))
+   }
+}
+
 /// Reports an error list coming from the parser or compiler.
 ///
 /// # Parameters
+/// - `out`: The output stream where the errors are written (usually, the standard error).
 /// - `filepath`: The file path of where the errors occurred.
 /// - `errors`: An `ErrorList` containing the errors.
 /// - `source`: A reference to the source contents.
-pub fn report_errors_list(filepath: &Path, errors: Vec<ErrorReport>, source: &str) {
-   let source_lines: Vec<&str> = source.split('\n').collect();
+pub fn report_errors_list(
+   out: &mut dyn Write,
+   filepath: &Path,
+   errors: Vec<ErrorReport>,
+   source: &str,
+) -> io::Result<()> {
+   print_errors_list(out, filepath, &errors, source)?;
+   writeln!(
+      out,
+      "For more information about an error, run `hinton explain <code>`."
+   )?;
+   writeln!(
+      out,
+      "{}",
+      paint("\x1b[31;1mERROR:\x1b[0m Aborted execution due to previous errors.")
+   )
+}
+
+/// Prints each error in an error list coming from the parser or compiler, without aborting.
+///
+/// # Parameters
+/// - `out`: The output stream where the errors are written.
+/// - `filepath`: The file path of where the errors occurred.
+/// - `errors`: A reference to the list of errors.
+/// - `source`: A reference to the source contents.
+pub fn print_errors_list(
+   out: &mut dyn Write,
+   filepath: &Path,
+   errors: &[ErrorReport],
+   source: &str,
+) -> io::Result<()> {
+   let source_lines = split_lines(source);
 
    for error in errors.iter() {
-      eprintln!("{}", error.message);
-      print_error_source(
+      writeln!(out, "{}", paint(&error.message))?;
+      print_source(
+         out,
          filepath,
-         error.line,
-         error.column,
-         error.lexeme_len,
+         (error.line, error.column),
+         (error.end_line, error.end_column),
          &source_lines,
-      );
+         "\x1b[31;1m",
+      )?;
    }
 
-   eprintln!("\x1b[31;1mERROR:\x1b[0m Aborted execution due to previous errors.");
+   Ok(())
 }
 
-/// Prints the filepath and a snippet of the source line associated with a parser or compiler error.
+/// Prints each warning in a warning list coming from the compiler.
 ///
 /// # Parameters
-/// - `filepath`: The file path of where the errors occurred.
-/// - `line_num`: The source line number of the error.
-/// - `col`: The source column number of the error.
-/// - `len`: The length of the token that produced the error.
+/// - `out`: The output stream where the warnings are written.
+/// - `filepath`: The file path of where the warnings occurred.
+/// - `warnings`: A reference to the list of warnings.
+/// - `source`: A reference to the source contents.
+pub fn print_warnings_list(
+   out: &mut dyn Write,
+   filepath: &Path,
+   warnings: &[WarningReport],
+   source: &str,
+) -> io::Result<()> {
+   let source_lines = split_lines(source);
+
+   for warning in warnings.iter() {
+      writeln!(out, "{}", paint(&warning.message))?;
+      print_source(
+         out,
+         filepath,
+         (warning.line, warning.column),
+         (warning.line, warning.column + warning.lexeme_len),
+         &source_lines,
+         "\x1b[33;1m",
+      )?;
+   }
+
+   Ok(())
+}
+
+/// Prints the filepath and a snippet of the source code between two positions.
+///
+/// # Parameters
+/// - `out`: The output stream where the snippet is written.
+/// - `filepath`: The file path of the source.
+/// - `start`: The source line and column of the first highlighted character.
+/// - `end`: The source line and column after the last highlighted character.
 /// - `lines`: A reference to a vector with the source lines.
-fn print_error_source(filepath: &Path, line_num: usize, col: usize, len: usize, lines: &[&str]) {
-   let front_pad = (f64::log10(line_num as f64).floor() + 1f64) as usize;
-   let line = lines.get(line_num - 1).unwrap();
+/// - `color`: The ANSI escape sequence for the color of the highlight.
+fn print_source(
+   out: &mut dyn Write,
+   filepath: &Path,
+   start: (usize, usize),
+   end: (usize, usize),
+   lines: &[&str],
+   color: &str,
+) -> io::Result<()> {
+   // Reports without a position (e.g., line 0) point to the first line of the source.
+   let start = (start.0.max(1), start.1);
 
-   eprintln!(
+   // Spans that end before they start (or outside of the source) only highlight their first line.
+   let mut end = (end.0.clamp(start.0, lines.len().max(start.0)), end.1);
+
+   // Spans that end at the start of a line (e.g., after a new line character) end in the previous line.
+   if end.1 == 0 && end.0 > start.0 {
+      end = (end.0 - 1, lines.get(end.0 - 2).map_or(0, |l| l.chars().count()));
+   }
+
+   let end_line = end.0;
+   let front_pad = count_digits(end_line);
+
+   writeln!(
+      out,
       " {}---> File '{}'.",
       "-".repeat(front_pad),
-      filepath.to_str().unwrap()
-   );
-   print_error_snippet(line_num, col, len, line);
+      filepath.display()
+   )?;
+
+   if end_line == start.0 {
+      let len = if end.0 == start.0 {
+         end.1.saturating_sub(start.1)
+      } else {
+         1
+      };
+      let line = lines.get(start.0 - 1).unwrap_or(&"");
+      print_snippet(out, start.0, start.1, len.max(1), line, color)
+   } else {
+      print_multiline_snippet(out, start, (end_line, end.1), lines, color)
+   }
 }
 
 /// Prints a snippet of the source line associated with an error.
 ///
 /// # Parameters
+/// - `out`: The output stream where the snippet is written.
 /// - `line_num`: The source line number of the error.
 /// - `col`: The source column number of the error.
 /// - `len`: The length of the token that produced the error.
 /// - `src`: A reference to the source error line.
-pub fn print_error_snippet(line_num: usize, col: usize, len: usize, src: &str) {
-   let front_pad = (f64::log10(line_num as f64).floor() + 1f64) as usize;
+pub fn print_error_snippet(
+   out: &mut dyn Write,
+   line_num: usize,
+   col: usize,
+   len: usize,
+   src: &str,
+) -> io::Result<()> {
+   print_snippet(out, line_num, col, len, src, "\x1b[31;1m")
+}
+
+/// Counts the number of digits in a line number, which determines the width of the gutter.
+fn count_digits(line_num: usize) -> usize {
+   line_num.max(1).to_string().len()
+}
+
+/// Prints a snippet of a source line, highlighting the given column range.
+///
+/// # Parameters
+/// - `out`: The output stream where the snippet is written.
+/// - `line_num`: The source line number.
+/// - `col`: The source column number to highlight.
+/// - `len`: The number of characters to highlight.
+/// - `src`: A reference to the source line.
+/// - `color`: The ANSI escape sequence for the color of the highlight.
+fn print_snippet(
+   out: &mut dyn Write,
+   line_num: usize,
+   col: usize,
+   len: usize,
+   src: &str,
+   color: &str,
+) -> io::Result<()> {
+   let front_pad = count_digits(line_num);
    // +2 for one extra space at the front and one at the back
    let whitespace_pad_size = " ".repeat(front_pad + 2);
 
-   // Compute the column of the error with trimmed whitespaces from the source line.
-   let mut removed_whitespace = 0;
-   for c in src.chars() {
-      if c == ' ' {
-         removed_whitespace += 1;
-      } else {
-         break;
-      }
+   // The indentation of the source line is not shown, so the column of the error is shifted.
+   let chars: Vec<char> = src.trim_end().chars().collect();
+   let indent = chars.iter().take_while(|c| c.is_whitespace()).count();
+   let text = &chars[indent..];
+
+   // Columns outside of the line (e.g., at the end of the file) point right after the line.
+   let col = col.saturating_sub(indent);
+
+   if !text.is_empty() {
+      writeln!(out, "{}|", whitespace_pad_size)?;
+      write!(out, " {} | ", line_num)?;
+      writeln!(out, "{}", expand_tabs(text))?;
+      write!(out, "{}|", whitespace_pad_size)?;
+      writeln!(
+         out,
+         "{}",
+         paint(&format!(
+            " {}{}{}\x1b[0m",
+            " ".repeat(display_width(columns(text, 0, col))),
+            color,
+            "^".repeat(display_width(columns(text, col, col + len)).max(1))
+         ))
+      )?;
    }
 
-   let col = col - removed_whitespace;
-   let trimmed_source = src.trim();
+   writeln!(out)
+}
+
+/// Splits a source text into lines, removing the carriage returns of `\r\n` line endings.
+///
+/// # Parameters
+/// - `source`: The source text.
+fn split_lines(source: &str) -> Vec<&str> {
+   source
+      .split('\n')
+      .map(|l| l.strip_suffix('\r').unwrap_or(l))
+      .collect()
+}
+
+/// Gets the characters of a source line between two columns, which are clamped to the line.
+fn columns(chars: &[char], start: usize, end: usize) -> &[char] {
+   let end = end.min(chars.len());
+   &chars[start.min(end)..end]
+}
+
+/// The number of columns a tab character takes in the source snippets.
+const TAB_WIDTH: usize = 4;
+
+/// Computes the number of columns taken by some characters in a source snippet.
+fn display_width(chars: &[char]) -> usize {
+   chars.iter().map(|c| if *c == '\t' { TAB_WIDTH } else { 1 }).sum()
+}
+
+/// Replaces the tabs in some characters with spaces, so that they are aligned with the markers.
+fn expand_tabs(chars: &[char]) -> String {
+   chars
+      .iter()
+      .map(|c| {
+         if *c == '\t' {
+            " ".repeat(TAB_WIDTH)
+         } else {
+            c.to_string()
+         }
+      })
+      .collect()
+}
+
+/// Prints a snippet of the source lines spanned by an error, marking where the span starts
+/// and ends, and connecting the lines in between with a bar in the gutter. Long spans only
+/// show their first and last lines.
+///
+/// # Parameters
+/// - `out`: The output stream where the snippet is written.
+/// - `start`: The source line and column of the first highlighted character.
+/// - `end`: The source line and column after the last highlighted character.
+/// - `lines`: A reference to a vector with the source lines.
+/// - `color`: The ANSI escape sequence for the color of the highlight.
+fn print_multiline_snippet(
+   out: &mut dyn Write,
+   start: (usize, usize),
+   end: (usize, usize),
+   lines: &[&str],
+   color: &str,
+) -> io::Result<()> {
+   let front_pad = count_digits(end.0);
+   let gutter = " ".repeat(front_pad + 2);
+   let span_lines: Vec<Vec<char>> = (start.0..=end.0)
+      .map(|l| lines.get(l - 1).unwrap_or(&"").trim_end().chars().collect())
+      .collect();
+
+   // The indentation shared by the spanned lines is removed, so that their relative indentation
+   // is preserved. Blank lines do not count towards the shared indentation.
+   let indent = span_lines
+      .iter()
+      .filter(|l| !l.is_empty())
+      .map(|l| l.iter().take_while(|c| c.is_whitespace()).count())
+      .min()
+      .unwrap_or(0)
+      .min(start.1);
+   let strip = |l: &[char]| expand_tabs(columns(l, indent, l.len()));
+
+   // Columns outside of their line point right after the line.
+   let first = &span_lines[0];
+   let last = &span_lines[span_lines.len() - 1];
+   let start_col = display_width(columns(first, indent, start.1));
+   let end_col = display_width(columns(last, indent, end.1)).max(1);
+
+   writeln!(out, "{}|", gutter)?;
+   writeln!(out, " {:>w$} |   {}", start.0, strip(first), w = front_pad)?;
+   writeln!(
+      out,
+      "{}",
+      paint(&format!(
+         "{}| {} {}^\x1b[0m",
+         gutter,
+         color,
+         "_".repeat(start_col + 1)
+      ))
+   )?;
 
-   if !trimmed_source.is_empty() {
-      eprintln!("{}|", whitespace_pad_size);
-      eprint!(" {} | ", line_num);
-      eprintln!("{}", trimmed_source);
-      eprint!("{}|", whitespace_pad_size);
-      eprintln!(" {}\x1b[31;1m{}\x1b[0m", " ".repeat(col), "^".repeat(len));
+   let bar = paint(&format!("{}|\x1b[0m", color)).to_string();
+   let inner_lines = span_lines.len() - 1;
+
+   for (idx, line) in span_lines.iter().enumerate().skip(1) {
+      // Spans longer than 6 lines only show their first 3 and last 2 lines.
+      if inner_lines > 5 && idx == 3 {
+         writeln!(out, "{}| {} ...", gutter, bar)?;
+      }
+
+      if inner_lines > 5 && idx > 2 && idx < inner_lines - 1 {
+         continue;
+      }
+
+      writeln!(
+         out,
+         " {:>w$} | {} {}",
+         start.0 + idx,
+         bar,
+         strip(line),
+         w = front_pad
+      )?;
    }
 
-   eprintln!()
+   writeln!(
+      out,
+      "{}",
+      paint(&format!("{}| {}|{}^\x1b[0m", gutter, color, "_".repeat(end_col)))
+   )?;
+   writeln!(out)
 }
 
-/// Throws a runtime error to the console.
+/// Reports a runtime error, along with the stack trace of the virtual machine.
 ///
 /// # Parameters
+/// - `out`: The output stream where the error is written (usually, the standard error).
 /// - `vm`: A reference to the virtual machine.
 /// - `error`: The generated error.
 /// - `message`: The error message to be displayed.
 /// - `source`: The program's source text.
-pub fn report_runtime_error(vm: &VM, error: RuntimeErrorType, message: String, source: &str) {
-   let source_lines: Vec<&str> = source.split('\n').collect();
-
-   let frame = vm.current_frame();
-   let f = frame.closure.function.borrow();
-   let line = f.chunk.get_line_info(frame.ip - 1);
-
-   let error_name = match error {
-      RuntimeErrorType::ArgumentError => "ArgumentError",
-      RuntimeErrorType::AssertionError => "AssertionError",
-      RuntimeErrorType::IndexError => "IndexError",
-      RuntimeErrorType::InstanceError => "InstanceError",
-      RuntimeErrorType::Internal => "InternalError",
-      RuntimeErrorType::KeyError => "KeyError",
-      RuntimeErrorType::RecursionError => "RecursionError",
-      RuntimeErrorType::ReferenceError => "ReferenceError",
-      RuntimeErrorType::StopIteration => "EndOfIterationError",
-      RuntimeErrorType::TypeError => "TypeError",
-      RuntimeErrorType::ZeroDivision => "ZeroDivisionError",
+pub fn report_runtime_error(
+   out: &mut dyn Write,
+   vm: &VM,
+   error: RuntimeErrorType,
+   message: String,
+   source: &str,
+) -> io::Result<()> {
+   let source_lines = split_lines(source);
+   let traceback = vm.traceback();
+
+   // Errors thrown before the program starts running have no source location.
+   let line = match traceback.last() {
+      Some(frame) => frame
+         .function
+         .borrow()
+         .chunk
+         .get_line_info(frame.ip.saturating_sub(1)),
+      None => (0, 0),
    };
 
-   eprintln!("\x1b[31;1m{}:\x1b[0m\x1b[1m {}\x1b[0m", error_name, message);
+   writeln!(
+      out,
+      "{}",
+      paint(&format!(
+         "\x1b[31;1m{}[{}]:\x1b[0m\x1b[1m {}\x1b[0m",
+         error.name(),
+         error.code(),
+         message
+      ))
+   )?;
 
-   let src_line = source_lines.get(line.0 - 1).unwrap();
-   print_error_snippet(line.0, line.1, 1, src_line);
+   // In the REPL, the error may have occurred in a function defined by a previous input.
+   if let Some(src_line) = line.0.checked_sub(1).and_then(|l| source_lines.get(l)) {
+      print_error_snippet(out, line.0, line.1, 1, src_line)?;
+   }
 
    // Print stack trace
-   println!("Traceback (most recent call last):");
+   writeln!(out, "Traceback (most recent call last):")?;
    let mut prev_err = String::new();
    let mut repeated_line_count = 0;
-   let frames_list = vm.frames_stack().iter();
+   let frames_list = traceback.iter();
    let frames_list_len = frames_list.len();
 
    for (i, frame) in frames_list.enumerate() {
-      let func = &frame.closure.function.borrow();
-      let line = func.chunk.get_line_info(frame.ip);
+      let func = &frame.function.borrow();
+      let line = func.chunk.get_line_info(frame.ip.saturating_sub(1));
 
       let new_err;
       if func.name.starts_with('<') {
@@ -206,31 +824,48 @@ pub fn report_runtime_error(vm: &VM, error: RuntimeErrorType, message: String, s
          repeated_line_count += 1;
 
          if repeated_line_count < 3 {
-            eprintln!("{}", new_err);
+            writeln!(out, "{}", new_err)?;
          } else {
             if i == frames_list_len - 1 {
-               eprintln!(
-                  "{:7}\x1b[1mPrevious line repeated {} more times.\x1b[0m",
+               writeln!(
+                  out,
+                  "{:7}{}",
                   "",
-                  repeated_line_count - 2
-               );
+                  paint(&format!(
+                     "\x1b[1mPrevious line repeated {} more times.\x1b[0m",
+                     repeated_line_count - 2
+                  ))
+               )?;
             }
 
             continue;
          }
       } else {
          if repeated_line_count > 0 {
-            eprintln!(
-               "{:7}\x1b[1mPrevious line repeated {} more times.\x1b[0m",
+            writeln!(
+               out,
+               "{:7}{}",
                "",
-               repeated_line_count - 2
-            );
+               paint(&format!(
+                  "\x1b[1mPrevious line repeated {} more times.\x1b[0m",
+                  repeated_line_count - 2
+               ))
+            )?;
             repeated_line_count = 0;
          }
-         eprintln!("{}", new_err);
+         writeln!(out, "{}", new_err)?;
          prev_err = new_err;
       }
    }
 
-   eprintln!("\n\x1b[31;1mERROR:\x1b[0m Aborted execution due to previous errors.");
+   writeln!(
+      out,
+      "\nFor more information about this error, run `hinton explain {}`.",
+      error.code()
+   )?;
+   writeln!(
+      out,
+      "{}",
+      paint("\x1b[31;1mERROR:\x1b[0m Aborted execution due to previous errors.")
+   )
 }
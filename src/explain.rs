@@ -0,0 +1,473 @@
+use crate::errors::{CompilerErrorType, RuntimeErrorType};
+
+/// Gets the name of the error type associated with an error code.
+///
+/// # Parameters
+/// - `code`: The error code (e.g., `E0012`).
+///
+/// # Returns
+/// - `Option<&'static str>`: The name of the error type, or `None` if the code is unknown.
+pub fn error_name(code: &str) -> Option<&'static str> {
+   let compiler_errors = CompilerErrorType::ALL.iter().map(|e| (e.code(), e.name()));
+   let runtime_errors = RuntimeErrorType::ALL.iter().map(|e| (e.code(), e.name()));
+
+   compiler_errors
+      .chain(runtime_errors)
+      .find(|(c, _)| *c == code)
+      .map(|(_, name)| name)
+}
+
+/// Gets every error code, along with the name of its error type, in the order of the codes.
+pub fn error_codes() -> Vec<(&'static str, &'static str)> {
+   let compiler_errors = CompilerErrorType::ALL.iter().map(|e| (e.code(), e.name()));
+   let runtime_errors = RuntimeErrorType::ALL.iter().map(|e| (e.code(), e.name()));
+
+   compiler_errors.chain(runtime_errors).collect()
+}
+
+/// Gets the longer description of an error code, with an example of
+/// code that causes the error and a way to fix it.
+///
+/// # Parameters
+/// - `code`: The error code (e.g., `E0012`).
+///
+/// # Returns
+/// - `Option<&'static str>`: The description of the error, or `None` if the code is unknown.
+pub fn explanation(code: &str) -> Option<&'static str> {
+   Some(match code {
+      "E0001" => {
+         "\
+The source code does not follow the grammar of Hinton (e.g., a missing semicolon,
+a missing closing parenthesis, or an unterminated string), or a statement is used
+where it is not allowed (e.g., a `return` statement outside of a function).
+
+Example:
+
+    var total = 1 + 2
+    print(total);
+
+Fix: Add the missing token, or move the statement to where it is allowed:
+
+    var total = 1 + 2;
+    print(total);"
+      }
+
+      "E0002" => {
+         "\
+The program refers to a name that has not been declared in the current scope, or uses
+`self` or `super` outside of a class. This error is also reported when a class does not
+implement the methods of the interfaces it claims to implement.
+
+Example:
+
+    func greet() {
+       print(mesage);
+    }
+
+Fix: Declare the name before it is used, or fix its spelling:
+
+    var message = 'Hello';
+    func greet() {
+       print(message);
+    }"
+      }
+
+      "E0003" => {
+         "\
+The program assigns a new value to a name that cannot be reassigned. Only variables
+and function parameters can be reassigned; constants, functions, classes, interfaces,
+class methods, constant fields, and the built-in functions are immutable.
+
+Example:
+
+    const limit = 10;
+    limit = 20;
+
+Fix: Declare the name as a variable if its value needs to change:
+
+    var limit = 10;
+    limit = 20;"
+      }
+
+      "E0004" => {
+         "\
+The same name is declared twice in the same scope.
+
+Example:
+
+    func area(r) { return 3.14 * r * r; }
+    func area(w, h) { return w * h; }
+
+Fix: Rename one of the declarations:
+
+    func circle_area(r) { return 3.14 * r * r; }
+    func rectangle_area(w, h) { return w * h; }"
+      }
+
+      "E0005" => {
+         "\
+A value does not match the type annotation of the variable, parameter, or function it
+is assigned to or returned from. Type annotations are checked when the program is compiled.
+
+Example:
+
+    var count: Int = '10';
+
+Fix: Assign a value of the annotated type, or change the annotation:
+
+    var count: Int = 10;"
+      }
+
+      "E0006" => {
+         "\
+A module imported by the program could not be found, read, or compiled. Modules are
+searched relative to the file that imports them, and in the source directories of the
+Hinton project.
+
+Example:
+
+    import { parse } from './utils/parser';
+
+Fix: Check that the path of the module is correct, that the file exists, and that the
+module itself does not have errors."
+      }
+
+      "E0007" => {
+         "\
+Two or more modules import each other, directly or through other modules, so none
+of them can be loaded before the others.
+
+Example:
+
+    // a.ht
+    import { b } from './b';
+
+    // b.ht
+    import { a } from './a';
+
+Fix: Move the declarations shared by both modules into a third module that
+both of them import."
+      }
+
+      "E0008" => {
+         "\
+The program exceeds one of the limits of the bytecode, like the number of local variables
+in a block, the number of constants in a function, the number of values in a collection
+literal, or the amount of code that a jump or a loop can skip over. Most of these limits
+are 65535 items (or bytes of bytecode).
+
+Example: An array literal with a hundred thousand values, written in the source code.
+
+Fix: Split the code into smaller functions, or build large collections while the program
+runs (e.g., by reading the values from a file) instead of writing them in the source code."
+      }
+
+      "E0009" => {
+         "\
+A function was called with the wrong number of arguments, with a named argument it
+does not accept, or with an argument whose value is not valid for the function.
+
+Example:
+
+    func add(a, b) { return a + b; }
+    add(1, 2, 3);
+
+Fix: Pass the arguments expected by the function:
+
+    add(1, 2);"
+      }
+
+      "E0010" => {
+         "\
+The condition of an `assert` statement was falsey, or the values passed to
+`assert_eq(...)` or `assert_ne(...)` were not equal (or were equal).
+
+Example:
+
+    var items = [];
+    assert items.len() > 0, 'Expected at least one item.';
+
+Fix: Check the logic that produced the asserted values. If the condition is not
+guaranteed to hold, handle the case with an `if` statement instead."
+      }
+
+      "E0011" => {
+         "\
+An index is outside the bounds of an array, tuple, string, or range.
+
+Example:
+
+    var names = ['Ann', 'Bob'];
+    print(names[2]);
+
+Fix: Use an index between 0 and the length of the collection minus one (or a negative
+index to count from the end), or check the length of the collection first:
+
+    print(names[1]);
+    print(names[-1]);"
+      }
+
+      "E0012" => {
+         "\
+An object was instantiated or inherited from, but it is not a class. This error is
+also reported when a class does not correctly implement the methods of an interface.
+
+Example:
+
+    var Point = 5;
+    var p = new Point();
+
+Fix: Only use `new` and `extends` with classes:
+
+    class Point {}
+    var p = new Point();"
+      }
+
+      "E0013" => {
+         "\
+An internal error of the interpreter, which is not caused by the program itself
+(e.g., the system clock is set before the Unix epoch).
+
+Fix: Check the environment in which the program runs. If the error persists, please
+report it as a bug in the Hinton interpreter."
+      }
+
+      "E0014" => {
+         "\
+An input or output operation failed, like reading a file that does not exist,
+writing to a directory without permissions, or running a command that cannot be found.
+
+Example:
+
+    var contents = File.read('missing.txt');
+
+Fix: Check that the file exists and is accessible, or handle the error:
+
+    try {
+       var contents = File.read('missing.txt');
+    } catch (e) {
+       print('Could not read the file.');
+    }"
+      }
+
+      "E0015" => {
+         "\
+The program was interrupted by the user (e.g., by pressing Ctrl-C).
+
+Fix: This error is expected when the program is interrupted. Long-running loops can be
+stopped earlier by checking a condition instead."
+      }
+
+      "E0016" => {
+         "\
+A dictionary does not have an entry with the given key.
+
+Example:
+
+    var ages = {'ann': 31};
+    print(ages['bob']);
+
+Fix: Check that the key exists before accessing it, or use a default value:
+
+    if ages.contains('bob') { print(ages['bob']); }
+    print(ages.get('bob', 0));"
+      }
+
+      "E0017" => {
+         "\
+The program exceeded the maximum depth of nested function calls, which usually
+means that a recursive function never reaches its base case.
+
+Example:
+
+    func countdown(n) {
+       print(n);
+       countdown(n - 1);
+    }
+
+Fix: Add a base case that stops the recursion, or rewrite the function with a loop:
+
+    func countdown(n) {
+       if n < 0 { return; }
+       print(n);
+       countdown(n - 1);
+    }"
+      }
+
+      "E0018" => {
+         "\
+The program accessed a property that is not defined in an object, accessed a private
+property from outside of its class, or reassigned a constant property.
+
+Example:
+
+    class User {
+       pub var name = 'Ann';
+    }
+    var user = new User();
+    print(user.email);
+
+Fix: Declare the property in the class (as `pub` if it is used outside of the class),
+or check the spelling of the property."
+      }
+
+      "E0019" => {
+         "\
+The `next(...)` function was called on an iterator that has no more items.
+
+Example:
+
+    var it = iter([1]);
+    next(it);
+    next(it);
+
+Fix: Use a `for` loop, which stops at the end of the iterator, or catch the error:
+
+    for var item in [1] { print(item); }"
+      }
+
+      "E0020" => {
+         "\
+An operation was applied to a value of the wrong type, like adding a number to a
+string, calling a value that is not a function, or passing an argument of the
+wrong type to a built-in function.
+
+Example:
+
+    var total = 'Total: ' + 10;
+
+Fix: Use values of compatible types, or use string interpolation:
+
+    var total = 'Total: ${10}';"
+      }
+
+      "E0021" => {
+         "\
+A number was divided by zero, or the modulus of a number by zero was computed.
+
+Example:
+
+    var average = total / count;
+
+Fix: Check the divisor before dividing:
+
+    var average = count == 0 ? 0 : total / count;"
+      }
+
+      "E0022" => {
+         "\
+The program exceeded the size of the values stack or the amount of heap memory allowed by
+the configuration of the virtual machine. These limits are only set by applications that
+embed Hinton to run untrusted programs, and these errors cannot be caught with `try`.
+
+Example: With a heap limit of 1 MB:
+
+    var items = [];
+    while true { items.push('item'); }
+
+Fix: Release the values that are no longer needed, process large data in smaller
+pieces, or ask the embedding application for a higher limit."
+      }
+
+      "E0023" => {
+         "\
+The program executed more instructions, or ran for longer, than allowed by the configuration
+of the virtual machine. These limits are only set by applications that embed Hinton to run
+untrusted programs. The error can be caught with `try`, but the program ends if it does not
+finish shortly after.
+
+Example: With a limit of one million instructions:
+
+    var total = 0;
+    while true { total += 1; }
+
+Fix: Make sure that every loop ends, reduce the amount of work done by the program, or
+ask the embedding application for a higher limit."
+      }
+
+      "E0024" => {
+         "\
+A value asserted to be non-null with the `!!` operator is `null`. The error points at the
+operator, and its message includes the source code of the asserted value.
+
+Example:
+
+    var user = { name: null };
+    print(user.name!!.len());
+
+Fix: Check for `null` before using the value, or use the optional chaining operator (`?.`)
+and a default value, as in `user.name?.len() ?? 0`."
+      }
+
+      "E0025" => {
+         "\
+A green thread waited to receive a value from an empty channel, or awaited a pending promise,
+but nothing could ever send the value or settle the promise, because every other thread was
+also waiting on a channel or a promise. The error is raised in the main thread. Receiving from
+an empty channel or awaiting a pending promise inside a function called by a native function
+(e.g., the callback of `arr.map(...)`) also raises this error, since green threads cannot
+switch in the middle of a native call.
+
+Example:
+
+    var results = Channel();
+    spawn(fn () { var x = 21 * 2; });
+    print(results.recv());
+
+Fix: Make sure that every value received from a channel is sent by another green thread,
+or use `chan.try_recv()`, which returns `null` instead of waiting. Await the promises
+outside of callbacks passed to native functions."
+      }
+
+      _ => return None,
+   })
+}
+
+/// Prints the explanation of an error code, or the list of error codes if no code is given.
+/// Codes are not case-sensitive, and the leading `E` and zeros can be omitted (e.g., `e12`
+/// and `12` are equivalent to `E0012`).
+///
+/// # Parameters
+/// - `code`: The error code to explain.
+///
+/// # Returns
+/// - `bool`: Whether the code is a known error code.
+pub fn explain(code: Option<&str>) -> bool {
+   let code = match code {
+      Some(c) => normalize_code(c),
+      None => {
+         println!("Run `hinton explain <code>` for a description of an error. The error codes are:");
+         for (code, name) in error_codes() {
+            println!("   {}  {}", code, name);
+         }
+         return true;
+      }
+   };
+
+   match (error_name(&code), explanation(&code)) {
+      (Some(name), Some(text)) => {
+         println!("{}: {}\n\n{}", code, name, text);
+         true
+      }
+      _ => {
+         eprintln!(
+            "Unknown error code '{}'. Run `hinton explain` for a list of codes.",
+            code
+         );
+         false
+      }
+   }
+}
+
+/// Converts an error code into its canonical form (e.g., `e12` into `E0012`).
+///
+/// # Parameters
+/// - `code`: The error code.
+pub fn normalize_code(code: &str) -> String {
+   let digits = code.trim().trim_start_matches(['E', 'e']);
+
+   match digits.parse::<u16>() {
+      Ok(n) if digits.chars().all(|c| c.is_ascii_digit()) => format!("E{:04}", n),
+      _ => code.to_string(),
+   }
+}
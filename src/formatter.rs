@@ -0,0 +1,1561 @@
+//! A pretty-printer that reprints the source code of a Hinton program with canonical
+//! indentation, spacing, and line-wrapping, which is used by the `hinton fmt` command.
+//!
+//! The program is printed from its abstract syntax tree, so the formatted code always has the
+//! same meaning as the original code. Since the syntax tree does not have the comments of the
+//! program, the comments are scanned separately, and each comment is printed before the first
+//! statement that comes after it in the original code (or at the end of the previous line, if
+//! the comment was on the same line as the code before it).
+
+use crate::core::ast::ASTNode::*;
+use crate::core::ast::*;
+use crate::core::tokens::{Token, TokenType};
+use crate::errors::ErrorReport;
+use crate::lexer::Lexer;
+use crate::objects::Object;
+use crate::parser::Parser;
+use hashbrown::HashMap;
+
+/// The text used to indent each level of nested code.
+const INDENT: &str = "    ";
+
+/// The maximum width of a line. Lists (e.g., arrays or the arguments of a call) that do not fit
+/// in a line are wrapped, with one item per line.
+const MAX_WIDTH: usize = 100;
+
+// The precedence of expressions, from the lowest to the highest.
+const ASSIGNMENT: u8 = 1;
+const TERNARY: u8 = 2;
+const NULLISH: u8 = 3;
+const LOGIC_OR: u8 = 4;
+const LOGIC_AND: u8 = 5;
+const BITWISE_OR: u8 = 6;
+const BITWISE_XOR: u8 = 7;
+const BITWISE_AND: u8 = 8;
+const EQUALITY: u8 = 9;
+const COMPARISON: u8 = 10;
+const RANGE: u8 = 11;
+const SHIFT: u8 = 12;
+const TERM: u8 = 13;
+const FACTOR: u8 = 14;
+const EXPO: u8 = 15;
+const UNARY: u8 = 16;
+const POSTFIX: u8 = 17;
+const PRIMARY: u8 = 18;
+
+/// Formats the source code of a Hinton program.
+///
+/// # Parameters
+/// - `source`: The source code of the program.
+///
+/// # Returns
+/// - `Ok(String)`: The formatted source code.
+/// - `Err(Vec<ErrorReport>)`: The syntax errors of the program, which cannot be formatted.
+pub fn format_source(source: &str) -> Result<String, Vec<ErrorReport>> {
+   let ast = Parser::parse(source)?;
+   let mut formatter = Formatter::new(source);
+
+   if let Module(module) = &ast {
+      for node in module.body.iter() {
+         formatter.statement(node);
+      }
+   }
+
+   formatter.flush_comments(usize::MAX);
+   Ok(formatter.out)
+}
+
+/// A comment of the program being formatted.
+struct Comment {
+   /// The byte offset of the comment in the source code.
+   start: usize,
+   /// The text of the comment, including the `//` or `/*` and `*/` characters.
+   text: String,
+   /// Whether the comment is on the same line as the code before it.
+   is_trailing: bool,
+}
+
+/// Prints the syntax tree of a program as formatted source code.
+struct Formatter<'a> {
+   /// The original source code.
+   source: &'a str,
+   /// The tokens of the source code, without the comments.
+   tokens: Vec<Token<'a>>,
+   /// The byte offset of the matching `}` of each `{` in the source code.
+   braces: HashMap<usize, usize>,
+   /// The byte offset of the matching `{` of each `}` in the source code.
+   opening_braces: HashMap<usize, usize>,
+   /// The comments of the source code.
+   comments: Vec<Comment>,
+   /// The index of the next comment to print.
+   next_comment: usize,
+   /// The formatted source code.
+   out: String,
+   /// The indentation level of the code being printed.
+   depth: usize,
+   /// The column at which the expression being formatted starts in its line.
+   column: usize,
+}
+
+impl<'a> Formatter<'a> {
+   /// Creates a formatter for the source code of a program, scanning its comments.
+   fn new(source: &'a str) -> Self {
+      let mut tokens: Vec<Token> = vec![];
+      let mut comments = vec![];
+      let mut braces = HashMap::new();
+      let mut open_braces = vec![];
+
+      for token in Lexer::new(source).with_comments().tokens() {
+         match token.token_type {
+            TokenType::COMMENT => {
+               comments.push(Comment {
+                  start: token.span.start,
+                  text: token.lexeme.trim_end().to_string(),
+                  is_trailing: tokens.last().is_some_and(|t| t.span_end.0 == token.line_num),
+               });
+
+               continue;
+            }
+            TokenType::L_CURLY => open_braces.push(token.span.start),
+            TokenType::R_CURLY => {
+               if let Some(open) = open_braces.pop() {
+                  braces.insert(open, token.span.start);
+               }
+            }
+            _ => {}
+         }
+
+         tokens.push(token);
+      }
+
+      let opening_braces = braces.iter().map(|(open, close)| (*close, *open)).collect();
+
+      Self {
+         source,
+         tokens,
+         braces,
+         opening_braces,
+         comments,
+         next_comment: 0,
+         out: String::new(),
+         depth: 0,
+         column: 0,
+      }
+   }
+
+   /// Gets the indentation of the current nesting level.
+   fn indent(&self) -> String {
+      INDENT.repeat(self.depth)
+   }
+
+   /// Gets the column at the end of the printed code, where the next text is printed.
+   fn out_column(&self) -> usize {
+      let line_start = self.out.rfind('\n').map_or(0, |idx| idx + 1);
+      self.out[line_start..].chars().count()
+   }
+
+   /// Prints the comments that appear before a position of the source code.
+   ///
+   /// # Parameters
+   /// - `limit`: The byte offset in the source code before which comments are printed.
+   fn flush_comments(&mut self, limit: usize) {
+      while let Some(comment) = self.comments.get(self.next_comment) {
+         if comment.start >= limit {
+            break;
+         }
+
+         let text = comment.text.clone();
+         let (start, is_trailing) = (comment.start, comment.is_trailing);
+         self.next_comment += 1;
+
+         if is_trailing && self.out.ends_with('\n') {
+            self.out.pop();
+            self.out.push(' ');
+         } else {
+            self.blank_line_before(start);
+            self.out.push_str(&self.indent());
+         }
+
+         self.out.push_str(&text);
+         self.out.push('\n');
+      }
+   }
+
+   /// Checks if there are comments to print before a position of the source code.
+   fn has_comments_before(&self, limit: usize) -> bool {
+      self
+         .comments
+         .get(self.next_comment)
+         .is_some_and(|c| c.start < limit)
+   }
+
+   /// Prints a blank line if there was at least one blank line before a position of the
+   /// source code, unless it would be the first line of a body.
+   ///
+   /// # Parameters
+   /// - `offset`: The byte offset in the source code of the code printed after the blank line.
+   fn blank_line_before(&mut self, offset: usize) {
+      if self.out.is_empty() || self.out.ends_with("{\n") || self.out.ends_with("\n\n") {
+         return;
+      }
+
+      let before = &self.source[..offset.min(self.source.len())];
+      let whitespace = &before[before.trim_end().len()..];
+
+      if whitespace.matches('\n').count() >= 2 {
+         self.out.push('\n');
+      }
+   }
+
+   /// Gets the index of the token that starts at a byte offset of the source code.
+   fn token_index(&self, offset: usize) -> Option<usize> {
+      self.tokens.binary_search_by_key(&offset, |t| t.span.start).ok()
+   }
+
+   /// Gets the byte offset where the code of a statement starts in the source code. The syntax
+   /// tree keeps the name of declarations, rather than their first token, so the modifiers and
+   /// keywords before the name are included. The same applies to the operators and parenthesis
+   /// before the first operand of an expression.
+   fn statement_start(&self, node: &ASTNode) -> usize {
+      if let BlockStmt(block) = node {
+         if let Some(open) = self.opening_braces.get(&block.end_of_block.span.start) {
+            return *open;
+         }
+      }
+
+      let offset = start_of(node);
+
+      let mut index = match self.token_index(offset) {
+         Some(i) => i,
+         None => return offset,
+      };
+
+      while index > 0 {
+         let is_modifier = |t: &Token| {
+            matches!(
+               t.token_type,
+               TokenType::PUBLIC_KW
+                  | TokenType::STATIC_KW
+                  | TokenType::OVERRIDE_KW
+                  | TokenType::SEMICOLON
+                  | TokenType::L_CURLY
+                  | TokenType::R_CURLY
+            )
+         };
+
+         let previous = &self.tokens[index - 1];
+         let is_prefix = match previous.token_type {
+            TokenType::VAR_KW
+            | TokenType::CONST_KW
+            | TokenType::FUNC_KW
+            | TokenType::ASYNC_KW
+            | TokenType::AWAIT_KW
+            | TokenType::CLASS_KW
+            | TokenType::INTERFACE_KW
+            | TokenType::PUBLIC_KW
+            | TokenType::STATIC_KW
+            | TokenType::OVERRIDE_KW
+            | TokenType::EXPORT_KW
+            | TokenType::NEW_KW
+            | TokenType::MINUS
+            | TokenType::LOGIC_NOT
+            | TokenType::BIT_NOT
+            | TokenType::L_PAREN => true,
+            // The `get` and `set` keywords of property accessors are scanned as identifiers.
+            TokenType::IDENTIFIER => {
+               matches!(previous.lexeme.as_ref(), "get" | "set")
+                  && (index < 2 || is_modifier(&self.tokens[index - 2]))
+            }
+            _ => false,
+         };
+
+         if !is_prefix {
+            break;
+         }
+
+         index -= 1;
+      }
+
+      self.tokens[index].span.start
+   }
+
+   /// Gets the byte offset of the `}` that closes the body of a statement, which is the first
+   /// `{` after the start of the statement that is not nested in parenthesis, brackets, or
+   /// dictionaries.
+   ///
+   /// # Parameters
+   /// - `header`: The byte offset of a token in the statement before its body.
+   /// - `body`: The statements in the body.
+   fn body_end(&self, header: usize, body: &[ASTNode]) -> Option<usize> {
+      let mut index = self.token_index(header)?;
+
+      let mut nesting = 0;
+
+      let open = loop {
+         let token = self.tokens.get(index)?;
+
+         match token.token_type {
+            TokenType::L_PAREN | TokenType::L_BRACKET => nesting += 1,
+            TokenType::R_PARENTHESIS | TokenType::R_BRACKET => nesting -= 1,
+            // A `{` at the start of an operand opens a dictionary (e.g., `for var k in {a: 1} {`),
+            // so the body is the `{` after a token that ends an expression.
+            TokenType::L_CURLY if nesting == 0 => match index.checked_sub(1).map(|i| &self.tokens[i]) {
+               Some(previous) if ends_expression(&previous.token_type) => break token.span.start,
+               _ => {
+                  let close = *self.braces.get(&token.span.start)?;
+                  index = self.token_index(close)?;
+               }
+            },
+            _ => {}
+         }
+
+         index += 1;
+      };
+
+      let close = *self.braces.get(&open)?;
+
+      // A body that is not wrapped in curly braces (e.g., `while (x) x -= 1;`)
+      // has no closing brace, so the matched braces belong to other code.
+      match body.first() {
+         Some(first) if start_of(first) < open || start_of(first) > close => None,
+         _ => Some(close),
+      }
+   }
+
+   /// Prints a statement, along with the comments before it.
+   fn statement(&mut self, node: &ASTNode) {
+      let start = self.statement_start(node);
+      self.flush_comments(start);
+      self.blank_line_before(start);
+
+      self.out.push_str(&self.indent());
+      self.statement_text(node);
+      self.out.push('\n');
+   }
+
+   /// Prints a statement, without indentation before it or a new line after it.
+   fn statement_text(&mut self, node: &ASTNode) {
+      self.column = self.out_column();
+
+      match node {
+         VariableDecl(decl) => {
+            let text = self.var_declaration(decl);
+            self.out.push_str(&text);
+         }
+         ConstantDecl(decl) => {
+            let text = self.const_declaration(decl);
+            self.out.push_str(&text);
+         }
+         FunctionDecl(decl) => {
+            let keyword = if decl.is_async { "async func " } else { "func " };
+            let header = format!(
+               "{}{}",
+               keyword,
+               self.signature(
+                  self.column + keyword.len(),
+                  &decl.name,
+                  &decl.params,
+                  &decl.return_type
+               )
+            );
+            self.out.push_str(&header);
+            self.out.push(' ');
+            self.body(&decl.body, self.body_end(decl.name.span.start, &decl.body));
+         }
+         ClassDecl(decl) => self.class_declaration(decl),
+         InterfaceDecl(decl) => self.interface_declaration(decl),
+         EnumDecl(decl) => {
+            let variants: Vec<String> = decl
+               .variants
+               .iter()
+               .map(|v| match v.fields.is_empty() {
+                  true => v.name.lexeme.to_string(),
+                  false => {
+                     let fields: Vec<&str> = v.fields.iter().map(|f| f.lexeme.as_ref()).collect();
+                     format!("{}({})", v.name.lexeme, fields.join(", "))
+                  }
+               })
+               .collect();
+
+            let text = format!("enum {} {{ {} }}", decl.name.lexeme, variants.join(", "));
+            self.out.push_str(&text);
+         }
+         ImportDecl(decl) => {
+            let text = format!("import {} from {};", decl.name.lexeme, decl.path_token.lexeme);
+            self.out.push_str(&text);
+         }
+         ExportDecl(decl) => {
+            self.out.push_str("export ");
+            self.statement_text(&decl.decl);
+         }
+         ExpressionStmt(stmt) => {
+            let text = self.statement_expr(&stmt.child);
+            self.out.push_str(&text);
+
+            // A call with a trailing closure (e.g., `list.each { print(it); }`) ends like a block.
+            if !matches!(&*stmt.child, FunctionCall(call) if call.args.last().is_some_and(|a| is_trailing_closure(&a.value)))
+            {
+               self.out.push(';');
+            }
+         }
+         BlockStmt(block) => self.body(&block.body, Some(block.end_of_block.span.start)),
+         IfStmt(stmt) => self.if_statement(stmt),
+         WhileStmt(stmt) => self.while_statement(stmt),
+         ForStmt(stmt) => {
+            let mut header = format!("{}for var {}", label_prefix(&stmt.label), stmt.id.token.lexeme);
+
+            if let Some(value_id) = &stmt.value_id {
+               header.push_str(&format!(", {}", value_id.token.lexeme));
+            }
+
+            header.push_str(&format!(" in {} ", self.condition(&stmt.iterator)));
+            self.out.push_str(&header);
+            self.body(&stmt.body, self.body_end(stmt.token.span.start, &stmt.body));
+         }
+         MatchStmt(stmt) => self.match_statement(stmt),
+         LoopBranch(stmt) => {
+            let mut text = stmt.token.lexeme.to_string();
+
+            if let Some(label) = &stmt.label {
+               text.push_str(&format!(" {}", label.lexeme));
+            }
+
+            if let Some(value) = &stmt.value {
+               text.push_str(&format!(" {}", self.expr(value, ASSIGNMENT)));
+            }
+
+            text.push(';');
+            self.out.push_str(&text);
+         }
+         ReturnStmt(stmt) => {
+            let text = match stmt.value.as_deref() {
+               // The values of a `return a, b;` statement are parsed as a tuple, which
+               // has the position of the `return` keyword.
+               Some(Tuple(tuple)) if matches!(tuple.token.token_type, TokenType::RETURN_KW) => {
+                  format!("return {};", self.expr_list(&tuple.values).join(", "))
+               }
+               Some(value) => format!(
+                  "return {};",
+                  self.expr_at(self.column + "return ".len(), value, ASSIGNMENT)
+               ),
+               None => String::from("return;"),
+            };
+
+            self.out.push_str(&text);
+         }
+         TryStmt(stmt) => self.try_statement(stmt),
+         DeferStmt(stmt) => {
+            let body = &stmt.body.body;
+
+            // A deferred expression is parsed as the only statement of the body, with
+            // the position of the `defer` keyword.
+            match body.first() {
+               Some(ExpressionStmt(expr))
+                  if body.len() == 1 && expr.pos == (stmt.token.line_num, stmt.token.column_start) =>
+               {
+                  let text = format!("defer {};", self.expr(&expr.child, ASSIGNMENT));
+                  self.out.push_str(&text);
+               }
+               _ => {
+                  self.out.push_str("defer ");
+                  self.body(body, self.body_end(stmt.token.span.start, body));
+               }
+            }
+         }
+         AssertStmt(stmt) => {
+            let text = match &stmt.message {
+               Some(message) => format!(
+                  "assert {}, {};",
+                  self.expr(&stmt.condition, ASSIGNMENT),
+                  self.expr(message, ASSIGNMENT)
+               ),
+               None => format!("assert {};", self.expr(&stmt.condition, ASSIGNMENT)),
+            };
+
+            self.out.push_str(&text);
+         }
+         // Expressions are only found as statements inside an expression statement.
+         _ => {
+            let text = self.statement_expr(node);
+            self.out.push_str(&text);
+            self.out.push(';');
+         }
+      }
+   }
+
+   /// Formats the expression of an expression statement. An expression that starts with a
+   /// dictionary or an `if` expression is wrapped in parenthesis, so that it is not parsed as
+   /// a block or an `if` statement.
+   fn statement_expr(&mut self, node: &ASTNode) -> String {
+      let text = self.expr_at(self.out_column(), node, ASSIGNMENT);
+
+      match leftmost(node) {
+         Dictionary(_) => format!("({})", text),
+         TernaryConditional(expr) if expr.is_if_expression => format!("({})", text),
+         _ => text,
+      }
+   }
+
+   /// Formats an expression that is followed by a block (e.g., the condition of a `while` loop),
+   /// which is wrapped in parenthesis if it has a trailing closure, since the closure would
+   /// otherwise be parsed as the block.
+   fn condition(&mut self, node: &ASTNode) -> String {
+      let text = self.expr(node, ASSIGNMENT);
+
+      match has_trailing_closure(node) {
+         true => format!("({})", text),
+         false => text,
+      }
+   }
+
+   /// Prints a body of statements wrapped in curly braces, along with the comments in it.
+   ///
+   /// # Parameters
+   /// - `body`: The statements in the body.
+   /// - `end`: The byte offset of the closing `}` in the source code, if known.
+   fn body(&mut self, body: &[ASTNode], end: Option<usize>) {
+      let has_comments = end.is_some_and(|e| self.has_comments_before(e));
+
+      if body.is_empty() && !has_comments {
+         self.out.push_str("{}");
+         return;
+      }
+
+      self.out.push_str("{\n");
+      self.depth += 1;
+
+      for node in body {
+         self.statement(node);
+      }
+
+      if let Some(end) = end {
+         self.flush_comments(end);
+      }
+
+      self.depth -= 1;
+      self.out.push_str(&self.indent());
+      self.out.push('}');
+   }
+
+   /// Prints the branch of a conditional statement or loop, wrapping it in
+   /// curly braces if it is a single statement (e.g., `if (x) return;`).
+   fn branch(&mut self, node: &ASTNode) {
+      match node {
+         BlockStmt(block) => self.body(&block.body, Some(block.end_of_block.span.start)),
+         _ => self.body(std::slice::from_ref(node), None),
+      }
+   }
+
+   /// Prints a `while`, `do-while`, or `loop` statement.
+   fn while_statement(&mut self, stmt: &WhileStmtNode) {
+      self.out.push_str(&label_prefix(&stmt.label));
+
+      match (&stmt.token.token_type, &stmt.condition) {
+         (TokenType::DO_KW, Some(condition)) => {
+            self.out.push_str("do ");
+            self.branch(&stmt.body);
+            let text = format!(" while {};", self.expr(condition, ASSIGNMENT));
+            self.out.push_str(&text);
+         }
+         (_, Some(condition)) => {
+            let text = format!("while {} ", self.condition(condition));
+            self.out.push_str(&text);
+            self.branch(&stmt.body);
+         }
+         (_, None) => {
+            self.out.push_str("loop ");
+            self.branch(&stmt.body);
+         }
+      }
+   }
+
+   /// Prints an `if` statement, along with its `else` branches.
+   fn if_statement(&mut self, stmt: &IfStmtNode) {
+      let text = format!("if {} ", self.condition(&stmt.condition));
+      self.out.push_str(&text);
+      self.branch(&stmt.then_branch);
+
+      match &*stmt.else_branch {
+         Some(IfStmt(else_if)) => {
+            self.out.push_str(" else ");
+            self.if_statement(else_if);
+         }
+         Some(else_branch) => {
+            self.out.push_str(" else ");
+            self.branch(else_branch);
+         }
+         None => {}
+      }
+   }
+
+   /// Prints a `try` statement, along with its `catch` and `finally` blocks.
+   fn try_statement(&mut self, stmt: &TryStmtNode) {
+      self.out.push_str("try ");
+      self.branch(&stmt.body);
+
+      if let Some(catch_body) = &stmt.catch_body {
+         match &stmt.catch_id {
+            Some(id) => self.out.push_str(&format!(" catch ({}) ", id.lexeme)),
+            None => self.out.push_str(" catch "),
+         }
+
+         self.branch(catch_body);
+      }
+
+      if let Some(finally_body) = &stmt.finally_body {
+         self.out.push_str(" finally ");
+         self.branch(finally_body);
+      }
+   }
+
+   /// Prints a `match` statement, with one arm per line.
+   fn match_statement(&mut self, stmt: &MatchStmtNode) {
+      let text = format!("match {} {{\n", self.condition(&stmt.subject));
+      self.out.push_str(&text);
+      self.depth += 1;
+
+      for arm in stmt.arms.iter() {
+         let patterns: Vec<String> = arm.patterns.iter().map(match_pattern).collect();
+         let mut pattern = patterns.join(", ");
+
+         if let Some(guard) = &arm.guard {
+            pattern.push_str(&format!(" if {}", self.expr(guard, ASSIGNMENT)));
+         }
+
+         self.match_arm(&pattern, arm.patterns[0].token().span.start, &arm.body);
+      }
+
+      if let Some(default) = &stmt.default {
+         self.match_arm("else", self.statement_start(default), default);
+      }
+
+      if let Some(end) = self.body_end(stmt.token.span.start, &[]) {
+         self.flush_comments(end);
+      }
+
+      self.depth -= 1;
+      self.out.push_str(&self.indent());
+      self.out.push('}');
+   }
+
+   /// Prints an arm of a `match` statement.
+   ///
+   /// # Parameters
+   /// - `pattern`: The formatted patterns of the arm.
+   /// - `start`: The byte offset of the arm in the source code.
+   /// - `body`: The body of the arm.
+   fn match_arm(&mut self, pattern: &str, start: usize, body: &ASTNode) {
+      self.flush_comments(start);
+      self.blank_line_before(start);
+      self.out.push_str(&format!("{}{} => ", self.indent(), pattern));
+
+      match body {
+         ExpressionStmt(stmt) => {
+            let text = self.statement_expr(&stmt.child);
+            self.out.push_str(&text);
+            self.out.push(',');
+         }
+         _ => self.statement_text(body),
+      }
+
+      self.out.push('\n');
+   }
+
+   /// Formats a variable declaration.
+   fn var_declaration(&mut self, decl: &VariableDeclNode) -> String {
+      let names: Vec<String> = decl
+         .identifiers
+         .iter()
+         .zip(decl.annotations.iter())
+         .map(|(name, annotation)| format!("{}{}", name.lexeme, type_annotation(annotation, ": ")))
+         .collect();
+
+      // Several destructured variables do not need the parenthesis (e.g., `var a, b = f();`).
+      let mut text = if decl.destructure && names.len() == 1 {
+         format!("var ({})", names.join(", "))
+      } else {
+         format!("var {}", names.join(", "))
+      };
+
+      // Variables declared without a value are initialized with a `null`
+      // literal, whose token is the last token of the declaration.
+      let has_value = match &*decl.value {
+         Literal(lit) => {
+            matches!(lit.token.token_type, TokenType::NULL) || !matches!(lit.value, Object::Null)
+         }
+         _ => true,
+      };
+
+      if has_value {
+         let column = self.out_column() + text.len() + 3;
+         text.push_str(&format!(" = {}", self.expr_at(column, &decl.value, ASSIGNMENT)));
+      }
+
+      text.push(';');
+      text
+   }
+
+   /// Formats a constant declaration.
+   fn const_declaration(&mut self, decl: &ConstantDeclNode) -> String {
+      let prefix = format!(
+         "const {}{} = ",
+         decl.name.lexeme,
+         type_annotation(&decl.annotation, ": ")
+      );
+
+      let column = self.out_column() + prefix.chars().count();
+      format!("{}{};", prefix, self.expr_at(column, &decl.value, ASSIGNMENT))
+   }
+
+   /// Formats the signature of a function: its name, parameters, and return type.
+   ///
+   /// # Parameters
+   /// - `column`: The column at which the signature starts in its line.
+   /// - `name`: The name of the function.
+   /// - `params`: The parameters of the function.
+   /// - `return_type`: The return type of the function, if it has one.
+   fn signature(
+      &mut self,
+      column: usize,
+      name: &Token,
+      params: &[Parameter],
+      return_type: &Option<TypeAnnotation>,
+   ) -> String {
+      let params: Vec<String> = params.iter().map(|p| self.parameter(p)).collect();
+
+      format!(
+         "{}{}{}",
+         name.lexeme,
+         self.list(column + name.lexeme.chars().count(), "(", params, ")"),
+         type_annotation(return_type, " -> ")
+      )
+   }
+
+   /// Formats a parameter of a function.
+   fn parameter(&mut self, param: &Parameter) -> String {
+      let name = format!(
+         "{}{}",
+         param.name.lexeme,
+         type_annotation(&param.annotation, ": ")
+      );
+
+      if param.is_variadic {
+         format!("...{}", name)
+      } else if let Some(default) = &param.default {
+         format!("{} = {}", name, self.expr(default, ASSIGNMENT))
+      } else if param.is_optional {
+         format!("{}?", name)
+      } else {
+         name
+      }
+   }
+
+   /// Prints a class declaration, along with its members.
+   fn class_declaration(&mut self, decl: &ClassDeclNode) {
+      let mut header = format!("class {}", decl.name.lexeme);
+
+      if let Some(superclass) = &decl.superclass {
+         header.push_str(&format!(" extends {}", superclass.token.lexeme));
+      }
+
+      if !decl.interfaces.is_empty() {
+         let names: Vec<&str> = decl.interfaces.iter().map(|i| i.token.lexeme.as_ref()).collect();
+         header.push_str(&format!(" implements {}", names.join(", ")));
+      }
+
+      self.out.push_str(&header);
+      self.out.push(' ');
+
+      let end = self.body_end(decl.name.span.start, &[]);
+
+      if decl.members.is_empty() && !end.is_some_and(|e| self.has_comments_before(e)) {
+         self.out.push_str("{}");
+         return;
+      }
+
+      self.out.push_str("{\n");
+      self.depth += 1;
+
+      for member in decl.members.iter() {
+         self.class_member(member);
+      }
+
+      if let Some(end) = end {
+         self.flush_comments(end);
+      }
+
+      self.depth -= 1;
+      self.out.push_str(&self.indent());
+      self.out.push('}');
+   }
+
+   /// Prints a member of a class, along with its modifiers.
+   fn class_member(&mut self, member: &ClassMemberDeclNode) {
+      let name = match &member.member_type {
+         ClassMemberDecl::Var(decl) => &decl.identifiers[0],
+         ClassMemberDecl::Const(decl) => &decl.name,
+         ClassMemberDecl::Method(decl) => &decl.name,
+      };
+
+      let start = self.statement_start(&Identifier(IdentifierExprNode { token: name.clone() }));
+      self.flush_comments(start);
+      self.blank_line_before(start);
+
+      let mut modifiers = self.indent();
+      for (bit, keyword) in [(0b_1000, "pub "), (0b_0100, "static "), (0b_0010, "override ")] {
+         if member.mode & bit != 0 {
+            modifiers.push_str(keyword);
+         }
+      }
+
+      self.out.push_str(&modifiers);
+
+      match &member.member_type {
+         ClassMemberDecl::Var(decl) => {
+            let text = self.var_declaration(decl);
+            self.out.push_str(&text);
+         }
+         ClassMemberDecl::Const(decl) => {
+            let text = self.const_declaration(decl);
+            self.out.push_str(&text);
+         }
+         ClassMemberDecl::Method(decl) => {
+            let keyword = if member.mode & 0b_0001_0000 != 0 {
+               "get"
+            } else if member.mode & 0b_0010_0000 != 0 {
+               "set"
+            } else if decl.is_async {
+               "async func"
+            } else {
+               "func"
+            };
+
+            let column = self.out_column() + keyword.len() + 1;
+            let header = format!(
+               "{} {} ",
+               keyword,
+               self.signature(column, &decl.name, &decl.params, &decl.return_type)
+            );
+            self.out.push_str(&header);
+            self.body(&decl.body, self.body_end(decl.name.span.start, &decl.body));
+         }
+      }
+
+      self.out.push('\n');
+   }
+
+   /// Prints an interface declaration, with one method signature per line.
+   fn interface_declaration(&mut self, decl: &InterfaceDeclNode) {
+      self.out.push_str(&format!("interface {} {{\n", decl.name.lexeme));
+      self.depth += 1;
+
+      for method in decl.methods.iter() {
+         let start = self.statement_start(&Identifier(IdentifierExprNode {
+            token: method.name.clone(),
+         }));
+
+         self.flush_comments(start);
+         self.blank_line_before(start);
+
+         let column = self.depth * INDENT.len() + "func ".len();
+         let text = format!(
+            "{}func {};\n",
+            self.indent(),
+            self.signature(column, &method.name, &method.params, &method.return_type)
+         );
+         self.out.push_str(&text);
+      }
+
+      if let Some(end) = self.body_end(decl.name.span.start, &[]) {
+         self.flush_comments(end);
+      }
+
+      self.depth -= 1;
+      self.out.push_str(&self.indent());
+      self.out.push('}');
+   }
+
+   /// Formats an expression, wrapping it in parenthesis if its precedence is lower than the
+   /// precedence required by the code around it.
+   ///
+   /// # Parameters
+   /// - `node`: The expression.
+   /// - `min_precedence`: The lowest precedence of an expression that does not need parenthesis.
+   fn expr(&mut self, node: &ASTNode, min_precedence: u8) -> String {
+      let text = self.expr_text(node);
+
+      if precedence(node) < min_precedence {
+         format!("({})", text)
+      } else {
+         text
+      }
+   }
+
+   /// Formats an expression that starts at the given column of its line.
+   ///
+   /// # Parameters
+   /// - `column`: The column at which the expression starts.
+   /// - `node`: The expression.
+   /// - `min_precedence`: The lowest precedence of an expression that does not need parenthesis.
+   fn expr_at(&mut self, column: usize, node: &ASTNode, min_precedence: u8) -> String {
+      let outer = std::mem::replace(&mut self.column, column);
+      let text = self.expr(node, min_precedence);
+      self.column = outer;
+      text
+   }
+
+   /// Formats an expression, without parenthesis around it.
+   fn expr_text(&mut self, node: &ASTNode) -> String {
+      match node {
+         Literal(lit) => lit.token.lexeme.to_string(),
+         Identifier(id) => id.token.lexeme.to_string(),
+         SelfExpr(_) => String::from("self"),
+         SuperExpr(expr) => format!("super.{}", expr.method.lexeme),
+         StringInterpolation(expr) => self.string_interpolation(expr),
+         Array(expr) => {
+            let values = self.expr_list(&expr.values);
+            self.list(self.column, "[", values, "]")
+         }
+         Tuple(expr) => {
+            let values = self.expr_list(&expr.values);
+
+            // A tuple with one value needs a comma, so that it is not parsed as a grouping.
+            match values.len() {
+               1 => format!("({},)", values[0]),
+               _ => self.list(self.column, "(", values, ")"),
+            }
+         }
+         Comprehension(expr) => {
+            let mut text = match &expr.key {
+               Some(key) => format!("{}: ", self.expr(key, ASSIGNMENT)),
+               None => String::new(),
+            };
+
+            text.push_str(&self.expr(&expr.value, ASSIGNMENT));
+
+            for clause in expr.clauses.iter() {
+               text.push_str(&format!(" for {}", clause.id.token.lexeme));
+
+               if let Some(value_id) = &clause.value_id {
+                  text.push_str(&format!(", {}", value_id.token.lexeme));
+               }
+
+               text.push_str(&format!(" in {}", self.expr(&clause.iterator, ASSIGNMENT)));
+
+               if let Some(condition) = &clause.condition {
+                  text.push_str(&format!(" if {}", self.expr(condition, ASSIGNMENT)));
+               }
+            }
+
+            match expr.key {
+               Some(_) => format!("{{ {} }}", text),
+               None => format!("[{}]", text),
+            }
+         }
+         Dictionary(expr) => {
+            // Dictionaries that were written with their entries on separate lines
+            // keep them on separate lines, even if the entries fit in a line.
+            let is_multiline = match expr.keys.first() {
+               Some(key) => self.source[expr.token.span.end..key.span.start].contains('\n'),
+               None => false,
+            };
+
+            self.depth += is_multiline as usize;
+            let values = self.expr_list(&expr.values);
+            self.depth -= is_multiline as usize;
+
+            let entries: Vec<String> = expr
+               .keys
+               .iter()
+               .zip(values)
+               .map(|(key, value)| format!("{}: {}", key.lexeme, value))
+               .collect();
+
+            match entries.len() {
+               0 => String::from("{}"),
+               _ if is_multiline => self.wrapped_list("{", entries, "}"),
+               _ => self.list(self.column, "{ ", entries, " }"),
+            }
+         }
+         Unary(expr) => {
+            let (operator, min_precedence) = match expr.opr_type {
+               UnaryExprType::ArithmeticNeg => ("-", EXPO),
+               UnaryExprType::BitwiseNeg => ("~", EXPO),
+               // The logic negation applies to the whole expression after it, so it is
+               // wrapped in parenthesis if it is not a unary or primary expression.
+               UnaryExprType::LogicNeg => ("!", UNARY),
+               UnaryExprType::Await => ("await ", UNARY),
+            };
+
+            let operand = self.expr(&expr.operand, min_precedence);
+
+            // Avoids printing two consecutive operators (e.g., `- -x`) as a different operator.
+            if operand.starts_with(['-', '~', '!']) && !operator.ends_with(' ') {
+               format!("{}({})", operator, operand)
+            } else {
+               format!("{}{}", operator, operand)
+            }
+         }
+         Binary(expr) => {
+            let own = binary_precedence(&expr.opr_type);
+
+            // Binary operators are left-associative, except for ranges, which cannot be chained.
+            // The operand of a negation includes the exponentiations after it (e.g., `-a ** b`
+            // is `-(a ** b)`), so a negation on the left of an exponentiation is wrapped.
+            let left_precedence = match (&*expr.left, own) {
+               (_, RANGE) => own + 1,
+               // Prevents a comparison on the left of an ordering comparison from being chained.
+               (Binary(_) | ComparisonChain(_), COMPARISON) if is_ordering(&expr.opr_type) => own + 1,
+               (Unary(_), EXPO) => POSTFIX,
+               _ => own,
+            };
+
+            let (left, right) = (
+               self.expr(&expr.left, left_precedence),
+               self.expr(&expr.right, own + 1),
+            );
+
+            match own {
+               RANGE => format!("{}{}{}", left, expr.opr_token.lexeme, right),
+               _ => format!("{} {} {}", left, expr.opr_token.lexeme, right),
+            }
+         }
+         ComparisonChain(expr) => {
+            let mut chain = self.expr(&expr.operands[0], COMPARISON + 1);
+
+            for ((token, _), operand) in expr.operators.iter().zip(expr.operands[1..].iter()) {
+               chain.push_str(&format!(
+                  " {} {}",
+                  token.lexeme,
+                  self.expr(operand, COMPARISON + 1)
+               ));
+            }
+
+            chain
+         }
+         TernaryConditional(expr) if expr.is_if_expression => {
+            let condition = self.condition(&expr.condition);
+            let branch_true = self.expr(&expr.branch_true, ASSIGNMENT);
+            let branch_false = self.expr(&expr.branch_false, ASSIGNMENT);
+
+            match &*expr.branch_false {
+               // The `else` branch of an `else if` chain is not wrapped in curly braces.
+               TernaryConditional(e) if e.is_if_expression => {
+                  format!("if {} {{ {} }} else {}", condition, branch_true, branch_false)
+               }
+               _ => format!(
+                  "if {} {{ {} }} else {{ {} }}",
+                  condition, branch_true, branch_false
+               ),
+            }
+         }
+         TernaryConditional(expr) => format!(
+            "{} ? {} : {}",
+            self.expr(&expr.condition, NULLISH),
+            self.expr(&expr.branch_true, ASSIGNMENT),
+            self.expr(&expr.branch_false, ASSIGNMENT)
+         ),
+         VarReassignment(expr) => {
+            let prefix = format!(
+               "{} {} ",
+               expr.target.lexeme,
+               reassignment_operator(&expr.opr_type)
+            );
+            let column = self.column + prefix.chars().count();
+            format!("{}{}", prefix, self.expr_at(column, &expr.value, ASSIGNMENT))
+         }
+         ObjectGetter(expr) => format!(
+            "{}{}{}",
+            self.expr(&expr.target, POSTFIX),
+            if expr.is_optional { "?." } else { "." },
+            expr.getter.lexeme
+         ),
+         OptionalChain(expr) => self.expr_text(&expr.chain),
+         NonNullAssertion(expr) => {
+            // The assertion applies to the whole optional chain before it (e.g., `a?.b!!`).
+            let operand = match &*expr.operand {
+               OptionalChain(_) => self.expr_text(&expr.operand),
+               _ => self.expr(&expr.operand, POSTFIX),
+            };
+
+            format!("{}!!", operand)
+         }
+         ObjectSetter(expr) => format!(
+            "{}.{} {} {}",
+            self.expr(&expr.target, POSTFIX),
+            expr.setter.lexeme,
+            reassignment_operator(&expr.opr_type),
+            self.expr(&expr.value, ASSIGNMENT)
+         ),
+         Subscript(expr) => format!(
+            "{}[{}]",
+            self.expr(&expr.target, POSTFIX),
+            self.expr(&expr.index, ASSIGNMENT)
+         ),
+         SubscriptAssignment(expr) => format!(
+            "{}[{}] {} {}",
+            self.expr(&expr.target, POSTFIX),
+            self.expr(&expr.index, ASSIGNMENT),
+            reassignment_operator(&expr.opr_type),
+            self.expr(&expr.value, ASSIGNMENT)
+         ),
+         Slice(expr) => {
+            let mut bound = |node: &Option<Box<ASTNode>>| match node {
+               Some(n) => self.expr(n, SHIFT),
+               None => String::new(),
+            };
+
+            let (start, end, step) = (bound(&expr.start), bound(&expr.end), bound(&expr.step));
+            let target = self.expr(&expr.target, POSTFIX);
+
+            match step.is_empty() {
+               true => format!("{}[{}..{}]", target, start, end),
+               false => format!("{}[{}..{}..{}]", target, start, end, step),
+            }
+         }
+         FunctionCall(call) => self.function_call(call),
+         Instance(call) => format!("new {}", self.function_call(call)),
+         Lambda(decl) if matches!(decl.name.token_type, TokenType::BIT_OR | TokenType::LOGIC_OR) => {
+            let params: Vec<&str> = decl.params.iter().map(|p| p.name.lexeme.as_ref()).collect();
+            let value = match &decl.body[..] {
+               [ReturnStmt(ReturnStmtNode {
+                  value: Some(value), ..
+               })] => self.expr(value, ASSIGNMENT),
+               _ => String::new(),
+            };
+
+            format!("|{}| {}", params.join(", "), value)
+         }
+         Lambda(decl) if matches!(decl.name.token_type, TokenType::L_CURLY) => self.trailing_closure(decl),
+         Lambda(decl) => {
+            let params: Vec<String> = decl.params.iter().map(|p| self.parameter(p)).collect();
+            let header = format!(
+               "{}fn{}{} ",
+               if decl.is_async { "async " } else { "" },
+               self.list(self.column + "fn".len(), "(", params, ")"),
+               type_annotation(&decl.return_type, " -> ")
+            );
+
+            // The body of the lambda is printed into a separate buffer, since it may span
+            // multiple lines, which are indented relative to the line of the lambda.
+            let (line, column) = (std::mem::replace(&mut self.out, header), self.column);
+            self.body(&decl.body, self.body_end(decl.name.span.start, &decl.body));
+            self.column = column;
+            std::mem::replace(&mut self.out, line)
+         }
+         LoopExpr(expr) => {
+            let (line, column) = (std::mem::take(&mut self.out), self.column);
+            self.while_statement(expr);
+            self.column = column;
+            std::mem::replace(&mut self.out, line)
+         }
+         // Statements are never found inside an expression.
+         _ => String::new(),
+      }
+   }
+
+   /// Formats a list of expressions.
+   fn expr_list(&mut self, nodes: &[ASTNode]) -> Vec<String> {
+      nodes.iter().map(|n| self.expr(n, ASSIGNMENT)).collect()
+   }
+
+   /// Formats a function call (or the call after the `new` keyword of a class instance).
+   fn function_call(&mut self, call: &FunctionCallExprNode) -> String {
+      let target = self.expr(&call.target, POSTFIX);
+
+      // A trailing closure is written after the parenthesis of the other arguments, which
+      // are omitted if there are no other arguments (e.g., `list.each { print(it); }`).
+      let (args, closure) = match call.args.split_last() {
+         Some((last, rest)) if is_trailing_closure(&last.value) => (rest, Some(&last.value)),
+         _ => (&call.args[..], None),
+      };
+
+      let args: Vec<String> = args
+         .iter()
+         .map(|arg| {
+            let value = self.expr(&arg.value, ASSIGNMENT);
+
+            match &arg.name {
+               Some(name) => format!("{}: {}", name.lexeme, value),
+               None if arg.is_spread => format!("...{}", value),
+               None => value,
+            }
+         })
+         .collect();
+
+      match closure {
+         Some(closure) if args.is_empty() => format!("{} {}", target, self.expr(closure, ASSIGNMENT)),
+         Some(closure) => format!(
+            "{}{} {}",
+            target,
+            self.list(self.column + target.chars().count(), "(", args, ")"),
+            self.expr(closure, ASSIGNMENT)
+         ),
+         None => format!(
+            "{}{}",
+            target,
+            self.list(self.column + target.chars().count(), "(", args, ")")
+         ),
+      }
+   }
+
+   /// Formats a trailing closure, whose body is printed on the same line when it is a single
+   /// expression (e.g., `{ it * 2 }`).
+   fn trailing_closure(&mut self, decl: &FunctionDeclNode) -> String {
+      let end = self.body_end(decl.name.span.start, &decl.body);
+
+      match &decl.body[..] {
+         [ExpressionStmt(stmt)] if !end.is_some_and(|e| self.has_comments_before(e)) => {
+            format!("{{ {} }}", self.statement_expr(&stmt.child))
+         }
+         _ => {
+            let line = std::mem::take(&mut self.out);
+            self.body(&decl.body, end);
+            std::mem::replace(&mut self.out, line)
+         }
+      }
+   }
+
+   /// Formats an interpolated string, keeping the text of its segments as they were written.
+   fn string_interpolation(&mut self, expr: &StringInterpolationNode) -> String {
+      let quote = &expr.token.lexeme[..1];
+      let mut text = String::from(quote);
+
+      for part in expr.parts.iter() {
+         match part {
+            Literal(lit) if !matches!(lit.token.token_type, TokenType::STRING) => {
+               // The text of a segment is between the opening quote (or the closing
+               // brace of an interpolation) and the closing quote (or the next `${`).
+               let lexeme = &lit.token.lexeme;
+               let end = match lit.token.token_type {
+                  TokenType::INTERPOLATION_END => lexeme.len() - 1,
+                  _ => lexeme.len() - 2,
+               };
+
+               text.push_str(&lexeme[1..end]);
+            }
+            _ => {
+               let value = self.expr(part, ASSIGNMENT);
+               text.push_str(&format!("${{{}}}", value));
+            }
+         }
+      }
+
+      text.push_str(quote);
+      text
+   }
+
+   /// Formats a list of items separated by commas. If the list does not fit in the current line,
+   /// the list is wrapped, with one item per line.
+   ///
+   /// # Parameters
+   /// - `column`: The column at which the list starts in its line.
+   /// - `open`: The text before the first item (e.g., `[`).
+   /// - `items`: The formatted items.
+   /// - `close`: The text after the last item (e.g., `]`).
+   fn list(&self, column: usize, open: &str, items: Vec<String>, close: &str) -> String {
+      let flat = format!("{}{}{}", open, items.join(", "), close);
+      let width = column + flat.chars().count();
+
+      if width <= MAX_WIDTH || flat.contains('\n') || items.len() < 2 {
+         flat
+      } else {
+         self.wrapped_list(open.trim_end(), items, close.trim_start())
+      }
+   }
+
+   /// Formats a list of items separated by commas, with one item per line.
+   fn wrapped_list(&self, open: &str, items: Vec<String>, close: &str) -> String {
+      let indent = INDENT.repeat(self.depth + 1);
+      let items: Vec<String> = items.iter().map(|item| format!("{}{}", indent, item)).collect();
+
+      format!("{}\n{}\n{}{}", open, items.join(",\n"), self.indent(), close)
+   }
+}
+
+/// Formats a type annotation, if there is one.
+///
+/// # Parameters
+/// - `annotation`: The type annotation.
+/// - `separator`: The text before the type's name (e.g., `: `).
+fn type_annotation(annotation: &Option<TypeAnnotation>, separator: &str) -> String {
+   match annotation {
+      Some(a) => format!("{}{}", separator, a.name.lexeme),
+      None => String::new(),
+   }
+}
+
+/// Formats the pattern of a `match` arm.
+fn match_pattern(pattern: &MatchPattern) -> String {
+   // The negation of numeric literals is folded into the value
+   // of the pattern, but not into the lexeme of its token.
+   let literal = |lit: &LiteralExprNode| {
+      let is_negative = match lit.value {
+         Object::Int(i) => i < 0,
+         Object::Float(f) => f < 0.0,
+         Object::BigInt(ref b) => b.to_string().starts_with('-'),
+         _ => false,
+      };
+
+      if is_negative && !lit.token.lexeme.starts_with('-') {
+         format!("-{}", lit.token.lexeme)
+      } else {
+         lit.token.lexeme.to_string()
+      }
+   };
+
+   match pattern {
+      MatchPattern::Literal(lit) => literal(lit),
+      MatchPattern::Range(start, end) => format!("{}..{}", literal(start), literal(end)),
+      MatchPattern::Wildcard(token) | MatchPattern::Binding(token) => token.lexeme.to_string(),
+      MatchPattern::Variant {
+         enum_name,
+         variant,
+         fields,
+      } => match fields {
+         Some(fields) => {
+            let fields: Vec<String> = fields.iter().map(match_pattern).collect();
+            format!("{}.{}({})", enum_name.lexeme, variant.lexeme, fields.join(", "))
+         }
+         None => format!("{}.{}", enum_name.lexeme, variant.lexeme),
+      },
+      MatchPattern::Array(_, items) => {
+         let items: Vec<String> = items.iter().map(match_pattern).collect();
+         format!("[{}]", items.join(", "))
+      }
+      MatchPattern::Dictionary(_, entries) => {
+         let entries: Vec<String> = entries
+            .iter()
+            .map(|(key, value)| match value {
+               // A key without a pattern binds a variable with the same name.
+               MatchPattern::Binding(name) if name.span == key.span => key.lexeme.to_string(),
+               _ => format!("{}: {}", key.lexeme, match_pattern(value)),
+            })
+            .collect();
+
+         format!("{{{}}}", entries.join(", "))
+      }
+   }
+}
+
+/// Gets the operator of an assignment.
+fn reassignment_operator(opr_type: &ReassignmentType) -> &'static str {
+   match opr_type {
+      ReassignmentType::Plus => "+=",
+      ReassignmentType::Minus => "-=",
+      ReassignmentType::Mul => "*=",
+      ReassignmentType::Div => "/=",
+      ReassignmentType::FloorDiv => "~/=",
+      ReassignmentType::Expo => "**=",
+      ReassignmentType::Mod => "%=",
+      ReassignmentType::ShiftL => "<<=",
+      ReassignmentType::ShiftR => ">>=",
+      ReassignmentType::BitAnd => "&=",
+      ReassignmentType::Xor => "^=",
+      ReassignmentType::BitOr => "|=",
+      ReassignmentType::Assign => "=",
+   }
+}
+
+/// Gets the precedence of a binary operator.
+fn binary_precedence(opr_type: &BinaryExprType) -> u8 {
+   match opr_type {
+      BinaryExprType::Nullish => NULLISH,
+      BinaryExprType::LogicOR => LOGIC_OR,
+      BinaryExprType::LogicAND => LOGIC_AND,
+      BinaryExprType::BitwiseOR => BITWISE_OR,
+      BinaryExprType::BitwiseXOR => BITWISE_XOR,
+      BinaryExprType::BitwiseAND => BITWISE_AND,
+      BinaryExprType::LogicEQ | BinaryExprType::LogicNotEQ => EQUALITY,
+      BinaryExprType::LogicLessThan
+      | BinaryExprType::LogicLessThanEQ
+      | BinaryExprType::LogicGreaterThan
+      | BinaryExprType::LogicGreaterThanEQ
+      | BinaryExprType::LogicIs
+      | BinaryExprType::LogicIn => COMPARISON,
+      BinaryExprType::Range | BinaryExprType::RangeInclusive => RANGE,
+      BinaryExprType::BitwiseShiftLeft | BinaryExprType::BitwiseShiftRight => SHIFT,
+      BinaryExprType::Addition | BinaryExprType::Minus => TERM,
+      BinaryExprType::Multiplication
+      | BinaryExprType::Division
+      | BinaryExprType::FloorDivision
+      | BinaryExprType::Modulus => FACTOR,
+      BinaryExprType::Expo => EXPO,
+   }
+}
+
+/// Checks if a binary operator is an ordering comparison, which can be chained.
+fn is_ordering(opr_type: &BinaryExprType) -> bool {
+   matches!(
+      opr_type,
+      BinaryExprType::LogicLessThan
+         | BinaryExprType::LogicLessThanEQ
+         | BinaryExprType::LogicGreaterThan
+         | BinaryExprType::LogicGreaterThanEQ
+   )
+}
+
+/// Gets the precedence of an expression.
+fn precedence(node: &ASTNode) -> u8 {
+   match node {
+      VarReassignment(_) | ObjectSetter(_) | SubscriptAssignment(_) => ASSIGNMENT,
+      TernaryConditional(expr) if !expr.is_if_expression => TERNARY,
+      Binary(expr) => binary_precedence(&expr.opr_type),
+      ComparisonChain(_) => COMPARISON,
+      // The logic negation applies to the whole expression after it (e.g., `!a == b`
+      // is `!(a == b)`), so it must be wrapped in parenthesis when it is an operand.
+      Unary(expr) if matches!(expr.opr_type, UnaryExprType::LogicNeg) => ASSIGNMENT,
+      // The body of a shorthand lambda extends as far to the right as possible.
+      Lambda(decl) if matches!(decl.name.token_type, TokenType::BIT_OR | TokenType::LOGIC_OR) => ASSIGNMENT,
+      // An optional chain is wrapped in parenthesis when it is the target of a property access,
+      // a call, or a subscript, which would otherwise be part of the chain.
+      Unary(_) | Instance(_) | OptionalChain(_) => UNARY,
+      FunctionCall(_) | ObjectGetter(_) | Subscript(_) | Slice(_) | NonNullAssertion(_) => POSTFIX,
+      _ => PRIMARY,
+   }
+}
+
+/// Checks if an expression is a trailing closure (e.g., the `{ it * 2 }` of `list.map { it * 2 }`).
+fn is_trailing_closure(node: &ASTNode) -> bool {
+   matches!(node, Lambda(decl) if matches!(decl.name.token_type, TokenType::L_CURLY))
+}
+
+/// Checks if any of the calls in an expression, outside of parenthesis or brackets, has a
+/// trailing closure.
+fn has_trailing_closure(node: &ASTNode) -> bool {
+   match node {
+      FunctionCall(call) => {
+         call.args.last().is_some_and(|a| is_trailing_closure(&a.value)) || has_trailing_closure(&call.target)
+      }
+      Instance(call) => has_trailing_closure(&call.target),
+      Binary(expr) => has_trailing_closure(&expr.left) || has_trailing_closure(&expr.right),
+      ComparisonChain(expr) => expr.operands.iter().any(has_trailing_closure),
+      TernaryConditional(expr) => {
+         has_trailing_closure(&expr.condition)
+            || has_trailing_closure(&expr.branch_true)
+            || has_trailing_closure(&expr.branch_false)
+      }
+      Unary(expr) => has_trailing_closure(&expr.operand),
+      VarReassignment(expr) => has_trailing_closure(&expr.value),
+      ObjectSetter(expr) => has_trailing_closure(&expr.target) || has_trailing_closure(&expr.value),
+      SubscriptAssignment(expr) => has_trailing_closure(&expr.target) || has_trailing_closure(&expr.value),
+      ObjectGetter(expr) => has_trailing_closure(&expr.target),
+      OptionalChain(expr) => has_trailing_closure(&expr.chain),
+      NonNullAssertion(expr) => has_trailing_closure(&expr.operand),
+      Subscript(expr) => has_trailing_closure(&expr.target),
+      Slice(expr) => has_trailing_closure(&expr.target),
+      _ => false,
+   }
+}
+
+/// Gets the expression whose code comes first in an expression (e.g., the `a` of `a.b + c`).
+fn leftmost<'a>(node: &'a ASTNode<'a>) -> &'a ASTNode<'a> {
+   match node {
+      Binary(expr) => leftmost(&expr.left),
+      ComparisonChain(expr) => leftmost(&expr.operands[0]),
+      TernaryConditional(expr) if !expr.is_if_expression => leftmost(&expr.condition),
+      FunctionCall(call) => leftmost(&call.target),
+      ObjectGetter(expr) => leftmost(&expr.target),
+      ObjectSetter(expr) => leftmost(&expr.target),
+      OptionalChain(expr) => leftmost(&expr.chain),
+      NonNullAssertion(expr) => leftmost(&expr.operand),
+      Subscript(expr) => leftmost(&expr.target),
+      SubscriptAssignment(expr) => leftmost(&expr.target),
+      Slice(expr) => leftmost(&expr.target),
+      _ => node,
+   }
+}
+
+/// Gets the byte offset in the source code of the first token stored in a node, which may be
+/// after the first token of its code (e.g., the name of a variable after the `var` keyword).
+fn start_of(node: &ASTNode) -> usize {
+   match node {
+      Module(_) => 0,
+      Literal(lit) => lit.token.span.start,
+      Identifier(id) => id.token.span.start,
+      Array(expr) => expr.token.span.start,
+      Tuple(expr) => expr.token.span.start,
+      Dictionary(expr) => expr.token.span.start,
+      Comprehension(expr) => expr.token.span.start,
+      StringInterpolation(expr) => expr.token.span.start,
+      SelfExpr(expr) => expr.token.span.start,
+      SuperExpr(expr) => expr.token.span.start,
+      Lambda(decl) => decl.name.span.start,
+      LoopExpr(expr) => expr.label.as_ref().unwrap_or(&expr.token).span.start,
+      Binary(expr) => start_of(&expr.left),
+      ComparisonChain(expr) => start_of(&expr.operands[0]),
+      TernaryConditional(expr) => start_of(&expr.condition),
+      Unary(expr) => start_of(&expr.operand),
+      FunctionCall(call) | Instance(call) => start_of(&call.target),
+      ObjectGetter(expr) => start_of(&expr.target),
+      ObjectSetter(expr) => start_of(&expr.target),
+      OptionalChain(expr) => start_of(&expr.chain),
+      NonNullAssertion(expr) => start_of(&expr.operand),
+      Subscript(expr) => start_of(&expr.target),
+      SubscriptAssignment(expr) => start_of(&expr.target),
+      Slice(expr) => start_of(&expr.target),
+      VarReassignment(expr) => expr.target.span.start,
+      ClassDecl(decl) => decl.name.span.start,
+      ConstantDecl(decl) => decl.name.span.start,
+      ExportDecl(decl) => decl.token.span.start,
+      FunctionDecl(decl) => decl.name.span.start,
+      ImportDecl(decl) => decl.token.span.start,
+      InterfaceDecl(decl) => decl.name.span.start,
+      EnumDecl(decl) => decl.name.span.start,
+      VariableDecl(decl) => decl.identifiers[0].span.start,
+      AssertStmt(stmt) => stmt.token.span.start,
+      BlockStmt(block) => match block.body.first() {
+         Some(first) => start_of(first),
+         None => block.end_of_block.span.start,
+      },
+      DeferStmt(stmt) => stmt.token.span.start,
+      ExpressionStmt(stmt) => start_of(&stmt.child),
+      ForStmt(stmt) => stmt.label.as_ref().unwrap_or(&stmt.token).span.start,
+      IfStmt(stmt) => stmt.then_token.span.start,
+      LoopBranch(stmt) => stmt.token.span.start,
+      MatchStmt(stmt) => stmt.token.span.start,
+      ReturnStmt(stmt) => stmt.token.span.start,
+      TryStmt(stmt) => stmt.token.span.start,
+      WhileStmt(stmt) => stmt.label.as_ref().unwrap_or(&stmt.token).span.start,
+   }
+}
+
+/// Checks if a token ends an expression or a statement header, so that a `{` after it opens a
+/// body rather than a dictionary.
+fn ends_expression(token_type: &TokenType) -> bool {
+   use TokenType::*;
+
+   matches!(
+      token_type,
+      IDENTIFIER
+         | STRING
+         | CHAR_LITERAL
+         | INTERPOLATION_END
+         | INTEGER
+         | BIG_INTEGER
+         | FLOAT
+         | BINARY
+         | OCTAL
+         | HEXADECIMAL
+         | TRUE
+         | FALSE
+         | NULL
+         | SELF_KW
+         | R_PARENTHESIS
+         | R_BRACKET
+         | R_CURLY
+         | DEFER_KW
+         | TRY_KW
+         | CATCH_KW
+         | FINALLY_KW
+         | ELSE_KW
+   )
+}
+
+/// Gets the text printed before a loop for its label (e.g., `outer: `), if it has one.
+fn label_prefix(label: &Option<Token>) -> String {
+   match label {
+      Some(label) => format!("{}: ", label.lexeme),
+      None => String::new(),
+   }
+}
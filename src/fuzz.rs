@@ -0,0 +1,104 @@
+//! Entry points for fuzzing the interpreter. Each function takes arbitrary bytes and must
+//! return without panicking, no matter how malformed the input is: invalid programs and
+//! bytecode files are reported as errors, never as crashes. The `cargo-fuzz` targets in
+//! the `fuzz/` directory call these functions with the inputs generated by libFuzzer.
+
+use crate::built_in::BuiltIn;
+use crate::compiler::Compiler;
+use crate::core::serialization;
+use crate::core::tokens::TokenType;
+use crate::errors::print_errors_list;
+use crate::lexer::Lexer;
+use crate::parser::Parser;
+use crate::virtual_machine::{VmConfig, VM};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Scans the input into tokens, until the end of the input, checking that the span of each
+/// token is a valid range of the input.
+///
+/// # Parameters
+/// - `data`: The source text, which may not be valid UTF-8.
+pub fn fuzz_lexer(data: &[u8]) {
+   let source = String::from_utf8_lossy(data);
+   let mut lexer = Lexer::new(&source);
+
+   // Every call consumes at least one character, or returns the EOF token.
+   for _ in 0..=source.chars().count() {
+      let token = lexer.next_token();
+      assert!(
+         source.get(token.span.start..token.span.end).is_some(),
+         "Invalid token span."
+      );
+
+      if let TokenType::EOF = token.token_type {
+         return;
+      }
+   }
+
+   panic!("The lexer did not reach the end of the input.");
+}
+
+/// Parses and compiles the input, and renders the errors found in it.
+///
+/// # Parameters
+/// - `data`: The source text, which may not be valid UTF-8.
+pub fn fuzz_parser(data: &[u8]) {
+   let source = String::from_utf8_lossy(data);
+   let path = Path::new("<fuzz>");
+
+   let errors = match Parser::parse(&source) {
+      Ok(ast) => match Compiler::compile_ast(path, &ast, &BuiltIn::default()) {
+         Ok(_) => return,
+         Err(errors) => errors,
+      },
+      Err(errors) => errors,
+   };
+
+   let _ = print_errors_list(&mut io::sink(), path, &errors, &source);
+}
+
+/// Loads the input as a precompiled bytecode (`.hbc`) file. Since bytecode files have a
+/// checksum, a second attempt fixes the checksum of the input, so that the fuzzer can
+/// explore the contents of the file beyond its header.
+///
+/// # Parameters
+/// - `data`: The contents of the bytecode file.
+pub fn fuzz_bytecode(data: &[u8]) {
+   let _ = serialization::deserialize(data);
+   let _ = serialization::deserialize(&serialization::with_valid_checksum(data));
+}
+
+/// Compiles the input and runs the resulting bytecode in a VM with tight limits on its
+/// resources, and without access to the host system. The input is run twice: as a file
+/// (which reports its errors), and as a REPL input (which returns its errors).
+///
+/// # Parameters
+/// - `data`: The source text, which may not be valid UTF-8.
+pub fn fuzz_vm(data: &[u8]) {
+   let source = String::from_utf8_lossy(data);
+
+   sandboxed_vm().interpret(&source);
+   let _ = sandboxed_vm().eval(&source);
+}
+
+/// Creates a VM with tight limits on its resources and without access to the host system,
+/// whose programs have no input, and whose output and errors are discarded.
+fn sandboxed_vm() -> VM {
+   let config = VmConfig {
+      max_frames: 200,
+      max_stack_slots: Some(10_000),
+      max_heap_bytes: Some(16 * 1024 * 1024),
+      max_instructions: Some(100_000),
+      timeout: Some(Duration::from_secs(1)),
+      has_clock: true,
+   };
+
+   VM::new(PathBuf::from("<fuzz>"))
+      .with_config(config)
+      .without_system_access()
+      .with_input(Box::new(io::empty()))
+      .with_stdout(Box::new(io::sink()))
+      .with_stderr(Box::new(io::sink()))
+}
@@ -0,0 +1,209 @@
+//! Syntax highlighting of Hinton source code, as HTML or as text with ANSI escape sequences,
+//! which is printed by the `hinton highlight <file>` command.
+
+use crate::core::tokens::{Token, TokenType};
+use crate::lexer::Lexer;
+
+/// The formats of the highlighted source code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HighlightFormat {
+   /// An HTML `<pre>` element, where each token is wrapped in a `<span>` with the class of
+   /// its category (e.g., `<span class="ht-keyword">var</span>`), so that it can be styled
+   /// with CSS.
+   Html,
+   /// Text with ANSI escape sequences, which can be printed to the console.
+   Ansi,
+}
+
+impl HighlightFormat {
+   /// Gets the highlighting format from its name (`html` or `ansi`).
+   ///
+   /// # Parameters
+   /// - `name`: The name of the format.
+   pub fn from_name(name: &str) -> Option<Self> {
+      match name.to_lowercase().as_str() {
+         "html" => Some(HighlightFormat::Html),
+         "ansi" => Some(HighlightFormat::Ansi),
+         _ => None,
+      }
+   }
+}
+
+/// The categories of the tokens in a Hinton program, which are highlighted with different styles.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenCategory {
+   Keyword,
+   Literal,
+   String,
+   Operator,
+   Punctuation,
+   Identifier,
+   Comment,
+   Error,
+}
+
+impl TokenCategory {
+   /// Gets the category of a token type.
+   ///
+   /// # Parameters
+   /// - `token_type`: The type of the token.
+   pub fn of(token_type: &TokenType) -> Self {
+      use TokenType::*;
+
+      match token_type {
+         STRING | CHAR_LITERAL | INTERPOLATION_START | INTERPOLATION_MID | INTERPOLATION_END => {
+            TokenCategory::String
+         }
+         INTEGER | BIG_INTEGER | FLOAT | BINARY | OCTAL | HEXADECIMAL | TRUE | FALSE | NULL => {
+            TokenCategory::Literal
+         }
+         IDENTIFIER => TokenCategory::Identifier,
+         COMMA | DOT | SEMICOLON | COLON | L_PAREN | R_PARENTHESIS | L_BRACKET | R_BRACKET | L_CURLY
+         | R_CURLY => TokenCategory::Punctuation,
+         COMMENT => TokenCategory::Comment,
+         ERROR => TokenCategory::Error,
+         // Keyword operators (e.g., `and`, `equals`) are highlighted as keywords.
+         AS_OPERATOR | LOGIC_AND | LOGIC_EQ | LOGIC_IS | LOGIC_OR | ASSERT_KW | ASYNC_KW | AWAIT_KW
+         | BREAK_KW | CATCH_KW | CLASS_KW | CONST_KW | CONTINUE_KW | DEFER_KW | DO_KW | ELSE_KW | ENUM_KW
+         | EXPORT_KW | EXTENDS_KW | FINALLY_KW | FN_LAMBDA_KW | FOR_KW | FROM_KW | FUNC_KW | IF_KW
+         | IMPLEMENTS_KW | IMPORT_KW | INTERFACE_KW | IN_KW | LOOP_KW | MATCH_KW | NEW_KW | OVERRIDE_KW
+         | PRIVATE_KW | PUBLIC_KW | RETURN_KW | SELF_KW | STATIC_KW | SUPER_KW | TRY_KW | VAR_KW
+         | WHILE_KW => TokenCategory::Keyword,
+         _ => TokenCategory::Operator,
+      }
+   }
+
+   /// The CSS class of the category in highlighted HTML.
+   pub fn css_class(&self) -> &'static str {
+      match self {
+         TokenCategory::Keyword => "ht-keyword",
+         TokenCategory::Literal => "ht-literal",
+         TokenCategory::String => "ht-string",
+         TokenCategory::Operator => "ht-operator",
+         TokenCategory::Punctuation => "ht-punctuation",
+         TokenCategory::Identifier => "ht-identifier",
+         TokenCategory::Comment => "ht-comment",
+         TokenCategory::Error => "ht-error",
+      }
+   }
+
+   /// The ANSI escape sequence that starts the style of the category, if the category is styled.
+   pub fn ansi_style(&self) -> Option<&'static str> {
+      match self {
+         TokenCategory::Keyword => Some("\x1b[38;5;204m"),
+         TokenCategory::Literal => Some("\x1b[38;5;141m"),
+         TokenCategory::String => Some("\x1b[38;5;186m"),
+         TokenCategory::Operator => Some("\x1b[38;5;81m"),
+         TokenCategory::Comment => Some("\x1b[38;5;244m"),
+         TokenCategory::Error => Some("\x1b[31m"),
+         TokenCategory::Punctuation | TokenCategory::Identifier => None,
+      }
+   }
+}
+
+/// Splits the source code into fragments, each with the category used to highlight it. The
+/// fragments cover the whole source, so joining their text gives back the source. The
+/// whitespace between tokens is not styled.
+///
+/// # Parameters
+/// - `source`: The source code.
+///
+/// # Returns
+/// - `Vec<(Option<TokenCategory>, &str)>`: The fragments of the source, in order.
+pub fn fragments(source: &str) -> Vec<(Option<TokenCategory>, &str)> {
+   let mut fragments = vec![];
+   let mut last_end = 0;
+
+   for token in Lexer::new(source).with_comments().tokens() {
+      if token.span.start > last_end {
+         fragments.push((None, &source[last_end..token.span.start]));
+      }
+
+      fragments.push((
+         Some(category_of(&token)),
+         &source[token.span.start..token.span.end],
+      ));
+      last_end = token.span.end;
+   }
+
+   if last_end < source.len() {
+      fragments.push((None, &source[last_end..]));
+   }
+
+   fragments
+}
+
+/// Gets the category of a token. Unterminated strings are scanned as errors, but are
+/// highlighted as strings, so that a source being edited is still highlighted nicely.
+fn category_of(token: &Token) -> TokenCategory {
+   match token.token_type {
+      TokenType::ERROR if token.lexeme == "Unterminated string." => TokenCategory::String,
+      _ => TokenCategory::of(&token.token_type),
+   }
+}
+
+/// Highlights the source code in the given format.
+///
+/// # Parameters
+/// - `source`: The source code.
+/// - `format`: The format of the highlighted code.
+pub fn highlight(source: &str, format: HighlightFormat) -> String {
+   match format {
+      HighlightFormat::Html => to_html(source),
+      HighlightFormat::Ansi => to_ansi(source),
+   }
+}
+
+/// Highlights the source code as an HTML `<pre>` element.
+fn to_html(source: &str) -> String {
+   let mut html = String::from("<pre class=\"hinton\"><code>");
+
+   for (category, text) in fragments(source) {
+      match category {
+         Some(c) => html.push_str(&format!(
+            "<span class=\"{}\">{}</span>",
+            c.css_class(),
+            escape_html(text)
+         )),
+         None => html.push_str(&escape_html(text)),
+      }
+   }
+
+   html.push_str("</code></pre>\n");
+   html
+}
+
+/// Highlights the source code as text with ANSI escape sequences.
+fn to_ansi(source: &str) -> String {
+   let mut text = String::with_capacity(source.len());
+
+   for (category, fragment) in fragments(source) {
+      match category.and_then(|c| c.ansi_style()) {
+         Some(style) => text.push_str(&format!("{}{}\x1b[0m", style, fragment)),
+         None => text.push_str(fragment),
+      }
+   }
+
+   text
+}
+
+/// Escapes the characters of a text that have a special meaning in HTML.
+///
+/// # Parameters
+/// - `text`: The text to escape.
+pub fn escape_html(text: &str) -> String {
+   let mut escaped = String::with_capacity(text.len());
+
+   for c in text.chars() {
+      match c {
+         '<' => escaped.push_str("&lt;"),
+         '>' => escaped.push_str("&gt;"),
+         '&' => escaped.push_str("&amp;"),
+         '"' => escaped.push_str("&quot;"),
+         '\'' => escaped.push_str("&#39;"),
+         _ => escaped.push(c),
+      }
+   }
+
+   escaped
+}
@@ -2,22 +2,48 @@ use crate::core::tokens::Token;
 use crate::core::tokens::TokenType::*;
 use crate::lexer::Lexer;
 
-impl Lexer {
+impl<'a> Lexer<'a> {
    /// Scans the next token in the source file.
-   pub fn next_token(&mut self) -> Token {
+   pub fn next_token(&mut self) -> Token<'a> {
       self.skip_whitespace();
 
+      // Reset the start of the token
+      self.token_start = self.current;
+
       // If we are at the end, return the EOF token.
       if self.is_at_end() {
          return self.make_token(EOF);
       }
 
-      // Reset the start of the token
-      self.token_start = self.current;
+      // Block comments are only found here if they are not closed before the end of the source.
+      if !self.emit_comments && self.get_current() == '/' && self.next() == '*' {
+         return self.make_unterminated_comment_token();
+      }
+
+      // Generates a doc comment token if the lexer was created `with_doc_comments()`.
+      if self.emit_doc_comments && self.is_at_doc_comment() {
+         return self.make_doc_comment_token();
+      }
+
       let c = self.advance();
 
-      // Generates an identifier/keyword if the current character is alphanumeric
-      if c.is_alphabetic() {
+      // Generates a comment token if the lexer was created `with_comments()`.
+      if c == '/' && self.emit_comments && matches!(self.get_current(), '/' | '*') {
+         return self.make_comment_token();
+      }
+
+      // Generates a raw string literal if the current character is an `r` followed by a quote
+      if c == 'r' && (self.get_current() == '"' || self.get_current() == '\'') {
+         return self.make_raw_string_token();
+      }
+
+      // Generates a char literal if the current character is a `c` followed by a single quote
+      if c == 'c' && self.get_current() == '\'' {
+         return self.make_char_token();
+      }
+
+      // Generates an identifier/keyword if the current character is alphabetic or an underscore
+      if c.is_alphabetic() || c == '_' {
          return self.make_identifier_token();
       }
 
@@ -31,13 +57,25 @@ impl Lexer {
          '"' | '\'' => self.make_string_token(),
          '(' => self.make_token(L_PAREN),
          ')' => self.make_token(R_PARENTHESIS),
-         '{' => self.make_token(L_CURLY),
-         '}' => self.make_token(R_CURLY),
+         '{' => self.make_l_curly_token(),
+         '}' => self.make_r_curly_token(),
          '[' => self.make_token(L_BRACKET),
          ']' => self.make_token(R_BRACKET),
          ';' => self.make_token(SEMICOLON),
          ',' => self.make_token(COMMA),
-         '~' => self.make_token(BIT_NOT),
+         '~' => {
+            // The floor division operator is `~/`, since `//` starts a comment.
+            let tok = if self.matches('/') {
+               if self.matches('=') {
+                  FLOOR_DIV_EQ
+               } else {
+                  FLOOR_DIV
+               }
+            } else {
+               BIT_NOT
+            };
+            self.make_token(tok)
+         }
          '/' => {
             let tok = if self.matches('=') { SLASH_EQ } else { SLASH };
             self.make_token(tok)
@@ -49,13 +87,21 @@ impl Lexer {
          '!' => {
             let tok = if self.matches('=') {
                LOGIC_NOT_EQ
+            } else if self.matches('!') {
+               NON_NULL_ASSERT
             } else {
                LOGIC_NOT
             };
             self.make_token(tok)
          }
          '=' => {
-            let tok = if self.matches('=') { LOGIC_EQ } else { EQUALS };
+            let tok = if self.matches('=') {
+               LOGIC_EQ
+            } else if self.matches('>') {
+               FAT_ARROW
+            } else {
+               EQUALS
+            };
             self.make_token(tok)
          }
          ':' => {
@@ -89,6 +135,9 @@ impl Lexer {
          '?' => {
             if self.matches('?') {
                self.make_token(NULLISH)
+            } else if self.get_current() == '.' && !self.next().is_ascii_digit() && self.matches('.') {
+               // A dot followed by a digit is a float in the branch of a ternary (e.g., `a ?.5 : 1`).
+               self.make_token(OPTIONAL_CHAIN)
             } else {
                self.make_token(QUESTION)
             }
@@ -97,7 +146,14 @@ impl Lexer {
             if self.get_current().is_digit(10) {
                self.make_numeric_token()
             } else if self.matches('.') {
-               self.make_token(RANGE_OPR)
+               // Four dots are two range operators, as in slices with an omitted end (`arr[....-1]`).
+               if self.get_current() == '.' && self.next() != '.' && self.matches('.') {
+                  self.make_token(ELLIPSIS)
+               } else if self.matches('=') {
+                  self.make_token(RANGE_INCLUSIVE_OPR)
+               } else {
+                  self.make_token(RANGE_OPR)
+               }
             } else {
                self.make_token(DOT)
             }
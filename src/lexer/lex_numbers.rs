@@ -1,65 +1,165 @@
-use crate::core::tokens::Token;
 use crate::core::tokens::TokenType::*;
+use crate::core::tokens::{Span, Token};
 use crate::lexer::Lexer;
+use std::borrow::Cow;
 
-impl Lexer {
+impl<'a> Lexer<'a> {
    /// Makes a numeric literal. This includes Binary, Octal, Decimal,
-   /// Floating-Point, and Hexadecimal numbers.
+   /// Floating-Point, Hexadecimal, and BigInt numbers. The digits of a
+   /// literal may be separated by underscores (e.g., `1_000_000`), and
+   /// floats may have an exponent (e.g., `1.5e-3`).
    ///
    /// # Returns
-   /// - `Token`: A numeric token (integer, float, binary, octal, or hex).
-   pub(super) fn make_numeric_token(&mut self) -> Token {
-      // Support for hexadecimal integers
-      // Hexadecimal literals are converted to integer literals during compilation
-      if self.get_previous() == '0' && (self.get_current() == 'x' || self.get_current() == 'X') {
-         self.advance(); // consumes the "x"
-         self.advance_numeric_digit(16); // Consume digit character in base-16
-         return self.make_token(HEXADECIMAL);
-      }
+   /// - `Token`: A numeric token (integer, float, binary, octal, hex, or BigInt), or an error
+   ///   token that points at the first invalid character of a malformed literal.
+   pub(super) fn make_numeric_token(&mut self) -> Token<'a> {
+      // Binary, octal, and hexadecimal literals are converted to integer literals during compilation.
+      let prefixed = match (self.get_previous(), self.get_current()) {
+         ('0', 'x' | 'X') => Some((16, HEXADECIMAL, "hexadecimal")),
+         ('0', 'o' | 'O') => Some((8, OCTAL, "octal")),
+         ('0', 'b' | 'B') => Some((2, BINARY, "binary")),
+         _ => None,
+      };
 
-      // Support for octal integers
-      // Octal literals are converted to integer literals during compilation
-      if self.get_previous() == '0' && (self.get_current() == 'o' || self.get_current() == 'O') {
-         self.advance(); // consumes the 'o'
-         self.advance_numeric_digit(8); // Consume digit character in base-8
-         return self.make_token(OCTAL);
-      }
+      if let Some((radix, token_type, name)) = prefixed {
+         self.advance(); // consumes the "x", "o", or "b"
+
+         if !self.get_current().is_digit(radix) {
+            let message = format!("Expected {} digits after '{}'.", name, self.lexeme_so_far());
+            return self.numeric_error(message, self.current);
+         }
+
+         if let Err(e) = self.advance_numeric_digits(radix) {
+            return e;
+         }
 
-      // Support for binary integers
-      // Binary literals are converted to integer literals during compilation
-      if self.get_previous() == '0' && (self.get_current() == 'b' || self.get_current() == 'B') {
-         self.advance(); // consumes the 'b'
-         self.advance_numeric_digit(2); // Consume digit character in base-2
-         return self.make_token(BINARY);
+         return match self.get_current() {
+            c if is_identifier_char(c) => self.numeric_error(
+               format!("Invalid digit '{}' in {} literal.", c, name),
+               self.current,
+            ),
+            _ => self.make_token(token_type),
+         };
       }
 
       // Checks whether the numeric token started with a dot (to correctly mark it as a float).
       let started_with_dot = self.get_previous() == '.';
-      self.advance_numeric_digit(10); // Consume digit character in base-10
+      let mut is_float = started_with_dot;
+
+      if let Err(e) = self.advance_numeric_digits(10) {
+         return e;
+      }
 
       // Look for a fractional part (only for floats that do not start with a dot).
-      if !started_with_dot && self.get_current() == '.' && self.next().is_digit(10) {
+      if !started_with_dot && self.get_current() == '.' && self.next().is_ascii_digit() {
          self.advance(); // Consume the ".".
-         self.advance_numeric_digit(10); // Consume digit character in base-10
-         return self.make_token(FLOAT);
+         is_float = true;
+
+         if let Err(e) = self.advance_numeric_digits(10) {
+            return e;
+         }
       }
 
-      if started_with_dot {
-         self.make_token(FLOAT)
-      } else {
-         self.make_token(INTEGER)
+      // Look for an exponent (e.g., `1e9`, `2.5E-3`).
+      if matches!(self.get_current(), 'e' | 'E') {
+         self.advance(); // Consume the "e".
+         is_float = true;
+
+         if matches!(self.get_current(), '+' | '-') {
+            self.advance();
+         }
+
+         if !self.get_current().is_ascii_digit() {
+            return self.numeric_error(
+               "Expected digits in the exponent of the numeric literal.",
+               self.current,
+            );
+         }
+
+         if let Err(e) = self.advance_numeric_digits(10) {
+            return e;
+         }
+      }
+
+      // Support for BigInt literals, which are decimal integers with an `n` suffix (e.g., `123n`).
+      if !is_float && self.get_current() == 'n' && !is_identifier_char(self.next()) {
+         self.advance(); // consumes the 'n'
+         return self.make_token(BIG_INTEGER);
+      }
+
+      match self.get_current() {
+         c if is_identifier_char(c) => self.numeric_error(
+            format!("Invalid character '{}' in numeric literal.", c),
+            self.current,
+         ),
+         _ if is_float => self.make_token(FLOAT),
+         _ => self.make_token(INTEGER),
       }
    }
 
-   /// Consumes digit characters of the given radix base.
+   /// Consumes digit characters of the given radix base, along with the underscores that
+   /// separate them. An underscore must be between two digits.
    ///
    /// # Arguments
    /// - `radix`: The base of the expected digit.
-   fn advance_numeric_digit(&mut self, radix: u32) {
-      while !self.is_at_end() && self.get_current().is_digit(radix)
-         || (self.get_current() == '_' && self.get_previous() != '_')
+   ///
+   /// # Returns
+   /// - `Result<(), Token<'a>>`: An error token if an underscore is not between two digits.
+   fn advance_numeric_digits(&mut self, radix: u32) -> Result<(), Token<'a>> {
+      loop {
+         let c = self.get_current();
+
+         if c == '_' {
+            if !self.get_previous().is_digit(radix) || !self.next().is_digit(radix) {
+               let message = "A '_' in a numeric literal must be between two digits.";
+               return Err(self.numeric_error(message, self.current));
+            }
+         } else if self.is_at_end() || !c.is_digit(radix) {
+            return Ok(());
+         }
+
+         self.advance();
+      }
+   }
+
+   /// Gets the characters of the current token that have been consumed so far.
+   fn lexeme_so_far(&self) -> &'a str {
+      self.source_text(self.token_start, self.current)
+   }
+
+   /// Generates an error token for a malformed numeric literal. The rest of the literal is
+   /// consumed so that it is not scanned as other tokens, but the error token points at the
+   /// character that made the literal invalid.
+   ///
+   /// # Parameters
+   /// - `message`: The error message.
+   /// - `at`: The index of the invalid character in the source.
+   fn numeric_error(&mut self, message: impl Into<Cow<'a, str>>, at: usize) -> Token<'a> {
+      while !self.is_at_end()
+         && (is_identifier_char(self.get_current())
+            || self.get_current() == '.' && self.next().is_ascii_digit())
       {
          self.advance();
       }
+
+      let byte_offset = |index: usize| self.byte_offsets[index.min(self.source.len())];
+      let column = at - self.line_start;
+
+      Token {
+         line_num: self.line_num,
+         column_start: column,
+         column_end: at + 1,
+         span_end: (self.line_num, column + 1),
+         span: Span {
+            start: byte_offset(at),
+            end: byte_offset(at + 1),
+         },
+         ..self.make_error_token(message)
+      }
    }
 }
+
+/// Checks if a character can be part of an identifier.
+fn is_identifier_char(c: char) -> bool {
+   c.is_alphanumeric() || c == '_'
+}
@@ -1,14 +1,20 @@
 use crate::core::tokens;
-use crate::core::tokens::{Token, TokenType};
+use crate::core::tokens::{Span, Token, TokenType};
+use std::borrow::Cow;
 
 // Submodules
 pub mod lex_next_token;
 pub mod lex_numbers;
 
 /// Struct that represents the scanner.
-pub struct Lexer {
+pub struct Lexer<'a> {
+   /// The source text, which the lexemes of the tokens are borrowed from.
+   text: &'a str,
    /// A flat list of characters from the source file.
    source: Vec<char>,
+   /// The byte offset in the source text of each character, followed by the length of the
+   /// source text, so that the span of a token can be computed from its character indices.
+   byte_offsets: Vec<usize>,
    /// The index of the current character.
    current: usize,
    /// The current line index.
@@ -19,26 +25,122 @@ pub struct Lexer {
    /// The position in the flat source vector of the first
    /// character for the current token.
    token_start: usize,
+   /// The string interpolations currently being scanned, where the last element represents
+   /// the inner-most interpolation. Each element holds the quote of the interpolated string,
+   /// and the number of unclosed curly braces inside the interpolated expression.
+   interpolations: Vec<(char, usize)>,
+   /// Whether comments are scanned as `TokenType::COMMENT` tokens, instead of being skipped.
+   emit_comments: bool,
+   /// Whether doc comments (e.g., `/// ...`) are scanned as `TokenType::DOC_COMMENT` tokens.
+   emit_doc_comments: bool,
 }
 
-impl Lexer {
+/// An iterator over the tokens of a source text, which ends before the end-of-file token.
+/// Scanning errors are yielded as `TokenType::ERROR` tokens whose lexeme is the error message.
+pub struct Tokens<'a> {
+   /// The lexer that scans the tokens.
+   lexer: Lexer<'a>,
+   /// The next token, if it has already been scanned by `peek()`.
+   peeked: Option<Token<'a>>,
+   /// Whether the lexer has reached the end of the source.
+   is_done: bool,
+}
+
+impl<'a> Tokens<'a> {
+   /// Returns the next token without consuming it.
+   ///
+   /// # Returns
+   /// - `Option<&Token>`: The next token, or `None` if the lexer reached the end of the source.
+   pub fn peek(&mut self) -> Option<&Token<'a>> {
+      if self.peeked.is_none() {
+         self.peeked = self.next();
+      }
+
+      self.peeked.as_ref()
+   }
+}
+
+impl<'a> Iterator for Tokens<'a> {
+   type Item = Token<'a>;
+
+   fn next(&mut self) -> Option<Token<'a>> {
+      if let Some(token) = self.peeked.take() {
+         return Some(token);
+      }
+
+      if self.is_done {
+         return None;
+      }
+
+      let token = self.lexer.next_token();
+
+      if let TokenType::EOF = token.token_type {
+         self.is_done = true;
+         return None;
+      }
+
+      Some(token)
+   }
+}
+
+impl<'a> Lexer<'a> {
    /// An initialized instance of the lexer.
    /// # Parameters
    /// - `src` - the source file
    ///
    /// ## Example
    /// ```
-   /// let mut l = Lexer::lex("let x = 22;");
+   /// let mut l = Lexer::new("let x = 22;");
    /// ```
-   pub fn lex(src: &str) -> Self {
+   pub fn new(src: &'a str) -> Self {
       let chars: Vec<char> = src.chars().collect();
+      let byte_offsets = src
+         .char_indices()
+         .map(|(i, _)| i)
+         .chain(Some(src.len()))
+         .collect();
 
       Self {
+         text: src,
          source: chars,
+         byte_offsets,
          current: 0,
          line_num: 1,
          line_start: 0,
          token_start: 0,
+         interpolations: vec![],
+         emit_comments: false,
+         emit_doc_comments: false,
+      }
+   }
+
+   /// Makes the lexer scan comments as `TokenType::COMMENT` tokens, instead of skipping them,
+   /// so that tools like the formatter can keep the comments of a program. The parser does
+   /// not expect comment tokens, so it always uses a lexer without this option.
+   pub fn with_comments(mut self) -> Self {
+      self.emit_comments = true;
+      self
+   }
+
+   /// Makes the lexer scan doc comments (single-line comments that start with exactly three
+   /// slashes) as `TokenType::DOC_COMMENT` tokens, so that the parser can attach them to the
+   /// declarations that follow them. Other comments are still skipped.
+   pub fn with_doc_comments(mut self) -> Self {
+      self.emit_doc_comments = true;
+      self
+   }
+
+   /// Converts the lexer into an iterator over the tokens of the source.
+   ///
+   /// ## Example
+   /// ```
+   /// let lexemes: Vec<Cow<str>> = Lexer::new("let x = 22;").tokens().map(|t| t.lexeme).collect();
+   /// ```
+   pub fn tokens(self) -> Tokens<'a> {
+      Tokens {
+         lexer: self,
+         peeked: None,
+         is_done: false,
       }
    }
 
@@ -55,6 +157,10 @@ impl Lexer {
    /// # Returns
    /// - `char`: The current character.
    pub fn get_current(&self) -> char {
+      if self.is_at_end() {
+         return '\0';
+      }
+
       self.source[self.current]
    }
 
@@ -63,7 +169,7 @@ impl Lexer {
    /// # Returns
    /// - `char`: The next character.
    pub fn next(&self) -> char {
-      if self.is_at_end() {
+      if self.current + 1 >= self.source.len() {
          return '\0';
       }
 
@@ -100,6 +206,12 @@ impl Lexer {
       current
    }
 
+   /// Registers the start of a new source line, after a new line character has been consumed.
+   fn start_new_line(&mut self) {
+      self.line_num += 1;
+      self.line_start = self.current;
+   }
+
    /// Skips whitespace-like characters from the source code.
    pub fn skip_whitespace(&mut self) {
       loop {
@@ -112,98 +224,311 @@ impl Lexer {
          if c == ' ' || c == '\r' || c == '\t' {
             self.advance();
          } else if c == '\n' {
-            self.line_num += 1;
-            self.line_start = self.current + 1;
             self.advance();
+            self.start_new_line();
+         } else if (self.emit_comments && c == '/' && matches!(self.next(), '/' | '*'))
+            || (self.emit_doc_comments && self.is_at_doc_comment())
+         {
+            break;
          } else if c == '/' && self.next() == '/' {
             self.skip_single_line_comments();
          } else if c == '/' && self.next() == '*' {
-            self.skip_block_comments();
+            let start = (self.current, self.line_num, self.line_start);
+
+            // An unterminated block comment is scanned as an error token by `next_token()`,
+            // so the scanner goes back to the opening delimiter of the comment.
+            if !self.skip_block_comments() {
+               (self.current, self.line_num, self.line_start) = start;
+               break;
+            }
          } else {
             break;
          }
       }
    }
 
+   /// Checks if the current character starts a doc comment, which starts with exactly three
+   /// slashes (comments that start with four or more slashes are regular comments).
+   pub fn is_at_doc_comment(&self) -> bool {
+      let slashes = self.source[self.current..]
+         .iter()
+         .take_while(|c| **c == '/')
+         .count();
+      slashes == 3
+   }
+
    /// Skips single-line comments from the source code.
    fn skip_single_line_comments(&mut self) {
       while !self.is_at_end() && self.get_current() != '\n' {
          self.advance();
       }
-
-      // Reposition the start of the token to
-      // be after the comment has ended
-      self.token_start = self.current;
    }
 
-   /// Skips block-comments from the source code
-   fn skip_block_comments(&mut self) {
+   /// Skips a block comment from the source code, including the block comments nested in it.
+   ///
+   /// # Returns
+   /// - `bool`: True if the comment was closed, false if the source ended inside the comment.
+   fn skip_block_comments(&mut self) -> bool {
       self.advance();
       self.advance();
 
       while !self.is_at_end() {
          // Recursively skip nested block comments
          if self.get_current() == '/' && self.next() == '*' {
-            self.skip_block_comments();
-
-            // break out of the loop if we reached the end of the program
-            // before matching the end of this nested block-comment.
-            if self.is_at_end() {
-               break;
+            if !self.skip_block_comments() {
+               return false;
             }
+
+            continue;
          }
 
-         // Break if we are at the end of the comment.
+         // Return if we are at the end of the comment.
          if self.get_current() == '*' && self.next() == '/' {
             self.advance();
             self.advance();
-            break;
+            return true;
          }
 
-         // Take into account new lines inside block comments
-         if self.get_current() == '\n' {
-            self.line_num += 1;
+         // Skip everything inside the comment, taking into account new lines
+         if self.advance() == '\n' {
+            self.start_new_line();
          }
+      }
 
-         // Skip everything inside the comment
-         self.advance();
+      false
+   }
+
+   /// Makes an error token for an unterminated block comment, which points at the opening
+   /// delimiter of the comment, and then skips the rest of the source.
+   pub fn make_unterminated_comment_token(&mut self) -> Token<'a> {
+      self.current += 2;
+      let token = self.make_error_token("Unterminated block comment.");
+
+      self.current -= 2;
+      self.skip_block_comments();
+      token
+   }
+
+   /// Makes a comment token, after the first `/` of the comment has been consumed.
+   pub fn make_comment_token(&mut self) -> Token<'a> {
+      if self.get_current() == '/' {
+         self.skip_single_line_comments();
+      } else {
+         self.current -= 1;
+         self.skip_block_comments();
       }
 
-      // Reposition the start of the token to
-      // be after the comment has ended
-      self.token_start = self.current;
+      self.make_token(TokenType::COMMENT)
+   }
+
+   /// Makes a doc comment token, whose lexeme is the text of the comment without the slashes
+   /// and the first space after them.
+   pub fn make_doc_comment_token(&mut self) -> Token<'a> {
+      self.skip_single_line_comments();
+
+      let comment = self.source_text(self.token_start, self.current);
+      let text = comment[3..].strip_prefix(' ').unwrap_or(&comment[3..]);
+
+      Token {
+         lexeme: Cow::Borrowed(text.trim_end()),
+         ..self.make_token(TokenType::DOC_COMMENT)
+      }
    }
 
    /// Makes a string literal.
-   pub fn make_string_token(&mut self) -> Token {
+   pub fn make_string_token(&mut self) -> Token<'a> {
       // The opener single or double quote.
       let quote = self.get_previous();
+      self.scan_string(quote, false)
+   }
+
+   /// Makes a raw string literal (e.g., `r"C:\path"`), whose contents are
+   /// not processed for escape sequences or interpolated expressions.
+   pub fn make_raw_string_token(&mut self) -> Token<'a> {
+      // The opener single or double quote.
+      let quote = self.advance();
+
+      loop {
+         if self.is_at_end() {
+            return self.make_error_token("Unterminated string.");
+         }
+
+         let current = self.advance();
+
+         // Take into account new lines inside block strings
+         if current == '\n' {
+            self.start_new_line();
+         } else if current == quote {
+            break;
+         }
+      }
+
+      self.make_token(TokenType::STRING)
+   }
+
+   /// Makes a char literal (e.g., `c'a'` or `c'\n'`), which must contain
+   /// exactly one character or escape sequence.
+   pub fn make_char_token(&mut self) -> Token<'a> {
+      self.advance(); // Consume the opening quote.
+      let mut escape_error = None;
+
+      loop {
+         if self.is_at_end() || self.get_current() == '\n' {
+            return self.make_error_token("Unterminated char literal.");
+         }
+
+         match self.advance() {
+            '\\' => {
+               if let Err(msg) = self.scan_escape_sequence() {
+                  escape_error = escape_error.or(Some(msg));
+               }
+            }
+            '\'' => break,
+            _ => {}
+         }
+      }
+
+      if let Some(msg) = escape_error {
+         return self.make_error_token(msg);
+      }
+
+      let token = self.make_token(TokenType::CHAR_LITERAL);
+      if char_literal_value(&token.lexeme).is_none() {
+         return self.make_error_token("A char literal must contain exactly one character.");
+      }
+
+      token
+   }
+
+   /// Scans the rest of a string literal after its opening quote, or after the closing brace
+   /// of an interpolated expression. If the scanner finds the start of an interpolated
+   /// expression (`${`), the string is split into an interpolation token.
+   ///
+   /// # Parameters
+   /// - `quote`: The quote that opened the string.
+   /// - `is_continuation`: Whether the scanner is continuing an interpolated string.
+   fn scan_string(&mut self, quote: char, is_continuation: bool) -> Token<'a> {
+      let mut escape_error = None;
 
       loop {
          if self.is_at_end() {
             return self.make_error_token("Unterminated string.");
          }
 
-         let prev = self.get_previous();
          let current = self.advance();
 
          // Take into account new lines inside block strings
          if current == '\n' {
-            self.line_num += 1;
+            self.start_new_line();
+            continue;
+         }
+
+         // Validate escape sequences, but keep scanning until the end of the string
+         // so that the rest of the string is not scanned as source code.
+         if current == '\\' {
+            if let Err(msg) = self.scan_escape_sequence() {
+               escape_error = escape_error.or(Some(msg));
+            }
+
             continue;
          }
 
          // If we reach an unescaped quote, break the loop.
-         if current == quote && prev != '\\' {
+         if current == quote {
             break;
          }
+
+         // If we reach an unescaped `${`, the string is interpolated.
+         if current == '$' && self.matches('{') {
+            self.interpolations.push((quote, 0));
+
+            return if let Some(msg) = escape_error {
+               self.make_error_token(msg)
+            } else if is_continuation {
+               self.make_token(TokenType::INTERPOLATION_MID)
+            } else {
+               self.make_token(TokenType::INTERPOLATION_START)
+            };
+         }
       }
 
-      self.make_token(TokenType::STRING)
+      if let Some(msg) = escape_error {
+         self.make_error_token(msg)
+      } else if is_continuation {
+         self.make_token(TokenType::INTERPOLATION_END)
+      } else {
+         self.make_token(TokenType::STRING)
+      }
+   }
+
+   /// Scans the escape sequence that follows a backslash inside a string literal.
+   ///
+   /// # Returns
+   /// - `Result<(), &'static str>`: An error message if the escape sequence is invalid.
+   fn scan_escape_sequence(&mut self) -> Result<(), &'static str> {
+      if self.is_at_end() {
+         return Ok(());
+      }
+
+      match self.advance() {
+         'n' | 't' | 'r' | '0' | '\\' | '"' | '\'' | '$' => Ok(()),
+         'u' => {
+            if !self.matches('{') {
+               return Err("Expected '{' after '\\u' in unicode escape sequence.");
+            }
+
+            let start = self.current;
+            while self.get_current().is_ascii_hexdigit() {
+               self.advance();
+            }
+
+            let digits = self.source_text(start, self.current);
+
+            if !self.matches('}') {
+               return Err("Expected '}' after unicode escape sequence.");
+            }
+
+            match u32::from_str_radix(digits, 16).ok().and_then(char::from_u32) {
+               Some(_) if digits.len() <= 6 => Ok(()),
+               _ => Err("Invalid unicode escape sequence."),
+            }
+         }
+         '\n' => {
+            self.start_new_line();
+            Err("Invalid escape sequence.")
+         }
+         _ => Err("Invalid escape sequence."),
+      }
+   }
+
+   /// Generates a token for an opening curly brace.
+   pub fn make_l_curly_token(&mut self) -> Token<'a> {
+      if let Some(interpolation) = self.interpolations.last_mut() {
+         interpolation.1 += 1;
+      }
+
+      self.make_token(TokenType::L_CURLY)
+   }
+
+   /// Generates a token for a closing curly brace. If the brace closes an interpolated
+   /// expression, the scanner continues scanning the rest of the interpolated string.
+   pub fn make_r_curly_token(&mut self) -> Token<'a> {
+      match self.interpolations.last_mut() {
+         Some((quote, 0)) => {
+            let quote = *quote;
+            self.interpolations.pop();
+            self.scan_string(quote, true)
+         }
+         Some(interpolation) => {
+            interpolation.1 -= 1;
+            self.make_token(TokenType::R_CURLY)
+         }
+         None => self.make_token(TokenType::R_CURLY),
+      }
    }
 
    /// Generates an identifier token with the current state of the scanner.
-   pub fn make_identifier_token(&mut self) -> Token {
+   pub fn make_identifier_token(&mut self) -> Token<'a> {
       while !self.is_at_end() {
          let c = self.get_current();
 
@@ -214,30 +539,82 @@ impl Lexer {
          }
       }
 
-      let id: String = self.source[(self.token_start)..(self.current)].iter().collect();
-      let tok_type = tokens::make_identifier_type(id.as_str());
+      let id = self.source_text(self.token_start, self.current);
+      let tok_type = tokens::make_identifier_type(id);
 
       self.make_token(tok_type)
    }
 
-   /// Generates a token with the current state of the scanner.
-   pub fn make_token(&self, token_type: TokenType) -> Token {
-      let col_start = match token_type {
-         TokenType::EOF => self.current,
-         _ => self.token_start - self.line_start,
+   /// Gets the position in the flat source vector of the character at a byte offset of the
+   /// source text (e.g., of the first character of a token, from the start of its span).
+   ///
+   /// # Parameters
+   /// - `byte_offset`: The byte offset in the source text.
+   pub fn char_position(&self, byte_offset: usize) -> usize {
+      self.byte_offsets.partition_point(|b| *b < byte_offset)
+   }
+
+   /// Gets the source text between two positions in the flat source vector.
+   ///
+   /// # Parameters
+   /// - `start`: The position of the first character.
+   /// - `end`: The position after the last character.
+   pub fn source_text(&self, start: usize, end: usize) -> &'a str {
+      let byte_offset = |index: usize| self.byte_offsets[index.min(self.source.len())];
+      &self.text[byte_offset(start)..byte_offset(end.max(start))]
+   }
+
+   /// Computes the byte range in the source text of the token being scanned.
+   fn token_span(&self) -> Span {
+      let byte_offset = |index: usize| self.byte_offsets[index.min(self.source.len())];
+
+      Span {
+         start: byte_offset(self.token_start),
+         end: byte_offset(self.current),
+      }
+   }
+
+   /// Gets the source line and column of the first character of the current token. The
+   /// token may span multiple lines (e.g., a string literal with new line characters).
+   fn token_start_position(&self) -> (usize, usize) {
+      let token = &self.source[self.token_start..self.current];
+      let new_lines = token.iter().filter(|c| **c == '\n').count();
+
+      if new_lines == 0 {
+         return (self.line_num, self.token_start - self.line_start);
+      }
+
+      let line_start = match self.source[..self.token_start].iter().rposition(|c| *c == '\n') {
+         Some(pos) => pos + 1,
+         None => 0,
       };
 
+      (self.line_num - new_lines, self.token_start - line_start)
+   }
+
+   /// Generates a token with the current state of the scanner.
+   pub fn make_token(&self, token_type: TokenType) -> Token<'a> {
+      let (line_num, col_start) = self.token_start_position();
+
       let lexeme = match token_type {
-         TokenType::EOF => String::from("\0"),
-         _ => self.source[(self.token_start)..(self.current)].iter().collect(),
+         TokenType::EOF => "\0",
+         _ => self.source_text(self.token_start, self.current),
+      };
+
+      // The end-of-file token is displayed after the last character of the source.
+      let span_end = match token_type {
+         TokenType::EOF => (self.line_num, col_start + 1),
+         _ => (self.line_num, self.current - self.line_start),
       };
 
       Token {
-         line_num: self.line_num,
+         line_num,
          column_start: col_start,
          column_end: self.current,
+         span_end,
+         span: self.token_span(),
          token_type,
-         lexeme,
+         lexeme: Cow::Borrowed(lexeme),
       }
    }
 
@@ -248,13 +625,77 @@ impl Lexer {
    ///
    /// # Returns
    /// - `Token`: The generated error token.
-   pub fn make_error_token(&self, message: &str) -> Token {
+   pub fn make_error_token(&self, message: impl Into<Cow<'a, str>>) -> Token<'a> {
+      let (line_num, column_start) = self.token_start_position();
+
       Token {
-         line_num: self.line_num,
-         column_start: self.token_start - self.line_start,
+         line_num,
+         column_start,
          column_end: self.current,
+         span_end: (self.line_num, self.current - self.line_start),
+         span: self.token_span(),
          token_type: TokenType::ERROR,
-         lexeme: String::from(message),
+         lexeme: message.into(),
       }
    }
 }
+
+/// Gets the value of a string literal from its lexeme, removing the outer quotes and replacing
+/// its escape sequences. Raw string literals (e.g., `r"..."`) are returned as they are.
+///
+/// # Parameters
+/// - `lexeme`: The lexeme of the string literal.
+pub fn string_literal_value(lexeme: &str) -> String {
+   match lexeme.strip_prefix('r') {
+      Some(raw) => raw[1..(raw.len() - 1)].to_string(),
+      None => unescape_string(&lexeme[1..(lexeme.len() - 1)]),
+   }
+}
+
+/// Gets the value of a char literal from its lexeme (e.g., `c'a'`), or `None` if the
+/// literal does not contain exactly one character.
+///
+/// # Parameters
+/// - `lexeme`: The lexeme of the char literal.
+pub fn char_literal_value(lexeme: &str) -> Option<char> {
+   let mut chars = unescape_string(&lexeme[2..(lexeme.len() - 1)])
+      .chars()
+      .collect::<Vec<_>>();
+   if chars.len() == 1 {
+      chars.pop()
+   } else {
+      None
+   }
+}
+
+/// Replaces the escape sequences in the contents of a string literal with the characters they
+/// represent. The escape sequences are expected to have been validated by the scanner.
+///
+/// # Parameters
+/// - `contents`: The contents of the string literal, without its quotes.
+pub fn unescape_string(contents: &str) -> String {
+   let mut result = String::with_capacity(contents.len());
+   let mut chars = contents.chars();
+
+   while let Some(c) = chars.next() {
+      if c != '\\' {
+         result.push(c);
+         continue;
+      }
+
+      match chars.next() {
+         Some('n') => result.push('\n'),
+         Some('t') => result.push('\t'),
+         Some('r') => result.push('\r'),
+         Some('0') => result.push('\0'),
+         Some('u') => {
+            let digits: String = chars.by_ref().skip(1).take_while(|c| *c != '}').collect();
+            result.extend(u32::from_str_radix(&digits, 16).ok().and_then(char::from_u32));
+         }
+         Some(c) => result.push(c),
+         None => result.push('\\'),
+      }
+   }
+
+   result
+}
@@ -0,0 +1,122 @@
+//! Hinton is a dynamically-typed, object-oriented programming language. This crate contains
+//! the lexer, parser, compiler, and virtual machine of the language, which are used by the
+//! `hinton` command-line interface, and can also be embedded in other Rust programs:
+//!
+//! ```
+//! use std::convert::TryFrom;
+//!
+//! let value = hinton::interpret("var answer = 40; answer + 2").unwrap();
+//! assert_eq!(i64::try_from(value).unwrap(), 42);
+//! ```
+//!
+//! Programs that need more control (e.g., to evaluate several programs that share their global
+//! declarations, to read the globals of a program, to register native functions, or to limit the
+//! resources used by untrusted programs) can create a [`VM`] and call [`VM::eval`] instead.
+//! Programs can also run concurrently, each in its own VM on a separate thread, with a [`Worker`].
+
+#[macro_use]
+extern crate num_derive;
+
+use std::path::PathBuf;
+use virtual_machine::OutputBuffer;
+
+pub mod ast_dump;
+pub mod bench_runner;
+pub mod built_in;
+pub mod compiler;
+pub mod core;
+pub mod debugger;
+pub mod docs;
+pub mod errors;
+pub mod explain;
+pub mod formatter;
+pub mod fuzz;
+pub mod highlight;
+pub mod lexer;
+pub mod lsp;
+pub mod objects;
+pub mod parser;
+pub mod playground;
+pub mod project;
+pub mod repl;
+pub mod test_runner;
+pub mod virtual_machine;
+#[cfg(target_arch = "wasm32")]
+pub mod wasm;
+
+#[cfg(test)]
+mod tests;
+
+pub use errors::{ErrorReport, HintonError, RuntimeError, RuntimeErrorType};
+pub use objects::{FuncObject, Object};
+pub use virtual_machine::{Message, VmConfig, Worker, VM};
+
+// Static things
+static FRAMES_MAX: usize = 1000;
+
+/// Counts the bytes allocated by the tests, so that the heap limits of the VM can be tested.
+#[cfg(test)]
+#[global_allocator]
+static ALLOCATOR: virtual_machine::CountingAllocator = virtual_machine::CountingAllocator;
+
+/// Compiles the source text of a program into bytecode, without executing it.
+///
+/// # Parameters
+/// - `source`: The source text of the program.
+///
+/// # Returns
+/// - `Result<FuncObject, HintonError>`: The main function of the compiled program, or the syntax
+///   and compiler errors found in the program.
+pub fn compile(source: &str) -> Result<FuncObject, HintonError> {
+   let ast = parser::Parser::parse(source).map_err(HintonError::Parse)?;
+   let built_in = built_in::BuiltIn::default();
+
+   compiler::Compiler::compile_ast(&PathBuf::new(), &ast, &built_in).map_err(HintonError::Compile)
+}
+
+/// Interprets the source text of a program in a new virtual machine.
+///
+/// # Parameters
+/// - `source`: The source text of the program.
+///
+/// # Returns
+/// - `Result<Object, HintonError>`: The value of the expression statement at the end of the
+///   program (or `null` if the program does not end with one), or the error that stopped it.
+pub fn interpret(source: &str) -> Result<Object, HintonError> {
+   VM::new(PathBuf::new()).eval(source)
+}
+
+/// The text written by a program while it ran, as captured by `run_captured(...)`.
+#[derive(Debug)]
+pub struct CapturedOutput {
+   /// The output of the program (e.g., the text printed with `print(...)`).
+   pub output: String,
+   /// The syntax, compiler, and runtime errors (and the compiler warnings) reported while the
+   /// program ran, in the same format as the errors printed to the console.
+   pub errors: String,
+}
+
+/// Runs the source text of a program in a new virtual machine, and returns the text the
+/// program wrote instead of printing it. This allows programs to run where there is no console
+/// (e.g., in a browser), so the text is never colored with ANSI escape sequences.
+///
+/// # Parameters
+/// - `source`: The source text of the program.
+///
+/// # Returns
+/// - `CapturedOutput`: The output and the errors of the program.
+pub fn run_captured(source: &str) -> CapturedOutput {
+   let output = OutputBuffer::new();
+   let errors = OutputBuffer::new();
+
+   VM::new(PathBuf::new())
+      .without_colors()
+      .with_stdout(Box::new(output.clone()))
+      .with_stderr(Box::new(errors.clone()))
+      .interpret(source);
+
+   CapturedOutput {
+      output: output.take(),
+      errors: errors.take(),
+   }
+}
@@ -0,0 +1,306 @@
+use std::fmt;
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// A JSON value, as exchanged with the language client.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Json {
+   Null,
+   Bool(bool),
+   Number(f64),
+   String(String),
+   Array(Vec<Json>),
+   /// An object, whose members keep the order in which they were written.
+   Object(Vec<(String, Json)>),
+}
+
+impl Json {
+   /// Parses a JSON document.
+   ///
+   /// # Parameters
+   /// - `text`: The JSON document.
+   ///
+   /// # Returns
+   /// - `Result<Json, String>`: The parsed value, or a message describing why it is not valid JSON.
+   pub fn parse(text: &str) -> Result<Json, String> {
+      let mut chars = text.chars().peekable();
+      let value = parse_value(&mut chars)?;
+      skip_whitespace(&mut chars);
+
+      match chars.next() {
+         Some(c) => Err(format!("Unexpected character '{}' after the JSON value.", c)),
+         None => Ok(value),
+      }
+   }
+
+   /// Creates a JSON object from its members.
+   pub fn object(members: Vec<(&str, Json)>) -> Json {
+      Json::Object(members.into_iter().map(|(k, v)| (k.to_string(), v)).collect())
+   }
+
+   /// Gets the value of a member of a JSON object.
+   pub fn get(&self, key: &str) -> Option<&Json> {
+      match self {
+         Json::Object(members) => members.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+         _ => None,
+      }
+   }
+
+   /// Gets the value of a member nested in JSON objects, following the given path of keys.
+   pub fn at(&self, path: &[&str]) -> Option<&Json> {
+      path.iter().try_fold(self, |value, key| value.get(key))
+   }
+
+   /// Gets the text of a JSON string.
+   pub fn as_str(&self) -> Option<&str> {
+      match self {
+         Json::String(s) => Some(s),
+         _ => None,
+      }
+   }
+
+   /// Gets the value of a JSON number as a non-negative integer.
+   pub fn as_usize(&self) -> Option<usize> {
+      match self {
+         Json::Number(n) if *n >= 0.0 && n.fract() == 0.0 => Some(*n as usize),
+         _ => None,
+      }
+   }
+}
+
+impl From<&str> for Json {
+   fn from(s: &str) -> Self {
+      Json::String(s.to_string())
+   }
+}
+
+impl From<String> for Json {
+   fn from(s: String) -> Self {
+      Json::String(s)
+   }
+}
+
+impl From<usize> for Json {
+   fn from(n: usize) -> Self {
+      Json::Number(n as f64)
+   }
+}
+
+impl From<bool> for Json {
+   fn from(b: bool) -> Self {
+      Json::Bool(b)
+   }
+}
+
+impl fmt::Display for Json {
+   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      match self {
+         Json::Null => write!(f, "null"),
+         Json::Bool(b) => write!(f, "{}", b),
+         Json::Number(n) if n.fract() == 0.0 && n.abs() < 1e15 => write!(f, "{}", *n as i64),
+         Json::Number(n) => write!(f, "{}", n),
+         Json::String(s) => write_string(f, s),
+         Json::Array(values) => {
+            write!(f, "[")?;
+            for (idx, value) in values.iter().enumerate() {
+               if idx > 0 {
+                  write!(f, ",")?;
+               }
+               write!(f, "{}", value)?;
+            }
+            write!(f, "]")
+         }
+         Json::Object(members) => {
+            write!(f, "{{")?;
+            for (idx, (key, value)) in members.iter().enumerate() {
+               if idx > 0 {
+                  write!(f, ",")?;
+               }
+               write_string(f, key)?;
+               write!(f, ":{}", value)?;
+            }
+            write!(f, "}}")
+         }
+      }
+   }
+}
+
+/// Writes a string as a JSON string literal, escaping its special characters.
+fn write_string(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+   write!(f, "\"")?;
+
+   for c in s.chars() {
+      match c {
+         '"' => write!(f, "\\\"")?,
+         '\\' => write!(f, "\\\\")?,
+         '\n' => write!(f, "\\n")?,
+         '\r' => write!(f, "\\r")?,
+         '\t' => write!(f, "\\t")?,
+         c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+         c => write!(f, "{}", c)?,
+      }
+   }
+
+   write!(f, "\"")
+}
+
+/// Skips the whitespace before the next token of a JSON document.
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+   while chars.peek().is_some_and(|c| c.is_ascii_whitespace()) {
+      chars.next();
+   }
+}
+
+/// Consumes the next character of a JSON document, which must be the expected character.
+fn expect(chars: &mut Peekable<Chars>, expected: char) -> Result<(), String> {
+   skip_whitespace(chars);
+
+   match chars.next() {
+      Some(c) if c == expected => Ok(()),
+      Some(c) => Err(format!("Expected '{}' but found '{}'.", expected, c)),
+      None => Err(format!("Expected '{}' but the document ended.", expected)),
+   }
+}
+
+/// Parses a JSON value.
+fn parse_value(chars: &mut Peekable<Chars>) -> Result<Json, String> {
+   skip_whitespace(chars);
+
+   match chars.peek() {
+      Some('{') => parse_object(chars),
+      Some('[') => parse_array(chars),
+      Some('"') => parse_string(chars).map(Json::String),
+      Some('t') => parse_keyword(chars, "true", Json::Bool(true)),
+      Some('f') => parse_keyword(chars, "false", Json::Bool(false)),
+      Some('n') => parse_keyword(chars, "null", Json::Null),
+      Some(c) if *c == '-' || c.is_ascii_digit() => parse_number(chars),
+      Some(c) => Err(format!("Unexpected character '{}'.", c)),
+      None => Err(String::from("Unexpected end of the document.")),
+   }
+}
+
+/// Parses one of the keywords of JSON (`true`, `false`, or `null`).
+fn parse_keyword(chars: &mut Peekable<Chars>, keyword: &str, value: Json) -> Result<Json, String> {
+   for expected in keyword.chars() {
+      if chars.next() != Some(expected) {
+         return Err(format!("Expected the keyword '{}'.", keyword));
+      }
+   }
+
+   Ok(value)
+}
+
+/// Parses a JSON number.
+fn parse_number(chars: &mut Peekable<Chars>) -> Result<Json, String> {
+   let mut text = String::new();
+
+   while let Some(c) = chars.peek() {
+      if c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E') {
+         text.push(*c);
+         chars.next();
+      } else {
+         break;
+      }
+   }
+
+   text
+      .parse::<f64>()
+      .map(Json::Number)
+      .map_err(|_| format!("Invalid number '{}'.", text))
+}
+
+/// Parses a JSON string, including its escape sequences.
+fn parse_string(chars: &mut Peekable<Chars>) -> Result<String, String> {
+   expect(chars, '"')?;
+   let mut s = String::new();
+
+   loop {
+      match chars.next() {
+         Some('"') => return Ok(s),
+         Some('\\') => match chars.next() {
+            Some('n') => s.push('\n'),
+            Some('r') => s.push('\r'),
+            Some('t') => s.push('\t'),
+            Some('b') => s.push('\u{8}'),
+            Some('f') => s.push('\u{c}'),
+            Some('u') => {
+               let code = parse_hex_code(chars)?;
+
+               // Characters outside the Basic Multilingual Plane are written as surrogate pairs.
+               let code = if (0xD800..0xDC00).contains(&code) {
+                  if chars.next() != Some('\\') || chars.next() != Some('u') {
+                     return Err(String::from("Expected the low surrogate of a character."));
+                  }
+
+                  let low = parse_hex_code(chars)?;
+                  0x10000 + ((code - 0xD800) << 10) + low.wrapping_sub(0xDC00)
+               } else {
+                  code
+               };
+
+               s.push(char::from_u32(code).unwrap_or('\u{FFFD}'));
+            }
+            Some(c) => s.push(c),
+            None => return Err(String::from("Unterminated string.")),
+         },
+         Some(c) => s.push(c),
+         None => return Err(String::from("Unterminated string.")),
+      }
+   }
+}
+
+/// Parses the four hexadecimal digits of a `\u` escape sequence.
+fn parse_hex_code(chars: &mut Peekable<Chars>) -> Result<u32, String> {
+   let digits: String = chars.take(4).collect();
+
+   u32::from_str_radix(&digits, 16).map_err(|_| format!("Invalid escape sequence '\\u{}'.", digits))
+}
+
+/// Parses a JSON array.
+fn parse_array(chars: &mut Peekable<Chars>) -> Result<Json, String> {
+   expect(chars, '[')?;
+   let mut values = vec![];
+
+   skip_whitespace(chars);
+   if chars.peek() == Some(&']') {
+      chars.next();
+      return Ok(Json::Array(values));
+   }
+
+   loop {
+      values.push(parse_value(chars)?);
+      skip_whitespace(chars);
+
+      match chars.next() {
+         Some(',') => continue,
+         Some(']') => return Ok(Json::Array(values)),
+         _ => return Err(String::from("Expected ',' or ']' after an array value.")),
+      }
+   }
+}
+
+/// Parses a JSON object.
+fn parse_object(chars: &mut Peekable<Chars>) -> Result<Json, String> {
+   expect(chars, '{')?;
+   let mut members = vec![];
+
+   skip_whitespace(chars);
+   if chars.peek() == Some(&'}') {
+      chars.next();
+      return Ok(Json::Object(members));
+   }
+
+   loop {
+      skip_whitespace(chars);
+      let key = parse_string(chars)?;
+      expect(chars, ':')?;
+      members.push((key, parse_value(chars)?));
+      skip_whitespace(chars);
+
+      match chars.next() {
+         Some(',') => continue,
+         Some('}') => return Ok(Json::Object(members)),
+         _ => return Err(String::from("Expected ',' or '}' after an object member.")),
+      }
+   }
+}
@@ -0,0 +1,502 @@
+//! A Language Server Protocol server for Hinton, started by the `hinton lsp` command, which
+//! gives editors diagnostics as the code changes, go-to-definition, hover information, and the
+//! outline of a document. The messages are exchanged through the standard input and output.
+
+use crate::built_in::BuiltIn;
+use crate::compiler::{Compiler, SymbolIndex, SymbolType};
+use crate::core::tokens::Span;
+use crate::errors::strip_ansi_codes;
+use crate::parser::Parser;
+use hashbrown::HashMap;
+use json::Json;
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+
+pub mod json;
+
+/// The JSON-RPC error code for requests whose method is not supported by the server.
+const METHOD_NOT_FOUND: f64 = -32601.0;
+
+/// The severities of the diagnostics, as defined by the protocol.
+const SEVERITY_ERROR: usize = 1;
+const SEVERITY_WARNING: usize = 2;
+
+/// A document opened in the editor.
+struct Document {
+   /// The current text of the document.
+   text: String,
+   /// The symbols of the document. If the document has syntax errors, these are the symbols of
+   /// the statements that could be parsed.
+   symbols: SymbolIndex,
+}
+
+/// The state of the language server.
+pub struct LanguageServer {
+   /// The documents opened in the editor, by URI.
+   documents: HashMap<String, Document>,
+   /// The built-in functions and primitives, which are resolved by the compiler.
+   built_in: BuiltIn,
+   /// Whether the client has requested the server to shut down.
+   is_shut_down: bool,
+}
+
+impl Default for LanguageServer {
+   fn default() -> Self {
+      LanguageServer {
+         documents: HashMap::new(),
+         built_in: BuiltIn::default(),
+         is_shut_down: false,
+      }
+   }
+}
+
+impl LanguageServer {
+   /// Handles a message sent by the client.
+   ///
+   /// # Parameters
+   /// - `message`: The request or notification sent by the client.
+   ///
+   /// # Returns
+   /// - `Vec<Json>`: The messages to send back to the client, which are the response to the
+   ///   request (if the message is a request) and the notifications published by the server.
+   pub fn handle(&mut self, message: &Json) -> Vec<Json> {
+      let method = message.get("method").and_then(|m| m.as_str()).unwrap_or_default();
+      let params = message.get("params").cloned().unwrap_or(Json::Null);
+
+      let result = match method {
+         "initialize" => Some(capabilities()),
+         "shutdown" => {
+            self.is_shut_down = true;
+            Some(Json::Null)
+         }
+         "textDocument/didOpen" => {
+            let uri = params.at(&["textDocument", "uri"]).and_then(|u| u.as_str());
+            let text = params.at(&["textDocument", "text"]).and_then(|t| t.as_str());
+
+            return match (uri, text) {
+               (Some(uri), Some(text)) => vec![self.update(uri, text.to_string())],
+               _ => vec![],
+            };
+         }
+         "textDocument/didChange" => {
+            let uri = params.at(&["textDocument", "uri"]).and_then(|u| u.as_str());
+
+            // The server asks for the full text of the document on every change.
+            let text = match params.get("contentChanges") {
+               Some(Json::Array(changes)) => changes
+                  .last()
+                  .and_then(|c| c.get("text"))
+                  .and_then(|t| t.as_str()),
+               _ => None,
+            };
+
+            return match (uri, text) {
+               (Some(uri), Some(text)) => vec![self.update(uri, text.to_string())],
+               _ => vec![],
+            };
+         }
+         "textDocument/didClose" => {
+            return match params.at(&["textDocument", "uri"]).and_then(|u| u.as_str()) {
+               Some(uri) => {
+                  self.documents.remove(uri);
+                  vec![publish_diagnostics(uri, vec![])]
+               }
+               None => vec![],
+            };
+         }
+         "textDocument/definition" => Some(self.definition(&params)),
+         "textDocument/hover" => Some(self.hover(&params)),
+         "textDocument/documentSymbol" => Some(self.document_symbols(&params)),
+         _ => None,
+      };
+
+      // Notifications (messages without an id) are never answered.
+      let id = match message.get("id") {
+         Some(id) => id.clone(),
+         None => return vec![],
+      };
+
+      let response = match result {
+         Some(result) => Json::object(vec![("jsonrpc", "2.0".into()), ("id", id), ("result", result)]),
+         None => Json::object(vec![
+            ("jsonrpc", "2.0".into()),
+            ("id", id),
+            (
+               "error",
+               Json::object(vec![
+                  ("code", Json::Number(METHOD_NOT_FOUND)),
+                  ("message", format!("Unsupported method '{}'.", method).into()),
+               ]),
+            ),
+         ]),
+      };
+
+      vec![response]
+   }
+
+   /// Checks if the client has requested the server to shut down.
+   pub fn is_shut_down(&self) -> bool {
+      self.is_shut_down
+   }
+
+   /// Updates the text of a document, and analyzes it to find its diagnostics and symbols.
+   ///
+   /// # Returns
+   /// - `Json`: The notification that publishes the diagnostics of the document.
+   fn update(&mut self, uri: &str, text: String) -> Json {
+      let mut diagnostics = vec![];
+      let (ast, syntax_errors) = Parser::parse_with_recovery(&text);
+      let analysis = Compiler::analyze(&uri_to_path(uri), &ast, &self.built_in);
+
+      for error in syntax_errors.iter() {
+         let start = column_offset(&text, error.line, error.column);
+         let end = column_offset(&text, error.end_line, error.end_column).max(start);
+         diagnostics.push(diagnostic(&text, start, end, SEVERITY_ERROR, &error.message));
+      }
+
+      // The statements left out of the AST would cause spurious errors and warnings (e.g.,
+      // the uses of a variable whose declaration has a syntax error), so they are only
+      // reported for documents without syntax errors.
+      if syntax_errors.is_empty() {
+         for error in analysis.errors.iter() {
+            let start = column_offset(&text, error.line, error.column);
+            let end = column_offset(&text, error.end_line, error.end_column).max(start);
+            diagnostics.push(diagnostic(&text, start, end, SEVERITY_ERROR, &error.message));
+         }
+
+         for warning in analysis.warnings.iter() {
+            let start = column_offset(&text, warning.line, warning.column);
+            let end = column_offset(&text, warning.line, warning.column + warning.lexeme_len);
+            diagnostics.push(diagnostic(&text, start, end, SEVERITY_WARNING, &warning.message));
+         }
+      }
+
+      let symbols = analysis.symbols;
+      self.documents.insert(uri.to_string(), Document { text, symbols });
+      publish_diagnostics(uri, diagnostics)
+   }
+
+   /// Finds the document and the byte offset in its text of a `TextDocumentPositionParams`.
+   fn document_position<'a>(&'a self, params: &'a Json) -> Option<(&'a str, &'a Document, usize)> {
+      let uri = params.at(&["textDocument", "uri"])?.as_str()?;
+      let document = self.documents.get(uri)?;
+      let line = params.at(&["position", "line"])?.as_usize()?;
+      let character = params.at(&["position", "character"])?.as_usize()?;
+
+      Some((uri, document, position_offset(&document.text, line, character)))
+   }
+
+   /// Answers a `textDocument/definition` request with the location of the
+   /// declaration of the identifier at the given position.
+   fn definition(&self, params: &Json) -> Json {
+      let (uri, document, offset) = match self.document_position(params) {
+         Some(p) => p,
+         None => return Json::Null,
+      };
+
+      match document.symbols.declaration_at(offset) {
+         Some(declaration) => Json::object(vec![
+            ("uri", uri.into()),
+            ("range", range(&document.text, declaration.span)),
+         ]),
+         None => Json::Null,
+      }
+   }
+
+   /// Answers a `textDocument/hover` request with the description of the declaration of the
+   /// identifier at the given position (e.g., its type, or the signature of a function).
+   fn hover(&self, params: &Json) -> Json {
+      let (_, document, offset) = match self.document_position(params) {
+         Some(p) => p,
+         None => return Json::Null,
+      };
+
+      let declaration = match document.symbols.declaration_at(offset) {
+         Some(d) => d,
+         None => return Json::Null,
+      };
+
+      // The signature is shown as code, followed by the rest of the description.
+      let value = match declaration.description.split_once("\n\n") {
+         Some((signature, rest)) => format!("```hinton\n{}\n```\n{}", signature, rest),
+         None => format!("```hinton\n{}\n```", declaration.description),
+      };
+
+      Json::object(vec![(
+         "contents",
+         Json::object(vec![("kind", "markdown".into()), ("value", value.into())]),
+      )])
+   }
+
+   /// Answers a `textDocument/documentSymbol` request with the global declarations of a document.
+   fn document_symbols(&self, params: &Json) -> Json {
+      let document = match params
+         .at(&["textDocument", "uri"])
+         .and_then(|u| u.as_str())
+         .and_then(|uri| self.documents.get(uri))
+      {
+         Some(d) => d,
+         None => return Json::Null,
+      };
+
+      let symbols = document
+         .symbols
+         .declarations
+         .iter()
+         .filter(|d| d.is_global)
+         .map(|declaration| {
+            let range = range(&document.text, declaration.span);
+
+            Json::object(vec![
+               ("name", declaration.name.as_str().into()),
+               (
+                  "detail",
+                  declaration.description.lines().next().unwrap_or_default().into(),
+               ),
+               ("kind", symbol_kind(&declaration.s_type).into()),
+               ("range", range.clone()),
+               ("selectionRange", range),
+            ])
+         })
+         .collect();
+
+      Json::Array(symbols)
+   }
+}
+
+/// Runs the language server until the client sends the `exit` notification.
+///
+/// # Parameters
+/// - `input`: The stream where the client writes its messages.
+/// - `output`: The stream where the server writes its messages.
+///
+/// # Returns
+/// - `io::Result<i32>`: The exit code of the server, which is `0` if the client shut it down
+///   before the `exit` notification, and `1` otherwise.
+pub fn run(input: &mut dyn BufRead, output: &mut dyn Write) -> io::Result<i32> {
+   let mut server = LanguageServer::default();
+
+   while let Some(content) = read_message(input)? {
+      let message = match Json::parse(&content) {
+         Ok(m) => m,
+         Err(_) => continue,
+      };
+
+      if message.get("method").and_then(|m| m.as_str()) == Some("exit") {
+         return Ok(if server.is_shut_down() { 0 } else { 1 });
+      }
+
+      for reply in server.handle(&message) {
+         write_message(output, &reply)?;
+      }
+   }
+
+   Ok(1)
+}
+
+/// Reads the content of the next message sent by the client, which is preceded
+/// by a header with its length in bytes (e.g., `Content-Length: 52`).
+///
+/// # Returns
+/// - `io::Result<Option<String>>`: The content of the message, or `None` if the input ended.
+fn read_message(input: &mut dyn BufRead) -> io::Result<Option<String>> {
+   let mut length = None;
+
+   loop {
+      let mut line = String::new();
+      if input.read_line(&mut line)? == 0 {
+         return Ok(None);
+      }
+
+      let line = line.trim_end();
+      if line.is_empty() {
+         break;
+      }
+
+      if let Some((name, value)) = line.split_once(':') {
+         if name.eq_ignore_ascii_case("Content-Length") {
+            length = value.trim().parse::<usize>().ok();
+         }
+      }
+   }
+
+   let mut content = vec![0; length.unwrap_or(0)];
+   input.read_exact(&mut content)?;
+
+   Ok(Some(String::from_utf8_lossy(&content).into_owned()))
+}
+
+/// Writes a message to the client, preceded by the header with its length.
+fn write_message(output: &mut dyn Write, message: &Json) -> io::Result<()> {
+   let content = message.to_string();
+   write!(output, "Content-Length: {}\r\n\r\n{}", content.len(), content)?;
+   output.flush()
+}
+
+/// The capabilities of the server, as the result of the `initialize` request.
+fn capabilities() -> Json {
+   Json::object(vec![
+      (
+         "capabilities",
+         Json::object(vec![
+            // The client sends the full text of a document when it changes.
+            ("textDocumentSync", 1.into()),
+            ("definitionProvider", true.into()),
+            ("hoverProvider", true.into()),
+            ("documentSymbolProvider", true.into()),
+         ]),
+      ),
+      (
+         "serverInfo",
+         Json::object(vec![
+            ("name", "hinton".into()),
+            ("version", env!("CARGO_PKG_VERSION").into()),
+         ]),
+      ),
+   ])
+}
+
+/// Creates the notification that publishes the diagnostics of a document.
+fn publish_diagnostics(uri: &str, diagnostics: Vec<Json>) -> Json {
+   Json::object(vec![
+      ("jsonrpc", "2.0".into()),
+      ("method", "textDocument/publishDiagnostics".into()),
+      (
+         "params",
+         Json::object(vec![
+            ("uri", uri.into()),
+            ("diagnostics", Json::Array(diagnostics)),
+         ]),
+      ),
+   ])
+}
+
+/// Creates a diagnostic from an error or warning report. The position in the message of the
+/// report (e.g., `ReferenceError[E0002] at [1:8]: ...`) is removed, since the editor shows it.
+fn diagnostic(text: &str, start: usize, end: usize, severity: usize, message: &str) -> Json {
+   let message = strip_ansi_codes(message);
+
+   let message = match (message.find(" at ["), message.find("]: ")) {
+      (Some(at), Some(colon)) if at < colon => format!("{}:{}", &message[..at], &message[(colon + 2)..]),
+      _ => message,
+   };
+
+   Json::object(vec![
+      ("range", range(text, Span { start, end })),
+      ("severity", severity.into()),
+      ("source", "hinton".into()),
+      ("message", message.into()),
+   ])
+}
+
+/// Gets the kind of symbol shown by the editor for a declaration type.
+fn symbol_kind(s_type: &SymbolType) -> usize {
+   match s_type {
+      SymbolType::Class => 5,
+      SymbolType::Enum => 10,
+      SymbolType::Method => 6,
+      SymbolType::VarField | SymbolType::ConstField => 8,
+      SymbolType::Interface => 11,
+      SymbolType::Func => 12,
+      SymbolType::Var | SymbolType::Param => 13,
+      SymbolType::Const => 14,
+   }
+}
+
+/// Converts a `file://` URI into a file path.
+fn uri_to_path(uri: &str) -> PathBuf {
+   let path = uri.strip_prefix("file://").unwrap_or(uri);
+   let bytes = path.as_bytes();
+   let mut decoded = Vec::with_capacity(bytes.len());
+
+   let mut idx = 0;
+   while idx < bytes.len() {
+      let escaped = bytes.get(idx + 1..idx + 3).and_then(|hex| {
+         std::str::from_utf8(hex)
+            .ok()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+      });
+
+      match (bytes[idx], escaped) {
+         (b'%', Some(byte)) => {
+            decoded.push(byte);
+            idx += 3;
+         }
+         (byte, _) => {
+            decoded.push(byte);
+            idx += 1;
+         }
+      }
+   }
+
+   PathBuf::from(String::from_utf8_lossy(&decoded).into_owned())
+}
+
+/// Converts a range of bytes of a document into a range of the protocol.
+fn range(text: &str, span: Span) -> Json {
+   Json::object(vec![
+      ("start", position(text, span.start)),
+      ("end", position(text, span.end)),
+   ])
+}
+
+/// Converts a byte offset of a document into a position of the protocol, whose
+/// line is zero-based and whose character is counted in UTF-16 code units.
+fn position(text: &str, offset: usize) -> Json {
+   let offset = floor_char_boundary(text, offset);
+   let line_start = text[..offset].rfind('\n').map_or(0, |pos| pos + 1);
+   let line = text[..line_start].matches('\n').count();
+   let character: usize = text[line_start..offset].chars().map(char::len_utf16).sum();
+
+   Json::object(vec![("line", line.into()), ("character", character.into())])
+}
+
+/// Converts a position of the protocol into a byte offset of a document.
+fn position_offset(text: &str, line: usize, character: usize) -> usize {
+   let line_start = line_start(text, line);
+   let mut units = 0;
+
+   for (idx, c) in text[line_start..].char_indices() {
+      if units >= character || c == '\n' {
+         return line_start + idx;
+      }
+
+      units += c.len_utf16();
+   }
+
+   text.len()
+}
+
+/// Converts the one-based line and the zero-based character column of a position
+/// reported by the parser or the compiler into a byte offset of a document.
+fn column_offset(text: &str, line: usize, column: usize) -> usize {
+   let line_start = line_start(text, line.saturating_sub(1));
+
+   match text[line_start..].char_indices().nth(column) {
+      Some((idx, _)) => line_start + idx,
+      None => text.len(),
+   }
+}
+
+/// Gets the byte offset of the start of a zero-based line of a document.
+fn line_start(text: &str, line: usize) -> usize {
+   if line == 0 {
+      return 0;
+   }
+
+   match text.match_indices('\n').nth(line - 1) {
+      Some((idx, _)) => idx + 1,
+      None => text.len(),
+   }
+}
+
+/// Moves a byte offset back to the start of the character that contains it.
+fn floor_char_boundary(text: &str, offset: usize) -> usize {
+   let mut offset = offset.min(text.len());
+
+   while !text.is_char_boundary(offset) {
+      offset -= 1;
+   }
+
+   offset
+}
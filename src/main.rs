@@ -1,22 +1,23 @@
-#[macro_use]
-extern crate num_derive;
-
 use std::io::ErrorKind;
-use std::{env, fs};
-
-use crate::virtual_machine::{InterpretResult, VM};
-
-mod built_in;
-mod compiler;
-mod core;
-mod errors;
-mod lexer;
-mod objects;
-mod parser;
-mod virtual_machine;
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
 
-#[cfg(test)]
-mod tests;
+use hinton::ast_dump::{dump_ast, AstFormat};
+use hinton::bench_runner::{bench_file, BenchOptions, BenchResult};
+use hinton::built_in::BuiltIn;
+use hinton::compiler::{Compiler, OptLevel, WarningMode};
+use hinton::core::{disassembler, serialization};
+use hinton::debugger::CliDebugger;
+use hinton::docs::render_module_docs;
+use hinton::errors::{print_warnings_list, report_errors_list};
+use hinton::formatter::format_source;
+use hinton::highlight::{highlight, HighlightFormat};
+use hinton::parser::Parser;
+use hinton::project::Manifest;
+use hinton::test_runner::{TestOptions, TestSummary};
+use hinton::virtual_machine::{CountingAllocator, Debugger, GcConfig, InterpretResult, TraceMode, VM};
+use hinton::{errors, explain, lsp, playground, repl, test_runner};
 
 /// Represents the arguments passed to the Hinton CLI.
 struct HintonCLI {
@@ -24,8 +25,94 @@ struct HintonCLI {
    args: Vec<String>,
 }
 
-// Static things
-static FRAMES_MAX: usize = 1000;
+impl HintonCLI {
+   /// Gets the configuration of the garbage collector from the `--gc-threshold=<n>`
+   /// and `--gc-growth=<n>` flags, falling back to the default configuration.
+   fn gc_config(&self) -> GcConfig {
+      let mut config = GcConfig::default();
+
+      for flag in self.flags.iter() {
+         let (name, value) = match flag.split_once('=') {
+            Some((name, value)) => (name, value.parse::<usize>()),
+            None => continue,
+         };
+
+         match (name, value) {
+            (name, _) if !name.starts_with("--gc-") => continue,
+            ("--gc-threshold", Ok(n)) => config.initial_threshold = n,
+            ("--gc-growth", Ok(n)) if n > 1 => config.growth_factor = n,
+            _ => eprintln!("Invalid flag '{}'.", flag),
+         }
+      }
+
+      config
+   }
+
+   /// Checks if the program should be run in the CLI debugger (the `--debug` flag).
+   fn is_debug(&self) -> bool {
+      self.flags.iter().any(|f| f == "--debug")
+   }
+
+   /// Checks if the program should be run with the profiler (the `--profile` flag, or the
+   /// `--profile-output=<path>` flag).
+   fn is_profile(&self) -> bool {
+      self.flags.iter().any(|f| f == "--profile") || self.profile_output().is_some()
+   }
+
+   /// Gets the path of the file where the collapsed call stacks measured by the profiler
+   /// are written, from the `--profile-output=<path>` flag.
+   fn profile_output(&self) -> Option<&str> {
+      self
+         .flags
+         .iter()
+         .find_map(|f| f.strip_prefix("--profile-output="))
+   }
+
+   /// Gets which instructions are printed by the execution trace, from the `--trace` flag (every
+   /// instruction), or the `--trace=<mode>` flag, where the mode is `all`, `calls`, or the
+   /// name of a function.
+   fn trace_mode(&self) -> Option<TraceMode> {
+      self.flags.iter().find_map(|f| match f.as_str() {
+         "--trace" => Some(TraceMode::All),
+         _ => f.strip_prefix("--trace=").map(TraceMode::from_name),
+      })
+   }
+
+   /// Checks if the diagnostics should be printed without colors (the `--no-color` flag).
+   fn is_no_color(&self) -> bool {
+      self.flags.iter().any(|f| f == "--no-color")
+   }
+
+   /// Gets how the compiler warnings are handled from the `--deny-warnings`
+   /// and `--no-warnings` flags, falling back to showing the warnings.
+   fn warning_mode(&self) -> WarningMode {
+      if self.flags.iter().any(|f| f == "--deny-warnings") {
+         WarningMode::Deny
+      } else if self.flags.iter().any(|f| f == "--no-warnings") {
+         WarningMode::Silence
+      } else {
+         WarningMode::Show
+      }
+   }
+
+   /// Gets which optimizations the compiler applies from the `-O0` and `-O1` flags. Programs
+   /// run in the debugger are not optimized by default, so that every line can be stepped on.
+   fn opt_level(&self) -> OptLevel {
+      if self.flags.iter().any(|f| f == "-o0") {
+         OptLevel::O0
+      } else if self.flags.iter().any(|f| f == "-o1") {
+         OptLevel::O1
+      } else if self.is_debug() {
+         OptLevel::O0
+      } else {
+         OptLevel::O1
+      }
+   }
+}
+
+/// Counts the bytes allocated by each thread, so that the VM can limit the heap size of a program.
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
 
 /// The main function
 fn main() {
@@ -38,80 +125,761 @@ fn main() {
       args: vec![],
    };
 
-   // If no arguments are provided, run the REPL
-   if args.len() <= 1 {
-      todo!("We need a REPL!!!")
-   }
-
    // The argument position for the filename
    let mut file_name_arg_pos = 1;
 
    // Get program flags
-   for arg in args.iter() {
-      if arg.to_string().starts_with("--") {
-         _self.flags.push(arg.to_lowercase());
+   for arg in args.iter().skip(1) {
+      if is_flag(arg) {
+         _self.flags.push(normalize_flag(arg));
          file_name_arg_pos += 1;
       } else {
          break;
       }
    }
 
+   // The flags can also follow the `run` command (e.g., `hinton run --profile main.ht`).
+   let mut run_flags_count = 0;
+   if args.get(file_name_arg_pos).is_some_and(|a| a == "run") {
+      for arg in args.iter().skip(file_name_arg_pos + 1).take_while(|a| is_flag(a)) {
+         _self.flags.push(normalize_flag(arg));
+         run_flags_count += 1;
+      }
+   }
+
+   errors::set_colors_enabled(!_self.is_no_color());
+
+   // If no file or command is provided, run the REPL
+   if file_name_arg_pos >= args.len() {
+      repl::run_repl();
+      return;
+   }
+
    // Get the name of the file to run
    let file_name = &args[file_name_arg_pos];
 
    // Get the program args
-   _self.args = args[(file_name_arg_pos + 1)..].to_vec();
+   _self.args = args[(file_name_arg_pos + 1 + run_flags_count)..].to_vec();
 
    // Run the appropriate command
    match file_name.as_str() {
-      "compile" => todo!("Compile command is not yet supported."),
-      _ => run_file(file_name),
+      "ast" => dump_ast_file(&_self.args),
+      "bench" => run_benchmarks(&_self.args, &_self),
+      "compile" => compile_file(&_self.args, &_self),
+      "disasm" => match _self.args.first() {
+         Some(f) => disassemble_file(f, &_self),
+         None => {
+            eprintln!("Expected the path to the file to disassemble.");
+            std::process::exit(64)
+         }
+      },
+      "doc" => match _self.args.first() {
+         Some(f) => document_file(f),
+         None => {
+            eprintln!("Expected the path to the module to document.");
+            std::process::exit(64)
+         }
+      },
+      "explain" => {
+         if !explain::explain(_self.args.first().map(|c| c.as_str())) {
+            std::process::exit(64)
+         }
+      }
+      "fmt" => format_files(&_self.args),
+      "highlight" => highlight_file(&_self.args),
+      "lsp" => {
+         let stdin = io::stdin();
+         match lsp::run(&mut stdin.lock(), &mut io::stdout()) {
+            Ok(code) => std::process::exit(code),
+            Err(error) => {
+               eprintln!("The language server stopped. {}", error);
+               std::process::exit(70)
+            }
+         }
+      }
+      "playground" => run_playground(&_self.args),
+      "run" => match _self.args.split_first() {
+         Some((f, script_args)) if Path::new(f).is_file() => run_file(f, script_args, &_self),
+         Some((alias, script_args)) => run_project(Some(alias), script_args, &_self),
+         None => run_project(None, &[], &_self),
+      },
+      "test" => run_tests(&_self.args, &_self),
+      _ => run_file(file_name, &_self.args, &_self),
+   }
+}
+
+/// Checks if a command-line argument is a flag of the Hinton CLI.
+fn is_flag(arg: &str) -> bool {
+   arg.starts_with("--") || arg.starts_with("-O")
+}
+
+/// Converts the name of a flag to lowercase. The value of the flag (after
+/// the `=` sign, if any) keeps its case, since it may be a path.
+fn normalize_flag(flag: &str) -> String {
+   match flag.split_once('=') {
+      Some((name, value)) => format!("{}={}", name.to_lowercase(), value),
+      None => flag.to_lowercase(),
    }
 }
 
-/// Parses, compiles, and interprets a Hinton source file.
+/// Parses, compiles, and interprets a Hinton source file, or
+/// interprets a precompiled Hinton bytecode (`.hbc`) file.
 ///
 /// # Parameters
 /// - `filename`: The path to the file to run.
-fn run_file(filename: &str) {
+/// - `script_args`: The arguments passed to the program, after the path of the file.
+/// - `cli`: The arguments passed to the Hinton CLI.
+fn run_file(filename: &str, script_args: &[String], cli: &HintonCLI) {
    let filepath = match fs::canonicalize(filename) {
       Ok(path) => path,
+      Err(error) => exit_with_io_error(filename, error),
+   };
+
+   if filepath.extension().is_some_and(|ext| ext == "hbc") {
+      return run_bytecode_file(filename, filepath, script_args, cli);
+   }
+
+   let contents = match fs::read_to_string(filepath.clone()) {
+      Ok(src) => src,
+      Err(error) => exit_with_io_error(filename, error),
+   };
+
+   let module_dirs = project_module_dirs(&filepath);
+
+   let mut vm = VM::new(filepath)
+      .with_gc_config(cli.gc_config())
+      .with_warning_mode(cli.warning_mode())
+      .with_opt_level(cli.opt_level())
+      .with_module_dirs(module_dirs)
+      .with_script_args(script_args.to_vec());
+
+   if cli.is_debug() {
+      vm = vm.with_debugger(Debugger::new(Box::new(CliDebugger::default())));
+   }
+
+   if cli.is_profile() {
+      vm = vm.with_profiler();
+   }
+
+   if let Some(mode) = cli.trace_mode() {
+      vm = vm.with_trace(mode);
+   }
+
+   // Interprets the source contents in the VM
+   let result = vm.interpret(&contents);
+   report_profile(&mut vm, cli);
+   exit_with_result(result)
+}
+
+/// Runs a Hinton project from the `hinton.toml` manifest found in the current directory (or in
+/// one of its parents). Without an alias, the project's entry file is executed. Otherwise, the
+/// file of the script with the given alias is executed.
+///
+/// # Parameters
+/// - `alias`: The alias of the script to run, if any.
+/// - `script_args`: The arguments passed to the program, after the alias.
+/// - `cli`: The arguments passed to the Hinton CLI.
+fn run_project(alias: Option<&str>, script_args: &[String], cli: &HintonCLI) {
+   let cwd = env::current_dir().unwrap_or_default();
+
+   let manifest = match (Manifest::find(&cwd), alias) {
+      (Some(Ok(m)), _) => m,
+      (Some(Err(message)), _) => {
+         eprintln!("Invalid project manifest. {}", message);
+         std::process::exit(65)
+      }
+      // Outside of a project, the argument is reported as a missing file.
+      (None, Some(filename)) => return run_file(filename, script_args, cli),
+      (None, None) => {
+         eprintln!(
+            "Expected the path to the file to run, or a 'hinton.toml' manifest in the current directory."
+         );
+         std::process::exit(64)
+      }
+   };
+
+   let filepath = match alias {
+      Some(alias) => match manifest.scripts.get(alias) {
+         Some(path) => path.clone(),
+         None => {
+            eprintln!("File or script '{}' not found.", alias);
+            std::process::exit(64)
+         }
+      },
+      None => manifest.entry,
+   };
+
+   run_file(filepath.to_str().unwrap(), script_args, cli)
+}
+
+/// Serves the Hinton playground on the given port of the local machine (8000 by default), where
+/// programs can be written and run from a browser.
+///
+/// # Parameters
+/// - `args`: The arguments passed to the playground command.
+fn run_playground(args: &[String]) {
+   let port = match args.first().map(|p| p.parse::<u16>()) {
+      None => 8000,
+      Some(Ok(port)) => port,
+      Some(Err(_)) => {
+         eprintln!("Invalid port '{}'.", args[0]);
+         std::process::exit(64)
+      }
+   };
+
+   let listener = match TcpListener::bind(("127.0.0.1", port)) {
+      Ok(listener) => listener,
       Err(error) => {
-         match error.kind() {
-            ErrorKind::NotFound => eprintln!("File '{}' not found.", filename),
-            ErrorKind::PermissionDenied => eprintln!("Need permission to open '{}'.", filename),
-            ErrorKind::UnexpectedEof => eprintln!("Unexpected end-of-file '{}'.", filename),
-            _ => eprintln!("Unexpected error when opening file '{}'.", filename),
+         eprintln!("Could not listen on port {}. {}", port, error);
+         std::process::exit(70)
+      }
+   };
+
+   println!("The Hinton playground is running at http://127.0.0.1:{}", port);
+
+   if let Err(error) = playground::serve(listener, playground::default_limits()) {
+      eprintln!("The playground stopped. {}", error);
+      std::process::exit(70)
+   }
+}
+
+/// Runs the test functions (functions whose name starts with `test_`) found in the given files
+/// and directories, or in the current directory if no paths are provided. Only the tests whose
+/// name contains the string passed with `--filter <str>` are executed. Exits with code 1 if any
+/// test failed.
+///
+/// # Parameters
+/// - `args`: The arguments passed to the test command.
+/// - `cli`: The arguments passed to the Hinton CLI.
+fn run_tests(args: &[String], cli: &HintonCLI) {
+   let mut paths = vec![];
+   let mut filter = None;
+
+   let mut args_iter = args.iter();
+   while let Some(arg) = args_iter.next() {
+      match arg.as_str() {
+         "--filter" => filter = args_iter.next().map(|f| f.as_str()),
+         _ => match arg.strip_prefix("--filter=") {
+            Some(f) => filter = Some(f),
+            None => paths.push(PathBuf::from(arg)),
+         },
+      }
+   }
+
+   if paths.is_empty() {
+      paths.push(PathBuf::from("."));
+   }
+
+   let mut summary = TestSummary::default();
+
+   for path in paths.iter() {
+      let path = match fs::canonicalize(path) {
+         Ok(p) => p,
+         Err(error) => exit_with_io_error(path.to_str().unwrap(), error),
+      };
+
+      for file in test_runner::collect_test_files(&path) {
+         let options = TestOptions {
+            filter,
+            warning_mode: cli.warning_mode(),
+            opt_level: cli.opt_level(),
+            module_dirs: project_module_dirs(&file),
+         };
+
+         test_runner::run_test_file(&file, &options, &mut summary);
+      }
+   }
+
+   summary.print();
+   std::process::exit(if summary.is_ok() { 0 } else { 1 })
+}
+
+/// Benchmarks the Hinton source files found in the given files and directories, or in the
+/// `benches` directory if no paths are provided. Each file is scanned, parsed, compiled, and
+/// executed as many times as passed with `--runs <n>` (10 by default), and the median time of
+/// each stage is printed. Exits with code 1 if any file could not be benchmarked.
+///
+/// # Parameters
+/// - `args`: The arguments passed to the bench command.
+/// - `cli`: The arguments passed to the Hinton CLI.
+fn run_benchmarks(args: &[String], cli: &HintonCLI) {
+   let mut paths = vec![];
+   let mut runs = Some(10);
+
+   let mut args_iter = args.iter();
+   while let Some(arg) = args_iter.next() {
+      match arg.as_str() {
+         "--runs" => runs = args_iter.next().and_then(|n| n.parse().ok()),
+         _ => match arg.strip_prefix("--runs=") {
+            Some(n) => runs = n.parse().ok(),
+            None => paths.push(PathBuf::from(arg)),
+         },
+      }
+   }
+
+   let runs = match runs {
+      Some(n) if n > 0 => n,
+      _ => {
+         eprintln!("Expected a positive number of runs.");
+         std::process::exit(64)
+      }
+   };
+
+   if paths.is_empty() {
+      paths.push(PathBuf::from("benches"));
+   }
+
+   let mut has_errors = false;
+   BenchResult::print_header();
+
+   for path in paths.iter() {
+      let path = match fs::canonicalize(path) {
+         Ok(p) => p,
+         Err(error) => exit_with_io_error(path.to_str().unwrap(), error),
+      };
+
+      for file in test_runner::collect_test_files(&path) {
+         let options = BenchOptions {
+            runs,
+            opt_level: cli.opt_level(),
+            module_dirs: project_module_dirs(&file),
+         };
+
+         match bench_file(&file, &options) {
+            Some(result) => result.print(),
+            None => has_errors = true,
+         }
+      }
+   }
+
+   if has_errors {
+      std::process::exit(1)
+   }
+}
+
+/// Formats the Hinton source files found in the given files and directories (or in the current
+/// directory if no paths are provided), rewriting the files whose formatting changed. With the
+/// `--check` flag, the files are not rewritten; instead, the files that are not formatted are
+/// listed, and the command exits with code 1 if there are any. Exits with code 65 if a file
+/// has syntax errors.
+///
+/// # Parameters
+/// - `args`: The arguments passed to the fmt command.
+fn format_files(args: &[String]) {
+   let is_check = args.iter().any(|a| a == "--check");
+   let mut paths: Vec<PathBuf> = args
+      .iter()
+      .filter(|a| *a != "--check")
+      .map(PathBuf::from)
+      .collect();
+
+   if paths.is_empty() {
+      paths.push(PathBuf::from("."));
+   }
+
+   let mut has_errors = false;
+   let mut unformatted = 0;
+
+   for path in paths.iter() {
+      if !path.exists() {
+         exit_with_io_error(path.to_str().unwrap(), io::Error::from(ErrorKind::NotFound));
+      }
+
+      for file in test_runner::collect_test_files(path) {
+         let contents = match fs::read_to_string(&file) {
+            Ok(src) => src,
+            Err(error) => exit_with_io_error(file.to_str().unwrap(), error),
+         };
+
+         let formatted = match format_source(&contents) {
+            Ok(f) => f,
+            Err(errors) => {
+               let _ = report_errors_list(&mut io::stderr(), &file, errors, &contents);
+               has_errors = true;
+               continue;
+            }
+         };
+
+         if formatted == contents {
+            continue;
          }
 
-         match error.raw_os_error() {
-            Some(code) => std::process::exit(code),
-            None => std::process::exit(70),
+         if is_check {
+            println!("'{}' is not formatted.", file.display());
+            unformatted += 1;
+         } else if let Err(error) = fs::write(&file, formatted) {
+            exit_with_io_error(file.to_str().unwrap(), error)
          }
       }
+   }
+
+   if has_errors {
+      std::process::exit(65)
+   } else if unformatted > 0 {
+      std::process::exit(1)
+   }
+}
+
+/// Gets the source directories of the Hinton project that contains a file, which are searched
+/// for the modules imported by the file. Files outside of a project have no source directories.
+///
+/// # Parameters
+/// - `filepath`: The canonical path to the file.
+fn project_module_dirs(filepath: &Path) -> Vec<PathBuf> {
+   match filepath.parent().and_then(Manifest::find) {
+      Some(Ok(manifest)) => manifest.source_dirs,
+      Some(Err(message)) => {
+         eprintln!("Invalid project manifest. {}", message);
+         std::process::exit(65)
+      }
+      None => vec![],
+   }
+}
+
+/// Loads and interprets a precompiled Hinton bytecode (`.hbc`) file.
+///
+/// # Parameters
+/// - `filename`: The path to the file to run, as provided by the programmer.
+/// - `filepath`: The canonical path to the file to run.
+/// - `script_args`: The arguments passed to the program, after the path of the file.
+/// - `cli`: The arguments passed to the Hinton CLI.
+fn run_bytecode_file(filename: &str, filepath: PathBuf, script_args: &[String], cli: &HintonCLI) {
+   let bytes = match fs::read(&filepath) {
+      Ok(b) => b,
+      Err(error) => exit_with_io_error(filename, error),
+   };
+
+   let module = match serialization::deserialize(&bytes) {
+      Ok(m) => m,
+      Err(message) => {
+         eprintln!("Could not load '{}'. {}", filename, message);
+         std::process::exit(65)
+      }
+   };
+
+   let mut vm = VM::new(filepath)
+      .with_gc_config(cli.gc_config())
+      .with_script_args(script_args.to_vec());
+
+   if cli.is_debug() {
+      vm = vm.with_debugger(Debugger::new(Box::new(CliDebugger::default())));
+   }
+
+   if cli.is_profile() {
+      vm = vm.with_profiler();
+   }
+
+   if let Some(mode) = cli.trace_mode() {
+      vm = vm.with_trace(mode);
+   }
+
+   // The source code is not available for bytecode files,
+   // so runtime errors are reported without a source snippet.
+   let result = vm.execute(module, "");
+   report_profile(&mut vm, cli);
+   exit_with_result(result)
+}
+
+/// Prints the report of the profiler attached to a VM (if any) to the standard error, and writes
+/// the measured call stacks to the file given by the `--profile-output=<path>` flag, if any.
+///
+/// # Parameters
+/// - `vm`: The VM that executed the program.
+/// - `cli`: The arguments passed to the Hinton CLI.
+fn report_profile(vm: &mut VM, cli: &HintonCLI) {
+   let profiler = match vm.take_profiler() {
+      Some(p) => p,
+      None => return,
+   };
+
+   eprint!("\n{}", profiler.report());
+
+   if let Some(path) = cli.profile_output() {
+      match fs::write(path, profiler.collapsed_stacks()) {
+         Ok(_) => eprintln!("\nThe collapsed call stacks were written to '{}'.", path),
+         Err(error) => eprintln!("\nCould not write the call stacks to '{}'. {}", path, error),
+      }
+   }
+}
+
+/// Compiles a Hinton source file into a bytecode (`.hbc`) file, which can later be executed
+/// without being parsed and compiled again. The output file defaults to the path of the source
+/// file with the `.hbc` extension, unless the `-o <path>` option is provided.
+///
+/// # Parameters
+/// - `args`: The arguments passed to the compile command.
+/// - `cli`: The arguments passed to the Hinton CLI.
+fn compile_file(args: &[String], cli: &HintonCLI) {
+   let mut input = None;
+   let mut output = None;
+
+   let mut args_iter = args.iter();
+   while let Some(arg) = args_iter.next() {
+      match arg.as_str() {
+         "-o" => output = args_iter.next().map(PathBuf::from),
+         _ => input = Some(arg),
+      }
+   }
+
+   let filename = match input {
+      Some(f) => f,
+      None => {
+         eprintln!("Expected the path to the file to compile.");
+         std::process::exit(64)
+      }
+   };
+
+   let filepath = match fs::canonicalize(filename) {
+      Ok(path) => path,
+      Err(error) => exit_with_io_error(filename, error),
    };
 
    let contents = match fs::read_to_string(filepath.clone()) {
       Ok(src) => src,
-      Err(error) => {
-         match error.kind() {
-            ErrorKind::NotFound => eprintln!("File '{}' not found.", filename),
-            ErrorKind::PermissionDenied => eprintln!("Need permission to open '{}'.", filename),
-            ErrorKind::UnexpectedEof => eprintln!("Unexpected end-of-file '{}'.", filename),
-            _ => eprintln!("Unexpected error when opening file '{}'.", filename),
+      Err(error) => exit_with_io_error(filename, error),
+   };
+
+   let ast = match Parser::parse(&contents) {
+      Ok(x) => x,
+      Err(e) => {
+         let _ = report_errors_list(&mut io::stderr(), &filepath, e, &contents);
+         std::process::exit(65)
+      }
+   };
+
+   let module = match Compiler::compile_ast_with_warnings(
+      &filepath,
+      &ast,
+      &BuiltIn::default(),
+      cli.warning_mode(),
+      cli.opt_level(),
+      &project_module_dirs(&filepath),
+   ) {
+      Ok((x, warnings)) => {
+         let _ = print_warnings_list(&mut io::stderr(), &filepath, &warnings, &contents);
+         x
+      }
+      Err(e) => {
+         let _ = report_errors_list(&mut io::stderr(), &filepath, e, &contents);
+         std::process::exit(65)
+      }
+   };
+
+   let bytes = match serialization::serialize(&module) {
+      Ok(b) => b,
+      Err(message) => {
+         eprintln!("Could not compile '{}'. {}", filename, message);
+         std::process::exit(70)
+      }
+   };
+
+   let output = output.unwrap_or_else(|| filepath.with_extension("hbc"));
+   if let Err(error) = fs::write(&output, bytes) {
+      exit_with_io_error(output.to_str().unwrap(), error)
+   }
+}
+
+/// Prints a Hinton source file with syntax highlighting, as HTML (`--format=html`) or as text
+/// with ANSI escape sequences (`--format=ansi`, the default).
+///
+/// # Parameters
+/// - `args`: The arguments passed to the highlight command.
+fn highlight_file(args: &[String]) {
+   let mut input = None;
+   let mut format = HighlightFormat::Ansi;
+
+   let mut args_iter = args.iter();
+   while let Some(arg) = args_iter.next() {
+      let name = match arg.as_str() {
+         "--format" => args_iter.next().map(|f| f.as_str()),
+         _ => arg.strip_prefix("--format="),
+      };
+
+      match name.map(HighlightFormat::from_name) {
+         Some(Some(f)) => format = f,
+         Some(None) => {
+            eprintln!("Invalid highlighting format. Expected 'html' or 'ansi'.");
+            std::process::exit(64)
          }
+         None => input = Some(arg),
+      }
+   }
 
-         match error.raw_os_error() {
-            Some(code) => std::process::exit(code),
-            None => std::process::exit(70),
+   let filename = match input {
+      Some(f) => f,
+      None => {
+         eprintln!("Expected the path to the file to highlight.");
+         std::process::exit(64)
+      }
+   };
+
+   match fs::read_to_string(filename) {
+      Ok(src) => print!("{}", highlight(&src, format)),
+      Err(error) => exit_with_io_error(filename, error),
+   }
+}
+
+/// Prints the documentation of the exported functions and classes of a Hinton module as
+/// Markdown, from the doc comments (`/// ...`) written before their declarations.
+///
+/// # Parameters
+/// - `filename`: The path to the module to document.
+fn document_file(filename: &str) {
+   let filepath = Path::new(filename);
+   let contents = match fs::read_to_string(filepath) {
+      Ok(src) => src,
+      Err(error) => exit_with_io_error(filename, error),
+   };
+
+   let module_name = filepath
+      .file_stem()
+      .map_or(filename.into(), |s| s.to_string_lossy());
+
+   match render_module_docs(&contents, &module_name) {
+      Ok(docs) => print!("{}", docs),
+      Err(errors) => {
+         let _ = report_errors_list(&mut io::stderr(), filepath, errors, &contents);
+         std::process::exit(65)
+      }
+   }
+}
+
+/// Prints the syntax tree of a Hinton source file, without compiling it, as indented text
+/// (`--format=text`, the default) or as JSON (`--format=json`).
+///
+/// # Parameters
+/// - `args`: The arguments passed to the ast command.
+fn dump_ast_file(args: &[String]) {
+   let mut input = None;
+   let mut format = AstFormat::Text;
+
+   let mut args_iter = args.iter();
+   while let Some(arg) = args_iter.next() {
+      let name = match arg.as_str() {
+         "--format" => args_iter.next().map(|f| f.as_str()),
+         _ => arg.strip_prefix("--format="),
+      };
+
+      match name.map(AstFormat::from_name) {
+         Some(Some(f)) => format = f,
+         Some(None) => {
+            eprintln!("Invalid syntax tree format. Expected 'text' or 'json'.");
+            std::process::exit(64)
          }
+         None => input = Some(arg),
+      }
+   }
+
+   let filename = match input {
+      Some(f) => f,
+      None => {
+         eprintln!("Expected the path to the file to parse.");
+         std::process::exit(64)
       }
    };
 
-   // Interprets the source contents in the VM
-   let result = VM::interpret(filepath, &contents);
+   let contents = match fs::read_to_string(filename) {
+      Ok(src) => src,
+      Err(error) => exit_with_io_error(filename, error),
+   };
 
-   // Exit the interpreter with the appropriate code
+   match dump_ast(&contents, format) {
+      Ok(dump) => print!("{}", dump),
+      Err(errors) => {
+         let _ = report_errors_list(&mut io::stderr(), Path::new(filename), errors, &contents);
+         std::process::exit(65)
+      }
+   }
+}
+
+/// Prints the bytecode of a Hinton source file, or of a precompiled Hinton bytecode (`.hbc`)
+/// file, including the bytecode of every function declared in the program. Source files are
+/// compiled with the optimizations selected by the `-O0` and `-O1` flags.
+///
+/// # Parameters
+/// - `filename`: The path to the file to disassemble.
+/// - `cli`: The command-line interface, with the flags of the command.
+fn disassemble_file(filename: &str, cli: &HintonCLI) {
+   let filepath = match fs::canonicalize(filename) {
+      Ok(path) => path,
+      Err(error) => exit_with_io_error(filename, error),
+   };
+
+   let module = if filepath.extension().is_some_and(|ext| ext == "hbc") {
+      let bytes = match fs::read(&filepath) {
+         Ok(b) => b,
+         Err(error) => exit_with_io_error(filename, error),
+      };
+
+      match serialization::deserialize(&bytes) {
+         Ok(m) => m,
+         Err(message) => {
+            eprintln!("Could not load '{}'. {}", filename, message);
+            std::process::exit(65)
+         }
+      }
+   } else {
+      let contents = match fs::read_to_string(filepath.clone()) {
+         Ok(src) => src,
+         Err(error) => exit_with_io_error(filename, error),
+      };
+
+      let ast = match Parser::parse(&contents) {
+         Ok(x) => x,
+         Err(e) => {
+            let _ = report_errors_list(&mut io::stderr(), &filepath, e, &contents);
+            std::process::exit(65)
+         }
+      };
+
+      match Compiler::compile_ast_with_warnings(
+         &filepath,
+         &ast,
+         &BuiltIn::default(),
+         WarningMode::Silence,
+         cli.opt_level(),
+         &project_module_dirs(&filepath),
+      ) {
+         Ok((x, _)) => x,
+         Err(e) => {
+            let _ = report_errors_list(&mut io::stderr(), &filepath, e, &contents);
+            std::process::exit(65)
+         }
+      }
+   };
+
+   let built_in = BuiltIn::default();
+   disassembler::disassemble_function(
+      &module,
+      &built_in.natives.get_names(),
+      &built_in.primitives.get_names(),
+   );
+}
+
+/// Reports an error that occurred while opening a file, and exits the interpreter.
+///
+/// # Parameters
+/// - `filename`: The path to the file that could not be opened.
+/// - `error`: The error that occurred.
+fn exit_with_io_error(filename: &str, error: io::Error) -> ! {
+   match error.kind() {
+      ErrorKind::NotFound => eprintln!("File '{}' not found.", filename),
+      ErrorKind::PermissionDenied => eprintln!("Need permission to open '{}'.", filename),
+      ErrorKind::UnexpectedEof => eprintln!("Unexpected end-of-file '{}'.", filename),
+      _ => eprintln!("Unexpected error when opening file '{}'.", filename),
+   }
+
+   match error.raw_os_error() {
+      Some(code) => std::process::exit(code),
+      None => std::process::exit(70),
+   }
+}
+
+/// Exits the interpreter with the appropriate code for the result of the interpretation.
+///
+/// # Parameters
+/// - `result`: The result of the interpretation.
+fn exit_with_result(result: InterpretResult) -> ! {
    match result {
       InterpretResult::ParseError => std::process::exit(65),
       InterpretResult::CompileError => std::process::exit(65),
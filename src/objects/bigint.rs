@@ -0,0 +1,362 @@
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+
+/// The base of the digits of a BigInt's magnitude.
+const BASE: u64 = 1 << 32;
+
+/// The largest power of ten that fits in a digit, used to convert BigInts to and from decimal strings.
+const DECIMAL_CHUNK: u32 = 1_000_000_000;
+
+/// The number of decimal digits in a `DECIMAL_CHUNK`.
+const DECIMAL_CHUNK_DIGITS: usize = 9;
+
+/// Represents an arbitrary-precision integer. The magnitude is stored as base-2^32 digits,
+/// from least to most significant, without leading zeros. Zero has no digits and is never negative.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BigInt {
+   negative: bool,
+   digits: Vec<u32>,
+}
+
+impl From<i64> for BigInt {
+   fn from(value: i64) -> Self {
+      let mut magnitude = value.unsigned_abs();
+      let mut digits = vec![];
+
+      while magnitude > 0 {
+         digits.push(magnitude as u32);
+         magnitude >>= 32;
+      }
+
+      BigInt {
+         negative: value < 0,
+         digits,
+      }
+   }
+}
+
+impl BigInt {
+   /// Creates a BigInt from its sign and magnitude, removing the leading zeros of the magnitude.
+   fn new(negative: bool, mut digits: Vec<u32>) -> Self {
+      while digits.last() == Some(&0) {
+         digits.pop();
+      }
+
+      BigInt {
+         negative: negative && !digits.is_empty(),
+         digits,
+      }
+   }
+
+   /// Parses a decimal integer, with an optional leading minus sign.
+   ///
+   /// # Parameters
+   /// - `src`: The decimal string.
+   ///
+   /// # Returns
+   /// - `Option<BigInt>`: The parsed integer, or `None` if the string is not a decimal integer.
+   pub fn parse(src: &str) -> Option<BigInt> {
+      let (negative, digits) = match src.strip_prefix('-') {
+         Some(rest) => (true, rest),
+         None => (false, src),
+      };
+
+      if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+         return None;
+      }
+
+      let mut magnitude = vec![];
+
+      // The leading chunk is shorter so that the remaining chunks have exactly 9 digits.
+      let first_len = match digits.len() % DECIMAL_CHUNK_DIGITS {
+         0 => DECIMAL_CHUNK_DIGITS,
+         n => n,
+      };
+
+      let mut start = 0;
+      let mut end = first_len;
+      while start < digits.len() {
+         let chunk: u32 = digits[start..end].parse().ok()?;
+         mul_small_add(&mut magnitude, DECIMAL_CHUNK, chunk);
+         start = end;
+         end += DECIMAL_CHUNK_DIGITS;
+      }
+
+      Some(BigInt::new(negative, magnitude))
+   }
+
+   /// Checks if this integer is zero.
+   pub fn is_zero(&self) -> bool {
+      self.digits.is_empty()
+   }
+
+   /// Converts this integer to an i64, if it fits.
+   pub fn to_i64(&self) -> Option<i64> {
+      if self.digits.len() > 2 {
+         return None;
+      }
+
+      let magnitude = self
+         .digits
+         .iter()
+         .rev()
+         .fold(0u64, |acc, d| (acc << 32) | *d as u64);
+
+      if self.negative {
+         0i64.checked_sub_unsigned(magnitude)
+      } else {
+         i64::try_from(magnitude).ok()
+      }
+   }
+
+   /// Converts this integer to the nearest f64. Integers too large for a float become infinite.
+   pub fn to_f64(&self) -> f64 {
+      let magnitude = self
+         .digits
+         .iter()
+         .rev()
+         .fold(0f64, |acc, d| acc * BASE as f64 + *d as f64);
+
+      if self.negative {
+         -magnitude
+      } else {
+         magnitude
+      }
+   }
+
+   /// Adds two integers.
+   pub fn add(&self, other: &BigInt) -> BigInt {
+      if self.negative == other.negative {
+         return BigInt::new(self.negative, add_magnitudes(&self.digits, &other.digits));
+      }
+
+      // The signs differ, so the smaller magnitude is subtracted from the larger one.
+      match cmp_magnitudes(&self.digits, &other.digits) {
+         Ordering::Less => BigInt::new(other.negative, sub_magnitudes(&other.digits, &self.digits)),
+         _ => BigInt::new(self.negative, sub_magnitudes(&self.digits, &other.digits)),
+      }
+   }
+
+   /// Subtracts an integer from this integer.
+   pub fn sub(&self, other: &BigInt) -> BigInt {
+      self.add(&other.neg())
+   }
+
+   /// Multiplies two integers.
+   pub fn mul(&self, other: &BigInt) -> BigInt {
+      let mut result = vec![0u32; self.digits.len() + other.digits.len()];
+
+      for (i, a) in self.digits.iter().enumerate() {
+         let mut carry = 0u64;
+
+         for (j, b) in other.digits.iter().enumerate() {
+            let product = *a as u64 * *b as u64 + result[i + j] as u64 + carry;
+            result[i + j] = product as u32;
+            carry = product >> 32;
+         }
+
+         result[i + other.digits.len()] = carry as u32;
+      }
+
+      BigInt::new(self.negative != other.negative, result)
+   }
+
+   /// Divides this integer by another, rounding the quotient towards zero. The remainder has
+   /// the same sign as this integer, just like the `/` and `%` operators of Rust's integers.
+   ///
+   /// # Returns
+   /// - `Option<(BigInt, BigInt)>`: The quotient and the remainder, or `None` if the divisor is zero.
+   pub fn div_rem(&self, other: &BigInt) -> Option<(BigInt, BigInt)> {
+      if other.is_zero() {
+         return None;
+      }
+
+      let mut quotient = vec![0u32; self.digits.len()];
+      let mut remainder: Vec<u32> = vec![];
+
+      // Binary long division, from the most significant bit of the dividend.
+      for i in (0..self.digits.len() * 32).rev() {
+         shift_left_one(&mut remainder);
+         if (self.digits[i / 32] >> (i % 32)) & 1 == 1 {
+            match remainder.first_mut() {
+               Some(d) => *d |= 1,
+               None => remainder.push(1),
+            }
+         }
+
+         if cmp_magnitudes(&remainder, &other.digits) != Ordering::Less {
+            remainder = sub_magnitudes(&remainder, &other.digits);
+            quotient[i / 32] |= 1 << (i % 32);
+         }
+      }
+
+      Some((
+         BigInt::new(self.negative != other.negative, quotient),
+         BigInt::new(self.negative, remainder),
+      ))
+   }
+
+   /// Raises this integer to a power.
+   pub fn pow(&self, mut exp: u32) -> BigInt {
+      let mut result = BigInt::from(1);
+      let mut base = self.clone();
+
+      while exp > 0 {
+         if exp & 1 == 1 {
+            result = result.mul(&base);
+         }
+
+         exp >>= 1;
+         if exp > 0 {
+            base = base.mul(&base);
+         }
+      }
+
+      result
+   }
+
+   /// Negates this integer.
+   pub fn neg(&self) -> BigInt {
+      BigInt::new(!self.negative, self.digits.clone())
+   }
+}
+
+impl Ord for BigInt {
+   fn cmp(&self, other: &Self) -> Ordering {
+      match (self.negative, other.negative) {
+         (false, true) => Ordering::Greater,
+         (true, false) => Ordering::Less,
+         (false, false) => cmp_magnitudes(&self.digits, &other.digits),
+         (true, true) => cmp_magnitudes(&other.digits, &self.digits),
+      }
+   }
+}
+
+impl PartialOrd for BigInt {
+   fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+      Some(self.cmp(other))
+   }
+}
+
+impl fmt::Display for BigInt {
+   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      if self.is_zero() {
+         return f.write_str("0");
+      }
+
+      // Splits the magnitude into chunks of 9 decimal digits, from least to most significant.
+      let mut magnitude = self.digits.clone();
+      let mut chunks = vec![];
+      while !magnitude.is_empty() {
+         chunks.push(div_small(&mut magnitude, DECIMAL_CHUNK));
+      }
+
+      let mut s = String::from(if self.negative { "-" } else { "" });
+      s += &chunks.last().unwrap().to_string();
+      for chunk in chunks.iter().rev().skip(1) {
+         s += &format!("{:0width$}", chunk, width = DECIMAL_CHUNK_DIGITS);
+      }
+
+      f.write_str(&s)
+   }
+}
+
+/// Compares two magnitudes without leading zeros.
+fn cmp_magnitudes(a: &[u32], b: &[u32]) -> Ordering {
+   a.len()
+      .cmp(&b.len())
+      .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+/// Adds two magnitudes.
+fn add_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+   let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+   let mut result = Vec::with_capacity(long.len() + 1);
+   let mut carry = 0u64;
+
+   for (i, d) in long.iter().enumerate() {
+      let sum = *d as u64 + *short.get(i).unwrap_or(&0) as u64 + carry;
+      result.push(sum as u32);
+      carry = sum >> 32;
+   }
+
+   if carry > 0 {
+      result.push(carry as u32);
+   }
+
+   result
+}
+
+/// Subtracts the magnitude `b` from the magnitude `a`, which must not be smaller than `b`.
+fn sub_magnitudes(a: &[u32], b: &[u32]) -> Vec<u32> {
+   let mut result = Vec::with_capacity(a.len());
+   let mut borrow = 0i64;
+
+   for (i, d) in a.iter().enumerate() {
+      let mut diff = *d as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+      borrow = if diff < 0 {
+         diff += BASE as i64;
+         1
+      } else {
+         0
+      };
+      result.push(diff as u32);
+   }
+
+   while result.last() == Some(&0) {
+      result.pop();
+   }
+
+   result
+}
+
+/// Shifts a magnitude one bit to the left.
+fn shift_left_one(digits: &mut Vec<u32>) {
+   let mut carry = 0;
+
+   for d in digits.iter_mut() {
+      let next_carry = *d >> 31;
+      *d = (*d << 1) | carry;
+      carry = next_carry;
+   }
+
+   if carry > 0 {
+      digits.push(carry);
+   }
+}
+
+/// Multiplies a magnitude by a small factor and adds a small term to it, in place.
+fn mul_small_add(digits: &mut Vec<u32>, factor: u32, term: u32) {
+   let mut carry = term as u64;
+
+   for d in digits.iter_mut() {
+      let product = *d as u64 * factor as u64 + carry;
+      *d = product as u32;
+      carry = product >> 32;
+   }
+
+   if carry > 0 {
+      digits.push(carry as u32);
+   }
+}
+
+/// Divides a magnitude by a small divisor in place, removing the leading zeros of the quotient.
+///
+/// # Returns
+/// - `u32`: The remainder of the division.
+fn div_small(digits: &mut Vec<u32>, divisor: u32) -> u32 {
+   let mut remainder = 0u64;
+
+   for d in digits.iter_mut().rev() {
+      let current = (remainder << 32) | *d as u64;
+      *d = (current / divisor as u64) as u32;
+      remainder = current % divisor as u64;
+   }
+
+   while digits.last() == Some(&0) {
+      digits.pop();
+   }
+
+   remainder as u32
+}
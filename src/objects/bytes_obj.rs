@@ -0,0 +1,184 @@
+use crate::objects::Object;
+
+/// The encodings supported when converting between strings and Bytes objects.
+const ENCODINGS: [&str; 5] = ["utf-8", "ascii", "latin-1", "hex", "base64"];
+
+/// Normalizes the name of an encoding, so that `UTF8`, `utf_8`, and `utf-8` are the same encoding.
+fn normalize_encoding(encoding: &str) -> String {
+   match encoding.to_lowercase().replace('_', "-").as_str() {
+      "utf8" => String::from("utf-8"),
+      "latin1" => String::from("latin-1"),
+      e => e.to_string(),
+   }
+}
+
+/// The error message for an encoding that is not supported.
+fn unknown_encoding(encoding: &str) -> String {
+   format!(
+      "Unknown encoding '{}'. Expected one of: {}.",
+      encoding,
+      ENCODINGS.join(", ")
+   )
+}
+
+/// Encodes a string into binary data.
+///
+/// # Parameters
+/// - `s`: The string to encode.
+/// - `encoding`: The name of the encoding (e.g., `utf-8`, `ascii`, `latin-1`, `hex`, or `base64`).
+///
+/// # Returns
+/// - `Result<Vec<u8>, String>`: The encoded bytes, or an error message if the encoding is
+///   unknown or the string cannot be represented in the encoding.
+pub fn encode(s: &str, encoding: &str) -> Result<Vec<u8>, String> {
+   match normalize_encoding(encoding).as_str() {
+      "utf-8" => Ok(s.as_bytes().to_vec()),
+      "ascii" => s
+         .chars()
+         .map(|c| if c.is_ascii() { Some(c as u8) } else { None })
+         .collect::<Option<Vec<u8>>>()
+         .ok_or_else(|| String::from("The string contains non-ASCII characters.")),
+      "latin-1" => s
+         .chars()
+         .map(|c| if (c as u32) < 256 { Some(c as u8) } else { None })
+         .collect::<Option<Vec<u8>>>()
+         .ok_or_else(|| String::from("The string contains characters outside of Latin-1.")),
+      "hex" => {
+         let digits: Vec<u32> = s
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| c.to_digit(16))
+            .collect::<Option<Vec<u32>>>()
+            .ok_or_else(|| String::from("The string contains non-hexadecimal characters."))?;
+
+         if !digits.len().is_multiple_of(2) {
+            return Err(String::from(
+               "Hexadecimal strings must have an even number of digits.",
+            ));
+         }
+
+         Ok(digits
+            .chunks(2)
+            .map(|pair| (pair[0] * 16 + pair[1]) as u8)
+            .collect())
+      }
+      "base64" => from_base64(s),
+      _ => Err(unknown_encoding(encoding)),
+   }
+}
+
+/// Decodes binary data into a string.
+///
+/// # Parameters
+/// - `bytes`: The bytes to decode.
+/// - `encoding`: The name of the encoding (e.g., `utf-8`, `ascii`, `latin-1`, `hex`, or `base64`).
+///
+/// # Returns
+/// - `Result<String, String>`: The decoded string, or an error message if the encoding is
+///   unknown or the bytes are not valid in the encoding.
+pub fn decode(bytes: &[u8], encoding: &str) -> Result<String, String> {
+   match normalize_encoding(encoding).as_str() {
+      "utf-8" => {
+         String::from_utf8(bytes.to_vec()).map_err(|_| String::from("The bytes are not valid UTF-8."))
+      }
+      "ascii" if bytes.is_ascii() => Ok(bytes.iter().map(|b| *b as char).collect()),
+      "ascii" => Err(String::from("The bytes are not valid ASCII.")),
+      "latin-1" => Ok(bytes.iter().map(|b| *b as char).collect()),
+      "hex" => Ok(to_hex(bytes)),
+      "base64" => Ok(to_base64(bytes)),
+      _ => Err(unknown_encoding(encoding)),
+   }
+}
+
+/// Converts binary data into a string of lowercase hexadecimal digits.
+pub fn to_hex(bytes: &[u8]) -> String {
+   bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// The digits of the standard Base64 alphabet.
+const BASE64_DIGITS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Converts binary data into a padded string of standard Base64 digits.
+pub fn to_base64(bytes: &[u8]) -> String {
+   let mut text = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+   for chunk in bytes.chunks(3) {
+      let group = chunk
+         .iter()
+         .enumerate()
+         .fold(0u32, |g, (i, b)| g | (*b as u32) << (16 - 8 * i));
+
+      for i in 0..4 {
+         if i <= chunk.len() {
+            text.push(BASE64_DIGITS[(group >> (18 - 6 * i) & 0x3F) as usize] as char);
+         } else {
+            text.push('=');
+         }
+      }
+   }
+
+   text
+}
+
+/// Converts a string of Base64 digits into binary data. Both the standard and the URL-safe
+/// alphabets are accepted, and the padding and whitespace are optional.
+pub fn from_base64(s: &str) -> Result<Vec<u8>, String> {
+   let digits: Vec<u32> = s
+      .trim_end_matches(|c: char| c == '=' || c.is_whitespace())
+      .chars()
+      .filter(|c| !c.is_whitespace())
+      .map(|c| match c {
+         'A'..='Z' => Some(c as u32 - 'A' as u32),
+         'a'..='z' => Some(c as u32 - 'a' as u32 + 26),
+         '0'..='9' => Some(c as u32 - '0' as u32 + 52),
+         '+' | '-' => Some(62),
+         '/' | '_' => Some(63),
+         _ => None,
+      })
+      .collect::<Option<Vec<u32>>>()
+      .ok_or_else(|| String::from("The string contains non-Base64 characters."))?;
+
+   if digits.len() % 4 == 1 {
+      return Err(String::from("The string is not valid Base64."));
+   }
+
+   let mut bytes = Vec::with_capacity(digits.len() * 3 / 4);
+   for chunk in digits.chunks(4) {
+      let group = chunk
+         .iter()
+         .enumerate()
+         .fold(0u32, |g, (i, d)| g | d << (18 - 6 * i));
+
+      for i in 0..chunk.len() - 1 {
+         bytes.push((group >> (16 - 8 * i)) as u8);
+      }
+   }
+
+   Ok(bytes)
+}
+
+/// Gets the binary data of a String (encoded as UTF-8) or Bytes object.
+///
+/// # Returns
+/// - `Option<Vec<u8>>`: The bytes, or `None` if the object is neither a String nor Bytes.
+pub fn from_object(obj: &Object) -> Option<Vec<u8>> {
+   match obj {
+      Object::String(s) => Some(s.as_bytes().to_vec()),
+      Object::Bytes(b) => Some(b.borrow().clone()),
+      _ => None,
+   }
+}
+
+/// Converts an array of Hinton integers into binary data.
+///
+/// # Returns
+/// - `Option<Vec<u8>>`: The bytes, or `None` if an item is not an Int from 0 to 255.
+pub fn from_int_array(items: &[Object]) -> Option<Vec<u8>> {
+   items
+      .iter()
+      .map(|b| match b {
+         Object::Int(i) if (0..=255).contains(i) => Some(*i as u8),
+         _ => None,
+      })
+      .collect()
+}
@@ -0,0 +1,46 @@
+use crate::objects::Object;
+use std::collections::VecDeque;
+
+/// Represents the queue of a Hinton channel, through which the green threads of a program
+/// send values to each other. Channels are unbounded, so sending a value never waits, and
+/// values are received in the order they were sent.
+#[derive(Default)]
+pub struct ChannelObject {
+   /// The values sent through the channel that have not been received yet.
+   items: VecDeque<Object>,
+}
+
+impl ChannelObject {
+   /// Adds a value to the end of the channel's queue.
+   pub fn send(&mut self, value: Object) {
+      self.items.push_back(value);
+   }
+
+   /// Removes the value at the front of the channel's queue.
+   ///
+   /// # Returns
+   /// - `Option<Object>`: The oldest value in the channel, or `None` if the channel is empty.
+   pub fn recv(&mut self) -> Option<Object> {
+      self.items.pop_front()
+   }
+
+   /// Gets the number of values waiting in the channel.
+   pub fn len(&self) -> usize {
+      self.items.len()
+   }
+
+   /// Checks if the channel has no values waiting to be received.
+   pub fn is_empty(&self) -> bool {
+      self.items.is_empty()
+   }
+
+   /// Gets an iterator over the values waiting in the channel.
+   pub fn iter(&self) -> impl Iterator<Item = &Object> {
+      self.items.iter()
+   }
+
+   /// Removes every value waiting in the channel.
+   pub fn take(&mut self) -> VecDeque<Object> {
+      std::mem::take(&mut self.items)
+   }
+}
@@ -12,6 +12,12 @@ pub struct ClassObject {
    pub name: String,
    pub members: HashMap<String, ClassField>,
    pub statics: HashMap<String, ClassField>,
+   pub superclass: Option<Rc<RefCell<ClassObject>>>,
+   /// The interfaces implemented by this class, including those implemented by its superclasses.
+   pub interfaces: Vec<Rc<InterfaceObject>>,
+   /// The layout of the instances of this class. It is created along with the first instance of
+   /// the class, and discarded when a member is added to the class.
+   layout: Option<Rc<InstanceLayout>>,
 }
 
 /// Implements the display trait for Hinton class objects.
@@ -41,9 +47,93 @@ impl ClassObject {
          name: name.to_string(),
          members: HashMap::new(),
          statics: HashMap::new(),
+         superclass: None,
+         interfaces: vec![],
+         layout: None,
       }
    }
 
+   /// Adds a non-static member to this class. Instances created before the member was added
+   /// keep the previous layout of the class.
+   ///
+   /// # Arguments
+   /// * `name`: The name of the member.
+   /// * `field`: The member's value and configuration mode.
+   pub fn add_member(&mut self, name: String, field: ClassField) {
+      self.members.insert(name, field);
+      self.layout = None;
+   }
+
+   /// Checks if this class is, or inherits from, another class.
+   ///
+   /// # Arguments
+   /// * `other`: The other class.
+   pub fn is_subclass_of(&self, other: &Rc<RefCell<ClassObject>>) -> bool {
+      if std::ptr::eq(self, other.as_ptr()) {
+         return true;
+      }
+
+      match &self.superclass {
+         Some(s) => s.borrow().is_subclass_of(other),
+         None => false,
+      }
+   }
+
+   /// Checks if this class implements an interface.
+   ///
+   /// # Arguments
+   /// * `interface`: The interface.
+   pub fn implements(&self, interface: &Rc<InterfaceObject>) -> bool {
+      self.interfaces.iter().any(|i| Rc::ptr_eq(i, interface))
+   }
+
+   /// Checks that this class defines the methods required by an interface, and adds the
+   /// interface to the list of interfaces implemented by the class.
+   ///
+   /// # Arguments
+   /// * `interface`: The interface implemented by the class.
+   ///
+   /// # Returns:
+   /// Result<(), RuntimeResult>
+   pub fn implement(&mut self, interface: Rc<InterfaceObject>) -> Result<(), RuntimeResult> {
+      for (name, arity) in interface.methods.iter() {
+         let field = match self.members.get(name) {
+            Some(field) if field.is_public() => field,
+            _ => {
+               return Err(RuntimeResult::Error {
+                  error: RuntimeErrorType::InstanceError,
+                  message: format!(
+                     "Class '{}' does not implement the public method '{}' of interface '{}'.",
+                     self.name, name, interface.name
+                  ),
+               })
+            }
+         };
+
+         let accepts_arity = match &*field.value {
+            Object::Function(f) => f.borrow().accepts_arity(*arity),
+            Object::Closure(c) => c.function.borrow().accepts_arity(*arity),
+            _ => false,
+         };
+
+         if !accepts_arity {
+            return Err(RuntimeResult::Error {
+               error: RuntimeErrorType::InstanceError,
+               message: format!(
+                  "Method '{}' of class '{}' must accept {} argument(s) to implement interface '{}'.",
+                  name, self.name, arity, interface.name
+               ),
+            });
+         }
+      }
+
+      if !self.implements(&interface) {
+         self.interfaces.push(interface);
+      }
+
+      Ok(())
+   }
+
    /// Gets a non-static property from this class.
    ///
    /// # Arguments
@@ -114,14 +204,87 @@ impl ClassObject {
                Ok(*field.value.clone())
             }
          }
-         None => Err(RuntimeResult::Error {
-            error: RuntimeErrorType::ReferenceError,
-            message: format!("Property '{}' not defined in class '{}'.", prop_name, self.name),
-         }),
+         None => Err(self.missing_static_prop(&prop_name)),
+      }
+   }
+
+   /// Modifies the value of an existing static property of this class.
+   ///
+   /// # Arguments
+   /// * `prop_name`: The name of the property to be modified.
+   /// * `val`: The new value to assign to the property.
+   ///
+   /// # Returns:
+   /// Result<Object, RuntimeResult>
+   ///
+   /// # Examples
+   ///
+   /// ```
+   /// let counter = Class::new("Counter");
+   /// // ...
+   /// let prop_obj = counter.set_static_prop("count".to_string(), Object::Int(0i64));
+   /// ```
+   pub fn set_static_prop(&mut self, prop_name: String, val: Object) -> Result<Object, RuntimeResult> {
+      match self.statics.get_mut(&prop_name) {
+         Some(field) => {
+            if !field.is_public() {
+               Err(RuntimeResult::Error {
+                  error: RuntimeErrorType::ReferenceError,
+                  message: format!(
+                     "Cannot access private property '{}' in class '{}'.",
+                     prop_name, self.name
+                  ),
+               })
+            } else if field.is_constant() {
+               Err(RuntimeResult::Error {
+                  error: RuntimeErrorType::ReferenceError,
+                  message: format!(
+                     "Cannot reassign to immutable property '{}' in class '{}'.",
+                     prop_name, self.name
+                  ),
+               })
+            } else {
+               *field.value = val.clone();
+               Ok(val)
+            }
+         }
+         None => Err(self.missing_static_prop(&prop_name)),
+      }
+   }
+
+   /// Creates the error for accessing a static property that is not defined in this class.
+   /// If the class has a non-static property with that name, the error says so.
+   fn missing_static_prop(&self, prop_name: &str) -> RuntimeResult {
+      let message = if self.members.contains_key(prop_name) {
+         format!(
+            "Cannot access non-static property '{}' without an instance of '{}'.",
+            prop_name, self.name
+         )
+      } else {
+         format!("Property '{}' not defined in class '{}'.", prop_name, self.name)
+      };
+
+      RuntimeResult::Error {
+         error: RuntimeErrorType::ReferenceError,
+         message,
       }
    }
 }
 
+/// Represents a Hinton interface object. An interface lists the methods (and their number
+/// of parameters) that the classes which implement the interface must define.
+#[derive(Clone)]
+pub struct InterfaceObject {
+   pub name: String,
+   pub methods: Vec<(String, u8)>,
+}
+
+impl fmt::Display for InterfaceObject {
+   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+      write!(f, "<Interface '{}' at {:p}>", self.name, self as *const _)
+   }
+}
+
 /// Represents a Hinton class field.
 #[derive(Clone)]
 pub struct ClassField {
@@ -157,11 +320,36 @@ impl ClassField {
    }
 }
 
+/// Represents the layout of the instances of a class, which maps the names of the class's
+/// non-static members to the slots where the instances store them. The instances created
+/// from a class share its layout until a member is added to the class.
+pub struct InstanceLayout {
+   /// The names of the members, in the order of their slots.
+   pub names: Vec<String>,
+   /// The slots of the members, by name.
+   pub slots: HashMap<String, usize>,
+}
+
+impl InstanceLayout {
+   /// Creates the layout for the non-static members of a class.
+   ///
+   /// # Arguments
+   /// * `members`: The non-static members of the class.
+   fn new(members: &HashMap<String, ClassField>) -> Self {
+      let names: Vec<String> = members.keys().cloned().collect();
+      let slots = names.iter().enumerate().map(|(i, n)| (n.clone(), i)).collect();
+      Self { names, slots }
+   }
+}
+
 /// Represents a Hinton Instance object.
 #[derive(Clone)]
 pub struct InstanceObject {
    pub class: Rc<RefCell<ClassObject>>,
-   pub members: HashMap<String, ClassField>,
+   /// The layout of the instance's members.
+   pub layout: Rc<InstanceLayout>,
+   /// The instance's members, stored in the slots assigned to them by the layout.
+   pub fields: Vec<ClassField>,
 }
 
 impl fmt::Display for InstanceObject {
@@ -176,6 +364,39 @@ impl fmt::Display for InstanceObject {
 }
 
 impl InstanceObject {
+   /// Creates an instance of a class, whose members are initialized with a copy
+   /// of the non-static members of the class.
+   ///
+   /// # Arguments
+   /// * `class`: The class of the instance.
+   pub fn new(class: Rc<RefCell<ClassObject>>) -> Self {
+      let (layout, fields) = {
+         let mut c = class.borrow_mut();
+
+         if c.layout.is_none() {
+            c.layout = Some(Rc::new(InstanceLayout::new(&c.members)));
+         }
+
+         let layout = c.layout.clone().unwrap();
+         let fields = layout.names.iter().map(|n| c.members[n].clone()).collect();
+         (layout, fields)
+      };
+
+      Self {
+         class,
+         layout,
+         fields,
+      }
+   }
+
+   /// Gets a member of this instance, regardless of whether it is public or not.
+   ///
+   /// # Arguments
+   /// * `name`: The name of the member.
+   pub fn field(&self, name: &str) -> Option<&ClassField> {
+      self.layout.slots.get(name).map(|slot| &self.fields[*slot])
+   }
+
    /// Gets a bound-member property from this instance.
    ///
    /// # Arguments
@@ -192,7 +413,7 @@ impl InstanceObject {
    /// let prop_obj = vec_2d.get_prop("magnitude".to_string());
    /// ```
    pub fn get_prop(&self, prop_name: String) -> Result<Object, RuntimeResult> {
-      match self.members.get(&prop_name) {
+      match self.field(&prop_name) {
          Some(field) => {
             if !field.is_public() {
                Err(RuntimeResult::Error {
@@ -207,14 +428,7 @@ impl InstanceObject {
                Ok(*field.value.clone())
             }
          }
-         None => Err(RuntimeResult::Error {
-            error: RuntimeErrorType::ReferenceError,
-            message: format!(
-               "Property '{}' not defined in object of type '{}'.",
-               prop_name,
-               self.class.borrow().name
-            ),
-         }),
+         None => Err(self.missing_prop(&prop_name)),
       }
    }
 
@@ -235,8 +449,10 @@ impl InstanceObject {
    /// let prop_obj = vec_2d.set_prop("x".to_string(), Object::Int(55i64));
    /// ```
    pub fn set_prop(&mut self, prop_name: String, val: Object) -> Result<Object, RuntimeResult> {
-      match self.members.get_mut(&prop_name) {
-         Some(field) => {
+      match self.layout.slots.get(&prop_name) {
+         Some(slot) => {
+            let field = &mut self.fields[*slot];
+
             if !field.is_public() {
                Err(RuntimeResult::Error {
                   error: RuntimeErrorType::ReferenceError,
@@ -260,14 +476,40 @@ impl InstanceObject {
                Ok(val)
             }
          }
-         None => Err(RuntimeResult::Error {
-            error: RuntimeErrorType::ReferenceError,
-            message: format!(
-               "Property '{}' not defined in object of type '{}'.",
-               prop_name,
-               self.class.borrow().name
-            ),
-         }),
+         None => Err(self.missing_prop(&prop_name)),
+      }
+   }
+
+   /// Creates the error for accessing a property that is not defined in this instance.
+   /// If the instance's class has a static property with that name, the error says so.
+   fn missing_prop(&self, prop_name: &str) -> RuntimeResult {
+      let class = self.class.borrow();
+
+      let message = if class.statics.contains_key(prop_name) {
+         format!(
+            "Cannot access static property '{}' through an instance of '{}'.",
+            prop_name, class.name
+         )
+      } else if self.field(&format!("get {}", prop_name)).is_some() {
+         format!(
+            "Property '{}' of '{}' does not have a setter.",
+            prop_name, class.name
+         )
+      } else if self.field(&format!("set {}", prop_name)).is_some() {
+         format!(
+            "Property '{}' of '{}' does not have a getter.",
+            prop_name, class.name
+         )
+      } else {
+         format!(
+            "Property '{}' not defined in object of type '{}'.",
+            prop_name, class.name
+         )
+      };
+
+      RuntimeResult::Error {
+         error: RuntimeErrorType::ReferenceError,
+         message,
       }
    }
 }
@@ -279,6 +521,69 @@ pub struct BoundMethod {
    pub method: ClosureObject,
 }
 
+impl BoundMethod {
+   /// Finds a method of an instance, and binds it to the instance. Unlike `get_prop`, private
+   /// methods are also found, because the methods found this way are called by the VM itself.
+   ///
+   /// # Arguments
+   /// * `instance`: The instance that owns the method.
+   /// * `name`: The name of the method.
+   ///
+   /// # Returns:
+   /// Option<BoundMethod>
+   pub fn find(instance: &Rc<RefCell<InstanceObject>>, name: &str) -> Option<BoundMethod> {
+      let method = match &*instance.borrow().field(name)?.value {
+         Object::Closure(c) => c.clone(),
+         Object::Function(f) => ClosureObject {
+            function: f.clone(),
+            up_values: vec![],
+         },
+         _ => return None,
+      };
+
+      Some(BoundMethod {
+         receiver: instance.clone(),
+         method,
+      })
+   }
+
+   /// Finds the getter or setter of an instance's property, and binds it to the instance.
+   /// Accessors are stored as methods named `get <prop>` or `set <prop>`.
+   ///
+   /// # Arguments
+   /// * `instance`: The instance that owns the property.
+   /// * `kind`: The kind of accessor, either `"get"` or `"set"`.
+   /// * `prop_name`: The name of the property.
+   ///
+   /// # Returns:
+   /// Option<Result<BoundMethod, RuntimeResult>>
+   pub fn find_accessor(
+      instance: &Rc<RefCell<InstanceObject>>,
+      kind: &str,
+      prop_name: &str,
+   ) -> Option<Result<BoundMethod, RuntimeResult>> {
+      let accessor_name = format!("{} {}", kind, prop_name);
+
+      let is_public = match instance.borrow().field(&accessor_name) {
+         Some(field) => field.is_public(),
+         None => return None,
+      };
+
+      if !is_public {
+         return Some(Err(RuntimeResult::Error {
+            error: RuntimeErrorType::ReferenceError,
+            message: format!(
+               "Cannot access private property '{}' in object of type '{}'.",
+               prop_name,
+               instance.borrow().class.borrow().name
+            ),
+         }));
+      }
+
+      BoundMethod::find(instance, &accessor_name).map(Ok)
+   }
+}
+
 impl fmt::Display for BoundMethod {
    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
       let receiver = &self.receiver.borrow();
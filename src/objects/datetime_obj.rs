@@ -0,0 +1,442 @@
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The number of milliseconds in a day.
+const MILLIS_PER_DAY: i64 = 86_400_000;
+
+/// The first instant that can be represented by a DateTime object: `0001-01-01T00:00:00Z`.
+const MIN_MILLIS: i64 = -62_135_596_800_000;
+
+/// The last instant that can be represented by a DateTime object: `9999-12-31T23:59:59.999Z`.
+const MAX_MILLIS: i64 = 253_402_300_799_999;
+
+/// The names of the months, from January.
+const MONTH_NAMES: [&str; 12] = [
+   "January",
+   "February",
+   "March",
+   "April",
+   "May",
+   "June",
+   "July",
+   "August",
+   "September",
+   "October",
+   "November",
+   "December",
+];
+
+/// The names of the days of the week, from Monday.
+const WEEKDAY_NAMES: [&str; 7] = [
+   "Monday",
+   "Tuesday",
+   "Wednesday",
+   "Thursday",
+   "Friday",
+   "Saturday",
+   "Sunday",
+];
+
+/// Represents a Hinton DateTime object: an instant in UTC, with millisecond precision,
+/// between the years 1 and 9999.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DateTimeObject {
+   /// The number of milliseconds since the Unix epoch (`1970-01-01T00:00:00Z`).
+   millis: i64,
+}
+
+/// The calendar and clock components of a DateTime object.
+pub struct DateComponents {
+   pub year: i64,
+   pub month: i64,
+   pub day: i64,
+   pub hour: i64,
+   pub minute: i64,
+   pub second: i64,
+   pub millisecond: i64,
+   /// The ISO day of the week, from 1 (Monday) to 7 (Sunday).
+   pub weekday: i64,
+   /// The day of the year, from 1 to 366.
+   pub day_of_year: i64,
+}
+
+/// Checks if a year is a leap year in the proleptic Gregorian calendar.
+fn is_leap_year(year: i64) -> bool {
+   (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+/// Gets the number of days in a month of a year.
+fn days_in_month(year: i64, month: i64) -> i64 {
+   match month {
+      2 if is_leap_year(year) => 29,
+      2 => 28,
+      4 | 6 | 9 | 11 => 30,
+      _ => 31,
+   }
+}
+
+/// Converts a date of the proleptic Gregorian calendar into the number of days since the
+/// Unix epoch, using Howard Hinnant's `days_from_civil` algorithm.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+   let year = if month <= 2 { year - 1 } else { year };
+   let era = year.div_euclid(400);
+   let year_of_era = year - era * 400;
+   let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+   let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+   era * 146_097 + day_of_era - 719_468
+}
+
+/// Converts a number of days since the Unix epoch into a date of the proleptic Gregorian
+/// calendar, using Howard Hinnant's `civil_from_days` algorithm.
+///
+/// # Returns
+/// - `(i64, i64, i64)`: The year, month, and day.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+   let days = days + 719_468;
+   let era = days.div_euclid(146_097);
+   let day_of_era = days - era * 146_097;
+   let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+   let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+   let shifted_month = (5 * day_of_year + 2) / 153;
+   let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+   let month = if shifted_month < 10 {
+      shifted_month + 3
+   } else {
+      shifted_month - 9
+   };
+
+   let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+   (year, month, day)
+}
+
+impl DateTimeObject {
+   /// Creates a DateTime object from a number of milliseconds since the Unix epoch.
+   ///
+   /// # Returns
+   /// - `Option<DateTimeObject>`: The DateTime, or `None` if it is outside the supported range.
+   pub fn from_millis(millis: i64) -> Option<DateTimeObject> {
+      if (MIN_MILLIS..=MAX_MILLIS).contains(&millis) {
+         Some(DateTimeObject { millis })
+      } else {
+         None
+      }
+   }
+
+   /// Creates a DateTime object from a Unix timestamp, in seconds.
+   pub fn from_timestamp(seconds: f64) -> Option<DateTimeObject> {
+      let millis = (seconds * 1000.0).round();
+
+      if millis.is_finite() && millis.abs() < i64::MAX as f64 {
+         DateTimeObject::from_millis(millis as i64)
+      } else {
+         None
+      }
+   }
+
+   /// Creates a DateTime object from a reading of the system clock.
+   pub fn from_system_time(time: SystemTime) -> DateTimeObject {
+      let millis = match time.duration_since(UNIX_EPOCH) {
+         Ok(d) => d.as_millis() as i64,
+         Err(e) => -(e.duration().as_millis() as i64),
+      };
+
+      DateTimeObject { millis }
+   }
+
+   /// Creates a DateTime object from its calendar and clock components.
+   ///
+   /// # Parameters
+   /// - `date`: The year, month (from 1 to 12), and day (from 1).
+   /// - `time`: The hour, minute, second, and millisecond.
+   ///
+   /// # Returns
+   /// - `Result<DateTimeObject, String>`: The DateTime, or a message describing the invalid component.
+   pub fn from_components(
+      date: (i64, i64, i64),
+      time: (i64, i64, i64, i64),
+   ) -> Result<DateTimeObject, String> {
+      let (year, month, day) = date;
+      let (hour, minute, second, millisecond) = time;
+
+      let invalid = |name: &str, value: i64| Err(format!("Invalid {} '{}'.", name, value));
+
+      if !(1..=9999).contains(&year) {
+         return invalid("year", year);
+      } else if !(1..=12).contains(&month) {
+         return invalid("month", month);
+      } else if day < 1 || day > days_in_month(year, month) {
+         return invalid("day", day);
+      } else if !(0..24).contains(&hour) {
+         return invalid("hour", hour);
+      } else if !(0..60).contains(&minute) {
+         return invalid("minute", minute);
+      } else if !(0..60).contains(&second) {
+         return invalid("second", second);
+      } else if !(0..1000).contains(&millisecond) {
+         return invalid("millisecond", millisecond);
+      }
+
+      let millis_of_day = ((hour * 60 + minute) * 60 + second) * 1000 + millisecond;
+      Ok(DateTimeObject {
+         millis: days_from_civil(year, month, day) * MILLIS_PER_DAY + millis_of_day,
+      })
+   }
+
+   /// Gets the number of milliseconds since the Unix epoch.
+   pub fn millis(&self) -> i64 {
+      self.millis
+   }
+
+   /// Gets the Unix timestamp of this DateTime, in seconds.
+   pub fn timestamp(&self) -> f64 {
+      self.millis as f64 / 1000.0
+   }
+
+   /// Gets the calendar and clock components of this DateTime.
+   pub fn components(&self) -> DateComponents {
+      let days = self.millis.div_euclid(MILLIS_PER_DAY);
+      let millis_of_day = self.millis.rem_euclid(MILLIS_PER_DAY);
+      let (year, month, day) = civil_from_days(days);
+
+      DateComponents {
+         year,
+         month,
+         day,
+         hour: millis_of_day / 3_600_000,
+         minute: millis_of_day / 60_000 % 60,
+         second: millis_of_day / 1000 % 60,
+         millisecond: millis_of_day % 1000,
+         // The Unix epoch was a Thursday.
+         weekday: (days + 3).rem_euclid(7) + 1,
+         day_of_year: days - days_from_civil(year, 1, 1) + 1,
+      }
+   }
+
+   /// Adds a number of milliseconds to this DateTime.
+   ///
+   /// # Returns
+   /// - `Option<DateTimeObject>`: The new DateTime, or `None` if it is outside the supported range.
+   pub fn add_millis(&self, millis: i64) -> Option<DateTimeObject> {
+      DateTimeObject::from_millis(self.millis.checked_add(millis)?)
+   }
+
+   /// Adds a number of calendar months to this DateTime. If the day does not exist in the
+   /// resulting month, the last day of the month is used (e.g., `Jan 31 + 1 month = Feb 28`).
+   ///
+   /// # Returns
+   /// - `Option<DateTimeObject>`: The new DateTime, or `None` if it is outside the supported range.
+   pub fn add_months(&self, months: i64) -> Option<DateTimeObject> {
+      let c = self.components();
+      let total_months = (c.year * 12 + c.month - 1).checked_add(months)?;
+      let (year, month) = (total_months.div_euclid(12), total_months.rem_euclid(12) + 1);
+
+      if !(1..=9999).contains(&year) {
+         return None;
+      }
+
+      let day = c.day.min(days_in_month(year, month));
+      DateTimeObject::from_components((year, month, day), (c.hour, c.minute, c.second, c.millisecond)).ok()
+   }
+
+   /// Formats this DateTime with a format string. The format string supports the following
+   /// specifiers: `%Y` (year), `%y` (two-digit year), `%m` (month), `%d` (day), `%H` (hour),
+   /// `%I` (12-hour clock hour), `%p` (AM or PM), `%M` (minute), `%S` (second), `%f` (millisecond),
+   /// `%j` (day of the year), `%u` (day of the week, from 1 to 7), `%a` and `%A` (weekday name),
+   /// `%b` and `%B` (month name), and `%%` (a percent sign).
+   ///
+   /// # Returns
+   /// - `Result<String, String>`: The formatted DateTime, or an error message if the format
+   ///   string has an unknown specifier.
+   pub fn format(&self, format: &str) -> Result<String, String> {
+      let c = self.components();
+      let mut result = String::new();
+      let mut chars = format.chars();
+
+      while let Some(ch) = chars.next() {
+         if ch != '%' {
+            result.push(ch);
+            continue;
+         }
+
+         let hour_12 = if c.hour % 12 == 0 { 12 } else { c.hour % 12 };
+
+         match chars.next() {
+            Some('Y') => result += &format!("{:04}", c.year),
+            Some('y') => result += &format!("{:02}", c.year % 100),
+            Some('m') => result += &format!("{:02}", c.month),
+            Some('d') => result += &format!("{:02}", c.day),
+            Some('H') => result += &format!("{:02}", c.hour),
+            Some('I') => result += &format!("{:02}", hour_12),
+            Some('p') => result += if c.hour < 12 { "AM" } else { "PM" },
+            Some('M') => result += &format!("{:02}", c.minute),
+            Some('S') => result += &format!("{:02}", c.second),
+            Some('f') => result += &format!("{:03}", c.millisecond),
+            Some('j') => result += &format!("{:03}", c.day_of_year),
+            Some('u') => result += &c.weekday.to_string(),
+            Some('a') => result += &WEEKDAY_NAMES[c.weekday as usize - 1][..3],
+            Some('A') => result += WEEKDAY_NAMES[c.weekday as usize - 1],
+            Some('b') => result += &MONTH_NAMES[c.month as usize - 1][..3],
+            Some('B') => result += MONTH_NAMES[c.month as usize - 1],
+            Some('%') => result.push('%'),
+            Some(spec) => return Err(format!("Unknown format specifier '%{}'.", spec)),
+            None => {
+               return Err(String::from(
+                  "Incomplete format specifier at the end of the format string.",
+               ))
+            }
+         }
+      }
+
+      Ok(result)
+   }
+
+   /// Parses a date and time with a format string. The format string supports the same
+   /// specifiers as `format`, except for `%u`. Components that are not in the format string
+   /// default to the first instant of the year 1970.
+   ///
+   /// # Returns
+   /// - `Result<DateTimeObject, String>`: The parsed DateTime, or an error message if the text
+   ///   does not match the format string.
+   pub fn parse(text: &str, format: &str) -> Result<DateTimeObject, String> {
+      let mut input = DateInput {
+         chars: text.chars().collect(),
+         pos: 0,
+      };
+
+      let (mut year, mut month, mut day) = (1970, 1, 1);
+      let (mut hour, mut minute, mut second, mut millisecond) = (0, 0, 0, 0);
+      let mut day_of_year = None;
+      let mut is_pm = None;
+
+      let mut spec_chars = format.chars();
+      while let Some(ch) = spec_chars.next() {
+         if ch != '%' {
+            input.expect(ch)?;
+            continue;
+         }
+
+         match spec_chars.next() {
+            Some('Y') => year = input.number(4, "year")?,
+            Some('y') => {
+               // Like C's `strptime`, the years 69 to 99 are in the 20th century.
+               let short = input.number(2, "year")?;
+               year = if short < 69 { 2000 + short } else { 1900 + short };
+            }
+            Some('m') => month = input.number(2, "month")?,
+            Some('d') => day = input.number(2, "day")?,
+            Some('H') | Some('I') => hour = input.number(2, "hour")?,
+            Some('M') => minute = input.number(2, "minute")?,
+            Some('S') => second = input.number(2, "second")?,
+            Some('f') => millisecond = input.number(3, "millisecond")?,
+            Some('j') => day_of_year = Some(input.number(3, "day of the year")?),
+            Some('p') => is_pm = Some(input.name(&["AM", "PM"], "AM or PM")? == 1),
+            Some('a') | Some('A') => {
+               input.name(&WEEKDAY_NAMES, "weekday")?;
+            }
+            Some('b') | Some('B') => month = input.name(&MONTH_NAMES, "month")? as i64 + 1,
+            Some('%') => input.expect('%')?,
+            Some(spec) => return Err(format!("Unknown format specifier '%{}'.", spec)),
+            None => {
+               return Err(String::from(
+                  "Incomplete format specifier at the end of the format string.",
+               ))
+            }
+         }
+      }
+
+      if input.pos < input.chars.len() {
+         return Err(format!("Unexpected text at position {}.", input.pos));
+      }
+
+      match is_pm {
+         Some(_) if !(1..=12).contains(&hour) => return Err(format!("Invalid hour '{}'.", hour)),
+         Some(is_pm) => hour = hour % 12 + if is_pm { 12 } else { 0 },
+         None => {}
+      }
+
+      if let Some(day_of_year) = day_of_year {
+         let days_in_year = if is_leap_year(year) { 366 } else { 365 };
+         if !(1..=days_in_year).contains(&day_of_year) {
+            return Err(format!("Invalid day of the year '{}'.", day_of_year));
+         }
+
+         let (_, m, d) = civil_from_days(days_from_civil(year, 1, 1) + day_of_year - 1);
+         month = m;
+         day = d;
+      }
+
+      DateTimeObject::from_components((year, month, day), (hour, minute, second, millisecond))
+   }
+}
+
+/// The text being parsed by `DateTimeObject::parse`.
+struct DateInput {
+   chars: Vec<char>,
+   pos: usize,
+}
+
+impl DateInput {
+   /// Consumes a literal character of the format string.
+   fn expect(&mut self, expected: char) -> Result<(), String> {
+      if self.chars.get(self.pos) == Some(&expected) {
+         self.pos += 1;
+         Ok(())
+      } else {
+         Err(format!("Expected '{}' at position {}.", expected, self.pos))
+      }
+   }
+
+   /// Consumes a decimal number with up to `max_digits` digits.
+   fn number(&mut self, max_digits: usize, name: &str) -> Result<i64, String> {
+      let start = self.pos;
+      while self.pos - start < max_digits && self.chars.get(self.pos).is_some_and(|c| c.is_ascii_digit()) {
+         self.pos += 1;
+      }
+
+      if start == self.pos {
+         return Err(format!("Expected the {} at position {}.", name, start));
+      }
+
+      Ok(self.chars[start..self.pos]
+         .iter()
+         .collect::<String>()
+         .parse()
+         .unwrap())
+   }
+
+   /// Consumes a name (or its first three letters), ignoring the case.
+   ///
+   /// # Returns
+   /// - `Result<usize, String>`: The index of the name in the list of names.
+   fn name(&mut self, names: &[&str], description: &str) -> Result<usize, String> {
+      let rest: String = self.chars[self.pos..].iter().collect::<String>().to_lowercase();
+
+      // Full names are tried before abbreviations, so that `June` is not read as `Jun`.
+      for len in [None, Some(3)].iter() {
+         for (idx, name) in names.iter().enumerate() {
+            let name = name.to_lowercase();
+            let name = len.map_or(&name[..], |len| &name[..len.min(name.len())]);
+
+            if rest.starts_with(name) {
+               self.pos += name.chars().count();
+               return Ok(idx);
+            }
+         }
+      }
+
+      Err(format!("Expected the {} at position {}.", description, self.pos))
+   }
+}
+
+impl fmt::Display for DateTimeObject {
+   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      let format = if self.millis % 1000 == 0 {
+         "%Y-%m-%dT%H:%M:%SZ"
+      } else {
+         "%Y-%m-%dT%H:%M:%S.%fZ"
+      };
+
+      f.write_str(&self.format(format).unwrap())
+   }
+}
@@ -0,0 +1,129 @@
+use crate::errors::ObjectOprErrType;
+use crate::objects::hash_key::HashKey;
+use crate::objects::Object;
+use hashbrown::HashMap;
+use std::iter::FromIterator;
+
+/// Represents the entries of a Hinton dictionary. Entries are kept in insertion
+/// order, which is the order in which they are printed and iterated over.
+///
+/// Keys are usually strings, but any immutable object (e.g., a number or a tuple) can be a key.
+/// Keys are compared by their `HashKey`, just like the items of a set.
+#[derive(Clone, Default)]
+pub struct DictObject {
+   /// The entries of the dictionary, in insertion order.
+   entries: Vec<(Object, Object)>,
+   /// The position of each key in the list of entries.
+   indices: HashMap<HashKey, usize>,
+}
+
+impl DictObject {
+   /// Gets the number of entries in the dictionary.
+   pub fn len(&self) -> usize {
+      self.entries.len()
+   }
+
+   /// Checks if the dictionary has no entries.
+   pub fn is_empty(&self) -> bool {
+      self.entries.is_empty()
+   }
+
+   /// Checks if the dictionary has an entry with the given string key.
+   pub fn contains_key(&self, key: &str) -> bool {
+      self.contains(&Object::from(key))
+   }
+
+   /// Checks if the dictionary has an entry with the given key. Objects that
+   /// cannot be used as keys are never keys of a dictionary.
+   pub fn contains(&self, key: &Object) -> bool {
+      HashKey::from_object(key).is_some_and(|key| self.indices.contains_key(&key))
+   }
+
+   /// Gets the value associated with a string key.
+   pub fn get(&self, key: &str) -> Option<&Object> {
+      self.get_key(&Object::from(key))
+   }
+
+   /// Gets the value associated with a key.
+   pub fn get_key(&self, key: &Object) -> Option<&Object> {
+      let idx = self.indices.get(&HashKey::from_object(key)?)?;
+      Some(&self.entries[*idx].1)
+   }
+
+   /// Gets the entry at a position in insertion order.
+   pub fn get_entry(&self, idx: usize) -> Option<(&Object, &Object)> {
+      self.entries.get(idx).map(|(k, v)| (k, v))
+   }
+
+   /// Associates a value with a string key. If the key already exists, its value is replaced
+   /// and the entry keeps its position. Otherwise, the entry is added at the end.
+   ///
+   /// # Returns
+   /// - `Option<Object>`: The previous value associated with the key, if any.
+   pub fn insert(&mut self, key: String, value: Object) -> Option<Object> {
+      match self.insert_key(Object::from(key), value) {
+         Ok(previous) => previous,
+         Err(_) => unreachable!("Strings can always be used as dictionary keys."),
+      }
+   }
+
+   /// Associates a value with a key. If the key already exists, its value is replaced
+   /// and the entry keeps its position. Otherwise, the entry is added at the end.
+   ///
+   /// # Returns
+   /// - `Result<Option<Object>, ObjectOprErrType>`: The previous value associated with the key,
+   ///   if any, or a `TypeError` if the object cannot be used as a key.
+   pub fn insert_key(&mut self, key: Object, value: Object) -> Result<Option<Object>, ObjectOprErrType> {
+      let hash_key = HashKey::try_from_object(&key)?;
+
+      Ok(match self.indices.get(&hash_key) {
+         Some(idx) => Some(std::mem::replace(&mut self.entries[*idx].1, value)),
+         None => {
+            self.indices.insert(hash_key, self.entries.len());
+            self.entries.push((key, value));
+            None
+         }
+      })
+   }
+
+   /// Removes the entry with the given key, preserving the order of the remaining entries.
+   ///
+   /// # Returns
+   /// - `Option<Object>`: The value associated with the key, if any.
+   pub fn remove(&mut self, key: &Object) -> Option<Object> {
+      let idx = self.indices.remove(&HashKey::from_object(key)?)?;
+      let (_, value) = self.entries.remove(idx);
+
+      self
+         .indices
+         .values_mut()
+         .filter(|i| **i > idx)
+         .for_each(|i| *i -= 1);
+      Some(value)
+   }
+
+   /// Iterates over the entries of the dictionary in insertion order.
+   pub fn iter(&self) -> impl Iterator<Item = (&Object, &Object)> {
+      self.entries.iter().map(|(k, v)| (k, v))
+   }
+
+   /// Iterates over the keys of the dictionary in insertion order.
+   pub fn keys(&self) -> impl Iterator<Item = &Object> {
+      self.entries.iter().map(|(k, _)| k)
+   }
+
+   /// Iterates over the values of the dictionary in insertion order.
+   pub fn values(&self) -> impl Iterator<Item = &Object> {
+      self.entries.iter().map(|(_, v)| v)
+   }
+}
+
+impl FromIterator<(String, Object)> for DictObject {
+   fn from_iter<T: IntoIterator<Item = (String, Object)>>(iter: T) -> Self {
+      let mut dict = DictObject::default();
+      iter.into_iter().for_each(|(k, v)| {
+         dict.insert(k, v);
+      });
+      dict
+   }
+}
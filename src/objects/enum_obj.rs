@@ -0,0 +1,78 @@
+use crate::objects::Object;
+use std::fmt;
+use std::rc::Rc;
+
+/// Represents a Hinton enum. Each variant of an enum can carry a payload, whose fields are
+/// declared with the variant (e.g., `enum Shape { Circle(r), Rect(w, h), Empty }`).
+pub struct EnumObject {
+   pub name: String,
+   /// The name of each variant, along with the names of the fields of its payload.
+   pub variants: Vec<(String, Vec<String>)>,
+}
+
+impl EnumObject {
+   /// Gets the index of a variant of the enum from its name.
+   pub fn variant_index(&self, name: &str) -> Option<usize> {
+      self.variants.iter().position(|(variant, _)| variant == name)
+   }
+}
+
+impl fmt::Display for EnumObject {
+   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+      write!(f, "<Enum '{}' at {:p}>", self.name, self as *const _)
+   }
+}
+
+/// Represents a variant of an enum that carries a payload, which is called with the values
+/// of the payload to create a value of the enum (e.g., `Shape.Circle(2)`).
+#[derive(Clone)]
+pub struct EnumVariantObject {
+   pub enum_obj: Rc<EnumObject>,
+   pub variant: usize,
+}
+
+impl EnumVariantObject {
+   /// Gets the name of the variant.
+   pub fn name(&self) -> &str {
+      &self.enum_obj.variants[self.variant].0
+   }
+
+   /// Gets the names of the fields of the variant's payload.
+   pub fn fields(&self) -> &[String] {
+      &self.enum_obj.variants[self.variant].1
+   }
+}
+
+impl fmt::Display for EnumVariantObject {
+   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+      write!(f, "<Variant '{}.{}'>", self.enum_obj.name, self.name())
+   }
+}
+
+/// Represents a value of an enum, which is tagged with its variant, and holds the values
+/// of the variant's payload in the order in which its fields were declared.
+pub struct EnumValueObject {
+   pub variant: EnumVariantObject,
+   pub payload: Vec<Object>,
+}
+
+impl EnumValueObject {
+   /// Gets the value of a field of the payload from its name.
+   pub fn get_field(&self, name: &str) -> Option<&Object> {
+      let idx = self.variant.fields().iter().position(|field| field == name)?;
+      self.payload.get(idx)
+   }
+}
+
+impl fmt::Display for EnumValueObject {
+   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+      write!(f, "{}.{}", self.variant.enum_obj.name, self.variant.name())?;
+
+      if self.variant.fields().is_empty() {
+         return Ok(());
+      }
+
+      let values: Vec<String> = self.payload.iter().map(|v| format!("{}", v)).collect();
+      write!(f, "({})", values.join(", "))
+   }
+}
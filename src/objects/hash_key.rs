@@ -0,0 +1,54 @@
+use crate::errors::ObjectOprErrType;
+use crate::objects::bigint::BigInt;
+use crate::objects::Object;
+use std::rc::Rc;
+
+/// The hashable representation of an object used as a dictionary key or as a set item. Only
+/// immutable objects can be hashed: null, booleans, numbers, strings, chars, dates, and tuples of
+/// those objects. Objects that are equal with the `==` operator have the same key, so `true`, `1`,
+/// and `1.0` are the same key.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub enum HashKey {
+   Null,
+   Int(i64),
+   BigInt(Rc<BigInt>),
+   Float(u64),
+   String(Rc<str>),
+   Char(char),
+   DateTime(i64),
+   Tuple(Vec<HashKey>),
+}
+
+impl HashKey {
+   /// Gets the key of an object, or `None` if the object cannot be hashed.
+   pub fn from_object(obj: &Object) -> Option<HashKey> {
+      Some(match obj {
+         Object::Null => HashKey::Null,
+         Object::Bool(b) => HashKey::Int(*b as i64),
+         Object::Int(i) => HashKey::Int(*i),
+         // BigInts with a value that fits in an Int are equal to the corresponding Int.
+         Object::BigInt(b) => match b.to_i64() {
+            Some(i) => HashKey::Int(i),
+            None => HashKey::BigInt(b.clone()),
+         },
+         // Floats with an integer value are equal to the corresponding Int.
+         Object::Float(f) if f.fract() == 0.0 && f.abs() < i64::MAX as f64 => HashKey::Int(*f as i64),
+         Object::Float(f) => HashKey::Float(f.to_bits()),
+         Object::String(s) => HashKey::String(s.clone()),
+         Object::Char(c) => HashKey::Char(*c),
+         Object::DateTime(d) => HashKey::DateTime(d.millis()),
+         Object::Tuple(t) => HashKey::Tuple(t.iter().map(HashKey::from_object).collect::<Option<_>>()?),
+         _ => return None,
+      })
+   }
+
+   /// Gets the key of an object, or a `TypeError` if the object cannot be hashed.
+   pub fn try_from_object(obj: &Object) -> Result<HashKey, ObjectOprErrType> {
+      HashKey::from_object(obj).ok_or_else(|| {
+         ObjectOprErrType::TypeError(format!(
+            "Objects of type '{}' cannot be used as dictionary keys or set items.",
+            obj.type_name()
+         ))
+      })
+   }
+}
@@ -1,6 +1,7 @@
 use crate::errors::ObjectOprErrType;
+use crate::objects::dict_obj::DictObject;
+use crate::objects::hash_key::HashKey;
 use crate::objects::{Object, RangeObject};
-use hashbrown::HashMap;
 use std::cell::RefCell;
 use std::rc::Rc;
 
@@ -10,9 +11,11 @@ impl Object {
       match self {
          Object::Array(arr) => subscript_array(&arr.borrow(), index),
          Object::Tuple(tup) => subscript_tuple(&tup, index),
+         Object::EnumValue(val) => subscript_tuple(&val.payload, index),
          Object::String(str) => subscript_string(&str, index),
          Object::Range(range) => subscript_range(range, index),
          Object::Dict(dict) => subscript_dictionary(dict, index),
+         Object::Bytes(bytes) => subscript_bytes(&bytes.borrow(), index),
          _ => {
             return Err(ObjectOprErrType::TypeError(format!(
                "Cannot index object of type '{}'.",
@@ -21,6 +24,76 @@ impl Object {
          }
       }
    }
+
+   /// Defines the slicing operation of Hinton objects. Slicing an array, string, tuple, or Bytes
+   /// produces a new object of the same type with the items between the bounds of the slice.
+   ///
+   /// # Parameters
+   /// - `start`: The index of the first item in the slice, if any.
+   /// - `end`: The index after the last item in the slice, if any.
+   /// - `step`: The distance between the items in the slice. Negative steps slice backwards.
+   pub fn slice(&self, start: Option<i64>, end: Option<i64>, step: i64) -> Result<Object, ObjectOprErrType> {
+      if step == 0 {
+         return Err(ObjectOprErrType::IndexError(String::from(
+            "Slice step cannot be zero.",
+         )));
+      }
+
+      match self {
+         Object::Array(arr) => {
+            let arr = arr.borrow();
+            let items = slice_indices(arr.len(), start, end, step).map(|i| arr[i].clone());
+            Ok(Object::Array(Rc::new(RefCell::new(items.collect()))))
+         }
+         Object::Tuple(tup) => {
+            let items = slice_indices(tup.len(), start, end, step).map(|i| tup[i].clone());
+            Ok(Object::Tuple(Rc::new(items.collect())))
+         }
+         Object::String(str) => {
+            let chars: Vec<char> = str.chars().collect();
+            let slice: String = slice_indices(chars.len(), start, end, step)
+               .map(|i| chars[i])
+               .collect();
+            Ok(Object::from(slice))
+         }
+         Object::Bytes(bytes) => {
+            let bytes = bytes.borrow();
+            let slice: Vec<u8> = slice_indices(bytes.len(), start, end, step)
+               .map(|i| bytes[i])
+               .collect();
+            Ok(Object::from(slice))
+         }
+         _ => Err(ObjectOprErrType::TypeError(format!(
+            "Cannot slice object of type '{}'.",
+            self.type_name()
+         ))),
+      }
+   }
+}
+
+/// Computes the indices of the items in a slice. Negative bounds count from the end of the
+/// object, and bounds outside of the object are clamped to its length.
+///
+/// # Parameters
+/// - `len`: The length of the sliced object.
+/// - `start`: The index of the first item in the slice. Defaults to the first item of the
+///   object (or the last item, if the step is negative).
+/// - `end`: The index after the last item in the slice. Defaults to the end of the object
+///   (or the start, if the step is negative).
+/// - `step`: The non-zero distance between the items in the slice.
+fn slice_indices(len: usize, start: Option<i64>, end: Option<i64>, step: i64) -> impl Iterator<Item = usize> {
+   let len = len as i64;
+
+   // When slicing backwards, `-1` represents the position before the first item.
+   let (lower, upper) = if step > 0 { (0, len) } else { (-1, len - 1) };
+   let clamp = |x: i64| if x < 0 { (x + len).max(lower) } else { x.min(upper) };
+
+   let start = start.map_or(if step > 0 { lower } else { upper }, clamp);
+   let end = end.map_or(if step > 0 { upper } else { lower }, clamp);
+
+   std::iter::successors(Some(start), move |i| Some(i + step))
+      .take_while(move |i| if step > 0 { *i < end } else { *i > end })
+      .map(|i| i as usize)
 }
 
 /// Takes an i64 integer and converts it into an object index. This allows indexing objects with
@@ -71,8 +144,10 @@ fn subscript_array(arr: &[Object], index: &Object) -> Result<Object, ObjectOprEr
          }
       }
       // Indexing type: Array[Range]
-      Object::Range(_) => {
-         unimplemented!("Array indexing with ranges.")
+      Object::Range(range) => {
+         let items =
+            slice_indices(arr.len(), Some(range.min), Some(range.max), range.step).map(|i| arr[i].clone());
+         return Ok(Object::Array(Rc::new(RefCell::new(items.collect()))));
       }
       _ => {
          return Err(ObjectOprErrType::TypeError(format!(
@@ -115,8 +190,10 @@ fn subscript_tuple(tup: &[Object], index: &Object) -> Result<Object, ObjectOprEr
          }
       }
       // Indexing type: Tuple[Range]
-      Object::Range(_) => {
-         unimplemented!("Tuple indexing with ranges.")
+      Object::Range(range) => {
+         let items =
+            slice_indices(tup.len(), Some(range.min), Some(range.max), range.step).map(|i| tup[i].clone());
+         return Ok(Object::Tuple(Rc::new(items.collect())));
       }
       _ => {
          return Err(ObjectOprErrType::TypeError(format!(
@@ -131,6 +208,50 @@ fn subscript_tuple(tup: &[Object], index: &Object) -> Result<Object, ObjectOprEr
    )))
 }
 
+/// Get the ith byte in a Hinton Bytes object.
+///
+/// # Parameters
+/// - `bytes`: A reference to the underlying `Vec<u8>` in a Hinton Bytes object.
+/// - `index`: A Hinton object that will serve as the index of the Bytes object. For example,
+///   this object could be a Hinton integer, or a Hinton range.
+///
+/// # Returns
+/// - `Result<Object, ObjectOprErrType>`: Returns `Ok(Object)` with a Hinton Int (or a Bytes
+///   object, when indexing with a range) if the index is within bounds. Returns
+///   `Err(ObjectOprErrType)` if there was an error while indexing the Bytes object.
+fn subscript_bytes(bytes: &[u8], index: &Object) -> Result<Object, ObjectOprErrType> {
+   match index {
+      // Indexing type: Bytes[Int]
+      Object::Int(idx) => {
+         if let Some(pos) = to_bounded_index(*idx, bytes.len()) {
+            return Ok(Object::Int(bytes[pos] as i64));
+         }
+      }
+      // Indexing type: Bytes[Bool]
+      Object::Bool(val) => {
+         if let Some(b) = bytes.get(*val as usize) {
+            return Ok(Object::Int(*b as i64));
+         }
+      }
+      // Indexing type: Bytes[Range]
+      Object::Range(range) => {
+         let slice =
+            slice_indices(bytes.len(), Some(range.min), Some(range.max), range.step).map(|i| bytes[i]);
+         return Ok(Object::from(slice.collect::<Vec<u8>>()));
+      }
+      _ => {
+         return Err(ObjectOprErrType::TypeError(format!(
+            "Bytes index must be an Int or a Range. Found '{}' instead.",
+            index.type_name()
+         )))
+      }
+   }
+
+   Err(ObjectOprErrType::IndexError(String::from(
+      "Bytes index out of bounds.",
+   )))
+}
+
 /// Get the ith character in a Hinton string.
 ///
 /// # Parameters
@@ -163,8 +284,11 @@ fn subscript_string(str: &str, index: &Object) -> Result<Object, ObjectOprErrTyp
          }
       }
       // Indexing type: String[Range]
-      Object::Range(_) => {
-         unimplemented!("String indexing with ranges.")
+      Object::Range(range) => {
+         let chars: Vec<char> = str.chars().collect();
+         let slice =
+            slice_indices(chars.len(), Some(range.min), Some(range.max), range.step).map(|i| chars[i]);
+         return Ok(Object::from(slice.collect::<String>()));
       }
       _ => {
          return Err(ObjectOprErrType::TypeError(format!(
@@ -190,80 +314,43 @@ fn subscript_string(str: &str, index: &Object) -> Result<Object, ObjectOprErrTyp
 /// - `Result<Object, ObjectOprErrType>`: Returns `Ok(Object)` with a Hinton Object if the index is
 /// within bounds. Returns `Err(ObjectOprErrType)` if there was an error while indexing the range.
 fn subscript_range(range: &RangeObject, index: &Object) -> Result<Object, ObjectOprErrType> {
-   match index {
-      // Indexing type: Range[Int]
-      Object::Int(idx) => {
-         let min = range.min;
-         let max = range.max;
-
-         if let Some(pos) = to_bounded_index(*idx, i64::abs(max - min) as usize) {
-            return if max - min > 0 {
-               Ok(Object::Int(min + pos as i64))
-            } else {
-               Ok(Object::Int(min - pos as i64))
-            };
-         }
-      }
-      // Indexing type: Range[Bool]
-      Object::Bool(val) => {
-         let idx = (if *val { 1 } else { 0 }) as i64;
-         let min = range.min;
-         let max = range.max;
-
-         if let Some(pos) = to_bounded_index(idx, i64::abs(max - min) as usize) {
-            return if max - min > 0 {
-               Ok(Object::Int(min + pos as i64))
-            } else {
-               Ok(Object::Int(min - pos as i64))
-            };
-         }
-      }
-      // Indexing type: Range[Range]
-      Object::Range(_) => {
-         unimplemented!("Range indexing with ranges.")
-      }
+   let idx = match index {
+      // Indexing type: Range[Int], Range[Bool]
+      Object::Int(_) | Object::Bool(_) => index.as_int().unwrap(),
       _ => {
          return Err(ObjectOprErrType::TypeError(format!(
-            "Range index must be an Int or a Range. Found '{}' instead.",
+            "Range index must be an Int. Found '{}' instead.",
             index.type_name()
          )))
       }
-   }
+   };
 
-   Err(ObjectOprErrType::IndexError(String::from(
-      "Range index out of bounds.",
-   )))
+   match to_bounded_index(idx, range.len()).and_then(|pos| range.get(pos)) {
+      Some(item) => Ok(Object::Int(item)),
+      None => Err(ObjectOprErrType::IndexError(String::from(
+         "Range index out of bounds.",
+      ))),
+   }
 }
 
 /// Gets the value associated with a key in a Hinton dictionary.
 ///
 /// # Parameters
-/// - `dict`: A reference to the underlying `HashMap` in a Hinton dictionary.
+/// - `dict`: A reference to the entries of a Hinton dictionary.
 /// - `index`: A Hinton object that will serve as the index of the dictionary. For example, this
-/// object could be a Hinton string, or a Hinton range.
+/// object could be a Hinton string, or a Hinton tuple.
 ///
 /// # Returns
 /// - `Result<Object, ObjectOprErrType>`: Returns `Ok(Object)` with a Hinton Object if the key
 /// exists in the dictionary. Returns `Err(ObjectOprErrType)` otherwise.
-fn subscript_dictionary(
-   dict: &Rc<RefCell<HashMap<String, Object>>>,
-   index: &Object,
-) -> Result<Object, ObjectOprErrType> {
-   return match index {
-      Object::String(key) => match dict.borrow().get(key) {
-         Some(o) => Ok(o.clone()),
-         None => Err(ObjectOprErrType::KeyError(format!(
-            "Entry with key '{}' not found in the dictionary.",
-            key
-         ))),
-      },
-      // Indexing type: Range[Range]
-      Object::Range(_) => {
-         unimplemented!("Range indexing with ranges.")
-      }
-      _ => Err(ObjectOprErrType::TypeError(format!(
-         "Dictionaries can only be indexed by a String or Range. Found '{}' instead.",
-         index.type_name()
+fn subscript_dictionary(dict: &Rc<RefCell<DictObject>>, index: &Object) -> Result<Object, ObjectOprErrType> {
+   HashKey::try_from_object(index)?;
+
+   match dict.borrow().get_key(index) {
+      Some(o) => Ok(o.clone()),
+      None => Err(ObjectOprErrType::KeyError(format!(
+         "Entry with key '{}' not found in the dictionary.",
+         index.as_plain_string()
       ))),
-   };
+   }
 }
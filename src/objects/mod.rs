@@ -1,36 +1,210 @@
-use crate::built_in::{NativeBoundMethod, NativeFn};
+use crate::built_in::{NativeBoundMethod, NativeFnBody};
 use crate::core::chunk::Chunk;
+use crate::errors::{strip_ansi_codes, ObjectOprErrType};
+use crate::objects::bigint::BigInt;
+use crate::objects::channel_obj::ChannelObject;
 use crate::objects::class_obj::*;
-use hashbrown::HashMap;
+use crate::objects::datetime_obj::DateTimeObject;
+use crate::objects::dict_obj::DictObject;
+use crate::objects::enum_obj::{EnumObject, EnumValueObject, EnumVariantObject};
+use crate::objects::promise_obj::PromiseObject;
+use crate::objects::regex_obj::RegexObject;
+use crate::objects::set_obj::SetObject;
+use crate::objects::socket_obj::SocketObject;
 use std::cell::RefCell;
+use std::convert::TryFrom;
 use std::fmt;
 use std::fmt::Formatter;
+use std::path::PathBuf;
 use std::rc::Rc;
 
 // Submodules
+pub mod bigint;
+pub mod bytes_obj;
+pub mod channel_obj;
 pub mod class_obj;
+pub mod datetime_obj;
+pub mod dict_obj;
+pub mod enum_obj;
+pub mod hash_key;
 pub mod indexing;
+#[cfg(feature = "nan_boxing")]
+pub mod nan_box;
 mod native_operations;
-
-/// Represents a Hinton range object.
+pub mod promise_obj;
+pub mod regex_obj;
+pub mod set_obj;
+pub mod socket_obj;
+
+/// Represents a Hinton range object. A range produces the integers from `min` (inclusive) up
+/// to `max` (exclusive), and counts down when `max` is less than `min`. The items of a range
+/// are computed as they are needed, so a range never stores the integers it produces.
 #[derive(Clone)]
 pub struct RangeObject {
    pub min: i64,
    pub max: i64,
+   /// The distance between consecutive items of the range, which is always positive.
+   pub step: i64,
+}
+
+impl RangeObject {
+   /// Creates a range with a step of one.
+   pub fn new(min: i64, max: i64) -> Self {
+      Self { min, max, step: 1 }
+   }
+
+   /// Creates a range that includes its end (e.g., `0..=10`), which is stored as the
+   /// equivalent exclusive range. Returns `None` if the end is the largest (or smallest) Int.
+   pub fn inclusive(min: i64, max: i64) -> Option<Self> {
+      let max = if max < min {
+         max.checked_sub(1)?
+      } else {
+         max.checked_add(1)?
+      };
+
+      Some(Self::new(min, max))
+   }
+
+   /// Gets a copy of the range with the given step. Returns `None` if the step is not positive.
+   pub fn with_step(&self, step: i64) -> Option<Self> {
+      match step > 0 {
+         true => Some(Self { step, ..self.clone() }),
+         false => None,
+      }
+   }
+
+   /// Checks if the range counts down.
+   pub fn is_descending(&self) -> bool {
+      self.max < self.min
+   }
+
+   /// Gets the number of items produced by the range.
+   pub fn len(&self) -> usize {
+      let distance = (self.max as i128 - self.min as i128).unsigned_abs();
+      let step = self.step as u128;
+      distance.div_ceil(step) as usize
+   }
+
+   /// Checks if the range produces no items.
+   pub fn is_empty(&self) -> bool {
+      self.min == self.max
+   }
+
+   /// Gets the item at the given position in the range, if the position is within bounds.
+   pub fn get(&self, pos: usize) -> Option<i64> {
+      if pos >= self.len() {
+         return None;
+      }
+
+      let offset = pos as i128 * self.step as i128;
+      match self.is_descending() {
+         true => Some((self.min as i128 - offset) as i64),
+         false => Some((self.min as i128 + offset) as i64),
+      }
+   }
+
+   /// Checks if the range produces the given integer.
+   pub fn contains(&self, item: i64) -> bool {
+      let offset = match self.is_descending() {
+         true => self.min as i128 - item as i128,
+         false => item as i128 - self.min as i128,
+      };
+
+      offset >= 0 && offset % self.step as i128 == 0 && ((offset / self.step as i128) as usize) < self.len()
+   }
+
+   /// Gets a range that produces the items of this range in reverse order. Returns `None`
+   /// if the first item of this range is the smallest (or largest) Int.
+   pub fn reversed(&self) -> Option<Self> {
+      let last = match self.len() {
+         0 => return Some(self.clone()),
+         len => self.get(len - 1)?,
+      };
+
+      // The reversed range ends right after the first item of this range.
+      let max = match self.is_descending() {
+         true => self.min.checked_add(1)?,
+         false => self.min.checked_sub(1)?,
+      };
+
+      Some(Self {
+         min: last,
+         max,
+         step: self.step,
+      })
+   }
 }
 
 /// Represents a Hinton iterator object.
 pub struct IterObject {
+   /// The object the iterator gets its items from. The iterators composed by an adapter are
+   /// stored as an `Iter` object, or as a tuple of `Iter` objects (e.g., for `zip(...)`).
    pub iter: Box<Object>,
+   /// The number of items the iterator has produced (or, for chained
+   /// iterators, the position of the iterator that produces the next item).
    pub index: usize,
+   pub kind: IterKind,
+}
+
+impl IterObject {
+   /// Creates an iterator that has not produced any items yet.
+   pub fn new(iter: Object, kind: IterKind) -> Self {
+      Self {
+         iter: Box::new(iter),
+         index: 0,
+         kind,
+      }
+   }
+}
+
+/// The ways in which an iterator gets its items from its object.
+#[derive(Clone, Copy)]
+pub enum IterKind {
+   /// Produces the items of a collection (e.g., an array, a string, or a range).
+   Items,
+   /// Calls the `next()` method of an instance until the method returns `null`.
+   Protocol,
+   /// Produces `(index, item)` tuples with the items of an iterator.
+   Enumerate,
+   /// Produces, at most, the given number of items of an iterator.
+   Take(usize),
+   /// Skips the given number of items of an iterator, and produces the rest.
+   Skip(usize),
+   /// Produces tuples with the next item of each iterator, until one of them ends.
+   Zip,
+   /// Produces the items of each iterator, one after the other.
+   Chain,
 }
 
 impl fmt::Display for IterObject {
    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
-      write!(f, "<Iterable '{}'>", self.iter.type_name())
+      let name = match self.kind {
+         IterKind::Items | IterKind::Protocol => self.iter.type_name(),
+         IterKind::Enumerate => String::from("enumerate"),
+         IterKind::Take(_) => String::from("take"),
+         IterKind::Skip(_) => String::from("skip"),
+         IterKind::Zip => String::from("zip"),
+         IterKind::Chain => String::from("chain"),
+      };
+
+      write!(f, "<Iterable '{}'>", name)
    }
 }
 
+/// Represents a local variable declared in a function. Used by debugging tools
+/// to find the names of the values stored in a call frame's stack slots.
+#[derive(Clone)]
+pub struct LocalVariable {
+   /// The name of the local variable.
+   pub name: String,
+   /// The stack slot of the variable, relative to the base of the call frame.
+   pub slot: usize,
+   /// The position of the first instruction where the variable is in scope.
+   pub start: usize,
+   /// The position after the last instruction where the variable is in scope.
+   pub end: usize,
+}
+
 /// Represents a Hinton function object.
 #[derive(Clone)]
 pub struct FuncObject {
@@ -39,7 +213,21 @@ pub struct FuncObject {
    pub max_arity: u8,
    pub chunk: Chunk,
    pub name: String,
-   pub up_val_count: usize,
+   /// The names of the function's parameters, in the order they are declared.
+   pub params: Vec<String>,
+   /// Whether the last parameter of the function collects the extra arguments of a call.
+   pub is_variadic: bool,
+   /// The names of the UpValues captured by the function, in the order they are captured.
+   pub up_val_names: Vec<String>,
+   /// The path of the source file where the function was declared.
+   pub filepath: PathBuf,
+   /// The local variables declared in the function, in the order they are declared.
+   pub locals: Vec<LocalVariable>,
+   /// The doc comment written before the declaration of the function, if there is one.
+   pub doc: Option<String>,
+   /// The names of the global declarations made by the program, in the order of their slots.
+   /// Only the main function of a program declares globals.
+   pub globals: Vec<String>,
 }
 
 impl Default for FuncObject {
@@ -50,7 +238,24 @@ impl Default for FuncObject {
          max_arity: 0,
          chunk: Chunk::new(),
          name: String::from(""),
-         up_val_count: 0,
+         params: vec![],
+         is_variadic: false,
+         up_val_names: vec![],
+         filepath: PathBuf::new(),
+         locals: vec![],
+         doc: None,
+         globals: vec![],
+      }
+   }
+}
+
+impl FuncObject {
+   /// Checks if this function can be called with the given number of arguments.
+   pub fn accepts_arity(&self, arg_count: u8) -> bool {
+      if self.is_variadic {
+         arg_count >= self.min_arity
+      } else {
+         arg_count >= self.min_arity && arg_count <= self.max_arity
       }
    }
 }
@@ -71,7 +276,7 @@ pub struct NativeFuncObj {
    pub name: String,
    pub min_arity: u8,
    pub max_arity: u8,
-   pub body: NativeFn,
+   pub body: NativeFnBody,
 }
 
 impl fmt::Display for NativeFuncObj {
@@ -135,21 +340,34 @@ impl UpValRef {
 #[derive(Clone)]
 pub enum Object {
    Array(Rc<RefCell<Vec<Object>>>),
+   BigInt(Rc<BigInt>),
    Bool(bool),
    BoundMethod(BoundMethod),
    BoundNativeMethod(NativeMethodObj),
+   Bytes(Rc<RefCell<Vec<u8>>>),
+   Channel(Rc<RefCell<ChannelObject>>),
+   Char(char),
    Class(Rc<RefCell<ClassObject>>),
    Closure(ClosureObject),
-   Dict(Rc<RefCell<HashMap<String, Object>>>),
+   DateTime(DateTimeObject),
+   Dict(Rc<RefCell<DictObject>>),
+   Enum(Rc<EnumObject>),
+   EnumValue(Rc<EnumValueObject>),
+   EnumVariant(EnumVariantObject),
    Float(f64),
    Function(Rc<RefCell<FuncObject>>),
    Instance(Rc<RefCell<InstanceObject>>),
    Int(i64),
+   Interface(Rc<InterfaceObject>),
    Iter(Rc<RefCell<IterObject>>),
    Native(Box<NativeFuncObj>),
    Null,
+   Promise(Rc<RefCell<PromiseObject>>),
    Range(RangeObject),
-   String(String),
+   Regex(Rc<RegexObject>),
+   Set(Rc<RefCell<SetObject>>),
+   Socket(Rc<RefCell<SocketObject>>),
+   String(Rc<str>),
    Tuple(Rc<Vec<Object>>),
 }
 
@@ -185,13 +403,25 @@ impl From<InstanceObject> for Object {
 
 impl From<String> for Object {
    fn from(o: String) -> Self {
-      Object::String(o)
+      Object::String(o.into())
    }
 }
 
 impl From<&str> for Object {
    fn from(o: &str) -> Self {
-      Object::String(o.to_string())
+      Object::String(o.into())
+   }
+}
+
+impl From<BigInt> for Object {
+   fn from(o: BigInt) -> Self {
+      Object::BigInt(Rc::new(o))
+   }
+}
+
+impl From<Vec<u8>> for Object {
+   fn from(o: Vec<u8>) -> Self {
+      Object::Bytes(Rc::new(RefCell::new(o)))
    }
 }
 
@@ -201,6 +431,101 @@ impl From<usize> for Object {
    }
 }
 
+impl From<i64> for Object {
+   fn from(o: i64) -> Self {
+      Object::Int(o)
+   }
+}
+
+impl From<f64> for Object {
+   fn from(o: f64) -> Self {
+      Object::Float(o)
+   }
+}
+
+impl From<bool> for Object {
+   fn from(o: bool) -> Self {
+      Object::Bool(o)
+   }
+}
+
+impl From<Vec<Object>> for Object {
+   fn from(o: Vec<Object>) -> Self {
+      Object::Array(Rc::new(RefCell::new(o)))
+   }
+}
+
+impl<T: Into<Object>> From<Option<T>> for Object {
+   fn from(o: Option<T>) -> Self {
+      o.map_or(Object::Null, Into::into)
+   }
+}
+
+/// Creates the error returned when a Hinton object cannot be converted into a Rust value.
+fn conversion_error(obj: &Object, expected: &str) -> ObjectOprErrType {
+   ObjectOprErrType::TypeError(format!(
+      "Expected an object of type '{}'. Got '{}' instead.",
+      expected,
+      obj.type_name()
+   ))
+}
+
+impl TryFrom<Object> for i64 {
+   type Error = ObjectOprErrType;
+
+   fn try_from(o: Object) -> Result<Self, Self::Error> {
+      match o {
+         Object::Int(x) => Ok(x),
+         _ => Err(conversion_error(&o, "Int")),
+      }
+   }
+}
+
+impl TryFrom<Object> for f64 {
+   type Error = ObjectOprErrType;
+
+   fn try_from(o: Object) -> Result<Self, Self::Error> {
+      match o {
+         Object::Float(x) => Ok(x),
+         Object::Int(x) => Ok(x as f64),
+         _ => Err(conversion_error(&o, "Float")),
+      }
+   }
+}
+
+impl TryFrom<Object> for bool {
+   type Error = ObjectOprErrType;
+
+   fn try_from(o: Object) -> Result<Self, Self::Error> {
+      match o {
+         Object::Bool(x) => Ok(x),
+         _ => Err(conversion_error(&o, "Bool")),
+      }
+   }
+}
+
+impl TryFrom<Object> for String {
+   type Error = ObjectOprErrType;
+
+   fn try_from(o: Object) -> Result<Self, Self::Error> {
+      match o {
+         Object::String(x) => Ok(x.to_string()),
+         _ => Err(conversion_error(&o, "String")),
+      }
+   }
+}
+
+impl TryFrom<Object> for Vec<Object> {
+   type Error = ObjectOprErrType;
+
+   fn try_from(o: Object) -> Result<Self, Self::Error> {
+      match o {
+         Object::Array(x) => Ok(x.borrow().clone()),
+         _ => Err(conversion_error(&o, "Array")),
+      }
+   }
+}
+
 /// Checks that two vectors of objects are equal in value.
 ///
 /// # Parameters
@@ -227,25 +552,48 @@ impl Object {
    pub fn type_name(&self) -> String {
       return match self {
          Self::Array(_) => String::from("Array"),
+         Self::BigInt(_) => String::from("BigInt"),
          Self::Bool(_) => String::from("Bool"),
+         Self::Bytes(_) => String::from("Bytes"),
+         Self::Channel(_) => String::from("Channel"),
+         Self::Char(_) => String::from("Char"),
+         Self::DateTime(_) => String::from("DateTime"),
          Self::Dict(_) => String::from("Dict"),
          Self::Float(_) => String::from("Float"),
          Self::Function(_)
          | Self::Native(_)
          | Self::Closure(_)
          | Self::BoundMethod(_)
-         | Self::BoundNativeMethod(_) => String::from("Function"),
+         | Self::BoundNativeMethod(_)
+         | Self::EnumVariant(_) => String::from("Function"),
          Self::Int(_) => String::from("Int"),
          Self::Iter(_) => String::from("Iter"),
          Self::Null => String::from("Null"),
+         Self::Promise(_) => String::from("Promise"),
          Self::Range(_) => String::from("Range"),
+         Self::Regex(_) => String::from("Regex"),
+         Self::Set(_) => String::from("Set"),
+         Self::Socket(_) => String::from("Socket"),
          Self::String(_) => String::from("String"),
          Self::Tuple(_) => String::from("Tuple"),
          Self::Class(c) => c.borrow().name.clone(),
+         Self::Interface(i) => i.name.clone(),
+         Self::Enum(e) => e.name.clone(),
+         Self::EnumValue(v) => v.variant.enum_obj.name.clone(),
          Self::Instance(i) => i.borrow().class.borrow().name.clone(),
       };
    }
 
+   /// Converts this object into a string without the color codes used to display
+   /// the object in the console. Used when interpolating objects into strings.
+   pub fn as_plain_string(&self) -> String {
+      if let Object::String(s) = self {
+         return s.to_string();
+      }
+
+      strip_ansi_codes(&format!("{}", self))
+   }
+
    /// Checks that this object is a Hinton integer.
    pub fn is_int(&self) -> bool {
       matches!(self, Object::Int(_))
@@ -261,6 +609,29 @@ impl Object {
       matches!(self, Object::Bool(_))
    }
 
+   /// Checks that this object is an instance of a class (or of one of its subclasses),
+   /// or an instance of a class that implements an interface.
+   ///
+   /// # Parameters
+   /// - `ty`: The class or interface.
+   ///
+   /// # Returns
+   /// - `Option<bool>`: Whether the object is an instance of the type, or `None`
+   ///   if the type is neither a class nor an interface.
+   pub fn is_instance_of(&self, ty: &Object) -> Option<bool> {
+      let class = match self {
+         Object::Instance(i) => Some(i.borrow().class.clone()),
+         _ => None,
+      };
+
+      match ty {
+         Object::Class(c) => Some(class.is_some_and(|class| class.borrow().is_subclass_of(c))),
+         Object::Interface(i) => Some(class.is_some_and(|class| class.borrow().implements(i))),
+         Object::Enum(e) => Some(matches!(self, Object::EnumValue(v) if Rc::ptr_eq(&v.variant.enum_obj, e))),
+         _ => None,
+      }
+   }
+
    /// Checks that this object is falsey.
    pub fn is_falsey(&self) -> bool {
       match self {
@@ -268,6 +639,7 @@ impl Object {
          Self::Bool(val) => !val,
          Self::Int(x) if *x == 0i64 => true,
          Self::Float(x) if *x == 0f64 => true,
+         Self::BigInt(x) => x.is_zero(),
          _ => false,
       }
    }
@@ -303,9 +675,7 @@ impl Object {
       }
    }
 
-   /// Tries to convert this object to a Hinton function object (only used for bytecode pretty
-   /// printing.
-   #[cfg(feature = "show_bytecode")]
+   /// Tries to convert this object to a Hinton function object.
    pub fn as_function(&self) -> Option<&Rc<RefCell<FuncObject>>> {
       match self {
          Object::Function(v) => Some(v),
@@ -319,9 +689,11 @@ impl<'a> fmt::Display for Object {
    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
       match *self {
          Object::Int(ref inner) => write!(f, "\x1b[38;5;81m{}\x1b[0m", inner),
+         Object::BigInt(ref inner) => write!(f, "\x1b[38;5;81m{}\x1b[0m", inner),
          Object::Instance(ref inner) => write!(f, "{}", inner.borrow()),
          Object::Native(ref inner) => write!(f, "{}", inner),
          Object::String(ref inner) => write!(f, "{}", inner),
+         Object::Char(inner) => write!(f, "{}", inner),
          Object::Bool(inner) => write!(f, "\x1b[38;5;3m{}\x1b[0m", if inner { "true" } else { "false" }),
          Object::Iter(ref inner) => write!(f, "{}", inner.borrow()),
          Object::Function(ref inner) => write!(f, "{}", inner.borrow()),
@@ -333,13 +705,29 @@ impl<'a> fmt::Display for Object {
             let fractional = if inner.fract() == 0.0 { ".0" } else { "" };
             write!(f, "\x1b[38;5;81m{}{}\x1b[0m", inner, fractional)
          }
-         Object::Range(ref inner) => {
+         Object::Range(ref inner) if inner.step == 1 => {
             write!(
                f,
                "[\x1b[38;5;81m{}\x1b[0m..\x1b[38;5;81m{}\x1b[0m]",
                inner.min, inner.max
             )
          }
+         Object::Range(ref inner) => {
+            write!(
+               f,
+               "[\x1b[38;5;81m{}\x1b[0m..\x1b[38;5;81m{}\x1b[0m..\x1b[38;5;81m{}\x1b[0m]",
+               inner.min, inner.max, inner.step
+            )
+         }
+         Object::Interface(ref inner) => write!(f, "{}", inner),
+         Object::Enum(ref inner) => write!(f, "{}", inner),
+         Object::EnumValue(ref inner) => write!(f, "{}", inner),
+         Object::EnumVariant(ref inner) => write!(f, "{}", inner),
+         Object::Regex(ref inner) => write!(f, "{}", inner),
+         Object::DateTime(ref inner) => write!(f, "{}", inner),
+         Object::Channel(ref inner) => fmt::Display::fmt(&format!("<Channel at {:p}>", Rc::as_ptr(inner)), f),
+         Object::Promise(ref inner) => fmt::Display::fmt(&format!("<Promise at {:p}>", Rc::as_ptr(inner)), f),
+         Object::Socket(ref inner) => fmt::Display::fmt(&format!("<Socket to '{}'>", inner.borrow().peer), f),
          Object::Class(ref inner) => {
             let prt_str = format!("{:p}", &*inner.borrow() as *const _);
             fmt::Display::fmt(&format!("<Class '{}' at {}>", inner.borrow().name, prt_str), f)
@@ -368,6 +756,12 @@ impl<'a> fmt::Display for Object {
                   arr_str += &(format!("{}, ", obj))[..];
                }
             }
+
+            // Tuples with a single item have a trailing comma, like their literals.
+            if inner.len() == 1 {
+               arr_str += ",";
+            }
+
             arr_str += ")";
 
             write!(f, "{}", arr_str)
@@ -375,11 +769,17 @@ impl<'a> fmt::Display for Object {
          Object::Dict(ref inner) => {
             let mut arr_str = String::from("{");
 
-            for (idx, key) in inner.borrow().keys().enumerate() {
-               if idx == inner.borrow().keys().len() - 1 {
-                  arr_str += &(format!("'{}': {}", key, inner.borrow().get(key).unwrap()))[..]
+            for (idx, (key, value)) in inner.borrow().iter().enumerate() {
+               // String keys are quoted, so that they can be told apart from other keys.
+               let key = match key {
+                  Object::String(s) => format!("'{}'", s),
+                  _ => format!("{}", key),
+               };
+
+               if idx == inner.borrow().len() - 1 {
+                  arr_str += &(format!("{}: {}", key, value))[..]
                } else {
-                  arr_str += &(format!("'{}': {}, ", key, inner.borrow().get(key).unwrap()))[..]
+                  arr_str += &(format!("{}: {}, ", key, value))[..]
                }
             }
 
@@ -387,6 +787,14 @@ impl<'a> fmt::Display for Object {
 
             write!(f, "{}", arr_str)
          }
+         Object::Set(ref inner) => {
+            let items: Vec<String> = inner.borrow().iter().map(|obj| format!("{}", obj)).collect();
+            write!(f, "Set {{{}}}", items.join(", "))
+         }
+         Object::Bytes(ref inner) => {
+            let bytes: Vec<String> = inner.borrow().iter().map(|b| format!("{:02x}", b)).collect();
+            write!(f, "Bytes [{}]", bytes.join(" "))
+         }
       }
    }
 }
@@ -0,0 +1,112 @@
+use crate::objects::Object;
+use std::borrow::Cow;
+use std::rc::Rc;
+
+/// The bits of a quiet NaN. Floats whose bits do not match this pattern are stored as they are.
+const QNAN: u64 = 0x7ffc_0000_0000_0000;
+/// The sign bit, which is set for values that point to a heap-allocated object.
+const SIGN_BIT: u64 = 0x8000_0000_0000_0000;
+/// The tag bit for integers stored inside the NaN.
+const TAG_INT: u64 = 0x0001_0000_0000_0000;
+/// The bits available for the payload of a NaN-boxed value.
+const PAYLOAD_MASK: u64 = 0x0000_ffff_ffff_ffff;
+
+const NULL_VAL: u64 = QNAN | 1;
+const FALSE_VAL: u64 = QNAN | 2;
+const TRUE_VAL: u64 = QNAN | 3;
+
+/// The range of integers that fit in the payload of a NaN-boxed value.
+const MIN_INLINE_INT: i64 = -(1 << 47);
+const MAX_INLINE_INT: i64 = (1 << 47) - 1;
+
+/// A Hinton object stored in a single 8-byte word. Floats are stored as they are, while null,
+/// booleans, and integers of up to 48 bits are stored in the payload of a quiet NaN. Every other
+/// object is allocated on the heap, and the NaN holds a reference-counted pointer to it.
+pub struct NanBox(u64);
+
+impl NanBox {
+   /// Checks if this value points to a heap-allocated object.
+   fn is_ptr(&self) -> bool {
+      self.0 & (QNAN | SIGN_BIT) == (QNAN | SIGN_BIT)
+   }
+
+   /// Gets the pointer to the heap-allocated object of this value.
+   fn as_ptr(&self) -> *const Object {
+      (self.0 & PAYLOAD_MASK) as *const Object
+   }
+
+   /// Decodes the object stored in this value.
+   pub fn decode(&self) -> Cow<'_, Object> {
+      if self.0 & QNAN != QNAN {
+         return Cow::Owned(Object::Float(f64::from_bits(self.0)));
+      }
+
+      if self.is_ptr() {
+         // SAFETY: The pointer was created by `Rc::into_raw` and is kept alive by this value.
+         return Cow::Borrowed(unsafe { &*self.as_ptr() });
+      }
+
+      Cow::Owned(match self.0 {
+         NULL_VAL => Object::Null,
+         FALSE_VAL => Object::Bool(false),
+         TRUE_VAL => Object::Bool(true),
+         // Sign-extends the 48-bit payload.
+         bits => Object::Int(((bits << 16) as i64) >> 16),
+      })
+   }
+
+   /// Decodes the object stored in this value, consuming the value.
+   pub fn into_decoded(self) -> Object {
+      if !self.is_ptr() {
+         return self.decode().into_owned();
+      }
+
+      // SAFETY: The pointer was created by `Rc::into_raw`. Ownership of the
+      // strong reference is moved out of this value, so it must not be dropped.
+      let rc = unsafe { Rc::from_raw(self.as_ptr()) };
+      std::mem::forget(self);
+      Rc::try_unwrap(rc).unwrap_or_else(|rc| (*rc).clone())
+   }
+}
+
+impl From<Object> for NanBox {
+   fn from(obj: Object) -> Self {
+      match obj {
+         Object::Null => NanBox(NULL_VAL),
+         Object::Bool(b) => NanBox(if b { TRUE_VAL } else { FALSE_VAL }),
+         Object::Float(f) if f.is_nan() => NanBox(f64::NAN.to_bits()),
+         Object::Float(f) => NanBox(f.to_bits()),
+         Object::Int(i) if (MIN_INLINE_INT..=MAX_INLINE_INT).contains(&i) => {
+            NanBox(QNAN | TAG_INT | (i as u64 & PAYLOAD_MASK))
+         }
+         _ => {
+            let ptr = Rc::into_raw(Rc::new(obj)) as u64;
+            debug_assert!(
+               ptr & !PAYLOAD_MASK == 0,
+               "Pointer does not fit in a NaN-boxed value."
+            );
+            NanBox(SIGN_BIT | QNAN | ptr)
+         }
+      }
+   }
+}
+
+impl Clone for NanBox {
+   fn clone(&self) -> Self {
+      if self.is_ptr() {
+         // SAFETY: The pointer was created by `Rc::into_raw` and is kept alive by this value.
+         unsafe { Rc::increment_strong_count(self.as_ptr()) };
+      }
+
+      NanBox(self.0)
+   }
+}
+
+impl Drop for NanBox {
+   fn drop(&mut self) {
+      if self.is_ptr() {
+         // SAFETY: The pointer was created by `Rc::into_raw`, and this value owns one strong reference.
+         unsafe { Rc::decrement_strong_count(self.as_ptr()) };
+      }
+   }
+}
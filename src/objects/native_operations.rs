@@ -1,5 +1,147 @@
+use crate::core::ast::{BinaryExprType, UnaryExprType};
 use crate::errors::ObjectOprErrType;
+use crate::objects::bigint::BigInt;
+use crate::objects::datetime_obj::DateTimeObject;
 use crate::objects::{obj_vectors_equal, Object};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::rc::Rc;
+
+/// Converts an integer object (an Int, a Bool, or a BigInt) into a BigInt.
+fn to_big_int(obj: &Object) -> Option<BigInt> {
+   match obj {
+      Object::BigInt(b) => Some((**b).clone()),
+      Object::Int(_) | Object::Bool(_) => obj.as_int().map(BigInt::from),
+      _ => None,
+   }
+}
+
+/// Applies an arithmetic operation to two integer objects (Ints, Bools, or BigInts). Operations
+/// between Ints that overflow are promoted to BigInts, and operations that involve a BigInt
+/// always produce a BigInt.
+///
+/// # Parameters
+/// - `lhs`: The left-hand operand.
+/// - `rhs`: The right-hand operand.
+/// - `checked`: The checked version of the operation on i64 integers.
+/// - `big`: The operation on BigInts.
+///
+/// # Returns
+/// - `Option<Object>`: The result of the operation, or `None` if an operand is not an integer.
+fn integer_operation(
+   lhs: &Object,
+   rhs: &Object,
+   checked: fn(i64, i64) -> Option<i64>,
+   big: fn(&BigInt, &BigInt) -> BigInt,
+) -> Option<Object> {
+   if let (false, false, Some(l), Some(r)) = (
+      matches!(lhs, Object::BigInt(_)),
+      matches!(rhs, Object::BigInt(_)),
+      lhs.as_int(),
+      rhs.as_int(),
+   ) {
+      if let Some(result) = checked(l, r) {
+         return Some(Object::Int(result));
+      }
+   }
+
+   Some(Object::from(big(&to_big_int(lhs)?, &to_big_int(rhs)?)))
+}
+
+/// Divides two integers, rounding the quotient towards negative infinity.
+///
+/// # Returns
+/// - `Option<i64>`: The quotient, or `None` if the divisor is zero or the quotient overflows.
+fn checked_floor_div(lhs: i64, rhs: i64) -> Option<i64> {
+   let quotient = lhs.checked_div(rhs)?;
+
+   if lhs % rhs != 0 && (lhs < 0) != (rhs < 0) {
+      Some(quotient - 1)
+   } else {
+      Some(quotient)
+   }
+}
+
+/// Divides two BigInts, rounding the quotient towards negative infinity.
+fn big_floor_div(lhs: &BigInt, rhs: &BigInt) -> BigInt {
+   let (quotient, remainder) = lhs.div_rem(rhs).expect("The divisor should not be zero.");
+   let zero = BigInt::from(0);
+
+   if !remainder.is_zero() && (*lhs < zero) != (*rhs < zero) {
+      quotient.sub(&BigInt::from(1))
+   } else {
+      quotient
+   }
+}
+
+/// Converts two numeric objects into floats if at least one of them is a BigInt.
+fn big_int_as_floats(lhs: &Object, rhs: &Object) -> Option<(f64, f64)> {
+   let as_float = |obj: &Object| match obj {
+      Object::BigInt(b) => Some(b.to_f64()),
+      Object::Float(f) => Some(*f),
+      _ => obj.as_int().map(|i| i as f64),
+   };
+
+   if matches!(lhs, Object::BigInt(_)) || matches!(rhs, Object::BigInt(_)) {
+      Some((as_float(lhs)?, as_float(rhs)?))
+   } else {
+      None
+   }
+}
+
+/// Compares two objects if at least one of them is a BigInt, and the other is a number.
+///
+/// # Returns
+/// - `Option<Option<Ordering>>`: The ordering of the objects, which is `None` when comparing a
+///   BigInt with NaN. The outer `None` means that the objects are not a BigInt and a number.
+fn compare_big_int(lhs: &Object, rhs: &Object) -> Option<Option<Ordering>> {
+   match (lhs, rhs) {
+      (Object::BigInt(l), Object::Float(r)) => Some(l.to_f64().partial_cmp(r)),
+      (Object::Float(l), Object::BigInt(r)) => Some(l.partial_cmp(&r.to_f64())),
+      (Object::BigInt(_), _) | (_, Object::BigInt(_)) => Some(Some(to_big_int(lhs)?.cmp(&to_big_int(rhs)?))),
+      _ => None,
+   }
+}
+
+/// Converts a number of seconds (an Int, a Float, or a Bool) into milliseconds, which is how
+/// durations are added to and subtracted from DateTime objects.
+fn seconds_to_millis(obj: &Object) -> Option<i64> {
+   let millis = match obj {
+      Object::Float(f) => (f * 1000.0).round(),
+      _ => obj.as_int()? as f64 * 1000.0,
+   };
+
+   if millis.is_finite() && millis.abs() < i64::MAX as f64 {
+      Some(millis as i64)
+   } else {
+      None
+   }
+}
+
+/// Adds a duration, in seconds, to a DateTime object.
+fn shift_date_time(date: &DateTimeObject, seconds: &Object, opr: &str) -> Result<Object, ObjectOprErrType> {
+   let millis = seconds_to_millis(seconds).ok_or_else(|| {
+      ObjectOprErrType::TypeError(format!(
+         "Operation '{}' not defined for objects of type 'DateTime' and '{}'.",
+         opr,
+         seconds.type_name()
+      ))
+   })?;
+
+   let millis = if opr == "-" {
+      millis.checked_neg()
+   } else {
+      Some(millis)
+   };
+
+   match millis.and_then(|m| date.add_millis(m)) {
+      Some(d) => Ok(Object::DateTime(d)),
+      None => Err(ObjectOprErrType::TypeError(format!(
+         "The result of '{}' is outside the supported range of dates.",
+         opr
+      ))),
+   }
+}
 
 /// Defines negation of Hinton objects.
 impl std::ops::Neg for Object {
@@ -7,7 +149,11 @@ impl std::ops::Neg for Object {
 
    fn neg(self) -> Self::Output {
       match self {
-         Object::Int(lhs) => Ok(Object::Int(-lhs)),
+         Object::Int(lhs) => Ok(match lhs.checked_neg() {
+            Some(x) => Object::Int(x),
+            None => Object::from(BigInt::from(lhs).neg()),
+         }),
+         Object::BigInt(lhs) => Ok(Object::from(lhs.neg())),
          Object::Float(lhs) => Ok(Object::Float(-lhs)),
          Object::Bool(lhs) if lhs => Ok(Object::Int(-1)),
          Object::Bool(lhs) if !lhs => Ok(Object::Int(0)),
@@ -32,19 +178,33 @@ impl std::ops::Add<Object> for Object {
          rhs.type_name()
       )));
 
+      if let Some(result) = integer_operation(&self, &rhs, i64::checked_add, BigInt::add) {
+         return Ok(result);
+      }
+
+      if let Object::DateTime(date) = &rhs {
+         if seconds_to_millis(&self).is_some() {
+            return shift_date_time(date, &self, "+");
+         }
+      }
+
       match self {
          Object::Int(lhs) => match rhs {
-            Object::Int(rhs) => Ok(Object::Int(lhs + rhs)),
             Object::Float(rhs) => Ok(Object::Float(lhs as f64 + rhs)),
-            Object::Bool(rhs) => Ok(Object::Int(lhs + if rhs { 1 } else { 0 })),
-            Object::String(rhs) => Ok(Object::String(format!("{}{}", lhs, rhs))),
+            Object::String(rhs) => Ok(Object::from(format!("{}{}", lhs, rhs))),
+            _ => error_msg,
+         },
+         Object::BigInt(lhs) => match rhs {
+            Object::Float(rhs) => Ok(Object::Float(lhs.to_f64() + rhs)),
+            Object::String(rhs) => Ok(Object::from(format!("{}{}", lhs, rhs))),
             _ => error_msg,
          },
          Object::Float(lhs) => match rhs {
             Object::Int(rhs) => Ok(Object::Float(lhs + rhs as f64)),
+            Object::BigInt(rhs) => Ok(Object::Float(lhs + rhs.to_f64())),
             Object::Float(rhs) => Ok(Object::Float(lhs + rhs)),
             Object::Bool(rhs) => Ok(Object::Float(lhs + if rhs { 1f64 } else { 0f64 })),
-            Object::String(rhs) => Ok(Object::String(format!(
+            Object::String(rhs) => Ok(Object::from(format!(
                "{}{}{}",
                lhs,
                if lhs.fract() == 0.0 { ".0" } else { "" },
@@ -53,28 +213,33 @@ impl std::ops::Add<Object> for Object {
             _ => error_msg,
          },
          Object::String(lhs) => match rhs {
-            Object::Int(rhs) => Ok(Object::String(format!("{}{}", lhs, rhs))),
-            Object::Float(rhs) => Ok(Object::String(format!(
+            Object::Int(rhs) => Ok(Object::from(format!("{}{}", lhs, rhs))),
+            Object::BigInt(rhs) => Ok(Object::from(format!("{}{}", lhs, rhs))),
+            Object::Float(rhs) => Ok(Object::from(format!(
                "{}{}{}",
                lhs,
                rhs,
                if rhs.fract() == 0.0 { ".0" } else { "" }
             ))),
-            Object::String(rhs) => Ok(Object::String(format!("{}{}", lhs, rhs))),
+            Object::String(rhs) => Ok(Object::from(format!("{}{}", lhs, rhs))),
+            Object::Char(rhs) => Ok(Object::from(format!("{}{}", lhs, rhs))),
             _ => error_msg,
          },
-         Object::Bool(lhs) if lhs => match rhs {
-            Object::Int(rhs) => Ok(Object::Int(rhs + 1i64)),
-            Object::Float(rhs) => Ok(Object::Float(rhs + 1f64)),
-            Object::Bool(rhs) => Ok(Object::Int(1 + if rhs { 1 } else { 0 })),
+         // Concatenating a char with a char or a string produces a string.
+         Object::Char(lhs) => match rhs {
+            Object::String(rhs) => Ok(Object::from(format!("{}{}", lhs, rhs))),
+            Object::Char(rhs) => Ok(Object::from(format!("{}{}", lhs, rhs))),
             _ => error_msg,
          },
-         Object::Bool(lhs) if !lhs => match rhs {
-            Object::Int(rhs) => Ok(Object::Int(rhs)),
-            Object::Float(rhs) => Ok(Object::Float(rhs)),
-            Object::Bool(rhs) => Ok(Object::Int(if rhs { 1 } else { 0 })),
+         Object::Bool(lhs) => match rhs {
+            Object::Float(rhs) => Ok(Object::Float(rhs + if lhs { 1f64 } else { 0f64 })),
+            _ => error_msg,
+         },
+         Object::Bytes(lhs) => match rhs {
+            Object::Bytes(rhs) => Ok(Object::from([&lhs.borrow()[..], &rhs.borrow()[..]].concat())),
             _ => error_msg,
          },
+         Object::DateTime(lhs) => shift_date_time(&lhs, &rhs, "+"),
          _ => error_msg,
       }
    }
@@ -91,30 +256,33 @@ impl std::ops::Sub<Object> for Object {
          rhs.type_name()
       )));
 
+      if let Some(result) = integer_operation(&self, &rhs, i64::checked_sub, BigInt::sub) {
+         return Ok(result);
+      }
+
       match self {
          Object::Int(lhs) => match rhs {
-            Object::Int(rhs) => Ok(Object::Int(lhs - rhs)),
             Object::Float(rhs) => Ok(Object::Float(lhs as f64 - rhs)),
-            Object::Bool(rhs) => Ok(Object::Int(lhs - if rhs { 1 } else { 0 })),
+            _ => error_msg,
+         },
+         Object::BigInt(lhs) => match rhs {
+            Object::Float(rhs) => Ok(Object::Float(lhs.to_f64() - rhs)),
             _ => error_msg,
          },
          Object::Float(lhs) => match rhs {
             Object::Int(rhs) => Ok(Object::Float(lhs - rhs as f64)),
+            Object::BigInt(rhs) => Ok(Object::Float(lhs - rhs.to_f64())),
             Object::Float(rhs) => Ok(Object::Float(lhs - rhs)),
             Object::Bool(rhs) => Ok(Object::Float(lhs - if rhs { 1f64 } else { 0f64 })),
             _ => error_msg,
          },
-         Object::Bool(lhs) if lhs => match rhs {
-            Object::Int(rhs) => Ok(Object::Int(1i64 - rhs)),
-            Object::Float(rhs) => Ok(Object::Float(1f64 - rhs)),
-            Object::Bool(rhs) => Ok(Object::Int(1 - if rhs { 1 } else { 0 })),
+         Object::Bool(lhs) => match rhs {
+            Object::Float(rhs) => Ok(Object::Float(if lhs { 1f64 } else { 0f64 } - rhs)),
             _ => error_msg,
          },
-         Object::Bool(lhs) if !lhs => match rhs {
-            Object::Int(rhs) => Ok(Object::Int(-rhs)),
-            Object::Float(rhs) => Ok(Object::Float(-rhs)),
-            Object::Bool(rhs) => Ok(Object::Int(if rhs { -1 } else { 0 })),
-            _ => error_msg,
+         Object::DateTime(lhs) => match rhs {
+            Object::DateTime(rhs) => Ok(Object::Float((lhs.millis() - rhs.millis()) as f64 / 1000.0)),
+            _ => shift_date_time(&lhs, &rhs, "-"),
          },
          _ => error_msg,
       }
@@ -132,34 +300,33 @@ impl std::ops::Mul<Object> for Object {
          rhs.type_name()
       )));
 
+      if let Some(result) = integer_operation(&self, &rhs, i64::checked_mul, BigInt::mul) {
+         return Ok(result);
+      }
+
       match self {
          Object::Int(lhs) => match rhs {
-            Object::Int(rhs) => Ok(Object::Int(lhs.saturating_mul(rhs))),
             Object::Float(rhs) => Ok(Object::Float(lhs as f64 * rhs)),
-            Object::Bool(rhs) => Ok(Object::Int(if rhs { lhs } else { 0 })),
-            Object::String(rhs) => Ok(Object::String(rhs.repeat(lhs as usize))),
+            Object::String(rhs) => Ok(Object::from(rhs.repeat(lhs as usize))),
+            _ => error_msg,
+         },
+         Object::BigInt(lhs) => match rhs {
+            Object::Float(rhs) => Ok(Object::Float(lhs.to_f64() * rhs)),
             _ => error_msg,
          },
          Object::Float(lhs) => match rhs {
             Object::Int(rhs) => Ok(Object::Float(lhs * rhs as f64)),
+            Object::BigInt(rhs) => Ok(Object::Float(lhs * rhs.to_f64())),
             Object::Float(rhs) => Ok(Object::Float(lhs * rhs)),
             Object::Bool(rhs) => Ok(Object::Float(if rhs { lhs } else { 0f64 })),
             _ => error_msg,
          },
          Object::String(lhs) => match rhs {
-            Object::Int(rhs) => Ok(Object::String(lhs.repeat(rhs as usize))),
-            _ => error_msg,
-         },
-         Object::Bool(lhs) if lhs => match rhs {
-            Object::Int(rhs) => Ok(Object::Int(rhs)),
-            Object::Float(rhs) => Ok(Object::Float(rhs)),
-            Object::Bool(rhs) => Ok(Object::Int(if rhs { 1 } else { 0 })),
+            Object::Int(rhs) => Ok(Object::from(lhs.repeat(rhs as usize))),
             _ => error_msg,
          },
-         Object::Bool(lhs) if !lhs => match rhs {
-            Object::Int(_) => Ok(Object::Int(0)),
-            Object::Float(_) => Ok(Object::Float(0f64)),
-            Object::Bool(_) => Ok(Object::Int(0)),
+         Object::Bool(lhs) => match rhs {
+            Object::Float(rhs) => Ok(Object::Float(if lhs { rhs } else { 0f64 })),
             _ => error_msg,
          },
          _ => error_msg,
@@ -182,12 +349,18 @@ impl std::ops::Div<Object> for Object {
       if rhs.is_int() && rhs.as_int().unwrap() == 0
          || rhs.is_float() && rhs.as_float().unwrap() == 0f64
          || rhs.is_bool() && !rhs.as_bool().unwrap()
+         || matches!(&rhs, Object::BigInt(b) if b.is_zero())
       {
          return Err(ObjectOprErrType::ZeroDivisionError(String::from(
             "Cannot divide by zero.",
          )));
       }
 
+      // Divisions that involve a BigInt produce a float, just like divisions of Ints.
+      if let Some((lhs, rhs)) = big_int_as_floats(&self, &rhs) {
+         return Ok(Object::Float(lhs / rhs));
+      }
+
       match self {
          Object::Int(lhs) => match rhs {
             Object::Int(rhs) => Ok(Object::Float(lhs as f64 / rhs as f64)),
@@ -233,17 +406,26 @@ impl std::ops::Rem<Object> for Object {
       if rhs.is_int() && rhs.as_int().unwrap() == 0
          || rhs.is_float() && rhs.as_float().unwrap() == 0f64
          || rhs.is_bool() && !rhs.as_bool().unwrap()
+         || matches!(&rhs, Object::BigInt(b) if b.is_zero())
       {
          return Err(ObjectOprErrType::ZeroDivisionError(String::from(
             "Right-hand-side of modulus cannot be zero.",
          )));
       }
 
+      if let Some(result) = integer_operation(&self, &rhs, i64::checked_rem, |lhs, rhs| {
+         lhs.div_rem(rhs).expect("The divisor should not be zero.").1
+      }) {
+         return Ok(result);
+      }
+
+      if let Some((lhs, rhs)) = big_int_as_floats(&self, &rhs) {
+         return Ok(Object::Float(lhs % rhs));
+      }
+
       match self {
          Object::Int(lhs) => match rhs {
-            Object::Int(rhs) => Ok(Object::Int(lhs % rhs)),
-            Object::Float(rhs) => Ok(Object::Int(lhs % rhs.floor() as i64)),
-            Object::Bool(_) => Ok(Object::Int(0i64)),
+            Object::Float(rhs) => Ok(Object::Float(lhs as f64 % rhs)),
             _ => error_msg,
          },
          Object::Float(lhs) => match rhs {
@@ -252,16 +434,8 @@ impl std::ops::Rem<Object> for Object {
             Object::Bool(_) => Ok(Object::Float(lhs % 1f64)),
             _ => error_msg,
          },
-         Object::Bool(lhs) if lhs => match rhs {
-            Object::Int(rhs) => Ok(Object::Int(1i64 % rhs)),
-            Object::Float(rhs) => Ok(Object::Float(1f64 % rhs)),
-            Object::Bool(_) => Ok(Object::Int(0i64)),
-            _ => error_msg,
-         },
-         Object::Bool(lhs) if !lhs => match rhs {
-            Object::Int(_) => Ok(Object::Int(0i64)),
-            Object::Float(_) => Ok(Object::Float(0f64)),
-            Object::Bool(_) => Ok(Object::Int(0i64)),
+         Object::Bool(lhs) => match rhs {
+            Object::Float(rhs) => Ok(Object::Float(if lhs { 1f64 } else { 0f64 } % rhs)),
             _ => error_msg,
          },
          _ => error_msg,
@@ -431,6 +605,10 @@ impl Eq for Object {}
 /// Defines the equality operation for Hinton objects.
 impl PartialEq for Object {
    fn eq(&self, right: &Self) -> bool {
+      if let Some(ordering) = compare_big_int(self, right) {
+         return ordering == Some(Ordering::Equal);
+      }
+
       match self {
          Object::Int(i) => match right {
             Object::Int(x) if i == x => true,
@@ -450,9 +628,11 @@ impl PartialEq for Object {
             Object::Bool(x) => !(b ^ x),
             _ => false,
          },
+         Object::Char(a) => matches!(right, Object::Char(c) if a == c),
          Object::String(a) => {
             if let Object::String(s) = right {
-               a == s
+               // Interned strings with the same contents share the same allocation.
+               Rc::ptr_eq(a, s) || a == s
             } else {
                false
             }
@@ -473,9 +653,9 @@ impl PartialEq for Object {
          }
          Object::Range(a) => {
             if let Object::Range(r) = right {
-               // If the ranges match in boundaries,
+               // If the ranges match in boundaries and steps,
                // then they are equal in value.
-               a.min == r.min && a.max == r.max
+               a.min == r.min && a.max == r.max && a.step == r.step
             } else {
                false
             }
@@ -493,7 +673,7 @@ impl PartialEq for Object {
                for (key, val_1) in d1.iter() {
                   // If the current key in d1 does not exist in d2,
                   // then the dictionaries are not equal.
-                  let val_2 = match d2.get(key) {
+                  let val_2 = match d2.get_key(key) {
                      Some(v) => v,
                      None => return false,
                   };
@@ -510,6 +690,21 @@ impl PartialEq for Object {
                false
             }
          }
+         Object::Bytes(b1) => {
+            if let Object::Bytes(b2) = right {
+               *b1.borrow() == *b2.borrow()
+            } else {
+               false
+            }
+         }
+         Object::Set(s1) => {
+            if let Object::Set(s2) = right {
+               let (s1, s2) = (s1.borrow(), s2.borrow());
+               s1.len() == s2.len() && s1.iter().all(|item| s2.contains(item))
+            } else {
+               false
+            }
+         }
          Object::Native(n1) => {
             if let Object::Native(n2) = right {
                n1.name == n2.name
@@ -538,6 +733,41 @@ impl PartialEq for Object {
                false
             }
          }
+         Object::Interface(i1) => {
+            if let Object::Interface(i2) = right {
+               Rc::ptr_eq(i1, i2)
+            } else {
+               false
+            }
+         }
+         Object::Enum(e1) => matches!(right, Object::Enum(e2) if Rc::ptr_eq(e1, e2)),
+         Object::EnumVariant(v1) => {
+            matches!(right, Object::EnumVariant(v2) if Rc::ptr_eq(&v1.enum_obj, &v2.enum_obj) && v1.variant == v2.variant)
+         }
+         // Values of an enum are equal if they are the same variant, with equal payloads.
+         Object::EnumValue(v1) => {
+            if let Object::EnumValue(v2) = right {
+               Rc::ptr_eq(&v1.variant.enum_obj, &v2.variant.enum_obj)
+                  && v1.variant.variant == v2.variant.variant
+                  && obj_vectors_equal(&v1.payload, &v2.payload)
+            } else {
+               false
+            }
+         }
+         Object::DateTime(d1) => matches!(right, Object::DateTime(d2) if d1 == d2),
+         Object::Regex(r1) => {
+            if let Object::Regex(r2) = right {
+               r1.pattern == r2.pattern && r1.flags == r2.flags
+            } else {
+               false
+            }
+         }
+         // Channels are only equal to themselves.
+         Object::Channel(c1) => matches!(right, Object::Channel(c2) if Rc::ptr_eq(c1, c2)),
+         // Promises are only equal to themselves.
+         Object::Promise(p1) => matches!(right, Object::Promise(p2) if Rc::ptr_eq(p1, p2)),
+         // Sockets are only equal to themselves.
+         Object::Socket(s1) => matches!(right, Object::Socket(s2) if Rc::ptr_eq(s1, s2)),
          Object::Null => matches!(right, Object::Null),
          _ => false,
       }
@@ -545,6 +775,145 @@ impl PartialEq for Object {
 }
 
 impl Object {
+   /// Applies a unary operator to a Hinton object.
+   ///
+   /// # Parameters
+   /// - `opr`: The unary operator.
+   pub fn unary_operation(self, opr: &UnaryExprType) -> Result<Object, ObjectOprErrType> {
+      match opr {
+         UnaryExprType::ArithmeticNeg => -self,
+         UnaryExprType::LogicNeg => Ok(Object::Bool(self.is_falsey())),
+         UnaryExprType::BitwiseNeg => !self,
+         // Awaiting a value that is not a promise results in the value itself.
+         UnaryExprType::Await => Ok(self),
+      }
+   }
+
+   /// Applies a binary operator to two Hinton objects. The short-circuited operators
+   /// (logic 'AND' and logic 'OR') and the range operator are not applied here.
+   ///
+   /// # Parameters
+   /// - `opr`: The binary operator.
+   /// - `rhs`: The right-hand side of the operation.
+   pub fn binary_operation(self, opr: &BinaryExprType, rhs: Object) -> Result<Object, ObjectOprErrType> {
+      match opr {
+         BinaryExprType::Addition => self + rhs,
+         BinaryExprType::BitwiseAND => self & rhs,
+         BinaryExprType::BitwiseOR => self | rhs,
+         BinaryExprType::BitwiseShiftLeft => self << rhs,
+         BinaryExprType::BitwiseShiftRight => self >> rhs,
+         BinaryExprType::BitwiseXOR => self ^ rhs,
+         BinaryExprType::Division => self / rhs,
+         BinaryExprType::Expo => self.pow(rhs),
+         BinaryExprType::FloorDivision => self.floor_div(rhs),
+         BinaryExprType::LogicEQ => Ok(Object::Bool(self == rhs)),
+         BinaryExprType::LogicGreaterThan => self.gt(rhs),
+         BinaryExprType::LogicGreaterThanEQ => self.gteq(rhs),
+         BinaryExprType::LogicIn => rhs.contains(&self).map(Object::Bool),
+         BinaryExprType::LogicLessThan => self.lt(rhs),
+         BinaryExprType::LogicLessThanEQ => self.lteq(rhs),
+         BinaryExprType::LogicNotEQ => Ok(Object::Bool(self != rhs)),
+         BinaryExprType::Minus => self - rhs,
+         BinaryExprType::Modulus => self % rhs,
+         BinaryExprType::Multiplication => self * rhs,
+         BinaryExprType::Nullish => {
+            if matches!(self, Object::Null) {
+               Ok(rhs)
+            } else {
+               Ok(self)
+            }
+         }
+         _ => unreachable!("The other binary operations have special instruction methods."),
+      }
+   }
+
+   /// Checks if this Hinton object contains another object, as in `item in self`. Arrays and
+   /// tuples contain their items, sets contain their items, dictionaries contain their keys,
+   /// strings contain their substrings and chars, Bytes contain their bytes and their subsequences,
+   /// and ranges contain the integers they produce.
+   ///
+   /// # Parameters
+   /// - `item`: The object to look for.
+   pub fn contains(&self, item: &Object) -> Result<bool, ObjectOprErrType> {
+      Ok(match self {
+         Object::Array(arr) => arr.borrow().iter().any(|x| x == item),
+         Object::Tuple(tup) => tup.iter().any(|x| x == item),
+         Object::Set(set) => set.borrow().contains(item),
+         Object::Dict(dict) => dict.borrow().contains(item),
+         Object::Range(range) => match item.as_int() {
+            Some(i) if item.is_int() || item.is_bool() => range.contains(i),
+            _ => false,
+         },
+         Object::Bytes(bytes) => match item {
+            Object::Int(b) => bytes.borrow().iter().any(|x| *x as i64 == *b),
+            Object::Bytes(sub) if sub.borrow().is_empty() => true,
+            Object::Bytes(sub) => bytes
+               .borrow()
+               .windows(sub.borrow().len())
+               .any(|w| w == &sub.borrow()[..]),
+            _ => {
+               return Err(ObjectOprErrType::TypeError(format!(
+               "The left-hand side of 'in' must be an Int or Bytes when searching Bytes. Found '{}' instead.",
+               item.type_name()
+            )))
+            }
+         },
+         Object::String(s) => match item {
+            Object::String(sub) => s.contains(&**sub),
+            Object::Char(c) => s.contains(*c),
+            _ => {
+               return Err(ObjectOprErrType::TypeError(format!(
+                  "The left-hand side of 'in' must be a String or a Char when searching a String. Found '{}' instead.",
+                  item.type_name()
+               )))
+            }
+         },
+         _ => {
+            return Err(ObjectOprErrType::TypeError(format!(
+               "Operation 'in' not defined for objects of type '{}'.",
+               self.type_name()
+            )))
+         }
+      })
+   }
+
+   /// Defines floor division of Hinton objects, which rounds the quotient towards negative
+   /// infinity. The floor division of two integers is an integer (promoted to a BigInt if it
+   /// overflows), while the floor division that involves a float is a float.
+   pub fn floor_div(self, rhs: Object) -> Result<Object, ObjectOprErrType> {
+      let error_msg = Err(ObjectOprErrType::TypeError(format!(
+         "Operation '~/' not defined for objects of type '{}' and '{}'.",
+         self.type_name(),
+         rhs.type_name()
+      )));
+
+      // Divide-by-zero errors
+      if rhs.is_int() && rhs.as_int().unwrap() == 0
+         || rhs.is_float() && rhs.as_float().unwrap() == 0f64
+         || rhs.is_bool() && !rhs.as_bool().unwrap()
+         || matches!(&rhs, Object::BigInt(b) if b.is_zero())
+      {
+         return Err(ObjectOprErrType::ZeroDivisionError(String::from(
+            "Cannot divide by zero.",
+         )));
+      }
+
+      if let Some(result) = integer_operation(&self, &rhs, checked_floor_div, big_floor_div) {
+         return Ok(result);
+      }
+
+      let as_float = |obj: &Object| match obj {
+         Object::BigInt(b) => Some(b.to_f64()),
+         Object::Float(f) => Some(*f),
+         _ => obj.as_int().map(|i| i as f64),
+      };
+
+      match (as_float(&self), as_float(&rhs)) {
+         (Some(lhs), Some(rhs)) => Ok(Object::Float((lhs / rhs).floor())),
+         _ => error_msg,
+      }
+   }
+
    /// Defines exponentiation of Hinton objects.
    pub fn pow(self, rhs: Object) -> Result<Object, ObjectOprErrType> {
       let error_msg = Err(ObjectOprErrType::TypeError(format!(
@@ -553,6 +922,35 @@ impl Object {
          rhs.type_name()
       )));
 
+      // Integer powers with a non-negative exponent are exact, so they are promoted to BigInts
+      // instead of overflowing.
+      if let (Some(base), Some(exp)) = (to_big_int(&self), to_big_int(&rhs)) {
+         if let Some(exp) = exp.to_i64().filter(|e| *e >= 0) {
+            let exp = match u32::try_from(exp) {
+               Ok(e) => e,
+               Err(_) => {
+                  return Err(ObjectOprErrType::TypeError(String::from(
+                     "The exponent of '**' is too large.",
+                  )))
+               }
+            };
+
+            let small = match (&self, &rhs) {
+               (Object::BigInt(_), _) | (_, Object::BigInt(_)) => None,
+               _ => self.as_int().and_then(|b| b.checked_pow(exp)),
+            };
+
+            return Ok(match small {
+               Some(result) => Object::Int(result),
+               None => Object::from(base.pow(exp)),
+            });
+         }
+      }
+
+      if let Some((lhs, rhs)) = big_int_as_floats(&self, &rhs) {
+         return Ok(Object::Float(lhs.powf(rhs)));
+      }
+
       match self {
          // TODO: These conversions seems error-prone and slow...
          Object::Int(lhs) => match rhs {
@@ -594,6 +992,10 @@ impl Object {
          rhs.type_name()
       )));
 
+      if let Some(ordering) = compare_big_int(&self, &rhs) {
+         return Ok(Object::Bool(matches!(ordering, Some(Ordering::Greater))));
+      }
+
       match self {
          Object::Int(lhs) => match rhs {
             Object::Int(rhs) => Ok(Object::Bool(lhs > rhs)),
@@ -613,6 +1015,14 @@ impl Object {
             Object::Bool(rhs) => Ok(Object::Bool(if lhs { 1 } else { 0 } > rhs as i64)),
             _ => error_msg,
          },
+         Object::DateTime(lhs) => match rhs {
+            Object::DateTime(rhs) => Ok(Object::Bool(lhs > rhs)),
+            _ => error_msg,
+         },
+         Object::Char(lhs) => match rhs {
+            Object::Char(rhs) => Ok(Object::Bool(lhs > rhs)),
+            _ => error_msg,
+         },
          _ => error_msg,
       }
    }
@@ -625,6 +1035,13 @@ impl Object {
          rhs.type_name()
       )));
 
+      if let Some(ordering) = compare_big_int(&self, &rhs) {
+         return Ok(Object::Bool(matches!(
+            ordering,
+            Some(Ordering::Greater | Ordering::Equal)
+         )));
+      }
+
       match self {
          Object::Int(lhs) => match rhs {
             Object::Int(rhs) => Ok(Object::Bool(lhs >= rhs)),
@@ -644,6 +1061,14 @@ impl Object {
             Object::Bool(rhs) => Ok(Object::Bool(if lhs { 1 } else { 0 } >= rhs as i64)),
             _ => error_msg,
          },
+         Object::DateTime(lhs) => match rhs {
+            Object::DateTime(rhs) => Ok(Object::Bool(lhs >= rhs)),
+            _ => error_msg,
+         },
+         Object::Char(lhs) => match rhs {
+            Object::Char(rhs) => Ok(Object::Bool(lhs >= rhs)),
+            _ => error_msg,
+         },
          _ => error_msg,
       }
    }
@@ -656,6 +1081,10 @@ impl Object {
          rhs.type_name()
       )));
 
+      if let Some(ordering) = compare_big_int(&self, &rhs) {
+         return Ok(Object::Bool(matches!(ordering, Some(Ordering::Less))));
+      }
+
       match self {
          Object::Int(lhs) => match rhs {
             Object::Int(rhs) => Ok(Object::Bool(lhs < rhs)),
@@ -675,6 +1104,14 @@ impl Object {
             Object::Bool(rhs) => Ok(Object::Bool(if lhs { 1 } else { 0 } < rhs as i64)),
             _ => error_msg,
          },
+         Object::DateTime(lhs) => match rhs {
+            Object::DateTime(rhs) => Ok(Object::Bool(lhs < rhs)),
+            _ => error_msg,
+         },
+         Object::Char(lhs) => match rhs {
+            Object::Char(rhs) => Ok(Object::Bool(lhs < rhs)),
+            _ => error_msg,
+         },
          _ => error_msg,
       }
    }
@@ -687,6 +1124,13 @@ impl Object {
          rhs.type_name()
       )));
 
+      if let Some(ordering) = compare_big_int(&self, &rhs) {
+         return Ok(Object::Bool(matches!(
+            ordering,
+            Some(Ordering::Less | Ordering::Equal)
+         )));
+      }
+
       match self {
          Object::Int(lhs) => match rhs {
             Object::Int(rhs) => Ok(Object::Bool(lhs <= rhs)),
@@ -706,6 +1150,14 @@ impl Object {
             Object::Bool(rhs) => Ok(Object::Bool(if lhs { 1 } else { 0 } <= rhs as i64)),
             _ => error_msg,
          },
+         Object::DateTime(lhs) => match rhs {
+            Object::DateTime(rhs) => Ok(Object::Bool(lhs <= rhs)),
+            _ => error_msg,
+         },
+         Object::Char(lhs) => match rhs {
+            Object::Char(rhs) => Ok(Object::Bool(lhs <= rhs)),
+            _ => error_msg,
+         },
          _ => error_msg,
       }
    }
@@ -0,0 +1,57 @@
+use crate::errors::RuntimeErrorType;
+use crate::objects::Object;
+
+/// The state of a Hinton promise.
+#[derive(Default)]
+pub enum PromiseState {
+   /// The value of the promise is not known yet.
+   #[default]
+   Pending,
+   /// The promise was fulfilled with a value.
+   Resolved(Object),
+   /// The promise failed with a runtime error, which is raised again wherever it is awaited.
+   Rejected(RuntimeErrorType, String),
+}
+
+/// Represents the eventual result of an asynchronous operation, such as a call to an `async`
+/// function, or a timer created with the `delay(...)` native function. A promise is settled
+/// only once, after which its state never changes.
+#[derive(Default)]
+pub struct PromiseObject {
+   /// The state of the promise.
+   pub state: PromiseState,
+}
+
+impl PromiseObject {
+   /// Fulfills the promise with a value, if it has not been settled yet.
+   pub fn resolve(&mut self, value: Object) {
+      if !self.is_settled() {
+         self.state = PromiseState::Resolved(value);
+      }
+   }
+
+   /// Fails the promise with a runtime error, if it has not been settled yet.
+   pub fn reject(&mut self, error: RuntimeErrorType, message: String) {
+      if !self.is_settled() {
+         self.state = PromiseState::Rejected(error, message);
+      }
+   }
+
+   /// Checks if the promise has been resolved or rejected.
+   pub fn is_settled(&self) -> bool {
+      !matches!(self.state, PromiseState::Pending)
+   }
+
+   /// Gets the value the promise was resolved with, if it has been resolved.
+   pub fn value(&self) -> Option<&Object> {
+      match &self.state {
+         PromiseState::Resolved(value) => Some(value),
+         _ => None,
+      }
+   }
+
+   /// Removes the state of the promise, leaving it pending.
+   pub fn take(&mut self) -> PromiseState {
+      std::mem::take(&mut self.state)
+   }
+}
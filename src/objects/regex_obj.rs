@@ -0,0 +1,764 @@
+use std::fmt;
+
+/// The position (in characters) and the description of an error in a regular expression.
+pub type RegexError = (usize, String);
+
+/// The start and end (in characters) of each capture group in a match. Group 0 is the whole match.
+pub type Captures = Vec<Option<(usize, usize)>>;
+
+/// The maximum number of instructions in a compiled regular expression. Counted repetitions
+/// copy the repeated node, so small patterns can compile into large programs.
+const MAX_PROGRAM_LEN: usize = 100_000;
+
+/// The maximum nesting depth of the groups in a regular expression.
+const MAX_GROUP_DEPTH: usize = 250;
+
+/// A compiled Hinton regular expression. Regular expressions support the common subset of the
+/// syntax of Rust's `regex` crate: literals, `.`, character classes, the `\d`, `\w`, and `\s`
+/// classes, the `^` and `$` anchors, word boundaries, alternations, greedy and lazy repetitions,
+/// and capture groups (optionally named).
+///
+/// Like the `regex` crate, they are compiled into a program for a Pike VM, which runs all the
+/// ways of matching the pattern in lockstep. Matching takes time linear in the length of the
+/// text (for a given pattern), and never backtracks or recurses.
+pub struct RegexObject {
+   /// The source of the regular expression.
+   pub pattern: String,
+   /// The flags of the regular expression (`i`, `m`, and `s`).
+   pub flags: String,
+   /// The names of the capture groups, indexed by group number.
+   pub group_names: Vec<Option<String>>,
+   program: Vec<Inst>,
+   ignore_case: bool,
+   multi_line: bool,
+   dot_all: bool,
+}
+
+/// The nodes of a parsed regular expression.
+enum Node {
+   Empty,
+   Char(char),
+   Any,
+   Class(CharClass),
+   LineStart,
+   LineEnd,
+   WordBoundary(bool),
+   Group(Box<Node>, Option<usize>),
+   Concat(Vec<Node>),
+   Alternation(Vec<Node>),
+   Repeat {
+      node: Box<Node>,
+      min: usize,
+      max: Option<usize>,
+      greedy: bool,
+   },
+}
+
+/// The instructions of a compiled regular expression.
+#[derive(Clone)]
+enum Inst {
+   /// Consumes a character equal to this one.
+   Char(char),
+   /// Consumes any character (except line breaks, unless the `s` flag is set).
+   Any,
+   /// Consumes a character in the class.
+   Class(CharClass),
+   LineStart,
+   LineEnd,
+   WordBoundary(bool),
+   /// Continues at both instructions, preferring the first one.
+   Split(usize, usize),
+   Jump(usize),
+   /// Records the current position in a capture slot.
+   Save(usize),
+   Match,
+}
+
+/// A set of characters, as in `[a-z_]` or `\d`.
+#[derive(Clone)]
+struct CharClass {
+   negated: bool,
+   items: Vec<ClassItem>,
+}
+
+/// An item in a set of characters.
+#[derive(Clone)]
+enum ClassItem {
+   Range(char, char),
+   Digit(bool),
+   Word(bool),
+   Space(bool),
+}
+
+impl ClassItem {
+   /// Checks if a character matches this item.
+   fn matches(&self, c: char) -> bool {
+      match self {
+         ClassItem::Range(from, to) => *from <= c && c <= *to,
+         ClassItem::Digit(negated) => c.is_ascii_digit() != *negated,
+         ClassItem::Word(negated) => is_word_char(c) != *negated,
+         ClassItem::Space(negated) => c.is_whitespace() != *negated,
+      }
+   }
+}
+
+/// Checks if a character is a word character, as matched by `\w`.
+fn is_word_char(c: char) -> bool {
+   c.is_alphanumeric() || c == '_'
+}
+
+impl RegexObject {
+   /// Compiles a regular expression.
+   ///
+   /// # Parameters
+   /// - `pattern`: The source of the regular expression.
+   /// - `flags`: Any combination of `i` (ignore case), `m` (`^` and `$` match at line breaks),
+   ///   and `s` (`.` matches line breaks).
+   ///
+   /// # Returns
+   /// - `Result<RegexObject, RegexError>`: The compiled regular expression, or the position
+   ///   and description of the error in the pattern.
+   pub fn new(pattern: &str, flags: &str) -> Result<RegexObject, RegexError> {
+      if let Some(flag) = flags.chars().find(|f| !"ims".contains(*f)) {
+         return Err((0, format!("Unknown flag '{}'.", flag)));
+      }
+
+      let mut parser = PatternParser {
+         chars: pattern.chars().collect(),
+         pos: 0,
+         depth: 0,
+         group_names: vec![None],
+      };
+
+      let root = parser.parse_alternation()?;
+      if parser.pos < parser.chars.len() {
+         return Err((parser.pos, String::from("Unmatched ')'.")));
+      }
+
+      // The whole match is recorded in the slots of group 0.
+      let mut program = vec![Inst::Save(0)];
+      compile_node(&root, &mut program)?;
+      program.push(Inst::Save(1));
+      program.push(Inst::Match);
+
+      Ok(RegexObject {
+         pattern: pattern.to_string(),
+         flags: flags.to_string(),
+         group_names: parser.group_names,
+         program,
+         ignore_case: flags.contains('i'),
+         multi_line: flags.contains('m'),
+         dot_all: flags.contains('s'),
+      })
+   }
+
+   /// Finds the first match of this regular expression that starts at or after a position.
+   /// Among the matches that start at the same position, the match preferred by the
+   /// alternations and repetitions of the pattern is found (like a backtracking matcher would).
+   ///
+   /// # Parameters
+   /// - `text`: The characters of the searched text.
+   /// - `start`: The position where the search starts.
+   ///
+   /// # Returns
+   /// - `Option<Captures>`: The positions of the capture groups of the match, if any.
+   pub fn captures_at(&self, text: &[char], start: usize) -> Option<Captures> {
+      let slot_count = self.group_names.len() * 2;
+      let mut visited = vec![0; self.program.len()];
+      let mut current: Vec<Thread> = vec![];
+      let mut next: Vec<Thread> = vec![];
+      let mut matched: Option<Vec<Option<usize>>> = None;
+
+      for pos in start..=text.len() {
+         // A new match can start at each position until a match is found. Its thread has a
+         // lower priority than the threads of the matches that started earlier.
+         if matched.is_none() {
+            let mut ctx = ThreadCtx {
+               regex: self,
+               text,
+               visited: &mut visited,
+               generation: pos + 1,
+            };
+            ctx.add_thread(&mut current, 0, pos, vec![None; slot_count]);
+         }
+
+         if current.is_empty() {
+            if matched.is_some() {
+               break;
+            }
+            continue;
+         }
+
+         let mut ctx = ThreadCtx {
+            regex: self,
+            text,
+            visited: &mut visited,
+            generation: pos + 2,
+         };
+
+         for thread in current.drain(..) {
+            match &self.program[thread.pc] {
+               Inst::Match => {
+                  // The threads after this one have a lower priority, so they are discarded.
+                  matched = Some(thread.slots);
+                  break;
+               }
+               inst => {
+                  if pos < text.len() && self.char_matches(inst, text[pos]) {
+                     ctx.add_thread(&mut next, thread.pc + 1, pos + 1, thread.slots);
+                  }
+               }
+            }
+         }
+
+         current.clear();
+         std::mem::swap(&mut current, &mut next);
+      }
+
+      matched.map(|slots| {
+         slots
+            .chunks(2)
+            .map(|pair| match (pair[0], pair[1]) {
+               (Some(start), Some(end)) => Some((start, end)),
+               _ => None,
+            })
+            .collect()
+      })
+   }
+
+   /// Finds all the non-overlapping matches of this regular expression in a text.
+   ///
+   /// # Parameters
+   /// - `text`: The characters of the searched text.
+   /// - `limit`: The maximum number of matches to find, if any.
+   pub fn all_captures(&self, text: &[char], limit: Option<usize>) -> Vec<Captures> {
+      let mut matches = vec![];
+      let mut pos = 0;
+
+      while pos <= text.len() && limit.is_none_or(|l| matches.len() < l) {
+         let captures = match self.captures_at(text, pos) {
+            Some(c) => c,
+            None => break,
+         };
+
+         let (start, end) = captures[0].unwrap();
+         // Empty matches advance the search by one character, so that the search ends.
+         pos = if end == start { end + 1 } else { end };
+         matches.push(captures);
+      }
+
+      matches
+   }
+
+   /// Checks if a character matches a single-character instruction.
+   fn char_matches(&self, inst: &Inst, c: char) -> bool {
+      match inst {
+         Inst::Char(expected) if self.ignore_case => chars_eq_ignore_case(*expected, c),
+         Inst::Char(expected) => *expected == c,
+         Inst::Any => self.dot_all || c != '\n',
+         Inst::Class(class) => {
+            let matches = |c: char| class.items.iter().any(|item| item.matches(c));
+            let found = if self.ignore_case {
+               c.to_lowercase().chain(c.to_uppercase()).any(matches)
+            } else {
+               matches(c)
+            };
+
+            found != class.negated
+         }
+         _ => false,
+      }
+   }
+
+   /// Checks if a zero-width assertion holds at a position of the text.
+   fn assertion_holds(&self, inst: &Inst, text: &[char], pos: usize) -> bool {
+      match inst {
+         Inst::LineStart => pos == 0 || (self.multi_line && text[pos - 1] == '\n'),
+         Inst::LineEnd => pos == text.len() || (self.multi_line && text[pos] == '\n'),
+         Inst::WordBoundary(expected) => {
+            let before = pos > 0 && is_word_char(text[pos - 1]);
+            let after = pos < text.len() && is_word_char(text[pos]);
+            (before != after) == *expected
+         }
+         _ => false,
+      }
+   }
+}
+
+impl fmt::Display for RegexObject {
+   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      write!(f, "<Regex /{}/{}>", self.pattern, self.flags)
+   }
+}
+
+/// A way of matching the pattern, which waits at a consuming (or `Match`) instruction.
+struct Thread {
+   pc: usize,
+   /// The start and end positions of the capture groups, in pairs.
+   slots: Vec<Option<usize>>,
+}
+
+/// The state used to add the threads that are waiting at one position of the text.
+struct ThreadCtx<'a> {
+   regex: &'a RegexObject,
+   text: &'a [char],
+   /// The generation in which each instruction was last visited.
+   visited: &'a mut Vec<usize>,
+   /// The generation of the threads being added. Each position of the text has its own
+   /// generation, so each instruction is visited once per position.
+   generation: usize,
+}
+
+impl ThreadCtx<'_> {
+   /// Adds a thread at an instruction, following the jumps, splits, saves, and assertions
+   /// (with an explicit stack) until the thread waits at a consuming instruction. Threads are
+   /// added in the order of their priority, and instructions already visited are skipped.
+   fn add_thread(&mut self, threads: &mut Vec<Thread>, pc: usize, pos: usize, slots: Vec<Option<usize>>) {
+      let mut stack = vec![(pc, slots)];
+
+      while let Some((pc, mut slots)) = stack.pop() {
+         if self.visited[pc] == self.generation {
+            continue;
+         }
+         self.visited[pc] = self.generation;
+
+         match &self.regex.program[pc] {
+            Inst::Jump(to) => stack.push((*to, slots)),
+            Inst::Split(first, second) => {
+               stack.push((*second, slots.clone()));
+               stack.push((*first, slots));
+            }
+            Inst::Save(slot) => {
+               slots[*slot] = Some(pos);
+               stack.push((pc + 1, slots));
+            }
+            inst @ (Inst::LineStart | Inst::LineEnd | Inst::WordBoundary(_)) => {
+               if self.regex.assertion_holds(inst, self.text, pos) {
+                  stack.push((pc + 1, slots));
+               }
+            }
+            Inst::Char(_) | Inst::Any | Inst::Class(_) | Inst::Match => threads.push(Thread { pc, slots }),
+         }
+      }
+   }
+}
+
+/// Compiles a node into the instructions of a program.
+fn compile_node(node: &Node, program: &mut Vec<Inst>) -> Result<(), RegexError> {
+   if program.len() > MAX_PROGRAM_LEN {
+      return Err((0, String::from("The regular expression is too large.")));
+   }
+
+   match node {
+      Node::Empty => {}
+      Node::Char(c) => program.push(Inst::Char(*c)),
+      Node::Any => program.push(Inst::Any),
+      Node::Class(class) => program.push(Inst::Class(class.clone())),
+      Node::LineStart => program.push(Inst::LineStart),
+      Node::LineEnd => program.push(Inst::LineEnd),
+      Node::WordBoundary(expected) => program.push(Inst::WordBoundary(*expected)),
+      Node::Group(inner, None) => compile_node(inner, program)?,
+      Node::Group(inner, Some(idx)) => {
+         program.push(Inst::Save(idx * 2));
+         compile_node(inner, program)?;
+         program.push(Inst::Save(idx * 2 + 1));
+      }
+      Node::Concat(nodes) => {
+         for node in nodes {
+            compile_node(node, program)?;
+         }
+      }
+      Node::Alternation(options) => {
+         // Each option but the last one is preceded by a split to the next option,
+         // and followed by a jump to the end of the alternation.
+         let mut end_jumps = vec![];
+
+         for (idx, option) in options.iter().enumerate() {
+            if idx == options.len() - 1 {
+               compile_node(option, program)?;
+               break;
+            }
+
+            let split = program.len();
+            program.push(Inst::Split(split + 1, 0));
+            compile_node(option, program)?;
+            end_jumps.push(program.len());
+            program.push(Inst::Jump(0));
+            program[split] = Inst::Split(split + 1, program.len());
+         }
+
+         let end = program.len();
+         for jump in end_jumps {
+            program[jump] = Inst::Jump(end);
+         }
+      }
+      Node::Repeat {
+         node: inner,
+         min,
+         max,
+         greedy,
+      } => {
+         let split = |body: usize, exit: usize| {
+            if *greedy {
+               Inst::Split(body, exit)
+            } else {
+               Inst::Split(exit, body)
+            }
+         };
+
+         for _ in 0..*min {
+            compile_node(inner, program)?;
+         }
+
+         match max {
+            None => {
+               let start = program.len();
+               program.push(Inst::Jump(0));
+               compile_node(inner, program)?;
+               program.push(Inst::Jump(start));
+               program[start] = split(start + 1, program.len());
+            }
+            Some(max) => {
+               // Each optional repetition skips the rest of the repetitions when it is not taken.
+               let mut exits = vec![];
+
+               for _ in *min..*max {
+                  exits.push(program.len());
+                  program.push(Inst::Jump(0));
+                  compile_node(inner, program)?;
+               }
+
+               let end = program.len();
+               for exit in exits {
+                  program[exit] = split(exit + 1, end);
+               }
+            }
+         }
+      }
+   }
+
+   Ok(())
+}
+
+/// Compares two characters, ignoring their case.
+fn chars_eq_ignore_case(a: char, b: char) -> bool {
+   a == b || a.to_lowercase().eq(b.to_lowercase())
+}
+
+/// Parses the source of a regular expression into a tree of nodes.
+struct PatternParser {
+   chars: Vec<char>,
+   pos: usize,
+   /// The number of groups that enclose the current position.
+   depth: usize,
+   group_names: Vec<Option<String>>,
+}
+
+impl PatternParser {
+   /// Gets the current character without consuming it.
+   fn peek(&self) -> Option<char> {
+      self.chars.get(self.pos).copied()
+   }
+
+   /// Consumes the current character if it matches the expected character.
+   fn eat(&mut self, expected: char) -> bool {
+      if self.peek() == Some(expected) {
+         self.pos += 1;
+         true
+      } else {
+         false
+      }
+   }
+
+   /// Parses a list of alternatives separated by `|`.
+   fn parse_alternation(&mut self) -> Result<Node, RegexError> {
+      let mut options = vec![self.parse_concat()?];
+
+      while self.eat('|') {
+         options.push(self.parse_concat()?);
+      }
+
+      Ok(if options.len() == 1 {
+         options.pop().unwrap()
+      } else {
+         Node::Alternation(options)
+      })
+   }
+
+   /// Parses a sequence of (possibly repeated) atoms.
+   fn parse_concat(&mut self) -> Result<Node, RegexError> {
+      let mut nodes = vec![];
+
+      while let Some(c) = self.peek() {
+         if c == '|' || c == ')' {
+            break;
+         }
+
+         let atom = self.parse_atom()?;
+         nodes.push(self.parse_repetition(atom)?);
+      }
+
+      Ok(match nodes.len() {
+         0 => Node::Empty,
+         1 => nodes.pop().unwrap(),
+         _ => Node::Concat(nodes),
+      })
+   }
+
+   /// Parses the quantifier (if any) after an atom.
+   fn parse_repetition(&mut self, atom: Node) -> Result<Node, RegexError> {
+      let start = self.pos;
+
+      let (min, max) = match self.peek() {
+         Some('*') => (0, None),
+         Some('+') => (1, None),
+         Some('?') => (0, Some(1)),
+         Some('{') => {
+            self.pos += 1;
+            let bounds = self.parse_bounds(start)?;
+            self.pos -= 1;
+            bounds
+         }
+         _ => return Ok(atom),
+      };
+      self.pos += 1;
+
+      if matches!(
+         atom,
+         Node::Empty | Node::LineStart | Node::LineEnd | Node::WordBoundary(_)
+      ) {
+         return Err((start, String::from("Nothing to repeat.")));
+      }
+
+      let greedy = !self.eat('?');
+
+      if matches!(self.peek(), Some('*') | Some('+') | Some('?') | Some('{')) {
+         return Err((self.pos, String::from("Repetitions cannot be repeated.")));
+      }
+
+      Ok(Node::Repeat {
+         node: Box::new(atom),
+         min,
+         max,
+         greedy,
+      })
+   }
+
+   /// Parses the bounds of a `{n}`, `{n,}`, or `{n,m}` repetition, after the `{`. On success,
+   /// the parser is left after the closing `}`.
+   fn parse_bounds(&mut self, start: usize) -> Result<(usize, Option<usize>), RegexError> {
+      let invalid = || (start, String::from("Invalid repetition bounds."));
+
+      let min = self.parse_number().ok_or_else(invalid)?;
+      let max = if self.eat(',') {
+         if self.peek() == Some('}') {
+            None
+         } else {
+            Some(self.parse_number().ok_or_else(invalid)?)
+         }
+      } else {
+         Some(min)
+      };
+
+      if !self.eat('}') || max.is_some_and(|max| max < min) {
+         return Err(invalid());
+      }
+
+      Ok((min, max))
+   }
+
+   /// Parses a decimal number.
+   fn parse_number(&mut self) -> Option<usize> {
+      let start = self.pos;
+      while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+         self.pos += 1;
+      }
+
+      self.chars[start..self.pos]
+         .iter()
+         .collect::<String>()
+         .parse()
+         .ok()
+   }
+
+   /// Parses a single atom: a character, a class, an anchor, or a group.
+   fn parse_atom(&mut self) -> Result<Node, RegexError> {
+      let start = self.pos;
+      let c = self.peek().unwrap();
+      self.pos += 1;
+
+      Ok(match c {
+         '.' => Node::Any,
+         '^' => Node::LineStart,
+         '$' => Node::LineEnd,
+         '[' => Node::Class(self.parse_class(start)?),
+         '(' => self.parse_group(start)?,
+         '*' | '+' | '?' | '{' => return Err((start, String::from("Nothing to repeat."))),
+         '\\' => self.parse_escape(false)?,
+         c => Node::Char(c),
+      })
+   }
+
+   /// Parses a group, after the `(`.
+   fn parse_group(&mut self, start: usize) -> Result<Node, RegexError> {
+      let capture = if self.eat('?') {
+         if self.eat(':') {
+            None
+         } else if self.eat('<') || (self.eat('P') && self.eat('<')) {
+            let name_start = self.pos;
+            while self.peek().is_some_and(is_word_char) {
+               self.pos += 1;
+            }
+
+            let name: String = self.chars[name_start..self.pos].iter().collect();
+            if name.is_empty() || !self.eat('>') {
+               return Err((name_start, String::from("Invalid capture group name.")));
+            }
+
+            self.group_names.push(Some(name));
+            Some(self.group_names.len() - 1)
+         } else {
+            return Err((start, String::from("Unsupported group syntax.")));
+         }
+      } else {
+         self.group_names.push(None);
+         Some(self.group_names.len() - 1)
+      };
+
+      if self.depth >= MAX_GROUP_DEPTH {
+         return Err((start, String::from("Too many nested groups.")));
+      }
+
+      self.depth += 1;
+      let inner = self.parse_alternation()?;
+      self.depth -= 1;
+
+      if !self.eat(')') {
+         return Err((start, String::from("Unclosed group.")));
+      }
+
+      Ok(Node::Group(Box::new(inner), capture))
+   }
+
+   /// Parses a character class, after the `[`.
+   fn parse_class(&mut self, start: usize) -> Result<CharClass, RegexError> {
+      let negated = self.eat('^');
+      let mut items = vec![];
+
+      // A `]` at the start of the class is a literal character.
+      if self.eat(']') {
+         items.push(ClassItem::Range(']', ']'));
+      }
+
+      loop {
+         let c = match self.peek() {
+            Some(']') => {
+               self.pos += 1;
+               break;
+            }
+            Some(c) => c,
+            None => return Err((start, String::from("Unclosed character class."))),
+         };
+
+         let item_start = self.pos;
+         self.pos += 1;
+
+         let from = if c == '\\' {
+            match self.parse_escape(true)? {
+               Node::Char(c) => c,
+               Node::Class(class) => {
+                  items.extend(class.items);
+                  continue;
+               }
+               _ => unreachable!("Escapes in classes should be characters or classes."),
+            }
+         } else {
+            c
+         };
+
+         // A `-` between two characters makes a range. Otherwise, it is a literal character.
+         if self.peek() == Some('-') && !matches!(self.chars.get(self.pos + 1), Some(']') | None) {
+            self.pos += 1;
+
+            let to = match self.peek() {
+               Some('\\') => {
+                  self.pos += 1;
+                  match self.parse_escape(true)? {
+                     Node::Char(c) => c,
+                     _ => return Err((item_start, String::from("Invalid range in character class."))),
+                  }
+               }
+               Some(c) => {
+                  self.pos += 1;
+                  c
+               }
+               None => return Err((start, String::from("Unclosed character class."))),
+            };
+
+            if to < from {
+               return Err((item_start, String::from("Invalid range in character class.")));
+            }
+
+            items.push(ClassItem::Range(from, to));
+         } else {
+            items.push(ClassItem::Range(from, from));
+         }
+      }
+
+      Ok(CharClass { negated, items })
+   }
+
+   /// Parses an escape sequence, after the `\`.
+   ///
+   /// # Parameters
+   /// - `in_class`: Whether the escape sequence is inside a character class, where
+   ///   anchors and word boundaries are not allowed.
+   fn parse_escape(&mut self, in_class: bool) -> Result<Node, RegexError> {
+      let start = self.pos - 1;
+      let c = match self.peek() {
+         Some(c) => c,
+         None => return Err((start, String::from("Incomplete escape sequence."))),
+      };
+      self.pos += 1;
+
+      let class = |item: ClassItem| {
+         Node::Class(CharClass {
+            negated: false,
+            items: vec![item],
+         })
+      };
+
+      Ok(match c {
+         'd' => class(ClassItem::Digit(false)),
+         'D' => class(ClassItem::Digit(true)),
+         'w' => class(ClassItem::Word(false)),
+         'W' => class(ClassItem::Word(true)),
+         's' => class(ClassItem::Space(false)),
+         'S' => class(ClassItem::Space(true)),
+         'b' if !in_class => Node::WordBoundary(true),
+         'B' if !in_class => Node::WordBoundary(false),
+         'n' => Node::Char('\n'),
+         't' => Node::Char('\t'),
+         'r' => Node::Char('\r'),
+         'f' => Node::Char('\x0C'),
+         'v' => Node::Char('\x0B'),
+         '0' => Node::Char('\0'),
+         'x' => {
+            let digits: String = self.chars.iter().skip(self.pos).take(2).collect();
+            match u32::from_str_radix(&digits, 16)
+               .ok()
+               .and_then(std::char::from_u32)
+            {
+               Some(c) if digits.len() == 2 => {
+                  self.pos += 2;
+                  Node::Char(c)
+               }
+               _ => return Err((start, String::from("Invalid hexadecimal escape sequence."))),
+            }
+         }
+         '1'..='9' => return Err((start, String::from("Backreferences are not supported."))),
+         c if c.is_alphanumeric() => return Err((start, format!("Unknown escape sequence '\\{}'.", c))),
+         c => Node::Char(c),
+      })
+   }
+}
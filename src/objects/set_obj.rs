@@ -0,0 +1,94 @@
+use crate::errors::ObjectOprErrType;
+use crate::objects::hash_key::HashKey;
+use crate::objects::Object;
+use hashbrown::HashMap;
+
+/// Represents the items of a Hinton set. Items are kept in insertion order, which is the order
+/// in which they are printed and iterated over. Items are compared by their `HashKey`, so
+/// only immutable objects can be stored in a set.
+#[derive(Clone, Default)]
+pub struct SetObject {
+   /// The items of the set, in insertion order.
+   items: Vec<Object>,
+   /// The position of each item in the list of items.
+   indices: HashMap<HashKey, usize>,
+}
+
+impl SetObject {
+   /// Creates a set with the given items, ignoring repeated items.
+   ///
+   /// # Returns
+   /// - `Result<SetObject, ObjectOprErrType>`: The set, or a `TypeError` if one of the
+   ///   items cannot be stored in a set.
+   pub fn from_items(items: impl IntoIterator<Item = Object>) -> Result<SetObject, ObjectOprErrType> {
+      let mut set = SetObject::default();
+
+      for item in items {
+         set.insert(item)?;
+      }
+
+      Ok(set)
+   }
+
+   /// Gets the number of items in the set.
+   pub fn len(&self) -> usize {
+      self.items.len()
+   }
+
+   /// Checks if the set has no items.
+   pub fn is_empty(&self) -> bool {
+      self.items.is_empty()
+   }
+
+   /// Checks if the set contains an item. Objects that cannot be stored
+   /// in a set are never contained in one.
+   pub fn contains(&self, item: &Object) -> bool {
+      HashKey::from_object(item).is_some_and(|key| self.indices.contains_key(&key))
+   }
+
+   /// Gets the item at a position in insertion order.
+   pub fn get(&self, idx: usize) -> Option<&Object> {
+      self.items.get(idx)
+   }
+
+   /// Adds an item to the set, if the set does not already contain it.
+   ///
+   /// # Returns
+   /// - `Result<bool, ObjectOprErrType>`: Whether the item was added, or a `TypeError`
+   ///   if the item cannot be stored in a set.
+   pub fn insert(&mut self, item: Object) -> Result<bool, ObjectOprErrType> {
+      let key = HashKey::try_from_object(&item)?;
+
+      if self.indices.contains_key(&key) {
+         return Ok(false);
+      }
+
+      self.indices.insert(key, self.items.len());
+      self.items.push(item);
+      Ok(true)
+   }
+
+   /// Removes an item from the set, preserving the order of the remaining items.
+   ///
+   /// # Returns
+   /// - `bool`: Whether the set contained the item.
+   pub fn remove(&mut self, item: &Object) -> bool {
+      let idx = match HashKey::from_object(item).and_then(|key| self.indices.remove(&key)) {
+         Some(idx) => idx,
+         None => return false,
+      };
+
+      self.items.remove(idx);
+      self
+         .indices
+         .values_mut()
+         .filter(|i| **i > idx)
+         .for_each(|i| *i -= 1);
+      true
+   }
+
+   /// Iterates over the items of the set in insertion order.
+   pub fn iter(&self) -> impl Iterator<Item = &Object> {
+      self.items.iter()
+   }
+}
@@ -0,0 +1,83 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Shutdown, TcpStream};
+
+/// Represents a TCP connection opened by a Hinton program with `Net.connect(...)`. Reads are
+/// buffered, so that lines can be read from the connection without losing the bytes after them.
+pub struct SocketObject {
+   /// The connection, or `None` once the socket has been closed.
+   stream: Option<BufReader<TcpStream>>,
+   /// The address of the other end of the connection (e.g., `127.0.0.1:8080`).
+   pub peer: String,
+}
+
+impl SocketObject {
+   /// Wraps an open TCP connection.
+   pub fn new(stream: TcpStream) -> Self {
+      let peer = stream.peer_addr().map(|a| a.to_string()).unwrap_or_default();
+
+      SocketObject {
+         stream: Some(BufReader::new(stream)),
+         peer,
+      }
+   }
+
+   /// Gets the buffered connection, or an error if the socket has been closed.
+   fn stream(&mut self) -> io::Result<&mut BufReader<TcpStream>> {
+      self
+         .stream
+         .as_mut()
+         .ok_or_else(|| io::Error::new(io::ErrorKind::NotConnected, "The socket is closed"))
+   }
+
+   /// Reads up to `max` bytes from the connection, waiting until at least one byte is available.
+   ///
+   /// # Returns
+   /// - `io::Result<Vec<u8>>`: The bytes read, which are empty once the other end has
+   ///   closed the connection.
+   pub fn read(&mut self, max: usize) -> io::Result<Vec<u8>> {
+      let mut buf = vec![0; max];
+      let count = self.stream()?.read(&mut buf)?;
+      buf.truncate(count);
+      Ok(buf)
+   }
+
+   /// Reads a line from the connection, without its line terminator.
+   ///
+   /// # Returns
+   /// - `io::Result<Option<String>>`: The line, or `None` once the other end has closed the connection.
+   pub fn read_line(&mut self) -> io::Result<Option<String>> {
+      let mut line = Vec::new();
+
+      if self.stream()?.read_until(b'\n', &mut line)? == 0 {
+         return Ok(None);
+      }
+
+      if line.ends_with(b"\n") {
+         line.pop();
+      }
+      if line.ends_with(b"\r") {
+         line.pop();
+      }
+
+      Ok(Some(String::from_utf8_lossy(&line).into_owned()))
+   }
+
+   /// Writes all the given bytes to the connection.
+   pub fn write(&mut self, bytes: &[u8]) -> io::Result<()> {
+      let stream = self.stream()?.get_mut();
+      stream.write_all(bytes)?;
+      stream.flush()
+   }
+
+   /// Closes the connection. Closing a socket that is already closed does nothing.
+   pub fn close(&mut self) {
+      if let Some(stream) = self.stream.take() {
+         let _ = stream.get_ref().shutdown(Shutdown::Both);
+      }
+   }
+
+   /// Checks if the socket has been closed.
+   pub fn is_closed(&self) -> bool {
+      self.stream.is_none()
+   }
+}
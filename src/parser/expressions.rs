@@ -9,7 +9,7 @@
 // Bitwise And:     <expr> & <expr>
 // Equality:        (<expr> == <expr>), (<expr> equals <expr>)
 // Comparison:      <expr> (<, <=, >, >=) <expr>
-// Range:           <expr> .. <expr>
+// Range:           <expr> .. <expr>, <expr> ..= <expr>
 // Bitwise Shift:   <expr> (<<, >>) <expr>
 // Term:            <expr> (+, -) <expr>
 // Factor:          <expr> (*, /, %, mod) <expr>
@@ -20,20 +20,38 @@
 use crate::core::ast::ASTNode::*;
 use crate::core::ast::*;
 use crate::core::ast::{ASTNode, ReassignmentType};
-use crate::core::tokens::Token;
 use crate::core::tokens::TokenType::*;
 use crate::core::tokens::TokenType::{LOGIC_NOT_EQ, MINUS};
+use crate::core::tokens::{Token, TokenType};
+use crate::lexer::{char_literal_value, string_literal_value, unescape_string};
+use crate::objects::bigint::BigInt;
 use crate::objects::Object;
 use crate::parser::Parser;
+use std::borrow::Cow;
 
-impl<'a> Parser {
+impl<'a> Parser<'a> {
    /// Parses an expression.
-   pub(super) fn parse_expression(&mut self) -> Option<ASTNode> {
+   pub(super) fn parse_expression(&mut self) -> Option<ASTNode<'a>> {
       self.parse_assignment()
    }
 
+   /// Parses an expression that is followed by a block (e.g., the condition of an `if`
+   /// statement), so a '{' after a call in the expression does not start a trailing closure.
+   pub(super) fn parse_condition(&mut self) -> Option<ASTNode<'a>> {
+      self.with_trailing_closures(false, |p| p.parse_expression())
+   }
+
+   /// Parses an expression with trailing closures turned on or off, restoring the previous
+   /// setting afterwards.
+   fn with_trailing_closures<T>(&mut self, allowed: bool, parse: impl FnOnce(&mut Self) -> T) -> T {
+      let prev = std::mem::replace(&mut self.trailing_closures, allowed);
+      let result = parse(self);
+      self.trailing_closures = prev;
+      result
+   }
+
    /// Parses an assignment expression.
-   fn parse_assignment(&mut self) -> Option<ASTNode> {
+   fn parse_assignment(&mut self) -> Option<ASTNode<'a>> {
       let expr = self.parse_ternary_conditional();
       let expr_tok = self.previous.clone();
 
@@ -42,6 +60,7 @@ impl<'a> Parser {
          || self.matches(&MINUS_EQ)
          || self.matches(&STAR_EQ)
          || self.matches(&SLASH_EQ)
+         || self.matches(&FLOOR_DIV_EQ)
          || self.matches(&EXPO_EQUALS)
          || self.matches(&MOD_EQ)
          || self.matches(&BIT_L_SHIFT_EQ)
@@ -58,6 +77,7 @@ impl<'a> Parser {
             MINUS_EQ => ReassignmentType::Minus,
             STAR_EQ => ReassignmentType::Mul,
             SLASH_EQ => ReassignmentType::Div,
+            FLOOR_DIV_EQ => ReassignmentType::FloorDiv,
             EXPO_EQUALS => ReassignmentType::Expo,
             MOD_EQ => ReassignmentType::Mod,
             BIT_L_SHIFT_EQ => ReassignmentType::ShiftL,
@@ -119,7 +139,7 @@ impl<'a> Parser {
    }
 
    /// Parses a ternary conditional expression.
-   fn parse_ternary_conditional(&mut self) -> Option<ASTNode> {
+   fn parse_ternary_conditional(&mut self) -> Option<ASTNode<'a>> {
       let mut expr = self.parse_nullish_coalescing();
 
       if self.matches(&QUESTION) {
@@ -148,14 +168,51 @@ impl<'a> Parser {
             branch_true: Box::new(branch_true),
             branch_false: Box::new(branch_false),
             false_branch_token: false_branch_opr,
+            is_if_expression: false,
          }));
       }
 
       expr
    }
 
+   /// Parses an `if` expression (e.g., `if x > 0 { 'positive' } else { 'negative' }`), whose
+   /// branches are single expressions. The expression is parsed as a ternary conditional, so
+   /// it is compiled with the same short-circuiting jumps, but it keeps the form it was written in.
+   fn parse_if_expression(&mut self) -> Option<ASTNode<'a>> {
+      let condition = self.parse_condition()?;
+
+      self.consume(
+         &L_CURLY,
+         "Expected '{' after the condition of the 'if' expression.",
+      );
+      let true_branch_token = self.previous.clone();
+      let branch_true = self.parse_expression()?;
+      self.consume(&R_CURLY, "Expected '}' after the value of the 'if' branch.");
+
+      self.consume(&ELSE_KW, "Expected an 'else' branch for the 'if' expression.");
+      let false_branch_token = self.previous.clone();
+
+      let branch_false = if self.matches(&IF_KW) {
+         self.parse_if_expression()?
+      } else {
+         self.consume(&L_CURLY, "Expected '{' after the 'else' keyword.");
+         let value = self.parse_expression()?;
+         self.consume(&R_CURLY, "Expected '}' after the value of the 'else' branch.");
+         value
+      };
+
+      Some(TernaryConditional(TernaryConditionalNode {
+         condition: Box::new(condition),
+         true_branch_token,
+         branch_true: Box::new(branch_true),
+         false_branch_token,
+         branch_false: Box::new(branch_false),
+         is_if_expression: true,
+      }))
+   }
+
    /// Parses an '??' (nullish coalescing) expression.
-   fn parse_nullish_coalescing(&mut self) -> Option<ASTNode> {
+   fn parse_nullish_coalescing(&mut self) -> Option<ASTNode<'a>> {
       let mut expr = self.parse_logic_or();
 
       while self.matches(&NULLISH) {
@@ -179,7 +236,7 @@ impl<'a> Parser {
    }
 
    /// Parses an 'OR' expression.
-   fn parse_logic_or(&mut self) -> Option<ASTNode> {
+   fn parse_logic_or(&mut self) -> Option<ASTNode<'a>> {
       let mut expr = self.parse_logic_and();
 
       while self.matches(&LOGIC_OR) {
@@ -203,7 +260,7 @@ impl<'a> Parser {
    }
 
    /// Parses an 'AND' expression.
-   fn parse_logic_and(&mut self) -> Option<ASTNode> {
+   fn parse_logic_and(&mut self) -> Option<ASTNode<'a>> {
       let mut expr = self.parse_bitwise_or();
 
       while self.matches(&LOGIC_AND) {
@@ -227,7 +284,7 @@ impl<'a> Parser {
    }
 
    /// Parses a 'BITWISE OR' expression.
-   fn parse_bitwise_or(&mut self) -> Option<ASTNode> {
+   fn parse_bitwise_or(&mut self) -> Option<ASTNode<'a>> {
       let mut expr = self.parse_bitwise_xor();
 
       while self.matches(&BIT_OR) {
@@ -251,7 +308,7 @@ impl<'a> Parser {
    }
 
    /// Parses a 'BITWISE XOR' expression.
-   fn parse_bitwise_xor(&mut self) -> Option<ASTNode> {
+   fn parse_bitwise_xor(&mut self) -> Option<ASTNode<'a>> {
       let mut expr = self.parse_bitwise_and();
 
       while self.matches(&BIT_XOR) {
@@ -275,7 +332,7 @@ impl<'a> Parser {
    }
 
    /// Parses a 'BITWISE AND' expression.
-   fn parse_bitwise_and(&mut self) -> Option<ASTNode> {
+   fn parse_bitwise_and(&mut self) -> Option<ASTNode<'a>> {
       let mut expr = self.parse_equality();
 
       while self.matches(&BIT_AND) {
@@ -299,7 +356,7 @@ impl<'a> Parser {
    }
 
    /// Parses an equality expression.
-   fn parse_equality(&mut self) -> Option<ASTNode> {
+   fn parse_equality(&mut self) -> Option<ASTNode<'a>> {
       let mut expr = self.parse_comparison();
 
       while self.matches(&LOGIC_EQ) || self.matches(&LOGIC_NOT_EQ) {
@@ -328,49 +385,55 @@ impl<'a> Parser {
       expr
    }
 
-   /// Parses a comparison expression.
-   fn parse_comparison(&mut self) -> Option<ASTNode> {
-      let mut expr = self.parse_range();
+   /// Parses a comparison expression. Consecutive ordering comparisons are chained
+   /// (e.g., `a < b < c` is `a < b and b < c`, with `b` evaluated once).
+   fn parse_comparison(&mut self) -> Option<ASTNode<'a>> {
+      let mut expr = self.parse_range()?;
+
+      // The operators and right-hand operands of the ordering comparisons after `expr`.
+      let mut chain: Vec<(Token, BinaryExprType, ASTNode)> = vec![];
+
+      loop {
+         let opr_type = match self.get_current_tok_type() {
+            LESS_THAN => BinaryExprType::LogicLessThan,
+            LESS_THAN_EQ => BinaryExprType::LogicLessThanEQ,
+            GREATER_THAN => BinaryExprType::LogicGreaterThan,
+            GREATER_THAN_EQ => BinaryExprType::LogicGreaterThanEQ,
+            LOGIC_IS => BinaryExprType::LogicIs,
+            IN_KW => BinaryExprType::LogicIn,
+            _ => break,
+         };
 
-      while self.matches(&LESS_THAN)
-         || self.matches(&LESS_THAN_EQ)
-         || self.matches(&GREATER_THAN)
-         || self.matches(&GREATER_THAN_EQ)
-      {
+         self.advance();
          let opr = self.previous.clone();
-
-         let opr_type = if let LESS_THAN = opr.token_type {
-            BinaryExprType::LogicLessThan
-         } else if let LESS_THAN_EQ = opr.token_type {
-            BinaryExprType::LogicLessThanEQ
-         } else if let GREATER_THAN = opr.token_type {
-            BinaryExprType::LogicGreaterThan
+         let right = self.parse_range()?; // Could not create rhs of expression
+
+         if let BinaryExprType::LogicIs | BinaryExprType::LogicIn = opr_type {
+            // The `is` and `in` operators are not chained, so they apply to the whole chain before them.
+            expr = Binary(BinaryExprNode {
+               left: Box::new(fold_comparison_chain(expr, std::mem::take(&mut chain))),
+               right: Box::new(right),
+               opr_token: opr,
+               opr_type,
+            });
          } else {
-            BinaryExprType::LogicGreaterThanEQ
-         };
-
-         expr = Some(Binary(BinaryExprNode {
-            left: match expr {
-               Some(e) => Box::new(e),
-               None => return None, // Could not create lhs of expression
-            },
-            right: match self.parse_range() {
-               Some(e) => Box::new(e),
-               None => return None, // Could not create rhs of expression
-            },
-            opr_token: opr,
-            opr_type,
-         }));
+            chain.push((opr, opr_type, right));
+         }
       }
 
-      expr
+      Some(fold_comparison_chain(expr, chain))
    }
 
    /// Parses a range expression.
-   fn parse_range(&mut self) -> Option<ASTNode> {
+   fn parse_range(&mut self) -> Option<ASTNode<'a>> {
       let mut expr = self.parse_bitwise_shift();
 
-      if self.matches(&RANGE_OPR) {
+      // A range operator followed by `]` or by another range operator
+      // is part of a slice with an open end (e.g., `arr[1..]`).
+      if (self.check(&RANGE_OPR) && !matches!(self.peek(), R_BRACKET | RANGE_OPR))
+         || self.check(&RANGE_INCLUSIVE_OPR)
+      {
+         self.advance();
          let opr = self.previous.clone();
 
          expr = Some(Binary(BinaryExprNode {
@@ -382,8 +445,11 @@ impl<'a> Parser {
                Some(e) => Box::new(e),
                None => return None, // Could not create rhs of expression
             },
+            opr_type: match opr.token_type {
+               RANGE_INCLUSIVE_OPR => BinaryExprType::RangeInclusive,
+               _ => BinaryExprType::Range,
+            },
             opr_token: opr,
-            opr_type: BinaryExprType::Range,
          }));
       }
 
@@ -391,7 +457,7 @@ impl<'a> Parser {
    }
 
    /// Parses a 'BITWISE SHIFT'.
-   fn parse_bitwise_shift(&mut self) -> Option<ASTNode> {
+   fn parse_bitwise_shift(&mut self) -> Option<ASTNode<'a>> {
       let mut expr = self.parse_term();
 
       while self.matches(&BIT_L_SHIFT) || self.matches(&BIT_R_SHIFT) {
@@ -421,7 +487,7 @@ impl<'a> Parser {
    }
 
    /// Parses a term expression.
-   fn parse_term(&mut self) -> Option<ASTNode> {
+   fn parse_term(&mut self) -> Option<ASTNode<'a>> {
       let mut expr = self.parse_factor();
 
       while self.matches(&PLUS) || self.matches(&MINUS) {
@@ -451,14 +517,17 @@ impl<'a> Parser {
    }
 
    /// Parses a factor expression.
-   fn parse_factor(&mut self) -> Option<ASTNode> {
+   fn parse_factor(&mut self) -> Option<ASTNode<'a>> {
       let mut expr = self.parse_expo();
 
-      while self.matches(&SLASH) || self.matches(&STAR) || self.matches(&MODULUS) {
+      while self.matches(&SLASH) || self.matches(&FLOOR_DIV) || self.matches(&STAR) || self.matches(&MODULUS)
+      {
          let opr = self.previous.clone();
 
          let opr_type = if let SLASH = opr.token_type {
             BinaryExprType::Division
+         } else if let FLOOR_DIV = opr.token_type {
+            BinaryExprType::FloorDivision
          } else if let STAR = opr.token_type {
             BinaryExprType::Multiplication
          } else {
@@ -483,7 +552,7 @@ impl<'a> Parser {
    }
 
    /// Parses an exponentiation expression.
-   fn parse_expo(&mut self) -> Option<ASTNode> {
+   fn parse_expo(&mut self) -> Option<ASTNode<'a>> {
       let mut expr = self.parse_unary();
 
       while self.matches(&EXPO) {
@@ -507,7 +576,39 @@ impl<'a> Parser {
    }
 
    /// Parses a unary expression.
-   fn parse_unary(&mut self) -> Option<ASTNode> {
+   fn parse_unary(&mut self) -> Option<ASTNode<'a>> {
+      // Before an expression, `!!` is a double logic negation (e.g., `!!value`).
+      if self.matches(&NON_NULL_ASSERT) {
+         let opr = self.previous.clone();
+         let pos = (opr.line_num, opr.column_start);
+         let operand = self.parse_expression()?;
+
+         let negation = Unary(UnaryExprNode {
+            operand: Box::new(operand),
+            pos: (pos.0, pos.1 + 1),
+            opr_type: UnaryExprType::LogicNeg,
+         });
+
+         return Some(Unary(UnaryExprNode {
+            operand: Box::new(negation),
+            pos,
+            opr_type: UnaryExprType::LogicNeg,
+         }));
+      }
+
+      // The operand of an `await` is a unary expression, so that `await f() + 1` adds
+      // one to the awaited value rather than awaiting the sum.
+      if self.matches(&AWAIT_KW) {
+         let opr = self.previous.clone();
+         let operand = self.parse_unary()?;
+
+         return Some(Unary(UnaryExprNode {
+            operand: Box::new(operand),
+            pos: (opr.line_num, opr.column_start),
+            opr_type: UnaryExprType::Await,
+         }));
+      }
+
       if self.matches(&LOGIC_NOT) || self.matches(&MINUS) || self.matches(&BIT_NOT) {
          let opr = self.previous.clone();
 
@@ -519,8 +620,17 @@ impl<'a> Parser {
             UnaryExprType::ArithmeticNeg
          };
 
+         // The logic negation applies to the entire expression after it (e.g., `not a == b`),
+         // while the arithmetic and bitwise negations only apply to their immediate operand,
+         // so that `-1..-3` and `-a == b` negate the numbers rather than the whole expression.
+         let operand = if let LOGIC_NOT = opr.token_type {
+            self.parse_expression()
+         } else {
+            self.parse_expo()
+         };
+
          Some(Unary(UnaryExprNode {
-            operand: match self.parse_expression() {
+            operand: match operand {
                Some(e) => Box::new(e),
                None => return None, // Could not create rhs of expression
             },
@@ -528,8 +638,12 @@ impl<'a> Parser {
             opr_type,
          }))
       } else {
+         let start = self.lexer.char_position(self.current.span.start);
          let mut expr = self.parse_primary();
 
+         // The first optional chaining operator of the chain being parsed.
+         let mut optional_chain: Option<Token> = None;
+
          loop {
             // Parse array indexing
             if self.matches(&L_BRACKET) {
@@ -537,33 +651,63 @@ impl<'a> Parser {
             } else if self.matches(&L_PAREN) {
                // Parse function call
                expr = self.parse_function_call(expr);
-            } else if self.matches(&DOT) {
+            } else if self.matches(&DOT) || self.matches(&OPTIONAL_CHAIN) {
                // Parse object getter
                let target = match expr {
                   Some(node) => Box::new(node),
                   None => return None,
                };
 
+               let is_optional = matches!(self.previous.token_type, OPTIONAL_CHAIN);
+               if is_optional && optional_chain.is_none() {
+                  optional_chain = Some(self.previous.clone());
+               }
+
                self.consume(&&IDENTIFIER, "Expected property name after the dot.");
                let getter = self.previous.clone();
 
-               expr = Some(ObjectGetter(ObjectGetExprNode { target, getter }));
+               expr = Some(ObjectGetter(ObjectGetExprNode {
+                  target,
+                  getter,
+                  is_optional,
+               }));
+            } else if self.trailing_closures
+               && self.check(&L_CURLY)
+               && matches!(expr, Some(FunctionCall(_) | ObjectGetter(_) | Identifier(_)))
+            {
+               self.advance();
+               expr = self.parse_trailing_closure(expr?);
+            } else if self.check(&NON_NULL_ASSERT) {
+               let source = self.lexer.source_text(start, self.previous.column_end);
+               self.advance();
+
+               // The assertion applies to the result of the optional chain before it.
+               let token = self.previous.clone();
+               let operand = wrap_optional_chain(expr?, optional_chain.take());
+
+               expr = Some(NonNullAssertion(NonNullAssertionNode {
+                  operand: Box::new(operand),
+                  token,
+                  source,
+               }));
             } else {
                break;
             }
          }
 
-         expr
+         Some(wrap_optional_chain(expr?, optional_chain))
       }
    }
 
    /// Parses a primary (literal) expression.
-   pub(super) fn parse_primary(&mut self) -> Option<ASTNode> {
+   pub(super) fn parse_primary(&mut self) -> Option<ASTNode<'a>> {
       self.advance();
       let literal_token = self.previous.clone();
 
       let literal_value = match self.get_previous_tok_type() {
          STRING => self.compile_string(),
+         CHAR_LITERAL => Object::Char(char_literal_value(&self.previous.lexeme).unwrap_or_default()),
+         INTERPOLATION_START => return self.parse_string_interpolation(),
          TRUE => Object::Bool(true),
          FALSE => Object::Bool(false),
          NULL => Object::Null,
@@ -572,6 +716,10 @@ impl<'a> Parser {
             Ok(x) => x,
             Err(_) => return None,
          },
+         BIG_INTEGER => match self.compile_big_integer() {
+            Ok(x) => x,
+            Err(_) => return None,
+         },
          FLOAT => match self.compile_float() {
             Ok(x) => x,
             Err(_) => return None,
@@ -598,7 +746,7 @@ impl<'a> Parser {
                   token: start_token,
                }))
             } else {
-               let expr = self.parse_expression();
+               let expr = self.with_trailing_closures(true, |p| p.parse_expression());
 
                // If there is a comma after the first expression, then this becomes a tuple.
                if self.matches(&COMMA) {
@@ -614,6 +762,9 @@ impl<'a> Parser {
          L_CURLY => {
             return self.parse_dictionary();
          }
+         IF_KW => {
+            return self.parse_if_expression();
+         }
          IDENTIFIER => {
             return Some(Identifier(IdentifierExprNode {
                token: self.previous.clone(),
@@ -624,6 +775,16 @@ impl<'a> Parser {
                token: self.previous.clone(),
             }))
          }
+         SUPER_KW => {
+            let token = self.previous.clone();
+            self.consume(&DOT, "Expected '.' after 'super'.");
+            self.consume(&IDENTIFIER, "Expected a superclass method name after 'super.'.");
+
+            return Some(SuperExpr(SuperExprNode {
+               token,
+               method: self.previous.clone(),
+            }));
+         }
          NEW_KW => {
             // For class instances, we parse a unary after the "new" keyword so that the instance can
             // be parsed and compiled as a regular function call. The only purpose of the "new" keyword
@@ -641,7 +802,16 @@ impl<'a> Parser {
                }
             };
          }
-         FN_LAMBDA_KW => {
+         BIT_OR | LOGIC_OR => {
+            return self.parse_lambda_shorthand();
+         }
+         ASYNC_KW | FN_LAMBDA_KW => {
+            let is_async = matches!(self.previous.token_type, ASYNC_KW);
+
+            if is_async {
+               self.consume(&FN_LAMBDA_KW, "Expected 'fn' after the 'async' keyword.");
+            }
+
             let fn_keyword = self.previous.clone();
 
             self.consume(&L_PAREN, "Expected '(' before lambda expression parameters.");
@@ -649,6 +819,7 @@ impl<'a> Parser {
                Some(p) => p,
                None => return None,
             };
+            let return_type = self.parse_return_type();
             self.consume(&L_CURLY, "Expected '{' for the function body.");
 
             let min_arity = params.0;
@@ -658,6 +829,9 @@ impl<'a> Parser {
                name: fn_keyword,
                params: params.1,
                arity: (min_arity, max_arity),
+               return_type,
+               doc: None,
+               is_async,
                body: match self.parse_block() {
                   Some(node) => match node {
                      BlockStmt(b) => b.body,
@@ -683,25 +857,53 @@ impl<'a> Parser {
    ///
    /// # Returns
    /// `Object`: The Hinton string object.
-   fn compile_string(&mut self) -> Object {
-      let lexeme = self.previous.lexeme.clone();
+   pub(super) fn compile_string(&mut self) -> Object {
+      Object::from(string_literal_value(&self.previous.lexeme))
+   }
+
+   /// Parses an interpolated string into a list of string segments and interpolated expressions.
+   ///
+   /// # Returns
+   /// `Option<ASTNode>`: The string interpolation node.
+   fn parse_string_interpolation(&mut self) -> Option<ASTNode<'a>> {
+      let token = self.previous.clone();
+      let mut parts = vec![];
 
-      // Remove outer quotes from the source string
-      let lexeme = &lexeme[1..(lexeme.len() - 1)];
+      loop {
+         let segment_token = self.previous.clone();
+         let is_end = matches!(segment_token.token_type, INTERPOLATION_END);
 
-      // Replace escaped characters with the actual representations
-      let lexeme = lexeme
-         .replace("\\n", "\n")
-         .replace("\\t", "\t")
-         .replace("\\r", "\r")
-         .replace("\\\\", "\\")
-         .replace("\\\"", "\"");
+         // Remove the opening quote or closing brace, and the closing quote or the `${`.
+         let lexeme = &segment_token.lexeme;
+         let segment = unescape_string(&lexeme[1..(lexeme.len() - if is_end { 1 } else { 2 })]);
 
-      // Emits the constant instruction
-      Object::from(lexeme)
+         if !segment.is_empty() {
+            parts.push(Literal(LiteralExprNode {
+               value: Object::from(segment),
+               token: segment_token,
+            }));
+         }
+
+         if is_end {
+            break;
+         }
+
+         parts.push(self.parse_expression()?);
+
+         if !self.matches(&INTERPOLATION_MID) && !self.matches(&INTERPOLATION_END) {
+            self.error_at_current("Expected '}' after the interpolated expression.");
+            return None;
+         }
+      }
+
+      Some(StringInterpolation(StringInterpolationNode {
+         parts: parts.into_boxed_slice(),
+         token,
+      }))
    }
 
-   /// Compiles an integer token to a Hinton Int.
+   /// Compiles an integer token to a Hinton Int. Integers that do not fit
+   /// in an Int are compiled to a BigInt.
    ///
    /// # Returns
    /// `Result<Object, ()>`: The Hinton number object.
@@ -714,7 +916,22 @@ impl<'a> Parser {
 
       match num {
          Ok(x) => Ok(Object::Int(x)),
-         Err(_) => {
+         Err(_) => self.compile_big_integer(),
+      }
+   }
+
+   /// Compiles a BigInt token (or an integer token too large for an Int) to a Hinton BigInt.
+   ///
+   /// # Returns
+   /// `Result<Object, ()>`: The Hinton BigInt object.
+   fn compile_big_integer(&mut self) -> Result<Object, ()> {
+      let lexeme = self.previous.lexeme.replace('_', "");
+      // Removes the `n` suffix from the lexeme
+      let digits = lexeme.strip_suffix('n').unwrap_or(&lexeme);
+
+      match BigInt::parse(digits) {
+         Some(x) => Ok(Object::from(x)),
+         None => {
             // The lexeme could not be converted to an i64.
             self.error_at_previous("Unexpected token.");
             Err(())
@@ -766,7 +983,7 @@ impl<'a> Parser {
    }
 
    /// Parses an array expression.
-   fn construct_array(&mut self) -> Option<ASTNode> {
+   fn construct_array(&mut self) -> Option<ASTNode<'a>> {
       let start_token = self.previous.clone();
       let mut values: Vec<ASTNode> = vec![];
 
@@ -777,6 +994,12 @@ impl<'a> Parser {
                None => return None,
             });
 
+            // An array with a single value followed by a `for` clause is a comprehension.
+            if values.len() == 1 && self.check(&FOR_KW) {
+               let value = Box::new(values.pop().unwrap());
+               return self.parse_comprehension(start_token, None, value, &R_BRACKET);
+            }
+
             if self.matches(&COMMA) {
                continue;
             }
@@ -794,7 +1017,7 @@ impl<'a> Parser {
    }
 
    /// Parses a tuple literal expression.
-   fn parse_tuple(&mut self, start_token: Token, first: Option<ASTNode>) -> Option<ASTNode> {
+   fn parse_tuple(&mut self, start_token: Token<'a>, first: Option<ASTNode<'a>>) -> Option<ASTNode<'a>> {
       let first = match first {
          Some(node) => node,
          None => return None, // The first expression is invalid.
@@ -811,6 +1034,12 @@ impl<'a> Parser {
             });
 
             if self.matches(&COMMA) {
+               // If there is a closing parenthesis after the comma, we assume it
+               // is the end of the tuple.
+               if self.matches(&R_PARENTHESIS) {
+                  break;
+               }
+
                continue;
             }
 
@@ -826,38 +1055,38 @@ impl<'a> Parser {
    }
 
    /// Parses a dictionary literal expression.
-   fn parse_dictionary(&mut self) -> Option<ASTNode> {
+   fn parse_dictionary(&mut self) -> Option<ASTNode<'a>> {
       let token = self.previous.clone();
       let mut keys: Vec<Token> = vec![];
       let mut values: Vec<ASTNode> = vec![];
 
       if !self.matches(&R_CURLY) {
          loop {
-            // Parses the key
-            match self.parse_primary() {
-               Some(Identifier(id)) => keys.push(id.token),
-               Some(Literal(lit)) => match lit.value {
-                  Object::String(_) => keys.push(lit.token),
-                  _ => {
-                     self.error_at_previous("Expected an identifier, or a string, for dictionary key.");
-                     return None;
-                  }
-               },
-               _ => {
-                  self.error_at_previous("Expected an identifier, or a string, for dictionary key.");
-                  return None;
-               }
-            }
+            // Parses the key, which is only evaluated in a dictionary comprehension.
+            let key = self.parse_expression()?;
 
             // Consumes the colon
             self.consume(&COLON, "Expected ':' after dictionary key.");
 
             // Consumes the value
-            match self.parse_expression() {
-               Some(node) => values.push(node),
-               None => return None,
+            let value = self.parse_expression()?;
+
+            // A dictionary with a single entry followed by a `for` clause is a comprehension.
+            if keys.is_empty() && self.check(&FOR_KW) {
+               return self.parse_comprehension(token, Some(Box::new(key)), Box::new(value), &R_CURLY);
+            }
+
+            match key {
+               Identifier(id) => keys.push(id.token),
+               Literal(lit) if matches!(lit.value, Object::String(_)) => keys.push(lit.token),
+               _ => {
+                  self.error_at_previous("Expected an identifier, or a string, for dictionary key.");
+                  return None;
+               }
             }
 
+            values.push(value);
+
             // If matches a comma, consume next
             if self.matches(&COMMA) {
                // If there is a closing curly brace after the comma, we assume it
@@ -882,8 +1111,75 @@ impl<'a> Parser {
       }))
    }
 
+   /// Parses the `for` clauses of an array or dictionary comprehension, after its value.
+   ///
+   /// # Parameters
+   /// - `token`: The opening token of the comprehension.
+   /// - `key`: The key expression of a dictionary comprehension.
+   /// - `value`: The value expression of the comprehension.
+   /// - `closing`: The type of the token that closes the comprehension.
+   fn parse_comprehension(
+      &mut self,
+      token: Token<'a>,
+      key: Option<Box<ASTNode<'a>>>,
+      value: Box<ASTNode<'a>>,
+      closing: &TokenType,
+   ) -> Option<ASTNode<'a>> {
+      let mut clauses = vec![];
+
+      while self.matches(&FOR_KW) {
+         let clause_token = self.previous.clone();
+
+         // The `var` keyword is optional for the variables of a comprehension.
+         self.matches(&VAR_KW);
+
+         self.consume(&IDENTIFIER, "Expected an identifier name after 'for'.");
+         let id = IdentifierExprNode {
+            token: self.previous.clone(),
+         };
+
+         let value_id = if self.matches(&COMMA) {
+            self.consume(&IDENTIFIER, "Expected an identifier name after ','.");
+
+            Some(IdentifierExprNode {
+               token: self.previous.clone(),
+            })
+         } else {
+            None
+         };
+
+         self.consume(&IN_KW, "Expected the 'in' keyword after the identifier.");
+         let iterator = Box::new(self.parse_expression()?);
+
+         let condition = if self.matches(&IF_KW) {
+            Some(Box::new(self.parse_expression()?))
+         } else {
+            None
+         };
+
+         clauses.push(ComprehensionClause {
+            token: clause_token,
+            id,
+            value_id,
+            iterator,
+            condition,
+         });
+      }
+
+      let bracket = if let R_BRACKET = closing { ']' } else { '}' };
+      let message = format!("Expected matching '{}' for the comprehension.", bracket);
+      self.consume(closing, &message);
+
+      Some(Comprehension(ComprehensionExprNode {
+         token,
+         key,
+         value,
+         clauses: clauses.into_boxed_slice(),
+      }))
+   }
+
    /// Parses an array indexing expression.
-   fn parse_subscripting(&mut self, expr: Option<ASTNode>) -> Option<ASTNode> {
+   fn parse_subscripting(&mut self, expr: Option<ASTNode<'a>>) -> Option<ASTNode<'a>> {
       let expr = match expr {
          Some(e) => e,
          None => return None,
@@ -891,21 +1187,172 @@ impl<'a> Parser {
 
       let pos = (self.previous.line_num, self.previous.column_start);
 
-      let expr = Some(Subscript(SubscriptExprNode {
+      let index = if self.check(&RANGE_OPR) {
+         None
+      } else {
+         Some(self.parse_expression()?)
+      };
+
+      // Slices are indexed with a range, whose bounds are optional (e.g., `arr[..-1]`).
+      let (start, end) = match index {
+         Some(Binary(b)) if matches!(b.opr_type, BinaryExprType::Range) => (Some(b.left), Some(b.right)),
+         Some(index) if !self.check(&RANGE_OPR) => {
+            self.consume(&R_BRACKET, "Expected matching ']' for array indexing expression.");
+
+            return Some(Subscript(SubscriptExprNode {
+               target: Box::new(expr),
+               index: Box::new(index),
+               pos,
+            }));
+         }
+         start => {
+            self.consume(&RANGE_OPR, "Expected '..' in slice expression.");
+
+            let end = if self.check(&R_BRACKET) || self.check(&RANGE_OPR) {
+               None
+            } else {
+               Some(Box::new(self.parse_bitwise_shift()?))
+            };
+
+            (start.map(Box::new), end)
+         }
+      };
+
+      let step = if self.matches(&RANGE_OPR) {
+         Some(Box::new(self.parse_bitwise_shift()?))
+      } else {
+         None
+      };
+
+      self.consume(&R_BRACKET, "Expected matching ']' for slice expression.");
+
+      Some(Slice(SliceExprNode {
          target: Box::new(expr),
-         index: match self.parse_expression() {
-            Some(e) => Box::new(e),
-            None => return None,
-         },
+         start,
+         end,
+         step,
          pos,
-      }));
+      }))
+   }
 
-      self.consume(&R_BRACKET, "Expected matching ']' for array indexing expression.");
-      expr
+   /// Parses the shorthand form of a lambda, whose body is a single expression that is returned
+   /// (e.g., `|x| x * 2`, or `|| 0` without parameters).
+   fn parse_lambda_shorthand(&mut self) -> Option<ASTNode<'a>> {
+      let bar = self.previous.clone();
+      let mut params: Vec<Parameter> = vec![];
+
+      if let BIT_OR = bar.token_type {
+         while !self.matches(&BIT_OR) {
+            if !params.is_empty() {
+               self.consume(&COMMA, "Expected ',' or '|' after the lambda parameter.");
+            }
+
+            self.consume(&IDENTIFIER, "Expected a parameter name.");
+            let name = self.previous.clone();
+
+            if params.iter().any(|p| p.name.lexeme == name.lexeme) {
+               self.error_at_previous("Duplicate parameter name.");
+               return None;
+            }
+
+            params.push(Parameter {
+               name,
+               is_optional: false,
+               is_variadic: false,
+               default: None,
+               annotation: None,
+            });
+         }
+      }
+
+      let value = self.parse_expression()?;
+      let arity = params.len() as u8;
+
+      Some(Lambda(FunctionDeclNode {
+         name: bar.clone(),
+         params: params.into_boxed_slice(),
+         arity: (arity, arity),
+         return_type: None,
+         doc: None,
+         is_async: false,
+         body: vec![ReturnStmt(ReturnStmtNode {
+            token: bar,
+            value: Some(Box::new(value)),
+         })]
+         .into_boxed_slice(),
+      }))
+   }
+
+   /// Parses a trailing closure (e.g., the `{ print(it); }` in `list.each { print(it); }`), which
+   /// is passed as the last argument of the call before it. The closure takes one optional
+   /// parameter named `it`, and returns the value of its last expression statement.
+   fn parse_trailing_closure(&mut self, callee: ASTNode<'a>) -> Option<ASTNode<'a>> {
+      let brace = self.previous.clone();
+
+      self.closure_depths.push(self.brace_depth);
+      let body = self.with_trailing_closures(true, |p| p.parse_block());
+      self.closure_depths.pop();
+
+      let closure = Lambda(FunctionDeclNode {
+         name: brace.clone(),
+         params: vec![Parameter {
+            name: Token {
+               token_type: IDENTIFIER,
+               lexeme: Cow::Borrowed("it"),
+               ..brace.clone()
+            },
+            is_optional: true,
+            is_variadic: false,
+            default: None,
+            annotation: None,
+         }]
+         .into_boxed_slice(),
+         arity: (0, 1),
+         return_type: None,
+         doc: None,
+         is_async: false,
+         body: match body? {
+            BlockStmt(b) => b.body,
+            _ => unreachable!("Should have parsed a block statement."),
+         },
+      });
+
+      let argument = Argument {
+         name: None,
+         is_named: false,
+         is_spread: false,
+         value: Box::new(closure),
+      };
+
+      match callee {
+         FunctionCall(call) => {
+            if call.args.iter().any(|a| a.is_named || a.is_spread) {
+               self.error_at_token(
+                  &brace,
+                  "Cannot pass a trailing closure to a call with named or spread arguments.",
+               );
+               return None;
+            }
+
+            let mut args = call.args.into_vec();
+            args.push(argument);
+
+            Some(FunctionCall(FunctionCallExprNode {
+               target: call.target,
+               args: args.into_boxed_slice(),
+               pos: call.pos,
+            }))
+         }
+         _ => Some(FunctionCall(FunctionCallExprNode {
+            target: Box::new(callee),
+            args: vec![argument].into_boxed_slice(),
+            pos: (brace.line_num, brace.column_start),
+         })),
+      }
    }
 
    /// Parses a function call expression.
-   fn parse_function_call(&mut self, name: Option<ASTNode>) -> Option<ASTNode> {
+   fn parse_function_call(&mut self, name: Option<ASTNode<'a>>) -> Option<ASTNode<'a>> {
       let name = match name {
          Some(e) => e,
          None => return None,
@@ -921,13 +1368,20 @@ impl<'a> Parser {
                return None;
             }
 
-            match self.parse_argument() {
+            match self.with_trailing_closures(true, |p| p.parse_argument()) {
                Some(a) => {
                   if !args.is_empty() && !a.is_named && args.last().unwrap().is_named {
                      self.error_at_previous("Named arguments must be declared after all unnamed arguments.");
                      return None;
                   }
 
+                  if (a.is_named && args.iter().any(|x| x.is_spread))
+                     || (a.is_spread && args.iter().any(|x| x.is_named))
+                  {
+                     self.error_at_previous("Cannot use named arguments together with spread arguments.");
+                     return None;
+                  }
+
                   args.push(a);
                }
                None => return None, // Could not parse the argument
@@ -949,20 +1403,42 @@ impl<'a> Parser {
       }))
    }
 
-   /// Parses a function argument expression.
-   fn parse_argument(&mut self) -> Option<Argument> {
+   /// Parses a function argument expression. Named arguments are written as `name: value`.
+   fn parse_argument(&mut self) -> Option<Argument<'a>> {
+      // Spread arguments (e.g., `...list`) pass the items of a collection as separate arguments.
+      if self.matches(&ELLIPSIS) {
+         return Some(Argument {
+            name: None,
+            is_named: false,
+            is_spread: true,
+            value: match self.parse_expression() {
+               Some(x) => Box::new(x),
+               None => return None, // Could not parse the spread expression
+            },
+         });
+      }
+
       let expr = match self.parse_expression() {
          Some(e) => e,
          None => return None, // could not parse argument expression
       };
 
-      if self.matches(&COLON_EQUALS) {
+      if self.matches(&COLON) || self.matches(&COLON_EQUALS) {
+         let name = match expr {
+            Identifier(id) => id.token,
+            _ => {
+               self.error_at_previous("Expected an identifier for the name of the argument.");
+               return None;
+            }
+         };
+
          return Some(Argument {
-            name: Some(expr),
+            name: Some(name),
             is_named: true,
+            is_spread: false,
             value: match self.parse_expression() {
                Some(x) => Box::new(x),
-               None => return None, // Could not compile default value for parameter
+               None => return None, // Could not compile the value of the named argument
             },
          });
       }
@@ -970,7 +1446,59 @@ impl<'a> Parser {
       Some(Argument {
          name: None,
          is_named: false,
+         is_spread: false,
          value: Box::new(expr),
       })
    }
 }
+
+/// Builds the expression of a chain of ordering comparisons, which is a binary expression
+/// if the chain only has one comparison.
+fn fold_comparison_chain<'a>(
+   first: ASTNode<'a>,
+   mut chain: Vec<(Token<'a>, BinaryExprType, ASTNode<'a>)>,
+) -> ASTNode<'a> {
+   match chain.len() {
+      0 => first,
+      1 => {
+         let (opr_token, opr_type, right) = chain.remove(0);
+
+         Binary(BinaryExprNode {
+            left: Box::new(first),
+            right: Box::new(right),
+            opr_token,
+            opr_type,
+         })
+      }
+      _ => {
+         let mut operands = vec![first];
+         let mut operators = vec![];
+
+         for (token, opr_type, right) in chain {
+            operators.push((token, opr_type));
+            operands.push(right);
+         }
+
+         ComparisonChain(ComparisonChainNode {
+            operands: operands.into_boxed_slice(),
+            operators: operators.into_boxed_slice(),
+         })
+      }
+   }
+}
+
+/// Wraps a chain of property accesses, calls, and subscripts in an optional chain node, if the
+/// chain has an optional property access.
+///
+/// # Parameters
+/// - `chain`: The chain of property accesses, calls, and subscripts.
+/// - `token`: The first optional chaining operator (`?.`) in the chain, if any.
+fn wrap_optional_chain<'a>(chain: ASTNode<'a>, token: Option<Token<'a>>) -> ASTNode<'a> {
+   match token {
+      Some(token) => OptionalChain(OptionalChainNode {
+         chain: Box::new(chain),
+         token,
+      }),
+      None => chain,
+   }
+}
@@ -1,8 +1,9 @@
 use crate::core::ast::{ASTNode, ModuleNode};
 use crate::core::tokens::TokenType::*;
-use crate::core::tokens::{Token, TokenType};
-use crate::errors::ErrorReport;
+use crate::core::tokens::{Span, Token, TokenType};
+use crate::errors::{CompilerErrorType, ErrorReport};
 use crate::lexer::Lexer;
+use std::borrow::Cow;
 
 // Submodules
 mod expressions;
@@ -10,20 +11,38 @@ mod statements;
 
 /// Represents Hinton's parser, which converts source text into
 /// an Abstract Syntax Tree representation of the program.
-pub struct Parser {
+pub struct Parser<'a> {
    /// The lexer used in this parser.
-   lexer: Lexer,
+   lexer: Lexer<'a>,
    /// The previously consumed token.
-   previous: Token,
+   previous: Token<'a>,
    /// The current token (just consumed).
-   current: Token,
+   current: Token<'a>,
+   /// The doc comment written right before the current token, if there is one.
+   current_doc: Option<String>,
+   /// The token after the current token, along with the doc comment written before it,
+   /// if it has already been scanned by `peek()`.
+   peeked: Option<(Token<'a>, Option<String>)>,
    /// Whether the parser is in error-recovery mode or not.
    is_in_panic: bool,
+   /// The number of '{' consumed by the parser whose matching '}' has not been consumed yet.
+   brace_depth: usize,
+   /// The brace depths of the bodies (e.g., blocks and class bodies) currently being parsed.
+   /// After an error, the parser resumes with the next statement of the innermost body.
+   body_depths: Vec<usize>,
+   /// The brace depths of the bodies of the trailing closures currently being parsed.
+   closure_depths: Vec<usize>,
+   /// Whether a '{' after a call is parsed as a trailing closure. This is turned off while
+   /// parsing an expression that is followed by a block (e.g., the condition of a `while` loop).
+   trailing_closures: bool,
+   /// The labels of the loops currently being parsed, which tell a labeled `break` statement
+   /// (e.g., `break outer;`) apart from a `break` statement with a value (e.g., `break x;`).
+   loop_labels: Vec<Cow<'a, str>>,
    /// A list of reported errors generated while parsing.
    errors: Vec<ErrorReport>,
 }
 
-impl Parser {
+impl<'a> Parser<'a> {
    /// Parses a string of source test into a Hinton AST.
    ///
    /// # Parameters
@@ -32,25 +51,52 @@ impl Parser {
    /// # Returns
    /// - `Ok(ASTNode)`: The generated abstract syntax tree.
    /// - `Err(Vec<ErrorReport>)`: A list of parsing errors.
-   pub fn parse(src: &str) -> Result<ASTNode, Vec<ErrorReport>> {
+   pub fn parse(src: &'a str) -> Result<ASTNode<'a>, Vec<ErrorReport>> {
+      match Parser::parse_with_recovery(src) {
+         (ast, errors) if errors.is_empty() => Ok(ast),
+         (_, errors) => Err(errors),
+      }
+   }
+
+   /// Parses a string of source text into a Hinton AST, even if it has syntax errors. The
+   /// statements with errors are left out of the AST, and the parser resumes after each of them.
+   ///
+   /// # Parameters
+   /// - `src`: The source string for the program.
+   ///
+   /// # Returns
+   /// - `(ASTNode, Vec<ErrorReport>)`: The AST of the statements that could be parsed, and the
+   ///   list of parsing errors.
+   pub fn parse_with_recovery(src: &'a str) -> (ASTNode<'a>, Vec<ErrorReport>) {
       // Initialize the compiler
       let mut parser = Parser {
-         lexer: Lexer::lex(src),
+         lexer: Lexer::new(src).with_doc_comments(),
          previous: Token {
             line_num: 0,
             column_start: 0,
             column_end: 0,
+            span_end: (0, 0),
+            span: Span::default(),
             token_type: __INIT_PARSER__,
-            lexeme: "".to_string(),
+            lexeme: Cow::Borrowed(""),
          },
          current: Token {
             line_num: 0,
             column_start: 0,
             column_end: 0,
+            span_end: (0, 0),
+            span: Span::default(),
             token_type: __INIT_PARSER__,
-            lexeme: "".to_string(),
+            lexeme: Cow::Borrowed(""),
          },
+         current_doc: None,
+         peeked: None,
          is_in_panic: false,
+         brace_depth: 0,
+         body_depths: vec![],
+         closure_depths: vec![],
+         trailing_closures: true,
+         loop_labels: vec![],
          errors: vec![],
       };
 
@@ -64,13 +110,11 @@ impl Parser {
          }
       }
 
-      if parser.errors.is_empty() {
-         Ok(ASTNode::Module(ModuleNode {
-            body: program_body.into_boxed_slice(),
-         }))
-      } else {
-         Err(parser.errors)
-      }
+      let ast = ASTNode::Module(ModuleNode {
+         body: program_body.into_boxed_slice(),
+      });
+
+      (ast, parser.errors)
    }
 
    /// Checks that the current token matches the tokenType provided.
@@ -105,18 +149,59 @@ impl Parser {
 
    /// Advances the parser to the next token.
    fn advance(&mut self) {
+      match self.current.token_type {
+         L_CURLY => self.brace_depth += 1,
+         R_CURLY => self.brace_depth = self.brace_depth.saturating_sub(1),
+         _ => {}
+      }
+
       self.previous = self.current.clone();
 
       loop {
-         self.current = self.lexer.next_token();
+         let (token, doc) = match self.peeked.take() {
+            Some(peeked) => peeked,
+            None => self.scan_token(),
+         };
+
+         self.current = token;
+         self.current_doc = doc;
 
          match &self.current.token_type {
-            ERROR => self.error_at_current("Unexpected token."),
+            // Error tokens carry the scanner's error message as their lexeme.
+            ERROR => {
+               let message = self.current.lexeme.clone();
+               self.error_at_current(&message)
+            }
             _ => break,
          }
       }
    }
 
+   /// Gets the type of the token after the current token, without consuming any tokens.
+   fn peek(&mut self) -> &TokenType {
+      if self.peeked.is_none() {
+         self.peeked = Some(self.scan_token());
+      }
+
+      &self.peeked.as_ref().unwrap().0.token_type
+   }
+
+   /// Scans the next token that is not a doc comment, along with the text of the doc comments
+   /// written right before it (joined by new lines), if there are any.
+   fn scan_token(&mut self) -> (Token<'a>, Option<String>) {
+      let mut doc_lines = vec![];
+
+      loop {
+         let token = self.lexer.next_token();
+
+         match token.token_type {
+            DOC_COMMENT => doc_lines.push(token.lexeme),
+            _ if doc_lines.is_empty() => return (token, None),
+            _ => return (token, Some(doc_lines.join("\n"))),
+         }
+      }
+   }
+
    /// Consumes the current token only if it is of a given type. If the token does not match the
    /// type, emits a compiler error.
    ///
@@ -131,7 +216,14 @@ impl Parser {
       }
 
       if let SEMICOLON = tok_type {
+         let was_in_panic = self.is_in_panic;
          self.error_at_previous(message);
+
+         // A ';' missing at the end of a line is reported as if it were there, so that the
+         // statement in the next line is parsed normally instead of being skipped.
+         if !was_in_panic && self.current.line_num > self.previous.line_num {
+            self.is_in_panic = false;
+         }
       } else {
          self.error_at_current(message);
       }
@@ -174,7 +266,7 @@ impl Parser {
    /// # Parameters
    /// - `tok`: The token that caused the error.
    /// - `message`: The error message to display.
-   fn error_at_token(&mut self, tok: &Token, message: &str) {
+   fn error_at_token(&mut self, tok: &Token<'a>, message: &str) {
       if self.is_in_panic {
          return;
       }
@@ -182,40 +274,66 @@ impl Parser {
 
       // Construct the error message.
       let msg = format!(
-         "\x1b[31;1mSyntaxError\x1b[0m\x1b[1m at [{}:{}]: {}\x1b[0m",
-         tok.line_num, tok.column_start, message
+         "\x1b[31;1m{}[{}]\x1b[0m\x1b[1m at [{}:{}]: {}\x1b[0m",
+         CompilerErrorType::Syntax.name(),
+         CompilerErrorType::Syntax.code(),
+         tok.line_num,
+         tok.column_start,
+         message
       );
 
       // Push the error to the list
-      self.errors.push(ErrorReport {
-         line: tok.line_num,
-         column: tok.column_start,
-         lexeme_len: tok.lexeme.len(),
-         message: msg,
-      });
+      self.errors.push(ErrorReport::at_token(tok, msg));
+   }
+
+   /// Marks the start of a body (e.g., a block or a class body), whose opening '{' has just been
+   /// consumed. Until the body ends, the parser synchronizes to the statements of this body.
+   fn start_body(&mut self) {
+      self.body_depths.push(self.brace_depth);
+   }
+
+   /// Marks the end of the innermost body, before its closing '}' is consumed.
+   fn end_body(&mut self) {
+      self.body_depths.pop();
    }
 
    /// Synchronizes the compiler when it has found an error.
    /// This method helps minimize the number of cascading errors the compiler emits
    /// when it finds a parsing error. Once it reaches a synchronization point – like
-   /// a keyword for a statement – it stops emitting errors.
+   /// a keyword for a statement, the end of a statement, or the '}' that closes the
+   /// current body – it stops emitting errors. The tokens between a pair of braces
+   /// (e.g., a dictionary literal, or a nested block) are skipped as a whole.
    fn synchronize(&mut self) {
       self.is_in_panic = false;
 
-      while !self.get_current_tok_type().type_match(&EOF) {
-         if let SEMICOLON = self.get_previous_tok_type() {
-            return;
-         }
+      let body_depth = self.body_depths.last().copied().unwrap_or(0);
+      let mut has_advanced = false;
 
-         match self.get_current_tok_type() {
-            CLASS_KW | FUNC_KW | VAR_KW | FOR_KW | IF_KW | WHILE_KW | RETURN_KW => {
-               return;
+      while !self.get_current_tok_type().type_match(&EOF) {
+         if self.brace_depth == body_depth {
+            match self.get_previous_tok_type() {
+               SEMICOLON => return,
+               // A skipped pair of braces that ends a line also ends the statement.
+               R_CURLY if has_advanced && self.current.line_num > self.previous.line_num => return,
+               _ => {}
             }
 
-            _ => {}
+            match self.get_current_tok_type() {
+               CLASS_KW | INTERFACE_KW | ENUM_KW | FUNC_KW | VAR_KW | CONST_KW | FOR_KW | IF_KW
+               | WHILE_KW | DO_KW | LOOP_KW | RETURN_KW | TRY_KW | IMPORT_KW | EXPORT_KW | MATCH_KW
+               | DEFER_KW | ASSERT_KW | ASYNC_KW => {
+                  return;
+               }
+
+               // The '}' that closes the current body is consumed by the body's parser.
+               R_CURLY if !self.body_depths.is_empty() => return,
+
+               _ => {}
+            }
          }
 
          self.advance();
+         has_advanced = true;
       }
    }
 }
@@ -6,17 +6,31 @@ use crate::core::tokens::{Token, TokenType};
 use crate::objects::Object;
 use crate::parser::Parser;
 
-impl Parser {
+impl<'a> Parser<'a> {
    /// Parses a declaration.
-   pub(super) fn parse_declaration(&mut self) -> Option<ASTNode> {
+   pub(super) fn parse_declaration(&mut self) -> Option<ASTNode<'a>> {
+      let doc = self.current_doc.take();
+
       let decl = if self.matches(&VAR_KW) {
-         self.parse_var_declaration().map(VariableDecl)
+         self.parse_var_declaration(false).map(VariableDecl)
       } else if self.matches(&CONST_KW) {
          self.parse_const_declaration().map(ConstantDecl)
       } else if self.matches(&FUNC_KW) {
-         self.parse_func_declaration().map(FunctionDecl)
+         self.parse_func_declaration(doc, false).map(FunctionDecl)
+      } else if self.check(&ASYNC_KW) && matches!(self.peek(), FUNC_KW) {
+         self.advance();
+         self.advance();
+         self.parse_func_declaration(doc, true).map(FunctionDecl)
       } else if self.matches(&CLASS_KW) {
-         self.parse_class_declaration()
+         self.parse_class_declaration(doc)
+      } else if self.matches(&INTERFACE_KW) {
+         self.parse_interface_declaration()
+      } else if self.matches(&ENUM_KW) {
+         self.parse_enum_declaration()
+      } else if self.matches(&IMPORT_KW) {
+         self.parse_import_declaration()
+      } else if self.matches(&EXPORT_KW) {
+         self.parse_export_declaration(doc)
       } else {
          self.parse_statement()
       };
@@ -29,66 +43,71 @@ impl Parser {
    }
 
    /// Parses a statement.
-   fn parse_statement(&mut self) -> Option<ASTNode> {
+   fn parse_statement(&mut self) -> Option<ASTNode<'a>> {
       if self.matches(&L_CURLY) {
          self.parse_block()
       } else if self.matches(&IF_KW) {
          self.parse_if_statement()
-      } else if self.matches(&WHILE_KW) {
-         self.parse_while_statement()
-      } else if self.matches(&FOR_KW) {
-         self.parse_for_statement()
+      } else if self.check(&IDENTIFIER) && matches!(self.peek(), COLON) {
+         self.parse_labeled_loop()
+      } else if self.check(&WHILE_KW) || self.check(&DO_KW) || self.check(&LOOP_KW) || self.check(&FOR_KW) {
+         self.parse_loop(None)
+      } else if self.matches(&MATCH_KW) {
+         self.parse_match_statement()
       } else if self.matches(&BREAK_KW) {
-         let tok = self.previous.clone();
-         self.consume(&SEMICOLON, "Expected a ';' after the 'break' keyword.");
-
-         Some(LoopBranch(LoopBranchStmtNode {
-            token: tok,
-            is_break: true,
-         }))
+         self.parse_loop_branch(true)
       } else if self.matches(&CONTINUE_KW) {
-         let tok = self.previous.clone();
-         self.consume(&SEMICOLON, "Expected a ';' after the 'continue' keyword.");
-
-         Some(LoopBranch(LoopBranchStmtNode {
-            token: tok,
-            is_break: false,
-         }))
+         self.parse_loop_branch(false)
       } else if self.matches(&RETURN_KW) {
          self.parse_return_stmt()
+      } else if self.matches(&TRY_KW) {
+         self.parse_try_statement()
+      } else if self.matches(&DEFER_KW) {
+         self.parse_defer_stmt()
+      } else if self.matches(&ASSERT_KW) {
+         self.parse_assert_stmt()
       } else {
          self.parse_expression_statement()
       }
    }
 
    /// Parses an expression statement.
-   fn parse_expression_statement(&mut self) -> Option<ASTNode> {
+   fn parse_expression_statement(&mut self) -> Option<ASTNode<'a>> {
       let opr = self.previous.clone();
       let expr = self.parse_expression();
 
-      self.consume(&SEMICOLON, "Expected a ';' after the expression.");
+      // The last expression of a trailing closure is its return value, so it may omit the ';'.
+      // So may a call that ends with a trailing closure (e.g., `list.each { print(it); }`).
+      let ends_closure = self.check(&R_CURLY) && self.closure_depths.last() == Some(&self.brace_depth);
+      let has_closure = matches!(expr, Some(FunctionCall(_))) && matches!(self.previous.token_type, R_CURLY);
+
+      if !(ends_closure || (has_closure && !self.check(&SEMICOLON))) {
+         self.consume(&SEMICOLON, "Expected a ';' after the expression.");
+      }
 
       Some(ExpressionStmt(ExpressionStmtNode {
          child: match expr {
             Some(t) => Box::new(t),
-            None => return None, // Could not create expression to print
+            None => return None, // Could not parse the expression
          },
          pos: (opr.line_num, opr.column_start),
       }))
    }
 
    /// Parses a block statement.
-   pub(super) fn parse_block(&mut self) -> Option<ASTNode> {
+   pub(super) fn parse_block(&mut self) -> Option<ASTNode<'a>> {
       let mut body: Vec<ASTNode> = vec![];
+      self.start_body();
 
       while !self.check(&R_CURLY) && !self.check(&EOF) {
-         match self.parse_declaration() {
-            Some(val) => body.push(val),
-            // Report parse error if node has None value
-            None => return None,
+         // Declarations with errors are skipped, so that the errors in
+         // the rest of the block can also be reported.
+         if let Some(val) = self.parse_declaration() {
+            body.push(val)
          }
       }
 
+      self.end_body();
       self.consume(&R_CURLY, "Expected a matching '}' for the block statement.");
 
       Some(BlockStmt(BlockNode {
@@ -98,24 +117,50 @@ impl Parser {
    }
 
    /// Parses a variable declaration.
-   fn parse_var_declaration(&mut self) -> Option<VariableDeclNode> {
+   ///
+   /// # Parameters
+   /// - `is_field`: Whether the variable is a class field, which is never destructured.
+   fn parse_var_declaration(&mut self, is_field: bool) -> Option<VariableDeclNode<'a>> {
       let mut declarations: Vec<Token> = Vec::new();
+      let mut annotations: Vec<Option<TypeAnnotation>> = Vec::new();
+
+      // A list of names in parenthesis declares the variables that a tuple
+      // (or an array) is unpacked into, as in `var (a, b) = (1, 2);`.
+      let parenthesized = self.matches(&L_PAREN);
 
       // Gets at least one variable name, or a list of
       // names separated by a comma
       self.consume(&IDENTIFIER, "Expected variable name.");
 
       declarations.push(self.previous.clone());
+      annotations.push(self.parse_type_annotation());
 
       while self.matches(&COMMA) {
          self.consume(&IDENTIFIER, "Expected variable name.");
 
          declarations.push(self.previous.clone());
+         annotations.push(self.parse_type_annotation());
       }
 
+      if parenthesized {
+         self.consume(
+            &R_PARENTHESIS,
+            "Expected ')' after the destructured variable names.",
+         );
+
+         if !self.check(&EQUALS) {
+            self.error_at_current("Destructured variables must be initialized upon declaration.");
+            return None;
+         }
+      }
+
+      // Several variables declared with a single value are also unpacked from that value, as in
+      // `var a, b = f();`, except for class fields, which are all initialized with the value.
+      let destructure = parenthesized || (!is_field && declarations.len() > 1 && self.check(&EQUALS));
+
       // Gets the variable's value.
       let initializer = if self.matches(&EQUALS) {
-         match self.parse_expression() {
+         match self.parse_value() {
             Some(val) => val,
             None => return None, // Could not create value for variable
          }
@@ -139,19 +184,22 @@ impl Parser {
 
       Some(VariableDeclNode {
          identifiers: declarations.into_boxed_slice(),
+         annotations: annotations.into_boxed_slice(),
          value: Box::new(initializer),
+         destructure,
       })
    }
 
    /// Parses a constant declaration.
-   fn parse_const_declaration(&mut self) -> Option<ConstantDeclNode> {
+   fn parse_const_declaration(&mut self) -> Option<ConstantDeclNode<'a>> {
       self.consume(&IDENTIFIER, "Expected a name for the constant declaration.");
 
       let name = self.previous.clone();
+      let annotation = self.parse_type_annotation();
 
       self.consume(&EQUALS, "Constants must be initialized upon declaration.");
 
-      let initializer = match self.parse_expression() {
+      let initializer = match self.parse_value() {
          Some(val) => val,
          None => return None, // Could not create value for variable
       };
@@ -169,18 +217,71 @@ impl Parser {
 
       Some(ConstantDeclNode {
          name,
+         annotation,
          value: Box::new(initializer),
       })
    }
 
+   /// Parses an `import` declaration.
+   fn parse_import_declaration(&mut self) -> Option<ASTNode<'a>> {
+      let token = self.previous.clone();
+
+      self.consume(&IDENTIFIER, "Expected a name for the imported module.");
+      let name = self.previous.clone();
+
+      self.consume(&FROM_KW, "Expected the 'from' keyword after the module name.");
+      self.consume(&STRING, "Expected a string with the path to the module.");
+      let path_token = self.previous.clone();
+      let path = match self.compile_string() {
+         Object::String(s) => s.to_string(),
+         _ => unreachable!("Should have compiled a string."),
+      };
+
+      self.consume(&SEMICOLON, "Expected a ';' after the import declaration.");
+
+      Some(ImportDecl(ImportDeclNode {
+         token,
+         name,
+         path,
+         path_token,
+      }))
+   }
+
+   /// Parses an `export` declaration.
+   fn parse_export_declaration(&mut self, doc: Option<String>) -> Option<ASTNode<'a>> {
+      let token = self.previous.clone();
+
+      let decl = if self.matches(&VAR_KW) {
+         self.parse_var_declaration(false).map(VariableDecl)
+      } else if self.matches(&CONST_KW) {
+         self.parse_const_declaration().map(ConstantDecl)
+      } else if self.matches(&FUNC_KW) {
+         self.parse_func_declaration(doc, false).map(FunctionDecl)
+      } else if self.matches(&ASYNC_KW) {
+         self.consume(&FUNC_KW, "Expected 'func' after the 'async' keyword.");
+         self.parse_func_declaration(doc, true).map(FunctionDecl)
+      } else if self.matches(&CLASS_KW) {
+         self.parse_class_declaration(doc)
+      } else if self.matches(&INTERFACE_KW) {
+         self.parse_interface_declaration()
+      } else if self.matches(&ENUM_KW) {
+         self.parse_enum_declaration()
+      } else {
+         self.error_at_current("Expected a declaration after the 'export' keyword.");
+         return None;
+      };
+
+      Some(ExportDecl(ExportDeclNode {
+         token,
+         decl: Box::new(decl?),
+      }))
+   }
+
    /// Parses an `if` statement.
-   fn parse_if_statement(&mut self) -> Option<ASTNode> {
+   fn parse_if_statement(&mut self) -> Option<ASTNode<'a>> {
       let then_tok = self.previous.clone();
 
-      let condition = match self.parse_expression() {
-         Some(val) => val,
-         None => return None, // Could not create condition for if-statement
-      };
+      let condition = self.parse_condition()?;
 
       let then_branch;
       if let R_PARENTHESIS = self.previous.token_type {
@@ -217,14 +318,174 @@ impl Parser {
       }))
    }
 
+   /// Parses a loop preceded by a label (e.g., `outer: for var x in xs { ... }`), which
+   /// `break` and `continue` statements in nested loops can refer to.
+   fn parse_labeled_loop(&mut self) -> Option<ASTNode<'a>> {
+      self.advance();
+      let label = self.previous.clone();
+      self.advance(); // Consume the ':'.
+
+      if self.check(&WHILE_KW) || self.check(&DO_KW) || self.check(&LOOP_KW) || self.check(&FOR_KW) {
+         self.loop_labels.push(label.lexeme.clone());
+         let stmt = self.parse_loop(Some(label));
+         self.loop_labels.pop();
+         stmt
+      } else {
+         self.error_at_current(&format!("Expected a loop after the label '{}'.", label.lexeme));
+         None
+      }
+   }
+
+   /// Parses the value of a variable declaration, a constant declaration, a `return`
+   /// statement, or a `break` statement, which can also be a (possibly labeled) `loop` expression whose value
+   /// is given by its `break` statements (e.g., `var x = loop { ... break 42; };`).
+   fn parse_value(&mut self) -> Option<ASTNode<'a>> {
+      let is_labeled = self.check(&IDENTIFIER) && matches!(self.peek(), COLON);
+
+      if !is_labeled && !self.check(&LOOP_KW) {
+         return self.parse_expression();
+      }
+
+      let label = if is_labeled {
+         self.advance();
+         let label = self.previous.clone();
+         self.advance(); // Consume the ':'.
+         Some(label)
+      } else {
+         None
+      };
+
+      if !self.matches(&LOOP_KW) {
+         self.error_at_current("Only a 'loop' can be used as an expression.");
+         return None;
+      }
+
+      if let Some(label) = &label {
+         self.loop_labels.push(label.lexeme.clone());
+      }
+
+      let is_labeled = label.is_some();
+      let expr = self.parse_loop_statement(label);
+
+      if is_labeled {
+         self.loop_labels.pop();
+      }
+
+      match expr? {
+         WhileStmt(expr) => Some(LoopExpr(expr)),
+         _ => unreachable!("A 'loop' statement is always parsed into a 'while' node."),
+      }
+   }
+
+   /// Parses a `while`, `do-while`, `loop`, or `for-in` statement.
+   ///
+   /// # Parameters
+   /// - `label`: The label of the loop, if it has one.
+   fn parse_loop(&mut self, label: Option<Token<'a>>) -> Option<ASTNode<'a>> {
+      self.advance();
+
+      match self.get_previous_tok_type() {
+         WHILE_KW => self.parse_while_statement(label),
+         DO_KW => self.parse_do_while_statement(label),
+         LOOP_KW => self.parse_loop_statement(label),
+         _ => self.parse_for_statement(label),
+      }
+   }
+
+   /// Parses a `break` or `continue` statement, along with the label of the loop it refers
+   /// to (e.g., `break outer;`), if there is one. A `break` statement can also have a value
+   /// (e.g., `break outer 42;`), which becomes the value of a `loop` expression.
+   ///
+   /// # Parameters
+   /// - `is_break`: Whether the statement is a `break` statement.
+   fn parse_loop_branch(&mut self, is_break: bool) -> Option<ASTNode<'a>> {
+      let token = self.previous.clone();
+
+      // The identifier after a `break` keyword is only a label if it names an enclosing
+      // loop. Otherwise, it is the start of the value of the `break` statement.
+      let is_label =
+         self.check(&IDENTIFIER) && (!is_break || self.loop_labels.contains(&self.current.lexeme));
+
+      let label = if is_label && self.matches(&IDENTIFIER) {
+         Some(self.previous.clone())
+      } else {
+         None
+      };
+
+      let value = if is_break && !self.check(&SEMICOLON) {
+         Some(Box::new(self.parse_value()?))
+      } else {
+         None
+      };
+
+      self.consume(
+         &SEMICOLON,
+         &format!(
+            "Expected a ';' after the '{}' {}.",
+            token.lexeme,
+            if value.is_some() { "value" } else { "keyword" }
+         ),
+      );
+
+      Some(LoopBranch(LoopBranchStmtNode {
+         token,
+         is_break,
+         label,
+         value,
+      }))
+   }
+
+   /// Parses a `do-while` statement, whose body runs before its condition is checked.
+   ///
+   /// # Parameters
+   /// - `label`: The label of the loop, if it has one.
+   fn parse_do_while_statement(&mut self, label: Option<Token<'a>>) -> Option<ASTNode<'a>> {
+      let token = self.previous.clone();
+
+      self.consume(&L_CURLY, "Expected '{' after the 'do' keyword.");
+      let body = self.parse_block()?;
+
+      self.consume(
+         &WHILE_KW,
+         "Expected 'while' after the body of the 'do-while' loop.",
+      );
+      let condition = self.parse_expression()?;
+      self.consume(&SEMICOLON, "Expected a ';' after the 'do-while' condition.");
+
+      Some(WhileStmt(WhileStmtNode {
+         token,
+         condition: Some(Box::new(condition)),
+         body: Box::new(body),
+         label,
+      }))
+   }
+
+   /// Parses an infinite `loop` statement.
+   ///
+   /// # Parameters
+   /// - `label`: The label of the loop, if it has one.
+   fn parse_loop_statement(&mut self, label: Option<Token<'a>>) -> Option<ASTNode<'a>> {
+      let token = self.previous.clone();
+
+      self.consume(&L_CURLY, "Expected '{' after the 'loop' keyword.");
+      let body = self.parse_block()?;
+
+      Some(WhileStmt(WhileStmtNode {
+         token,
+         condition: None,
+         body: Box::new(body),
+         label,
+      }))
+   }
+
    /// Parses a `while` statement.
-   fn parse_while_statement(&mut self) -> Option<ASTNode> {
+   ///
+   /// # Parameters
+   /// - `label`: The label of the loop, if it has one.
+   fn parse_while_statement(&mut self, label: Option<Token<'a>>) -> Option<ASTNode<'a>> {
       let tok = self.previous.clone();
 
-      let condition = match self.parse_expression() {
-         Some(val) => val,
-         None => return None, // Could not create condition for while-loop
-      };
+      let condition = self.parse_condition()?;
 
       let body;
       if let R_PARENTHESIS = self.previous.token_type {
@@ -243,13 +504,17 @@ impl Parser {
 
       Some(WhileStmt(WhileStmtNode {
          token: tok,
-         condition: Box::new(condition),
+         condition: Some(Box::new(condition)),
          body: Box::new(body),
+         label,
       }))
    }
 
    /// Parses a `for-in` statement.
-   fn parse_for_statement(&mut self) -> Option<ASTNode> {
+   ///
+   /// # Parameters
+   /// - `label`: The label of the loop, if it has one.
+   fn parse_for_statement(&mut self, label: Option<Token<'a>>) -> Option<ASTNode<'a>> {
       let token = self.previous.clone();
 
       let mut has_parenthesis = false;
@@ -273,9 +538,21 @@ impl Parser {
          None => return None, // Could not parse an identifier for loop
       };
 
+      // With two loop variables, each item is unpacked into a pair (e.g., the key and value
+      // of a dictionary's entries).
+      let value_id = if self.matches(&COMMA) {
+         self.consume(&IDENTIFIER, "Expected an identifier name after ','.");
+
+         Some(IdentifierExprNode {
+            token: self.previous.clone(),
+         })
+      } else {
+         None
+      };
+
       self.consume(&IN_KW, "Expected the 'in' keyword after the identifier.");
 
-      let iterator = match self.parse_expression() {
+      let iterator = match self.parse_condition() {
          Some(expr) => Box::new(expr),
          None => return None, // Could not parse an iterator expression
       };
@@ -309,13 +586,345 @@ impl Parser {
       Some(ForStmt(ForStmtNode {
          token,
          id,
+         value_id,
          iterator,
          body,
+         label,
+      }))
+   }
+
+   /// Parses a `match` statement.
+   fn parse_match_statement(&mut self) -> Option<ASTNode<'a>> {
+      let token = self.previous.clone();
+
+      let subject = self.parse_condition()?;
+      self.consume(&L_CURLY, "Expected '{' after the 'match' subject.");
+
+      let mut arms: Vec<MatchArm> = vec![];
+      let mut default = None;
+
+      while !self.check(&R_CURLY) && !self.check(&EOF) {
+         if default.is_some() {
+            self.error_at_current("The 'else' arm must be the last arm of a 'match' statement.");
+            return None;
+         }
+
+         if self.matches(&ELSE_KW) {
+            self.consume(&FAT_ARROW, "Expected '=>' after the 'else' keyword.");
+            default = Some(Box::new(self.parse_match_arm_body()?));
+         } else {
+            let mut patterns = vec![self.parse_match_pattern()?];
+
+            while self.matches(&COMMA) {
+               patterns.push(self.parse_match_pattern()?);
+            }
+
+            // The variables of an arm are bound by its only pattern.
+            if patterns.len() > 1 {
+               if let Some(name) = patterns.iter().flat_map(|p| p.bindings()).next() {
+                  self.error_at_token(
+                     name,
+                     "Patterns that bind variables cannot be combined with other patterns.",
+                  );
+                  return None;
+               }
+            }
+
+            let guard = if self.matches(&IF_KW) {
+               Some(Box::new(self.parse_expression()?))
+            } else {
+               None
+            };
+
+            self.consume(&FAT_ARROW, "Expected '=>' after the 'match' arm pattern.");
+
+            arms.push(MatchArm {
+               patterns: patterns.into_boxed_slice(),
+               guard,
+               body: Box::new(self.parse_match_arm_body()?),
+            });
+         }
+
+         // Arms can optionally be separated by commas.
+         self.matches(&COMMA);
+      }
+
+      self.consume(&R_CURLY, "Expected a matching '}' for the 'match' statement.");
+
+      Some(MatchStmt(MatchStmtNode {
+         token,
+         subject: Box::new(subject),
+         arms: arms.into_boxed_slice(),
+         default,
+      }))
+   }
+
+   /// Parses the body of a `match` arm. The body can be a statement, or an expression
+   /// terminated by either a `;` or a `,` (which can be omitted in the last arm).
+   fn parse_match_arm_body(&mut self) -> Option<ASTNode<'a>> {
+      match self.get_current_tok_type() {
+         L_CURLY | IF_KW | WHILE_KW | DO_KW | LOOP_KW | FOR_KW | MATCH_KW | BREAK_KW | CONTINUE_KW
+         | RETURN_KW | TRY_KW | DEFER_KW => self.parse_statement(),
+         _ => {
+            let opr = self.previous.clone();
+            let expr = self.parse_expression()?;
+
+            if !self.matches(&SEMICOLON) && !self.check(&COMMA) && !self.check(&R_CURLY) {
+               self.error_at_current("Expected a ',' or ';' after the 'match' arm.");
+               return None;
+            }
+
+            Some(ExpressionStmt(ExpressionStmtNode {
+               child: Box::new(expr),
+               pos: (opr.line_num, opr.column_start),
+            }))
+         }
+      }
+   }
+
+   /// Parses the pattern of a `match` arm, which can be a literal value, a range with integer
+   /// literal bounds, a wildcard, a binding, or an array or dictionary of nested patterns.
+   fn parse_match_pattern(&mut self) -> Option<MatchPattern<'a>> {
+      let pattern = self.parse_nested_match_pattern()?;
+
+      // Each variable can only be bound once by a pattern.
+      let bindings = pattern.bindings();
+      for (idx, name) in bindings.iter().enumerate() {
+         if bindings[..idx].iter().any(|prev| prev.lexeme == name.lexeme) {
+            self.error_at_token(
+               name,
+               &format!(
+                  "The variable '{}' is bound more than once in the pattern.",
+                  name.lexeme
+               ),
+            );
+            return None;
+         }
+      }
+
+      Some(pattern)
+   }
+
+   /// Parses a pattern of a `match` arm, or a pattern nested in an array or dictionary pattern.
+   fn parse_nested_match_pattern(&mut self) -> Option<MatchPattern<'a>> {
+      if self.check(&IDENTIFIER) && matches!(self.peek(), DOT) {
+         return self.parse_variant_pattern();
+      }
+
+      if self.matches(&IDENTIFIER) {
+         return match self.previous.lexeme.as_ref() {
+            "_" => Some(MatchPattern::Wildcard(self.previous.clone())),
+            _ => Some(MatchPattern::Binding(self.previous.clone())),
+         };
+      }
+
+      if self.matches(&L_BRACKET) {
+         let token = self.previous.clone();
+         let mut items = vec![];
+
+         while !self.check(&R_BRACKET) {
+            items.push(self.parse_nested_match_pattern()?);
+
+            if !self.matches(&COMMA) {
+               break;
+            }
+         }
+
+         self.consume(&R_BRACKET, "Expected ']' after the array pattern.");
+         return Some(MatchPattern::Array(token, items.into_boxed_slice()));
+      }
+
+      if self.matches(&L_CURLY) {
+         return self.parse_dictionary_pattern();
+      }
+
+      let pattern = match self.parse_expression()? {
+         Binary(BinaryExprNode {
+            left,
+            right,
+            opr_type: BinaryExprType::Range,
+            ..
+         }) => match (Parser::as_match_literal(*left), Parser::as_match_literal(*right)) {
+            (Some(start), Some(end)) if start.value.is_int() && end.value.is_int() => {
+               Some(MatchPattern::Range(start, end))
+            }
+            _ => None,
+         },
+         node => Parser::as_match_literal(node).map(MatchPattern::Literal),
+      };
+
+      if pattern.is_none() {
+         self.error_at_previous(
+            "Expected a literal, a range, a name, or an array or dictionary pattern for the 'match' arm.",
+         );
+      }
+
+      pattern
+   }
+
+   /// Parses a variant pattern of a `match` arm (e.g., `Shape.Rect(w, h)`).
+   fn parse_variant_pattern(&mut self) -> Option<MatchPattern<'a>> {
+      self.advance();
+      let enum_name = self.previous.clone();
+      self.advance();
+
+      self.consume(&IDENTIFIER, "Expected the name of a variant after the enum name.");
+      let variant = self.previous.clone();
+
+      let fields = if self.matches(&L_PAREN) {
+         let mut fields = vec![];
+
+         while !self.check(&R_PARENTHESIS) {
+            fields.push(self.parse_nested_match_pattern()?);
+
+            if !self.matches(&COMMA) {
+               break;
+            }
+         }
+
+         self.consume(
+            &R_PARENTHESIS,
+            "Expected ')' after the payload of the variant pattern.",
+         );
+         Some(fields.into_boxed_slice())
+      } else {
+         None
+      };
+
+      Some(MatchPattern::Variant {
+         enum_name,
+         variant,
+         fields,
+      })
+   }
+
+   /// Parses a dictionary pattern of a `match` arm, after its opening curly brace.
+   fn parse_dictionary_pattern(&mut self) -> Option<MatchPattern<'a>> {
+      let token = self.previous.clone();
+      let mut entries = vec![];
+
+      while !self.check(&R_CURLY) {
+         if !self.matches(&IDENTIFIER) && !self.matches(&STRING) {
+            self.error_at_current(
+               "Expected an identifier, or a string, for the key of the dictionary pattern.",
+            );
+            return None;
+         }
+
+         let key = self.previous.clone();
+
+         let pattern = if self.matches(&COLON) {
+            self.parse_nested_match_pattern()?
+         } else if let IDENTIFIER = key.token_type {
+            MatchPattern::Binding(key.clone())
+         } else {
+            self.error_at_current("Expected ':' after the string key of the dictionary pattern.");
+            return None;
+         };
+
+         entries.push((key, pattern));
+
+         if !self.matches(&COMMA) {
+            break;
+         }
+      }
+
+      self.consume(&R_CURLY, "Expected '}' after the dictionary pattern.");
+      Some(MatchPattern::Dictionary(token, entries.into_boxed_slice()))
+   }
+
+   /// Converts an expression node into a literal that can be used as a `match` pattern,
+   /// folding the negation of numeric literals.
+   fn as_match_literal(node: ASTNode<'a>) -> Option<LiteralExprNode<'a>> {
+      match node {
+         Literal(lit) => Some(lit),
+         Unary(UnaryExprNode {
+            operand,
+            opr_type: UnaryExprType::ArithmeticNeg,
+            ..
+         }) => match *operand {
+            Literal(LiteralExprNode {
+               value: Object::Int(i),
+               token,
+            }) => Some(LiteralExprNode {
+               value: Object::Int(i.checked_neg()?),
+               token,
+            }),
+            Literal(LiteralExprNode {
+               value: Object::Float(f),
+               token,
+            }) => Some(LiteralExprNode {
+               value: Object::Float(-f),
+               token,
+            }),
+            Literal(LiteralExprNode {
+               value: Object::BigInt(b),
+               token,
+            }) => Some(LiteralExprNode {
+               value: Object::from(b.neg()),
+               token,
+            }),
+            _ => None,
+         },
+         _ => None,
+      }
+   }
+
+   /// Parses a `try-catch-finally` statement.
+   fn parse_try_statement(&mut self) -> Option<ASTNode<'a>> {
+      let token = self.previous.clone();
+
+      self.consume(&L_CURLY, "Expected '{' after the 'try' keyword.");
+      let body = self.parse_block()?;
+
+      let mut catch_token = None;
+      let mut catch_id = None;
+      let mut catch_body = None;
+      if self.matches(&CATCH_KW) {
+         catch_token = Some(self.previous.clone());
+
+         // The error's identifier is optional, and it can be wrapped in parenthesis.
+         if self.matches(&L_PAREN) {
+            self.consume(&IDENTIFIER, "Expected an identifier for the caught error.");
+            catch_id = Some(self.previous.clone());
+            self.consume(
+               &R_PARENTHESIS,
+               "Expected a matching ')' after the error identifier.",
+            );
+         } else if self.matches(&IDENTIFIER) {
+            catch_id = Some(self.previous.clone());
+         }
+
+         self.consume(&L_CURLY, "Expected '{' for the 'catch' block.");
+         catch_body = Some(Box::new(self.parse_block()?));
+      }
+
+      let mut finally_token = None;
+      let mut finally_body = None;
+      if self.matches(&FINALLY_KW) {
+         finally_token = Some(self.previous.clone());
+         self.consume(&L_CURLY, "Expected '{' after the 'finally' keyword.");
+         finally_body = Some(Box::new(self.parse_block()?));
+      }
+
+      if catch_body.is_none() && finally_body.is_none() {
+         self.error_at_current("Expected a 'catch' or 'finally' block after the 'try' block.");
+         return None;
+      }
+
+      Some(TryStmt(TryStmtNode {
+         token,
+         body: Box::new(body),
+         catch_token,
+         catch_id,
+         catch_body,
+         finally_token,
+         finally_body,
       }))
    }
 
-   /// Parses a function declaration.
-   fn parse_func_declaration(&mut self) -> Option<FunctionDeclNode> {
+   /// Parses a function declaration, documented by the given doc comment.
+   fn parse_func_declaration(&mut self, doc: Option<String>, is_async: bool) -> Option<FunctionDeclNode<'a>> {
       self.consume(
          &IDENTIFIER,
          "Expected an identifier for the function declaration.",
@@ -328,6 +937,7 @@ impl Parser {
          Some(p) => p,
          None => return None,
       };
+      let return_type = self.parse_return_type();
       self.consume(&L_CURLY, "Expected '{' for the function body.");
 
       let min_arity = params.0;
@@ -337,6 +947,9 @@ impl Parser {
          name,
          params: params.1,
          arity: (min_arity, max_arity),
+         return_type,
+         doc,
+         is_async,
          body: match self.parse_block() {
             Some(node) => match node {
                BlockStmt(b) => b.body,
@@ -348,7 +961,7 @@ impl Parser {
    }
 
    /// Parses a parameter declaration.
-   pub(super) fn parse_parameters(&mut self) -> Option<(u8, Box<[Parameter]>)> {
+   pub(super) fn parse_parameters(&mut self) -> Option<(u8, Box<[Parameter<'a>]>)> {
       let mut params: Vec<Parameter> = vec![];
       let mut min_arity: u8 = 0;
 
@@ -358,29 +971,52 @@ impl Parser {
             return None;
          }
 
+         let is_variadic = self.matches(&ELLIPSIS);
          self.consume(&IDENTIFIER, "Expected a parameter name.");
          let name = self.previous.clone();
+         let annotation = self.parse_type_annotation();
+
+         // The rest parameter collects the extra arguments of a call into an array,
+         // so it must be the last parameter of the function.
+         if is_variadic {
+            params.push(Parameter {
+               name,
+               is_optional: true,
+               is_variadic: true,
+               default: None,
+               annotation,
+            });
+
+            self.consume(&R_PARENTHESIS, "Expected ')' after the rest parameter.");
+            break;
+         }
 
          let param = if self.matches(&QUESTION) {
             Parameter {
                name,
                is_optional: true,
+               is_variadic: false,
                default: None,
+               annotation,
             }
-         } else if self.matches(&COLON_EQUALS) {
+         } else if self.matches(&EQUALS) || self.matches(&COLON_EQUALS) {
             Parameter {
                name,
                is_optional: true,
+               is_variadic: false,
                default: match self.parse_expression() {
                   Some(x) => Some(Box::new(x)),
                   None => return None, // Could not compile default value for parameter
                },
+               annotation,
             }
          } else {
             Parameter {
                name,
                is_optional: false,
+               is_variadic: false,
                default: None,
+               annotation,
             }
          };
 
@@ -408,14 +1044,60 @@ impl Parser {
       Some((min_arity, params.into_boxed_slice()))
    }
 
+   /// Parses the type annotation after a declaration's name (e.g., `: Int`), if there is one.
+   fn parse_type_annotation(&mut self) -> Option<TypeAnnotation<'a>> {
+      if !self.matches(&COLON) {
+         return None;
+      }
+
+      self.consume(&IDENTIFIER, "Expected a type name after ':'.");
+      Some(TypeAnnotation {
+         name: self.previous.clone(),
+      })
+   }
+
+   /// Parses the return type annotation of a function (e.g., `-> Int`), if there is one.
+   pub(super) fn parse_return_type(&mut self) -> Option<TypeAnnotation<'a>> {
+      if !self.matches(&THIN_ARROW) {
+         return None;
+      }
+
+      self.consume(&IDENTIFIER, "Expected a type name after '->'.");
+      Some(TypeAnnotation {
+         name: self.previous.clone(),
+      })
+   }
+
    /// Parses a `return` statement.
-   fn parse_return_stmt(&mut self) -> Option<ASTNode> {
+   fn parse_return_stmt(&mut self) -> Option<ASTNode<'a>> {
       let tok = self.previous.clone();
 
       // Compiles the return expression
       if !self.matches(&SEMICOLON) {
-         let expr = self.parse_expression()?;
-         self.consume(&SEMICOLON, "Expected a ';' after the expression.");
+         let mut expr = self.parse_value()?;
+
+         // Several values are returned as a tuple, as in `return a, b;`.
+         if self.matches(&COMMA) {
+            let mut values = vec![expr];
+
+            loop {
+               values.push(self.parse_expression()?);
+
+               if !self.matches(&COMMA) {
+                  break;
+               }
+            }
+
+            expr = Tuple(TupleExprNode {
+               values: values.into_boxed_slice(),
+               token: tok.clone(),
+            });
+         }
+
+         // The ';' after a `loop` expression is optional.
+         if !matches!(expr, LoopExpr(_)) || self.check(&SEMICOLON) {
+            self.consume(&SEMICOLON, "Expected a ';' after the expression.");
+         }
 
          return Some(ReturnStmt(ReturnStmtNode {
             token: tok,
@@ -429,57 +1111,299 @@ impl Parser {
       }))
    }
 
-   /// Parses a `class` declaration statement.
-   fn parse_class_declaration(&mut self) -> Option<ASTNode> {
+   /// Parses a `defer` statement. The deferred expression (or block) is parsed as the body
+   /// of a lambda with no parameters, which is called when the enclosing function returns.
+   fn parse_defer_stmt(&mut self) -> Option<ASTNode<'a>> {
+      let tok = self.previous.clone();
+
+      let body = if self.matches(&L_CURLY) {
+         match self.parse_block()? {
+            BlockStmt(block) => block.body,
+            _ => unreachable!("Parsing a block should return a block statement."),
+         }
+      } else {
+         let expr = self.parse_expression()?;
+         self.consume(&SEMICOLON, "Expected a ';' after the deferred expression.");
+
+         vec![ExpressionStmt(ExpressionStmtNode {
+            child: Box::new(expr),
+            pos: (tok.line_num, tok.column_start),
+         })]
+         .into_boxed_slice()
+      };
+
+      Some(DeferStmt(DeferStmtNode {
+         token: tok.clone(),
+         body: FunctionDeclNode {
+            name: tok,
+            params: vec![].into_boxed_slice(),
+            arity: (0, 0),
+            body,
+            return_type: None,
+            doc: None,
+            is_async: false,
+         },
+      }))
+   }
+
+   /// Parses an `assert` statement. The condition and the message can also be wrapped in
+   /// parentheses, like in `assert(x > 0, "message");`.
+   fn parse_assert_stmt(&mut self) -> Option<ASTNode<'a>> {
+      let tok = self.previous.clone();
+      let in_parens = self.matches(&L_PAREN);
+
+      let start = self.lexer.char_position(self.current.span.start);
+      let condition = self.parse_expression()?;
+      let source = self.lexer.source_text(start, self.previous.column_end);
+
+      let message = if self.matches(&COMMA) {
+         Some(Box::new(self.parse_expression()?))
+      } else {
+         None
+      };
+
+      if in_parens {
+         self.consume(&R_PARENTHESIS, "Expected a ')' after the assertion.");
+      }
+
+      self.consume(&SEMICOLON, "Expected a ';' after the assertion.");
+
+      Some(AssertStmt(AssertStmtNode {
+         token: tok,
+         condition: Box::new(condition),
+         message,
+         source,
+      }))
+   }
+
+   /// Parses a `class` declaration statement, documented by the given doc comment.
+   fn parse_class_declaration(&mut self, doc: Option<String>) -> Option<ASTNode<'a>> {
       self.consume(&IDENTIFIER, "Expected an identifier for the class declaration.");
       let name = self.previous.clone();
 
+      let superclass = if self.matches(&EXTENDS_KW) {
+         self.consume(&IDENTIFIER, "Expected a superclass name after 'extends'.");
+
+         Some(IdentifierExprNode {
+            token: self.previous.clone(),
+         })
+      } else {
+         None
+      };
+
+      let mut interfaces: Vec<IdentifierExprNode> = vec![];
+      if self.matches(&IMPLEMENTS_KW) {
+         loop {
+            self.consume(&IDENTIFIER, "Expected an interface name.");
+            interfaces.push(IdentifierExprNode {
+               token: self.previous.clone(),
+            });
+
+            if !self.matches(&COMMA) {
+               break;
+            }
+         }
+      }
+
       self.consume(&L_CURLY, "Expected '{' for the class body.");
       let mut members: Vec<ClassMemberDeclNode> = vec![];
+      self.start_body();
 
-      while !self.matches(&TokenType::R_CURLY) {
-         let mut mode = self.capture_field_mode()?;
-
-         let member_type = if self.matches(&FUNC_KW) {
-            match self.parse_func_declaration() {
-               Some(decl) => {
-                  if decl.name.lexeme == "init" {
-                     if (mode & 0b_0000_1000) != 8 {
-                        self.error_at_token(&decl.name, "Class initializer must be public.");
-                        return None;
-                     } else if (mode & 0b_0000_0100) == 4 {
-                        self.error_at_token(&decl.name, "Class initializer cannot be static.");
-                        return None;
-                     } else if (mode & 0b_0000_0010) == 2 {
-                        self.error_at_token(&decl.name, "Cannot override class initializer.");
-                        return None;
-                     }
-                  }
+      while !self.check(&R_CURLY) && !self.check(&EOF) {
+         let member_start = (self.current.line_num, self.current.column_start);
+
+         match self.parse_class_member() {
+            Some(member) => members.push(member),
+            None => {
+               // Skips the token that caused the error if it could not start a class member.
+               if (self.current.line_num, self.current.column_start) == member_start {
+                  self.advance();
+               }
+               self.synchronize()
+            }
+         }
+      }
+
+      self.end_body();
+      if !self.matches(&R_CURLY) {
+         self.error_at_current("Unexpected end of file while parsing class body.");
+      }
+
+      Some(ClassDecl(ClassDeclNode {
+         name,
+         superclass,
+         interfaces: interfaces.into_boxed_slice(),
+         members: members.into_boxed_slice(),
+         doc,
+      }))
+   }
+
+   /// Parses a member of a class body, along with its modifiers.
+   fn parse_class_member(&mut self) -> Option<ClassMemberDeclNode<'a>> {
+      let doc = self.current_doc.take();
+      let mut mode = self.capture_field_mode()?;
+
+      let is_async = self.matches(&ASYNC_KW);
+
+      if is_async {
+         self.consume(&FUNC_KW, "Expected 'func' after the 'async' keyword.");
+      }
 
-                  ClassMemberDecl::Method(decl)
+      let member_type = if is_async || self.matches(&FUNC_KW) {
+         match self.parse_func_declaration(doc, is_async) {
+            Some(decl) => {
+               if decl.name.lexeme == "init" {
+                  if is_async {
+                     self.error_at_token(&decl.name, "Class initializer cannot be async.");
+                     return None;
+                  } else if (mode & 0b_0000_1000) != 8 {
+                     self.error_at_token(&decl.name, "Class initializer must be public.");
+                     return None;
+                  } else if (mode & 0b_0000_0100) == 4 {
+                     self.error_at_token(&decl.name, "Class initializer cannot be static.");
+                     return None;
+                  } else if (mode & 0b_0000_0010) == 2 {
+                     self.error_at_token(&decl.name, "Cannot override class initializer.");
+                     return None;
+                  }
                }
-               None => return None, // Could not parse method
+
+               ClassMemberDecl::Method(decl)
             }
-         } else if self.matches(&VAR_KW) {
-            self.parse_var_declaration().map(ClassMemberDecl::Var)?
-         } else if self.matches(&CONST_KW) {
-            mode |= 0b_0000_0001; // Sets the "constant" mode bit.
-            self.parse_const_declaration().map(ClassMemberDecl::Const)?
-         } else {
-            if self.check(&EOF) {
-               self.error_at_current("Unexpected end of file while parsing class body.")
-            } else {
-               self.error_at_current("Unexpected token.");
+            None => return None, // Could not parse method
+         }
+      } else if self.check(&IDENTIFIER) && matches!(self.current.lexeme.as_ref(), "get" | "set") {
+         self.advance();
+         let is_getter = self.previous.lexeme == "get";
+         let decl = self.parse_func_declaration(doc, false)?;
+
+         if (mode & 0b_0000_0100) == 4 {
+            self.error_at_token(&decl.name, "Property accessors cannot be static.");
+            return None;
+         } else if is_getter && decl.arity.1 != 0 {
+            self.error_at_token(&decl.name, "A getter cannot have parameters.");
+            return None;
+         } else if !is_getter && (decl.arity != (1, 1) || decl.params[0].is_variadic) {
+            self.error_at_token(&decl.name, "A setter must have exactly one parameter.");
+            return None;
+         }
+
+         // Sets the "getter" or "setter" mode bit.
+         mode |= if is_getter { 0b_0001_0000 } else { 0b_0010_0000 };
+         ClassMemberDecl::Method(decl)
+      } else if self.matches(&VAR_KW) {
+         self.parse_var_declaration(true).map(ClassMemberDecl::Var)?
+      } else if self.matches(&CONST_KW) {
+         mode |= 0b_0000_0001; // Sets the "constant" mode bit.
+         self.parse_const_declaration().map(ClassMemberDecl::Const)?
+      } else {
+         self.error_at_current("Unexpected token.");
+         return None;
+      };
+
+      Some(ClassMemberDeclNode { member_type, mode })
+   }
+
+   /// Parses an interface declaration. The body of an interface is a list of
+   /// method signatures, which end with a semicolon instead of a function body.
+   fn parse_interface_declaration(&mut self) -> Option<ASTNode<'a>> {
+      self.consume(
+         &IDENTIFIER,
+         "Expected an identifier for the interface declaration.",
+      );
+      let name = self.previous.clone();
+
+      self.consume(&L_CURLY, "Expected '{' for the interface body.");
+      let mut methods: Vec<InterfaceMethodNode> = vec![];
+
+      while !self.matches(&R_CURLY) {
+         if self.check(&EOF) {
+            self.error_at_current("Unexpected end of file while parsing interface body.");
+            return None;
+         }
+
+         self.consume(&FUNC_KW, "Expected a method signature in the interface body.");
+         self.consume(&IDENTIFIER, "Expected an identifier for the method signature.");
+         let method_name = self.previous.clone();
+
+         self.consume(&L_PAREN, "Expected '(' after method name.");
+         let params = self.parse_parameters()?.1;
+         let return_type = self.parse_return_type();
+         self.consume(&SEMICOLON, "Expected ';' after the method signature.");
+
+         if methods.iter().any(|m| m.name.lexeme == method_name.lexeme) {
+            self.error_at_token(&method_name, "Duplicate method signature in interface.");
+            return None;
+         }
+
+         methods.push(InterfaceMethodNode {
+            name: method_name,
+            params,
+            return_type,
+         });
+      }
+
+      Some(InterfaceDecl(InterfaceDeclNode {
+         name,
+         methods: methods.into_boxed_slice(),
+      }))
+   }
+
+   /// Parses an enum declaration, whose variants are separated by commas.
+   fn parse_enum_declaration(&mut self) -> Option<ASTNode<'a>> {
+      self.consume(&IDENTIFIER, "Expected an identifier for the enum declaration.");
+      let name = self.previous.clone();
+
+      self.consume(&L_CURLY, "Expected '{' for the enum body.");
+      let mut variants: Vec<EnumVariantNode> = vec![];
+
+      while !self.check(&R_CURLY) {
+         self.consume(&IDENTIFIER, "Expected an identifier for the enum variant.");
+         let variant_name = self.previous.clone();
+         let mut fields: Vec<Token> = vec![];
+
+         if self.matches(&L_PAREN) {
+            while !self.check(&R_PARENTHESIS) {
+               self.consume(
+                  &IDENTIFIER,
+                  "Expected an identifier for the field of the variant.",
+               );
+
+               if fields.iter().any(|f| f.lexeme == self.previous.lexeme) {
+                  self.error_at_previous("Duplicate field in enum variant.");
+                  return None;
+               }
+
+               fields.push(self.previous.clone());
+
+               if !self.matches(&COMMA) {
+                  break;
+               }
             }
+
+            self.consume(&R_PARENTHESIS, "Expected ')' after the fields of the variant.");
+         }
+
+         if variants.iter().any(|v| v.name.lexeme == variant_name.lexeme) {
+            self.error_at_token(&variant_name, "Duplicate variant in enum.");
             return None;
-         };
+         }
+
+         variants.push(EnumVariantNode {
+            name: variant_name,
+            fields: fields.into_boxed_slice(),
+         });
 
-         members.push(ClassMemberDeclNode { member_type, mode });
+         if !self.matches(&COMMA) {
+            break;
+         }
       }
 
-      Some(ClassDecl(ClassDeclNode {
+      self.consume(&R_CURLY, "Expected '}' after the enum variants.");
+
+      Some(EnumDecl(EnumDeclNode {
          name,
-         members: members.into_boxed_slice(),
+         variants: variants.into_boxed_slice(),
       }))
    }
 
@@ -506,7 +1430,8 @@ impl Parser {
       // [1, 1, 0, 1] = 13 -> (public,     static,        non-override,    constant)
       // [1, 1, 1, 0] = 14 -> (public,     static,        override,        non-constant)
       // [1, 1, 1, 1] = 15 -> (public,     static,        override,        constant)
-      // The `parse_class_declaration` function adds the `constant` mode bit.
+      // The `parse_class_declaration` function adds the `constant` mode bit, and
+      // the `getter` (0b_0001_0000) or `setter` (0b_0010_0000) mode bits.
       let mut mode: u8 = 0;
 
       while self.matches(&PUBLIC_KW) || self.matches(&STATIC_KW) || self.matches(&OVERRIDE_KW) {
@@ -0,0 +1,213 @@
+//! The Hinton playground (`hinton playground`) is a local web server with a page where programs
+//! can be written and run without installing an editor. The programs run in a VM without access
+//! to the host system and with limits on their resources, and their output and errors are
+//! streamed back to the page while they run.
+
+use crate::built_in::natives::http::read_message;
+use crate::lsp::json::Json;
+use crate::virtual_machine::{InterpretResult, VmConfig, VM};
+use std::io::{self, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::thread;
+use std::time::Duration;
+
+/// The page served by the playground, with the editor and the output of the programs.
+const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Hinton Playground</title>
+<style>
+   body { margin: 0; font-family: sans-serif; background: #1e1e1e; color: #ddd; }
+   header { padding: 8px 16px; background: #2d2d2d; display: flex; gap: 16px; align-items: center; }
+   main { display: flex; height: calc(100vh - 48px); }
+   textarea, pre { flex: 1; margin: 0; padding: 12px; font: 14px monospace; background: #1e1e1e; color: #ddd; }
+   textarea { border: none; border-right: 1px solid #444; resize: none; outline: none; }
+   pre { overflow: auto; white-space: pre-wrap; }
+   .errors { color: #f88; }
+   #status { color: #999; }
+</style>
+</head>
+<body>
+<header>
+   <strong>Hinton Playground</strong>
+   <button id="run">Run (Ctrl+Enter)</button>
+   <span id="status"></span>
+</header>
+<main>
+   <textarea id="code" spellcheck="false">func greet(name) {
+   return "Hello, " + name + "!";
+}
+
+print(greet("world"));</textarea>
+   <pre id="output"></pre>
+</main>
+<script>
+   const code = document.getElementById("code");
+   const output = document.getElementById("output");
+   const status = document.getElementById("status");
+
+   async function run() {
+      output.textContent = "";
+      status.textContent = "Running...";
+
+      const response = await fetch("/run", { method: "POST", body: code.value });
+      const reader = response.body.getReader();
+      const decoder = new TextDecoder();
+      let buffered = "";
+
+      for (;;) {
+         const { done, value } = await reader.read();
+         if (done) break;
+
+         buffered += decoder.decode(value, { stream: true });
+         const lines = buffered.split("\n");
+         buffered = lines.pop();
+         lines.forEach(line => show(JSON.parse(line)));
+      }
+   }
+
+   function show(message) {
+      if (message.kind === "exit") {
+         status.textContent = message.text;
+         return;
+      }
+
+      const span = document.createElement("span");
+      span.className = message.kind;
+      span.textContent = message.text;
+      output.appendChild(span);
+   }
+
+   document.getElementById("run").onclick = run;
+   code.onkeydown = e => {
+      if (e.key === "Enter" && (e.ctrlKey || e.metaKey)) run();
+   };
+</script>
+</body>
+</html>
+"#;
+
+/// The default limits on the resources used by the programs run in the playground.
+pub fn default_limits() -> VmConfig {
+   VmConfig {
+      max_frames: 500,
+      max_stack_slots: Some(100_000),
+      max_heap_bytes: Some(64 * 1024 * 1024),
+      max_instructions: Some(50_000_000),
+      timeout: Some(Duration::from_secs(10)),
+      has_clock: true,
+   }
+}
+
+/// Serves the playground, handling each connection in its own thread. Runs until the
+/// listener fails.
+///
+/// # Parameters
+/// - `listener`: The listener that accepts the connections of the browsers.
+/// - `limits`: The limits on the resources used by each program.
+pub fn serve(listener: TcpListener, limits: VmConfig) -> io::Result<()> {
+   loop {
+      let (stream, _) = listener.accept()?;
+      thread::spawn(move || handle_connection(stream, limits));
+   }
+}
+
+/// Handles a request of the browser, which either loads the page, or runs a program.
+fn handle_connection(stream: TcpStream, limits: VmConfig) {
+   let _ = stream.set_read_timeout(Some(Duration::from_secs(30)));
+
+   let request = match read_message(&mut BufReader::new(&stream), false) {
+      Ok(request) => request,
+      Err(_) => return,
+   };
+
+   // The browser may close the connection at any time, which is not an error of the playground.
+   let mut words = request.start_line.split(' ');
+   let _ = match (words.next(), words.next()) {
+      (Some("GET"), Some("/")) => send_response(&stream, "200 OK", "text/html; charset=utf-8", PAGE),
+      (Some("POST"), Some("/run")) => run_program(stream, &String::from_utf8_lossy(&request.body), limits),
+      _ => send_response(&stream, "404 Not Found", "text/plain", "Not Found"),
+   };
+}
+
+/// Sends a complete response to the browser.
+fn send_response(mut stream: &TcpStream, status: &str, content_type: &str, body: &str) -> io::Result<()> {
+   write!(
+      stream,
+      "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+      status,
+      content_type,
+      body.len(),
+      body
+   )?;
+   stream.flush()
+}
+
+/// Sends a message to the browser, as a line of JSON in a chunk of the response.
+fn send_message(mut stream: &TcpStream, kind: &str, text: &str) -> io::Result<()> {
+   let message = Json::object(vec![
+      ("kind", Json::String(kind.to_string())),
+      ("text", Json::String(text.to_string())),
+   ]);
+   let line = format!("{}\n", message);
+
+   write!(stream, "{:x}\r\n{}\r\n", line.len(), line)?;
+   stream.flush()
+}
+
+/// An output stream of a program, which sends the text written to it to the browser.
+struct MessageWriter {
+   stream: Rc<TcpStream>,
+   /// The kind of the messages (either `output` or `errors`).
+   kind: &'static str,
+}
+
+impl Write for MessageWriter {
+   fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+      send_message(&self.stream, self.kind, &String::from_utf8_lossy(buf))?;
+      Ok(buf.len())
+   }
+
+   fn flush(&mut self) -> io::Result<()> {
+      Ok(())
+   }
+}
+
+/// Runs a program in a new VM without access to the host system, streaming its output and
+/// errors to the browser while it runs. The stream ends with an `exit` message.
+fn run_program(stream: TcpStream, source: &str, limits: VmConfig) -> io::Result<()> {
+   let stream = Rc::new(stream);
+
+   write!(
+      &*stream,
+      "HTTP/1.1 200 OK\r\nContent-Type: application/x-ndjson\r\nTransfer-Encoding: chunked\r\nConnection: close\r\n\r\n"
+   )?;
+
+   // The output is shown in a web page, which cannot display ANSI escape sequences.
+   let result = VM::new(PathBuf::from("playground.ht"))
+      .without_colors()
+      .with_config(limits)
+      .without_system_access()
+      .with_input(Box::new(io::empty()))
+      .with_stdout(Box::new(MessageWriter {
+         stream: stream.clone(),
+         kind: "output",
+      }))
+      .with_stderr(Box::new(MessageWriter {
+         stream: stream.clone(),
+         kind: "errors",
+      }))
+      .interpret(source);
+
+   let status = match result {
+      InterpretResult::Ok => "Finished.",
+      _ => "Finished with errors.",
+   };
+
+   send_message(&stream, "exit", status)?;
+   write!(&*stream, "0\r\n\r\n")?;
+   (&*stream).flush()
+}
@@ -0,0 +1,166 @@
+use hashbrown::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The name of the manifest file of a Hinton project.
+pub const MANIFEST_FILE: &str = "hinton.toml";
+
+/// The manifest of a Hinton project, read from the `hinton.toml` file at the root of the
+/// project. The manifest supports a small subset of TOML:
+///
+/// ```toml
+/// [project]
+/// name = "app"
+/// entry = "src/main.ht"
+/// source_dirs = ["src", "lib"]
+///
+/// [scripts]
+/// test = "tests/main.ht"
+/// ```
+pub struct Manifest {
+   /// The name of the project.
+   pub name: Option<String>,
+   /// The path to the file executed by `hinton run`.
+   pub entry: PathBuf,
+   /// The directories searched for modules that cannot be found relative to the importing file.
+   pub source_dirs: Vec<PathBuf>,
+   /// Named aliases for other files in the project, which can be executed with `hinton run <alias>`.
+   pub scripts: HashMap<String, PathBuf>,
+}
+
+/// A value in the manifest file.
+enum ManifestValue {
+   Str(String),
+   List(Vec<String>),
+}
+
+impl Manifest {
+   /// Finds the manifest of the project that contains a directory, by searching the directory
+   /// and each of its ancestors for a `hinton.toml` file.
+   ///
+   /// # Parameters
+   /// - `dir`: The directory where the search starts.
+   ///
+   /// # Returns
+   /// - `Option<Result<Manifest, String>>`: The parsed manifest, or an error message if the
+   ///   manifest could not be read. `None` if the directory is not inside a Hinton project.
+   pub fn find(dir: &Path) -> Option<Result<Manifest, String>> {
+      let root = dir.ancestors().find(|d| d.join(MANIFEST_FILE).is_file())?;
+      let path = root.join(MANIFEST_FILE);
+
+      Some(match fs::read_to_string(&path) {
+         Ok(src) => Manifest::parse(&src, root),
+         Err(_) => Err(format!("Could not read '{}'.", path.display())),
+      })
+   }
+
+   /// Parses the contents of a manifest file. Relative paths in the manifest are resolved
+   /// against the root directory of the project.
+   ///
+   /// # Parameters
+   /// - `src`: The contents of the manifest file.
+   /// - `root`: The root directory of the project.
+   ///
+   /// # Returns
+   /// - `Result<Manifest, String>`: The parsed manifest, or an error message.
+   pub fn parse(src: &str, root: &Path) -> Result<Manifest, String> {
+      let mut manifest = Manifest {
+         name: None,
+         entry: root.join("main.ht"),
+         source_dirs: vec![],
+         scripts: HashMap::new(),
+      };
+
+      let mut section = String::new();
+
+      for (idx, line) in src.lines().enumerate() {
+         let line = strip_comment(line).trim();
+         let line_num = idx + 1;
+
+         if line.is_empty() {
+            continue;
+         }
+
+         if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = name.trim().to_string();
+
+            if section != "project" && section != "scripts" {
+               return Err(format!("Line {}: Unknown section '[{}]'.", line_num, section));
+            }
+
+            continue;
+         }
+
+         let (key, value) = match line.split_once('=') {
+            Some((key, value)) => (key.trim(), parse_value(value.trim())),
+            None => return Err(format!("Line {}: Expected a 'key = value' pair.", line_num)),
+         };
+
+         let value = match value {
+            Some(v) => v,
+            None => return Err(format!("Line {}: Invalid value for '{}'.", line_num, key)),
+         };
+
+         match (section.as_str(), key, value) {
+            ("project", "name", ManifestValue::Str(s)) => manifest.name = Some(s),
+            ("project", "entry", ManifestValue::Str(s)) => manifest.entry = root.join(s),
+            ("project", "source_dirs", ManifestValue::List(dirs)) => {
+               manifest.source_dirs = dirs.iter().map(|d| root.join(d)).collect()
+            }
+            ("scripts", alias, ManifestValue::Str(s)) => {
+               manifest.scripts.insert(alias.to_string(), root.join(s));
+            }
+            ("", _, _) => return Err(format!("Line {}: Expected a section before '{}'.", line_num, key)),
+            (section, key, _) => {
+               return Err(format!(
+                  "Line {}: Unknown or invalid key '{}' in section '[{}]'.",
+                  line_num, key, section
+               ))
+            }
+         }
+      }
+
+      Ok(manifest)
+   }
+}
+
+/// Removes the comment (if any) at the end of a line in the manifest.
+fn strip_comment(line: &str) -> &str {
+   let mut in_string = false;
+
+   for (i, c) in line.char_indices() {
+      match c {
+         '"' => in_string = !in_string,
+         '#' if !in_string => return &line[..i],
+         _ => {}
+      }
+   }
+
+   line
+}
+
+/// Parses a value in the manifest, which is either a string or a list of strings.
+fn parse_value(value: &str) -> Option<ManifestValue> {
+   if let Some(list) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+      return list
+         .split(',')
+         .map(str::trim)
+         .filter(|item| !item.is_empty())
+         .map(parse_string)
+         .collect::<Option<Vec<String>>>()
+         .map(ManifestValue::List);
+   }
+
+   parse_string(value).map(ManifestValue::Str)
+}
+
+/// Parses a double-quoted string in the manifest.
+fn parse_string(value: &str) -> Option<String> {
+   let inner = value.strip_prefix('"')?.strip_suffix('"')?;
+
+   if inner.contains('"') {
+      return None;
+   }
+
+   Some(inner.to_string())
+}
@@ -0,0 +1,212 @@
+use crate::compiler::Compiler;
+use crate::core::ast::ASTNode;
+use crate::core::tokens::TokenType;
+use crate::errors::{paint, print_errors_list, print_warnings_list, ErrorReport};
+use crate::lexer::Lexer;
+use crate::objects::Object;
+use crate::parser::Parser;
+#[cfg(unix)]
+use crate::virtual_machine::INTERRUPTED;
+use crate::virtual_machine::{InterpretResult, VM};
+use std::env;
+use std::io::{self, Write};
+#[cfg(unix)]
+use std::sync::atomic::Ordering;
+
+/// Represents the result of reading a line of input from the programmer.
+enum ReadLine {
+   /// A complete line of text, including the line break.
+   Text(String),
+   /// The programmer pressed Ctrl-C while typing the line.
+   #[cfg_attr(not(unix), allow(dead_code))]
+   Interrupted,
+   /// The programmer pressed Ctrl-D, or the input stream was closed.
+   Eof,
+}
+
+/// Runs the Hinton Read-Eval-Print Loop. The same compiler and virtual machine are used to
+/// execute every input, so the global declarations made by an input are available to the
+/// inputs that follow it.
+pub fn run_repl() {
+   install_interrupt_handler();
+
+   // Modules imported from the REPL are resolved relative to the current directory.
+   let filepath = env::current_dir().unwrap_or_default().join("<REPL>");
+
+   let mut vm = VM::new(filepath.clone());
+   let mut compiler = Compiler::new_repl(&filepath, &vm.built_in);
+
+   println!(
+      "Hinton v{} REPL. Press Ctrl-D to exit.",
+      env!("CARGO_PKG_VERSION")
+   );
+
+   while let Some(input) = read_input() {
+      if input.trim().is_empty() {
+         continue;
+      }
+
+      let mut buffer = String::new();
+      let ast = match parse_input(&input, &mut buffer) {
+         Ok(x) => x,
+         Err(errors) => {
+            let _ = print_errors_list(&mut io::stderr(), &filepath, &errors, &input);
+            continue;
+         }
+      };
+
+      let module = match compiler.compile_repl_input(&ast) {
+         Ok(x) => {
+            let _ = print_warnings_list(&mut io::stderr(), &filepath, &compiler.take_warnings(), &input);
+            x
+         }
+         Err(errors) => {
+            let _ = print_errors_list(&mut io::stderr(), &filepath, &errors, &input);
+            continue;
+         }
+      };
+
+      if let InterpretResult::Ok = vm.execute(module, &input) {
+         match vm.last_value() {
+            Some(Object::Null) | None => {}
+            Some(value) => println!("{}", paint(&value.to_string())),
+         }
+      }
+   }
+}
+
+/// Parses an input entered in the REPL. For convenience, the semicolon at the end of
+/// an input with a single statement can be omitted (e.g., `>>> 2 + 2`).
+///
+/// # Parameters
+/// - `input`: The source text of the input.
+/// - `buffer`: The storage for the input with a semicolon at its end, which the AST borrows
+///   its tokens from when the input is only valid with the semicolon.
+pub(crate) fn parse_input<'a>(
+   input: &'a str,
+   buffer: &'a mut String,
+) -> Result<ASTNode<'a>, Vec<ErrorReport>> {
+   match Parser::parse(input) {
+      Ok(ast) => Ok(ast),
+      Err(errors) => {
+         *buffer = format!("{};", input.trim_end());
+         Parser::parse(buffer).map_err(|_| errors)
+      }
+   }
+}
+
+/// Reads an input from the programmer. If the input has unbalanced brackets, the programmer
+/// is prompted for more lines until the brackets are balanced.
+///
+/// # Returns
+/// - `Option<String>`: The input, or `None` if the REPL should exit.
+fn read_input() -> Option<String> {
+   let mut input = String::new();
+
+   loop {
+      print!("{}", if input.is_empty() { ">>> " } else { "... " });
+      std::io::stdout().flush().unwrap_or_default();
+
+      match read_line() {
+         ReadLine::Text(line) => {
+            input.push_str(&line);
+
+            if !has_unclosed_brackets(&input) {
+               return Some(input);
+            }
+         }
+         ReadLine::Interrupted => {
+            // Ctrl-C discards the current input instead of exiting the REPL.
+            println!("\nKeyboardInterrupt");
+            input.clear();
+         }
+         ReadLine::Eof => {
+            println!();
+            return None;
+         }
+      }
+   }
+}
+
+/// Checks if the given input has brackets that have been opened but not yet closed.
+///
+/// # Parameters
+/// - `input`: The source text of the input.
+fn has_unclosed_brackets(input: &str) -> bool {
+   let mut lexer = Lexer::new(input);
+   let mut depth = 0i32;
+
+   loop {
+      let token = lexer.next_token();
+
+      match token.token_type {
+         TokenType::L_BRACKET | TokenType::L_CURLY | TokenType::L_PAREN => depth += 1,
+         TokenType::R_BRACKET | TokenType::R_CURLY | TokenType::R_PARENTHESIS => depth -= 1,
+         TokenType::EOF => break,
+         _ => {}
+      }
+   }
+
+   depth > 0
+}
+
+/// Installs a handler for the SIGINT signal (Ctrl-C) so that it interrupts the program being
+/// executed or the line being typed, instead of terminating the REPL.
+#[cfg(unix)]
+fn install_interrupt_handler() {
+   extern "C" fn handle_interrupt(_: libc::c_int) {
+      INTERRUPTED.store(true, Ordering::Relaxed);
+   }
+
+   // The handler is installed without the `SA_RESTART` flag so that
+   // reading from stdin is interrupted when Ctrl-C is pressed.
+   unsafe {
+      let mut action: libc::sigaction = std::mem::zeroed();
+      action.sa_sigaction = handle_interrupt as extern "C" fn(libc::c_int) as libc::sighandler_t;
+      libc::sigemptyset(&mut action.sa_mask);
+      libc::sigaction(libc::SIGINT, &action, std::ptr::null_mut());
+   }
+}
+
+#[cfg(not(unix))]
+fn install_interrupt_handler() {}
+
+/// Reads a line from stdin, one byte at a time, so that an interrupt signal
+/// received while waiting for the programmer's input can be detected.
+#[cfg(unix)]
+fn read_line() -> ReadLine {
+   let mut bytes: Vec<u8> = vec![];
+
+   loop {
+      let mut byte = 0u8;
+      let count = unsafe { libc::read(libc::STDIN_FILENO, &mut byte as *mut u8 as *mut libc::c_void, 1) };
+
+      if count < 0 && INTERRUPTED.swap(false, Ordering::Relaxed) {
+         return ReadLine::Interrupted;
+      } else if count < 0 {
+         // Reads that fail for other reasons (e.g., other signals) are retried.
+         continue;
+      } else if count == 0 && bytes.is_empty() {
+         return ReadLine::Eof;
+      }
+
+      if count > 0 {
+         bytes.push(byte);
+      }
+
+      if count == 0 || byte == b'\n' {
+         return ReadLine::Text(String::from_utf8_lossy(&bytes).into_owned());
+      }
+   }
+}
+
+/// Reads a line from stdin.
+#[cfg(not(unix))]
+fn read_line() -> ReadLine {
+   let mut line = String::new();
+
+   match std::io::stdin().read_line(&mut line) {
+      Ok(0) | Err(_) => ReadLine::Eof,
+      Ok(_) => ReadLine::Text(line),
+   }
+}
@@ -0,0 +1,223 @@
+use crate::built_in::BuiltIn;
+use crate::compiler::{Compiler, OptLevel, WarningMode};
+use crate::core::ast::ASTNode;
+use crate::errors::{paint, print_errors_list, print_warnings_list};
+use crate::parser::Parser;
+use crate::virtual_machine::{InterpretResult, OutputBuffer, VM};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// The prefix of the names of test functions.
+const TEST_PREFIX: &str = "test_";
+
+/// The options used to run the tests in a file.
+pub struct TestOptions<'a> {
+   /// Only the tests whose name contains this string are executed.
+   pub filter: Option<&'a str>,
+   /// Determines how the warnings found in the test files are handled.
+   pub warning_mode: WarningMode,
+   /// Determines which optimizations are applied to the test files.
+   pub opt_level: OptLevel,
+   /// The directories searched for the modules imported by the test files.
+   pub module_dirs: Vec<PathBuf>,
+}
+
+/// The results of running a set of tests.
+#[derive(Default)]
+pub struct TestSummary {
+   /// The number of tests that passed.
+   pub passed: usize,
+   /// The number of tests that failed, including the tests of files that could not be compiled.
+   pub failed: usize,
+   /// The number of tests that were skipped by the filter.
+   pub filtered: usize,
+   /// The total time spent running the tests.
+   pub duration: Duration,
+}
+
+impl TestSummary {
+   /// Checks if every test that was executed passed.
+   pub fn is_ok(&self) -> bool {
+      self.failed == 0
+   }
+
+   /// Prints the summary of the test results.
+   pub fn print(&self) {
+      println!(
+         "\ntest result: {}. {} passed; {} failed; {} filtered out; finished in {:.2?}",
+         paint(if self.is_ok() {
+            "\x1b[32mok\x1b[0m"
+         } else {
+            "\x1b[31mFAILED\x1b[0m"
+         }),
+         self.passed,
+         self.failed,
+         self.filtered,
+         self.duration
+      );
+   }
+}
+
+/// Finds the test functions declared at the top-level of a program. Test functions are
+/// functions whose name starts with `test_`, and which can be called without arguments.
+///
+/// # Parameters
+/// - `program`: The root node of the program's AST.
+///
+/// # Returns
+/// - `Vec<String>`: The names of the test functions, in declaration order.
+pub fn discover_tests(program: &ASTNode) -> Vec<String> {
+   let body = match program {
+      ASTNode::Module(m) => &m.body,
+      _ => return vec![],
+   };
+
+   body
+      .iter()
+      .filter_map(|node| match node {
+         ASTNode::FunctionDecl(f) => Some(f),
+         ASTNode::ExportDecl(e) => match &*e.decl {
+            ASTNode::FunctionDecl(f) => Some(f),
+            _ => None,
+         },
+         _ => None,
+      })
+      .filter(|f| f.name.lexeme.starts_with(TEST_PREFIX) && f.arity.0 == 0)
+      .map(|f| f.name.lexeme.to_string())
+      .collect()
+}
+
+/// Finds the Hinton source files at a path. If the path is a directory,
+/// its `.ht` files are collected recursively, in alphabetical order.
+///
+/// # Parameters
+/// - `path`: The path to a file or directory.
+pub fn collect_test_files(path: &Path) -> Vec<PathBuf> {
+   if !path.is_dir() {
+      return vec![path.to_path_buf()];
+   }
+
+   let mut entries: Vec<PathBuf> = match fs::read_dir(path) {
+      Ok(dir) => dir.filter_map(|e| e.ok()).map(|e| e.path()).collect(),
+      Err(_) => return vec![],
+   };
+   entries.sort();
+
+   entries
+      .into_iter()
+      .flat_map(|p| {
+         if p.is_dir() {
+            collect_test_files(&p)
+         } else if p.extension().is_some_and(|ext| ext == "ht") {
+            vec![p]
+         } else {
+            vec![]
+         }
+      })
+      .collect()
+}
+
+/// Runs the test functions of a Hinton source file. Each test runs in a fresh VM, where the
+/// top-level code of the file executes before the test function is called. A test passes if
+/// the test function returns without a runtime error (e.g., a failed assertion). The output
+/// printed by a test is captured, and only shown if the test fails.
+///
+/// # Parameters
+/// - `filepath`: The canonical path to the file.
+/// - `options`: The options used to run the tests.
+/// - `summary`: The summary where the results of the tests are added.
+pub fn run_test_file(filepath: &Path, options: &TestOptions, summary: &mut TestSummary) {
+   let source = match fs::read_to_string(filepath) {
+      Ok(src) => src,
+      Err(_) => {
+         eprintln!("Could not read test file '{}'.", filepath.display());
+         summary.failed += 1;
+         return;
+      }
+   };
+
+   let ast = match Parser::parse(&source) {
+      Ok(ast) => ast,
+      Err(e) => {
+         let _ = print_errors_list(&mut io::stderr(), filepath, &e, &source);
+         summary.failed += 1;
+         return;
+      }
+   };
+
+   // The file is compiled once before running its tests,
+   // so that its errors and warnings are only reported once.
+   match Compiler::compile_ast_with_warnings(
+      filepath,
+      &ast,
+      &BuiltIn::default(),
+      options.warning_mode,
+      options.opt_level,
+      &options.module_dirs,
+   ) {
+      Ok((_, warnings)) => {
+         let _ = print_warnings_list(&mut io::stderr(), filepath, &warnings, &source);
+      }
+      Err(e) => {
+         let _ = print_errors_list(&mut io::stderr(), filepath, &e, &source);
+         summary.failed += 1;
+         return;
+      }
+   }
+
+   let (tests, filtered): (Vec<String>, Vec<String>) = discover_tests(&ast)
+      .into_iter()
+      .partition(|name| options.filter.is_none_or(|f| name.contains(f)));
+   summary.filtered += filtered.len();
+
+   if tests.is_empty() {
+      return;
+   }
+
+   let plural = if tests.len() == 1 { "test" } else { "tests" };
+   println!("\nrunning {} {} in '{}'", tests.len(), plural, filepath.display());
+
+   for name in tests.iter() {
+      // The test function is called at the end of the file, so that the line
+      // numbers of the file's code are preserved in the error reports.
+      let program = format!("{}\n{}();\n", source, name);
+
+      // The output of the test is only shown if the test fails.
+      let output = OutputBuffer::new();
+      let mut vm = VM::new(filepath.to_path_buf())
+         .with_stdout(Box::new(output.clone()))
+         .with_stderr(Box::new(output.clone()))
+         .with_warning_mode(WarningMode::Silence)
+         .with_opt_level(options.opt_level)
+         .with_module_dirs(options.module_dirs.clone());
+
+      let start = Instant::now();
+      let result = vm.interpret(&program);
+      let elapsed = start.elapsed();
+      summary.duration += elapsed;
+
+      match result {
+         InterpretResult::Ok => {
+            println!(
+               "test {} ... {} ({:.2?})",
+               name,
+               paint("\x1b[32mok\x1b[0m"),
+               elapsed
+            );
+            summary.passed += 1;
+         }
+         _ => {
+            println!(
+               "test {} ... {} ({:.2?})",
+               name,
+               paint("\x1b[31mFAILED\x1b[0m"),
+               elapsed
+            );
+            print!("{}", output.contents());
+            summary.failed += 1;
+         }
+      }
+   }
+}
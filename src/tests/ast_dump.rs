@@ -0,0 +1,111 @@
+use crate::ast_dump::{dump_ast, syntax_tree, AstFormat, SyntaxNode};
+use crate::lsp::json::Json;
+
+fn tree(src: &str) -> SyntaxNode {
+   match syntax_tree(src) {
+      Ok(tree) => tree,
+      Err(_) => panic!("Source should be parsed without errors."),
+   }
+}
+
+fn text_of<'a>(src: &'a str, node: &SyntaxNode) -> &'a str {
+   &src[node.span.start..node.span.end]
+}
+
+#[test]
+fn dump_the_syntax_tree_as_text() {
+   let dump = match dump_ast("var x = 1 + f();", AstFormat::Text) {
+      Ok(dump) => dump,
+      Err(_) => panic!("Source should be parsed without errors."),
+   };
+
+   assert_eq!(
+      dump,
+      "Module [0..16]
+  body: VariableDecl [0..16] names=x
+    value: Binary [8..15] operator=+
+      left: Literal [8..9] value=1 type=Int
+      right: FunctionCall [12..15]
+        target: Identifier [12..13] name=f
+"
+   );
+}
+
+#[test]
+fn dump_the_syntax_tree_as_json() {
+   let dump = match dump_ast("print(-a);", AstFormat::Json) {
+      Ok(dump) => dump,
+      Err(_) => panic!("Source should be parsed without errors."),
+   };
+
+   let json = Json::parse(&dump).unwrap();
+   let statement = match json.get("children") {
+      Some(Json::Array(children)) => children[0].clone(),
+      _ => panic!("The module should have children."),
+   };
+
+   assert_eq!(json.get("kind").and_then(|k| k.as_str()), Some("Module"));
+   assert_eq!(statement.get("role").and_then(|r| r.as_str()), Some("body"));
+   assert_eq!(
+      statement.get("kind").and_then(|k| k.as_str()),
+      Some("ExpressionStmt")
+   );
+   assert_eq!(
+      statement.at(&["span", "end"]).and_then(|e| e.as_usize()),
+      Some(10)
+   );
+}
+
+#[test]
+fn spans_cover_the_source_of_each_node() {
+   let src =
+      "func add(a, b = 2) {\n   return (a + b) * 2;\n}\nclass P { pub var x = [1, 2]; }\nvar s = \"v${x}!\";";
+   let root = tree(src);
+   let body: Vec<&SyntaxNode> = root.children_with_role("body").collect();
+
+   assert_eq!(
+      text_of(src, body[0]),
+      "func add(a, b = 2) {\n   return (a + b) * 2;\n}"
+   );
+   assert_eq!(text_of(src, body[1]), "class P { pub var x = [1, 2]; }");
+
+   let ret = body[0].children_with_role("body").next().unwrap();
+   assert_eq!(text_of(src, ret), "return (a + b) * 2;");
+   assert_eq!(text_of(src, &ret.children[0].1), "(a + b) * 2");
+
+   let member = body[1].children_with_role("member").next().unwrap();
+   assert_eq!(text_of(src, member), "pub var x = [1, 2];");
+
+   let parts: Vec<&str> = body[2].children[0]
+      .1
+      .children_with_role("part")
+      .map(|p| text_of(src, p))
+      .collect();
+   assert_eq!(parts, vec!["\"v${", "x", "}!\""]);
+}
+
+#[test]
+fn do_not_dump_sources_with_syntax_errors() {
+   assert!(dump_ast("var x = ;", AstFormat::Text).is_err());
+}
+
+#[test]
+fn labeled_loops_span_their_labels() {
+   let src = "outer: do { break outer; } while x;";
+   let tree = tree(src);
+   let stmt = &tree.children[0].1;
+
+   assert_eq!(stmt.kind, "DoWhileStmt");
+   assert_eq!(text_of(src, stmt), src);
+   assert_eq!(stmt.children[0].0, "do");
+   assert_eq!(text_of(src, &stmt.children[0].1), "{ break outer; }");
+   assert_eq!(text_of(src, &stmt.children[0].1.children[0].1), "break outer;");
+}
+
+#[test]
+fn if_expressions_are_not_dumped_as_ternary_conditionals() {
+   let tree = tree("var s = if x { 'p' } else { 'n' }; var t = x ? 'p' : 'n';");
+
+   assert_eq!(tree.children[0].1.children[0].1.kind, "IfExpression");
+   assert_eq!(tree.children[1].1.children[0].1.kind, "TernaryConditional");
+}
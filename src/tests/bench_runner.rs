@@ -0,0 +1,34 @@
+use crate::bench_runner::{bench_file, BenchOptions};
+use crate::compiler::OptLevel;
+
+#[test]
+fn bench_files_and_skip_files_with_errors() {
+   let dir = std::env::temp_dir().join(format!("hinton_bench_runner_{}", std::process::id()));
+   std::fs::create_dir_all(&dir).unwrap();
+
+   let ok_path = dir.join("sum.ht");
+   std::fs::write(&ok_path, "var s = 0; for var i in 0..100 { s += i; } print(s);").unwrap();
+
+   let err_path = dir.join("fails.ht");
+   std::fs::write(&err_path, "var s = 0; s.missing();").unwrap();
+
+   let options = BenchOptions {
+      runs: 3,
+      opt_level: OptLevel::default(),
+      module_dirs: vec![],
+   };
+
+   let result = bench_file(&ok_path, &options);
+   let failed = bench_file(&err_path, &options);
+
+   std::fs::remove_dir_all(&dir).unwrap();
+
+   match result {
+      Some(r) if r.file == ok_path => {}
+      _ => panic!("Should measure each stage of a file without errors."),
+   }
+
+   if failed.is_some() {
+      panic!("Should not benchmark files whose execution ends in an error.")
+   }
+}
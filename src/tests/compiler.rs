@@ -1,6 +1,9 @@
 use std::path::PathBuf;
 
 use crate::built_in::BuiltIn;
+use crate::compiler::{OptLevel, WarningMode};
+use crate::core::bytecode::OpCode;
+use crate::objects::Object;
 use crate::{compiler::Compiler, parser::Parser};
 
 #[test]
@@ -152,6 +155,18 @@ fn error_if_return_outside_func() {
    }
 }
 
+#[test]
+fn error_if_defer_outside_func() {
+   let program = match Parser::parse("defer print(1);") {
+      Ok(ast) => ast,
+      Err(_) => panic!("Parser Had Errors."),
+   };
+
+   if Compiler::compile_ast(&PathBuf::new(), &program, &BuiltIn::default()).is_ok() {
+      panic!("Compiler should emit error when deferring outside of function.")
+   }
+}
+
 #[test]
 fn allow_return_inside_loop_inside_func() {
    let program = match Parser::parse("func my_func(x) { while x { return false; } }") {
@@ -203,3 +218,515 @@ fn cannot_return_from_init() {
       panic!("Compiler should emit error if program returns from class initializer.")
    }
 }
+
+#[test]
+fn catch_variable_is_scoped_to_catch_block() {
+   let program = match Parser::parse("try { 1 / 0; } catch (err) { print(err); } print(err);") {
+      Ok(ast) => ast,
+      Err(_) => panic!("Parser Had Errors."),
+   };
+
+   if Compiler::compile_ast(&PathBuf::new(), &program, &BuiltIn::default()).is_ok() {
+      panic!("Caught error should not be accessible outside of the catch block.")
+   }
+}
+
+#[test]
+fn cannot_import_missing_module() {
+   let program = match Parser::parse("import x from \"./this/module/does/not/exist\";") {
+      Ok(ast) => ast,
+      Err(_) => panic!("Parser Had Errors."),
+   };
+
+   if Compiler::compile_ast(&PathBuf::new(), &program, &BuiltIn::default()).is_ok() {
+      panic!("Compiler should emit error if the imported module does not exist.")
+   }
+}
+
+#[test]
+fn cannot_export_from_inner_scope() {
+   let program = match Parser::parse("{ export var x = 1; }") {
+      Ok(ast) => ast,
+      Err(_) => panic!("Parser Had Errors."),
+   };
+
+   if Compiler::compile_ast(&PathBuf::new(), &program, &BuiltIn::default()).is_ok() {
+      panic!("Compiler should emit error if exports are declared in an inner scope.")
+   }
+}
+
+#[test]
+fn constants_can_be_referenced_after_initialization() {
+   let program = match Parser::parse("const a = 1; print(a); { const b = 2; print(b); }") {
+      Ok(ast) => ast,
+      Err(_) => panic!("Parser Had Errors."),
+   };
+
+   if Compiler::compile_ast(&PathBuf::new(), &program, &BuiltIn::default()).is_err() {
+      panic!("Constants should be accessible after they have been initialized.")
+   }
+}
+
+#[test]
+fn constant_expressions_can_be_referenced_before_their_declaration() {
+   for src in [
+      "func area(r) { return PI * r * r; } const PI = 3.14;",
+      "print(TAU); const TAU = PI * 2; const PI = 3.14;",
+      "const GREETING = 'Hi ' + NAME; const NAME = 'Ada';",
+   ] {
+      let program = match Parser::parse(src) {
+         Ok(ast) => ast,
+         Err(_) => panic!("Parser Had Errors."),
+      };
+
+      if Compiler::compile_ast(&PathBuf::new(), &program, &BuiltIn::default()).is_err() {
+         panic!("Constant '{}' should be evaluated at compile time.", src)
+      }
+   }
+
+   for src in [
+      "print(NOW); const NOW = clock();",
+      "const A = B; const B = A;",
+      "PI = 3; const PI = 3.14;",
+      "func f() { PI += 1; } const PI = 3.14;",
+   ] {
+      let program = match Parser::parse(src) {
+         Ok(ast) => ast,
+         Err(_) => panic!("Parser Had Errors."),
+      };
+
+      if Compiler::compile_ast(&PathBuf::new(), &program, &BuiltIn::default()).is_ok() {
+         panic!("Should not allow '{}'.", src)
+      }
+   }
+}
+
+#[test]
+fn repl_inputs_share_global_declarations() {
+   let mut compiler = Compiler::new_repl(&PathBuf::new(), &BuiltIn::default());
+
+   for (src, should_compile) in [
+      ("var x = 1;", true),
+      ("x + 1;", true),
+      ("var y = z;", false),
+      ("y;", false),
+   ] {
+      let program = match Parser::parse(src) {
+         Ok(ast) => ast,
+         Err(_) => panic!("Parser Had Errors."),
+      };
+
+      if compiler.compile_repl_input(&program).is_ok() != should_compile {
+         panic!("REPL inputs should only see the globals declared by previous valid inputs.")
+      }
+   }
+}
+
+#[test]
+fn warns_about_suspicious_code() {
+   let src = "
+      func f(a) {
+         var unused = 1;
+         if (true) { print(a); }
+         for var i in 0..3 { { var i = 2; print(i); } }
+         return a;
+         print(a);
+      }
+      print(f(1));
+   ";
+
+   let program = match Parser::parse(src) {
+      Ok(ast) => ast,
+      Err(_) => panic!("Parser Had Errors."),
+   };
+
+   let warnings = match Compiler::compile_ast_with_warnings(
+      &PathBuf::new(),
+      &program,
+      &BuiltIn::default(),
+      WarningMode::Show,
+      OptLevel::default(),
+      &[],
+   ) {
+      Ok((_, warnings)) => warnings,
+      Err(_) => panic!("Compiler Had Errors."),
+   };
+
+   let lines: Vec<usize> = warnings.iter().map(|w| w.line).collect();
+   // The outer `i` on line 5 is both shadowed and never used.
+   if lines != vec![3, 4, 5, 5, 6] {
+      panic!("Compiler should warn about unused, constant, shadowed, and unreachable code.")
+   }
+}
+
+#[test]
+fn warn_about_matches_that_miss_variants_of_an_enum() {
+   let src = "
+      enum Shape { Circle(r), Rect(w, h), Empty }
+      var s = Shape.Empty;
+      match s { Shape.Circle(_) => print(1), Shape.Rect(1, h) => print(h), }
+      match s { Shape.Circle(_) => print(1), Shape.Rect(w, _) => print(w), Shape.Empty => print(0) }
+      match s { Shape.Circle(_) => print(1), else => print(0) }
+   ";
+
+   let program = match Parser::parse(src) {
+      Ok(ast) => ast,
+      Err(_) => panic!("Parser Had Errors."),
+   };
+
+   let warnings = match Compiler::compile_ast_with_warnings(
+      &PathBuf::new(),
+      &program,
+      &BuiltIn::default(),
+      WarningMode::Show,
+      OptLevel::default(),
+      &[],
+   ) {
+      Ok((_, warnings)) => warnings,
+      Err(_) => panic!("Compiler Had Errors."),
+   };
+
+   // `Shape.Rect(1, h)` does not cover every value of the `Rect` variant.
+   match warnings.as_slice() {
+      [w] if w.line == 4 && w.message.contains("'Shape.Rect', 'Shape.Empty'") => {}
+      _ => panic!("Compiler should warn about the variants not covered by a match."),
+   }
+}
+
+#[test]
+fn denied_warnings_are_reported_as_errors() {
+   let program = match Parser::parse("func f() { var x = 1; }") {
+      Ok(ast) => ast,
+      Err(_) => panic!("Parser Had Errors."),
+   };
+
+   let built_in = BuiltIn::default();
+   let path = PathBuf::new();
+
+   if Compiler::compile_ast_with_warnings(
+      &path,
+      &program,
+      &built_in,
+      WarningMode::Deny,
+      OptLevel::default(),
+      &[],
+   )
+   .is_ok()
+   {
+      panic!("Compiler should emit errors for warnings when warnings are denied.")
+   }
+
+   match Compiler::compile_ast_with_warnings(
+      &path,
+      &program,
+      &built_in,
+      WarningMode::Silence,
+      OptLevel::default(),
+      &[],
+   ) {
+      Ok((_, warnings)) if warnings.is_empty() => {}
+      _ => panic!("Compiler should not report warnings when warnings are silenced."),
+   }
+}
+
+#[test]
+fn well_typed_annotations_compile() {
+   let src = "
+      var x: Int = 5;
+      var y: Float = x;
+      var later: String;
+      class Point { var px: Int = 0; }
+      var p: Point = new Point();
+      func greet(who: String, times: Int := 1) -> String { return 'Hi ' + who; }
+      var msg: String = greet('Ana', 2);
+      var double = fn (a: Int) -> Int { return a * 2; };
+   ";
+
+   let program = match Parser::parse(src) {
+      Ok(ast) => ast,
+      Err(_) => panic!("Parser Had Errors."),
+   };
+
+   if Compiler::compile_ast(&PathBuf::new(), &program, &BuiltIn::default()).is_err() {
+      panic!("Compiler should allow values that match their type annotations.")
+   }
+}
+
+#[test]
+fn mistyped_annotations_are_compile_errors() {
+   for src in [
+      "var x: Int = 'five';",
+      "const c: Bool = 1 + 2;",
+      "func f(a: String) -> Bool { return a; }",
+      "func f(a: String) {} f(10);",
+      "var x: Int = 1; x = 2.5;",
+      "var x: Banana = 1;",
+      "func f() -> Void { return 1; }",
+   ] {
+      let program = match Parser::parse(src) {
+         Ok(ast) => ast,
+         Err(_) => panic!("Parser Had Errors."),
+      };
+
+      if Compiler::compile_ast(&PathBuf::new(), &program, &BuiltIn::default()).is_ok() {
+         panic!("Compiler should emit a type error for '{}'.", src)
+      }
+   }
+}
+
+#[test]
+fn optimizations_fold_constants_and_remove_dead_code() {
+   let chunk_size = |src: &str, opt_level: OptLevel| {
+      let program = match Parser::parse(src) {
+         Ok(ast) => ast,
+         Err(_) => panic!("Parser Had Errors."),
+      };
+
+      match Compiler::compile_ast_with_warnings(
+         &PathBuf::new(),
+         &program,
+         &BuiltIn::default(),
+         WarningMode::Silence,
+         opt_level,
+         &[],
+      ) {
+         Ok((res, _)) => res.chunk.len(),
+         Err(_) => panic!("Compiler Had Errors."),
+      }
+   };
+
+   // An expression statement is a `LOAD_IMM_N 7`, a `POP_STACK_TOP`, and the `END_VIRTUAL_MACHINE`.
+   for src in [
+      "1 + 2 * 3;",
+      "-(-7);",
+      "(3 > 2 and 7) or 5;",
+      "10 - (true ? 3 : 4);",
+   ] {
+      if chunk_size(src, OptLevel::O1) != 4 {
+         panic!("Compiler should fold '{}' into a constant.", src)
+      }
+
+      if chunk_size(src, OptLevel::O0) <= 4 {
+         panic!("Compiler should not fold '{}' without optimizations.", src)
+      }
+   }
+
+   // Only the `END_VIRTUAL_MACHINE` instruction is emitted for branches that never execute.
+   for src in ["if 1 > 2 { print(1); }", "while 2 < 1 { print(1); }"] {
+      if chunk_size(src, OptLevel::O1) != 1 {
+         panic!("Compiler should remove the branch in '{}'.", src)
+      }
+   }
+
+   let src = "var x = 1; while x { break; print(2); print(3); }";
+   if chunk_size(src, OptLevel::O1) >= chunk_size(src, OptLevel::O0) {
+      panic!("Compiler should remove the code after a 'break' statement.")
+   }
+
+   // Operations that fail at runtime are not folded.
+   if chunk_size("1 / 0;", OptLevel::O1) != chunk_size("1 / 0;", OptLevel::O0) {
+      panic!("Compiler should not fold operations that raise runtime errors.")
+   }
+}
+
+#[test]
+fn error_if_super_without_superclass() {
+   for src in [
+      "func f() { return super.speak(); }",
+      "class A { pub func speak() { return super.speak(); } }",
+      "class A extends A {}",
+   ] {
+      let program = match Parser::parse(src) {
+         Ok(ast) => ast,
+         Err(_) => panic!("Parser Had Errors."),
+      };
+
+      if Compiler::compile_ast(&PathBuf::new(), &program, &BuiltIn::default()).is_ok() {
+         panic!(
+            "Compiler should emit error for invalid superclass usage in '{}'.",
+            src
+         )
+      }
+   }
+}
+
+#[test]
+fn error_if_class_does_not_implement_interface() {
+   for src in [
+      "interface I { func m(a); } class A implements I {}",
+      "interface I { func m(a); } class A implements I { func m(a) {} }",
+      "interface I { func m(a); } class A implements I { pub func m() {} }",
+      "interface I { func m(a); } class A implements I { pub var m = 1; }",
+   ] {
+      let program = match Parser::parse(src) {
+         Ok(ast) => ast,
+         Err(_) => panic!("Parser Had Errors."),
+      };
+
+      if Compiler::compile_ast(&PathBuf::new(), &program, &BuiltIn::default()).is_ok() {
+         panic!(
+            "Compiler should emit error for unimplemented interface in '{}'.",
+            src
+         )
+      }
+   }
+}
+
+#[test]
+fn error_if_is_without_type_name() {
+   for src in ["5 is 5;", "5 is 'Int';", "5 is (fn () {})();"] {
+      let program = match Parser::parse(src) {
+         Ok(ast) => ast,
+         Err(_) => panic!("Parser Had Errors."),
+      };
+
+      if Compiler::compile_ast(&PathBuf::new(), &program, &BuiltIn::default()).is_ok() {
+         panic!("Compiler should emit error for invalid 'is' type in '{}'.", src)
+      }
+   }
+}
+
+#[test]
+fn error_if_self_in_static_method() {
+   for src in [
+      "class A { pub static func f() { return self; } }",
+      "class A { pub static func f() { return fn () { return self; }; } }",
+      "class B {} class A extends B { pub static func f() { return super.f(); } }",
+   ] {
+      let program = match Parser::parse(src) {
+         Ok(ast) => ast,
+         Err(_) => panic!("Parser Had Errors."),
+      };
+
+      if Compiler::compile_ast(&PathBuf::new(), &program, &BuiltIn::default()).is_ok() {
+         panic!(
+            "Compiler should emit error for instance access in static method in '{}'.",
+            src
+         )
+      }
+   }
+}
+
+#[test]
+fn error_if_break_value_is_not_in_loop_expression() {
+   for src in [
+      "while true { break 1; }",
+      "var x = loop { for var i in [] { break i; } };",
+      "class A { var x = loop { break 1; }; }",
+   ] {
+      let program = match Parser::parse(src) {
+         Ok(ast) => ast,
+         Err(_) => panic!("Parser Had Errors."),
+      };
+
+      if Compiler::compile_ast(&PathBuf::new(), &program, &BuiltIn::default()).is_ok() {
+         panic!(
+            "Compiler should emit error for invalid loop expression in '{}'.",
+            src
+         )
+      }
+   }
+}
+
+#[test]
+fn error_if_loop_label_is_undeclared_or_duplicated() {
+   for src in [
+      "loop { break outer; }",
+      "outer: while true { continue inner; }",
+      "outer: loop { var f = fn () { break outer; }; break; }",
+      "outer: loop { outer: for var x in [] { break outer; } }",
+   ] {
+      let program = match Parser::parse(src) {
+         Ok(ast) => ast,
+         Err(_) => panic!("Parser Had Errors."),
+      };
+
+      if Compiler::compile_ast(&PathBuf::new(), &program, &BuiltIn::default()).is_ok() {
+         panic!("Compiler should emit error for invalid loop label in '{}'.", src)
+      }
+   }
+}
+
+#[test]
+fn globals_are_accessed_by_slot() {
+   let program = match Parser::parse("var a = 1; func f() { return a; } var (b, c) = (2, 3); a = b + c;") {
+      Ok(ast) => ast,
+      Err(_) => panic!("Parser Had Errors."),
+   };
+
+   match Compiler::compile_ast(&PathBuf::new(), &program, &BuiltIn::default()) {
+      Ok(res) => {
+         if res.globals != ["a", "f", "b", "c"] {
+            panic!(
+               "Expected the globals in declaration order. Got {:?}.",
+               res.globals
+            )
+         }
+
+         // The names of the globals are not needed at runtime.
+         if res
+            .chunk
+            .get_constants()
+            .iter()
+            .any(|c| matches!(c, Object::String(_)))
+         {
+            panic!("The names of the globals should not be added to the constant pool.")
+         }
+      }
+      Err(_) => panic!("Compiler Had Errors."),
+   }
+}
+
+#[test]
+fn superinstructions_are_fused_only_with_optimizations() {
+   let op_codes = |src: &str, opt_level: OptLevel| {
+      let program = match Parser::parse(src) {
+         Ok(ast) => ast,
+         Err(_) => panic!("Parser Had Errors."),
+      };
+
+      match Compiler::compile_ast_with_warnings(
+         &PathBuf::new(),
+         &program,
+         &BuiltIn::default(),
+         WarningMode::Silence,
+         opt_level,
+         &[],
+      ) {
+         Ok((res, _)) => {
+            let mut op_codes = vec![];
+            let mut idx = 0;
+
+            while idx < res.chunk.len() {
+               op_codes.push(res.chunk.get_op_code(idx));
+               idx += res.chunk.instruction_len(idx);
+            }
+
+            op_codes
+         }
+         Err(_) => panic!("Compiler Had Errors."),
+      }
+   };
+
+   for (src, op_code) in [
+      ("var x = 5; x + 1;", OpCode::LoadImm1IAdd),
+      ("var x = 5; x + 20;", OpCode::LoadImmNAdd),
+      ("var x = 5; x + 0.5;", OpCode::LoadConstantAdd),
+   ] {
+      if !op_codes(src, OptLevel::O1).contains(&op_code) {
+         panic!("Compiler should fuse the instructions in '{}'.", src)
+      }
+
+      if op_codes(src, OptLevel::O0).contains(&op_code) {
+         panic!(
+            "Compiler should not fuse the instructions in '{}' without optimizations.",
+            src
+         )
+      }
+   }
+
+   // Sequences that span several lines are not fused.
+   if op_codes("var x = 5;\nx +\n1;", OptLevel::O1).contains(&OpCode::LoadImm1IAdd) {
+      panic!("Compiler should not fuse instructions from different lines.")
+   }
+}
@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+
+use crate::built_in::BuiltIn;
+use crate::core::disassembler::disassemble_instruction;
+use crate::objects::FuncObject;
+use crate::{compiler::Compiler, parser::Parser};
+
+/// Compiles a program and disassembles every instruction of its top-level chunk.
+fn disassemble_source(src: &str) -> Vec<String> {
+   let program = match Parser::parse(src) {
+      Ok(ast) => ast,
+      Err(_) => panic!("Parser Had Errors."),
+   };
+
+   let built_in = BuiltIn::default();
+   let module: FuncObject = match Compiler::compile_ast(&PathBuf::new(), &program, &built_in) {
+      Ok(res) => res,
+      Err(_) => panic!("Compiler Had Errors."),
+   };
+
+   let natives = built_in.natives.get_names();
+   let primitives = built_in.primitives.get_names();
+
+   let mut lines = vec![];
+   let mut idx = 0;
+   while idx < module.chunk.len() {
+      let (text, next_idx) = disassemble_instruction(&module.chunk, idx, &natives, &primitives);
+      lines.push(text);
+      idx = next_idx;
+   }
+
+   lines
+}
+
+#[test]
+fn disassembles_make_instance_with_its_arg_count() {
+   let src = "class A { pub func init(x, y) {} } var a = new A(1, 2); var b = new A(3, 4);";
+   let lines = disassemble_source(src);
+
+   let instances: Vec<&String> = lines.iter().filter(|l| l.contains("MAKE_INSTANCE")).collect();
+   assert_eq!(instances.len(), 2);
+
+   for line in instances {
+      assert!(line.trim_end().ends_with('2'), "Unexpected operand: {}", line);
+   }
+}
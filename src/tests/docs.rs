@@ -0,0 +1,101 @@
+use std::path::PathBuf;
+
+use crate::docs::render_module_docs;
+use crate::virtual_machine::{InterpretResult, OutputBuffer, VM};
+
+fn docs(src: &str) -> String {
+   match render_module_docs(src, "shapes") {
+      Ok(docs) => docs,
+      Err(_) => panic!("Source should be documented without errors."),
+   }
+}
+
+fn output_of(src: &str) -> String {
+   let stdout = OutputBuffer::new();
+   let result = VM::new(PathBuf::new())
+      .with_stdout(Box::new(stdout.clone()))
+      .interpret(src);
+
+   if !matches!(result, InterpretResult::Ok) {
+      panic!("Program Had Errors.")
+   }
+
+   stdout.take()
+}
+
+#[test]
+fn render_the_docs_of_exported_declarations() {
+   let src = "
+      /// Computes the area of a circle.
+      /// The radius must be positive.
+      export func area(radius: Float, scale = 1) -> Float { return 3.14 * radius * radius; }
+
+      /// Not exported, so not documented.
+      func helper() {}
+
+      /// A shape with sides.
+      export class Polygon {
+         /// Counts the sides of the polygon.
+         pub func sides() { return 0; }
+         /// Private methods are not documented.
+         func secret() {}
+      }
+   ";
+
+   assert_eq!(
+      docs(src),
+      "# Module `shapes`
+
+## `func area(radius: Float, scale = ...) -> Float`
+
+Computes the area of a circle.
+The radius must be positive.
+
+## `class Polygon`
+
+A shape with sides.
+
+### `func sides()`
+
+Counts the sides of the polygon.
+"
+   );
+}
+
+#[test]
+fn doc_comments_only_document_the_next_declaration() {
+   let src = "
+      /// Documents a variable, which is not documented.
+      var x = 1;
+      export func f() {}
+
+      //// Four slashes make a regular comment.
+      export func g() {}
+   ";
+
+   assert_eq!(docs(src), "# Module `shapes`\n\n## `func f()`\n\n## `func g()`\n");
+}
+
+#[test]
+fn help_prints_the_docs_of_functions_and_classes() {
+   let src = "
+      /// Greets someone.
+      func greet(name, ...rest) { return 'Hi ${name}'; }
+
+      class Greeter {
+         /// Says hello.
+         pub func hello(greeting = 'Hello') {}
+      }
+
+      help(greet);
+      help(Greeter);
+      help(1);
+   ";
+
+   assert_eq!(
+      output_of(src),
+      "func greet(name, ...rest)\n\nGreets someone.\n\
+       class Greeter\n\nfunc hello(greeting = ...)\n\nSays hello.\n\
+       No documentation for values of type 'Int'.\n"
+   );
+}
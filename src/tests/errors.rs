@@ -0,0 +1,89 @@
+use std::path::{Path, PathBuf};
+
+use crate::errors::{
+   print_errors_list, report_runtime_error, strip_ansi_codes, ErrorReport, RuntimeErrorType,
+};
+use crate::virtual_machine::VM;
+
+/// Renders the diagnostics of a list of errors, without ANSI escape sequences.
+fn render(errors: &[ErrorReport], source: &str) -> String {
+   let mut out = vec![];
+   print_errors_list(&mut out, Path::new("main.ht"), errors, source).unwrap();
+   strip_ansi_codes(&String::from_utf8(out).unwrap())
+}
+
+/// Creates an error report for the given span.
+fn report(start: (usize, usize), end: (usize, usize)) -> ErrorReport {
+   ErrorReport {
+      line: start.0,
+      column: start.1,
+      end_line: end.0,
+      end_column: end.1,
+      message: String::from("Error"),
+   }
+}
+
+#[test]
+fn align_markers_in_lines_with_tabs_and_crlf_endings() {
+   let source = "var a = 1;\r\n\tvar b =\t;\r\n";
+   let rendered = render(&[report((2, 9), (2, 10))], source);
+
+   if !rendered.contains(" 2 | var b =    ;\n   |            ^\n") {
+      panic!(
+         "Should expand tabs and remove carriage returns. Got:\n{}",
+         rendered
+      )
+   }
+}
+
+#[test]
+fn render_errors_with_positions_outside_of_the_source() {
+   let source = "var a = 1;\nprint(a);";
+
+   for (start, end) in [
+      ((0, 0), (0, 0)),
+      ((1, 500), (1, 600)),
+      ((99, 3), (99, 4)),
+      ((2, 4), (1, 0)),
+      ((1, 40), (2, 90)),
+      ((1, 3), (50, 0)),
+   ] {
+      let rendered = render(&[report(start, end)], source);
+
+      if !rendered.starts_with("Error\n") || !rendered.contains("---> File 'main.ht'.") {
+         panic!("Should render a best-effort snippet. Got:\n{}", rendered)
+      }
+   }
+
+   if !render(&[report((1, 500), (1, 600))], source).contains(" 1 | var a = 1;\n   |           ^\n") {
+      panic!("Columns past the end of a line should point right after the line.")
+   }
+}
+
+#[test]
+fn report_runtime_errors_of_programs_that_have_not_started() {
+   let mut out = vec![];
+   let vm = VM::new(PathBuf::new());
+
+   report_runtime_error(
+      &mut out,
+      &vm,
+      RuntimeErrorType::Internal,
+      String::from("Failed."),
+      "",
+   )
+   .unwrap();
+
+   if !strip_ansi_codes(&String::from_utf8(out).unwrap()).starts_with("InternalError[E0013]: Failed.") {
+      panic!("Should report errors without a source location.")
+   }
+}
+
+#[test]
+fn stripping_ansi_codes_only_removes_whole_control_sequences() {
+   let text = "\x1b[38;5;81mcolored\x1b[0m \x1b[2Jclear screen, then some more text";
+   assert_eq!(
+      strip_ansi_codes(text),
+      "colored clear screen, then some more text"
+   );
+}
@@ -0,0 +1,29 @@
+use crate::explain::{error_codes, error_name, explanation, normalize_code};
+use hashbrown::HashSet;
+
+#[test]
+fn every_error_code_is_unique_and_explained() {
+   let codes = error_codes();
+   let unique: HashSet<&str> = codes.iter().map(|(code, _)| *code).collect();
+   assert_eq!(unique.len(), codes.len());
+
+   for (code, name) in codes {
+      assert_eq!(error_name(code), Some(name));
+
+      match explanation(code) {
+         Some(text) if text.contains("Fix:") => {}
+         _ => panic!("Error code '{}' should be explained with a fix.", code),
+      }
+   }
+
+   assert_eq!(error_name("E0000"), None);
+   assert_eq!(explanation("E9999"), None);
+}
+
+#[test]
+fn normalize_error_codes() {
+   assert_eq!(normalize_code("E0012"), "E0012");
+   assert_eq!(normalize_code("e12"), "E0012");
+   assert_eq!(normalize_code("21"), "E0021");
+   assert_eq!(normalize_code("EX"), "EX");
+}
@@ -0,0 +1,117 @@
+use std::path::PathBuf;
+
+use crate::formatter::format_source;
+use crate::virtual_machine::{InterpretResult, OutputBuffer, VM};
+
+fn format(src: &str) -> String {
+   match format_source(src) {
+      Ok(formatted) => formatted,
+      Err(_) => panic!("Source should be formatted without errors."),
+   }
+}
+
+fn output_of(src: &str) -> String {
+   let stdout = OutputBuffer::new();
+   let result = VM::new(PathBuf::new())
+      .with_stdout(Box::new(stdout.clone()))
+      .interpret(src);
+
+   if !matches!(result, InterpretResult::Ok) {
+      panic!("Program Had Errors.")
+   }
+
+   stdout.take()
+}
+
+#[test]
+fn format_with_canonical_indentation_and_spacing() {
+   let src = "func add(a,b=2){return a+b;}\nvar x=add( 1 );\nwhile(x<5) x+=1;\n";
+
+   assert_eq!(
+      format(src),
+      "func add(a, b = 2) {\n    return a + b;\n}\nvar x = add(1);\nwhile x < 5 {\n    x += 1;\n}\n"
+   );
+}
+
+#[test]
+fn formatting_preserves_comments() {
+   let src = "// Header.\n\nvar x = 1; // One.\n\nfunc f() {\n  /* Empty. */\n}\n";
+
+   assert_eq!(format(src), src.replace("  /*", "    /*"));
+}
+
+#[test]
+fn formatting_preserves_the_meaning_of_programs() {
+   let src = "
+      var a = [(-2) ** 2, -2 ** 2, (!true) and false, !(true and false), 5 - (3 - 1)];
+      var b = [2 ** (3 ** 2), (1 | 2) & 3, - -5, (1 < 2 ? 3 : 4) + 1, ({a: 1}).a];
+      var c = [1 < 2 <= 2, 3 > 2 > 2, (1 < 2) is Bool, 1 < 2 is Bool];
+      for var k, v in {x: 1} { print([k, v]); }
+      match a[0] { 1, 2 => print('small'), else => { print('large'); } }
+      match b { [_, {a: 1..3, b}] if b => print(b), [x, _, _, _, 1] => print(x), _ => print(a) }
+      var d = {a: {b: {c: 2}}, n: null};
+      print([d?.a.b.c, d.n?.b.c, (d.n?.b)?.c, d!!.a, (d?.a)!!.b, !!d.n, -d.a?.b.c]);
+      enum E { A(x, y), B }
+      match E.A(1, 2) { E.A(1, y) => print(y), E.A(_, _) => print(0), E.B => print(1) }
+      print([(|x, y| x * y)(2, 3), a.map(|x| x + 1), a.filter { it > 0 }, (|| 7)()]);
+      if (a.any { it > 3 }) { a.each { print(it); } }
+      var q = [7~/2, -7.5 ~/ 2, 1 + 6 ~/ 4, (1 + 6) ~/ 4]; q[0] ~/= 2; print(q);
+      for var ch in 'a\\u{e9}' { print([ch, c'\\n'.code(), c'\\'' < c'z', ch + c'!']); }
+      var w = 0; do { w += 1; } while (w < 3); loop { w -= 1; if w == 0 { break; } }
+      outer: for var i in 0..3 { for var j in 0..3 { if j > i { continue outer; } if i == 2 { break outer; } print([i, j]); } }
+      var found = search: loop { for var i in 0..5 { if i * i > 5 { break search i; } } break; }; print(found);
+      for var x in (10..=0).step(5) { print([x, x in 0 ..= 10, (0..10).reverse().len()]); }
+      print([x * x for x in 0..10 if x mod 2 equals 0]); print({'k' + x.to_string(): x for x in 0..3});
+      func div_mod(a, b) { return a ~/ b, a mod b; } var quo, rem = div_mod(17, 5); print([quo, rem]);
+      print(a); print(b); print(c);
+   ";
+
+   let formatted = format(src);
+
+   assert_eq!(output_of(&formatted), output_of(src));
+   assert_eq!(format(&formatted), formatted);
+}
+
+#[test]
+fn wrap_long_lists() {
+   let items: Vec<String> = (0..30).map(|i| format!("item_{}", i)).collect();
+   let src = format!("var x = [{}];", items.join(","));
+   let formatted = format(&src);
+
+   assert!(formatted.starts_with("var x = [\n    item_0,\n    item_1,\n"));
+   assert!(formatted.ends_with("    item_29\n];\n"));
+}
+
+#[test]
+fn do_not_format_sources_with_syntax_errors() {
+   assert!(format_source("var x = ;").is_err());
+}
+
+#[test]
+fn wrap_lists_that_only_overflow_after_the_text_before_them() {
+   let items: Vec<String> = (0..11).map(|i| format!("item_{}", i)).collect();
+   let src = format!(
+      "var a_long_variable_name_for_the_items = [{}];\nfunc f() {{ return some_function({}); }}",
+      items.join(", "),
+      items.join(", ")
+   );
+   let formatted = format(&src);
+
+   // The lists fit in the line on their own, but not after the assignment and the `return`.
+   assert!(formatted.starts_with("var a_long_variable_name_for_the_items = [\n    item_0,\n"));
+   assert!(formatted.contains("    return some_function(\n        item_0,\n"));
+}
+
+#[test]
+fn format_if_expressions_as_they_are_written() {
+   let src = "var s = if x > 0 { 'p' } else if x < 0 { 'n' } else { 'z' };\nvar t = x > 0 ? 'p' : 'n';\n";
+   assert_eq!(format(src), src);
+
+   // An expression statement that starts with an `if` expression would be parsed as an `if` statement.
+   let src = "(if x { 1 } else { 2 }.to_string());\n";
+   assert_eq!(format(src), src);
+   assert_eq!(
+      format("var s = if x>0{'p'}else{'n'};"),
+      "var s = if x > 0 { 'p' } else { 'n' };\n"
+   );
+}
@@ -0,0 +1,108 @@
+use std::panic;
+
+use crate::fuzz::{fuzz_bytecode, fuzz_lexer, fuzz_parser, fuzz_vm};
+
+/// Fragments of Hinton code that are spliced into the mutated programs.
+const FRAGMENTS: [&str; 24] = [
+   "(", ")", "[", "]", "{", "}", "'", "\"", "${", "/*", "*/", "//", ";", ",", ".", "..", "?", ":", "fn",
+   "class", "func", "return", "\n", "\\",
+];
+
+/// A small, deterministic pseudo-random number generator (xorshift64).
+struct Rng(u64);
+
+impl Rng {
+   fn next(&mut self, bound: usize) -> usize {
+      self.0 ^= self.0 << 13;
+      self.0 ^= self.0 >> 7;
+      self.0 ^= self.0 << 17;
+      (self.0 % bound.max(1) as u64) as usize
+   }
+}
+
+/// Mutates the bytes of an input by flipping, deleting, and inserting bytes and fragments.
+fn mutate(rng: &mut Rng, input: &[u8]) -> Vec<u8> {
+   let mut bytes = input.to_vec();
+
+   for _ in 0..=rng.next(4) {
+      let pos = rng.next(bytes.len() + 1);
+
+      match rng.next(4) {
+         0 if pos < bytes.len() => bytes[pos] = rng.next(256) as u8,
+         1 if pos < bytes.len() => {
+            let end = (pos + rng.next(16)).min(bytes.len());
+            bytes.drain(pos..end);
+         }
+         2 => bytes
+            .splice(pos..pos, FRAGMENTS[rng.next(FRAGMENTS.len())].bytes())
+            .for_each(drop),
+         _ => bytes.truncate(pos),
+      }
+   }
+
+   bytes
+}
+
+/// Runs a fuzz target with mutations of the seed inputs, and reports the inputs that panic.
+/// The number of iterations is multiplied by the `HINTON_FUZZ_SCALE` environment variable.
+fn fuzz(seeds: &[Vec<u8>], iterations: usize, target: fn(&[u8])) {
+   let scale: usize = std::env::var("HINTON_FUZZ_SCALE")
+      .ok()
+      .and_then(|s| s.parse().ok())
+      .unwrap_or(1);
+   let iterations = iterations * scale;
+   let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+   let hook = panic::take_hook();
+   panic::set_hook(Box::new(|_| {}));
+
+   let mut crashes = vec![];
+   for i in 0..iterations {
+      let input = mutate(&mut rng, &seeds[i % seeds.len()]);
+
+      if panic::catch_unwind(|| target(&input)).is_err() {
+         crashes.push(String::from_utf8_lossy(&input).into_owned());
+      }
+   }
+
+   panic::set_hook(hook);
+
+   if let Some(input) = crashes.first() {
+      panic!(
+         "{} inputs crashed the target. The first one was:\n{}",
+         crashes.len(),
+         input
+      )
+   }
+}
+
+/// Gets the programs in the `tests/programs/` directory.
+fn seed_programs() -> Vec<Vec<u8>> {
+   let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/programs");
+   crate::test_runner::collect_test_files(&dir)
+      .iter()
+      .filter_map(|path| std::fs::read(path).ok())
+      .collect()
+}
+
+#[test]
+fn fuzz_the_lexer_and_parser() {
+   let seeds = seed_programs();
+   fuzz(&seeds, 500, fuzz_lexer);
+   fuzz(&seeds, 500, fuzz_parser);
+}
+
+#[test]
+fn fuzz_the_bytecode_loader() {
+   let seeds: Vec<Vec<u8>> = seed_programs()
+      .iter()
+      .filter_map(|src| crate::compile(&String::from_utf8_lossy(src)).ok())
+      .filter_map(|module| crate::core::serialization::serialize(&module).ok())
+      .collect();
+
+   fuzz(&seeds, 500, fuzz_bytecode);
+}
+
+#[test]
+fn fuzz_the_vm() {
+   fuzz(&seed_programs(), 200, fuzz_vm);
+}
@@ -0,0 +1,163 @@
+use std::path::PathBuf;
+
+use crate::objects::Object;
+use crate::virtual_machine::{GcConfig, InterpretResult, VM};
+
+fn run_with_threshold(src: &str, threshold: usize) -> VM {
+   let mut vm = VM::new(PathBuf::new()).with_gc_config(GcConfig {
+      initial_threshold: threshold,
+      growth_factor: 2,
+   });
+
+   if !matches!(vm.interpret(src), InterpretResult::Ok) {
+      panic!("Program Had Errors.")
+   }
+
+   vm
+}
+
+#[test]
+fn collects_unreachable_cycles() {
+   let vm = run_with_threshold(
+      "func f() { var a = []; a.push(a); var d = {'d': null}; d['d'] = d; }
+       var i = 0; while (i < 1000) { f(); i += 1; }",
+      16,
+   );
+
+   if vm.heap_size() > 64 {
+      panic!("Unreachable reference cycles should be collected.")
+   }
+}
+
+#[test]
+fn keeps_reachable_cycles_alive() {
+   run_with_threshold(
+      "var a = [1]; a.push(a);
+       var i = 0; while (i < 1000) { var t = [i]; t.push(t); i += 1; }
+       assert_eq(a[1][1][0], 1);",
+      8,
+   );
+}
+
+#[test]
+fn suspended_green_threads_keep_their_objects_alive() {
+   run_with_threshold(
+      "var results = Channel();
+       func build(id) {
+          var items = [];
+          for var i in 0..200 { items.push([i, {'id': id}]); if i % 50 == 0 { sleep(0); } }
+          results.send(items);
+       }
+       spawn(build, 1); spawn(build, 2);
+       var first = results.recv(); var second = results.recv();
+       assert_eq(first[199][1]['id'] + second[199][1]['id'], 3);
+       var c = Channel(); c.send(c);",
+      8,
+   );
+}
+
+#[test]
+fn pending_promises_keep_their_values_alive() {
+   run_with_threshold(
+      "async func build(id) {
+          var items = [];
+          for var i in 0..200 { items.push([i, {'id': id}]); if i % 50 == 0 { await delay(0); } }
+          return items;
+       }
+       var first = build(1); var second = build(2);
+       var a = await first; var b = await second;
+       assert_eq(a[199][1]['id'] + b[199][1]['id'], 3);
+       var cycle = [build(3)]; cycle.push(cycle);",
+      8,
+   );
+}
+
+#[test]
+fn collects_garbage_in_between_served_requests() {
+   use std::io::{Read, Write};
+
+   let port = std::net::TcpListener::bind("127.0.0.1:0")
+      .unwrap()
+      .local_addr()
+      .unwrap()
+      .port();
+
+   let client = std::thread::spawn(move || {
+      let mut sent = 0;
+      while sent < 50 {
+         if let Ok(mut stream) = std::net::TcpStream::connect(("127.0.0.1", port)) {
+            stream.write_all(b"GET / HTTP/1.1\r\n\r\n").unwrap();
+            stream.read_to_end(&mut vec![]).unwrap();
+            sent += 1;
+         }
+      }
+   });
+
+   let vm = run_with_threshold(
+      &format!(
+         "var seen = [];
+          Http.serve({}, fn (req) {{ var a = [req]; a.push(a); seen.push(req.path); return 'ok'; }}, 50);
+          assert_eq(seen.len(), 50);",
+         port
+      ),
+      16,
+   );
+
+   client.join().unwrap();
+
+   if vm.heap_size() > 64 {
+      panic!("Objects created by a request handler should be collected while serving requests.")
+   }
+}
+
+#[test]
+fn values_held_by_the_host_survive_collections() {
+   let mut vm = VM::new(PathBuf::new()).with_gc_config(GcConfig {
+      initial_threshold: 8,
+      growth_factor: 2,
+   });
+
+   let held = match vm.eval("var items = [[1, 2], [3]]; var cycle = [items]; cycle.push(cycle); items") {
+      Ok(value) => value,
+      Err(e) => panic!("Program Had Errors: {}", e),
+   };
+
+   // The program drops its references to the value, and allocates enough to force collections.
+   if let Err(e) = vm.eval("items = null; cycle = null; for var i in 0..1000 { var t = [i]; t.push(t); }") {
+      panic!("Program Had Errors: {}", e);
+   }
+
+   let lengths: Vec<usize> = match &held {
+      Object::Array(items) => items
+         .borrow()
+         .iter()
+         .map(|item| match item {
+            Object::Array(inner) => inner.borrow().len(),
+            _ => 0,
+         })
+         .collect(),
+      _ => vec![],
+   };
+
+   if lengths != [2, 1] {
+      panic!("A value held by the host should not be cleared by a collection.")
+   }
+
+   if vm.heap_size() > 64 {
+      panic!("Unreachable reference cycles should still be collected.")
+   }
+}
+
+#[test]
+fn collections_sweep_the_interned_strings_that_are_no_longer_used() {
+   let mut vm = VM::new(PathBuf::new());
+   let _kept = vm.intern("kept");
+   let interned = vm.interner.len();
+
+   drop(vm.intern("dropped"));
+   vm.collect_garbage();
+
+   if vm.interner.len() != interned {
+      panic!("Should remove the interned strings that are only referenced by the table.")
+   }
+}
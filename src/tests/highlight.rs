@@ -0,0 +1,43 @@
+use crate::highlight::{fragments, highlight, HighlightFormat, TokenCategory};
+
+#[test]
+fn fragments_cover_the_whole_source() {
+   let src = "var s = \"a ${1 + 2} b\"; // comment\n\t/* é */ print(s)\n";
+   let text: String = fragments(src).iter().map(|(_, text)| *text).collect();
+
+   assert_eq!(text, src);
+}
+
+#[test]
+fn categorize_the_tokens_of_a_source() {
+   let src = "func f(x) { return x equals null; } // done";
+   let styled: Vec<(TokenCategory, &str)> = fragments(src)
+      .into_iter()
+      .filter_map(|(category, text)| category.map(|c| (c, text)))
+      .collect();
+
+   assert_eq!(styled[0], (TokenCategory::Keyword, "func"));
+   assert_eq!(styled[1], (TokenCategory::Identifier, "f"));
+   assert_eq!(styled[2], (TokenCategory::Punctuation, "("));
+   assert_eq!(styled[8], (TokenCategory::Keyword, "equals"));
+   assert_eq!(styled[9], (TokenCategory::Literal, "null"));
+   assert_eq!(styled.last(), Some(&(TokenCategory::Comment, "// done")));
+}
+
+#[test]
+fn highlight_as_html_and_ansi() {
+   let src = "var x = \"<b>\";";
+
+   assert_eq!(
+      highlight(src, HighlightFormat::Html),
+      "<pre class=\"hinton\"><code><span class=\"ht-keyword\">var</span> \
+       <span class=\"ht-identifier\">x</span> <span class=\"ht-operator\">=</span> \
+       <span class=\"ht-string\">&quot;&lt;b&gt;&quot;</span><span class=\"ht-punctuation\">;</span>\
+       </code></pre>\n"
+   );
+
+   assert_eq!(
+      highlight("x = 1;", HighlightFormat::Ansi),
+      "x \x1b[38;5;81m=\x1b[0m \x1b[38;5;141m1\x1b[0m;"
+   );
+}
@@ -0,0 +1,194 @@
+use crate::core::tokens::{Token, TokenType};
+use crate::lexer::Lexer;
+use std::borrow::Cow;
+
+#[test]
+fn iterate_over_the_tokens_of_a_source() {
+   let lexemes: Vec<Cow<str>> = Lexer::new("var x = 22; // comment")
+      .tokens()
+      .map(|t| t.lexeme)
+      .collect();
+
+   assert_eq!(lexemes, vec!["var", "x", "=", "22", ";"]);
+}
+
+#[test]
+fn token_spans_are_byte_ranges_of_the_source() {
+   let src = "var café = \"ñandú ${1 + 2}\";\n/* é */ print(café);";
+
+   for token in Lexer::new(src).tokens() {
+      assert_eq!(&src[token.span.start..token.span.end], token.lexeme);
+   }
+}
+
+#[test]
+fn lexemes_are_borrowed_from_the_source() {
+   let src = "var s = 'text' + r\"raw\"; /// Doc.\n1_000 \"unterminated";
+   let source_range = src.as_bytes().as_ptr_range();
+
+   for token in Lexer::new(src).with_doc_comments().tokens() {
+      match (token.token_type, &token.lexeme) {
+         // The lexeme of an error token is its message.
+         (TokenType::ERROR, _) => {}
+         (_, Cow::Borrowed(lexeme)) if source_range.contains(&lexeme.as_ptr()) => {}
+         (_, lexeme) => panic!("The lexeme '{}' should be borrowed from the source.", lexeme),
+      }
+   }
+}
+
+#[test]
+fn peek_at_the_next_token() {
+   let mut tokens = Lexer::new("a + b").tokens();
+
+   assert_eq!(tokens.peek().map(|t| t.lexeme.as_ref()), Some("a"));
+   assert_eq!(tokens.next().map(|t| t.lexeme), Some(Cow::from("a")));
+   assert_eq!(tokens.peek().map(|t| t.lexeme.as_ref()), Some("+"));
+   assert_eq!(tokens.nth(1).map(|t| t.lexeme), Some(Cow::from("b")));
+   assert!(tokens.peek().is_none());
+   assert!(tokens.next().is_none());
+}
+
+#[test]
+fn yield_scanning_errors_as_tokens() {
+   let src = "x = \"unterminated";
+   let error = Lexer::new(src).tokens().last().unwrap();
+
+   assert!(matches!(error.token_type, TokenType::ERROR));
+   assert_eq!(error.lexeme, "Unterminated string.");
+   assert_eq!(&src[error.span.start..error.span.end], "\"unterminated");
+}
+
+#[test]
+fn emit_comments_as_tokens() {
+   let src = "// line\nvar x /* block /* nested */ */ = 1;";
+   let comments: Vec<Cow<str>> = Lexer::new(src)
+      .with_comments()
+      .tokens()
+      .filter(|t| matches!(t.token_type, TokenType::COMMENT))
+      .map(|t| t.lexeme)
+      .collect();
+
+   assert_eq!(comments, vec!["// line", "/* block /* nested */ */"]);
+   assert_eq!(Lexer::new(src).with_comments().tokens().count(), 7);
+}
+
+#[test]
+fn emit_doc_comments_as_tokens() {
+   let src = "/// Adds numbers.\n//// Not a doc comment.\n// Nor this one.\nfunc add() {}";
+   let docs: Vec<Cow<str>> = Lexer::new(src)
+      .with_doc_comments()
+      .tokens()
+      .filter(|t| matches!(t.token_type, TokenType::DOC_COMMENT))
+      .map(|t| t.lexeme)
+      .collect();
+
+   assert_eq!(docs, vec!["Adds numbers."]);
+   assert_eq!(Lexer::new(src).tokens().count(), 6);
+}
+
+#[test]
+fn track_lines_across_nested_block_comments() {
+   let src = "/* one\n /* two\n */ three */ /* a *//* b */\nx";
+   let token = Lexer::new(src).tokens().next().unwrap();
+
+   assert_eq!(token.lexeme, "x");
+   assert_eq!((token.line_num, token.column_start), (4, 0));
+}
+
+#[test]
+fn report_unterminated_block_comments_at_their_opening_delimiter() {
+   let src = "x\n  /* outer /* inner */\n\n";
+   let tokens: Vec<Token> = Lexer::new(src).tokens().collect();
+
+   assert_eq!(tokens.len(), 2);
+   assert!(matches!(tokens[1].token_type, TokenType::ERROR));
+   assert_eq!(tokens[1].lexeme, "Unterminated block comment.");
+   assert_eq!((tokens[1].line_num, tokens[1].column_start), (2, 2));
+   assert_eq!(&src[tokens[1].span.start..tokens[1].span.end], "/*");
+}
+
+#[test]
+fn scan_numeric_literals_with_separators_and_exponents() {
+   let src = "1_000_000 0xFF_FF 1.5e-3 2E3 .5e1 12n";
+   let tokens: Vec<(TokenType, Cow<str>)> = Lexer::new(src)
+      .tokens()
+      .map(|t| (t.token_type, t.lexeme))
+      .collect();
+
+   assert!(matches!(tokens[0], (TokenType::INTEGER, ref l) if l == "1_000_000"));
+   assert!(matches!(tokens[1], (TokenType::HEXADECIMAL, ref l) if l == "0xFF_FF"));
+   assert!(matches!(tokens[2], (TokenType::FLOAT, ref l) if l == "1.5e-3"));
+   assert!(matches!(tokens[3], (TokenType::FLOAT, ref l) if l == "2E3"));
+   assert!(matches!(tokens[4], (TokenType::FLOAT, ref l) if l == ".5e1"));
+   assert!(matches!(tokens[5], (TokenType::BIG_INTEGER, ref l) if l == "12n"));
+}
+
+#[test]
+fn report_malformed_numeric_literals_at_the_invalid_character() {
+   let cases = [
+      (
+         "x = 1__000;",
+         5,
+         "A '_' in a numeric literal must be between two digits.",
+      ),
+      (
+         "x = 100_;",
+         7,
+         "A '_' in a numeric literal must be between two digits.",
+      ),
+      ("x = 0b102;", 8, "Invalid digit '2' in binary literal."),
+      ("x = 0x;", 6, "Expected hexadecimal digits after '0x'."),
+      (
+         "x = 1.5e+;",
+         9,
+         "Expected digits in the exponent of the numeric literal.",
+      ),
+      ("x = 12px;", 6, "Invalid character 'p' in numeric literal."),
+   ];
+
+   for (src, column, message) in cases {
+      let mut tokens = Lexer::new(src).tokens();
+      let error = tokens.find(|t| matches!(t.token_type, TokenType::ERROR)).unwrap();
+
+      assert_eq!((error.column_start, error.lexeme.as_ref()), (column, message));
+      assert_eq!(&src[error.span.start..error.span.end], &src[column..column + 1]);
+
+      // The rest of the literal is not scanned as other tokens.
+      assert!(matches!(
+         tokens.next().map(|t| t.token_type),
+         Some(TokenType::SEMICOLON)
+      ));
+   }
+}
+
+#[test]
+fn lex_char_literals() {
+   let tokens: Vec<_> = Lexer::new("c'a' c'\\n' c'\\u{1F600}' c'ab' c''")
+      .tokens()
+      .map(|t| (t.token_type, t.lexeme))
+      .collect();
+
+   assert!(matches!(tokens[0], (TokenType::CHAR_LITERAL, ref l) if l == "c'a'"));
+   assert!(matches!(tokens[1], (TokenType::CHAR_LITERAL, ref l) if l == "c'\\n'"));
+   assert!(matches!(tokens[2], (TokenType::CHAR_LITERAL, ref l) if l == "c'\\u{1F600}'"));
+   assert!(
+      matches!(tokens[3], (TokenType::ERROR, ref l) if l == "A char literal must contain exactly one character.")
+   );
+   assert!(
+      matches!(tokens[4], (TokenType::ERROR, ref l) if l == "A char literal must contain exactly one character.")
+   );
+
+   // Identifiers that end with a `c` are not char literals.
+   assert!(matches!(
+      Lexer::new("abc'x'").tokens().next().map(|t| t.token_type),
+      Some(TokenType::IDENTIFIER)
+   ));
+}
+
+#[test]
+fn lex_inclusive_range_operator() {
+   let tokens: Vec<_> = Lexer::new("0..=5 0..5").tokens().map(|t| t.token_type).collect();
+
+   assert!(matches!(tokens[1], TokenType::RANGE_INCLUSIVE_OPR));
+   assert!(matches!(tokens[4], TokenType::RANGE_OPR));
+}
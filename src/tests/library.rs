@@ -0,0 +1,260 @@
+use std::convert::TryFrom;
+use std::path::PathBuf;
+
+use crate::errors::{HintonError, RuntimeErrorType};
+use crate::objects::Object;
+use crate::virtual_machine::{InterpretResult, Message, OutputBuffer, Worker, VM};
+
+#[test]
+fn interpret_returns_the_value_of_the_last_expression() {
+   match crate::interpret("var items = [1, 2, 3]; items.len() * 2") {
+      Ok(value) if i64::try_from(value.clone()).ok() == Some(6) => {}
+      Ok(value) => panic!("Expected 6. Got '{}' instead.", value),
+      Err(e) => panic!("Program Had Errors: {}", e),
+   }
+
+   match crate::interpret("var x = 1;") {
+      Ok(Object::Null) => {}
+      _ => panic!("Programs that do not end with an expression should return null."),
+   }
+}
+
+#[test]
+fn interpret_returns_structured_errors() {
+   match crate::interpret("var x = ;") {
+      Err(HintonError::Parse(errors)) if !errors.is_empty() => {}
+      _ => panic!("Should return the syntax errors of the program."),
+   }
+
+   match crate::interpret("print(undeclared);") {
+      Err(HintonError::Compile(errors)) if !errors.is_empty() => {}
+      _ => panic!("Should return the compiler errors of the program."),
+   }
+
+   match crate::interpret("var a = 1;\nvar b = a / 0;") {
+      Err(HintonError::Runtime(e)) => {
+         if e.error_type != RuntimeErrorType::ZeroDivision || e.line != 2 {
+            panic!("Should report the type and location of the error. Got '{}'.", e)
+         }
+
+         if !HintonError::Runtime(e)
+            .to_string()
+            .starts_with("ZeroDivisionError[E0021] at [2:")
+         {
+            panic!("Should display the error without ANSI escape sequences.")
+         }
+      }
+      _ => panic!("Should return the runtime error of the program."),
+   }
+}
+
+#[test]
+fn run_captured_returns_the_output_and_errors_of_a_program() {
+   let run = crate::run_captured("print('first'); print('second'); [][2];");
+   assert_eq!(run.output, "first\nsecond\n");
+   assert!(run.errors.contains("IndexError"));
+
+   let run = crate::run_captured("print(undeclared);");
+   assert_eq!(run.output, "");
+   assert!(run.errors.contains("undeclared"));
+
+   let run = crate::run_captured("print('ok');");
+   assert_eq!(run.errors, "");
+}
+
+#[test]
+fn run_captured_does_not_color_the_text() {
+   // The colors are enabled outside of WebAssembly, unless the `--no-color` flag is passed.
+   let run = crate::run_captured("print([1, 'two', null]); [][2];");
+   assert_eq!(run.output, "[1, two, null]\n");
+   assert!(run.errors.starts_with("IndexError"));
+   assert!(!run.errors.contains('\x1b'));
+
+   // The escape sequences written by the program itself are kept.
+   let run = crate::run_captured("print('\\u{1b}[2Jclear screen, then some more text'); print('x');");
+   assert_eq!(run.output, "\x1b[2Jclear screen, then some more text\nx\n");
+}
+
+#[test]
+fn print_functions_write_lines_of_values() {
+   let run =
+      crate::run_captured("print('a', 'b c'); println(); println('d'); eprint('warning:', 'e'); print();");
+   assert_eq!(run.output, "a b c\n\nd\n\n");
+   assert_eq!(run.errors, "warning: e\n");
+}
+
+#[test]
+fn programs_without_system_access_cannot_reach_the_host() {
+   let run_sandboxed = |source: &str| {
+      let errors = OutputBuffer::new();
+      let result = VM::new(PathBuf::new())
+         .without_system_access()
+         .with_stderr(Box::new(errors.clone()))
+         .interpret(source);
+      (result, errors.take())
+   };
+
+   for name in ["File", "Http", "Net", "Process", "load_plugin"] {
+      let (result, errors) = run_sandboxed(&format!("print({});", name));
+      assert!(matches!(result, InterpretResult::CompileError));
+      assert!(errors.contains(&format!("undeclared identifier '{}'", name)));
+   }
+
+   let (result, errors) = run_sandboxed("import util from './util';");
+   assert!(matches!(result, InterpretResult::CompileError));
+   assert!(errors.contains("cannot import modules"));
+
+   let (result, _) = run_sandboxed("assert_eq(Math.max(1, 2), 2);");
+   assert!(matches!(result, InterpretResult::Ok));
+}
+
+#[test]
+fn eval_shares_globals_with_the_host() {
+   let mut vm = VM::new(PathBuf::new());
+   vm.set_global("limit", Object::from(10i64));
+
+   if let Err(e) = vm.eval("var total = 0; for var i in 0..limit { total += i; }") {
+      panic!("Program Had Errors: {}", e)
+   }
+
+   if vm.get_global("total").and_then(|t| i64::try_from(t).ok()) != Some(45) {
+      panic!("Should read the globals declared by the program.")
+   }
+
+   match vm.eval("'total: ${total}'").map(String::try_from) {
+      Ok(Ok(s)) if s == "total: 45" => {}
+      _ => panic!("Should keep the globals of previously evaluated programs."),
+   }
+}
+
+#[test]
+fn globals_of_separately_compiled_programs_are_linked_by_name() {
+   let mut vm = VM::new(PathBuf::new());
+
+   if let Err(e) = vm.eval("var a = 1; var b = 2;") {
+      panic!("Program Had Errors: {}", e)
+   }
+
+   // The program executed with `interpret` declares its globals in different slots.
+   if !matches!(vm.interpret("var b = 20; var c = b + 1;"), InterpretResult::Ok) {
+      panic!("Program Had Errors.")
+   }
+
+   vm.set_global("d", Object::from(4i64));
+
+   if vm.get_global("c").and_then(|c| i64::try_from(c).ok()) != Some(21) {
+      panic!("Should read the globals declared by the program.")
+   }
+
+   match vm.eval("a + b + d").map(i64::try_from) {
+      Ok(Ok(25)) => {}
+      _ => panic!("Should keep the globals of every program executed in the VM."),
+   }
+}
+
+#[test]
+fn convert_between_rust_values_and_objects() {
+   let array = Object::from(vec![Object::from(true), Object::from(1.5), Object::from("text")]);
+
+   match Vec::<Object>::try_from(array) {
+      Ok(items) if items.len() == 3 => {
+         if bool::try_from(items[0].clone()).ok() != Some(true)
+            || f64::try_from(items[1].clone()).ok() != Some(1.5)
+            || String::try_from(items[2].clone()).ok().as_deref() != Some("text")
+         {
+            panic!("Should convert the objects back into Rust values.")
+         }
+      }
+      _ => panic!("Should convert arrays into vectors of objects."),
+   }
+
+   if f64::try_from(Object::from(2i64)).ok() != Some(2.0) || i64::try_from(Object::from(2.5)).is_ok() {
+      panic!("Should only convert objects of compatible types.")
+   }
+
+   if !matches!(Object::from(None::<i64>), Object::Null) {
+      panic!("Should convert `None` into null.")
+   }
+}
+
+#[test]
+fn workers_run_programs_concurrently_and_exchange_messages() {
+   let source = "
+      var total = 0;
+      var n = receive_message();
+      while n != null {
+         total += n;
+         post_message((n, [n * 2], {'n': n}));
+         n = receive_message();
+      }
+      total
+   ";
+
+   let workers: Vec<Worker> = (0..4).map(|_| Worker::spawn(source)).collect();
+
+   for (i, worker) in workers.iter().enumerate() {
+      for n in 1..=3 {
+         worker.send(Message::Int(i as i64 * n));
+      }
+   }
+
+   for (i, worker) in workers.into_iter().enumerate() {
+      for n in 1..=3 {
+         let n = i as i64 * n;
+         let expected = Message::Tuple(vec![
+            Message::Int(n),
+            Message::Array(vec![Message::Int(n * 2)]),
+            Message::Dict(vec![(Message::String(String::from("n")), Message::Int(n))]),
+         ]);
+
+         if worker.recv() != Some(expected) {
+            panic!("Should receive the messages posted by the worker, in order.")
+         }
+      }
+
+      match worker.join() {
+         Ok(Message::Int(total)) if total == i as i64 * 6 => {}
+         _ => panic!("Should return the value of the last expression of the worker's program."),
+      }
+   }
+}
+
+#[test]
+fn workers_only_send_plain_data() {
+   let worker = Worker::spawn(
+      "
+      func f() {}
+      var a = [1];
+      a.push(a);
+
+      for var value in [f, a] {
+         try { post_message(value); } catch (e) { post_message(e.name); }
+      }
+      post_message(Set([1, 2, 3]));
+      receive_message()
+      ",
+   );
+
+   for expected in ["TypeError", "TypeError"] {
+      if worker.recv() != Some(Message::String(String::from(expected))) {
+         panic!("Should not send functions or collections that contain themselves.")
+      }
+   }
+
+   match worker.recv() {
+      Some(Message::Set(items)) if items.len() == 3 => {}
+      _ => panic!("Should send the items of a set."),
+   }
+
+   worker.send(Message::Set(vec![Message::Int(1), Message::Char('c')]));
+
+   match worker.join() {
+      Ok(Message::Set(items)) if items == [Message::Int(1), Message::Char('c')] => {}
+      _ => panic!("Should convert the messages sent by the host into objects."),
+   }
+
+   match Worker::spawn("var a = 1;\nreceive_message() + a").join() {
+      Err(HintonError::Runtime(e)) if e.error_type == RuntimeErrorType::TypeError && e.line == 2 => {}
+      _ => panic!("Should return the error that stopped the worker's program."),
+   }
+}
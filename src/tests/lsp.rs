@@ -0,0 +1,214 @@
+use crate::lsp::json::Json;
+use crate::lsp::{run, LanguageServer};
+
+const URI: &str = "file:///tmp/main.ht";
+
+fn open(server: &mut LanguageServer, text: &str) -> Json {
+   let message = Json::object(vec![
+      ("jsonrpc", "2.0".into()),
+      ("method", "textDocument/didOpen".into()),
+      (
+         "params",
+         Json::object(vec![(
+            "textDocument",
+            Json::object(vec![("uri", URI.into()), ("text", text.into())]),
+         )]),
+      ),
+   ]);
+
+   server.handle(&message).remove(0)
+}
+
+fn request(server: &mut LanguageServer, method: &str, line: usize, character: usize) -> Json {
+   let message = Json::object(vec![
+      ("jsonrpc", "2.0".into()),
+      ("id", 1.into()),
+      ("method", method.into()),
+      (
+         "params",
+         Json::object(vec![
+            ("textDocument", Json::object(vec![("uri", URI.into())])),
+            (
+               "position",
+               Json::object(vec![("line", line.into()), ("character", character.into())]),
+            ),
+         ]),
+      ),
+   ]);
+
+   server.handle(&message).remove(0).get("result").cloned().unwrap()
+}
+
+fn start_of_range(value: &Json) -> (usize, usize) {
+   let start = value.at(&["range", "start"]).unwrap();
+   (
+      start.get("line").and_then(|l| l.as_usize()).unwrap(),
+      start.get("character").and_then(|c| c.as_usize()).unwrap(),
+   )
+}
+
+#[test]
+fn publish_diagnostics_for_errors_and_warnings() {
+   let mut server = LanguageServer::default();
+
+   let notification = open(&mut server, "func f() {\n   var unused = 1;\n}\nprint(missing);");
+   let diagnostics = match notification.at(&["params", "diagnostics"]) {
+      Some(Json::Array(d)) => d.clone(),
+      _ => panic!("Should publish the diagnostics of the document."),
+   };
+
+   assert_eq!(diagnostics.len(), 2);
+   assert_eq!(
+      diagnostics[0].get("message").and_then(|m| m.as_str()),
+      Some("ReferenceError[E0002]: Use of undeclared identifier 'missing'.")
+   );
+   assert_eq!(start_of_range(&diagnostics[0]), (3, 6));
+   assert_eq!(diagnostics[1].get("severity").and_then(|s| s.as_usize()), Some(2));
+   assert_eq!(start_of_range(&diagnostics[1]), (1, 7));
+
+   let notification = open(&mut server, "var x = ;");
+   assert!(matches!(notification.at(&["params", "diagnostics"]), Some(Json::Array(d)) if d.len() == 1));
+}
+
+#[test]
+fn go_to_the_definition_of_globals_and_locals() {
+   let mut server = LanguageServer::default();
+   open(
+      &mut server,
+      "var count = 0;\nfunc bump(step) {\n   var next = count + step;\n   return fn() { return next; };\n}",
+   );
+
+   // `count` in the body of the function is a global.
+   let location = request(&mut server, "textDocument/definition", 2, 15);
+   assert_eq!(location.get("uri").and_then(|u| u.as_str()), Some(URI));
+   assert_eq!(start_of_range(&location), (0, 4));
+
+   // `step` is a parameter, and `next` is captured by the lambda.
+   assert_eq!(
+      start_of_range(&request(&mut server, "textDocument/definition", 2, 24)),
+      (1, 10)
+   );
+   assert_eq!(
+      start_of_range(&request(&mut server, "textDocument/definition", 3, 25)),
+      (2, 7)
+   );
+
+   assert_eq!(request(&mut server, "textDocument/definition", 1, 0), Json::Null);
+}
+
+#[test]
+fn hover_shows_the_types_and_arity_of_declarations() {
+   let mut server = LanguageServer::default();
+   open(
+      &mut server,
+      "func add(a: Int, b = 2, ...rest) -> Int { return a + b; }\nvar sum = add(1);\nvar name = 'Ann';",
+   );
+
+   let hover_text = |server: &mut LanguageServer, line, character| {
+      let hover = request(server, "textDocument/hover", line, character);
+      hover
+         .at(&["contents", "value"])
+         .and_then(|v| v.as_str())
+         .unwrap()
+         .to_string()
+   };
+
+   assert_eq!(
+      hover_text(&mut server, 1, 11),
+      "```hinton\nfunc add(a: Int, b = ..., ...rest) -> Int\n```\nTakes at least 1 argument."
+   );
+   assert_eq!(hover_text(&mut server, 1, 5), "```hinton\nvar sum: Int\n```");
+   assert_eq!(hover_text(&mut server, 2, 4), "```hinton\nvar name: String\n```");
+   assert_eq!(hover_text(&mut server, 0, 17), "```hinton\nparam b: Any\n```");
+}
+
+#[test]
+fn list_the_global_declarations_of_a_document() {
+   let mut server = LanguageServer::default();
+   open(
+      &mut server,
+      "class Point { var x = 0; }\nconst ORIGIN = new Point();\nfunc f(a) { var b = a; }",
+   );
+
+   let symbols = match request(&mut server, "textDocument/documentSymbol", 0, 0) {
+      Json::Array(s) => s,
+      _ => panic!("Should list the symbols of the document."),
+   };
+
+   let names: Vec<(&str, usize)> = symbols
+      .iter()
+      .map(|s| {
+         (
+            s.get("name").and_then(|n| n.as_str()).unwrap(),
+            s.get("kind").and_then(|k| k.as_usize()).unwrap(),
+         )
+      })
+      .collect();
+
+   assert_eq!(names, vec![("Point", 5), ("ORIGIN", 14), ("f", 12)]);
+}
+
+#[test]
+fn exchange_messages_with_content_length_headers() {
+   let messages = [
+      r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{}}"#,
+      r#"{"jsonrpc":"2.0","id":2,"method":"shutdown"}"#,
+      r#"{"jsonrpc":"2.0","method":"exit"}"#,
+   ];
+
+   let input: String = messages
+      .iter()
+      .map(|m| format!("Content-Length: {}\r\n\r\n{}", m.len(), m))
+      .collect();
+   let mut output = vec![];
+
+   let code = run(&mut input.as_bytes(), &mut output).unwrap();
+   let output = String::from_utf8(output).unwrap();
+
+   assert_eq!(code, 0);
+   assert!(output.starts_with("Content-Length: "));
+   assert!(output.contains(r#""definitionProvider":true"#));
+   assert!(output.ends_with(r#"{"jsonrpc":"2.0","id":2,"result":null}"#));
+}
+
+#[test]
+fn parse_and_print_json() {
+   let text = r#"{"a": [1, -2.5, true, null], "b": "q\"é😀\n"}"#;
+   let value = Json::parse(text).unwrap();
+
+   assert_eq!(value.get("b").and_then(|b| b.as_str()), Some("q\"é😀\n"));
+   assert_eq!(value.to_string(), r#"{"a":[1,-2.5,true,null],"b":"q\"é😀\n"}"#);
+   assert!(Json::parse("[1, 2").is_err());
+}
+
+#[test]
+fn find_the_symbols_of_documents_with_syntax_errors() {
+   let mut server = LanguageServer::default();
+   let notification = open(
+      &mut server,
+      "var count = 0;\nfunc bump(step) {\n   var x = ;\n   return count + step;\n}\nprint(count +);",
+   );
+
+   // Only the syntax errors are reported.
+   assert!(matches!(notification.at(&["params", "diagnostics"]), Some(Json::Array(d)) if d.len() == 2));
+
+   assert_eq!(
+      start_of_range(&request(&mut server, "textDocument/definition", 3, 10)),
+      (0, 4)
+   );
+   assert_eq!(
+      start_of_range(&request(&mut server, "textDocument/definition", 3, 19)),
+      (1, 10)
+   );
+
+   let hover = request(&mut server, "textDocument/hover", 0, 4);
+   assert_eq!(
+      hover.at(&["contents", "value"]).and_then(|v| v.as_str()),
+      Some("```hinton\nvar count: Int\n```")
+   );
+
+   match request(&mut server, "textDocument/documentSymbol", 0, 0) {
+      Json::Array(s) if s.len() == 2 => {}
+      _ => panic!("Should list the symbols of the statements without syntax errors."),
+   }
+}
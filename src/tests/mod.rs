@@ -1,2 +1,20 @@
+mod ast_dump;
+mod bench_runner;
 mod compiler;
+mod disassembler;
+mod docs;
+mod errors;
+mod explain;
+mod formatter;
+mod fuzz;
+mod gc;
+mod highlight;
+mod lexer;
+mod library;
+mod lsp;
 mod parser;
+mod playground;
+mod project;
+mod serialization;
+mod test_runner;
+mod vm;
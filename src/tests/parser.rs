@@ -34,6 +34,17 @@ fn allow_chained_ternary_expressions() {
    }
 }
 
+#[test]
+fn expect_else_branch_in_if_expressions() {
+   if Parser::parse("var x = if true { 1 };").is_ok() {
+      panic!("Should expect an 'else' branch in 'if' expressions.")
+   }
+
+   if Parser::parse("var x = if a { 1 } else if b { 2 } else { 3 };").is_err() {
+      panic!("Should allow chained 'if' expressions.")
+   }
+}
+
 #[test]
 fn panic_on_unterminated_string() {
    if Parser::parse("\"hello world").is_ok() {
@@ -80,3 +91,334 @@ fn allow_escaped_single_quoted_strings() {
       panic!("Should allow escaped single-quoted strings.")
    }
 }
+
+#[test]
+fn allow_raw_strings() {
+   if Parser::parse("r\"C:\\new\\${dir}\"; r'raw';").is_err() {
+      panic!("Should allow raw strings.")
+   }
+}
+
+#[test]
+fn expect_valid_escape_sequences() {
+   if Parser::parse("\"\\u{1F600} \\\\ \\0\";").is_err() {
+      panic!("Should allow valid escape sequences.")
+   }
+
+   if Parser::parse("\"\\q\";").is_ok() {
+      panic!("Should not allow unknown escape sequences.")
+   }
+
+   if Parser::parse("\"\\u{110000}\";").is_ok() {
+      panic!("Should not allow invalid unicode escape sequences.")
+   }
+}
+
+#[test]
+fn allow_type_annotations() {
+   if Parser::parse(
+      "var x: Int = 1; const y: String = ''; func f(a: Int, b: Bool := true) -> Float { return 1.0; }",
+   )
+   .is_err()
+   {
+      panic!("Should allow type annotations.")
+   }
+
+   if Parser::parse("var f = fn (a: Int) -> Int { return a; };").is_err() {
+      panic!("Should allow type annotations in lambdas.")
+   }
+}
+
+#[test]
+fn allow_default_parameters_and_named_arguments() {
+   if Parser::parse("func greet(name, greeting = 'Hello', end := '!') {} greet('Ana', end: '?');").is_err() {
+      panic!("Should allow default parameters and named arguments.")
+   }
+}
+
+#[test]
+fn expect_identifier_for_argument_name() {
+   if Parser::parse("greet('name': 'Ana');").is_ok() {
+      panic!("Should expect an identifier for the name of an argument.")
+   }
+
+   if Parser::parse("greet(name: 'Ana', 'Hello');").is_ok() {
+      panic!("Should expect named arguments after all unnamed arguments.")
+   }
+}
+
+#[test]
+fn allow_rest_parameters_and_spread_arguments() {
+   if Parser::parse("func sum(first, ...rest) {} sum(1, ...[2, 3], 4); var f = fn (...xs) {};").is_err() {
+      panic!("Should allow rest parameters and spread arguments.")
+   }
+}
+
+#[test]
+fn expect_rest_parameter_to_be_last() {
+   if Parser::parse("func sum(...rest, last) {}").is_ok() {
+      panic!("Should expect the rest parameter to be the last parameter.")
+   }
+
+   if Parser::parse("sum(...list, name: 1);").is_ok() {
+      panic!("Should not allow named arguments together with spread arguments.")
+   }
+}
+
+#[test]
+fn allow_try_catch_finally_statements() {
+   if Parser::parse("try { 1 / 0; } catch (err) { print(err); } finally { print(0); }").is_err() {
+      panic!("Should allow try-catch-finally statements.")
+   }
+
+   if Parser::parse("try { 1 / 0; } catch { print(0); }").is_err() {
+      panic!("Should allow catch blocks without an error identifier.")
+   }
+}
+
+#[test]
+fn expect_catch_or_finally_after_try() {
+   if Parser::parse("try { 1 / 0; }").is_ok() {
+      panic!("Should expect a 'catch' or 'finally' block after the 'try' block.")
+   }
+}
+
+#[test]
+fn allow_import_and_export_declarations() {
+   if Parser::parse("import math from \"./math\"; export func add(a, b) { return a + b; }").is_err() {
+      panic!("Should allow import and export declarations.")
+   }
+}
+
+#[test]
+fn expect_declaration_after_export() {
+   if Parser::parse("export 1 + 2;").is_ok() {
+      panic!("Should expect a declaration after the 'export' keyword.")
+   }
+}
+
+#[test]
+fn allow_string_interpolation() {
+   if Parser::parse("\"Hello ${name}, you are ${age + 1}. ${ \"nested ${ {'a': 1}['a'] }\" }\";").is_err() {
+      panic!("Should allow interpolated expressions in strings.")
+   }
+}
+
+#[test]
+fn expect_closing_brace_after_interpolated_expression() {
+   if Parser::parse("\"Hello ${name \";").is_ok() {
+      panic!("Should expect a closing brace after the interpolated expression.")
+   }
+}
+
+#[test]
+fn allow_match_statements() {
+   let src = "match x {
+      0 => print(0),
+      1, 2 => { print(1); }
+      3..10 => print(3);
+      \"str\", null, -4.5 => print(4),
+      else => print(5)
+   }";
+
+   if Parser::parse(src).is_err() {
+      panic!("Should allow match statements with literal, range, and default arms.")
+   }
+}
+
+#[test]
+fn expect_literal_patterns_in_match_arms() {
+   if Parser::parse("match x { 1 + y => print(y); }").is_ok() {
+      panic!("Should expect patterns rather than expressions in match arms.")
+   }
+}
+
+#[test]
+fn allow_defer_statements() {
+   if Parser::parse("func f() { defer print(1); defer { print(2); print(3); } }").is_err() {
+      panic!("Should allow deferred expressions and blocks.")
+   }
+}
+
+#[test]
+fn expect_semicolon_after_deferred_expression() {
+   if Parser::parse("func f() { defer print(1) }").is_ok() {
+      panic!("Should expect a ';' after the deferred expression.")
+   }
+}
+
+#[test]
+fn allow_assert_statements() {
+   if Parser::parse("assert x > 0; assert x > 0, 'message'; assert(x > 0); assert(x > 0, 'message');")
+      .is_err()
+   {
+      panic!("Should allow assert statements with and without parentheses.")
+   }
+}
+
+#[test]
+fn expect_semicolon_after_assertion() {
+   if Parser::parse("assert x > 0, 'message'").is_ok() {
+      panic!("Should expect a ';' after the assertion.")
+   }
+}
+
+#[test]
+fn allow_two_for_loop_variables() {
+   if Parser::parse("for var key, value in d { print(key); }").is_err() {
+      panic!("Should allow two variables in for-loops.")
+   }
+}
+
+#[test]
+fn allow_identifiers_starting_with_underscores() {
+   if Parser::parse("var _x = 1; class A { func __add__(other) { return _x; } }").is_err() {
+      panic!("Should allow identifiers that start with underscores.")
+   }
+}
+
+#[test]
+fn allow_class_inheritance() {
+   if Parser::parse("class Dog extends Animal { pub func speak() { return super.speak(); } }").is_err() {
+      panic!("Should allow classes to extend a superclass.")
+   }
+}
+
+#[test]
+fn allow_interface_declarations() {
+   let src = "interface Shape { func area() -> Float; func scale(f: Int); } class Sq extends A implements Shape, B {}";
+
+   if Parser::parse(src).is_err() {
+      panic!("Should allow interface declarations and classes that implement them.")
+   }
+}
+
+#[test]
+fn allow_property_accessors() {
+   if Parser::parse("class A { pub get x() { return 1; } pub set x(v) {} pub func get() {} }").is_err() {
+      panic!("Should allow getters and setters in classes.")
+   }
+}
+
+#[test]
+fn expect_one_parameter_in_setters() {
+   for src in [
+      "class A { set x() {} }",
+      "class A { set x(a, b) {} }",
+      "class A { get x(a) {} }",
+   ] {
+      if Parser::parse(src).is_ok() {
+         panic!("Should expect no parameters in getters, and one parameter in setters.")
+      }
+   }
+}
+
+#[test]
+fn allow_slicing_expressions() {
+   for src in [
+      "a[1..4];",
+      "a[..-1];",
+      "a[2..];",
+      "a[..];",
+      "a[0..10..2];",
+      "a[....-1];",
+      "a[-3..-1];",
+   ] {
+      if Parser::parse(src).is_err() {
+         panic!("Should allow slicing expression '{}'.", src)
+      }
+   }
+}
+
+#[test]
+fn allow_tuple_literals_and_destructuring() {
+   for src in [
+      "var t = ();",
+      "var t = (1,);",
+      "var t = (1, 'a', true);",
+      "var t = (1, 'a', true,);",
+      "var (a, b) = (1, 2);",
+      "var (a: Int, b: String) = (1, 'a');",
+      "var a, b = (1, 2);",
+      "func f() { return 1, 'a', true; }",
+      "var x = -(1);",
+   ] {
+      if Parser::parse(src).is_err() {
+         panic!("Should allow tuple expression '{}'.", src)
+      }
+   }
+
+   for src in ["var (a, b);", "var (a, b = (1, 2);", "var () = ();"] {
+      if Parser::parse(src).is_ok() {
+         panic!("Should not allow destructuring declaration '{}'.", src)
+      }
+   }
+}
+
+#[test]
+fn report_independent_syntax_errors_in_one_run() {
+   let src = "
+      func f() {
+         var x = ;
+         print(x);
+         var d = {'a' 1};
+         print(d)
+         print(1 2);
+      }
+
+      class A {
+         func a() { return ) }
+         var b = 2;
+         c d;
+      }
+
+      var y = 3
+      print('ok';
+   ";
+
+   match Parser::parse(src) {
+      Ok(_) => panic!("Should emit errors."),
+      Err(errors) => {
+         let lines: Vec<usize> = errors.iter().map(|e| e.line).collect();
+         assert_eq!(lines, vec![3, 5, 6, 7, 11, 13, 16, 17]);
+      }
+   }
+}
+
+#[test]
+fn report_the_source_span_of_multiline_errors() {
+   match Parser::parse("var a = 1;\nvar s = 'abc\n   def;") {
+      Ok(_) => panic!("Should emit error on unterminated strings."),
+      Err(errors) => {
+         let e = &errors[0];
+         assert_eq!((e.line, e.column, e.end_line, e.end_column), (2, 8, 3, 7));
+      }
+   }
+
+   // The tokens after a multiline string have the columns of their own line.
+   match Parser::parse("var s = 'abc\ndef'; var x = ;") {
+      Ok(_) => panic!("Should emit error on missing expressions."),
+      Err(errors) => {
+         let e = &errors[0];
+         assert_eq!((e.line, e.column, e.end_line, e.end_column), (2, 14, 2, 15));
+      }
+   }
+}
+
+#[test]
+fn match_patterns_bind_each_variable_once() {
+   assert!(Parser::parse("match x { [a, {b}] if a => 1; _ => 2; }").is_ok());
+   assert!(Parser::parse("match x { [a, a] => 1; }").is_err());
+   assert!(Parser::parse("match x { [a], 1 => 1; }").is_err());
+   assert!(Parser::parse("match x { {'key'} => 1; }").is_err());
+}
+
+#[test]
+fn comprehensions_need_a_single_value_before_their_clauses() {
+   assert!(Parser::parse("var x = [a for a in b if a for c in a];").is_ok());
+   assert!(Parser::parse("var x = {k: v for k, v in d};").is_ok());
+   assert!(Parser::parse("var x = [a, b for a in c];").is_err());
+   assert!(Parser::parse("var x = {a: 1, k: v for k in d};").is_err());
+   assert!(Parser::parse("var x = [a for a in b;").is_err());
+   assert!(Parser::parse("var x = {a.b: 1};").is_err());
+}
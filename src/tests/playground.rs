@@ -0,0 +1,130 @@
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+use std::time::Duration;
+
+use crate::playground::{self, default_limits};
+use crate::virtual_machine::VmConfig;
+
+/// Starts a playground with small limits, and returns its address.
+fn start_playground() -> String {
+   start_playground_with(VmConfig {
+      max_instructions: Some(100_000),
+      ..default_limits()
+   })
+}
+
+/// Starts a playground with the given limits, and returns its address.
+fn start_playground_with(limits: VmConfig) -> String {
+   let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+   let address = listener.local_addr().unwrap().to_string();
+
+   thread::spawn(move || playground::serve(listener, limits));
+   address
+}
+
+/// Sends a request to the playground, and returns the complete response.
+fn request(address: &str, method: &str, path: &str, body: &str) -> String {
+   let mut stream = TcpStream::connect(address).unwrap();
+   stream.set_read_timeout(Some(Duration::from_secs(30))).unwrap();
+
+   write!(
+      stream,
+      "{} {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+      method,
+      path,
+      body.len(),
+      body
+   )
+   .unwrap();
+
+   let mut response = String::new();
+   stream.read_to_string(&mut response).unwrap();
+   response
+}
+
+#[test]
+fn serves_the_page_of_the_playground() {
+   let address = start_playground();
+
+   let response = request(&address, "GET", "/", "");
+   assert!(response.starts_with("HTTP/1.1 200 OK"));
+   assert!(response.contains("<textarea"));
+
+   let response = request(&address, "GET", "/missing", "");
+   assert!(response.starts_with("HTTP/1.1 404 Not Found"));
+}
+
+#[test]
+fn streams_the_output_and_errors_of_programs() {
+   let address = start_playground();
+
+   let response = request(&address, "POST", "/run", "print('hi'); [][1];");
+   let output = response
+      .find(r#"{"kind":"output","text":"hi"}"#)
+      .expect("Should stream the output.");
+   let errors = response.find("IndexError").expect("Should stream the errors.");
+   assert!(output < errors);
+   assert!(response.contains(r#"{"kind":"exit","text":"Finished with errors."}"#));
+   assert!(response.ends_with("0\r\n\r\n"));
+
+   let response = request(&address, "POST", "/run", "print(1 + 1);");
+   assert!(response.contains(r#"{"kind":"exit","text":"Finished."}"#));
+}
+
+#[test]
+fn runs_programs_without_access_to_the_host_and_with_limits() {
+   let address = start_playground();
+
+   let response = request(&address, "POST", "/run", "File.read('secrets.txt');");
+   assert!(response.contains("undeclared identifier 'File'"));
+
+   let response = request(&address, "POST", "/run", "while true {}");
+   assert!(response.contains("TimeoutError"));
+}
+
+#[test]
+fn reports_an_error_when_programs_read_a_missing_clock() {
+   // Runs the programs as on the wasm32 target, which has no clocks.
+   let limits = VmConfig {
+      max_instructions: Some(100_000),
+      timeout: None,
+      has_clock: false,
+      ..default_limits()
+   };
+   let address = start_playground_with(limits);
+
+   let programs = [
+      "clock();",
+      "Time.now();",
+      "Time.timestamp();",
+      "Time.monotonic();",
+      "Math.random();",
+      "Random.int(1, 6);",
+      "sleep(10);",
+      "delay(10);",
+   ];
+
+   for src in programs {
+      let response = request(&address, "POST", "/run", src);
+      assert!(response.contains("IOError"), "'{}' should raise an IOError.", src);
+      assert!(response.contains(r#"{"kind":"exit","text":"Finished with errors."}"#));
+   }
+
+   // Seeded generators do not read the clock.
+   let response = request(
+      &address,
+      "POST",
+      "/run",
+      "Random.seed(7); print(Random.int(1, 6));",
+   );
+   assert!(response.contains(r#"{"kind":"exit","text":"Finished."}"#));
+
+   // A timeout cannot be enforced without a clock.
+   let address = start_playground_with(VmConfig {
+      timeout: Some(Duration::from_secs(1)),
+      ..limits
+   });
+   let response = request(&address, "POST", "/run", "print(1);");
+   assert!(response.contains("IOError"));
+}
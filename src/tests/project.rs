@@ -0,0 +1,65 @@
+use std::path::{Path, PathBuf};
+
+use crate::project::Manifest;
+use crate::virtual_machine::{InterpretResult, VM};
+
+#[test]
+fn parse_project_manifests() {
+   let src = "
+      # The project's manifest.
+      [project]
+      name = \"demo\" # A comment.
+      entry = \"src/main.ht\"
+      source_dirs = [\"src\", \"lib\"]
+
+      [scripts]
+      test = \"tests/all.ht\"
+   ";
+
+   let manifest = match Manifest::parse(src, Path::new("/app")) {
+      Ok(m) => m,
+      Err(_) => panic!("Manifest Had Errors."),
+   };
+
+   if manifest.name.as_deref() != Some("demo")
+      || manifest.entry != Path::new("/app/src/main.ht")
+      || manifest.source_dirs != vec![PathBuf::from("/app/src"), PathBuf::from("/app/lib")]
+      || manifest.scripts.get("test") != Some(&PathBuf::from("/app/tests/all.ht"))
+   {
+      panic!("Should parse the project's entry, source directories, and scripts.")
+   }
+}
+
+#[test]
+fn report_invalid_project_manifests() {
+   for src in [
+      "entry = \"main.ht\"",
+      "[package]",
+      "[project]\nentry = main.ht",
+      "[project]\nsource_dirs = \"src\"",
+      "[project]\nversion = \"1.0\"",
+   ] {
+      if Manifest::parse(src, Path::new("/app")).is_ok() {
+         panic!("Should report an error for the manifest '{}'.", src)
+      }
+   }
+}
+
+#[test]
+fn modules_are_imported_from_module_dirs() {
+   let dir = std::env::temp_dir().join(format!("hinton_project_test_{}", std::process::id()));
+   std::fs::create_dir_all(dir.join("lib")).unwrap();
+   std::fs::write(dir.join("lib/util.ht"), "export func double(x) { return x * 2; }").unwrap();
+
+   let src = "import util from 'util'; assert_eq(util.double(4), 8);";
+
+   let result = VM::new(dir.join("main.ht"))
+      .with_module_dirs(vec![dir.join("lib")])
+      .interpret(src);
+
+   std::fs::remove_dir_all(&dir).unwrap();
+
+   if !matches!(result, InterpretResult::Ok) {
+      panic!("Should import modules from the module directories.")
+   }
+}
@@ -0,0 +1,59 @@
+use std::path::PathBuf;
+
+use crate::built_in::BuiltIn;
+use crate::core::serialization::{deserialize, serialize};
+use crate::objects::FuncObject;
+use crate::{compiler::Compiler, parser::Parser};
+
+fn compile(src: &str) -> FuncObject {
+   let program = match Parser::parse(src) {
+      Ok(ast) => ast,
+      Err(_) => panic!("Parser Had Errors."),
+   };
+
+   match Compiler::compile_ast(&PathBuf::new(), &program, &BuiltIn::default()) {
+      Ok(f) => f,
+      Err(_) => panic!("Compiler Had Errors."),
+   }
+}
+
+#[test]
+fn bytecode_survives_serialization_round_trip() {
+   let module = compile(
+      "var a = 3.5; const b = 'hello'; const c = 99999999999999999999n; \
+       func f(x) { try { return x / 0; } catch { return 700000; } }",
+   );
+
+   let bytes = match serialize(&module) {
+      Ok(b) => b,
+      Err(_) => panic!("Should serialize the compiled program."),
+   };
+
+   match deserialize(&bytes) {
+      Ok(f) => {
+         if serialize(&f) != Ok(bytes) {
+            panic!("Deserialized program should be identical to the original program.")
+         }
+      }
+      Err(_) => panic!("Should deserialize the serialized program."),
+   }
+}
+
+#[test]
+fn reject_corrupted_bytecode() {
+   let mut bytes = match serialize(&compile("print(1 + 2);")) {
+      Ok(b) => b,
+      Err(_) => panic!("Should serialize the compiled program."),
+   };
+
+   let last = bytes.len() - 1;
+   bytes[last] ^= 0xFF;
+
+   if deserialize(&bytes).is_ok() {
+      panic!("Should reject bytecode files whose checksum does not match.")
+   }
+
+   if deserialize(b"not bytecode").is_ok() {
+      panic!("Should reject files without the bytecode header.")
+   }
+}
@@ -0,0 +1,66 @@
+use crate::compiler::{OptLevel, WarningMode};
+use crate::parser::Parser;
+use crate::test_runner::{discover_tests, run_test_file, TestOptions, TestSummary};
+
+#[test]
+fn discover_test_functions() {
+   let src = "
+      func test_first() {}
+      func helper() {}
+      export func test_second() {}
+      func test_with_args(x) {}
+      func outer() { func test_nested() {} }
+      var test_value = 1;
+   ";
+
+   let program = match Parser::parse(src) {
+      Ok(ast) => ast,
+      Err(_) => panic!("Parser Had Errors."),
+   };
+
+   if discover_tests(&program) != vec!["test_first", "test_second"] {
+      panic!("Should discover the top-level test functions without parameters.")
+   }
+}
+
+#[test]
+fn run_tests_and_count_failures() {
+   let dir = std::env::temp_dir().join(format!("hinton_test_runner_{}", std::process::id()));
+   std::fs::create_dir_all(&dir).unwrap();
+
+   let path = dir.join("math_test.ht");
+   std::fs::write(
+      &path,
+      "
+      var calls = 0;
+      func test_passes() { calls += 1; assert calls == 1; }
+      func test_fresh_state() { calls += 1; assert_eq(calls, 1); }
+      func test_fails() { assert 1 + 1 == 3; }
+      ",
+   )
+   .unwrap();
+
+   let mut options = TestOptions {
+      filter: None,
+      warning_mode: WarningMode::Silence,
+      opt_level: OptLevel::default(),
+      module_dirs: vec![],
+   };
+
+   let mut summary = TestSummary::default();
+   run_test_file(&path, &options, &mut summary);
+
+   options.filter = Some("passes");
+   let mut filtered = TestSummary::default();
+   run_test_file(&path, &options, &mut filtered);
+
+   std::fs::remove_dir_all(&dir).unwrap();
+
+   if summary.passed != 2 || summary.failed != 1 || summary.is_ok() {
+      panic!("Should run each test in a fresh VM, and count the failed tests.")
+   }
+
+   if filtered.passed != 1 || filtered.failed != 0 || filtered.filtered != 2 {
+      panic!("Should only run the tests that match the filter.")
+   }
+}
@@ -0,0 +1,2592 @@
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use crate::compiler::Compiler;
+use crate::core::bytecode::OpCode;
+use crate::errors::ObjectOprErrType;
+use crate::objects::Object;
+use crate::parser::Parser;
+use crate::virtual_machine::{
+   Breakpoints, DebugCommand, DebugHook, Debugger, InterpretResult, OutputBuffer, PauseReason,
+   SourceLocation, TraceMode, VmConfig, VM,
+};
+
+fn run(src: &str) {
+   if !matches!(VM::new(PathBuf::new()).interpret(src), InterpretResult::Ok) {
+      panic!("Program Had Errors.")
+   }
+}
+
+#[test]
+fn match_arms_are_selected_with_and_without_jump_tables() {
+   // The first function has dense integer arms (compiled into a jump table),
+   // while the second has a string arm (compiled into cascaded comparisons).
+   run("
+      func dense(x) {
+         match x { 0 => return 'a'; 1, 2 => return 'b'; 3..6 => return 'c'; 6 => return 'a'; else => return 'd'; }
+      }
+      func sparse(x) {
+         match x { 0 => return 'a'; 1, 2 => return 'b'; 3..6 => return 'c'; 6, 'a' => return 'a'; else => return 'd'; }
+      }
+
+      for var x in [0, 1, 2, 3, 5, 6, 7, 2.0, 4.5, -1, true, 'z', null] {
+         assert_eq(dense(x), sparse(x));
+      }
+
+      assert_eq(dense(4.5), 'c');
+      assert_eq(dense(6), 'a');
+      assert_eq(sparse('z'), 'd');
+   ");
+}
+
+#[test]
+fn break_and_continue_inside_match_arms() {
+   run(
+      "
+      var total = 0;
+      for var i in 0..10 {
+         match i { 1 => continue; 5 => break; else => total += i }
+      }
+      assert_eq(total, 9);
+   ",
+   );
+}
+
+#[test]
+fn match_arms_destructure_and_bind_values() {
+   run(
+      "
+      func describe(v) {
+         match v {
+            n if n is Int and n < 0 => return 'negative';
+            [] => return 'empty';
+            [x, [y, _]] => return x + y;
+            [1..5, b] => return b;
+            {name, age: a} if a >= 18 => return name;
+            {'kind': 'cat'} => return 'cat';
+            _ => return 'other';
+         }
+      }
+
+      assert_eq(describe(-3), 'negative');
+      assert_eq(describe([]), 'empty');
+      assert_eq(describe([1, [2, 3]]), 3);
+      assert_eq(describe((4, 8)), 8);
+      assert_eq(describe([9, 8]), 'other');
+      assert_eq(describe({name: 'Ann', age: 30}), 'Ann');
+      assert_eq(describe({name: 'Bob', age: 3}), 'other');
+      assert_eq(describe({kind: 'cat', age: 3}), 'cat');
+      assert_eq(describe('cat'), 'other');
+
+      // The bindings of arms whose guard is false are popped off the stack.
+      var total = 0;
+      for var i in 0..100 {
+         var before = i;
+         match [i] { [x] if x > 90 => total += x; [_] => total += 0; }
+         assert_eq(before, i);
+      }
+      assert_eq(total, 855);
+   ",
+   );
+}
+
+#[test]
+fn optional_chains_short_circuit_and_non_null_assertions_throw() {
+   run(
+      "
+      var user = {name: 'Ann', address: null};
+      var calls = 0;
+      func count() { calls += 1; return 1; }
+
+      assert_eq(user?.name, 'Ann');
+      assert_eq(user.address?.city, null);
+      assert_eq(user.address?.city.zip[count()], null);
+      assert_eq(user.address?.city(count()), null);
+      assert_eq(calls, 0);
+      assert_eq(user.name?.len(), 3);
+      assert_eq(user.address?.city ?? 'none', 'none');
+      assert_eq(true ?.5 : 1, 0.5);
+      assert_eq(!!user.address, false);
+
+      assert_eq(user.name!!, 'Ann');
+      var message = null;
+      try { user.address!!; } catch (e) { message = [e.name, e.message]; }
+      assert_eq(message, ['NullError', \"Expected 'user.address' not to be null.\"]);
+
+      try { user.address?.city!!; } catch (e) { message = e.message; }
+      assert_eq(message, \"Expected 'user.address?.city' not to be null.\");
+   ",
+   );
+}
+
+#[test]
+fn enum_variants_carry_payloads() {
+   run(
+      "
+      enum Shape { Circle(r), Rect(w, h), Empty }
+
+      func area(s) {
+         match s {
+            Shape.Circle(r) => return 3 * r * r;
+            Shape.Rect(w, h) if w == h => return w * w;
+            Shape.Rect(w, _) => return w;
+            Shape.Empty => return 0;
+         }
+      }
+
+      var c = Shape.Circle(2);
+      assert_eq(c.r, 2);
+      assert_eq(c[0], 2);
+      assert(c is Shape);
+      assert_eq(c, Shape.Circle(2));
+      assert(c != Shape.Circle(3));
+      assert_eq(Shape.Empty, Shape.Empty);
+      assert_eq(area(c), 12);
+      assert_eq(area(Shape.Rect(3, 3)), 9);
+      assert_eq(area(Shape.Rect(5, 2)), 5);
+      assert_eq(area(Shape.Empty), 0);
+   ",
+   );
+}
+
+#[test]
+fn host_programs_can_register_native_functions() {
+   let calls = Rc::new(RefCell::new(0));
+   let mut vm = VM::new(PathBuf::new());
+
+   let counter = calls.clone();
+   vm.register_native("double", 1, move |args| {
+      *counter.borrow_mut() += 1;
+
+      match args[0] {
+         Object::Int(x) => Ok(Object::Int(x * 2)),
+         _ => Err(ObjectOprErrType::TypeError(String::from("Expected an Int."))),
+      }
+   });
+
+   let src = "assert_eq(double(21), 42); try { double('a'); } catch (e) { assert_eq(e.name, 'TypeError'); }";
+   if !matches!(vm.interpret(src), InterpretResult::Ok) {
+      panic!("Program Had Errors.")
+   }
+
+   if *calls.borrow() != 2 {
+      panic!("Registered native functions should be called by the program.")
+   }
+}
+
+#[test]
+fn plugins_add_namespaces_with_native_functions() {
+   use crate::built_in::plugins::{register_plugin, Plugin, PLUGIN_ABI_VERSION, PLUGIN_BUILD_ID};
+
+   fn register(plugin: &mut Plugin) {
+      plugin.add_function("shout", 1, |args| {
+         Ok(Object::from(format!("{}!", args[0]).to_uppercase()))
+      });
+   }
+
+   let build_id = PLUGIN_BUILD_ID.trim_end_matches('\0');
+
+   if register_plugin("outdated", PLUGIN_ABI_VERSION + 1, build_id, register).is_ok() {
+      panic!("Plugins built for another ABI version should be rejected.")
+   }
+
+   if register_plugin(
+      "mismatched",
+      PLUGIN_ABI_VERSION,
+      "hinton 0.0.0, rustc 1.0.0",
+      register,
+   )
+   .is_ok()
+   {
+      panic!("Plugins built with another compiler or crate version should be rejected.")
+   }
+
+   let greeter = Object::from(register_plugin("greeter", PLUGIN_ABI_VERSION, build_id, register).unwrap());
+   let mut vm = VM::new(PathBuf::new());
+   vm.register_native("greeter", 0, move |_| Ok(greeter.clone()));
+
+   let src = "
+      assert_eq(greeter().shout('hi'), 'HI!');
+      try { load_plugin('/missing/libplugin.so'); } catch (e) { assert_eq(e.name, 'IOError'); }
+   ";
+   if !matches!(vm.interpret(src), InterpretResult::Ok) {
+      panic!("Program Had Errors.")
+   }
+}
+
+#[test]
+fn math_namespace_functions() {
+   run(
+      "
+      assert_eq(Math.sqrt(16), 4.0);
+      assert_eq(Math.floor(2.7), 2.0);
+      assert_eq(Math.ceil(2.2), 3.0);
+      assert_eq(Math.abs(0 - 5), 5);
+      assert_eq(Math.min(3, 7), 3);
+      assert_eq(Math.max(3, 7.5), 7.5);
+      assert_eq(Math.clamp(15, 0, 10), 10);
+      assert_eq(Math.log(Math.exp(2)), 2.0);
+      assert_eq(Math.log(8, 2), 3.0);
+      assert(Math.abs(Math.sin(Math.pi / 2) - 1) < 0.000001);
+      assert(Math.abs(Math.cos(0) - 1) < 0.000001);
+      assert(Math.tan(0) == 0);
+      assert(Math.e > 2.71 and Math.e < 2.72);
+
+      for var i in 0..100 {
+         var r = Math.random();
+         assert(r >= 0 and r < 1);
+      }
+
+      try { Math.sqrt('a'); } catch (e) { assert_eq(e.name, 'TypeError'); }
+      try { Math.clamp(1, 10, 0); } catch (e) { assert_eq(e.name, 'ArgumentError'); }
+   ",
+   );
+}
+
+#[test]
+fn random_namespace_is_reproducible_with_a_seed() {
+   run(
+      "
+      func sample() {
+         var items = [1, 2, 3, 4, 5, 6, 7, 8];
+         Random.shuffle(items);
+         return [Random.int(1, 6), Random.float(), Random.choice(['a', 'b', 'c']), items];
+      }
+
+      Random.seed(42);
+      var first = sample();
+      Random.seed(42);
+      assert_eq(sample(), first);
+      Random.seed(43);
+      assert(sample() != first);
+
+      var shuffled = first[3];
+      shuffled.sort();
+      assert_eq(shuffled, [1, 2, 3, 4, 5, 6, 7, 8]);
+
+      var seen = Set();
+      for var i in 0..200 {
+         var n = Random.int(0 - 2, 2);
+         assert(n >= 0 - 2 and n <= 2);
+         seen.add(n);
+
+         var f = Random.float();
+         assert(f >= 0 and f < 1);
+      }
+      assert_eq(seen.len(), 5);
+      assert_eq(Random.int(7, 7), 7);
+
+      try { Random.int(3, 1); } catch (e) { assert_eq(e.name, 'ArgumentError'); }
+      try { Random.int(1.5, 2); } catch (e) { assert_eq(e.name, 'TypeError'); }
+      try { Random.choice([]); } catch (e) { assert_eq(e.name, 'IndexError'); }
+      try { Random.shuffle('abc'); } catch (e) { assert_eq(e.name, 'TypeError'); }
+   ",
+   );
+}
+
+#[test]
+fn uuids_and_tokens_are_unique_and_well_formed() {
+   run(
+      "
+      var ids = Set();
+      for var _ in 0..100 {
+         var id = Uuid.v4();
+         assert_eq(id.len(), 36);
+         assert_eq(id[14], '4');
+         assert(['8', '9', 'a', 'b'].index_of(id[19]) >= 0);
+         assert_eq(id.split('-').map(fn (part) { return part.len(); }), [8, 4, 4, 4, 12]);
+         ids.add(id);
+      }
+      assert_eq(ids.len(), 100);
+
+      assert_eq(Random.token(16).len(), 32);
+      assert_eq(Encoding.hex_decode(Random.token(5)).len(), 5);
+
+      Random.seed(7);
+      var token = Random.token(16);
+      Random.seed(7);
+      assert(Random.token(16) != token);
+
+      try { Random.token(0); } catch (e) { assert_eq(e.name, 'ArgumentError'); }
+      try { Random.token('16'); } catch (e) { assert_eq(e.name, 'TypeError'); }
+   ",
+   );
+}
+
+#[test]
+fn format_strings_with_alignment_width_and_precision() {
+   run(
+      "
+      assert_eq(format('{} + {} = {}', 1, 2, 3), '1 + 2 = 3');
+      assert_eq(format('[{:>6}] [{:<6}] [{:^7}]', 'ab', 'cd', 'mid'), '[    ab] [cd    ] [  mid  ]');
+      assert_eq(format('[{:6}] [{:6}]', 'ab', 42), '[ab    ] [    42]');
+      assert_eq(format('{:*^9}', 'x'), '****x****');
+      assert_eq(format('{:05} {:05}', 42, 0 - 7), '00042 -0007');
+      assert_eq(format('{:.2} {:8.3} {:.0}', 3.14159, 2, 2.5), '3.14    2.000 2');
+      assert_eq(format('{:.3}', 'truncated'), 'tru');
+      assert_eq(format('{1:>3}{0:<3}|', 'a', 'b'), '  ba  |');
+      assert_eq('{:>4}'.format(1), '   1');
+
+      try { format('{:x}', 1); assert(false); } catch (e) { assert_eq(e.name, 'ArgumentError'); }
+      try { format('{:.}', 1); assert(false); } catch (e) { assert_eq(e.name, 'ArgumentError'); }
+      try { format(1); assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+   ",
+   );
+}
+
+#[test]
+fn crypto_and_encoding_namespaces() {
+   run(
+      "
+      assert_eq(Crypto.md5(''), 'd41d8cd98f00b204e9800998ecf8427e');
+      assert_eq(Crypto.md5('abc'), '900150983cd24fb0d6963f7d28e17f72');
+      assert_eq(Crypto.sha1('abc'), 'a9993e364706816aba3e25717850c26c9cd0d89d');
+      assert_eq(Crypto.sha256(''), 'e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855');
+      assert_eq(Crypto.sha256(Bytes('abc')), Crypto.sha256('abc'));
+
+      var long = '';
+      for var _ in 0..200 { long += 'a'; }
+      assert_eq(Crypto.sha256(long), 'c2a908d98f5df987ade41b5fce213067efbcc21ef2240212a41e54b5e7c28ae5');
+      assert_eq(Crypto.md5(long), '887f30b43b2867f4a9accceee7d16e6c');
+
+      assert_eq(
+         Crypto.hmac('sha256', 'key', 'The quick brown fox jumps over the lazy dog'),
+         'f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8'
+      );
+      assert_eq(Crypto.hmac('SHA-1', 'key', Bytes('msg')), '102900b72b7bf1031eec76b4804b66052376896b');
+
+      assert_eq(Encoding.base64_encode('hello!?'), 'aGVsbG8hPw==');
+      assert_eq(Encoding.base64_encode('ab'), 'YWI=');
+      assert_eq(Encoding.base64_encode(''), '');
+      assert_eq(Encoding.base64_decode('aGVsbG8hPw==').decode(), 'hello!?');
+      assert_eq(Encoding.base64_decode('YWI').decode(), 'ab');
+      assert_eq(Encoding.base64_decode('-_-_'), Encoding.base64_decode('+/+/'));
+      assert_eq(Encoding.hex_encode('hi'), '6869');
+      assert_eq(Encoding.hex_decode('6869').decode(), 'hi');
+      assert_eq(Bytes('aGk=', 'base64').decode('base64'), 'aGk=');
+
+      try { Crypto.sha256(5); } catch (e) { assert_eq(e.name, 'TypeError'); }
+      try { Crypto.hmac('sha3', 'key', 'msg'); } catch (e) { assert_eq(e.name, 'ArgumentError'); }
+      try { Encoding.base64_decode('a$'); } catch (e) { assert_eq(e.name, 'ArgumentError'); }
+      try { Encoding.hex_decode('abc'); } catch (e) { assert_eq(e.name, 'ArgumentError'); }
+   ",
+   );
+}
+
+#[test]
+fn file_namespace_reads_and_writes_files() {
+   let dir = std::env::temp_dir().join(format!("hinton_file_test_{}", std::process::id()));
+   std::fs::create_dir_all(&dir).unwrap();
+
+   run(&format!(
+      "
+      const dir = '{}';
+      const path = dir + '/notes.txt';
+
+      assert(not File.exists(path));
+      File.write(path, 'first');
+      File.append(path, '\nsecond');
+      assert(File.exists(path));
+      assert_eq(File.read(path), 'first\nsecond');
+      assert_eq(File.lines(path), ['first', 'second']);
+
+      File.write_bytes(dir + '/data.bin', [0, 104, 255]);
+      assert_eq(File.read_bytes(dir + '/data.bin'), Bytes([0, 104, 255]));
+      File.write_bytes(dir + '/data.bin', 'hi'.encode());
+      assert_eq(File.read_bytes(dir + '/data.bin').decode(), 'hi');
+      assert_eq(File.list_dir(dir), ['data.bin', 'notes.txt']);
+
+      try {{ File.read(dir + '/missing.txt'); }} catch (e) {{ assert_eq(e.name, 'IOError'); }}
+   ",
+      dir.display()
+   ));
+
+   std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn http_and_net_namespaces_talk_to_servers() {
+   use std::io::{Read, Write};
+
+   let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+   let port = listener.local_addr().unwrap().port();
+
+   let server = std::thread::spawn(move || {
+      // Answers a GET with a sized body, and echoes the body of a POST with chunks.
+      for response in [
+         String::from("HTTP/1.1 200 OK\r\nContent-Length: 2\r\nX-Test: yes\r\n\r\nhi"),
+         String::from("HTTP/1.1 201 Created\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nabc\r\n0\r\n\r\n"),
+      ] {
+         let (mut stream, _) = listener.accept().unwrap();
+         let mut request = [0; 1024];
+         let _ = stream.read(&mut request).unwrap();
+         stream.write_all(response.as_bytes()).unwrap();
+      }
+
+      // Echoes a line back through a raw socket.
+      let (mut stream, _) = listener.accept().unwrap();
+      let mut line = [0; 5];
+      stream.read_exact(&mut line).unwrap();
+      stream.write_all(&line).unwrap();
+   });
+
+   run(&format!(
+      "
+      const url = 'http://127.0.0.1:{}';
+
+      var res = Http.get(url + '/path?q=1', {{'Accept': 'text/plain'}});
+      assert_eq(res.status, 200);
+      assert_eq(res.reason, 'OK');
+      assert_eq(res.headers['x-test'], 'yes');
+      assert_eq(res.body, 'hi');
+
+      res = Http.post(url, 'data');
+      assert_eq(res.status, 201);
+      assert_eq(res.bytes, Bytes([97, 98, 99]));
+
+      var socket = Net.connect('127.0.0.1', {});
+      socket.write('ping\n');
+      assert_eq(socket.read_line(), 'ping');
+      socket.close();
+      assert(socket.is_closed());
+
+      try {{ Http.get('https://example.com'); }} catch (e) {{ assert_eq(e.name, 'IOError'); }}
+      try {{ Net.connect('127.0.0.1', 70000); }} catch (e) {{ assert_eq(e.name, 'ArgumentError'); }}
+   ",
+      port, port
+   ));
+
+   server.join().unwrap();
+}
+
+#[test]
+fn http_serve_calls_the_handler_for_each_request() {
+   use std::io::{Read, Write};
+
+   // Finds a free port for the server.
+   let port = std::net::TcpListener::bind("127.0.0.1:0")
+      .unwrap()
+      .local_addr()
+      .unwrap()
+      .port();
+
+   let client = std::thread::spawn(move || {
+      let send = |request: &str| loop {
+         // Retries until a server is listening (the connections made while the previous server
+         // shuts down are reset).
+         let response = std::net::TcpStream::connect(("127.0.0.1", port)).and_then(|mut stream| {
+            stream.write_all(request.as_bytes())?;
+            let mut response = String::new();
+            stream.read_to_string(&mut response)?;
+            Ok(response)
+         });
+
+         match response {
+            Ok(response) if !response.is_empty() => return response,
+            _ => std::thread::sleep(std::time::Duration::from_millis(5)),
+         }
+      };
+
+      let hello = send("GET /hello?name=J%C3%BCrgen+X HTTP/1.1\r\nHost: localhost\r\n\r\n");
+      assert!(hello.starts_with("HTTP/1.1 200 OK\r\n"));
+      assert!(hello.ends_with("\r\n\r\nHello, Jürgen X!"));
+
+      let echo = send("POST /echo HTTP/1.1\r\nX-Id: 7\r\nContent-Length: 4\r\n\r\ndata");
+      assert!(echo.starts_with("HTTP/1.1 201 Created\r\n"));
+      assert!(echo.contains("\r\nX-Id: 7\r\n"));
+      assert!(echo.ends_with("\r\n\r\nPOST data"));
+
+      let fail = send("GET /fail HTTP/1.1\r\n\r\n");
+      assert!(fail.starts_with("HTTP/1.1 500 Internal Server Error\r\n"));
+   });
+
+   run(&format!(
+      "
+      var served = [];
+
+      func handle(req) {{
+         served.push(req.path);
+
+         if req.path == '/hello' {{ return 'Hello, ' + req.query['name'] + '!'; }}
+         if req.path == '/echo' {{
+            return {{'status': 201, 'headers': {{'X-Id': req.headers['x-id']}}, 'body': req.method + ' ' + req.body}};
+         }}
+
+         return [][3];
+      }}
+
+      assert_eq(Http.serve({}, handle, 2), null);
+      assert_eq(served, ['/hello', '/echo']);
+
+      // An error in the handler stops the server.
+      try {{ Http.serve({}, handle); }} catch (e) {{ assert_eq(e.name, 'IndexError'); }}
+      assert_eq(served.len(), 3);
+   ",
+      port, port
+   ));
+
+   client.join().unwrap();
+}
+
+#[test]
+fn json_parse_and_stringify_round_trip() {
+   run(
+      r#"
+      var doc = Json.parse('{"name": "hinton", "tags": [1, 2.5, true, null], "nested": {"x": -3e2}}');
+      assert_eq(doc["name"], "hinton");
+      assert_eq(doc["tags"], [1, 2.5, true, null]);
+      assert_eq(doc["nested"]["x"], -300.0);
+
+      assert_eq(Json.stringify(doc), '{"name":"hinton","nested":{"x":-300.0},"tags":[1,2.5,true,null]}');
+      assert_eq(Json.stringify([1, [2]], 1), '[\n 1,\n [\n  2\n ]\n]');
+      assert_eq(Json.stringify(Json.parse(Json.stringify(doc))), Json.stringify(doc));
+
+      var cyclic = [1];
+      cyclic.push(cyclic);
+      try { Json.stringify(cyclic); assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+      try { Json.stringify(print); assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+      try { Json.parse('[1, 2'); assert(false); } catch (e) { assert_eq(e.name, 'ArgumentError'); }
+   "#,
+   );
+}
+
+#[test]
+fn each_for_loop_iteration_has_a_fresh_binding() {
+   run(
+      "
+      var fns = [];
+      for var i in 0..4 {
+         if i == 1 { continue; }
+         fns.push(fn () { return i; });
+         i = i * 10;
+         if i == 20 { break; }
+      }
+
+      assert_eq(fns.len(), 2);
+      assert_eq(fns[0](), 0);
+      assert_eq(fns[1](), 20);
+   ",
+   );
+}
+
+#[test]
+fn host_programs_can_inspect_closures() {
+   let src = "
+      func counter(start) {
+         var count = start;
+         func next() { count += 1; return count; }
+         return next;
+      }
+
+      var c = counter(10);
+      c();
+      c;
+   ";
+
+   let program = match Parser::parse(src) {
+      Ok(ast) => ast,
+      Err(_) => panic!("Parser Had Errors."),
+   };
+
+   let mut vm = VM::new(PathBuf::new());
+   let module = match Compiler::new_repl(&PathBuf::new(), &vm.built_in).compile_repl_input(&program) {
+      Ok(m) => m,
+      Err(_) => panic!("Compiler Had Errors."),
+   };
+
+   if !matches!(vm.execute(module, src), InterpretResult::Ok) {
+      panic!("Program Had Errors.")
+   }
+
+   let closure = vm.last_value().unwrap();
+   let captured = match vm.inspect_closure(&closure) {
+      Some(c) => c,
+      None => panic!("Closures should be inspectable."),
+   };
+
+   match captured.as_slice() {
+      [v] if v.name == "count" && v.value == Object::Int(11) && !v.is_open => {}
+      _ => panic!("Closures should expose the variables they captured."),
+   }
+
+   if vm.inspect_closure(&Object::Int(1)).is_some() {
+      panic!("Only functions should be inspectable.")
+   }
+}
+
+#[test]
+fn strings_process_escape_sequences() {
+   run(
+      r#"
+      assert_eq("a\tb".len(), 3);
+      assert_eq("\\n".len(), 2);
+      assert_eq("\u{48}\u{69}", "Hi");
+      assert_eq("\"q\" \$x", '"q" $' + "x");
+      assert_eq(r"\n${x}".len(), 6);
+      assert_eq({"k\n": 1}["k\n"], 1);
+   "#,
+   );
+}
+
+/// The line, reason, and local variables of each pause recorded by the debugger.
+type Pauses = Rc<RefCell<Vec<(usize, PauseReason, Vec<String>)>>>;
+
+/// A debugger front-end that records every pause, and resumes with a list of commands.
+struct RecordingHook {
+   commands: Vec<DebugCommand>,
+   pauses: Pauses,
+}
+
+impl DebugHook for RecordingHook {
+   fn on_pause(
+      &mut self,
+      vm: &VM,
+      loc: &SourceLocation,
+      reason: PauseReason,
+      _: &mut Breakpoints,
+   ) -> DebugCommand {
+      let locals = vm
+         .frame_locals(vm.frames_stack().len() - 1)
+         .iter()
+         .map(|(name, value)| format!("{}={}", name, value.as_plain_string()))
+         .collect();
+
+      self.pauses.borrow_mut().push((loc.line, reason, locals));
+      self.commands.pop().unwrap_or(DebugCommand::Continue)
+   }
+}
+
+#[test]
+fn debugger_pauses_at_breakpoints_and_steps() {
+   let src = "var total = 0;
+      func add(a, b) {
+         var sum = a + b;
+         return sum;
+      }
+      for var i in 0..2 {
+         total = add(total, i);
+      }";
+
+   let pauses = Rc::new(RefCell::new(vec![]));
+   let hook = RecordingHook {
+      // Commands are popped from the back.
+      commands: vec![DebugCommand::StepOut, DebugCommand::StepOver],
+      pauses: pauses.clone(),
+   };
+
+   let mut breakpoints = Breakpoints::default();
+   breakpoints.add(&PathBuf::new(), 3);
+
+   let debugger = Debugger::with_breakpoints(Box::new(hook), breakpoints);
+   if !matches!(
+      VM::new(PathBuf::new()).with_debugger(debugger).interpret(src),
+      InterpretResult::Ok
+   ) {
+      panic!("Program Had Errors.")
+   }
+
+   let pauses = pauses.borrow();
+   let lines: Vec<usize> = pauses.iter().map(|p| p.0).collect();
+   assert_eq!(lines, vec![3, 4, 7, 3]);
+
+   assert_eq!(pauses[0].1, PauseReason::Breakpoint);
+   assert_eq!(pauses[0].2, vec!["a=0", "b=0"]);
+   assert_eq!(pauses[1].1, PauseReason::Step);
+   assert_eq!(pauses[1].2, vec!["a=0", "b=0", "sum=0"]);
+   assert_eq!(pauses[2].2, vec!["i=0"]);
+   assert_eq!(pauses[3].1, PauseReason::Breakpoint);
+   assert_eq!(pauses[3].2, vec!["a=0", "b=1"]);
+}
+
+#[test]
+fn named_arguments_are_bound_to_parameters() {
+   run(
+      "
+      func greet(name, greeting = 'Hello', end := '!') { return greeting + ', ' + name + end; }
+      assert_eq(greet('Ana'), 'Hello, Ana!');
+      assert_eq(greet(name: 'Ana'), 'Hello, Ana!');
+      assert_eq(greet('Ana', end: '?'), 'Hello, Ana?');
+      assert_eq(greet(end: '.', greeting: 'Hi', name: 'Bo'), 'Hi, Bo.');
+
+      class Point {
+         pub var x, y;
+         pub func init(x, y = 0) { self.x = x; self.y = y; }
+         pub func shifted(dx = 0, dy = 0) { return [self.x + dx, self.y + dy]; }
+      }
+      var p = new Point(y: 2, x: 1);
+      assert_eq(p.shifted(dy: 1), [1, 3]);
+
+      var sub = fn (a, b = 1) { return a - b; };
+      assert_eq(sub(b: 3, a: 10), 7);
+
+      try { greet(nme: 'Ana'); assert(false); } catch (e) { assert_eq(e.name, 'ArgumentError'); }
+      try { greet('Ana', name: 'Bo'); assert(false); } catch (e) { assert_eq(e.name, 'ArgumentError'); }
+      try { greet(greeting: 'Hi'); assert(false); } catch (e) { assert_eq(e.name, 'ArgumentError'); }
+      try { print(value: 1); assert(false); } catch (e) { assert_eq(e.name, 'ArgumentError'); }
+   ",
+   );
+}
+
+#[test]
+fn variadic_functions_collect_extra_arguments() {
+   run(
+      "
+      func sum(...nums) { var total = 0; for var n in nums { total += n; } return total; }
+      assert_eq(sum(), 0);
+      assert_eq(sum(1, 2, 3), 6);
+      assert_eq(sum(1, ...[2, 3], 4, ...(5, 6)), 21);
+
+      func split(first, second = 0, ...rest) { return [first, second, rest]; }
+      assert_eq(split(1), [1, 0, []]);
+      assert_eq(split(1, 2, 3, 4), [1, 2, [3, 4]]);
+      assert_eq(split(...[7, 8, 9]), [7, 8, [9]]);
+      assert_eq(split(second: 5, first: 1), [1, 5, []]);
+
+      class Bag { pub var items; pub func init(...items) { self.items = items; } }
+      var bag = new Bag(...[1, 2]);
+      assert_eq(bag.items, [1, 2]);
+
+      try { split(); assert(false); } catch (e) { assert_eq(e.name, 'ArgumentError'); }
+      try { sum(...5); assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+   ",
+   );
+}
+
+#[test]
+fn tail_calls_run_in_constant_frame_space() {
+   run(
+      "
+      func count(n, acc = 0) { if n == 0 { return acc; } return count(n - 1, acc + 1); }
+      assert_eq(count(100000), 100000);
+
+      var is_odd;
+      func is_even(n) { if n == 0 { return true; } return is_odd(n - 1); }
+      is_odd = fn (n) { if n == 0 { return false; } return is_even(n - 1); };
+      assert(is_even(20000));
+
+      class Counter { pub func down(n) { if n == 0 { return 'done'; } return self.down(n - 1); } }
+      var counter = new Counter();
+      assert_eq(counter.down(50000), 'done');
+
+      // Calls inside a `try` statement keep their frame, so their errors are still caught.
+      func safe(n) { try { if n == 0 { 1 / 0; } return safe(n - 1); } catch { return 'caught'; } }
+      assert_eq(safe(3), 'caught');
+   ",
+   );
+}
+
+#[test]
+fn returning_from_nested_blocks_closes_captured_locals() {
+   run(
+      "
+      func make(n) {
+         var x = n;
+         var get_x = fn () { return x; };
+         if n == 0 { return get_x; }
+         return make(n - 1);
+      }
+      assert_eq(make(5)(), 0);
+
+      func make_all() {
+         var fns = [];
+         for var i in 0..3 { fns.push(fn () { return i; }); }
+         if true { return fns; }
+      }
+      var fns = make_all();
+      var result = [];
+      for var f in fns { result.push(f()); }
+      assert_eq(result, [0, 1, 2]);
+   ",
+   );
+}
+
+#[test]
+fn failed_assertions_report_their_source() {
+   run(
+      "
+      var x = 3;
+      assert x == 3;
+      assert(x > 1, 'unreachable');
+
+      try { assert x  > 5, 'x is ' + x; assert(false); } catch (e) {
+         assert_eq(e.name, 'AssertionError');
+         assert_eq(e.message, 'Assertion `x  > 5` failed: x is 3');
+      }
+
+      try { assert(x == 4); assert(false); } catch (e) { assert_eq(e.message, 'Assertion `x == 4` failed.'); }
+
+      try { assert_eq([1, 2, 3], [1, 2, 4]); assert(false); } catch (e) {
+         assert_eq(e.message, 'Assertion values are not equal.\n  left:  [1, 2, 3]\n  right: [1, 2, 4]\n  first difference at index 2.');
+      }
+
+      try { assert_eq(1, '1'); assert(false); } catch (e) {
+         assert_eq(e.message, 'Assertion values are not equal.\n  left:  1 (Int)\n  right: 1 (String)');
+      }
+   ",
+   );
+}
+
+#[test]
+fn deferred_calls_run_when_functions_return() {
+   run(
+      "
+      var log = [];
+      func f(x) {
+         defer log.push('first');
+         defer { log.push('second ' + x); }
+         x = 5;
+         return x * 2;
+      }
+      assert_eq(f(1), 10);
+      assert_eq(log, ['second 5', 'first']);
+
+      // Deferred calls also run while an error propagates to its handler.
+      log = [];
+      func fail() { defer log.push('fail'); 1 / 0; }
+      func outer() { defer log.push('outer'); return fail(); }
+      try { outer(); assert(false); } catch (e) { assert_eq(e.name, 'ZeroDivisionError'); }
+      assert_eq(log, ['fail', 'outer']);
+
+      // Functions with deferred calls keep their frame in tail calls.
+      log = [];
+      func count(n) { defer log.push(n); if n == 0 { return 0; } return count(n - 1); }
+      count(3);
+      assert_eq(log, [0, 1, 2, 3]);
+   ",
+   );
+}
+
+#[test]
+fn if_expressions_evaluate_only_the_selected_branch() {
+   run(
+      "
+      var calls = 0;
+      func count(value) { calls += 1; return value; }
+      func sign(x) { return if x > 0 { 1 } else if x < 0 { -1 } else { count(0) }; }
+
+      assert_eq([sign(5), sign(-5), sign(0)], [1, -1, 0]);
+      assert_eq(calls, 1);
+      assert_eq(2 * if calls == 1 { 10 } else { count(20) }, 20);
+      assert_eq(calls, 1);
+   ",
+   );
+}
+
+#[test]
+fn compound_assignments_evaluate_subscript_and_property_targets_once() {
+   run(
+      "
+      var calls = 0;
+      func count(value) { calls += 1; return value; }
+
+      var arr = [1, 2, 3];
+      arr[count(1)] += 10;
+      count(arr)[count(0)] *= 5;
+      assert_eq(arr, [5, 12, 3]);
+      assert_eq(calls, 3);
+
+      var dict = {'a': 2};
+      dict['a'] **= 3;
+      dict['a'] -= 1;
+      dict['a'] %= 4;
+      assert_eq(dict['a'], 3);
+
+      class Box {
+         pub var value = 8;
+         pub var items = [1];
+         pub get first() { return self.items[0]; }
+         pub set first(v) { self.items[0] = v; }
+      }
+
+      var box = new Box();
+      count(box).value /= 2;
+      count(box).first += 41;
+      assert_eq([box.value, box.first], [4.0, 42]);
+      assert_eq(calls, 5);
+   ",
+   );
+}
+
+#[test]
+fn folded_constants_match_runtime_results() {
+   run(
+      "
+      var two = 2;
+      assert_eq(1 + 2 * 3, 1 + two * 3);
+      assert_eq(2 ** 10 % 7, two ** 10 % 7);
+      assert_eq('a' + 'b' + 'c', 'a' + 'b' + 'c' * (two - 1));
+      assert_eq(null ?? 5, null ?? two + 3);
+      assert_eq(~5 ^ 3, ~5 ^ (two + 1));
+      assert_eq(!0 and 'x', !(two - 2) and 'x');
+      assert_eq(false or 0 or 'y', false or (two - 2) or 'y');
+
+      var reached = false;
+      try {
+         var x = 1 / 0;
+      } catch (e) {
+         reached = true;
+      }
+      assert(reached);
+   ",
+   );
+}
+
+#[test]
+#[cfg(feature = "nan_boxing")]
+fn nan_boxed_values_round_trip() {
+   use crate::objects::nan_box::NanBox;
+
+   if std::mem::size_of::<NanBox>() != 8 {
+      panic!("NaN-boxed values should be stored in 8-byte words.")
+   }
+
+   for obj in [
+      Object::Null,
+      Object::Bool(true),
+      Object::Bool(false),
+      Object::Int(0),
+      Object::Int(-1),
+      Object::Int((1 << 47) - 1),
+      Object::Int(-(1 << 47)),
+      Object::Int(i64::MAX),
+      Object::Int(i64::MIN),
+      Object::Float(-0.5),
+      Object::Float(f64::INFINITY),
+      Object::from("hinton"),
+      Object::Tuple(Rc::new(vec![Object::Int(1), Object::Null])),
+   ] {
+      let boxed = NanBox::from(obj.clone());
+      let copy = boxed.clone();
+      drop(boxed);
+
+      if copy.decode().into_owned() != obj || copy.into_decoded() != obj {
+         panic!(
+            "NaN-boxed value should decode into the original object '{}'.",
+            obj
+         )
+      }
+   }
+
+   if !matches!(NanBox::from(Object::Float(f64::NAN)).into_decoded(), Object::Float(f) if f.is_nan()) {
+      panic!("NaN floats should be NaN-boxed as floats.")
+   }
+}
+
+#[test]
+fn identical_strings_are_interned() {
+   let src = "
+      var greeting = 'hello';
+      func get_greeting() { return 'hello'; }
+      var other = get_greeting();
+   ";
+
+   let mut vm = VM::new(PathBuf::new());
+   if !matches!(vm.interpret(src), InterpretResult::Ok) {
+      panic!("Program Had Errors.")
+   }
+
+   let globals = vm.global_values();
+   let strings: Vec<Rc<str>> = globals
+      .iter()
+      .filter_map(|(_, v)| match v {
+         Object::String(s) => Some(s.clone()),
+         _ => None,
+      })
+      .collect();
+
+   let interned = match vm.intern("hello") {
+      Object::String(s) => s,
+      _ => panic!("Interned strings should be String objects."),
+   };
+
+   match strings.as_slice() {
+      [a, b] if Rc::ptr_eq(a, b) && Rc::ptr_eq(a, &interned) => {}
+      _ => panic!("Identical string literals should share the same interned allocation."),
+   }
+}
+
+#[test]
+fn dictionaries_iterate_in_insertion_order() {
+   run(
+      "
+      var d = {'b': 1, 'a': 2, 'c': 3};
+      d['a'] = 4;
+      d['d'] = 5;
+
+      var keys = [];
+      var values = [];
+      for var key, value in d { keys.push(key); values.push(value); }
+      assert_eq(keys, ['b', 'a', 'c', 'd']);
+      assert_eq(values, [1, 4, 3, 5]);
+
+      // With a single loop variable, each entry is a (key, value) tuple.
+      var entries = [];
+      for var entry in d { entries.push(entry[0]); }
+      assert_eq(entries, keys);
+
+      // Other arrays and tuples can also be unpacked into two loop variables.
+      var sums = [];
+      for var a, b in [[1, 2], [3, 4]] { sums.push(a + b); }
+      assert_eq(sums, [3, 7]);
+
+      try { for var a, b in [[1, 2, 3]] {} assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+      try { for var a, b in [1] {} assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+   ",
+   );
+}
+
+#[test]
+fn classes_overload_operators_with_magic_methods() {
+   run(
+      "
+      class Vec2 {
+         pub var x, y;
+         pub func init(x, y) { self.x = x; self.y = y; }
+         func __add__(other) { return new Vec2(self.x + other.x, self.y + other.y); }
+         func __mul__(k) { return new Vec2(self.x * k, self.y * k); }
+         func __eq__(other) { return self.x == other.x and self.y == other.y; }
+         func __lt__(other) { return self.x < other.x; }
+         func __neg__() { return new Vec2(-self.x, -self.y); }
+         func __index__(i) { return i == 0 ? self.x : self.y; }
+         func __set_index__(i, v) { if i == 0 { self.x = v; } else { self.y = v; } }
+         func __str__() { return 'Vec2(' + self.x + ', ' + self.y + ')'; }
+      }
+
+      var a = new Vec2(1, 2);
+      assert(a + new Vec2(3, 4) * 2 == new Vec2(7, 10));
+      assert((-a) == new Vec2(-1, -2));
+      assert(a != new Vec2(2, 2));
+      assert(a < new Vec2(2, 0));
+      assert_eq(a[1], 2);
+      a[0] = 10;
+      assert_eq(a.x, 10);
+      assert_eq('${a}', 'Vec2(10, 2)');
+
+      // Errors raised by magic methods are caught by the caller's handlers.
+      class Bad { func __str__() { return 1 / 0; } }
+      try { print('${new Bad()}'); assert(false); } catch (e) { assert_eq(e.name, 'ZeroDivisionError'); }
+
+      // Without a magic method, the operators raise the usual errors.
+      class Plain {}
+      var p = new Plain();
+      try { p + 1; assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+      try { p[0] = 1; assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+   ",
+   );
+}
+
+#[test]
+fn subclasses_inherit_and_override_methods() {
+   run(
+      "
+      class Animal {
+         pub var name;
+         pub func init(name) { self.name = name; }
+         pub func speak() { return self.name + ' makes a sound'; }
+         pub func describe() { return 'I am ' + self.name; }
+         pub static func kingdom() { return 'Animalia'; }
+      }
+      class Dog extends Animal {
+         pub func init(name) { super.init(name + ' the dog'); }
+         pub func speak() { return super.speak() + ' (woof)'; }
+      }
+      class Puppy extends Dog {
+         pub func speak() { var f = fn () { return super.speak(); }; return f() + '!'; }
+      }
+
+      var dog = new Dog('Rex');
+      assert_eq(dog.speak(), 'Rex the dog makes a sound (woof)');
+      assert_eq(dog.describe(), 'I am Rex the dog');
+      assert_eq(Dog.kingdom(), 'Animalia');
+
+      // `super` is bound to the superclass of the class where the method is declared.
+      var puppy = new Puppy('Bo');
+      assert_eq(puppy.speak(), 'Bo the dog makes a sound (woof)!');
+
+      class Cat extends Animal { pub func purr() { return super.purr(); } }
+      try { (new Cat('Tom')).purr(); assert(false); } catch (e) { assert_eq(e.name, 'InstanceError'); }
+
+      var not_a_class = 5;
+      try { class Bad extends not_a_class {} assert(false); } catch (e) { assert_eq(e.name, 'InstanceError'); }
+   ",
+   );
+}
+
+#[test]
+fn classes_implement_interfaces() {
+   run(
+      "
+      interface Shape {
+         func area();
+         func scale(factor);
+      }
+      class Square implements Shape {
+         pub var side;
+         pub func init(side) { self.side = side; }
+         pub func area() { return self.side * self.side; }
+         pub func scale(factor, ...rest) { return new Square(self.side * factor); }
+      }
+      class Cube extends Square {}
+      class Base { pub func area() { return 0; } }
+
+      var cube = new Cube(2);
+      assert(instance_of(cube, Shape));
+      assert(instance_of(cube, Square));
+      assert(!instance_of(new Base(), Shape));
+      assert(!instance_of(new Square(1), Cube));
+      assert(!instance_of(5, Shape));
+
+      // Methods inherited from the superclass are checked at runtime.
+      try { class Bad extends Base implements Shape {} assert(false); } catch (e) { assert_eq(e.name, 'InstanceError'); }
+      try { class Bad implements Base {} assert(false); } catch (e) { assert_eq(e.name, 'InstanceError'); }
+      try { instance_of(cube, 5); assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+   ",
+   );
+}
+
+#[test]
+fn is_operator_tests_runtime_types() {
+   run(
+      "
+      interface Shape { func area(); }
+      class Square implements Shape { pub func area() { return 1; } }
+      class Cube extends Square {}
+      var cube = new Cube();
+
+      assert(cube is Cube and cube is Square and cube is Shape and cube is Any);
+      assert(!((new Square()) is Cube));
+      assert(!(cube is String));
+      assert(5 is Int and !(5 is Float) and 5.0 is Float);
+      assert('a' is String and null is Null and null is Void and true is Bool);
+      assert([1] is Array and {'a': 1} is Dict and (1, 2) is Tuple and 1..2 is Range);
+      assert(print is Function and fn () {} is Function and cube.area is Function);
+      assert(!(Square is Square));
+
+      var not_a_type = 5;
+      try { 5 is not_a_type; assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+   ",
+   );
+}
+
+#[test]
+fn static_members_are_stored_on_the_class() {
+   run(
+      "
+      class Counter {
+         pub static var count = 0;
+         pub static const MAX = 10;
+         pub var id;
+         pub func init() { Counter.count += 1; self.id = Counter.count; }
+         pub static func reset() { Counter.count = 0; }
+      }
+
+      var a = new Counter();
+      var b = new Counter();
+      assert_eq(Counter.count, 2);
+      assert_eq(b.id, 2);
+      Counter.reset();
+      assert_eq(Counter.count, 0);
+
+      try { a.count; assert(false); } catch (e) { assert_eq(e.name, 'ReferenceError'); }
+      try { Counter.id; assert(false); } catch (e) { assert_eq(e.name, 'ReferenceError'); }
+      try { Counter.MAX = 3; assert(false); } catch (e) { assert_eq(e.name, 'ReferenceError'); }
+   ",
+   );
+}
+
+#[test]
+fn property_accessors_run_on_access_and_assignment() {
+   run(
+      "
+      class Rect {
+         pub var w, h;
+         pub func init(w, h) { self.w = w; self.h = h; }
+         pub get area() { return self.w * self.h; }
+         pub get width() { return self.w; }
+         pub set width(v) { self.w = v; }
+         pub set height(v) { self.h = v; }
+      }
+
+      var r = new Rect(2, 3);
+      assert_eq(r.area, 6);
+      r.width = 5;
+      assert_eq(r.width, 5);
+      r.width += 1;
+      assert_eq(r.area, 18);
+      r.height = 10;
+      assert_eq(r.h, 10);
+
+      // Accessors are inherited like any other method.
+      class Square extends Rect { pub func init(s) { super.init(s, s); } }
+      assert_eq((new Square(4)).area, 16);
+
+      try { r.area = 3; assert(false); } catch (e) { assert_eq(e.name, 'ReferenceError'); }
+      try { r.height; assert(false); } catch (e) { assert_eq(e.name, 'ReferenceError'); }
+   ",
+   );
+}
+
+#[test]
+fn slices_arrays_strings_and_tuples() {
+   run(
+      "
+      var a = [0, 1, 2, 3, 4, 5];
+      assert_eq(a[1..4], [1, 2, 3]);
+      assert_eq(a[..-1], [0, 1, 2, 3, 4]);
+      assert_eq(a[-2..], [4, 5]);
+      assert_eq(a[..], a);
+      assert_eq(a[0..6..2], [0, 2, 4]);
+      assert_eq(a[....-1], [5, 4, 3, 2, 1, 0]);
+      assert_eq(a[4..1..-1], [4, 3, 2]);
+      assert_eq(a[-100..100], a);
+      assert_eq(a[3..1], []);
+
+      // Range objects can also be used as subscripts.
+      var r = 1..3;
+      assert_eq(a[r], [1, 2]);
+
+      assert_eq('héllo'[1..3], 'él');
+      assert_eq('héllo'[....-1], 'olléh');
+      assert_eq((1, 2, 3)[1..], (2, 3));
+
+      // The arithmetic negation only applies to its immediate operand.
+      assert_eq(-2 ** 2, -4);
+      assert(-1 == 0 - 1);
+
+      try { a[0....0]; assert(false); } catch (e) { assert_eq(e.name, 'IndexError'); }
+      try { a['x'..]; assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+      try { 5[1..]; assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+   ",
+   );
+}
+
+#[test]
+fn arrays_have_higher_order_methods() {
+   run(
+      "
+      var a = [3, 1, 4, 1, 5, 9, 2, 6];
+      assert_eq(a.map(fn (x) { return x * 2; }), [6, 2, 8, 2, 10, 18, 4, 12]);
+      assert_eq(a.filter(fn (x) { return x % 2 == 0; }), [4, 2, 6]);
+      assert_eq(a.reduce(fn (acc, x) { return acc + x; }), 31);
+      assert_eq(a.reduce(fn (acc, x) { return acc + x; }, 100), 131);
+      assert_eq(a.find(fn (x) { return x > 4; }), 5);
+      assert_eq(a.find(fn (x) { return x > 10; }), null);
+      assert(a.any(fn (x) { return x == 9; }));
+      assert(not a.all(fn (x) { return x > 1; }));
+      assert([].all(fn (x) { return false; }));
+
+      // Callbacks can capture variables and modify the array being iterated.
+      var seen = 0;
+      a.map(fn (x) { seen += 1; a.push(x); });
+      assert_eq(seen, 8);
+      assert_eq(a.len(), 16);
+
+      var b = [5, 2, 8, 1];
+      assert_eq(b.sort(), [1, 2, 5, 8]);
+      assert_eq(b, [1, 2, 5, 8]);
+      assert_eq(b.sort(fn (x, y) { return y - x; }), [8, 5, 2, 1]);
+      assert_eq(['pear', 'fig', 'apple'].sort(), ['apple', 'fig', 'pear']);
+
+      // The sort is stable.
+      var pairs = [(1, 'a'), (0, 'b'), (1, 'c'), (0, 'd')];
+      pairs.sort(fn (x, y) { return x[0] - y[0]; });
+      assert_eq(pairs, [(0, 'b'), (0, 'd'), (1, 'a'), (1, 'c')]);
+
+      try { [].reduce(fn (acc, x) { return acc; }); assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+      try { [1, 'a'].sort(); assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+      try { [1, 2].sort(fn (x, y) { return 'a'; }); assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+      try { [1, 0].map(fn (x) { return 1 / x; }); assert(false); } catch (e) { assert_eq(e.name, 'ZeroDivisionError'); }
+   ",
+   );
+}
+
+#[test]
+fn strings_have_text_processing_methods() {
+   run(
+      "
+      assert_eq('a,b,,c'.split(','), ['a', 'b', '', 'c']);
+      assert_eq('  one  two three '.split(), ['one', 'two', 'three']);
+      assert_eq('héy'.split(''), ['h', 'é', 'y']);
+      assert_eq('  padded\t\n'.trim(), 'padded');
+      assert_eq('a-b-c'.replace('-', '+'), 'a+b+c');
+      assert_eq('MiXeD'.to_upper(), 'MIXED');
+      assert_eq('MiXeD'.to_lower(), 'mixed');
+      assert('hello'.contains('ell'));
+      assert(not 'hello'.contains('xyz'));
+
+      // Lengths and indices count characters, not bytes.
+      var s = 'héllo wörld';
+      assert_eq(s.len(), 11);
+      assert_eq(s.byte_len(), 13);
+      assert_eq(s.index_of('wö'), 6);
+      assert_eq(s[s.index_of('wö')], 'w');
+      assert_eq(s.index_of('xyz'), null);
+      assert_eq('añb'.chars(), ['a', 'ñ', 'b']);
+
+      assert_eq('{} + {} = {}'.format(1, 2, 3), '1 + 2 = 3');
+      assert_eq('{1}, {0}, {1}'.format('a', 'b'), 'b, a, b');
+      assert_eq('{{literal}} {}'.format([1, 2]), '{literal} [1, 2]');
+      assert_eq('no placeholders'.format(), 'no placeholders');
+
+      try { '{} {}'.format(1); assert(false); } catch (e) { assert_eq(e.name, 'ArgumentError'); }
+      try { '{'.format(1); assert(false); } catch (e) { assert_eq(e.name, 'ArgumentError'); }
+      try { '{x}'.format(1); assert(false); } catch (e) { assert_eq(e.name, 'ArgumentError'); }
+      try { 'abc'.split(1); assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+   ",
+   );
+}
+
+#[test]
+fn dictionaries_have_methods() {
+   run(
+      "
+      var d = {a: 1, b: 2, c: 3};
+      assert_eq(d.keys(), ['a', 'b', 'c']);
+      assert_eq(d.values(), [1, 2, 3]);
+      assert_eq(d.entries(), [('a', 1), ('b', 2), ('c', 3)]);
+      assert_eq(d.len(), 3);
+      assert(d.contains('a'));
+      assert(not d.contains('z'));
+
+      assert_eq(d.get('a'), 1);
+      assert_eq(d.get('z'), null);
+      assert_eq(d.get('z', 26), 26);
+
+      assert_eq(d.remove('b'), 2);
+      assert_eq(d.keys(), ['a', 'c']);
+      try { d.remove('b'); assert(false); } catch (e) { assert_eq(e.name, 'KeyError'); }
+
+      // Merging overwrites the values of existing keys, which keep their position.
+      var m = d.merge({c: 30, d: 40});
+      assert_eq(m, d);
+      assert_eq(d.entries(), [('a', 1), ('c', 30), ('d', 40)]);
+
+      // Entries take precedence over the methods with the same name.
+      var shadow = {keys: 'entry'};
+      assert_eq(shadow.keys, 'entry');
+      assert_eq(shadow.values(), ['entry']);
+      try { d.missing; assert(false); } catch (e) { assert_eq(e.name, 'KeyError'); }
+
+      try { d[[1]]; assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+      try { d.merge([1]); assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+   ",
+   );
+}
+
+#[test]
+fn sets_store_unique_items() {
+   run(
+      "
+      var s = Set([3, 1, 3, 2, 1]);
+      assert_eq(s.len(), 3);
+      assert_eq(s.to_array(), [3, 1, 2]);
+      assert(s is Set);
+      assert_eq(Set().len(), 0);
+      assert_eq(Set('abca').to_array(), [c'a', c'b', c'c']);
+
+      // Items that are equal with '==' are the same item.
+      assert(1 in s);
+      assert(1.0 in s);
+      assert(true in s);
+      assert(not (4 in s));
+      assert(not ([1] in s));
+
+      s.add(4);
+      s.add(4);
+      assert_eq(s.len(), 4);
+      s.remove(3);
+      assert_eq(s.to_array(), [1, 2, 4]);
+      try { s.remove(3); assert(false); } catch (e) { assert_eq(e.name, 'KeyError'); }
+      try { s.add([1, 2]); assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+
+      var a = Set([1, 2, 3]);
+      var b = Set([2, 3, 4]);
+      assert_eq(a.union(b), Set([1, 2, 3, 4]));
+      assert_eq(a.intersection(b), Set([2, 3]));
+      assert_eq(a.difference(b), Set([1]));
+      assert_eq(Set([1, 2]), Set([2, 1]));
+
+      // Tuples of immutable values can be stored in sets.
+      var pairs = Set([(1, 'a'), (1, 'a'), (2, 'b')]);
+      assert_eq(pairs.len(), 2);
+      assert((2, 'b') in pairs);
+
+      var total = 0;
+      for var x in a { total += x; }
+      assert_eq(total, 6);
+   ",
+   );
+}
+
+#[test]
+fn in_operator_tests_membership() {
+   run(
+      "
+      assert(2 in [1, 2, 3]);
+      assert(not (5 in [1, 2, 3]));
+      assert('b' in ('a', 'b'));
+      assert('ell' in 'hello');
+      assert('key' in {key: 1});
+      assert(not ('value' in {key: 'value'}));
+      assert(3 in 0..5);
+      assert(not (5 in 0..5));
+      assert(4 in 5..0);
+      assert(not (0 in 5..0));
+
+      try { 1 in 'abc'; assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+      try { 1 in 5; assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+   ",
+   );
+}
+
+#[test]
+fn tuples_are_immutable_and_hashable() {
+   run(
+      "
+      var t = (1, 'a', true);
+      assert_eq(t, (1, 'a', true));
+      assert(t != (1, 'a', false));
+      assert_eq(t[1], 'a');
+      assert_eq(t[-1], true);
+      assert_eq((5,)[0], 5);
+      assert_eq((1, 2,), (1, 2));
+      assert_eq((2), 2);
+      try { t[0] = 2; assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+
+      // Tuples (and arrays) can be destructured into variables.
+      var (a, b, c) = t;
+      assert_eq(a, 1);
+      assert_eq(b, 'a');
+      assert_eq(c, true);
+
+      func swap(pair) {
+         var (x, y) = pair;
+         return (y, x);
+      }
+      assert_eq(swap((1, 2)), (2, 1));
+      try { var (p, q) = (1, 2, 3); assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+
+      // Unlike arrays, tuples can be used as dictionary keys.
+      var grid = {};
+      grid[(0, 1)] = 'a';
+      grid[(2, 3)] = 'b';
+      grid[(0, 1)] = 'c';
+      assert_eq(grid.len(), 2);
+      assert_eq(grid[(0, 1)], 'c');
+      assert((2, 3) in grid);
+      assert_eq(grid.get((9, 9), 'none'), 'none');
+      assert_eq(grid.keys(), [(0, 1), (2, 3)]);
+      try { grid[[0, 1]] = 'd'; assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+      try { grid[(5, 5)]; assert(false); } catch (e) { assert_eq(e.name, 'KeyError'); }
+   ",
+   );
+}
+
+#[test]
+fn integers_are_promoted_to_big_ints_on_overflow() {
+   run(
+      "
+      var max = 9223372036854775807;
+      assert_eq('' + (max + 1), '9223372036854775808');
+      assert_eq('' + (-max - 2), '-9223372036854775809');
+      assert_eq('' + 2 ** 64, '18446744073709551616');
+      assert_eq(2 ** 10, 1024);
+
+      func factorial(n) {
+         var result = 1;
+         for var i in 1..(n + 1) { result *= i; }
+         return result;
+      }
+      assert_eq('' + factorial(25), '15511210043330985984000000');
+      assert_eq(factorial(25) / factorial(24), 25.0);
+      assert_eq(factorial(25) % 7, 0);
+
+      // BigInts can also be created explicitly, and compare equal to Ints with the same value.
+      var big = 12345678901234567890123n;
+      assert_eq(big - 12345678901234567890000n, 123);
+      assert_eq(5n, 5);
+      assert(5n > 4 && 4 < 5n && 2n ** 100 > max);
+      assert_eq(-big + big, 0);
+      assert_eq(BigInt('-42'), -42n);
+      assert_eq(BigInt(7), 7n);
+      assert_eq(123456789012345678901234567890 + 0, 123456789012345678901234567890n);
+
+      var counts = {};
+      counts[10n] = 'ten';
+      assert_eq(counts[10], 'ten');
+
+      try { 1n / 0n; assert(false); } catch (e) { assert_eq(e.name, 'ZeroDivisionError'); }
+      try { BigInt('12a'); assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+   ",
+   );
+}
+
+#[test]
+fn bytes_store_binary_data() {
+   run(
+      "
+      var data = Bytes([0, 104, 255]);
+      assert_eq(data.len(), 3);
+      assert_eq(data[1], 104);
+      assert_eq(data[-1], 255);
+      assert_eq(data[1..3], Bytes([104, 255]));
+      assert_eq(data[....-1].to_array(), [255, 104, 0]);
+      assert_eq(data.hex(), '0068ff');
+      assert_eq(Bytes('0068FF', 'hex'), data);
+      assert_eq(Bytes(2), Bytes([0, 0]));
+      assert(104 in data and Bytes([104, 255]) in data);
+
+      data[0] = 1;
+      data.push(7);
+      data.extend(Bytes([8]));
+      assert_eq(data + Bytes([9]), Bytes([1, 104, 255, 7, 8, 9]));
+
+      var total = 0;
+      for var b in data { total += b; }
+      assert_eq(total, 375);
+
+      // Strings can be converted to and from Bytes with an encoding.
+      var text = 'héllo';
+      assert_eq(text.encode().len(), 6);
+      assert_eq(text.encode('latin-1').len(), 5);
+      assert_eq(text.encode('latin-1').decode('latin-1'), text);
+      assert_eq(Bytes(text).decode('utf8'), text);
+      assert_eq('hi'.encode().decode('hex'), '6869');
+
+      try { data[0] = 256; assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+      try { data[10]; assert(false); } catch (e) { assert_eq(e.name, 'IndexError'); }
+      try { Bytes([1, -1]); assert(false); } catch (e) { assert_eq(e.name, 'ArgumentError'); }
+      try { text.encode('ascii'); assert(false); } catch (e) { assert_eq(e.name, 'ArgumentError'); }
+      try { Bytes([255]).decode(); assert(false); } catch (e) { assert_eq(e.name, 'ArgumentError'); }
+      try { 'x'.encode('utf-16'); assert(false); } catch (e) { assert_eq(e.name, 'ArgumentError'); }
+   ",
+   );
+}
+
+#[test]
+fn regular_expressions_match_text() {
+   run(
+      r#"
+      var date = Regex.compile(r'(?<year>\d{4})-(\d{2})');
+      assert(date.test('Released on 2021-03.'));
+      assert(not date.test('Released in March.'));
+
+      var m = date.find('From 2021-03 to 1999-12');
+      assert_eq(m['text'], '2021-03');
+      assert_eq([m['start'], m['end']], [5, 12]);
+      assert_eq(m['groups'], ['2021', '03']);
+      assert_eq(m['named']['year'], '2021');
+      assert_eq(date.find('No dates here'), null);
+
+      var years = date.find_all('From 2021-03 to 1999-12').map(fn (m) { return m['named']['year']; });
+      assert_eq(years, ['2021', '1999']);
+
+      // Replacements can refer to the capture groups, or be computed by a function.
+      assert_eq(date.replace('2021-03 and 1999-12', r'$2/${year}'), '03/2021 and 12/1999');
+      assert_eq(date.replace('2021-03 and 1999-12', fn (m) { return m['groups'][1]; }, 1), '03 and 1999-12');
+
+      assert_eq(Regex.compile(r'\s*,\s*').split('a , b,c'), ['a', 'b', 'c']);
+      assert_eq(Regex.compile('hello', 'i').find('Say HELLO')['text'], 'HELLO');
+      assert_eq(Regex.compile(r'^\w+$', 'm').find_all('one\ntwo').len(), 2);
+      assert_eq(Regex.compile('<.+?>').find('<a><b>')['text'], '<a>');
+
+      try { Regex.compile('ab(c'); assert(false); } catch (e) {
+         assert_eq(e.name, 'TypeError');
+         assert_eq(e.message, 'Invalid regular expression at position 2: Unclosed group.');
+      }
+      try { Regex.compile('a', 'x'); assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+   "#,
+   );
+}
+
+#[test]
+fn regular_expressions_match_in_linear_time() {
+   run(
+      r#"
+      // Nested repetitions do not backtrack exponentially.
+      assert(not Regex.compile('(a+)+b').test('a' * 5000));
+      assert_eq(Regex.compile('(a|aa)*c').find('a' * 5000 + 'c')['end'], 5001);
+
+      // Long texts do not exhaust the native stack.
+      var text = 'ab' * 100000;
+      assert(not Regex.compile('(a|b)*c').test(text));
+      assert_eq(Regex.compile('(a|b)*c').find(text + 'c')['groups'], ['b']);
+
+      // Alternations and repetitions prefer the same matches as a backtracking matcher.
+      assert_eq(Regex.compile('(a|ab)(c|bcd)(d*)').find('abcd')['groups'], ['a', 'bcd', '']);
+      assert_eq(Regex.compile('a{2,3}?').find('aaaa')['text'], 'aa');
+      assert_eq(Regex.compile('(x)?y').find('y')['groups'], [null]);
+
+      try { Regex.compile('(a{1000}){1000}'); assert(false); } catch (e) {
+         assert_eq(e.message, 'Invalid regular expression at position 0: The regular expression is too large.');
+      }
+   "#,
+   );
+}
+
+#[test]
+fn time_module_handles_dates_and_durations() {
+   run(
+      "
+      var d = Time.date(2024, 2, 29, 13, 5, 9, 250);
+      assert_eq('${d}', '2024-02-29T13:05:09.250Z');
+      assert_eq([d.year(), d.month(), d.day(), d.hour(), d.minute(), d.second()], [2024, 2, 29, 13, 5, 9]);
+      assert_eq([d.millisecond(), d.weekday(), d.day_of_year()], [250, 4, 60]);
+      assert_eq(d.format('%a %b %d, %Y %I:%M %p'), 'Thu Feb 29, 2024 01:05 PM');
+
+      // Durations are measured in seconds.
+      assert_eq(d + 86400, Time.date(2024, 3, 1, 13, 5, 9, 250));
+      assert_eq(d - Time.date(2024, 2, 29, 13, 5, 9), 0.25);
+      assert_eq(d.add_days(-60).format('%Y-%m-%d'), '2023-12-31');
+      assert_eq(d.add_months(12).format('%Y-%m-%d'), '2025-02-28');
+      assert(d > Time.date(2024, 1, 1) and Time.now() > d - 86400 * 365);
+
+      assert_eq(Time.parse('March 5, 99 10:30 PM', '%B %d, %y %I:%M %p'), Time.date(1999, 3, 5, 22, 30));
+      assert_eq(Time.from_timestamp(0), Time.parse('1970-01-01', '%Y-%m-%d'));
+      assert_eq(Time.date(1970, 1, 2).timestamp(), 86400.0);
+
+      var start = Time.monotonic();
+      assert(Time.monotonic() >= start and Time.timestamp() > 0);
+
+      try { Time.date(2023, 2, 29); assert(false); } catch (e) { assert_eq(e.name, 'ArgumentError'); }
+      try { Time.parse('2021/01', '%Y-%m'); assert(false); } catch (e) { assert_eq(e.name, 'ArgumentError'); }
+      try { d.format('%Q'); assert(false); } catch (e) { assert_eq(e.name, 'ArgumentError'); }
+      try { d + 'a'; assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+   ",
+   );
+}
+
+#[test]
+fn process_namespace_exposes_args_and_environment() {
+   let src = "
+      assert_eq(Process.args(), ['input.txt', '--verbose']);
+
+      Process.set_env('HINTON_PROCESS_TEST', 'on');
+      assert_eq(Process.env('HINTON_PROCESS_TEST'), 'on');
+      assert_eq(Process.env_vars()['HINTON_PROCESS_TEST'], 'on');
+      assert_eq(Process.env('HINTON_UNSET_VARIABLE'), null);
+      assert(Process.pid() > 0);
+
+      var result = Process.run('sh', ['-c', 'echo $HINTON_PROCESS_TEST; echo oops >&2; exit 3']);
+      assert_eq(result['code'], 3);
+      assert_eq(result['stdout'], 'on\n');
+      assert_eq(result['stderr'], 'oops\n');
+
+      try { Process.run('hinton-missing-command'); assert(false); } catch (e) { assert_eq(e.name, 'IOError'); }
+      try { Process.run('sh', [1]); assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+   ";
+
+   let args = vec![String::from("input.txt"), String::from("--verbose")];
+   if !matches!(
+      VM::new(PathBuf::new()).with_script_args(args).interpret(src),
+      InterpretResult::Ok
+   ) {
+      panic!("Program Had Errors.")
+   }
+}
+
+#[test]
+fn input_functions_read_lines_until_the_end_of_the_input() {
+   let src = "
+      assert_eq(input(), 'first');
+      assert_eq(read_line(), 'second');
+      assert_eq(read_all(), 'third\nfourth');
+      assert_eq(read_line(), null);
+      assert_eq(input('> '), null);
+      assert_eq(read_all(), null);
+   ";
+
+   let input = std::io::Cursor::new("first\r\nsecond\nthird\nfourth");
+   if !matches!(
+      VM::new(PathBuf::new()).with_input(Box::new(input)).interpret(src),
+      InterpretResult::Ok
+   ) {
+      panic!("Program Had Errors.")
+   }
+}
+
+#[test]
+fn caught_errors_include_their_error_code() {
+   run(
+      "
+      try { 1 / 0; assert(false); } catch (e) { assert_eq(e.code, 'E0021'); }
+      try { [][0]; assert(false); } catch (e) { assert_eq(e.code, 'E0011'); }
+   ",
+   );
+}
+
+#[test]
+fn profiler_records_the_calls_and_time_of_each_function() {
+   let src = "
+      func fib(n) { if n < 2 { return n; } return fib(n - 1) + fib(n - 2); }
+      func main() { var result = fib(10); return result; }
+      main();
+   ";
+
+   let mut vm = VM::new(PathBuf::new()).with_profiler();
+   if !matches!(vm.interpret(src), InterpretResult::Ok) {
+      panic!("Program Had Errors.")
+   }
+
+   let profiler = vm.take_profiler().unwrap();
+   let functions = profiler.functions();
+
+   let fib = functions.iter().find(|f| f.name == "fib").unwrap();
+   let main = functions.iter().find(|f| f.name == "main").unwrap();
+   assert_eq!(fib.calls, 177);
+   assert_eq!(main.calls, 1);
+
+   // The time spent in `fib` is part of the total time of `main`.
+   assert!(fib.self_time <= fib.total_time);
+   assert!(fib.total_time <= main.total_time);
+   assert!(main.total_time <= profiler.total_time());
+
+   let report = profiler.report();
+   assert!(report.contains("fib"));
+   assert!(report.contains("Functions & closures"));
+
+   for line in profiler.collapsed_stacks().lines() {
+      let (stack, micros) = line.rsplit_once(' ').unwrap();
+      assert!(stack.contains(";main") || !stack.contains("fib"));
+      assert!(micros.parse::<u128>().is_ok());
+   }
+}
+
+#[test]
+fn trace_modes_select_the_traced_instructions() {
+   assert_eq!(TraceMode::from_name("all"), TraceMode::All);
+   assert_eq!(TraceMode::from_name("calls"), TraceMode::Calls);
+   assert_eq!(
+      TraceMode::from_name("fib"),
+      TraceMode::Function(String::from("fib"))
+   );
+
+   assert!(TraceMode::All.includes("fib", &OpCode::Add));
+   assert!(TraceMode::Calls.includes("fib", &OpCode::FuncCall));
+   assert!(TraceMode::Calls.includes("fib", &OpCode::Return));
+   assert!(!TraceMode::Calls.includes("fib", &OpCode::Add));
+   assert!(TraceMode::Function(String::from("fib")).includes("fib", &OpCode::Add));
+   assert!(!TraceMode::Function(String::from("fib")).includes("main", &OpCode::Add));
+
+   // Tracing a program does not change its behavior.
+   let src = "
+      func fib(n) { if n < 2 { return n; } return fib(n - 1) + fib(n - 2); }
+      assert_eq(fib(5), 5);
+   ";
+
+   let mut vm = VM::new(PathBuf::new()).with_trace(TraceMode::Calls);
+   if !matches!(vm.interpret(src), InterpretResult::Ok) {
+      panic!("Program Had Errors.")
+   }
+}
+
+#[test]
+fn vm_config_limits_the_resources_of_a_program() {
+   let run_with = |config: VmConfig, src: &str| VM::new(PathBuf::new()).with_config(config).interpret(src);
+
+   let frames = VmConfig {
+      max_frames: 50,
+      ..Default::default()
+   };
+   let src = "
+      func depth(n) { return 1 + depth(n + 1); }
+      try { depth(0); assert(false); } catch (e) { assert_eq(e.name, 'RecursionError'); }
+   ";
+   assert!(matches!(run_with(frames, src), InterpretResult::Ok));
+
+   let stack = VmConfig {
+      max_stack_slots: Some(100),
+      ..Default::default()
+   };
+   let src = "
+      func deep(n) {
+         var a = [n];
+         var b = [n];
+         return n == 0 ? 0 : deep(n - 1) + a.len() + b.len();
+      }
+      assert_eq(deep(5), 10);
+
+      // Exceeding the stack size cannot be caught.
+      try { deep(100); } catch (e) { }
+   ";
+   assert!(matches!(run_with(stack, src), InterpretResult::RuntimeError));
+
+   let heap = VmConfig {
+      max_heap_bytes: Some(1 << 20),
+      ..Default::default()
+   };
+   let src = "
+      var items = [];
+      for var i in 0..1000 { items.push(i); }
+      try {
+         while true { items.push('item ${items.len()}'); }
+      } catch (e) { }
+   ";
+   assert!(matches!(run_with(heap, src), InterpretResult::RuntimeError));
+}
+
+#[test]
+fn vm_config_times_out_long_running_programs() {
+   let run_with = |config: VmConfig, src: &str| VM::new(PathBuf::new()).with_config(config).interpret(src);
+
+   let fuel = VmConfig {
+      max_instructions: Some(10_000),
+      ..Default::default()
+   };
+   let src = "
+      var caught = null;
+      try { while true {} } catch (e) { caught = e.name; }
+      assert_eq(caught, 'TimeoutError');
+   ";
+   assert!(matches!(run_with(fuel, src), InterpretResult::Ok));
+
+   let timeout = VmConfig {
+      timeout: Some(std::time::Duration::from_millis(20)),
+      ..Default::default()
+   };
+   let src = "
+      var caught = null;
+      try { while true {} } catch (e) { caught = e.code; }
+      assert_eq(caught, 'E0023');
+   ";
+   assert!(matches!(run_with(timeout, src), InterpretResult::Ok));
+
+   // Programs that keep running after being timed out are ended.
+   let src = "
+      while true {
+         try { while true {} } catch (e) { }
+      }
+   ";
+   assert!(matches!(run_with(fuel, src), InterpretResult::RuntimeError));
+   assert!(matches!(run_with(timeout, src), InterpretResult::RuntimeError));
+}
+
+#[test]
+fn capture_the_output_and_errors_of_programs() {
+   let stdout = OutputBuffer::new();
+   let stderr = OutputBuffer::new();
+
+   let src = "
+      print('Hello');
+      var name = input('Name: ');
+      print('Hi, ${name}!');
+      var x = 1 / 0;
+   ";
+
+   let result = VM::new(PathBuf::new())
+      .with_input(Box::new(std::io::Cursor::new("Ann\n")))
+      .with_stdout(Box::new(stdout.clone()))
+      .with_stderr(Box::new(stderr.clone()))
+      .interpret(src);
+
+   if !matches!(result, InterpretResult::RuntimeError) {
+      panic!("Program should fail with a runtime error.")
+   }
+
+   if stdout.take() != "Hello\nName: Hi, Ann!\n" {
+      panic!("Should write the output of the program to the VM's output stream.")
+   }
+
+   let errors = stderr.contents();
+   if !errors.contains("ZeroDivisionError[E0021]") || !errors.contains("Traceback") {
+      panic!("Should write the runtime errors to the VM's error stream.")
+   }
+}
+
+#[test]
+fn chained_comparisons_evaluate_each_operand_once() {
+   run(
+      "
+      var calls = 0;
+      func count(x) { calls += 1; return x; }
+
+      assert_eq(1 < count(5) <= 10, true);
+      assert_eq(calls, 1);
+      assert_eq(10 > 5 >= 5 > 4, true);
+
+      // The comparisons are short-circuited like logic 'AND' expressions.
+      assert_eq(5 < 1 < count(3), false);
+      assert_eq(calls, 1);
+      assert_eq(1 < count(0) < count(3), false);
+      assert_eq(calls, 2);
+   ",
+   );
+}
+
+#[test]
+fn lambda_shorthand_and_trailing_closures() {
+   run(
+      "
+      var xs = [1, 2, 3];
+      assert_eq(xs.map(|x| x * 2), [2, 4, 6]);
+      assert_eq(xs.reduce(|a, b| a + b), 6);
+      assert_eq((|| 'none')(), 'none');
+
+      // A trailing closure takes an implicit 'it' parameter and returns its last expression.
+      var total = 0;
+      xs.each { total += it; }
+      assert_eq(total, 6);
+      assert_eq(xs.filter { it != 2 }, [1, 3]);
+      assert_eq(xs.map { var y = it * 10; y + 1 }, [11, 21, 31]);
+
+      func apply(value, f) { return f(value); }
+      assert_eq(apply(5) { it + 1 }, 6);
+      assert_eq(apply(5) { if it > 1 { return 'big'; } 'small' }, 'big');
+
+      // Trailing closures are not allowed in the condition of an if statement.
+      var found = false;
+      if xs.any(|x| x == 2) { found = true; }
+      assert_eq(found, true);
+   ",
+   );
+}
+
+#[test]
+fn numeric_tower_and_conversions() {
+   run(
+      "
+      // '/' always produces a Float, while '~/' floors the quotient and keeps Ints as Ints.
+      assert_eq(7 / 2, 3.5);
+      assert_eq((6 / 3) is Float, true);
+      assert_eq(7 ~/ 2, 3);
+      assert_eq((7 ~/ 2) is Int, true);
+      assert_eq(-7 ~/ 2, -4);
+      assert_eq(7 ~/ -2, -4);
+      assert_eq((7.5 ~/ 2) is Float, true);
+      assert_eq(-7.5 ~/ 2, -4.0);
+      assert_eq(BigInt('100000000000000000001') ~/ 10, BigInt('10000000000000000000'));
+
+      var x = 17;
+      x ~/= 5;
+      assert_eq(x, 3);
+
+      // Mixed arithmetic promotes Ints to Floats.
+      assert_eq((1 + 2.0) is Float, true);
+      assert_eq((3 * 2) is Int, true);
+
+      assert_eq([int(3.9), int(-3.9), int(' 42 '), int(true)], [3, -3, 42, 1]);
+      assert_eq([float(3), float('2.5')], [3.0, 2.5]);
+      assert_eq(float(3) is Float, true);
+      assert_eq([round(2.5), round(-2.5), round(2.4), round(1250, -2)], [3, -3, 2, 1300]);
+      assert_eq(round(3.14159, 2), 3.14);
+      assert_eq(round(2.5) is Int, true);
+      assert_eq([truncate(-3.7), truncate(9)], [-3, 9]);
+
+      var caught = null;
+      try { int('abc'); } catch (e) { caught = e.message; }
+      assert_eq(caught, \"Cannot convert 'abc' to an Int.\");
+      try { 1 ~/ 0; } catch (e) { caught = e.name; }
+      assert_eq(caught, 'ZeroDivisionError');
+   ",
+   );
+}
+
+#[test]
+fn char_literals_and_string_iteration() {
+   run(
+      "
+      var a = c'a';
+      assert_eq(a is Char, true);
+      assert_eq(a.code(), 97);
+      assert_eq(Char.from_code(0x1F600), c'\\u{1F600}');
+      assert_eq([c'a' < c'b', c'z' >= c'a', c'a' == c'a', c'a' == 'a'], [true, true, true, false]);
+      assert_eq(a + 'bc', 'abc');
+      assert_eq('x' + a + c'!', 'xa!');
+      assert_eq(c'\\n'.code(), 10);
+      assert_eq([c'A'.is_upper(), c'7'.is_digit(), c' '.is_whitespace(), c'q'.to_upper()], [true, true, true, c'Q']);
+      assert_eq(c'\\u{e9}' in 'caf\\u{e9}', true);
+      assert_eq(Set([c'a', c'a', c'b']).len(), 2);
+
+      // Strings are iterated by character, not by byte.
+      var chars = [];
+      for var c in 'h\\u{e9}llo' { chars.push(c); }
+      assert_eq(chars, [c'h', c'\\u{e9}', c'l', c'l', c'o']);
+
+      var vowels = 0;
+      for var c in 'programming language' {
+         if c in 'aeiou' { vowels += 1; }
+      }
+      assert_eq(vowels, 7);
+
+      var caught = null;
+      try { Char.from_code(-1); } catch (e) { caught = e.message; }
+      assert_eq(caught, \"'-1' is not a valid Unicode code point.\");
+   ",
+   );
+}
+
+#[test]
+fn do_while_loop_and_labeled_loops() {
+   run(
+      "
+      // The body of a do-while loop runs at least once.
+      var runs = 0;
+      do { runs += 1; } while false;
+      assert_eq(runs, 1);
+
+      // A 'continue' in a do-while loop checks the condition.
+      var i = 0;
+      var odds = [];
+      do {
+         i += 1;
+         if i % 2 == 0 { continue; }
+         odds.push(i);
+      } while i < 7;
+      assert_eq(odds, [1, 3, 5, 7]);
+
+      var n = 0;
+      loop {
+         n += 1;
+         if n == 5 { break; }
+      }
+      assert_eq(n, 5);
+
+      var pairs = [];
+      outer: for var x in 0..4 {
+         for var y in 0..4 {
+            if y > x { continue outer; }
+            if x == 3 { break outer; }
+            pairs.push((x, y));
+         }
+      }
+      assert_eq(pairs, [(0, 0), (1, 0), (1, 1), (2, 0), (2, 1), (2, 2)]);
+
+      // Breaking out of nested loops pops their locals and iterators.
+      var found = null;
+      search: loop {
+         for var row in [[1, 2], [3, 4]] {
+            var scaled = row.map(|v| v * 10);
+            loop {
+               if 40 in scaled { found = scaled; break search; }
+               break;
+            }
+         }
+      }
+      var after = 'still works';
+      assert_eq(found, [30, 40]);
+      assert_eq(after, 'still works');
+
+      var rounds = 0;
+      retry: do {
+         rounds += 1;
+         for var attempt in 0..3 {
+            if attempt == 1 { continue retry; }
+         }
+      } while rounds < 3;
+      assert_eq(rounds, 3);
+   ",
+   );
+}
+
+#[test]
+fn loop_expressions_break_with_values() {
+   run(
+      "
+      var i = 0;
+      var square = loop {
+         var sq = i * i;
+         i += 1;
+         if sq > 20 { break sq; }
+      };
+      assert_eq(square, 25);
+
+      // A loop without a value breaks with null.
+      var nothing = loop { break; };
+      assert_eq(nothing, null);
+
+      func find(xs, target) {
+         var k = 0;
+         var index = loop {
+            if k >= xs.len() { break -1; }
+            var item = xs[k];
+            if item == target { break k; }
+            k += 1;
+         };
+         return index;
+      }
+      assert_eq(find([4, 5, 6], 6), 2);
+      assert_eq(find([4, 5, 6], 9), -1);
+
+      // A labeled loop expression can be broken out of from nested loops.
+      func first_pair(n) {
+         var unused = 0;
+         return outer: loop {
+            for var a in 0..n {
+               var b = a * 2;
+               if b > 4 { break outer (a, b); }
+            }
+            break null;
+         }
+      }
+      assert_eq(first_pair(10), (3, 6));
+      assert_eq(first_pair(2), null);
+
+      // The locals of nested loop expressions can be captured.
+      {
+         var closures = [];
+         const outer = loop {
+            var n = 4;
+            var inner = loop {
+               var twice = n * 2;
+               closures.push(fn () { return twice; });
+               break twice;
+            };
+            break inner + 1;
+         };
+         assert_eq(outer, 9);
+         assert_eq(closures[0](), 8);
+      }
+
+      // 'break x' breaks with the value of 'x' if there is no loop labeled 'x'.
+      var x = 5;
+      var y = loop { break x; };
+      assert_eq(y, 5);
+   ",
+   );
+}
+
+#[test]
+fn ranges_with_steps_and_inclusive_ends() {
+   run(
+      "
+      var items = [];
+      for var x in 1..=3 { items.push(x); }
+      for var x in 3..=1 { items.push(x); }
+      assert_eq(items, [1, 2, 3, 3, 2, 1]);
+      assert_eq(0..=5, 0..6);
+
+      var r = (0..10).step(3);
+      assert_eq(r.len(), 4);
+      assert_eq(r, range(0, 10, 3));
+      assert_eq([r[0], r[-1]], [0, 9]);
+      assert(9 in r and not (8 in r) and not (12 in r));
+
+      var reversed = [];
+      for var x in r.reverse() { reversed.push(x); }
+      assert_eq(reversed, [9, 6, 3, 0]);
+
+      var down = [];
+      for var x in (10..0).step(4) { down.push(x); }
+      assert_eq(down, [10, 6, 2]);
+      assert_eq((10..0).step(4).reverse().len(), 3);
+      assert_eq(range(5, 5).len(), 0);
+
+      // Ranges produce their items lazily.
+      var big = 0..10_000_000_000;
+      assert_eq(big.len(), 10_000_000_000);
+      assert(9_999_999_999 in big);
+      var count = 0;
+      for var x in big { count += 1; if x == 2 { break; } }
+      assert_eq(count, 3);
+
+      assert_eq([1, 2, 3, 4, 5][1..=3], [2, 3, 4]);
+
+      try { (0..10).step(0); assert(false); } catch (e) { assert_eq(e.name, 'ArgumentError'); }
+      try { range(0, 10, -1); assert(false); } catch (e) { assert_eq(e.name, 'ArgumentError'); }
+      try { range(0, 'a'); assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+   ",
+   );
+}
+
+#[test]
+fn iterator_protocol_and_lazy_adapters() {
+   run(
+      "
+      class Countdown {
+         pub var n;
+         pub func init(n) { self.n = n; }
+         pub func next() {
+            if self.n == 0 { return null; }
+            self.n -= 1;
+            return self.n + 1;
+         }
+      }
+
+      var items = [];
+      for var x in new Countdown(3) { items.push(x); }
+      assert_eq(items, [3, 2, 1]);
+
+      // Adapters produce their items only as they are needed.
+      var pairs = [];
+      for var p in take(enumerate(skip(0..1_000_000_000, 5)), 2) { pairs.push(p); }
+      assert_eq(pairs, [(0, 5), (1, 6)]);
+
+      var zipped = [];
+      for var z in zip([1, 2, 3], 'ab', new Countdown(10)) { zipped.push(z); }
+      assert_eq(zipped, [(1, c'a', 10), (2, c'b', 9)]);
+
+      var chained = [];
+      for var c in chain([1, 2], [], 3..5, new Countdown(1)) { chained.push(c); }
+      assert_eq(chained, [1, 2, 3, 4, 1]);
+
+      var it = iter([1, 2]);
+      assert_eq(it.next(), 1);
+      assert_eq(next(it), 2);
+      assert_eq(it.next(), null);
+
+      // Errors raised by `next()` are not mistaken for the end of the iterator.
+      class Broken { pub func next() { return [][1]; } }
+      try { for var b in new Broken() {} assert(false); } catch (e) { assert_eq(e.name, 'IndexError'); }
+
+      try { take([1], -1); assert(false); } catch (e) { assert_eq(e.name, 'ArgumentError'); }
+      try { skip([1], 'a'); assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+      try { zip([1], 5); assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+   ",
+   );
+}
+
+#[test]
+fn array_and_dict_comprehensions() {
+   run(
+      "
+      assert_eq([x * x for x in 0..10 if x mod 2 equals 0], [0, 4, 16, 36, 64]);
+      assert_eq({k: v * 2 for k, v in {a: 1, b: 2}}, {a: 2, b: 4});
+      assert_eq([(a, b) for a in 1..4 for b in a..4 if a != b], [(1, 2), (1, 3), (2, 3)]);
+      assert_eq([[x for x in 0..y] for y in 0..3], [[], [0], [0, 1]]);
+      assert_eq([x for var x in []], []);
+
+      // Comprehensions can be used inside other expressions, and capture their surroundings.
+      func scaled(n) { var xs = [1, 2]; return (n, [x * n for x in xs]); }
+      assert_eq(scaled(3), (3, [3, 6]));
+      var fns = [fn () { return i; } for i in 0..3];
+      assert_eq([f() for f in fns], [0, 1, 2]);
+
+      class Shop {
+         pub var prices = {apple: 1, pear: 2};
+         pub func discounted() { return {k: v / 2 for k, v in self.prices}; }
+      }
+      var shop = new Shop();
+      assert_eq(shop.discounted(), {apple: 0.5, pear: 1.0});
+   ",
+   );
+}
+
+#[test]
+fn multiple_return_values() {
+   run(
+      "
+      func div_mod(a, b) { return a ~/ b, a mod b; }
+      assert_eq(div_mod(17, 5), (3, 2));
+
+      // Several variables declared with one value are unpacked from that value.
+      var q, r = div_mod(17, 5);
+      assert_eq([q, r], [3, 2]);
+
+      func stats(xs) {
+         var lo, hi = (xs[0], xs[0]);
+         for var x in xs { if x < lo { lo = x; } if x > hi { hi = x; } }
+         return lo, hi, hi - lo;
+      }
+      var lo, hi, spread = stats([4, 9, 1]);
+      assert_eq([lo, hi, spread], [1, 9, 8]);
+
+      var first, second = [1, 2];
+      assert_eq(first + second, 3);
+      try { var a, b = (1, 2, 3); assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+
+      // Class fields declared together are still all initialized with the same value.
+      class Point { pub var x, y = 0; }
+      var p = new Point();
+      assert_eq([p.x, p.y], [0, 0]);
+   ",
+   );
+}
+
+#[test]
+fn constants_are_evaluated_at_compile_time() {
+   run(
+      "
+      func circle_area(r) { return PI * r * r; }
+      assert_eq(circle_area(2), 12.0);
+      assert_eq(TAU, 6.0);
+
+      const TAU = PI * 2;
+      const PI = 3.0;
+      assert_eq(PI, 3.0);
+
+      // Local declarations shadow the constants of the module.
+      func shadowed(PI) { return PI; }
+      assert_eq(shadowed(1), 1);
+
+      const LABEL = 'v' + VERSION.to_string();
+      const VERSION = 2;
+      assert_eq(LABEL, 'v2');
+   ",
+   );
+}
+
+#[test]
+fn property_accesses_are_cached_per_layout() {
+   run(
+      "
+      class Point { pub var x = 0; pub var y = 0; }
+      class Size { pub var w = 1; pub var x = 5; pub func get_x() { return self.x; } }
+      class Temp {
+         pub var c = 0;
+         pub const x = 9;
+         pub get y() { return self.c; }
+         pub set y(v) { self.c = v * 2; }
+      }
+
+      // The same instructions access instances of classes with different layouts.
+      func get_x(obj) { return obj.x; }
+      func set_y(obj, v) { obj.y = v; return obj.y; }
+
+      var p = new Point();
+      var s = new Size();
+      var t = new Temp();
+
+      for var i in 0..3 {
+         p.x = i;
+         assert_eq(get_x(p), i);
+         assert_eq(get_x(s), 5);
+         assert_eq(get_x(t), 9);
+         assert_eq(set_y(p, i), i);
+         assert_eq(set_y(t, i), i * 2);
+         assert_eq(s.get_x(), 5);
+      }
+
+      // Accesses that fail are not cached.
+      func set_x(obj, v) { obj.x = v; }
+      set_x(p, 1);
+      try { set_x(t, 1); assert(false); } catch (e) { assert_eq(e.name, 'ReferenceError'); }
+      try { get_x({}); assert(false); } catch (e) { assert_eq(e.name, 'KeyError'); }
+      assert_eq(get_x(p), 1);
+   ",
+   );
+}
+
+#[test]
+fn superinstructions_behave_like_the_fused_instructions() {
+   run(
+      "
+      class Vec { pub var n = 0; pub func init(n) { self.n = n; } pub func __lt__(o) { return self.n < o.n; } }
+
+      func count(lo, hi, step) {
+         var n = 0;
+         while lo < hi {
+            n = n + 1;
+            lo = lo + step;
+         }
+         return n;
+      }
+
+      assert_eq(count(0, 10, 1), 10);
+      assert_eq(count(0, 10, 2.5), 4);
+      assert_eq(count(new Vec(0), new Vec(0), 1), 0);
+
+      func add(x) { return [x + 1, x + 20, x + 0.5]; }
+      assert_eq(add(1), [2, 21, 1.5]);
+      assert_eq(add(0.5), [1.5, 20.5, 1]);
+
+      func concat(s) { return s + 'b'; }
+      assert_eq(concat('a'), 'ab');
+
+      try { add(null); assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+      try { count('a', 2, 1); assert(false); } catch (e) { assert_eq(e.name, 'TypeError'); }
+   ",
+   );
+}
+
+#[test]
+fn programs_with_large_constant_pools_are_compiled_correctly() {
+   // With more than 256 constants, the instructions refer to the constants with two-byte operands.
+   let constants: String = (0..300)
+      .map(|i| format!("var s{} = 'constant {}';\n", i, i))
+      .collect();
+
+   run(&format!(
+      "{}
+      class Point {{ pub var x = 1; }}
+      var p = new Point();
+      assert_eq(p.x, 1);
+
+      func count(lo, hi) {{
+         var n = 0;
+         while lo < hi {{ n += 1; lo += 1; }}
+         return n;
+      }}
+      assert_eq(count(0, 200), 200);
+      assert_eq(s299, 'constant 299');
+   ",
+      constants
+   ));
+}
+
+#[test]
+fn green_threads_switch_at_sleeps_and_channels() {
+   run(
+      "
+      var log = [];
+      var ch = Channel();
+
+      func worker(id, n) {
+         for var i in 0..n { ch.send('${id}${i}'); sleep(0); }
+      }
+
+      spawn(worker, 'a', 2);
+      spawn(worker, 'b', 2);
+      for var i in 0..4 { log.push(ch.recv()); }
+      assert_eq(log, ['a0', 'b0', 'a1', 'b1']);
+
+      // Threads share the variables they capture, even while the thread that declared them waits.
+      func count() {
+         var total = 0;
+         var done = Channel();
+         spawn(fn () { total += 10; done.send(true); });
+         done.recv();
+         return total + 1;
+      }
+      assert_eq(count(), 11);
+
+      // Sleeping threads wake up in order, and a thread waiting for a channel
+      // that nothing sends through does not keep the program from ending.
+      var order = Channel();
+      spawn(fn () { sleep(20); order.send(20); });
+      spawn(fn () { sleep(5); order.send(5); });
+      var first = order.recv();
+      assert_eq([first, order.recv()], [5, 20]);
+      spawn(fn () { ch.recv(); });
+
+      assert_eq(ch.try_recv(), null);
+      assert_eq(ch.len(), 0);
+   ",
+   );
+}
+
+#[test]
+fn waiting_for_a_channel_that_nothing_sends_through_is_a_deadlock() {
+   run(
+      "
+      var ch = Channel();
+      var name = null;
+      try { ch.recv(); } catch (e) { name = e.name; }
+      assert_eq(name, 'DeadlockError');
+
+      // Threads cannot switch inside a function called by a native function.
+      try { [1].map(fn (x) { return ch.recv(); }); } catch (e) { name = e.name; }
+      assert_eq(name, 'DeadlockError');
+   ",
+   );
+
+   let mut vm = VM::new(PathBuf::new()).with_stderr(Box::new(std::io::sink()));
+   let src = "spawn(fn () { var x = [][1]; }); sleep(1);";
+   if !matches!(vm.interpret(src), InterpretResult::RuntimeError) {
+      panic!("Errors in green threads should stop the program.")
+   }
+}
+
+#[test]
+fn async_functions_return_promises_that_are_awaited() {
+   run(
+      "
+      var log = [];
+
+      async func work(name, ms) {
+         await delay(ms);
+         log.push(name);
+         return name;
+      }
+
+      // The calls run concurrently, so the shorter delay finishes first.
+      var slow = work('slow', 20);
+      var fast = work('fast', 5);
+      assert_eq(slow.is_settled(), false);
+      var first = await slow;
+      assert_eq([first, await fast], ['slow', 'fast']);
+      assert_eq(log, ['fast', 'slow']);
+      assert_eq(slow.is_settled(), true);
+
+      // Async lambdas and methods, recursion, and values that are not promises.
+      var double = async fn (x) { return x * 2; };
+      assert_eq(await double(21), 42);
+      assert_eq(await 5, 5);
+
+      class Counter {
+         pub var n = 0;
+         pub async func bump() { self.n += 1; return self.n; }
+      }
+      var counter = new Counter();
+      assert_eq(await counter.bump(), 1);
+
+      async func fib(n) {
+         if n < 2 { return n; }
+         return await fib(n - 1) + await fib(n - 2);
+      }
+      assert_eq(await fib(10), 55);
+   ",
+   );
+}
+
+#[test]
+fn errors_in_async_functions_reject_their_promises() {
+   run(
+      "
+      async func fails() {
+         await delay(1);
+         return [][3];
+      }
+
+      var name = null;
+      try { await fails(); } catch (e) { name = e.name; }
+      assert_eq(name, 'IndexError');
+
+      // A rejected promise that is never awaited does not stop the program.
+      var ignored = fails();
+      await delay(5);
+      assert_eq(ignored.is_settled(), true);
+
+      // Awaiting a promise that nothing can settle is a deadlock.
+      var ch = Channel();
+      async func waits() { return ch.recv(); }
+      try { await waits(); } catch (e) { name = e.name; }
+      assert_eq(name, 'DeadlockError');
+   ",
+   );
+}
+
+#[test]
+fn errors_rethrown_by_finally_blocks_are_reported_where_they_were_thrown() {
+   let src = "func fail() {
+   return 1 + 'a' + [];
+}
+try {
+   [][1];
+} catch {
+   fail();
+} finally {
+   print('fin');
+}";
+
+   let stdout = OutputBuffer::new();
+   let stderr = OutputBuffer::new();
+   let result = VM::new(PathBuf::new())
+      .with_stdout(Box::new(stdout.clone()))
+      .with_stderr(Box::new(stderr.clone()))
+      .interpret(src);
+
+   if !matches!(result, InterpretResult::RuntimeError) || stdout.take() != "fin\n" {
+      panic!("The error should escape the catch block after the finally block runs.")
+   }
+
+   let errors = crate::errors::strip_ansi_codes(&stderr.contents());
+   if !errors.contains(" 2 | return 1 + 'a' + [];") || !errors.contains("at [2:") {
+      panic!(
+         "The error should be reported at the line that threw it.\n{}",
+         errors
+      )
+   }
+
+   if !errors.contains("at [7:") || !errors.contains("in 'fail()'") {
+      panic!(
+         "The traceback should have the frames that threw the error.\n{}",
+         errors
+      )
+   }
+
+   match VM::new(PathBuf::new()).eval(src) {
+      Err(crate::HintonError::Runtime(e)) if e.line == 2 => {}
+      _ => panic!("The error returned to the host should have the line that threw it."),
+   }
+}
@@ -0,0 +1,45 @@
+use crate::errors::RuntimeErrorType;
+use crate::virtual_machine::{RuntimeResult, VM};
+use std::time::{Instant, SystemTime};
+
+impl VM {
+   /// Checks whether the programs executed in this VM can read the clocks of the host. The
+   /// wasm32 target has no clocks, so reading them there would panic instead.
+   fn has_clock(&self) -> bool {
+      self.config.has_clock && !cfg!(target_arch = "wasm32")
+   }
+
+   /// Reads the monotonic clock of the host.
+   ///
+   /// # Returns
+   /// - `Result<Instant, RuntimeResult>`: The current instant, or an `IOError` if the VM
+   ///   cannot read the clocks of the host.
+   pub(crate) fn now(&self) -> Result<Instant, RuntimeResult> {
+      if self.has_clock() {
+         Ok(Instant::now())
+      } else {
+         Err(no_clock_error())
+      }
+   }
+
+   /// Reads the system clock of the host.
+   ///
+   /// # Returns
+   /// - `Result<SystemTime, RuntimeResult>`: The current system time, or an `IOError` if the
+   ///   VM cannot read the clocks of the host.
+   pub(crate) fn system_time(&self) -> Result<SystemTime, RuntimeResult> {
+      if self.has_clock() {
+         Ok(SystemTime::now())
+      } else {
+         Err(no_clock_error())
+      }
+   }
+}
+
+/// The error raised by the natives that read the time when the VM has no clock.
+fn no_clock_error() -> RuntimeResult {
+   RuntimeResult::Error {
+      error: RuntimeErrorType::IOError,
+      message: String::from("The clock of the system is not available."),
+   }
+}
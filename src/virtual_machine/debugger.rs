@@ -0,0 +1,301 @@
+use crate::objects::Object;
+use crate::virtual_machine::{StackSlot, VM};
+use std::path::{Path, PathBuf};
+
+/// Represents a position in the source code of a program.
+#[derive(Clone, PartialEq)]
+pub struct SourceLocation {
+   /// The path of the source file.
+   pub filepath: PathBuf,
+   /// The line number in the source file.
+   pub line: usize,
+   /// The column number in the source file.
+   pub column: usize,
+   /// The name of the function being executed.
+   pub function: String,
+}
+
+/// The reasons why the debugger may pause the execution of a program.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PauseReason {
+   /// The program is about to execute its first line.
+   Entry,
+   /// The program reached a line with a breakpoint.
+   Breakpoint,
+   /// The program finished a step requested by a `DebugCommand`.
+   Step,
+}
+
+/// The commands that resume the execution of a paused program.
+#[derive(Clone, Copy, PartialEq)]
+pub enum DebugCommand {
+   /// Runs the program until it reaches a breakpoint.
+   Continue,
+   /// Runs the program until it reaches a new line, entering function calls.
+   StepInto,
+   /// Runs the program until it reaches a new line in the current function, or in a caller.
+   StepOver,
+   /// Runs the program until the current function returns to its caller.
+   StepOut,
+}
+
+/// Determines when the debugger pauses the program, based on the last `DebugCommand`.
+enum Stepping {
+   None,
+   Into,
+   Over(usize),
+   Out(usize),
+}
+
+/// A hook into the debugger, called every time the program is paused. Debugger front-ends
+/// (like the CLI debugger, or an IDE integration) implement this trait to inspect the state
+/// of the virtual machine, and to decide how the execution continues.
+pub trait DebugHook {
+   /// Called when the program is paused, before the instruction at `location` is executed.
+   ///
+   /// # Parameters
+   /// - `vm`: The paused virtual machine.
+   /// - `location`: The source location where the program is paused.
+   /// - `reason`: Why the program was paused.
+   /// - `breakpoints`: The debugger's breakpoints, which can be modified while paused.
+   ///
+   /// # Returns
+   /// - `DebugCommand`: How the execution of the program continues.
+   fn on_pause(
+      &mut self,
+      vm: &VM,
+      location: &SourceLocation,
+      reason: PauseReason,
+      breakpoints: &mut Breakpoints,
+   ) -> DebugCommand;
+}
+
+/// Represents the list of breakpoints set in the debugger.
+#[derive(Default)]
+pub struct Breakpoints(Vec<(PathBuf, usize)>);
+
+impl Breakpoints {
+   /// Adds a breakpoint at a line of a file. Breakpoints match the source files whose path ends
+   /// with the given path, so `main.ht` matches `/home/user/project/main.ht`.
+   ///
+   /// # Parameters
+   /// - `filepath`: The path of the file.
+   /// - `line`: The line number.
+   pub fn add(&mut self, filepath: &Path, line: usize) {
+      if !self.0.iter().any(|(p, l)| p == filepath && *l == line) {
+         self.0.push((filepath.to_path_buf(), line));
+      }
+   }
+
+   /// Removes a breakpoint from a line of a file.
+   ///
+   /// # Returns
+   /// - `bool`: True if the breakpoint existed, false otherwise.
+   pub fn remove(&mut self, filepath: &Path, line: usize) -> bool {
+      let len = self.0.len();
+      self.0.retain(|(p, l)| !(p == filepath && *l == line));
+      self.0.len() != len
+   }
+
+   /// Gets the list of breakpoints, as (path, line) pairs.
+   pub fn list(&self) -> &[(PathBuf, usize)] {
+      &self.0
+   }
+
+   /// Checks if there is a breakpoint at the given line of a file.
+   pub fn matches(&self, filepath: &Path, line: usize) -> bool {
+      self.0.iter().any(|(p, l)| *l == line && filepath.ends_with(p))
+   }
+}
+
+/// The debugger controller. Checked by the VM before executing each instruction to determine
+/// if the program should be paused, in which case the debugger's `DebugHook` is called.
+pub struct Debugger {
+   /// The front-end called when the program is paused.
+   hook: Box<dyn DebugHook>,
+   /// The breakpoints set in the debugger.
+   breakpoints: Breakpoints,
+   /// When to pause the program next.
+   stepping: Stepping,
+   /// The frame depth, line, and instruction pointer of the last executed instruction.
+   last_position: Option<(usize, usize, usize)>,
+}
+
+impl Debugger {
+   /// Creates a new debugger that pauses the program before its first line is executed.
+   ///
+   /// # Parameters
+   /// - `hook`: The front-end called when the program is paused.
+   pub fn new(hook: Box<dyn DebugHook>) -> Self {
+      Debugger {
+         hook,
+         breakpoints: Breakpoints::default(),
+         stepping: Stepping::Into,
+         last_position: None,
+      }
+   }
+
+   /// Creates a new debugger that only pauses the program when it reaches a breakpoint.
+   ///
+   /// # Parameters
+   /// - `hook`: The front-end called when the program is paused.
+   /// - `breakpoints`: The initial breakpoints.
+   pub fn with_breakpoints(hook: Box<dyn DebugHook>, breakpoints: Breakpoints) -> Self {
+      Debugger {
+         hook,
+         breakpoints,
+         stepping: Stepping::None,
+         last_position: None,
+      }
+   }
+}
+
+impl VM {
+   /// Checks whether the debugger attached to this VM should pause the program before the next
+   /// instruction is executed, and calls the debugger's hook if it should. The program only
+   /// pauses when it reaches a new line, or when it jumps back to the start of a line.
+   pub(super) fn debugger_check(&mut self) {
+      let location = match self.current_location() {
+         Some(loc) if loc.line > 0 => loc,
+         _ => return,
+      };
+
+      let mut debugger = match self.debugger.take() {
+         Some(d) => d,
+         None => return,
+      };
+
+      let depth = self.frames.len();
+      let ip = self.current_frame().ip;
+
+      let is_new_line = match debugger.last_position {
+         Some((d, line, last_ip)) => d != depth || line != location.line || ip < last_ip,
+         None => true,
+      };
+
+      let reason = if !is_new_line {
+         None
+      } else if debugger.last_position.is_none() && matches!(debugger.stepping, Stepping::Into) {
+         Some(PauseReason::Entry)
+      } else if debugger.breakpoints.matches(&location.filepath, location.line) {
+         Some(PauseReason::Breakpoint)
+      } else {
+         match debugger.stepping {
+            Stepping::Into => Some(PauseReason::Step),
+            Stepping::Over(d) if depth <= d => Some(PauseReason::Step),
+            Stepping::Out(d) if depth < d => Some(PauseReason::Step),
+            _ => None,
+         }
+      };
+
+      debugger.last_position = Some((depth, location.line, ip));
+
+      if let Some(reason) = reason {
+         let command = debugger
+            .hook
+            .on_pause(self, &location, reason, &mut debugger.breakpoints);
+
+         debugger.stepping = match command {
+            DebugCommand::Continue => Stepping::None,
+            DebugCommand::StepInto => Stepping::Into,
+            DebugCommand::StepOver => Stepping::Over(depth),
+            DebugCommand::StepOut => Stepping::Out(depth),
+         };
+      }
+
+      self.debugger = Some(debugger);
+   }
+
+   /// Gets the source location of the next instruction to be executed.
+   pub fn current_location(&self) -> Option<SourceLocation> {
+      self.frame_location(self.frames.len().checked_sub(1)?)
+   }
+
+   /// Gets the source location of the instruction being executed by a call frame. For the
+   /// frames below the top of the call stack, this is the location of the ongoing call.
+   ///
+   /// # Parameters
+   /// - `frame_idx`: The position of the frame in the call stack, where 0 is the main function.
+   pub fn frame_location(&self, frame_idx: usize) -> Option<SourceLocation> {
+      let frame = self.frames.get(frame_idx)?;
+      let function = frame.closure.function.borrow();
+      let ip = self.frame_ip(frame_idx);
+
+      if ip >= function.chunk.len() {
+         return None;
+      }
+
+      let (line, column) = function.chunk.get_line_info(ip);
+
+      Some(SourceLocation {
+         filepath: function.filepath.clone(),
+         line,
+         column,
+         function: function.name.clone(),
+      })
+   }
+
+   /// Gets the local variables in scope in a call frame, in the order they were declared.
+   ///
+   /// # Parameters
+   /// - `frame_idx`: The position of the frame in the call stack, where 0 is the main function.
+   pub fn frame_locals(&self, frame_idx: usize) -> Vec<(String, Object)> {
+      let frame = match self.frames.get(frame_idx) {
+         Some(f) => f,
+         None => return vec![],
+      };
+
+      let function = frame.closure.function.borrow();
+      let ip = self.frame_ip(frame_idx);
+
+      // The base of the next frame marks the end of this frame's slots.
+      let frame_end = match self.frames.get(frame_idx + 1) {
+         Some(next) => next.return_index,
+         None => self.stack.len(),
+      };
+
+      function
+         .locals
+         .iter()
+         .filter(|l| l.start <= ip && ip < l.end && !l.name.starts_with('<'))
+         .filter(|l| frame.return_index + l.slot < frame_end)
+         .map(|l| {
+            (
+               l.name.clone(),
+               self.stack[frame.return_index + l.slot].to_object(),
+            )
+         })
+         .collect()
+   }
+
+   /// Gets the global declarations made in the program, sorted by name.
+   pub fn global_values(&self) -> Vec<(String, Object)> {
+      let mut globals: Vec<(String, Object)> = self
+         .global_names
+         .iter()
+         .enumerate()
+         .filter(|(slot, name)| self.global_names[slot + 1..].iter().all(|n| n != *name))
+         .map(|(slot, name)| (name.to_string(), self.globals[slot].clone()))
+         .collect();
+      globals.sort_by(|a, b| a.0.cmp(&b.0));
+      globals
+   }
+
+   /// Gets the objects in the VM's values stack, from the bottom to the top of the stack.
+   pub fn value_stack(&self) -> Vec<Object> {
+      self.stack.iter().map(StackSlot::to_object).collect()
+   }
+
+   /// Gets the position of the instruction being executed by a call frame.
+   fn frame_ip(&self, frame_idx: usize) -> usize {
+      let ip = self.frames[frame_idx].ip;
+
+      // The top frame points to its next instruction. The other frames point
+      // to the instruction after the call they are waiting on.
+      if frame_idx + 1 == self.frames.len() {
+         ip
+      } else {
+         ip.saturating_sub(1)
+      }
+   }
+}
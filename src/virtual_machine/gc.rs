@@ -0,0 +1,594 @@
+use crate::objects::channel_obj::ChannelObject;
+use crate::objects::class_obj::{ClassObject, InstanceObject};
+use crate::objects::dict_obj::DictObject;
+use crate::objects::promise_obj::PromiseObject;
+use crate::objects::{ClosureObject, Object, UpValRef};
+use crate::virtual_machine::scheduler::Wait;
+use crate::virtual_machine::{CallFrame, StackSlot, VM};
+use hashbrown::{HashMap, HashSet};
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+/// The configuration of the garbage collector.
+#[derive(Clone, Copy)]
+pub struct GcConfig {
+   /// The number of tracked objects that triggers the first collection.
+   pub initial_threshold: usize,
+   /// After a collection, the next collection is triggered when the number
+   /// of tracked objects reaches the live objects times this factor.
+   pub growth_factor: usize,
+}
+
+impl Default for GcConfig {
+   fn default() -> Self {
+      Self {
+         initial_threshold: 1024,
+         growth_factor: 2,
+      }
+   }
+}
+
+/// A weak reference to a heap object that can be part of a reference cycle.
+enum HeapRef {
+   Array(Weak<RefCell<Vec<Object>>>),
+   Channel(Weak<RefCell<ChannelObject>>),
+   Class(Weak<RefCell<ClassObject>>),
+   Dict(Weak<RefCell<DictObject>>),
+   Instance(Weak<RefCell<InstanceObject>>),
+   Promise(Weak<RefCell<PromiseObject>>),
+   UpValue(Weak<RefCell<UpValRef>>),
+}
+
+impl HeapRef {
+   /// Gets the address of the referenced object.
+   fn address(&self) -> usize {
+      match self {
+         HeapRef::Array(w) => w.as_ptr() as usize,
+         HeapRef::Channel(w) => w.as_ptr() as usize,
+         HeapRef::Class(w) => w.as_ptr() as usize,
+         HeapRef::Dict(w) => w.as_ptr() as usize,
+         HeapRef::Instance(w) => w.as_ptr() as usize,
+         HeapRef::Promise(w) => w.as_ptr() as usize,
+         HeapRef::UpValue(w) => w.as_ptr() as usize,
+      }
+   }
+
+   /// Checks that the referenced object has not been freed by reference counting.
+   fn is_alive(&self) -> bool {
+      match self {
+         HeapRef::Array(w) => w.strong_count() > 0,
+         HeapRef::Channel(w) => w.strong_count() > 0,
+         HeapRef::Class(w) => w.strong_count() > 0,
+         HeapRef::Dict(w) => w.strong_count() > 0,
+         HeapRef::Instance(w) => w.strong_count() > 0,
+         HeapRef::Promise(w) => w.strong_count() > 0,
+         HeapRef::UpValue(w) => w.strong_count() > 0,
+      }
+   }
+
+   /// Clears the contents of the referenced object, dropping the references it holds. The
+   /// contents are moved out of the object before being dropped, so that dropping them never
+   /// happens while the object is borrowed.
+   fn clear(&self) {
+      match self {
+         HeapRef::Array(w) => {
+            if let Some(a) = w.upgrade() {
+               drop(std::mem::take(&mut *a.borrow_mut()));
+            }
+         }
+         HeapRef::Channel(w) => {
+            if let Some(c) = w.upgrade() {
+               drop(c.borrow_mut().take());
+            }
+         }
+         HeapRef::Class(w) => {
+            if let Some(c) = w.upgrade() {
+               let members = std::mem::take(&mut c.borrow_mut().members);
+               let statics = std::mem::take(&mut c.borrow_mut().statics);
+               let superclass = c.borrow_mut().superclass.take();
+               drop((members, statics, superclass));
+            }
+         }
+         HeapRef::Dict(w) => {
+            if let Some(d) = w.upgrade() {
+               drop(std::mem::take(&mut *d.borrow_mut()));
+            }
+         }
+         HeapRef::Instance(w) => {
+            if let Some(i) = w.upgrade() {
+               drop(std::mem::take(&mut i.borrow_mut().fields));
+            }
+         }
+         HeapRef::Promise(w) => {
+            if let Some(p) = w.upgrade() {
+               drop(p.borrow_mut().take());
+            }
+         }
+         HeapRef::UpValue(w) => {
+            if let Some(u) = w.upgrade() {
+               drop(u.replace(UpValRef::Closed(Object::Null)));
+            }
+         }
+      }
+   }
+}
+
+/// The managed heap of the VM. Objects that cannot form reference cycles are freed by reference
+/// counting as soon as they are no longer used. Objects that can form reference cycles (arrays,
+/// channels, dictionaries, classes, instances, promises, and UpValues) are also tracked by the heap, so that the
+/// cycles that are no longer reachable from the program can be found and broken by a tracing
+/// (mark-and-sweep) collection.
+pub struct Heap {
+   /// The objects tracked by the heap.
+   objects: Vec<HeapRef>,
+   /// The number of tracked objects that triggers the next collection.
+   next_collection: usize,
+   /// The configuration of the garbage collector.
+   config: GcConfig,
+}
+
+impl Heap {
+   /// Creates a new managed heap.
+   pub fn new(config: GcConfig) -> Self {
+      Self {
+         objects: vec![],
+         next_collection: config.initial_threshold,
+         config,
+      }
+   }
+
+   /// Checks if enough objects have been tracked since the last collection to start a new one.
+   pub fn should_collect(&self) -> bool {
+      self.objects.len() >= self.next_collection
+   }
+
+   /// Gets the number of objects currently tracked by the heap.
+   #[cfg(test)]
+   pub fn len(&self) -> usize {
+      self.objects.len()
+   }
+}
+
+/// The set of heap objects that are reachable from the roots, identified by their address.
+struct Marker {
+   marked: HashSet<usize>,
+   worklist: Vec<Object>,
+}
+
+impl Marker {
+   /// Marks an object as reachable, and schedules its children to be marked.
+   fn mark(&mut self, obj: &Object) {
+      let address = match obj {
+         Object::Array(a) => Rc::as_ptr(a) as usize,
+         Object::Channel(c) => Rc::as_ptr(c) as usize,
+         Object::Class(c) => Rc::as_ptr(c) as usize,
+         Object::Dict(d) => Rc::as_ptr(d) as usize,
+         Object::Function(f) => Rc::as_ptr(f) as usize,
+         Object::Instance(i) => Rc::as_ptr(i) as usize,
+         Object::Iter(i) => Rc::as_ptr(i) as usize,
+         Object::Promise(p) => Rc::as_ptr(p) as usize,
+         Object::Tuple(t) => Rc::as_ptr(t) as usize,
+         Object::EnumValue(v) => Rc::as_ptr(v) as usize,
+         Object::BoundMethod(_) | Object::BoundNativeMethod(_) | Object::Closure(_) => {
+            // These objects are not allocated in the heap themselves,
+            // but they hold references to objects that are.
+            self.worklist.push(obj.clone());
+            return;
+         }
+         _ => return,
+      };
+
+      if self.marked.insert(address) {
+         self.worklist.push(obj.clone());
+      }
+   }
+
+   /// Marks the function and UpValues of a closure as reachable.
+   fn mark_closure(&mut self, closure: &ClosureObject) {
+      self.mark(&Object::Function(closure.function.clone()));
+
+      for up_val in closure.up_values.iter() {
+         self.mark_up_value(up_val);
+      }
+   }
+
+   /// Marks the closure of a call frame, and the closures of its deferred calls, as reachable.
+   fn mark_frame(&mut self, frame: &CallFrame) {
+      self.mark_closure(&frame.closure);
+      frame.defers.iter().for_each(|o| self.mark(o));
+   }
+
+   /// Marks an UpValue (and the object it has captured, if closed) as reachable.
+   fn mark_up_value(&mut self, up_val: &Rc<RefCell<UpValRef>>) {
+      if self.marked.insert(Rc::as_ptr(up_val) as usize) {
+         if let UpValRef::Closed(obj) = &*up_val.borrow() {
+            self.mark(obj);
+         }
+      }
+   }
+
+   /// Marks the children of the objects in the worklist until every reachable object is marked.
+   fn trace(&mut self) {
+      while let Some(obj) = self.worklist.pop() {
+         match obj {
+            Object::Array(a) => a.borrow().iter().for_each(|o| self.mark(o)),
+            Object::Channel(c) => c.borrow().iter().for_each(|o| self.mark(o)),
+            Object::Tuple(t) => t.iter().for_each(|o| self.mark(o)),
+            Object::EnumValue(v) => v.payload.iter().for_each(|o| self.mark(o)),
+            Object::Dict(d) => d.borrow().values().for_each(|o| self.mark(o)),
+            Object::Iter(i) => self.mark(&i.borrow().iter),
+            Object::Promise(p) => {
+               if let Some(value) = p.borrow().value() {
+                  self.mark(value);
+               }
+            }
+            Object::Class(c) => {
+               let c = c.borrow();
+               c.members.values().for_each(|f| self.mark(&f.value));
+               c.statics.values().for_each(|f| self.mark(&f.value));
+
+               if let Some(s) = &c.superclass {
+                  self.mark(&Object::Class(s.clone()));
+               }
+            }
+            Object::Instance(i) => {
+               let i = i.borrow();
+               self.mark(&Object::Class(i.class.clone()));
+               i.fields.iter().for_each(|f| self.mark(&f.value));
+            }
+            Object::Function(f) => {
+               let f = f.borrow();
+               f.defaults.iter().for_each(|o| self.mark(o));
+               f.chunk.get_constants().iter().for_each(|o| self.mark(o));
+            }
+            Object::Closure(c) => self.mark_closure(&c),
+            Object::BoundMethod(b) => {
+               self.mark(&Object::Instance(b.receiver.clone()));
+               self.mark_closure(&b.method);
+            }
+            Object::BoundNativeMethod(n) => self.mark(&n.value),
+            _ => {}
+         }
+      }
+   }
+}
+
+/// An object counted by the `RefCounter`, which is either an object allocated with
+/// reference counting, or an UpValue.
+#[derive(Clone)]
+enum Node {
+   Object(Object),
+   UpValue(Rc<RefCell<UpValRef>>),
+}
+
+impl Node {
+   /// Gets the address and the number of strong references of a node, if it is
+   /// allocated with reference counting.
+   fn rc_info(&self) -> Option<(usize, usize)> {
+      let info = match self {
+         Node::UpValue(u) => (Rc::as_ptr(u) as usize, Rc::strong_count(u)),
+         Node::Object(obj) => match obj {
+            Object::Array(a) => (Rc::as_ptr(a) as usize, Rc::strong_count(a)),
+            Object::Channel(c) => (Rc::as_ptr(c) as usize, Rc::strong_count(c)),
+            Object::Class(c) => (Rc::as_ptr(c) as usize, Rc::strong_count(c)),
+            Object::Dict(d) => (Rc::as_ptr(d) as usize, Rc::strong_count(d)),
+            Object::Function(f) => (Rc::as_ptr(f) as usize, Rc::strong_count(f)),
+            Object::Instance(i) => (Rc::as_ptr(i) as usize, Rc::strong_count(i)),
+            Object::Iter(i) => (Rc::as_ptr(i) as usize, Rc::strong_count(i)),
+            Object::Promise(p) => (Rc::as_ptr(p) as usize, Rc::strong_count(p)),
+            Object::Tuple(t) => (Rc::as_ptr(t) as usize, Rc::strong_count(t)),
+            Object::EnumValue(v) => (Rc::as_ptr(v) as usize, Rc::strong_count(v)),
+            _ => return None,
+         },
+      };
+
+      Some(info)
+   }
+}
+
+/// Counts the references that the objects reachable from the tracked objects hold to each
+/// other. An object with more strong references than those counted is also referenced from
+/// outside of the VM (e.g., by the host program that embeds the VM, or by a native function),
+/// so it must be kept alive along with everything it references.
+///
+/// References that are not counted only make an object look referenced from outside, which
+/// keeps it alive, so the objects that hold no references to tracked objects are not visited.
+#[derive(Default)]
+struct RefCounter {
+   /// The counted nodes, by address. Each node is held exactly once by this map.
+   nodes: HashMap<usize, Node>,
+   /// The number of references to each node from the other counted nodes, by address.
+   refs: HashMap<usize, usize>,
+   worklist: Vec<Node>,
+}
+
+impl RefCounter {
+   /// Adds a node to the counted nodes, if it is allocated with reference counting.
+   ///
+   /// # Parameters
+   /// - `node`: The node to add.
+   /// - `is_reference`: Whether the node is referenced by another counted node.
+   fn add(&mut self, node: Node, is_reference: bool) {
+      let address = match node.rc_info() {
+         Some((address, _)) => address,
+         None => return,
+      };
+
+      if is_reference {
+         *self.refs.entry(address).or_insert(0) += 1;
+      }
+
+      if !self.nodes.contains_key(&address) {
+         self.nodes.insert(address, node.clone());
+         self.worklist.push(node);
+      }
+   }
+
+   /// Counts the references held by an object. The closures and bound methods are stored in
+   /// their owners, so the references they hold are counted as references of their owners.
+   fn count(&mut self, obj: &Object) {
+      match obj {
+         Object::Closure(c) => self.count_closure(c),
+         Object::BoundMethod(b) => {
+            self.add(Node::Object(Object::Instance(b.receiver.clone())), true);
+            self.count_closure(&b.method);
+         }
+         Object::BoundNativeMethod(n) => self.count(&n.value),
+         _ => self.add(Node::Object(obj.clone()), true),
+      }
+   }
+
+   /// Counts the references held by a closure.
+   fn count_closure(&mut self, closure: &ClosureObject) {
+      self.add(Node::Object(Object::Function(closure.function.clone())), true);
+
+      for up_val in closure.up_values.iter() {
+         self.add(Node::UpValue(up_val.clone()), true);
+      }
+   }
+
+   /// Counts the references held by the nodes in the worklist, and by the nodes they reference.
+   fn trace(&mut self) {
+      while let Some(node) = self.worklist.pop() {
+         let obj = match node {
+            Node::Object(obj) => obj,
+            Node::UpValue(u) => {
+               if let UpValRef::Closed(obj) = &*u.borrow() {
+                  self.count(obj);
+               }
+               continue;
+            }
+         };
+
+         match obj {
+            Object::Array(a) => a.borrow().iter().for_each(|o| self.count(o)),
+            Object::Channel(c) => c.borrow().iter().for_each(|o| self.count(o)),
+            Object::Tuple(t) => t.iter().for_each(|o| self.count(o)),
+            Object::EnumValue(v) => v.payload.iter().for_each(|o| self.count(o)),
+            Object::Dict(d) => d.borrow().values().for_each(|o| self.count(o)),
+            Object::Iter(i) => self.count(&i.borrow().iter),
+            Object::Promise(p) => {
+               if let Some(value) = p.borrow().value() {
+                  self.count(value);
+               }
+            }
+            Object::Class(c) => {
+               let c = c.borrow();
+               c.members.values().for_each(|f| self.count(&f.value));
+               c.statics.values().for_each(|f| self.count(&f.value));
+
+               if let Some(s) = &c.superclass {
+                  self.count(&Object::Class(s.clone()));
+               }
+            }
+            Object::Instance(i) => {
+               let i = i.borrow();
+               self.count(&Object::Class(i.class.clone()));
+               i.fields.iter().for_each(|f| self.count(&f.value));
+            }
+            Object::Function(f) => {
+               let f = f.borrow();
+               f.defaults.iter().for_each(|o| self.count(o));
+               f.chunk.get_constants().iter().for_each(|o| self.count(o));
+            }
+            _ => {}
+         }
+      }
+   }
+
+   /// Gets the counted nodes that are referenced from outside of the counted nodes.
+   fn external_nodes(self) -> Vec<Node> {
+      let refs = self.refs;
+
+      self
+         .nodes
+         .into_iter()
+         .filter(|(address, node)| {
+            // One of the strong references is held by the map of counted nodes.
+            let strong_count = node.rc_info().map_or(0, |(_, count)| count) - 1;
+            strong_count > refs.get(address).copied().unwrap_or(0)
+         })
+         .map(|(_, node)| node)
+         .collect()
+   }
+}
+
+impl VM {
+   /// Allocates a new array object in the managed heap.
+   pub(crate) fn alloc_array(&mut self, values: Vec<Object>) -> Object {
+      let arr = Rc::new(RefCell::new(values));
+      self.heap.objects.push(HeapRef::Array(Rc::downgrade(&arr)));
+      Object::Array(arr)
+   }
+
+   /// Allocates a new channel object in the managed heap.
+   pub(crate) fn alloc_channel(&mut self) -> Object {
+      let channel = Rc::new(RefCell::new(ChannelObject::default()));
+      self.heap.objects.push(HeapRef::Channel(Rc::downgrade(&channel)));
+      Object::Channel(channel)
+   }
+
+   /// Allocates a new class object in the managed heap.
+   pub(crate) fn alloc_class(&mut self, class: ClassObject) -> Object {
+      let class = Rc::new(RefCell::new(class));
+      self.heap.objects.push(HeapRef::Class(Rc::downgrade(&class)));
+      Object::Class(class)
+   }
+
+   /// Allocates a new dictionary object in the managed heap.
+   pub(crate) fn alloc_dict(&mut self, dict: DictObject) -> Object {
+      let dict = Rc::new(RefCell::new(dict));
+      self.heap.objects.push(HeapRef::Dict(Rc::downgrade(&dict)));
+      Object::Dict(dict)
+   }
+
+   /// Allocates a new instance object in the managed heap.
+   pub(crate) fn alloc_instance(&mut self, instance: InstanceObject) -> Object {
+      let instance = Rc::new(RefCell::new(instance));
+      self
+         .heap
+         .objects
+         .push(HeapRef::Instance(Rc::downgrade(&instance)));
+      Object::Instance(instance)
+   }
+
+   /// Allocates a new pending promise in the managed heap.
+   pub(crate) fn alloc_promise(&mut self) -> Rc<RefCell<PromiseObject>> {
+      let promise = Rc::new(RefCell::new(PromiseObject::default()));
+      self.heap.objects.push(HeapRef::Promise(Rc::downgrade(&promise)));
+      promise
+   }
+
+   /// Allocates a new UpValue in the managed heap.
+   pub(crate) fn alloc_up_value(&mut self, up_val: UpValRef) -> Rc<RefCell<UpValRef>> {
+      let up_val = Rc::new(RefCell::new(up_val));
+      self.heap.objects.push(HeapRef::UpValue(Rc::downgrade(&up_val)));
+      up_val
+   }
+
+   /// Runs the garbage collector, if a collection is due, from a native function that is in
+   /// between two calls to Hinton code (e.g., a server waiting for its next request). The
+   /// objects held by the native function are kept alive. Nothing is collected while other
+   /// native functions wait for a call to return, because their objects are not known.
+   ///
+   /// # Parameters
+   /// - `held`: The objects held by the native function.
+   pub(crate) fn collect_garbage_from_native(&mut self, held: &[Object]) {
+      if self.native_calls > 0 || !self.heap.should_collect() {
+         return;
+      }
+
+      let stack_len = self.stack.len();
+      held.iter().for_each(|o| {
+         self.push_stack(o.clone());
+      });
+
+      self.profile_garbage_collection();
+      self.collect_garbage();
+      self.stack.truncate(stack_len);
+   }
+
+   /// Finds the objects reachable from the tracked objects that are also referenced from
+   /// outside of the VM, by comparing their strong references with the references between them.
+   fn external_nodes(&self) -> Vec<Node> {
+      let mut counter = RefCounter::default();
+
+      for obj in self.heap.objects.iter() {
+         let node = match obj {
+            HeapRef::Array(w) => w.upgrade().map(|a| Node::Object(Object::Array(a))),
+            HeapRef::Channel(w) => w.upgrade().map(|c| Node::Object(Object::Channel(c))),
+            HeapRef::Class(w) => w.upgrade().map(|c| Node::Object(Object::Class(c))),
+            HeapRef::Dict(w) => w.upgrade().map(|d| Node::Object(Object::Dict(d))),
+            HeapRef::Instance(w) => w.upgrade().map(|i| Node::Object(Object::Instance(i))),
+            HeapRef::Promise(w) => w.upgrade().map(|p| Node::Object(Object::Promise(p))),
+            HeapRef::UpValue(w) => w.upgrade().map(Node::UpValue),
+         };
+
+         if let Some(node) = node {
+            counter.add(node, false);
+         }
+      }
+
+      counter.trace();
+      counter.external_nodes()
+   }
+
+   /// Finds the tracked objects that are no longer reachable from the program, and breaks the
+   /// reference cycles between them by clearing their contents. Must only be called in between
+   /// the execution of two instructions, when every object in use is reachable from the roots.
+   pub(crate) fn collect_garbage(&mut self) {
+      // Counted before the roots are marked, because the marker holds references to them.
+      let external = self.external_nodes();
+
+      // Only open UpValues are roots. Closed UpValues are reachable through their closures.
+      self
+         .up_values
+         .retain(|u| matches!(*u.borrow(), UpValRef::Open(_)));
+
+      let mut marker = Marker {
+         marked: HashSet::new(),
+         worklist: vec![],
+      };
+
+      // Mark the roots.
+      self.stack.iter().for_each(|o| marker.mark(&o.as_object()));
+      self.globals.iter().for_each(|o| marker.mark(o));
+      self.modules.values().for_each(|o| marker.mark(o));
+      self.up_values.iter().for_each(|u| marker.mark_up_value(u));
+      self.frames.iter().for_each(|f| marker.mark_frame(f));
+
+      // The state of the suspended green threads is also reachable.
+      for thread in self.scheduler.threads.iter() {
+         thread.stack.iter().for_each(|o| marker.mark(&o.as_object()));
+         thread.frames.iter().for_each(|f| marker.mark_frame(f));
+         thread.up_values.iter().for_each(|(u, _)| marker.mark_up_value(u));
+
+         match &thread.wait {
+            Wait::Recv(channel) => marker.mark(&Object::Channel(channel.clone())),
+            Wait::Promise(promise) => marker.mark(&Object::Promise(promise.clone())),
+            _ => {}
+         }
+
+         if let Some(promise) = &thread.promise {
+            marker.mark(&Object::Promise(promise.clone()));
+         }
+      }
+
+      if let Some(promise) = &self.scheduler.running_promise {
+         marker.mark(&Object::Promise(promise.clone()));
+      }
+
+      for (promise, _) in self.scheduler.events.iter() {
+         marker.mark(&Object::Promise(promise.clone()));
+      }
+
+      if let Some(stack) = &self.scheduler.main_stack {
+         stack.iter().for_each(|o| marker.mark(&o.as_object()));
+      }
+
+      // The objects referenced from outside of the VM (e.g., the values that `VM::eval`
+      // returned to the host) are also roots, even if the program no longer uses them.
+      for node in external {
+         match node {
+            Node::Object(obj) => marker.mark(&obj),
+            Node::UpValue(u) => marker.mark_up_value(&u),
+         }
+      }
+      marker.trace();
+
+      // Sweep the unreachable objects. Objects already freed by reference counting are removed
+      // from the heap, and the contents of unreachable objects are cleared to break the cycles.
+      let (live, garbage): (Vec<HeapRef>, Vec<HeapRef>) = std::mem::take(&mut self.heap.objects)
+         .into_iter()
+         .filter(|o| o.is_alive())
+         .partition(|o| marker.marked.contains(&o.address()));
+
+      self.heap.objects = live;
+      garbage.iter().for_each(|o| o.clear());
+      self.interner.sweep();
+
+      let live = self.heap.objects.len();
+      self.heap.next_collection =
+         (live * self.heap.config.growth_factor).max(self.heap.config.initial_threshold);
+   }
+}
@@ -0,0 +1,80 @@
+use crate::objects::{FuncObject, Object};
+use crate::virtual_machine::VM;
+use hashbrown::HashSet;
+use std::rc::Rc;
+
+/// A table of interned strings. Interned strings with the same contents share a single
+/// allocation, so comparing two interned strings only requires comparing their pointers.
+#[derive(Default)]
+pub struct Interner {
+   strings: HashSet<Rc<str>>,
+}
+
+impl Interner {
+   /// Gets the interned copy of a string, adding the string to the table if needed.
+   ///
+   /// # Parameters
+   /// - `s`: The string to intern.
+   pub fn intern(&mut self, s: &str) -> Rc<str> {
+      match self.strings.get(s) {
+         Some(interned) => interned.clone(),
+         None => {
+            let interned: Rc<str> = Rc::from(s);
+            self.strings.insert(interned.clone());
+            interned
+         }
+      }
+   }
+
+   /// Gets the interned copy of a shared string. If the string is not yet interned,
+   /// the given allocation becomes the interned copy.
+   ///
+   /// # Parameters
+   /// - `s`: The string to intern.
+   pub fn intern_rc(&mut self, s: &Rc<str>) -> Rc<str> {
+      match self.strings.get(&**s) {
+         Some(interned) => interned.clone(),
+         None => {
+            self.strings.insert(s.clone());
+            s.clone()
+         }
+      }
+   }
+
+   /// Gets the number of strings in the table.
+   #[cfg(test)]
+   pub(crate) fn len(&self) -> usize {
+      self.strings.len()
+   }
+
+   /// Removes the strings that are only referenced by the table.
+   pub fn sweep(&mut self) {
+      self.strings.retain(|s| Rc::strong_count(s) > 1);
+   }
+}
+
+impl VM {
+   /// Creates a String object from the interned copy of a string. Native functions can use
+   /// this to share the allocation of strings that are likely to be created repeatedly.
+   ///
+   /// # Parameters
+   /// - `s`: The contents of the string.
+   pub fn intern(&mut self, s: &str) -> Object {
+      Object::String(self.interner.intern(s))
+   }
+
+   /// Interns the strings in the constant pool of a function and of the functions nested in it,
+   /// so that identical string literals and identifier names share a single allocation.
+   ///
+   /// # Parameters
+   /// - `function`: The function whose constants are interned.
+   pub(super) fn intern_constants(&mut self, function: &mut FuncObject) {
+      for constant in function.chunk.get_constants_mut() {
+         match constant {
+            Object::String(s) => *s = self.interner.intern_rc(s),
+            Object::Function(f) => self.intern_constants(&mut f.borrow_mut()),
+            _ => {}
+         }
+      }
+   }
+}
@@ -0,0 +1,225 @@
+use crate::errors::RuntimeErrorType;
+use crate::virtual_machine::{RuntimeResult, VM};
+use crate::FRAMES_MAX;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+/// The number of instructions executed in between two checks of the limits of the VM.
+const CHECK_INTERVAL: u64 = 1024;
+
+/// The number of instructions a program can execute after a `TimeoutError` is thrown, so
+/// that its `catch` and `finally` blocks can run. Once they are executed, the program ends.
+const TIMEOUT_GRACE_INSTRUCTIONS: u64 = 10_000;
+
+/// The limits on the resources used by the programs executed in a VM. Embedders can lower
+/// them to run untrusted programs without letting them exhaust the resources of the host.
+/// Except for `max_frames` and `max_instructions`, the limits are checked every few
+/// instructions, so a program may briefly exceed them before it is stopped.
+#[derive(Clone, Copy)]
+pub struct VmConfig {
+   /// The maximum number of nested function calls. Calls beyond this depth
+   /// fail with a `RecursionError`.
+   pub max_frames: usize,
+   /// The maximum number of slots in the values stack, if any. Programs
+   /// that grow the stack beyond this size fail with a `MemoryError`.
+   pub max_stack_slots: Option<usize>,
+   /// The maximum number of bytes the program can allocate on the heap, if any. Programs
+   /// that allocate more than this (without freeing it) fail with a `MemoryError`.
+   ///
+   /// The allocations are measured by `CountingAllocator`, which the host program must install
+   /// as its global allocator (with `#[global_allocator]`). Otherwise, this limit is never
+   /// reached, and programs can allocate as much memory as the host allows.
+   pub max_heap_bytes: Option<usize>,
+   /// The maximum number of instructions the program can execute, if any. Programs that
+   /// execute more instructions than this fail with a `TimeoutError`.
+   pub max_instructions: Option<u64>,
+   /// The maximum time the program can run for, if any. Programs that run
+   /// for longer than this fail with a `TimeoutError`. Requires `has_clock`.
+   pub timeout: Option<Duration>,
+   /// Whether the programs can read the clocks of the host. Without a clock, the natives that
+   /// read the time (e.g., `clock()` or `sleep(...)`) raise an `IOError`. Always false on the
+   /// wasm32 target, which has no clocks.
+   pub has_clock: bool,
+}
+
+impl Default for VmConfig {
+   fn default() -> Self {
+      Self {
+         max_frames: FRAMES_MAX,
+         max_stack_slots: None,
+         max_heap_bytes: None,
+         max_instructions: None,
+         timeout: None,
+         has_clock: !cfg!(target_arch = "wasm32"),
+      }
+   }
+}
+
+thread_local! {
+   /// The number of bytes currently allocated by this thread (minus the bytes freed by it).
+   static ALLOCATED_BYTES: Cell<isize> = const { Cell::new(0) };
+}
+
+/// A wrapper around the system allocator that counts the bytes allocated by each thread,
+/// so that the memory used by the programs executed in a VM can be limited. Memory freed
+/// by a thread other than the one that allocated it is counted towards the freeing thread.
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+   /// Adds the given number of bytes to the allocated bytes of the current thread.
+   fn record(bytes: isize) {
+      // The thread-local may already be destroyed while the thread is exiting.
+      let _ = ALLOCATED_BYTES.try_with(|b| b.set(b.get() + bytes));
+   }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+   unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+      let ptr = System.alloc(layout);
+      if !ptr.is_null() {
+         Self::record(layout.size() as isize);
+      }
+      ptr
+   }
+
+   unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+      System.dealloc(ptr, layout);
+      Self::record(-(layout.size() as isize));
+   }
+
+   unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+      let ptr = System.alloc_zeroed(layout);
+      if !ptr.is_null() {
+         Self::record(layout.size() as isize);
+      }
+      ptr
+   }
+
+   unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+      let new_ptr = System.realloc(ptr, layout, new_size);
+      if !new_ptr.is_null() {
+         Self::record(new_size as isize - layout.size() as isize);
+      }
+      new_ptr
+   }
+}
+
+/// Gets the number of bytes currently allocated by this thread.
+pub fn allocated_bytes() -> isize {
+   ALLOCATED_BYTES.try_with(|b| b.get()).unwrap_or(0)
+}
+
+/// The resources used by the program being executed, measured to enforce the limits of the VM.
+#[derive(Default)]
+pub(super) struct Usage {
+   /// The number of instructions left until the limits are checked again.
+   pub(super) budget: u64,
+   /// The number of instructions between the last check of the limits and the next one.
+   interval: u64,
+   /// The number of instructions executed before the last check of the limits.
+   executed: u64,
+   /// The number of bytes allocated by the thread when the program started executing.
+   heap_baseline: isize,
+   /// The time when the program times out, if it has a timeout.
+   deadline: Option<Instant>,
+   /// The number of executed instructions after which the program ends, if it has timed out.
+   grace_end: Option<u64>,
+   /// Whether the program has run out of time to handle a `TimeoutError`.
+   pub(super) is_out_of_time: bool,
+}
+
+impl VM {
+   /// Starts measuring the resources used by a new program.
+   pub(super) fn reset_usage(&mut self) {
+      self.usage = Usage {
+         heap_baseline: allocated_bytes(),
+         deadline: self.config.timeout.and_then(|t| Some(self.now().ok()? + t)),
+         ..Default::default()
+      };
+   }
+
+   /// Checks that the program being executed has not exceeded the limits of this VM's
+   /// configuration, and schedules the next check. Called when the budget of instructions
+   /// until the next check runs out. Stack and heap errors cannot be caught by `try` blocks,
+   /// since the handlers would run without the resources to do so. Timeouts can be caught,
+   /// but the program ends if it does not finish shortly after being timed out.
+   ///
+   /// # Returns
+   /// - `Option<RuntimeResult>`: The error for the exceeded limit, if any.
+   pub(super) fn check_limits(&mut self) -> Option<RuntimeResult> {
+      let usage = &mut self.usage;
+      usage.executed += usage.interval;
+
+      let instruction_limit = usage.grace_end.or(self.config.max_instructions);
+      let is_periodic = self.config.max_stack_slots.is_some()
+         || self.config.max_heap_bytes.is_some()
+         || (usage.deadline.is_some() && usage.grace_end.is_none());
+
+      // Schedules the next check.
+      usage.interval = match instruction_limit {
+         Some(limit) if is_periodic => (limit.saturating_sub(usage.executed)).clamp(1, CHECK_INTERVAL),
+         Some(limit) => limit.saturating_sub(usage.executed).max(1),
+         None if is_periodic => CHECK_INTERVAL,
+         None => u64::MAX,
+      };
+      usage.budget = usage.interval;
+
+      if let Some(max) = self.config.max_stack_slots {
+         if self.stack.len() > max {
+            return Some(RuntimeResult::Error {
+               error: RuntimeErrorType::MemoryError,
+               message: format!("Maximum stack size of {} slots exceeded.", max),
+            });
+         }
+      }
+
+      if let Some(max) = self.config.max_heap_bytes {
+         if allocated_bytes() - usage.heap_baseline > max as isize {
+            return Some(RuntimeResult::Error {
+               error: RuntimeErrorType::MemoryError,
+               message: format!("Maximum heap size of {} bytes exceeded.", max),
+            });
+         }
+      }
+
+      // The deadline is missing when the VM has no clock to enforce the timeout with.
+      if self.config.timeout.is_some() && usage.deadline.is_none() {
+         return Some(RuntimeResult::Error {
+            error: RuntimeErrorType::IOError,
+            message: String::from("Cannot enforce the timeout without the clock of the system."),
+         });
+      }
+
+      if let Some(grace_end) = usage.grace_end {
+         if usage.executed >= grace_end {
+            usage.is_out_of_time = true;
+            return Some(RuntimeResult::Error {
+               error: RuntimeErrorType::TimeoutError,
+               message: String::from("The program did not finish after being timed out."),
+            });
+         }
+
+         return None;
+      }
+
+      let message = match (self.config.max_instructions, self.config.timeout) {
+         (Some(max), _) if usage.executed >= max => {
+            format!("Maximum number of instructions ({}) exceeded.", max)
+         }
+         (_, Some(timeout)) if usage.deadline.is_some_and(|d| Instant::now() >= d) => {
+            format!("Execution timed out after {:?}.", timeout)
+         }
+         _ => return None,
+      };
+
+      usage.grace_end = Some(usage.executed + TIMEOUT_GRACE_INSTRUCTIONS);
+      usage.interval = TIMEOUT_GRACE_INSTRUCTIONS;
+      usage.budget = usage.interval;
+
+      Some(RuntimeResult::Error {
+         error: RuntimeErrorType::TimeoutError,
+         message,
+      })
+   }
+}
@@ -0,0 +1,118 @@
+use crate::core::ast::{BinaryExprType, UnaryExprType};
+use crate::errors::RuntimeErrorType;
+use crate::objects::class_obj::BoundMethod;
+use crate::objects::Object;
+use crate::virtual_machine::{RuntimeResult, VM};
+
+/// Gets the name of the magic method that overloads a binary operator, if the operator can be
+/// overloaded. The `!=` operator falls back to negating `__eq__` if `__ne__` is not defined.
+pub fn binary_magic_method(opr: &BinaryExprType) -> Option<&'static str> {
+   Some(match opr {
+      BinaryExprType::Addition => "__add__",
+      BinaryExprType::BitwiseAND => "__and__",
+      BinaryExprType::BitwiseOR => "__or__",
+      BinaryExprType::BitwiseShiftLeft => "__shl__",
+      BinaryExprType::BitwiseShiftRight => "__shr__",
+      BinaryExprType::BitwiseXOR => "__xor__",
+      BinaryExprType::Division => "__div__",
+      BinaryExprType::Expo => "__pow__",
+      BinaryExprType::FloorDivision => "__floordiv__",
+      BinaryExprType::LogicEQ => "__eq__",
+      BinaryExprType::LogicGreaterThan => "__gt__",
+      BinaryExprType::LogicGreaterThanEQ => "__ge__",
+      BinaryExprType::LogicLessThan => "__lt__",
+      BinaryExprType::LogicLessThanEQ => "__le__",
+      BinaryExprType::LogicNotEQ => "__ne__",
+      BinaryExprType::Minus => "__sub__",
+      BinaryExprType::Modulus => "__mod__",
+      BinaryExprType::Multiplication => "__mul__",
+      _ => return None,
+   })
+}
+
+/// Gets the name of the magic method that overloads a unary operator, if the operator can be
+/// overloaded. The logic negation operator always applies to the truthiness of its operand.
+pub fn unary_magic_method(opr: &UnaryExprType) -> Option<&'static str> {
+   match opr {
+      UnaryExprType::ArithmeticNeg => Some("__neg__"),
+      UnaryExprType::BitwiseNeg => Some("__invert__"),
+      UnaryExprType::LogicNeg | UnaryExprType::Await => None,
+   }
+}
+
+impl VM {
+   /// Finds a magic method of an object, bound to the object.
+   ///
+   /// # Parameters
+   /// - `obj`: The object that may define the method.
+   /// - `name`: The name of the magic method.
+   fn find_magic_method(obj: &Object, name: &str) -> Option<Object> {
+      match obj {
+         Object::Instance(inst) => BoundMethod::find(inst, name).map(Object::BoundMethod),
+         _ => None,
+      }
+   }
+
+   /// Calls a magic method on the receiver of an operation. The receiver is the object below
+   /// the top `arg_count` objects of the stack, which are the arguments of the method. The
+   /// receiver's slot is replaced with the bound method, so the method's return value takes
+   /// the place of the receiver and its arguments once the method returns.
+   ///
+   /// # Parameters
+   /// - `name`: The name of the magic method.
+   /// - `arg_count`: The number of arguments on top of the receiver.
+   ///
+   /// # Returns
+   /// - `Option<RuntimeResult>`: The result of calling the method, or `None` if the receiver
+   ///   does not define the method.
+   pub(super) fn call_magic_method(&mut self, name: &str, arg_count: u8) -> Option<RuntimeResult> {
+      let method = VM::find_magic_method(&self.peek_stack(arg_count as usize), name)?;
+
+      self.set_stack(arg_count as usize, method.clone());
+      Some(self.call_object(method, arg_count))
+   }
+
+   /// Executes the `!=` operator for a receiver that defines `__eq__` but not `__ne__`, by
+   /// negating the result of `__eq__`.
+   ///
+   /// # Returns
+   /// - `Option<RuntimeResult>`: The result of the operation, or `None` if the receiver
+   ///   does not define `__eq__`.
+   pub(super) fn call_negated_eq(&mut self) -> Option<RuntimeResult> {
+      let method = VM::find_magic_method(&self.peek_stack(1), "__eq__")?;
+      let rhs = self.pop_stack();
+      self.pop_stack();
+
+      Some(match self.call_value(method, vec![rhs]) {
+         Ok(result) => self.push_stack(Object::Bool(result.is_falsey())),
+         Err(e) => e,
+      })
+   }
+
+   /// Converts an object into a string without colors. Instances that define a `__str__`
+   /// method are converted by calling the method, which must return a String.
+   ///
+   /// # Parameters
+   /// - `obj`: The object to convert.
+   ///
+   /// # Returns
+   /// - `Result<String, RuntimeResult>`: The string, or the runtime error
+   ///   raised by the `__str__` method.
+   pub fn stringify(&mut self, obj: &Object) -> Result<String, RuntimeResult> {
+      let method = match VM::find_magic_method(obj, "__str__") {
+         Some(m) => m,
+         None => return Ok(obj.as_plain_string()),
+      };
+
+      match self.call_value(method, vec![])? {
+         Object::String(s) => Ok(s.to_string()),
+         other => Err(RuntimeResult::Error {
+            error: RuntimeErrorType::TypeError,
+            message: format!(
+               "Method '__str__' must return a String. Found '{}' instead.",
+               other.type_name()
+            ),
+         }),
+      }
+   }
+}
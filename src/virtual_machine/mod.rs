@@ -1,18 +1,60 @@
-use crate::built_in::BuiltIn;
-use crate::compiler::Compiler;
+use crate::built_in::{BuiltIn, NativeFnBody};
+use crate::compiler::{Compiler, OptLevel, WarningMode};
 use crate::core::bytecode::OpCode;
-use crate::errors::{report_errors_list, report_runtime_error, RuntimeErrorType};
-use crate::objects::class_obj::InstanceObject;
+use crate::core::chunk::InlineCache;
+use crate::errors::{
+   print_warnings_list, report_errors_list, report_runtime_error, ColorsScope, HintonError, ObjectOprErrType,
+   RuntimeError, RuntimeErrorType,
+};
+use crate::objects::class_obj::{BoundMethod, InstanceLayout, InstanceObject};
+use crate::objects::dict_obj::DictObject;
+use crate::objects::enum_obj::{EnumValueObject, EnumVariantObject};
 use crate::objects::{ClosureObject, FuncObject, Object, UpValRef};
 use crate::parser::Parser;
-use crate::FRAMES_MAX;
+use crate::repl::parse_input;
+use crate::virtual_machine::gc::Heap;
 use hashbrown::HashMap;
+use std::borrow::Cow;
 use std::cell::RefCell;
+use std::io::{self, BufRead, Write};
 use std::path::PathBuf;
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
+use std::sync::atomic::AtomicBool;
 
 // Submodules
+mod clock;
+mod debugger;
+#[cfg(feature = "threaded_dispatch")]
+mod dispatch;
+mod gc;
+mod interner;
+mod limits;
+mod magic_methods;
+mod output;
+mod profiler;
 mod run;
+mod scheduler;
+mod stack;
+mod trace;
+mod worker;
+
+pub use debugger::{Breakpoints, DebugCommand, DebugHook, Debugger, PauseReason, SourceLocation};
+pub use gc::GcConfig;
+pub use interner::Interner;
+use limits::Usage;
+pub use limits::{CountingAllocator, VmConfig};
+pub use output::OutputBuffer;
+pub use profiler::Profiler;
+use scheduler::Scheduler;
+pub(crate) use scheduler::Timer;
+use stack::{StackSlot, StackValue};
+pub use trace::TraceMode;
+use trace::Tracer;
+pub use worker::{Message, Worker};
+
+/// Set when the programmer requests to interrupt the program currently being executed
+/// (e.g., by pressing Ctrl-C in the REPL). Checked by the VM before each instruction.
+pub static INTERRUPTED: AtomicBool = AtomicBool::new(false);
 
 /// Represents a single ongoing function call.
 pub struct CallFrame {
@@ -22,6 +64,13 @@ pub struct CallFrame {
    pub ip: usize,
    /// The stack index for the base of this call frame.
    pub return_index: usize,
+   /// The closures scheduled by `defer` statements, which are called (in reverse order)
+   /// when this call frame returns.
+   pub defers: Vec<Object>,
+   /// Whether this call frame belongs to a deferred call, whose return value is discarded.
+   pub is_deferred: bool,
+   /// The error being propagated through this call frame while its deferred calls run.
+   pub pending_error: Option<(RuntimeErrorType, String)>,
 }
 
 impl CallFrame {
@@ -54,6 +103,27 @@ impl CallFrame {
    }
 }
 
+/// Represents a variable captured by a closure. Used to inspect closures for debugging.
+pub struct CapturedVariable {
+   /// The name of the captured variable.
+   pub name: String,
+   /// The current value of the captured variable.
+   pub value: Object,
+   /// Whether the variable still lives on the stack (open), or has been moved into the
+   /// closure (closed) because the scope that declared it has ended.
+   pub is_open: bool,
+}
+
+/// Represents a call frame at the moment a runtime error was thrown. Used to report where
+/// an error re-thrown by a `finally` block was originally thrown.
+#[derive(Clone)]
+pub struct TraceFrame {
+   /// The function of the call frame.
+   pub function: Rc<RefCell<FuncObject>>,
+   /// The index of the instruction after the one being executed by the call frame.
+   pub ip: usize,
+}
+
 /// Represents a virtual machine.
 pub struct VM {
    /// The path to the source file.
@@ -61,14 +131,63 @@ pub struct VM {
    /// A list of call frames (the VM's call frames stack).
    frames: Vec<CallFrame>,
    /// A list of temporary objects (the VM's values stack).
-   pub(crate) stack: Vec<Object>,
-   /// The global declarations made in the program.
-   globals: HashMap<String, Object>,
+   pub(crate) stack: Vec<StackValue>,
+   /// The values of the global declarations made in the program, indexed by their slots.
+   globals: Vec<Object>,
+   /// The names of the global declarations made in the program, in the order of their slots.
+   global_names: Vec<Rc<str>>,
+   /// The exports of the modules that have already been
+   /// loaded, keyed by the name of the module's function.
+   modules: HashMap<String, Object>,
    /// A collection of UpValues in the program.
    /// TODO: Find a better way to manage UpValues.
    up_values: Vec<Rc<RefCell<UpValRef>>>,
    /// The built-in functions and primitives of Hinton
    pub(crate) built_in: BuiltIn,
+   /// The managed heap, which collects reference cycles.
+   heap: Heap,
+   /// The limits on the resources used by the programs executed in this VM.
+   config: VmConfig,
+   /// The resources used by the program being executed.
+   usage: Usage,
+   /// The table of interned strings, shared by the programs executed in this VM.
+   pub(crate) interner: Interner,
+   /// Determines how the warnings found while compiling a program are handled.
+   warning_mode: WarningMode,
+   /// Determines which optimizations are applied when compiling a program.
+   opt_level: OptLevel,
+   /// The directories searched for the modules imported by a program.
+   module_dirs: Vec<PathBuf>,
+   /// The command-line arguments passed to the program, after the path of the script.
+   pub(crate) script_args: Vec<String>,
+   /// The source of the text read by `input()`, `read_line()`, and `read_all()`.
+   /// Without a source, the text is read from the standard input of the process.
+   pub(crate) input: Option<Box<dyn BufRead>>,
+   /// The stream where the output of the program (e.g., by `print(...)`) is written.
+   pub(crate) stdout: Box<dyn Write>,
+   /// The stream where the errors reported while interpreting a program are written.
+   pub(crate) stderr: Box<dyn Write>,
+   /// The debugger attached to this VM, if any.
+   debugger: Option<Debugger>,
+   /// The profiler attached to this VM, if any.
+   profiler: Option<Profiler>,
+   /// The tracer that prints the instructions executed by this VM, if any.
+   tracer: Option<Tracer>,
+   /// The number of native functions waiting for a call made with `VM::call_value` to return.
+   native_calls: usize,
+   /// The green threads spawned by the program being executed.
+   scheduler: Scheduler,
+   /// The compiler of the programs evaluated with `VM::eval`, which keeps track of
+   /// the global declarations made by the previously evaluated programs.
+   compiler: Option<Compiler>,
+   /// Whether the output and the diagnostics of the programs executed in this VM may be colored.
+   colors: bool,
+   /// The call frames where the error being propagated was thrown, if it was re-thrown by a
+   /// `finally` block. Otherwise, the error was thrown by the current call frames.
+   error_trace: Option<Vec<TraceFrame>>,
+   /// The call frames where each caught error was thrown, along with the error's dictionary
+   /// (which is the pending error of a `finally` block, or the error of a `catch` block).
+   error_traces: Vec<(Weak<RefCell<DictObject>>, Vec<TraceFrame>)>,
 }
 
 /// The types of results the interpreter can return.
@@ -87,70 +206,498 @@ pub enum RuntimeResult {
    },
    EndOK,
    Continue,
+   /// The running green thread waits for something (e.g., a timer, or a value from a channel),
+   /// so the VM switches to another green thread before executing the next instruction.
+   Suspend,
 }
 
 impl VM {
-   /// Interprets the source text of a file.
+   /// Creates a new virtual machine.
    ///
-   /// # Returns
-   /// - `InterpretResult`: The result of the source interpretation.
-   pub fn interpret(filepath: PathBuf, source: &str) -> InterpretResult {
-      // Creates a new virtual machine
-      let mut _self = VM {
+   /// # Parameters
+   /// - `filepath`: The path to the source file.
+   pub fn new(filepath: PathBuf) -> Self {
+      VM {
          stack: Vec::with_capacity(256),
          frames: Vec::with_capacity(256),
          filepath,
-         globals: Default::default(),
+         globals: vec![],
+         global_names: vec![],
+         modules: Default::default(),
          up_values: vec![],
          built_in: BuiltIn::default(),
+         heap: Heap::new(GcConfig::default()),
+         config: VmConfig::default(),
+         usage: Usage::default(),
+         interner: Interner::default(),
+         warning_mode: WarningMode::default(),
+         opt_level: OptLevel::default(),
+         module_dirs: vec![],
+         script_args: vec![],
+         input: None,
+         stdout: Box::new(io::stdout()),
+         stderr: Box::new(io::stderr()),
+         debugger: None,
+         profiler: None,
+         tracer: None,
+         native_calls: 0,
+         scheduler: Scheduler::default(),
+         compiler: None,
+         colors: true,
+         error_trace: None,
+         error_traces: vec![],
+      }
+   }
+
+   /// Sets the configuration of this VM's garbage collector.
+   ///
+   /// # Parameters
+   /// - `config`: The configuration of the garbage collector.
+   pub fn with_gc_config(mut self, config: GcConfig) -> Self {
+      self.heap = Heap::new(config);
+      self
+   }
+
+   /// Sets the limits on the resources used by the programs executed in this VM.
+   ///
+   /// # Parameters
+   /// - `config`: The limits of the VM.
+   pub fn with_config(mut self, config: VmConfig) -> Self {
+      self.config = config;
+      self
+   }
+
+   /// Prevents the programs executed in this VM from accessing the host system. The natives
+   /// that read or write files, spawn processes, open connections, or load plugins are removed,
+   /// and programs cannot import modules. Together with the limits set with `with_config(...)`,
+   /// this allows the VM to run untrusted programs.
+   pub fn without_system_access(mut self) -> Self {
+      self.built_in.remove_system_access();
+      self
+   }
+
+   /// Sets how the warnings found while compiling a program are handled.
+   ///
+   /// # Parameters
+   /// - `mode`: The warning mode.
+   pub fn with_warning_mode(mut self, mode: WarningMode) -> Self {
+      self.warning_mode = mode;
+      self
+   }
+
+   /// Sets which optimizations are applied when compiling a program.
+   ///
+   /// # Parameters
+   /// - `level`: The optimization level.
+   pub fn with_opt_level(mut self, level: OptLevel) -> Self {
+      self.opt_level = level;
+      self
+   }
+
+   /// Sets the directories searched for the modules that cannot be found relative to the
+   /// file that imports them (e.g., the source directories of a Hinton project).
+   ///
+   /// # Parameters
+   /// - `dirs`: The module directories.
+   pub fn with_module_dirs(mut self, dirs: Vec<PathBuf>) -> Self {
+      self.module_dirs = dirs;
+      self
+   }
+
+   /// Sets the command-line arguments passed to the program, which
+   /// are available through `Process.args()`.
+   ///
+   /// # Parameters
+   /// - `args`: The arguments that follow the path of the script.
+   pub fn with_script_args(mut self, args: Vec<String>) -> Self {
+      self.script_args = args;
+      self
+   }
+
+   /// Sets the source of the text read by the program with `input()`, `read_line()`,
+   /// and `read_all()`, instead of the standard input of the process.
+   ///
+   /// # Parameters
+   /// - `input`: The source of the text.
+   pub fn with_input(mut self, input: Box<dyn BufRead>) -> Self {
+      self.input = Some(input);
+      self
+   }
+
+   /// Sets the stream where the output of the programs executed in this VM is written,
+   /// instead of the standard output of the process. An `OutputBuffer` can be used to
+   /// capture the output, and `std::io::sink()` to suppress it.
+   ///
+   /// # Parameters
+   /// - `stdout`: The output stream.
+   pub fn with_stdout(mut self, stdout: Box<dyn Write>) -> Self {
+      self.stdout = stdout;
+      self
+   }
+
+   /// Sets the stream where the syntax, compiler, and runtime errors (and the compiler
+   /// warnings) reported by this VM are written, instead of the standard error of the process.
+   ///
+   /// # Parameters
+   /// - `stderr`: The error stream.
+   pub fn with_stderr(mut self, stderr: Box<dyn Write>) -> Self {
+      self.stderr = stderr;
+      self
+   }
+
+   /// Prevents the programs executed in this VM from coloring their output and diagnostics
+   /// with ANSI escape sequences, even if colors are enabled for the console. Useful when the
+   /// output is captured (e.g., with an `OutputBuffer`) instead of printed to a terminal.
+   pub fn without_colors(mut self) -> Self {
+      self.colors = false;
+      self
+   }
+
+   /// Attaches a debugger to this VM, which can pause the program being executed.
+   ///
+   /// # Parameters
+   /// - `debugger`: The debugger.
+   pub fn with_debugger(mut self, debugger: Debugger) -> Self {
+      self.debugger = Some(debugger);
+      self
+   }
+
+   /// Attaches a profiler to this VM, which measures the time spent executing each function
+   /// and each category of instructions. The profile can be retrieved with `take_profiler`.
+   pub fn with_profiler(mut self) -> Self {
+      self.profiler = Some(Profiler::new());
+      self
+   }
+
+   /// Detaches the profiler from this VM.
+   ///
+   /// # Returns
+   /// - `Option<Profiler>`: The profiler with the measurements taken so far, if any.
+   pub fn take_profiler(&mut self) -> Option<Profiler> {
+      self.profiler.take()
+   }
+
+   /// Prints the instructions executed by this VM to the standard error, along with the
+   /// values in the stack slots of the current call frame.
+   ///
+   /// # Parameters
+   /// - `mode`: Which of the executed instructions are printed.
+   pub fn with_trace(mut self, mode: TraceMode) -> Self {
+      self.tracer = Some(Tracer::new(mode));
+      self
+   }
+
+   /// Registers a native function that can be called by the programs executed in this VM. This
+   /// allows host Rust programs that embed the VM to expose their own functionality to Hinton.
+   /// Objects kept by the function in between calls are not seen by the garbage collector, so
+   /// they may have their contents cleared if the program no longer references them.
+   ///
+   /// # Parameters
+   /// - `name`: The name of the function in Hinton programs.
+   /// - `arity`: The number of arguments the function takes.
+   /// - `body`: The function's body, which receives the arguments of the call.
+   ///
+   /// # Panics
+   /// If there is already a native function with the given name.
+   ///
+   /// # Examples
+   /// ```
+   /// vm.register_native("double", 1, |args| match args[0] {
+   ///    Object::Int(x) => Ok(Object::Int(x * 2)),
+   ///    _ => Err(ObjectOprErrType::TypeError(String::from("Expected an Int."))),
+   /// });
+   /// ```
+   pub fn register_native<F>(&mut self, name: &str, arity: u8, body: F)
+   where
+      F: Fn(&[Object]) -> Result<Object, ObjectOprErrType> + 'static,
+   {
+      self
+         .built_in
+         .natives
+         .add_function(name, arity, arity, NativeFnBody::Host(Rc::new(body)));
+   }
+
+   /// Gets the variables captured by a closure, in the order the closure captured them. This
+   /// allows host Rust programs and debugging tools to inspect the state of a closure.
+   ///
+   /// # Parameters
+   /// - `obj`: The closure (or bound method) to inspect.
+   ///
+   /// # Returns
+   /// - `Option<Vec<CapturedVariable>>`: The captured variables, or `None` if the object is not
+   ///   a function. Functions that do not capture any variables have no captured variables.
+   pub fn inspect_closure(&self, obj: &Object) -> Option<Vec<CapturedVariable>> {
+      let closure = match obj {
+         Object::Closure(c) => c,
+         Object::BoundMethod(b) => &b.method,
+         Object::Function(_) => return Some(vec![]),
+         _ => return None,
       };
 
+      let function = closure.function.borrow();
+      let captured = function.up_val_names.iter().zip(closure.up_values.iter());
+
+      Some(
+         captured
+            .map(|(name, up_val)| {
+               let (value, is_open) = match &*up_val.borrow() {
+                  UpValRef::Open(idx) => (self.stack[*idx].to_object(), true),
+                  UpValRef::Closed(obj) => (obj.clone(), false),
+               };
+
+               CapturedVariable {
+                  name: name.clone(),
+                  value,
+                  is_open,
+               }
+            })
+            .collect(),
+      )
+   }
+
+   /// Interprets the source text of a file.
+   ///
+   /// # Parameters
+   /// - `source`: The source text of the file at this VM's filepath.
+   ///
+   /// # Returns
+   /// - `InterpretResult`: The result of the source interpretation.
+   pub fn interpret(&mut self, source: &str) -> InterpretResult {
+      let _colors = ColorsScope::new(self.colors);
+
       // Parses the program into an AST and aborts if there are any parsing errors.
       let ast = match Parser::parse(source) {
          Ok(x) => Rc::new(x),
          Err(e) => {
-            report_errors_list(&_self.filepath, e, source);
+            let _ = report_errors_list(&mut self.stderr, &self.filepath, e, source);
             return InterpretResult::ParseError;
          }
       };
 
       // Compiles the program into bytecode and aborts if there are any compiling errors.
-      let module = match Compiler::compile_ast(&_self.filepath, &ast, &_self.built_in) {
-         Ok(x) => x,
+      let module = match Compiler::compile_ast_with_warnings(
+         &self.filepath,
+         &ast,
+         &self.built_in,
+         self.warning_mode,
+         self.opt_level,
+         &self.module_dirs,
+      ) {
+         Ok((x, warnings)) => {
+            let _ = print_warnings_list(&mut self.stderr, &self.filepath, &warnings, source);
+            x
+         }
          Err(e) => {
-            report_errors_list(&_self.filepath, e, source);
+            let _ = report_errors_list(&mut self.stderr, &self.filepath, e, source);
             return InterpretResult::CompileError;
          }
       };
 
+      self.execute(module, source)
+   }
+
+   /// Executes a compiled program in this virtual machine. The global declarations made by
+   /// previously executed programs are preserved, which allows the REPL to reuse the same VM
+   /// for every input.
+   ///
+   /// # Parameters
+   /// - `module`: The compiled program's main function.
+   /// - `source`: The source text of the program. Used for error reporting.
+   ///
+   /// # Returns
+   /// - `InterpretResult`: The result of the program's execution.
+   pub fn execute(&mut self, module: FuncObject, source: &str) -> InterpretResult {
+      let _colors = ColorsScope::new(self.colors);
+
+      match self.run_module(module) {
+         RuntimeResult::EndOK => InterpretResult::Ok,
+         RuntimeResult::Error { error, message } => {
+            // The error stream is taken out of the VM while the VM's state is reported.
+            let mut stderr = std::mem::replace(&mut self.stderr, Box::new(io::sink()));
+            let _ = report_runtime_error(&mut stderr, self, error, message, source);
+            self.stderr = stderr;
+            InterpretResult::RuntimeError
+         }
+         RuntimeResult::Continue | RuntimeResult::Suspend => unreachable!(),
+      }
+   }
+
+   /// Runs a compiled program in this virtual machine, without reporting its errors.
+   ///
+   /// # Parameters
+   /// - `module`: The compiled program's main function.
+   ///
+   /// # Returns
+   /// - `RuntimeResult`: Either `EndOK`, or the error that stopped the program.
+   fn run_module(&mut self, mut module: FuncObject) -> RuntimeResult {
+      // Discards the state left behind by a previous program that ended in an error.
+      self.stack.clear();
+      self.frames.clear();
+      self.up_values.clear();
+      self.scheduler.reset();
+      self.error_trace = None;
+
+      self.intern_constants(&mut module);
+      self.link_globals(&module.globals);
+      self.reset_usage();
+
       let f = Rc::new(RefCell::new(module));
-      _self.stack.push(Object::Function(f.clone()));
+      self
+         .stack
+         .push(StackValue::from_object(Object::Function(f.clone())));
 
-      match _self.call_function(f, 0) {
-         RuntimeResult::Continue => {
-            // Runs the program.
-            match _self.run() {
-               RuntimeResult::EndOK => InterpretResult::Ok,
-               RuntimeResult::Error { error, message } => {
-                  report_runtime_error(&_self, error, message, source);
-                  InterpretResult::RuntimeError
-               }
-               RuntimeResult::Continue => unreachable!(),
+      match self.call_function(f, 0) {
+         RuntimeResult::Continue => self.run(),
+         result => result,
+      }
+   }
+
+   /// Assigns the slots of the globals declared by a program before it runs. The compiler
+   /// numbers the globals of the programs evaluated in the REPL (or with `VM::eval`) one after
+   /// another, so the slots of their globals usually extend the slots already in use. Otherwise,
+   /// the table of globals is rebuilt in the program's order, and the values of the existing
+   /// globals are moved to the slots of the program's globals with the same name.
+   ///
+   /// # Parameters
+   /// - `names`: The names of the program's globals, in the order of their slots.
+   fn link_globals(&mut self, names: &[String]) {
+      let is_extension = names.len() >= self.global_names.len()
+         && self.global_names.iter().zip(names).all(|(a, b)| **a == **b);
+
+      if !is_extension {
+         let mut globals = Vec::with_capacity(names.len());
+         let mut global_names = Vec::with_capacity(names.len());
+
+         for name in names.iter() {
+            globals.push(self.get_global(name).unwrap_or(Object::Null));
+            global_names.push(Rc::from(name.as_str()));
+         }
+
+         // The globals that are not declared by the program keep their values, so that the
+         // host can still read them after the program runs.
+         for (slot, name) in self.global_names.iter().enumerate() {
+            let is_latest = self.global_names[slot + 1..].iter().all(|n| n != name);
+
+            if is_latest && !names.iter().any(|n| **n == **name) {
+               globals.push(self.globals[slot].clone());
+               global_names.push(name.clone());
             }
          }
+
+         self.globals = globals;
+         self.global_names = global_names;
+      }
+
+      for name in names[self.global_names.len().min(names.len())..].iter() {
+         self.globals.push(Object::Null);
+         self.global_names.push(Rc::from(name.as_str()));
+      }
+   }
+
+   /// Evaluates the source text of a program, and returns its result instead of printing its
+   /// errors. Like in the REPL, the global declarations made by the programs previously
+   /// evaluated in this VM are available to the program, and the value of the expression
+   /// statement at the end of the program (if any) is returned. The semicolon at the end of
+   /// the program can be omitted, and compiler warnings are ignored. The garbage collector
+   /// keeps the returned object alive for as long as the host holds it.
+   ///
+   /// # Parameters
+   /// - `source`: The source text of the program.
+   ///
+   /// # Returns
+   /// - `Result<Object, HintonError>`: The value of the last expression statement in the program
+   ///   (or `null` if the program does not end with one), or the error that stopped the program.
+   pub fn eval(&mut self, source: &str) -> Result<Object, HintonError> {
+      let _colors = ColorsScope::new(self.colors);
+
+      let mut buffer = String::new();
+      let ast = parse_input(source, &mut buffer).map_err(HintonError::Parse)?;
+
+      if self.compiler.is_none() {
+         self.compiler = Some(Compiler::new_repl(&self.filepath, &self.built_in));
+      }
+
+      let compiler = self.compiler.as_mut().unwrap();
+      let module = compiler.compile_repl_input(&ast).map_err(HintonError::Compile)?;
+      compiler.take_warnings();
+
+      match self.run_module(module) {
          RuntimeResult::Error { error, message } => {
-            report_runtime_error(&_self, error, message, source);
-            InterpretResult::RuntimeError
+            Err(HintonError::Runtime(RuntimeError::new(self, error, message)))
          }
-         RuntimeResult::EndOK => unreachable!(),
+         _ => Ok(self.last_value().unwrap_or(Object::Null)),
       }
    }
 
+   /// Gets the value of a global declaration made by the programs executed in this VM.
+   ///
+   /// # Parameters
+   /// - `name`: The name of the global declaration.
+   pub fn get_global(&self, name: &str) -> Option<Object> {
+      // Globals are accessed by slot, so the host is the only one who looks them up by name.
+      let slot = self.global_names.iter().rposition(|n| **n == *name)?;
+      Some(self.globals[slot].clone())
+   }
+
+   /// Sets the value of a global variable, which can be used by the programs that are later
+   /// evaluated in this VM with `VM::eval` (the programs executed with `VM::interpret` are
+   /// compiled on their own, so they cannot refer to globals they do not declare).
+   ///
+   /// # Parameters
+   /// - `name`: The name of the global variable.
+   /// - `value`: The value of the global variable.
+   pub fn set_global(&mut self, name: &str, value: Object) {
+      if self.compiler.is_none() {
+         self.compiler = Some(Compiler::new_repl(&self.filepath, &self.built_in));
+      }
+
+      let compiler = self.compiler.as_mut().unwrap();
+      let slot = compiler.declare_host_global(name);
+      let names = compiler.global_names();
+
+      self.link_globals(&names);
+      self.globals[slot] = value;
+   }
+
+   /// Gets the object left on top of the stack by the last executed program, if any. In the
+   /// REPL, this is the value of the expression statement entered by the programmer.
+   pub fn last_value(&self) -> Option<Object> {
+      // The first slot of the stack is always occupied by the main function.
+      if self.stack.len() > 1 {
+         self.stack.last().map(StackSlot::to_object)
+      } else {
+         None
+      }
+   }
+
+   /// Gets the number of objects tracked by this VM's managed heap.
+   #[cfg(test)]
+   pub fn heap_size(&self) -> usize {
+      self.heap.len()
+   }
+
    /// Gets a reference to the call frames stack.
    pub fn frames_stack(&self) -> &Vec<CallFrame> {
       &self.frames
    }
 
+   /// Gets the call frames where the error being reported was thrown, from the outermost to
+   /// the innermost frame. These are the current call frames, unless the error was re-thrown
+   /// by a `finally` block after the frames that threw it had been unwound.
+   pub fn traceback(&self) -> Vec<TraceFrame> {
+      match &self.error_trace {
+         Some(trace) => trace.clone(),
+         None => self
+            .frames
+            .iter()
+            .map(|frame| TraceFrame {
+               function: frame.closure.function.clone(),
+               ip: frame.ip,
+            })
+            .collect(),
+      }
+   }
+
    /// Gets an immutable reference to the current call frame.
    pub fn current_frame(&self) -> &CallFrame {
       self.frames.last().unwrap()
@@ -180,7 +727,7 @@ impl VM {
    /// Pops the last object in the objects stack.
    pub(crate) fn pop_stack(&mut self) -> Object {
       match self.stack.pop() {
-         Some(obj) => obj,
+         Some(obj) => obj.into_object(),
          None => {
             panic!("Stack is empty!")
          }
@@ -189,24 +736,30 @@ impl VM {
 
    /// Pushes an object onto the back of the objects stack.
    pub(crate) fn push_stack(&mut self, new_val: Object) -> RuntimeResult {
-      self.stack.push(new_val);
+      self.stack.push(StackValue::from_object(new_val));
       RuntimeResult::Continue
    }
 
    /// Gets an immutable reference to the object at the provided stack-top offset.
-   pub(crate) fn peek_stack(&self, pos: usize) -> &Object {
-      &self.stack[self.stack.len() - 1 - pos]
+   pub(crate) fn peek_stack(&self, pos: usize) -> Cow<'_, Object> {
+      self.stack[self.stack.len() - 1 - pos].as_object()
+   }
+
+   /// Pops the top `N` objects from the stack, in the order they were pushed onto the stack.
+   pub(crate) fn pop_stack_n(&mut self, n: usize) -> Vec<Object> {
+      let start = self.stack.len() - n;
+      self.stack.drain(start..).map(StackSlot::into_object).collect()
    }
 
-   /// Gets a mutable reference to the object at the provided stack-top offset.
-   fn peek_stack_mut(&mut self, pos: usize) -> &mut Object {
+   /// Replaces the object at the provided stack-top offset.
+   fn set_stack(&mut self, pos: usize, new_val: Object) {
       let stack_size = self.stack.len();
-      &mut self.stack[stack_size - 1 - pos]
+      self.stack[stack_size - 1 - pos] = StackValue::from_object(new_val);
    }
 
    /// Gets an immutable reference to the object at the provided stack index.
-   fn peek_stack_abs(&self, pos: usize) -> &Object {
-      &self.stack[pos]
+   fn peek_stack_abs(&self, pos: usize) -> Cow<'_, Object> {
+      self.stack[pos].as_object()
    }
 
    /// Gets an UpValue from the UpValues list.
@@ -233,17 +786,81 @@ impl VM {
       }
    }
 
+   /// Gets the slot of the property accessed by the current instruction, if the inline cache of
+   /// the instruction remembers the given layout.
+   ///
+   /// # Parameters
+   /// - `cache`: The position of the instruction's inline cache in the chunk.
+   /// - `layout`: The layout of the instance whose property is accessed.
+   fn cached_slot(&self, cache: usize, layout: &Rc<InstanceLayout>) -> Option<usize> {
+      let function = self.current_frame().closure.function.borrow();
+      let cache = function.chunk.get_cache(cache);
+
+      match &cache.layout {
+         Some(l) if Rc::ptr_eq(l, layout) => Some(cache.slot),
+         _ => None,
+      }
+   }
+
+   /// Makes the inline cache of the current instruction remember the slot of a property in the
+   /// given layout. Only the accesses to public properties that do not have accessors (and, when
+   /// assigned, are not constant) succeed, so the cached accesses can skip those checks.
+   ///
+   /// # Parameters
+   /// - `cache`: The position of the instruction's inline cache in the chunk.
+   /// - `layout`: The layout of the instance whose property was accessed.
+   /// - `prop_name`: The name of the property.
+   fn update_cache(&self, cache: usize, layout: &Rc<InstanceLayout>, prop_name: &str) {
+      if let Some(slot) = layout.slots.get(prop_name) {
+         let mut function = self.current_frame().closure.function.borrow_mut();
+         function.chunk.set_cache(
+            cache,
+            InlineCache {
+               layout: Some(layout.clone()),
+               slot: *slot,
+            },
+         );
+      }
+   }
+
+   /// Pushes a member of an instance onto the stack. Methods are bound to the instance first.
+   ///
+   /// # Parameters
+   /// - `instance`: The instance that owns the member.
+   /// - `member`: The value of the member.
+   fn push_instance_member(
+      &mut self,
+      instance: &Rc<RefCell<InstanceObject>>,
+      member: Object,
+   ) -> RuntimeResult {
+      match member {
+         Object::Closure(c) => self.push_stack(Object::BoundMethod(BoundMethod {
+            receiver: instance.clone(),
+            method: c,
+         })),
+         Object::Function(f) => self.push_stack(Object::BoundMethod(BoundMethod {
+            receiver: instance.clone(),
+            method: ClosureObject {
+               function: f,
+               up_values: vec![],
+            },
+         })),
+         _ => self.push_stack(member),
+      }
+   }
+
    /// Tries to call the given object, or returns a runtime error if the object is not callable.
    fn call_object(&mut self, callee: Object, arg_count: u8) -> RuntimeResult {
       return match callee {
          Object::Function(obj) => self.call_function(obj, arg_count),
          Object::Closure(obj) => self.call_closure(obj, arg_count),
          Object::BoundMethod(obj) => {
-            *self.peek_stack_mut(arg_count as usize) = Object::Instance(obj.receiver);
+            self.set_stack(arg_count as usize, Object::Instance(obj.receiver));
             self.call_closure(obj.method, arg_count)
          }
          Object::Native(obj) => BuiltIn::call_native_fn(self, *obj, arg_count),
          Object::BoundNativeMethod(obj) => BuiltIn::call_bound_method(self, obj, arg_count),
+         Object::EnumVariant(obj) => self.call_enum_variant(obj, arg_count),
          _ => RuntimeResult::Error {
             error: RuntimeErrorType::TypeError,
             message: format!("Cannot call object of type '{}'.", callee.type_name()),
@@ -251,6 +868,66 @@ impl VM {
       };
    }
 
+   /// Creates a value of an enum by calling one of its variants with the values of its payload.
+   ///
+   /// # Parameters
+   /// - `variant`: The variant of the enum.
+   /// - `arg_count`: The number of arguments passed to the variant.
+   ///
+   /// # Returns
+   /// - `RuntimeResult`: The result of the call.
+   fn call_enum_variant(&mut self, variant: EnumVariantObject, arg_count: u8) -> RuntimeResult {
+      let field_count = variant.fields().len() as u8;
+      if let Err(e) = self.arity_check(field_count, field_count, arg_count) {
+         return e;
+      }
+
+      let payload = self.pop_stack_n(arg_count as usize);
+      self.pop_stack(); // Pop the variant off the stack.
+
+      self.push_stack(Object::EnumValue(Rc::new(EnumValueObject { variant, payload })))
+   }
+
+   /// Calls an object with the given arguments from native code, and runs the call until it
+   /// returns. While the call runs, the garbage collector is paused, because the objects held
+   /// by the caller are not reachable from the VM's roots.
+   ///
+   /// # Parameters
+   /// - `callee`: The object to call.
+   /// - `args`: The arguments of the call.
+   ///
+   /// # Returns
+   /// - `Result<Object, RuntimeResult>`: The return value of the call, or the runtime error
+   ///   that was not caught within the call.
+   pub fn call_value(&mut self, callee: Object, args: Vec<Object>) -> Result<Object, RuntimeResult> {
+      let depth = self.frames.len();
+      let stack_len = self.stack.len();
+      let arg_count = args.len() as u8;
+
+      self.push_stack(callee.clone());
+      args.into_iter().for_each(|arg| {
+         self.push_stack(arg);
+      });
+
+      self.native_calls += 1;
+      let result = match self.call_object(callee, arg_count) {
+         RuntimeResult::Continue if self.frames.len() > depth => self.run_until(depth),
+         result => result,
+      };
+      self.native_calls -= 1;
+
+      match result {
+         RuntimeResult::Continue => Ok(self.pop_stack()),
+         _ => {
+            // Discards the frames and stack slots left by the failed call.
+            self.close_up_values_from(stack_len);
+            self.frames.truncate(depth);
+            self.stack.truncate(stack_len);
+            Err(result)
+         }
+      }
+   }
+
    /// Tries to call a function object, or returns a runtime error is there was a problem while
    /// creating the function's call frame.
    fn call_function(&mut self, callee: Rc<RefCell<FuncObject>>, arg_count: u8) -> RuntimeResult {
@@ -260,6 +937,10 @@ impl VM {
 
       let max_arity = callee.borrow().max_arity as usize;
 
+      if let Some(profiler) = &mut self.profiler {
+         profiler.record_call(&callee.borrow().name);
+      }
+
       self.frames.push(CallFrame {
          closure: ClosureObject {
             function: callee,
@@ -267,6 +948,9 @@ impl VM {
          },
          ip: 0,
          return_index: self.stack.len() - max_arity - 1,
+         defers: vec![],
+         is_deferred: false,
+         pending_error: None,
       });
 
       RuntimeResult::Continue
@@ -281,10 +965,17 @@ impl VM {
 
       let max_arity = callee.function.borrow().max_arity as usize;
 
+      if let Some(profiler) = &mut self.profiler {
+         profiler.record_call(&callee.function.borrow().name);
+      }
+
       self.frames.push(CallFrame {
          closure: callee,
          ip: 0,
          return_index: self.stack.len() - max_arity - 1,
+         defers: vec![],
+         is_deferred: false,
+         pending_error: None,
       });
 
       RuntimeResult::Continue
@@ -293,11 +984,30 @@ impl VM {
    /// Verifies that a function or closure can be called with the provided number of arguments,
    /// and that the maximum recursion depth has not been exceeded.
    fn verify_call(&mut self, function: &FuncObject, arg_count: u8) -> Result<(), RuntimeResult> {
-      let max_arity = function.max_arity;
       let min_arity = function.min_arity;
 
+      // The rest parameter of a variadic function is not counted as a regular
+      // parameter, because it collects any number of extra arguments.
+      let max_arity = if function.is_variadic {
+         function.max_arity - 1
+      } else {
+         function.max_arity
+      };
+
       // Performs an arity check on the function call.
-      self.arity_check(min_arity, max_arity, arg_count)?;
+      if function.is_variadic {
+         if arg_count < min_arity {
+            return Err(RuntimeResult::Error {
+               error: RuntimeErrorType::ArgumentError,
+               message: format!(
+                  "Expected at least {} arguments but got {} instead.",
+                  min_arity, arg_count
+               ),
+            });
+         }
+      } else {
+         self.arity_check(min_arity, max_arity, arg_count)?;
+      }
 
       // Pushes the default values onto the stack
       // if they were not passed into the func call
@@ -311,8 +1021,16 @@ impl VM {
          }
       }
 
+      // Collects the extra arguments into an array for the rest parameter.
+      if function.is_variadic {
+         let extra_args = arg_count.saturating_sub(max_arity) as usize;
+         let rest = self.pop_stack_n(extra_args);
+         let rest = self.alloc_array(rest);
+         self.push_stack(rest);
+      }
+
       // Check we are not overflowing the stack of frames
-      if self.frames.len() >= (FRAMES_MAX as usize) {
+      if self.frames.len() >= self.config.max_frames {
          return Err(RuntimeResult::Error {
             error: RuntimeErrorType::RecursionError,
             message: String::from("Maximum recursion depth exceeded."),
@@ -322,6 +1040,171 @@ impl VM {
       Ok(())
    }
 
+   /// Moves the named arguments of a call into the stack positions of the callee's parameters, and
+   /// fills the parameters that were not given an argument with their default values. Before this
+   /// function is called, the positional arguments are on top of the stack, followed by a name and
+   /// a value for each named argument.
+   ///
+   /// # Parameters
+   /// - `callee`: The function or method being called.
+   /// - `arg_count`: The total number of arguments in the call.
+   /// - `named_count`: How many of those arguments are named.
+   ///
+   /// # Returns
+   /// - `Result<u8, RuntimeResult>`: The number of arguments on the stack after they are bound.
+   pub(super) fn bind_named_args(
+      &mut self,
+      callee: &Object,
+      arg_count: u8,
+      named_count: u8,
+   ) -> Result<u8, RuntimeResult> {
+      let function = match callee {
+         Object::Function(f) => f.clone(),
+         Object::Closure(c) => c.function.clone(),
+         Object::BoundMethod(m) => m.method.function.clone(),
+         _ => {
+            return Err(RuntimeResult::Error {
+               error: RuntimeErrorType::ArgumentError,
+               message: format!(
+                  "Cannot pass named arguments to an object of type '{}'.",
+                  callee.type_name()
+               ),
+            })
+         }
+      };
+
+      let function = function.borrow();
+      let min_arity = function.min_arity as usize;
+
+      // Named arguments cannot be bound to the rest parameter of a variadic function.
+      let max_arity = if function.is_variadic {
+         function.max_arity as usize - 1
+      } else {
+         function.max_arity as usize
+      };
+
+      if !function.is_variadic && arg_count > function.max_arity {
+         self.arity_check(function.min_arity, function.max_arity, arg_count)?;
+      }
+
+      let mut named: Vec<(Object, Object)> = Vec::with_capacity(named_count as usize);
+      for _ in 0..named_count {
+         let value = self.pop_stack();
+         let name = self.pop_stack();
+         named.push((name, value));
+      }
+      named.reverse();
+
+      let positional_count = (arg_count - named_count) as usize;
+      let mut slots: Vec<Option<Object>> = self.pop_stack_n(positional_count).into_iter().map(Some).collect();
+      let extra_args = if slots.len() > max_arity {
+         slots.split_off(max_arity)
+      } else {
+         vec![]
+      };
+      slots.resize(max_arity, None);
+
+      for (name, value) in named {
+         let name = match name {
+            Object::String(s) => s,
+            _ => unreachable!("Expected a String object for the argument's name."),
+         };
+
+         let idx = match function.params[..max_arity].iter().position(|p| **p == *name) {
+            Some(idx) => idx,
+            None => {
+               return Err(RuntimeResult::Error {
+                  error: RuntimeErrorType::ArgumentError,
+                  message: format!("Unknown argument '{}' in call to '{}'.", name, function.name),
+               })
+            }
+         };
+
+         if slots[idx].is_some() {
+            return Err(RuntimeResult::Error {
+               error: RuntimeErrorType::ArgumentError,
+               message: format!("Duplicate argument '{}' in call to '{}'.", name, function.name),
+            });
+         }
+
+         slots[idx] = Some(value);
+      }
+
+      for (idx, slot) in slots.into_iter().enumerate() {
+         let value = match slot {
+            Some(value) => value,
+            None if idx >= min_arity => function.defaults[idx - min_arity].clone(),
+            None => {
+               return Err(RuntimeResult::Error {
+                  error: RuntimeErrorType::ArgumentError,
+                  message: format!(
+                     "Missing argument '{}' in call to '{}'.",
+                     function.params[idx], function.name
+                  ),
+               })
+            }
+         };
+
+         self.push_stack(value);
+      }
+
+      // The extra positional arguments are later collected by the rest parameter.
+      let extra_count = extra_args.len();
+      self
+         .stack
+         .extend(extra_args.into_iter().flatten().map(StackValue::from_object));
+
+      Ok((max_arity + extra_count) as u8)
+   }
+
+   /// Expands the spread arguments of a call (e.g., `f(...list)`) into separate arguments on the
+   /// stack. The operands of the call instruction mark which of its arguments are spread.
+   ///
+   /// # Parameters
+   /// - `arg_count`: The number of arguments in the call, before they are expanded.
+   ///
+   /// # Returns
+   /// - `Result<u8, RuntimeResult>`: The number of arguments on the stack after they are expanded.
+   pub(super) fn spread_args(&mut self, arg_count: u8) -> Result<u8, RuntimeResult> {
+      let is_spread: Vec<bool> = (0..arg_count).map(|_| self.next_byte() == 1).collect();
+      let args = self.pop_stack_n(arg_count as usize);
+      let mut count = 0usize;
+
+      for (arg, is_spread) in args.into_iter().zip(is_spread) {
+         if !is_spread {
+            self.push_stack(arg);
+            count += 1;
+            continue;
+         }
+
+         let items = match &arg {
+            Object::Array(a) => a.borrow().clone(),
+            Object::Tuple(t) => t.to_vec(),
+            _ => {
+               return Err(RuntimeResult::Error {
+                  error: RuntimeErrorType::TypeError,
+                  message: format!(
+                     "Cannot spread an object of type '{}' into arguments.",
+                     arg.type_name()
+                  ),
+               })
+            }
+         };
+
+         count += items.len();
+         self.stack.extend(items.into_iter().map(StackValue::from_object));
+      }
+
+      if count > u8::MAX as usize {
+         return Err(RuntimeResult::Error {
+            error: RuntimeErrorType::ArgumentError,
+            message: String::from("Can't have more than 255 arguments."),
+         });
+      }
+
+      Ok(count as u8)
+   }
+
    pub fn arity_check(&self, min: u8, max: u8, count: u8) -> Result<(), RuntimeResult> {
       if count < min || count > max {
          let msg = if min == max {
@@ -347,11 +1230,31 @@ impl VM {
          }
       }
 
-      let new_up_val = Rc::new(RefCell::new(UpValRef::Open(index)));
+      let new_up_val = self.alloc_up_value(UpValRef::Open(index));
       self.up_values.push(new_up_val.clone());
       new_up_val
    }
 
+   /// Closes the open UpValues that refer to a stack slot at or above the given stack index.
+   ///
+   /// # Parameters
+   /// - `stack_idx`: The lowest stack index whose UpValues should be closed.
+   fn close_up_values_from(&mut self, stack_idx: usize) {
+      for u in self.up_values.iter() {
+         let idx = match *u.borrow() {
+            UpValRef::Open(i) if i >= stack_idx => i,
+            _ => continue,
+         };
+
+         u.replace(UpValRef::Closed(self.stack[idx].to_object()));
+      }
+
+      // Closed UpValues are owned by their closures, so the VM stops tracking them.
+      self
+         .up_values
+         .retain(|u| matches!(*u.borrow(), UpValRef::Open(_)));
+   }
+
    /// Tries to create a class instance with the given object, or returns a runtime error if the
    /// object is not instantiable.
    fn create_instance(&mut self, callee: Object, arg_count: u8) -> RuntimeResult {
@@ -365,15 +1268,12 @@ impl VM {
          }
       };
 
-      let new_instance = Object::from(InstanceObject {
-         class: class.clone(),
-         members: class.borrow().members.clone(),
-      });
+      let new_instance = self.alloc_instance(InstanceObject::new(class));
 
       let class_pos = self.stack.len() - (arg_count as usize) - 1;
-      self.stack[class_pos] = new_instance;
+      self.stack[class_pos] = StackValue::from_object(new_instance);
 
-      match self.stack[class_pos].clone() {
+      match self.stack[class_pos].to_object() {
          Object::Instance(i) => {
             if let Ok(value) = i.borrow().get_prop("init".to_string()) {
                self.call_object(value, arg_count);
@@ -385,25 +1285,134 @@ impl VM {
       RuntimeResult::Continue
    }
 
-   /// Prints the execution trace for the program. Useful for debugging the VM.
-   #[allow(dead_code)]
-   fn print_execution(&mut self, instr: OpCode) {
-      println!("\n==========================");
+   /// Looks for an exception handler that covers the instruction being executed by any of the
+   /// active call frames. If a handler is found, the frames and stack are unwound, the error is
+   /// pushed onto the stack as a dictionary object, and execution continues at the handler.
+   ///
+   /// # Parameters
+   /// - `depth`: The number of call frames (from the bottom of the frames stack) to skip.
+   /// - `error`: The type of error that was thrown.
+   /// - `message`: The error message.
+   ///
+   /// # Returns
+   /// - `bool`: True if the error was caught by a handler, false otherwise.
+   fn unwind_to_handler(&mut self, depth: usize, error: &RuntimeErrorType, message: &str) -> bool {
+      // Interrupts and exceeded memory limits end the program right away, even
+      // when they reach the caller through a call made by a native function.
+      if matches!(
+         error,
+         RuntimeErrorType::KeyboardInterrupt | RuntimeErrorType::MemoryError
+      ) {
+         return false;
+      }
+
+      let mut handler = None;
+
+      for (idx, frame) in self.frames.iter().enumerate().skip(depth).rev() {
+         if let Some(h) = frame.closure.function.borrow().chunk.find_handler(frame.ip - 1) {
+            handler = Some((idx, h.target, frame.return_index + h.stack_depth));
+            break;
+         }
+
+         // The deferred calls of the frames being unwound run before the error reaches
+         // the handler. The error is propagated again once they have finished running.
+         if !frame.defers.is_empty() {
+            return self.unwind_to_deferred(idx, error, message);
+         }
+      }
+
+      let (frame_idx, target, stack_len) = match handler {
+         Some(h) => h,
+         None => return false,
+      };
 
-      // Prints the next instruction to be executed
-      println!("OpCode:\t\x1b[36m{:?}\x1b[0m ", instr);
-      println!("Byte:\t{:#04X} ", instr as u8);
+      // Remembers where the error was thrown before the frames are unwound, in case the
+      // handler is a `finally` block that re-throws the error.
+      let trace = self.traceback();
+      self.error_trace = None;
 
-      // Prints the index of the current instruction
-      println!("IP:\t{:>04} ", self.current_frame().ip);
+      // Closes the UpValues that refer to the stack slots about to be discarded.
+      self.close_up_values_from(stack_len);
 
-      // Prints the current state of the values stack
-      print!("stack\t[");
-      for val in self.stack[1..].iter() {
-         print!("{}; ", val);
+      self.frames.truncate(frame_idx + 1);
+      self.stack.truncate(stack_len);
+      self.current_frame_mut().ip = target;
+
+      let mut err_obj = DictObject::default();
+      err_obj.insert(String::from("name"), self.intern(error.name()));
+      err_obj.insert(String::from("code"), self.intern(error.code()));
+      err_obj.insert(String::from("message"), Object::from(message));
+      let err_obj = self.alloc_dict(err_obj);
+
+      if let Object::Dict(dict) = &err_obj {
+         self.error_traces.retain(|(dict, _)| dict.strong_count() > 0);
+         self.error_traces.push((Rc::downgrade(dict), trace));
+      }
+
+      self.push_stack(err_obj);
+      true
+   }
+
+   /// Discards the call frames above a frame with deferred calls, and starts running the
+   /// deferred calls of that frame while a runtime error is being propagated.
+   ///
+   /// # Parameters
+   /// - `frame_idx`: The index of the call frame with deferred calls.
+   /// - `error`: The type of runtime error being propagated.
+   /// - `message`: The error message.
+   ///
+   /// # Returns
+   /// - `bool`: True if the first deferred call was started, false otherwise.
+   fn unwind_to_deferred(&mut self, frame_idx: usize, error: &RuntimeErrorType, message: &str) -> bool {
+      // The stack slots of the discarded frames start at the base of the frame above.
+      if let Some(stack_len) = self.frames.get(frame_idx + 1).map(|f| f.return_index) {
+         self.close_up_values_from(stack_len);
+         self.stack.truncate(stack_len);
+         self.frames.truncate(frame_idx + 1);
       }
-      println!("]");
 
-      print!("Output:\t");
+      self.error_trace = None;
+
+      let frame = self.current_frame_mut();
+      let deferred = frame.defers.pop().unwrap();
+      frame.pending_error = Some((error.clone(), String::from(message)));
+
+      matches!(self.call_deferred(deferred), RuntimeResult::Continue)
+   }
+
+   /// Calls a closure scheduled by a `defer` statement. The return value of the call is
+   /// discarded once the call returns.
+   ///
+   /// # Parameters
+   /// - `deferred`: The deferred closure.
+   fn call_deferred(&mut self, deferred: Object) -> RuntimeResult {
+      self.push_stack(deferred.clone());
+
+      match self.call_object(deferred, 0) {
+         RuntimeResult::Continue => {
+            self.current_frame_mut().is_deferred = true;
+            RuntimeResult::Continue
+         }
+         result => result,
+      }
+   }
+
+   /// Continues the execution of a call frame after one of its deferred calls returns. If the
+   /// deferred calls were running because of a runtime error, the next deferred call is started,
+   /// or the error is propagated again if there are no more deferred calls.
+   fn resume_after_deferred(&mut self) -> RuntimeResult {
+      let frame = self.current_frame_mut();
+
+      if frame.pending_error.is_none() {
+         return RuntimeResult::Continue;
+      }
+
+      match frame.defers.pop() {
+         Some(deferred) => self.call_deferred(deferred),
+         None => {
+            let (error, message) = frame.pending_error.take().unwrap();
+            RuntimeResult::Error { error, message }
+         }
+      }
    }
 }
@@ -0,0 +1,39 @@
+use std::cell::RefCell;
+use std::io::{self, Write};
+use std::rc::Rc;
+
+/// An output stream that keeps the text written to it in memory, so that the output of the
+/// programs executed in a VM can be captured (e.g., by passing it to `VM::with_stdout`).
+/// Clones of a buffer share the same contents.
+#[derive(Clone, Default)]
+pub struct OutputBuffer(Rc<RefCell<Vec<u8>>>);
+
+impl OutputBuffer {
+   /// Creates a new, empty output buffer.
+   pub fn new() -> Self {
+      Self::default()
+   }
+
+   /// Gets the text written to the buffer so far. Invalid UTF-8
+   /// sequences are replaced with the replacement character.
+   pub fn contents(&self) -> String {
+      String::from_utf8_lossy(&self.0.borrow()).into_owned()
+   }
+
+   /// Gets the text written to the buffer so far, and clears the buffer.
+   pub fn take(&self) -> String {
+      let bytes = std::mem::take(&mut *self.0.borrow_mut());
+      String::from_utf8_lossy(&bytes).into_owned()
+   }
+}
+
+impl Write for OutputBuffer {
+   fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+      self.0.borrow_mut().extend_from_slice(buf);
+      Ok(buf.len())
+   }
+
+   fn flush(&mut self) -> io::Result<()> {
+      Ok(())
+   }
+}
@@ -0,0 +1,517 @@
+use crate::core::bytecode::OpCode;
+use crate::virtual_machine::VM;
+use hashbrown::HashMap;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// The categories of instructions whose execution time is measured by the profiler.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum OpCategory {
+   ObjectLoaders,
+   ObjectMakers,
+   Globals,
+   Locals,
+   Operators,
+   Jumps,
+   FunctionsAndClosures,
+   ClassesAndInstances,
+   Modules,
+   CollectionManipulators,
+   VmSpecific,
+   /// Not an instruction category. The time spent collecting garbage in between instructions.
+   GarbageCollection,
+}
+
+impl OpCategory {
+   /// Every category, in the order they are declared.
+   pub const ALL: [OpCategory; 12] = [
+      OpCategory::ObjectLoaders,
+      OpCategory::ObjectMakers,
+      OpCategory::Globals,
+      OpCategory::Locals,
+      OpCategory::Operators,
+      OpCategory::Jumps,
+      OpCategory::FunctionsAndClosures,
+      OpCategory::ClassesAndInstances,
+      OpCategory::Modules,
+      OpCategory::CollectionManipulators,
+      OpCategory::VmSpecific,
+      OpCategory::GarbageCollection,
+   ];
+
+   /// Gets the category of an instruction.
+   pub fn of(instruction: &OpCode) -> Self {
+      match instruction {
+         OpCode::LoadConstant
+         | OpCode::LoadConstantLong
+         | OpCode::LoadImm0F
+         | OpCode::LoadImm0I
+         | OpCode::LoadImm1F
+         | OpCode::LoadImm1I
+         | OpCode::LoadImmFalse
+         | OpCode::LoadImmN
+         | OpCode::LoadImmNLong
+         | OpCode::LoadImmNull
+         | OpCode::LoadImmTrue
+         | OpCode::LoadNative
+         | OpCode::LoadPrimitive => OpCategory::ObjectLoaders,
+
+         OpCode::MakeArray
+         | OpCode::MakeArrayLong
+         | OpCode::MakeClosure
+         | OpCode::MakeClosureLong
+         | OpCode::MakeClosureLarge
+         | OpCode::MakeClosureLongLarge
+         | OpCode::MakeDict
+         | OpCode::MakeDictLong
+         | OpCode::MakeIter
+         | OpCode::MakeInclusiveRange
+         | OpCode::MakeRange
+         | OpCode::MakeTuple
+         | OpCode::MakeTupleLong => OpCategory::ObjectMakers,
+
+         OpCode::DefineGlobal
+         | OpCode::DefineGlobalLong
+         | OpCode::GetGlobal
+         | OpCode::GetGlobalLong
+         | OpCode::SetGlobal
+         | OpCode::SetGlobalLong => OpCategory::Globals,
+
+         OpCode::PopStackTop
+         | OpCode::DupTop
+         | OpCode::DupTopTwo
+         | OpCode::RotThree
+         | OpCode::RotTwo
+         | OpCode::GetLocal
+         | OpCode::GetLocalLong
+         | OpCode::SetLocal
+         | OpCode::SetLocalLong => OpCategory::Locals,
+
+         OpCode::Add
+         | OpCode::BitwiseAnd
+         | OpCode::BitwiseNot
+         | OpCode::BitwiseOr
+         | OpCode::BitwiseShiftLeft
+         | OpCode::BitwiseShiftRight
+         | OpCode::BitwiseXor
+         | OpCode::Contains
+         | OpCode::Divide
+         | OpCode::Equals
+         | OpCode::Expo
+         | OpCode::FloorDivide
+         | OpCode::GreaterThan
+         | OpCode::GreaterThanEq
+         | OpCode::LessThan
+         | OpCode::LessThanEq
+         | OpCode::LogicNot
+         | OpCode::Modulus
+         | OpCode::Multiply
+         | OpCode::Negate
+         | OpCode::NotEq
+         | OpCode::NullishCoalescing
+         | OpCode::Slice
+         | OpCode::Subscript
+         | OpCode::Subtract
+         | OpCode::LoadConstantAdd
+         | OpCode::LoadImm1IAdd
+         | OpCode::LoadImmNAdd => OpCategory::Operators,
+
+         OpCode::ForIterNextOrJump
+         | OpCode::Unpack
+         | OpCode::JumpForward
+         | OpCode::JumpIfFalseOrPop
+         | OpCode::JumpIfNull
+         | OpCode::JumpIfTrueOrPop
+         | OpCode::JumpTable
+         | OpCode::LoopJump
+         | OpCode::LoopJumpLong
+         | OpCode::PopJumpIfFalse
+         | OpCode::GetLocalsCompareJump => OpCategory::Jumps,
+
+         OpCode::Async
+         | OpCode::Await
+         | OpCode::BindDefaults
+         | OpCode::BuildString
+         | OpCode::CloseUpVal
+         | OpCode::CloseUpValLong
+         | OpCode::Defer
+         | OpCode::FuncCall
+         | OpCode::FuncCallNamed
+         | OpCode::FuncCallSpread
+         | OpCode::GetUpVal
+         | OpCode::GetUpValLong
+         | OpCode::PopCloseUpVal
+         | OpCode::Return
+         | OpCode::TailCall
+         | OpCode::SetUpVal
+         | OpCode::SetUpValLong => OpCategory::FunctionsAndClosures,
+
+         OpCode::AppendClassField
+         | OpCode::Implement
+         | OpCode::Inherit
+         | OpCode::InstanceOf
+         | OpCode::MatchSequence
+         | OpCode::MatchVariant
+         | OpCode::MakeClass
+         | OpCode::MakeClassLong
+         | OpCode::MakeInstance
+         | OpCode::MakeInstanceNamed
+         | OpCode::MakeInstanceSpread => OpCategory::ClassesAndInstances,
+
+         OpCode::ExportModule | OpCode::ImportModule | OpCode::ImportModuleLong => OpCategory::Modules,
+
+         OpCode::ArrayPush
+         | OpCode::GetProp
+         | OpCode::GetPropLong
+         | OpCode::GetSuper
+         | OpCode::GetSuperLong
+         | OpCode::SetProp
+         | OpCode::SetPropLong
+         | OpCode::SubscriptAssign => OpCategory::CollectionManipulators,
+
+         OpCode::AssertNonNull | OpCode::FailAssertion | OpCode::EndVirtualMachine | OpCode::Rethrow => {
+            OpCategory::VmSpecific
+         }
+      }
+   }
+
+   /// Gets the name of this category, as shown in the profiler's report.
+   pub fn name(&self) -> &'static str {
+      match self {
+         OpCategory::ObjectLoaders => "Object loaders",
+         OpCategory::ObjectMakers => "Object makers",
+         OpCategory::Globals => "Global variables",
+         OpCategory::Locals => "Local variables",
+         OpCategory::Operators => "Operators",
+         OpCategory::Jumps => "Jumps",
+         OpCategory::FunctionsAndClosures => "Functions & closures",
+         OpCategory::ClassesAndInstances => "Classes & instances",
+         OpCategory::Modules => "Modules",
+         OpCategory::CollectionManipulators => "Properties & subscripts",
+         OpCategory::VmSpecific => "VM-specific",
+         OpCategory::GarbageCollection => "Garbage collection",
+      }
+   }
+}
+
+/// The time spent executing the instructions of a function, and the number of times it was called.
+pub struct FunctionProfile {
+   /// The name of the function.
+   pub name: String,
+   /// The number of times the function was called.
+   pub calls: u64,
+   /// The time spent executing the function's own instructions.
+   pub self_time: Duration,
+   /// The time spent executing the function, including the functions it called.
+   pub total_time: Duration,
+}
+
+/// A node in the tree of call stacks observed while profiling. The root node (at index 0)
+/// represents the empty stack, and every other node represents the call stack formed by the
+/// call stack of its parent, plus a call to its function.
+struct StackNode {
+   function: usize,
+   parent: usize,
+   children: HashMap<usize, usize>,
+   time: Duration,
+}
+
+/// An instrumentation profiler, which measures the time spent executing each instruction of
+/// a program, and attributes it to the call stack and the category of the instruction.
+pub struct Profiler {
+   /// The names of the profiled functions, indexed by their ID.
+   function_names: Vec<String>,
+   /// The IDs of the profiled functions, keyed by their name.
+   function_ids: HashMap<String, usize>,
+   /// The number of times each function was called, indexed by the function's ID.
+   calls: Vec<u64>,
+   /// The tree of call stacks observed while profiling.
+   stacks: Vec<StackNode>,
+   /// The time spent and number of instructions executed in each category.
+   categories: [(Duration, u64); OpCategory::ALL.len()],
+   /// The number of frames, the address of the function in the topmost frame, and the stack
+   /// node of the last measured instruction. The stack node is only looked up again when the
+   /// call stack changes.
+   last_stack: Option<(usize, usize, usize)>,
+   /// The stack node and category of the instruction being measured, and when it started.
+   current: Option<(usize, OpCategory, Instant)>,
+}
+
+impl Profiler {
+   /// Creates a new profiler.
+   pub(crate) fn new() -> Self {
+      Profiler {
+         function_names: vec![],
+         function_ids: HashMap::new(),
+         calls: vec![],
+         stacks: vec![StackNode {
+            function: 0,
+            parent: 0,
+            children: HashMap::new(),
+            time: Duration::ZERO,
+         }],
+         categories: [(Duration::ZERO, 0); OpCategory::ALL.len()],
+         last_stack: None,
+         current: None,
+      }
+   }
+
+   /// Gets the ID of a function, registering the function if it has not been profiled yet.
+   fn function_id(&mut self, name: &str) -> usize {
+      if let Some(id) = self.function_ids.get(name) {
+         return *id;
+      }
+
+      let id = self.function_names.len();
+      self.function_names.push(name.to_string());
+      self.function_ids.insert(name.to_string(), id);
+      self.calls.push(0);
+      id
+   }
+
+   /// Gets the stack node for a call to a function from the call stack of the given node.
+   fn child_node(&mut self, parent: usize, function: usize) -> usize {
+      if let Some(node) = self.stacks[parent].children.get(&function) {
+         return *node;
+      }
+
+      let node = self.stacks.len();
+      self.stacks.push(StackNode {
+         function,
+         parent,
+         children: HashMap::new(),
+         time: Duration::ZERO,
+      });
+      self.stacks[parent].children.insert(function, node);
+      node
+   }
+
+   /// Records a call to a function.
+   ///
+   /// # Parameters
+   /// - `name`: The name of the called function.
+   pub(super) fn record_call(&mut self, name: &str) {
+      let id = self.function_id(name);
+      self.calls[id] += 1;
+
+      // Tail calls replace the topmost frame, so the call stack
+      // may change without changing the number of frames.
+      self.last_stack = None;
+   }
+
+   /// Ends the measurement of the current instruction (if any), and starts measuring the next one.
+   ///
+   /// # Parameters
+   /// - `end`: When the current instruction finished executing.
+   /// - `next`: The stack node and category of the next instruction, if any.
+   fn lap(&mut self, end: Instant, next: Option<(usize, OpCategory)>) {
+      if let Some((node, category, start)) = self.current.take() {
+         let elapsed = end - start;
+         self.stacks[node].time += elapsed;
+         self.categories[category as usize].0 += elapsed;
+
+         if category != OpCategory::GarbageCollection {
+            self.categories[category as usize].1 += 1;
+         }
+      }
+
+      // The time spent by the profiler itself is not attributed to any instruction.
+      self.current = next.map(|(node, category)| (node, category, Instant::now()));
+   }
+
+   /// Stops measuring the last instruction of the program.
+   pub(super) fn stop(&mut self) {
+      self.lap(Instant::now(), None);
+      self.last_stack = None;
+   }
+
+   /// Gets the profile of every called function, sorted by the time spent executing their
+   /// own instructions.
+   pub fn functions(&self) -> Vec<FunctionProfile> {
+      let mut self_times = vec![Duration::ZERO; self.function_names.len()];
+      let mut total_times = vec![Duration::ZERO; self.function_names.len()];
+
+      for (idx, node) in self.stacks.iter().enumerate().skip(1) {
+         self_times[node.function] += node.time;
+
+         // Recursive functions appear more than once in a call stack,
+         // but their time is only counted once towards their total time.
+         let mut seen = vec![];
+         let mut current = idx;
+         while current != 0 {
+            let function = self.stacks[current].function;
+            if !seen.contains(&function) {
+               seen.push(function);
+               total_times[function] += node.time;
+            }
+            current = self.stacks[current].parent;
+         }
+      }
+
+      let mut functions: Vec<FunctionProfile> = self
+         .function_names
+         .iter()
+         .enumerate()
+         .map(|(id, name)| FunctionProfile {
+            name: name.clone(),
+            calls: self.calls[id],
+            self_time: self_times[id],
+            total_time: total_times[id],
+         })
+         .collect();
+
+      functions.sort_by(|a, b| b.self_time.cmp(&a.self_time).then(a.name.cmp(&b.name)));
+      functions
+   }
+
+   /// Gets the time spent and the number of instructions executed in each category, sorted by
+   /// the time spent. Categories without any executed instructions are omitted.
+   pub fn categories(&self) -> Vec<(OpCategory, Duration, u64)> {
+      let mut categories: Vec<(OpCategory, Duration, u64)> = OpCategory::ALL
+         .iter()
+         .map(|c| (*c, self.categories[*c as usize].0, self.categories[*c as usize].1))
+         .filter(|(_, time, count)| *count > 0 || !time.is_zero())
+         .collect();
+
+      categories.sort_by_key(|c| std::cmp::Reverse(c.1));
+      categories
+   }
+
+   /// Gets the total time measured by the profiler.
+   pub fn total_time(&self) -> Duration {
+      self.stacks.iter().map(|n| n.time).sum()
+   }
+
+   /// Gets a report with the time spent in each function and in each category of instructions,
+   /// sorted from the most to the least expensive.
+   pub fn report(&self) -> String {
+      let total = self.total_time();
+      let instructions: u64 = self.categories.iter().map(|(_, count)| count).sum();
+
+      let percent = |time: Duration| {
+         if total.is_zero() {
+            0.0
+         } else {
+            time.as_secs_f64() / total.as_secs_f64() * 100.0
+         }
+      };
+
+      let mut report = format!(
+         "Profile: {} in {} instructions.\n\n",
+         format_duration(total),
+         instructions
+      );
+
+      report += &format!(
+         "{:<40} {:>10} {:>12} {:>7} {:>12} {:>7}\n",
+         "Function", "Calls", "Self", "%", "Total", "%"
+      );
+
+      for f in self.functions() {
+         report += &format!(
+            "{:<40} {:>10} {:>12} {:>6.1}% {:>12} {:>6.1}%\n",
+            f.name,
+            f.calls,
+            format_duration(f.self_time),
+            percent(f.self_time),
+            format_duration(f.total_time),
+            percent(f.total_time)
+         );
+      }
+
+      report += &format!(
+         "\n{:<40} {:>10} {:>12} {:>7}\n",
+         "Instruction category", "Count", "Time", "%"
+      );
+
+      for (category, time, count) in self.categories() {
+         report += &format!(
+            "{:<40} {:>10} {:>12} {:>6.1}%\n",
+            category.name(),
+            count,
+            format_duration(time),
+            percent(time)
+         );
+      }
+
+      report
+   }
+
+   /// Gets the measured call stacks in the collapsed-stack format used by flame graph tools
+   /// (e.g., `flamegraph.pl` or `inferno`). Each line has the names of the functions in a call
+   /// stack, from the outermost to the innermost, separated by semicolons, followed by the
+   /// time spent in that call stack, in microseconds.
+   pub fn collapsed_stacks(&self) -> String {
+      let mut lines: Vec<String> = vec![];
+
+      for node in self.stacks.iter().skip(1) {
+         let micros = node.time.as_micros();
+         if micros == 0 {
+            continue;
+         }
+
+         let mut names = vec![];
+         let mut current = node;
+         loop {
+            names.push(self.function_names[current.function].replace(';', ":"));
+            if current.parent == 0 {
+               break;
+            }
+            current = &self.stacks[current.parent];
+         }
+
+         names.reverse();
+         lines.push(format!("{} {}", names.join(";"), micros));
+      }
+
+      lines.sort();
+      lines.iter().map(|l| format!("{}\n", l)).collect()
+   }
+}
+
+/// Formats a duration in milliseconds, with microsecond precision.
+fn format_duration(duration: Duration) -> String {
+   format!("{:.3} ms", duration.as_secs_f64() * 1000.0)
+}
+
+impl VM {
+   /// Measures the execution of the next instruction with the profiler attached to this VM.
+   ///
+   /// # Parameters
+   /// - `instruction`: The instruction about to be executed.
+   pub(super) fn profile_instruction(&mut self, instruction: &OpCode) {
+      let end = Instant::now();
+      let depth = self.frames.len();
+      let top = Rc::as_ptr(&self.current_frame().closure.function) as usize;
+
+      let mut profiler = match self.profiler.take() {
+         Some(p) => p,
+         None => return,
+      };
+
+      let node = match profiler.last_stack {
+         Some((d, t, node)) if d == depth && t == top => node,
+         _ => {
+            let mut node = 0;
+            for frame in self.frames.iter() {
+               let function = profiler.function_id(&frame.closure.function.borrow().name);
+               node = profiler.child_node(node, function);
+            }
+
+            profiler.last_stack = Some((depth, top, node));
+            node
+         }
+      };
+
+      profiler.lap(end, Some((node, OpCategory::of(instruction))));
+      self.profiler = Some(profiler);
+   }
+
+   /// Attributes the time until the next instruction to the garbage collector.
+   pub(super) fn profile_garbage_collection(&mut self) {
+      if let Some(profiler) = &mut self.profiler {
+         let node = profiler.current.as_ref().map_or(0, |(node, _, _)| *node);
+         profiler.lap(Instant::now(), Some((node, OpCategory::GarbageCollection)));
+      }
+   }
+}
@@ -1,132 +1,369 @@
-use crate::built_in::natives::{get_next_in_iter, make_iter};
+use crate::built_in::natives::{get_next_in_iter, is_end_of_iter, make_iter};
 use crate::built_in::BuiltIn;
 use crate::core::ast::{BinaryExprType, UnaryExprType};
 use crate::core::bytecode::OpCode;
 use crate::errors::RuntimeErrorType;
 use crate::objects::class_obj::{BoundMethod, ClassField, ClassObject};
+use crate::objects::dict_obj::DictObject;
+use crate::objects::enum_obj::{EnumValueObject, EnumVariantObject};
 use crate::objects::indexing::to_bounded_index;
 use crate::objects::*;
-use crate::virtual_machine::{RuntimeResult, VM};
-use hashbrown::HashMap;
+#[cfg(feature = "threaded_dispatch")]
+use crate::virtual_machine::dispatch::HANDLERS;
+use crate::virtual_machine::magic_methods::{binary_magic_method, unary_magic_method};
+use crate::virtual_machine::{RuntimeResult, StackSlot, StackValue, INTERRUPTED, VM};
 use std::cell::RefCell;
 use std::rc::Rc;
+use std::sync::atomic::Ordering;
 
 impl VM {
    /// Executes the instructions in a chunk.
    pub(crate) fn run(&mut self) -> RuntimeResult {
+      let result = self.run_until(0);
+
+      if let Some(profiler) = &mut self.profiler {
+         profiler.stop();
+      }
+
+      result
+   }
+
+   /// Executes instructions until the number of active call frames drops to the given depth.
+   /// Runtime errors are only caught by the handlers of the frames above that depth.
+   ///
+   /// # Parameters
+   /// - `depth`: The number of call frames that were active before the call being run.
+   pub(super) fn run_until(&mut self, depth: usize) -> RuntimeResult {
       loop {
+         if self.debugger.is_some() {
+            self.debugger_check();
+         }
+
          let instruction = self.next_op_code();
 
-         let exec = match instruction {
-            OpCode::PopStackTop => {
-               self.pop_stack();
-               RuntimeResult::Continue
-            }
+         if self.tracer.is_some() {
+            self.trace_instruction(&instruction);
+         }
 
-            // Object loaders
-            OpCode::LoadConstant | OpCode::LoadConstantLong => self.op_load_constant(),
-            OpCode::LoadImm0F => self.push_stack(Object::Float(0f64)),
-            OpCode::LoadImm0I => self.push_stack(Object::Int(0i64)),
-            OpCode::LoadImm1F => self.push_stack(Object::Float(1f64)),
-            OpCode::LoadImm1I => self.push_stack(Object::Int(1i64)),
-            OpCode::LoadImmFalse => self.push_stack(Object::Bool(false)),
-            OpCode::LoadImmN | OpCode::LoadImmNLong => self.op_load_immediate_n(),
-            OpCode::LoadImmNull => self.push_stack(Object::Null),
-            OpCode::LoadImmTrue => self.push_stack(Object::Bool(true)),
-            OpCode::LoadNative => self.op_load_native(),
-            OpCode::LoadPrimitive => self.op_load_primitive(),
-
-            // Object makers
-            OpCode::MakeArray | OpCode::MakeArrayLong => self.op_make_array(),
-            OpCode::MakeClosure | OpCode::MakeClosureLong => self.op_make_closure(),
-            OpCode::MakeClosureLarge | OpCode::MakeClosureLongLarge => self.op_make_closure_large(),
-            OpCode::MakeDict | OpCode::MakeDictLong => self.op_make_dictionary(),
-            OpCode::MakeIter => self.op_make_iter(),
-            OpCode::MakeRange => self.op_make_range(),
-            OpCode::MakeTuple | OpCode::MakeTupleLong => self.op_make_tuple(),
-
-            // Global declarations
-            OpCode::DefineGlobal | OpCode::DefineGlobalLong => self.op_define_global(),
-            OpCode::GetGlobal | OpCode::GetGlobalLong => self.op_get_global(),
-            OpCode::SetGlobal | OpCode::SetGlobalLong => self.op_set_global(),
-
-            // Local declarations
-            OpCode::GetLocal | OpCode::GetLocalLong => self.op_get_local(),
-            OpCode::SetLocal | OpCode::SetLocalLong => self.op_set_local(),
-
-            // Operators
-            OpCode::Add => self.binary_operation(BinaryExprType::Addition),
-            OpCode::BitwiseAnd => self.binary_operation(BinaryExprType::BitwiseAND),
-            OpCode::BitwiseNot => self.unary_operation(UnaryExprType::BitwiseNeg),
-            OpCode::BitwiseOr => self.binary_operation(BinaryExprType::BitwiseOR),
-            OpCode::BitwiseShiftLeft => self.binary_operation(BinaryExprType::BitwiseShiftLeft),
-            OpCode::BitwiseShiftRight => self.binary_operation(BinaryExprType::BitwiseShiftRight),
-            OpCode::BitwiseXor => self.binary_operation(BinaryExprType::BitwiseXOR),
-            OpCode::Divide => self.binary_operation(BinaryExprType::Division),
-            OpCode::Equals => self.binary_operation(BinaryExprType::LogicEQ),
-            OpCode::Expo => self.binary_operation(BinaryExprType::Expo),
-            OpCode::GreaterThan => self.binary_operation(BinaryExprType::LogicGreaterThan),
-            OpCode::GreaterThanEq => self.binary_operation(BinaryExprType::LogicGreaterThanEQ),
-            OpCode::LessThan => self.binary_operation(BinaryExprType::LogicLessThan),
-            OpCode::LessThanEq => self.binary_operation(BinaryExprType::LogicLessThanEQ),
-            OpCode::LogicNot => self.unary_operation(UnaryExprType::LogicNeg),
-            OpCode::Modulus => self.binary_operation(BinaryExprType::Modulus),
-            OpCode::Multiply => self.binary_operation(BinaryExprType::Multiplication),
-            OpCode::Negate => self.unary_operation(UnaryExprType::ArithmeticNeg),
-            OpCode::NotEq => self.binary_operation(BinaryExprType::LogicNotEQ),
-            OpCode::NullishCoalescing => self.binary_operation(BinaryExprType::Nullish),
-            OpCode::Subscript => self.op_subscript(),
-            OpCode::Subtract => self.binary_operation(BinaryExprType::Minus),
-
-            // Jumps
-            OpCode::ForIterNextOrJump => self.op_get_iter_next_or_jump(),
-            OpCode::JumpForward => self.op_jump_forward(),
-            OpCode::JumpIfFalseOrPop => self.op_jump_if_false_or_pop(),
-            OpCode::JumpIfTrueOrPop => self.op_jump_if_true_or_pop(),
-            OpCode::LoopJump | OpCode::LoopJumpLong => self.op_loop_jump(),
-            OpCode::PopJumpIfFalse => self.op_pop_and_jump_if_false(),
-
-            // Functions and Closures
-            OpCode::BindDefaults => self.op_bind_function_defaults(),
-            OpCode::CloseUpVal | OpCode::CloseUpValLong => self.up_close_up_value(),
-            OpCode::FuncCall => self.op_func_call(),
-            OpCode::GetUpVal | OpCode::GetUpValLong => self.op_get_up_value(),
-            OpCode::PopCloseUpVal => self.op_pop_stack_and_close_up_value(),
-            OpCode::Return => self.op_function_return(),
-            OpCode::SetUpVal | OpCode::SetUpValLong => self.op_set_up_value(),
-
-            // Classes & Instances
-            OpCode::AppendClassField => self.append_class_field(),
-            OpCode::MakeClass | OpCode::MakeClassLong => self.op_make_class(),
-            OpCode::MakeInstance => self.op_make_instance(),
-
-            // Collection manipulators
-            OpCode::GetProp | OpCode::GetPropLong => self.op_get_property(),
-            OpCode::SetProp | OpCode::SetPropLong => self.op_set_property(),
-            OpCode::SubscriptAssign => self.op_subscript_assign(),
-
-            // VM-Specific
-            OpCode::EndVirtualMachine => self.op_end_virtual_machine(),
-         };
+         if self.profiler.is_some() {
+            self.profile_instruction(&instruction);
+         }
+
+         // Interrupts are not catchable by `try` blocks, so they end the program right away.
+         if INTERRUPTED.load(Ordering::Relaxed) {
+            INTERRUPTED.store(false, Ordering::Relaxed);
+
+            return RuntimeResult::Error {
+               error: RuntimeErrorType::KeyboardInterrupt,
+               message: String::from("Execution interrupted."),
+            };
+         }
+
+         // Checks the limits of the VM once the budget of instructions until the next check runs
+         // out. If a handler catches the error, the fetched instruction is not executed.
+         if self.usage.budget == 0 {
+            if let Some(RuntimeResult::Error { error, message }) = self.check_limits() {
+               if !self.unwind_to_handler(depth, &error, &message) {
+                  return RuntimeResult::Error { error, message };
+               }
+
+               continue;
+            }
+         }
+         self.usage.budget -= 1;
 
-         // Prints the execution of the program.
-         // self.print_execution(instruction);
+         #[cfg(not(feature = "threaded_dispatch"))]
+         let exec = self.execute_instruction(instruction);
+         #[cfg(feature = "threaded_dispatch")]
+         let exec = HANDLERS[instruction as usize](self);
 
          match exec {
-            RuntimeResult::Continue => continue,
+            RuntimeResult::Continue => {
+               if self.frames.len() == depth {
+                  // Only green threads return from the outermost call frame of the VM,
+                  // since the main function of a program ends the VM instead.
+                  if depth + self.native_calls > 0 {
+                     return RuntimeResult::Continue;
+                  }
+
+                  let result = self.end_thread();
+                  if let Some(result) = self.finish_switch(depth, result) {
+                     return result;
+                  }
+
+                  continue;
+               }
+
+               // In between instructions, every object in use is reachable from the roots,
+               // except for the objects held by native functions waiting for a call to return.
+               if self.native_calls == 0 && self.heap.should_collect() {
+                  if self.profiler.is_some() {
+                     self.profile_garbage_collection();
+                  }
+
+                  self.collect_garbage();
+               }
+            }
+            RuntimeResult::Error { .. } => {
+               if let Some(result) = self.finish_switch(depth, exec) {
+                  return result;
+               }
+            }
+            RuntimeResult::Suspend => {
+               let result = self.switch_thread();
+               if let Some(result) = self.finish_switch(depth, result) {
+                  return result;
+               }
+            }
+            RuntimeResult::EndOK if self.scheduler.has_threads() => {
+               let result = self.end_main_thread();
+               if let Some(result) = self.finish_switch(depth, result) {
+                  return result;
+               }
+            }
             _ => return exec,
          }
       }
    }
 
+   /// Handles a runtime error, or the result of switching to another green thread, which is
+   /// an error if the thread could not be resumed, or `EndOK` if every thread has ended. An
+   /// error that is not caught by the running thread rejects the promise of the async call
+   /// run by the thread, if any, and the next thread is resumed.
+   ///
+   /// # Parameters
+   /// - `depth`: The number of call frames that were active before the call being run.
+   /// - `result`: The runtime error, or the result of the switch.
+   ///
+   /// # Returns
+   /// - `Option<RuntimeResult>`: The result the VM returns with, if it stops running.
+   fn finish_switch(&mut self, depth: usize, mut result: RuntimeResult) -> Option<RuntimeResult> {
+      loop {
+         match result {
+            RuntimeResult::Continue => return None,
+            RuntimeResult::Error { error, message } => {
+               if self.unwind_to_handler(depth, &error, &message) {
+                  return None;
+               }
+
+               // Only the outermost call frames of a thread can reject its promise.
+               if depth + self.native_calls > 0 {
+                  return Some(RuntimeResult::Error { error, message });
+               }
+
+               result = match self.reject_thread(error, message) {
+                  Ok(next) => next,
+                  Err(error) => return Some(error),
+               };
+            }
+            result => return Some(result),
+         }
+      }
+   }
+
+   /// Executes a single instruction.
+   ///
+   /// # Parameters
+   /// - `instruction`: The opcode of the instruction, whose operands follow the instruction
+   ///   pointer of the current call frame.
+   #[inline(always)]
+   pub(super) fn execute_instruction(&mut self, instruction: OpCode) -> RuntimeResult {
+      match instruction {
+         OpCode::PopStackTop => {
+            self.pop_stack();
+            RuntimeResult::Continue
+         }
+         OpCode::DupTop => self.push_stack(self.peek_stack(0).into_owned()),
+         OpCode::DupTopTwo => {
+            let (second, top) = (self.peek_stack(1).into_owned(), self.peek_stack(0).into_owned());
+            self.push_stack(second);
+            self.push_stack(top)
+         }
+         OpCode::RotThree => {
+            // Moves the top of the stack below the two values under it.
+            let len = self.stack.len();
+            self.stack[len - 3..].rotate_right(1);
+            RuntimeResult::Continue
+         }
+         OpCode::RotTwo => {
+            let len = self.stack.len();
+            self.stack.swap(len - 1, len - 2);
+            RuntimeResult::Continue
+         }
+
+         // Object loaders
+         OpCode::LoadConstant | OpCode::LoadConstantLong => self.op_load_constant(),
+         OpCode::LoadImm0F => self.push_stack(Object::Float(0f64)),
+         OpCode::LoadImm0I => self.push_stack(Object::Int(0i64)),
+         OpCode::LoadImm1F => self.push_stack(Object::Float(1f64)),
+         OpCode::LoadImm1I => self.push_stack(Object::Int(1i64)),
+         OpCode::LoadImmFalse => self.push_stack(Object::Bool(false)),
+         OpCode::LoadImmN | OpCode::LoadImmNLong => self.op_load_immediate_n(),
+         OpCode::LoadImmNull => self.push_stack(Object::Null),
+         OpCode::LoadImmTrue => self.push_stack(Object::Bool(true)),
+         OpCode::LoadNative => self.op_load_native(),
+         OpCode::LoadPrimitive => self.op_load_primitive(),
+
+         // Object makers
+         OpCode::MakeArray | OpCode::MakeArrayLong => self.op_make_array(),
+         OpCode::MakeClosure | OpCode::MakeClosureLong => self.op_make_closure(),
+         OpCode::MakeClosureLarge | OpCode::MakeClosureLongLarge => self.op_make_closure_large(),
+         OpCode::MakeDict | OpCode::MakeDictLong => self.op_make_dictionary(),
+         OpCode::MakeIter => self.op_make_iter(),
+         OpCode::MakeInclusiveRange => self.op_make_range(true),
+         OpCode::MakeRange => self.op_make_range(false),
+         OpCode::MakeTuple | OpCode::MakeTupleLong => self.op_make_tuple(),
+
+         // Global declarations
+         OpCode::DefineGlobal | OpCode::DefineGlobalLong => self.op_define_global(),
+         OpCode::GetGlobal | OpCode::GetGlobalLong => self.op_get_global(),
+         OpCode::SetGlobal | OpCode::SetGlobalLong => self.op_set_global(),
+
+         // Local declarations
+         OpCode::GetLocal | OpCode::GetLocalLong => self.op_get_local(),
+         OpCode::SetLocal | OpCode::SetLocalLong => self.op_set_local(),
+
+         // Operators
+         OpCode::Add => self.binary_operation(BinaryExprType::Addition),
+         OpCode::BitwiseAnd => self.binary_operation(BinaryExprType::BitwiseAND),
+         OpCode::BitwiseNot => self.unary_operation(UnaryExprType::BitwiseNeg),
+         OpCode::BitwiseOr => self.binary_operation(BinaryExprType::BitwiseOR),
+         OpCode::BitwiseShiftLeft => self.binary_operation(BinaryExprType::BitwiseShiftLeft),
+         OpCode::BitwiseShiftRight => self.binary_operation(BinaryExprType::BitwiseShiftRight),
+         OpCode::BitwiseXor => self.binary_operation(BinaryExprType::BitwiseXOR),
+         OpCode::Contains => self.binary_operation(BinaryExprType::LogicIn),
+         OpCode::Defer => self.op_defer(),
+         OpCode::Divide => self.binary_operation(BinaryExprType::Division),
+         OpCode::Equals => self.binary_operation(BinaryExprType::LogicEQ),
+         OpCode::Expo => self.binary_operation(BinaryExprType::Expo),
+         OpCode::FloorDivide => self.binary_operation(BinaryExprType::FloorDivision),
+         OpCode::GreaterThan => self.binary_operation(BinaryExprType::LogicGreaterThan),
+         OpCode::GreaterThanEq => self.binary_operation(BinaryExprType::LogicGreaterThanEQ),
+         OpCode::LessThan => self.binary_operation(BinaryExprType::LogicLessThan),
+         OpCode::LessThanEq => self.binary_operation(BinaryExprType::LogicLessThanEQ),
+         OpCode::LogicNot => self.unary_operation(UnaryExprType::LogicNeg),
+         OpCode::Modulus => self.binary_operation(BinaryExprType::Modulus),
+         OpCode::Multiply => self.binary_operation(BinaryExprType::Multiplication),
+         OpCode::Negate => self.unary_operation(UnaryExprType::ArithmeticNeg),
+         OpCode::NotEq => self.binary_operation(BinaryExprType::LogicNotEQ),
+         OpCode::NullishCoalescing => self.binary_operation(BinaryExprType::Nullish),
+         OpCode::Slice => self.op_slice(),
+         OpCode::Subscript => self.op_subscript(),
+         OpCode::Subtract => self.binary_operation(BinaryExprType::Minus),
+
+         // Jumps
+         OpCode::ForIterNextOrJump => self.op_get_iter_next_or_jump(),
+         OpCode::Unpack => self.op_unpack(),
+         OpCode::JumpForward => self.op_jump_forward(),
+         OpCode::JumpIfFalseOrPop => self.op_jump_if_false_or_pop(),
+         OpCode::JumpIfNull => self.op_jump_if_null(),
+         OpCode::JumpIfTrueOrPop => self.op_jump_if_true_or_pop(),
+         OpCode::JumpTable => self.op_jump_table(),
+         OpCode::LoopJump | OpCode::LoopJumpLong => self.op_loop_jump(),
+         OpCode::PopJumpIfFalse => self.op_pop_and_jump_if_false(),
+
+         // Functions and Closures
+         OpCode::Async => self.op_async(),
+         OpCode::Await => self.op_await(),
+         OpCode::BindDefaults => self.op_bind_function_defaults(),
+         OpCode::BuildString => self.op_build_string(),
+         OpCode::CloseUpVal | OpCode::CloseUpValLong => self.up_close_up_value(),
+         OpCode::FuncCall => self.op_func_call(),
+         OpCode::FuncCallNamed => self.op_func_call_named(),
+         OpCode::FuncCallSpread => self.op_func_call_spread(),
+         OpCode::GetUpVal | OpCode::GetUpValLong => self.op_get_up_value(),
+         OpCode::PopCloseUpVal => self.op_pop_stack_and_close_up_value(),
+         OpCode::Return => self.op_function_return(),
+         OpCode::TailCall => self.op_tail_call(),
+         OpCode::SetUpVal | OpCode::SetUpValLong => self.op_set_up_value(),
+
+         // Classes & Instances
+         OpCode::AppendClassField => self.append_class_field(),
+         OpCode::Implement => self.op_implement(),
+         OpCode::Inherit => self.op_inherit(),
+         OpCode::InstanceOf => self.op_instance_of(),
+         OpCode::MatchSequence => self.op_match_sequence(),
+         OpCode::MatchVariant => self.op_match_variant(),
+         OpCode::MakeClass | OpCode::MakeClassLong => self.op_make_class(),
+         OpCode::MakeInstance => self.op_make_instance(),
+         OpCode::MakeInstanceNamed => self.op_make_instance_named(),
+         OpCode::MakeInstanceSpread => self.op_make_instance_spread(),
+
+         // Modules
+         OpCode::ExportModule => self.op_export_module(),
+         OpCode::FailAssertion => self.op_fail_assertion(),
+         OpCode::AssertNonNull => self.op_assert_non_null(),
+         OpCode::ImportModule | OpCode::ImportModuleLong => self.op_import_module(),
+
+         // Collection manipulators
+         OpCode::GetProp | OpCode::GetPropLong => self.op_get_property(),
+         OpCode::GetSuper | OpCode::GetSuperLong => self.op_get_super(),
+         OpCode::SetProp | OpCode::SetPropLong => self.op_set_property(),
+         OpCode::SubscriptAssign => self.op_subscript_assign(),
+         OpCode::ArrayPush => self.op_array_push(),
+
+         // Superinstructions
+         OpCode::GetLocalsCompareJump => self.op_get_locals_compare_jump(),
+         OpCode::LoadConstantAdd => {
+            let pos = self.next_byte() as usize;
+            self.op_fused_add(self.read_constant(pos))
+         }
+         OpCode::LoadImm1IAdd => self.op_fused_add(Object::Int(1i64)),
+         OpCode::LoadImmNAdd => {
+            let imm = self.next_byte() as i64;
+            self.op_fused_add(Object::Int(imm))
+         }
+
+         // VM-Specific
+         OpCode::EndVirtualMachine => self.op_end_virtual_machine(),
+         OpCode::Rethrow => self.op_rethrow(),
+      }
+   }
+
    /// Executes the instruction to end the virtual machine with an OK result.
    fn op_end_virtual_machine(&mut self) -> RuntimeResult {
-      self.pop_stack(); // Remove the main function off the stack
+      // The main function (and the value of the last expression evaluated by
+      // the REPL, if any) are left on the stack until the next program executes.
       self.frames.pop();
 
       RuntimeResult::EndOK
    }
 
+   /// Executes the instruction to pop the pending error at the end of a `finally` block, and
+   /// re-throw it if the block was entered because of an uncaught error.
+   fn op_rethrow(&mut self) -> RuntimeResult {
+      let err = match self.pop_stack() {
+         Object::Dict(d) => d,
+         Object::Null => return RuntimeResult::Continue,
+         _ => unreachable!("Expected a pending error or null on TOS."),
+      };
+
+      // Restores the call frames where the error was originally thrown, so that it is
+      // reported there instead of at the end of the `finally` block.
+      if let Some(idx) = self
+         .error_traces
+         .iter()
+         .position(|(d, _)| d.as_ptr() == Rc::as_ptr(&err))
+      {
+         self.error_trace = Some(self.error_traces.swap_remove(idx).1);
+      }
+
+      let err = err.borrow();
+      let error = match err.get("name") {
+         Some(Object::String(name)) => RuntimeErrorType::from_name(name),
+         _ => None,
+      };
+      let message = match err.get("message") {
+         Some(Object::String(msg)) => msg.to_string(),
+         _ => String::new(),
+      };
+
+      RuntimeResult::Error {
+         error: error.unwrap_or(RuntimeErrorType::Internal),
+         message,
+      }
+   }
+
    /// Executes the instruction to pop the top of the stack, and jump forward by the given
    /// offset if the popped value is falsey.
    fn op_pop_and_jump_if_false(&mut self) -> RuntimeResult {
@@ -140,6 +377,90 @@ impl VM {
       RuntimeResult::Continue
    }
 
+   /// Executes the superinstruction that fuses an object loader with the `ADD` instruction
+   /// that follows it, which adds the loaded object to the object on top of the stack.
+   ///
+   /// # Parameters
+   /// - `operand`: The object loaded by the fused loader.
+   fn op_fused_add(&mut self, operand: Object) -> RuntimeResult {
+      // Skips the fused `ADD`, so that errors are reported at its location.
+      self.current_frame_mut().ip += 1;
+
+      // Numbers do not have operator methods, so they are added without the round-trip
+      // through the stack.
+      if matches!(*self.peek_stack(0), Object::Int(_) | Object::Float(_)) {
+         let val = self.pop_stack();
+
+         return match val.binary_operation(&BinaryExprType::Addition, operand) {
+            Ok(r) => self.push_stack(r),
+            Err(e) => e.to_runtime_error(),
+         };
+      }
+
+      self.push_stack(operand);
+      self.binary_operation(BinaryExprType::Addition)
+   }
+
+   /// Executes the superinstruction that fuses two `GET_LOCAL` instructions, the comparison of
+   /// the two locals, and the `POP_JUMP_IF_FALSE` instruction that follows the comparison.
+   fn op_get_locals_compare_jump(&mut self) -> RuntimeResult {
+      let first = self.next_byte() as usize;
+      // Skips the opcode of the second `GET_LOCAL`.
+      self.current_frame_mut().ip += 1;
+      let second = self.next_byte() as usize;
+
+      let opr = match self.next_op_code() {
+         OpCode::Equals => BinaryExprType::LogicEQ,
+         OpCode::GreaterThan => BinaryExprType::LogicGreaterThan,
+         OpCode::GreaterThanEq => BinaryExprType::LogicGreaterThanEQ,
+         OpCode::LessThan => BinaryExprType::LogicLessThan,
+         OpCode::LessThanEq => BinaryExprType::LogicLessThanEQ,
+         OpCode::NotEq => BinaryExprType::LogicNotEQ,
+         op => unreachable!("Expected a comparison instruction. Got '{:?}' instead.", op),
+      };
+
+      let base = self.current_frame().return_index;
+      let val1 = self.peek_stack_abs(base + first).into_owned();
+      let val2 = self.peek_stack_abs(base + second).into_owned();
+
+      // Numbers do not have operator methods, so they are compared without the round-trip
+      // through the stack.
+      if matches!(val1, Object::Int(_) | Object::Float(_))
+         && matches!(val2, Object::Int(_) | Object::Float(_))
+      {
+         // Skips the opcode of the fused `POP_JUMP_IF_FALSE`.
+         self.current_frame_mut().ip += 1;
+         let offset = self.next_short() as usize;
+
+         return match val1.binary_operation(&opr, val2) {
+            Ok(r) => {
+               if r.is_falsey() {
+                  self.current_frame_mut().ip += offset;
+               }
+
+               RuntimeResult::Continue
+            }
+            Err(e) => e.to_runtime_error(),
+         };
+      }
+
+      self.push_stack(val1);
+      self.push_stack(val2);
+
+      // Comparisons of instances may call an operator method in a new call frame. In that case,
+      // the fused `POP_JUMP_IF_FALSE` is executed after the method returns.
+      let frame_count = self.frames.len();
+
+      match self.binary_operation(opr) {
+         RuntimeResult::Continue if self.frames.len() == frame_count => {
+            // Skips the opcode of the fused `POP_JUMP_IF_FALSE`.
+            self.current_frame_mut().ip += 1;
+            self.op_pop_and_jump_if_false()
+         }
+         result => result,
+      }
+   }
+
    /// Executes the instruction to jump forward by the given offset if the top of the stack is
    /// falsey, or pop the top off the stack otherwise.
    fn op_jump_if_false_or_pop(&mut self) -> RuntimeResult {
@@ -155,6 +476,18 @@ impl VM {
       RuntimeResult::Continue
    }
 
+   /// Executes the instruction to jump forward by the given offset if the top of the stack is
+   /// `null`, which is kept on the stack as the value of the optional chain it short-circuits.
+   fn op_jump_if_null(&mut self) -> RuntimeResult {
+      let offset = self.next_short() as usize;
+
+      if let Object::Null = *self.peek_stack(0) {
+         self.current_frame_mut().ip += offset;
+      }
+
+      RuntimeResult::Continue
+   }
+
    /// Executes the instruction to pop the top of the stack, and jump forward by the given
    /// offset if the popped value is truthy.
    fn op_jump_if_true_or_pop(&mut self) -> RuntimeResult {
@@ -170,6 +503,48 @@ impl VM {
       RuntimeResult::Continue
    }
 
+   /// Executes the instruction to jump forward by the offset associated with the number at the
+   /// top of the stack in a jump table. Numbers that are not keys in the table continue after
+   /// the table, and values that are not numbers jump by the table's first offset. The value
+   /// is left on the stack.
+   fn op_jump_table(&mut self) -> RuntimeResult {
+      let min = self.next_short() as usize;
+      let min = self.read_constant(min).as_int().unwrap();
+      let size = self.next_short() as usize;
+      let table_end = self.current_frame().ip + (size + 1) * 2;
+
+      let entry = match &*self.peek_stack(0) {
+         Object::Int(_) | Object::Bool(_) => self.peek_stack(0).as_int(),
+         // Floats are keys when they are equal to an integer key.
+         Object::Float(f) if f.fract() == 0f64 => Some(*f as i64),
+         Object::Float(_) => None,
+         // Values that are not numbers jump by the table's first offset.
+         _ => Some(min - 1),
+      }
+      // The first offset is followed by one offset for each key, starting from the lowest.
+      .and_then(|key| key.checked_sub(min - 1))
+      .filter(|entry| *entry >= 0 && (*entry as usize) <= size);
+
+      let entry = match entry {
+         Some(entry) => entry as usize,
+         None => {
+            self.current_frame_mut().ip = table_end;
+            return RuntimeResult::Continue;
+         }
+      };
+
+      let frame = self.current_frame_mut();
+      let offset = frame
+         .closure
+         .function
+         .borrow()
+         .chunk
+         .get_short(frame.ip + entry * 2) as usize;
+      frame.ip = table_end + offset;
+
+      RuntimeResult::Continue
+   }
+
    /// Executes the instruction to jump forward by the given offset.
    fn op_jump_forward(&mut self) -> RuntimeResult {
       // The JUMP_FORWARD instruction always has a short as its operand.
@@ -213,10 +588,66 @@ impl VM {
    /// Executes the instruction to call a function object.
    fn op_func_call(&mut self) -> RuntimeResult {
       let arg_count = self.next_byte(); // Functions can only have 255-MAX parameters
-      let maybe_function = self.peek_stack(arg_count as usize).clone();
+      let maybe_function = self.peek_stack(arg_count as usize).into_owned();
       self.call_object(maybe_function, arg_count)
    }
 
+   /// Executes the instruction to call a function in tail position. When the callee is a Hinton
+   /// function, the current call frame is replaced by the callee's call frame, so that recursive
+   /// tail calls run in constant frame space. Other objects are called as usual.
+   fn op_tail_call(&mut self) -> RuntimeResult {
+      let arg_count = self.next_byte();
+      let callee = self.peek_stack(arg_count as usize).into_owned();
+
+      // The current frame cannot be reused if it still has deferred calls to run.
+      if !matches!(
+         callee,
+         Object::Function(_) | Object::Closure(_) | Object::BoundMethod(_)
+      ) || !self.current_frame().defers.is_empty()
+      {
+         return self.call_object(callee, arg_count);
+      }
+
+      // Moves the callee and its arguments to the base of the current call frame,
+      // discarding the current function's locals.
+      let base = self.current_frame().return_index;
+      let callee_pos = self.stack.len() - (arg_count as usize) - 1;
+      self.close_up_values_from(base);
+      self.stack.drain(base..callee_pos);
+      self.frames.pop();
+
+      self.call_object(callee, arg_count)
+   }
+
+   /// Executes the instruction to call a function with named arguments. The first operand is the
+   /// number of arguments in the call, and the second operand is how many of those are named.
+   /// Each named argument is preceded on the stack by a string with the argument's name.
+   fn op_func_call_named(&mut self) -> RuntimeResult {
+      let arg_count = self.next_byte();
+      let named_count = self.next_byte();
+      let maybe_function = self.peek_stack((arg_count + named_count) as usize).into_owned();
+
+      match self.bind_named_args(&maybe_function, arg_count, named_count) {
+         Ok(arg_count) => self.call_object(maybe_function, arg_count),
+         Err(e) => e,
+      }
+   }
+
+   /// Executes the instruction to call a function with spread arguments. The first operand is the
+   /// number of arguments in the call, followed by one operand per argument that is `1` when the
+   /// argument is spread, and `0` otherwise.
+   fn op_func_call_spread(&mut self) -> RuntimeResult {
+      let arg_count = self.next_byte();
+
+      match self.spread_args(arg_count) {
+         Ok(arg_count) => {
+            let maybe_function = self.peek_stack(arg_count as usize).into_owned();
+            self.call_object(maybe_function, arg_count)
+         }
+         Err(e) => e,
+      }
+   }
+
    /// Executes the instruction to make a closure object from a function object.
    /// This method only covers the `OP_MAKE_CLOSURE` and `OP_MAKE_CLOSURE_LONG` instructions
    /// with a variable number of operands. The byte or short immediately following the
@@ -230,7 +661,7 @@ impl VM {
          _ => unreachable!("Expected a Function object for closure."),
       };
 
-      let up_val_count = function.borrow().up_val_count;
+      let up_val_count = function.borrow().up_val_names.len();
       let mut up_values: Vec<Rc<RefCell<UpValRef>>> = Vec::with_capacity(up_val_count);
 
       for _ in 0..up_val_count {
@@ -262,7 +693,7 @@ impl VM {
          _ => unreachable!("Expected a Function object for closure."),
       };
 
-      let up_val_count = function.borrow().up_val_count;
+      let up_val_count = function.borrow().up_val_names.len();
       let mut up_values: Vec<Rc<RefCell<UpValRef>>> = Vec::with_capacity(up_val_count);
 
       for _ in 0..up_val_count {
@@ -286,7 +717,7 @@ impl VM {
       let pos = self.get_std_or_long_operand(OpCode::GetUpVal);
 
       let val = match &*self.get_up_val(pos).borrow() {
-         UpValRef::Open(l) => self.peek_stack_abs(*l).clone(),
+         UpValRef::Open(l) => self.peek_stack_abs(*l).into_owned(),
          UpValRef::Closed(o) => o.clone(),
       };
 
@@ -296,10 +727,10 @@ impl VM {
    /// Executes the instruction to modify an UpValue in the current call frame's closure.
    fn op_set_up_value(&mut self) -> RuntimeResult {
       let pos = self.get_std_or_long_operand(OpCode::SetUpVal);
-      let new_val = self.stack.last().unwrap().clone();
+      let new_val = self.peek_stack(0).into_owned();
 
       match &mut *self.get_up_val(pos).borrow_mut() {
-         UpValRef::Open(l) => self.stack[*l] = new_val,
+         UpValRef::Open(l) => self.stack[*l] = StackValue::from_object(new_val),
          UpValRef::Closed(u) => *u = new_val,
       }
 
@@ -314,7 +745,7 @@ impl VM {
       for u in self.up_values.iter() {
          if u.borrow().is_open_at(self.current_frame().return_index + pos) {
             let new_val = self.peek_stack_abs(self.current_frame().return_index + pos);
-            u.replace(UpValRef::Closed(new_val.clone()));
+            u.replace(UpValRef::Closed(new_val.into_owned()));
             break;
          }
       }
@@ -349,7 +780,7 @@ impl VM {
       }
       defaults.reverse();
 
-      match self.peek_stack_mut(0) {
+      match &*self.peek_stack(0) {
          Object::Function(m) => {
             m.borrow_mut().defaults = defaults;
          }
@@ -364,8 +795,20 @@ impl VM {
 
    /// Executes the instruction to return out of a function call.
    fn op_function_return(&mut self) -> RuntimeResult {
+      // The deferred calls run before the function's locals are discarded. The return value
+      // is left on the stack, and this instruction executes again after each deferred call.
+      if let Some(deferred) = self.current_frame_mut().defers.pop() {
+         self.current_frame_mut().ip -= 1;
+         return self.call_deferred(deferred);
+      }
+
       let result = self.pop_stack();
-      let locals_to_pop = self.stack.len() - self.current_frame().return_index;
+      let return_index = self.current_frame().return_index;
+      let locals_to_pop = self.stack.len() - return_index;
+
+      // Closes the UpValues that captured the function's locals, including
+      // those declared in enclosing blocks of the `return` statement.
+      self.close_up_values_from(return_index);
 
       // Pops local declarations from the stack
       for _ in 0..locals_to_pop {
@@ -373,10 +816,76 @@ impl VM {
       }
 
       // removes the call frame
-      self.frames.pop();
+      if self.frames.pop().unwrap().is_deferred {
+         return self.resume_after_deferred();
+      }
+
       self.push_stack(result)
    }
 
+   /// Executes the instruction to raise an `AssertionError`. The top of the stack is the message
+   /// of the assertion (or `null`), and below it is the source text of the failed condition.
+   fn op_fail_assertion(&mut self) -> RuntimeResult {
+      let message = self.pop_stack();
+      let source = self.pop_stack();
+
+      RuntimeResult::Error {
+         error: RuntimeErrorType::AssertionError,
+         message: match message {
+            Object::Null => format!("Assertion `{}` failed.", source),
+            message => format!("Assertion `{}` failed: {}", source, message.as_plain_string()),
+         },
+      }
+   }
+
+   /// Executes the instruction to assert that the top of the stack is not `null`. The operand
+   /// of the instruction is the position in the pool of the source text of the asserted value.
+   fn op_assert_non_null(&mut self) -> RuntimeResult {
+      let pos = self.next_short() as usize;
+
+      match *self.peek_stack(0) {
+         Object::Null => RuntimeResult::Error {
+            error: RuntimeErrorType::NullError,
+            message: format!("Expected '{}' not to be null.", self.read_constant(pos)),
+         },
+         _ => RuntimeResult::Continue,
+      }
+   }
+
+   /// Executes the instruction to add the closure at the top of the stack to the list
+   /// of deferred calls of the current call frame.
+   fn op_defer(&mut self) -> RuntimeResult {
+      let deferred = self.pop_stack();
+      self.current_frame_mut().defers.push(deferred);
+      RuntimeResult::Continue
+   }
+
+   /// Executes the instruction to import a module. If the module has already been loaded, its
+   /// cached exports are pushed onto the stack. Otherwise, the module's function is called.
+   fn op_import_module(&mut self) -> RuntimeResult {
+      let pos = self.get_std_or_long_operand(OpCode::ImportModule);
+
+      let module = match self.read_constant(pos) {
+         Object::Function(f) => f,
+         _ => unreachable!("Expected a Function object for module."),
+      };
+
+      if let Some(exports) = self.modules.get(&module.borrow().name) {
+         return self.push_stack(exports.clone());
+      }
+
+      self.push_stack(Object::Function(module.clone()));
+      self.call_function(module, 0)
+   }
+
+   /// Executes the instruction to cache the exports of the current module, which live at the TOS.
+   fn op_export_module(&mut self) -> RuntimeResult {
+      let name = self.current_frame().closure.function.borrow().name.clone();
+      let exports = self.peek_stack(0).into_owned();
+      self.modules.insert(name, exports);
+      RuntimeResult::Continue
+   }
+
    /// Executes the instruction to create a class object.
    fn op_make_class(&mut self) -> RuntimeResult {
       let pos = self.get_std_or_long_operand(OpCode::MakeClass);
@@ -386,93 +895,442 @@ impl VM {
          _ => unreachable!("Expected String for class name."),
       };
 
-      self.push_stack(Object::from(ClassObject::new(&name)))
+      let class = self.alloc_class(ClassObject::new(&name));
+      self.push_stack(class)
+   }
+
+   /// Executes the instruction to make the class below the TOS inherit from the superclass on
+   /// the TOS. The members of the superclass are copied into the class, so that the members the
+   /// class declares afterwards override them.
+   fn op_inherit(&mut self) -> RuntimeResult {
+      let superclass = match self.pop_stack() {
+         Object::Class(c) => c,
+         obj => {
+            return RuntimeResult::Error {
+               error: RuntimeErrorType::InstanceError,
+               message: format!("Cannot inherit from object of type '{}'.", obj.type_name()),
+            }
+         }
+      };
+
+      match self.peek_stack(0).into_owned() {
+         Object::Class(c) => {
+            let mut class = c.borrow_mut();
+            class.members = superclass.borrow().members.clone();
+            class.statics = superclass.borrow().statics.clone();
+            class.interfaces = superclass.borrow().interfaces.clone();
+            class.superclass = Some(superclass);
+         }
+         _ => unreachable!("Expected Class object on TOS to inherit from a superclass."),
+      }
+
+      RuntimeResult::Continue
+   }
+
+   /// Executes the instruction to make the class below the TOS implement the interface on the
+   /// TOS. The class must define every method required by the interface.
+   fn op_implement(&mut self) -> RuntimeResult {
+      let interface = match self.pop_stack() {
+         Object::Interface(i) => i,
+         obj => {
+            return RuntimeResult::Error {
+               error: RuntimeErrorType::InstanceError,
+               message: format!("Cannot implement object of type '{}'.", obj.type_name()),
+            }
+         }
+      };
+
+      let result = match self.peek_stack(0).into_owned() {
+         Object::Class(c) => c.borrow_mut().implement(interface),
+         _ => unreachable!("Expected Class object on TOS to implement an interface."),
+      };
+
+      match result {
+         Ok(_) => RuntimeResult::Continue,
+         Err(e) => e,
+      }
+   }
+
+   /// Executes the instruction to test whether the object below the TOS has the type on the
+   /// TOS, which is either the name of a built-in type, a class, or an interface. Instances
+   /// of a class are also instances of its superclasses.
+   fn op_instance_of(&mut self) -> RuntimeResult {
+      let ty = self.pop_stack();
+      let value = self.pop_stack();
+
+      let result = match &ty {
+         Object::String(name) => match (&**name, &value) {
+            ("Any", _) => true,
+            // The type name of classes and instances is the name of their class.
+            (_, Object::Class(_) | Object::Instance(_)) => false,
+            (name, value) => value.type_name() == name,
+         },
+         _ => match value.is_instance_of(&ty) {
+            Some(b) => b,
+            None => {
+               return RuntimeResult::Error {
+                  error: RuntimeErrorType::TypeError,
+                  message: format!(
+                     "Right-hand side of 'is' must be a class or an interface, but got '{}'.",
+                     ty.type_name()
+                  ),
+               }
+            }
+         },
+      };
+
+      self.push_stack(Object::Bool(result))
+   }
+
+   /// Executes the instruction to test whether the object below the TOS is an array or a tuple
+   /// whose length is the integer on the TOS, as required by the array patterns of `match` arms.
+   fn op_match_sequence(&mut self) -> RuntimeResult {
+      let len = self.pop_stack().as_int();
+      let value = self.pop_stack();
+
+      let result = match &value {
+         Object::Array(arr) => len == Some(arr.borrow().len() as i64),
+         Object::Tuple(tup) => len == Some(tup.len() as i64),
+         _ => false,
+      };
+
+      self.push_stack(Object::Bool(result))
+   }
+
+   /// Executes the instruction to check if a value is a given variant of an enum, with a
+   /// payload of the given length (if the length on the TOS is not `null`), as used by the
+   /// variant patterns of `match` arms.
+   fn op_match_variant(&mut self) -> RuntimeResult {
+      let len = self.pop_stack().as_int();
+      let variant = self.pop_stack();
+      let enum_obj = self.pop_stack();
+      let value = self.pop_stack();
+
+      let result = match (&value, &enum_obj, &variant) {
+         (Object::EnumValue(v), Object::Enum(e), Object::String(name)) => {
+            Rc::ptr_eq(&v.variant.enum_obj, e)
+               && v.variant.name() == name.as_ref()
+               && len.is_none_or(|len| len == v.payload.len() as i64)
+         }
+         _ => false,
+      };
+
+      self.push_stack(Object::Bool(result))
    }
 
    /// Executes the instruction to create an instance from a class object.
    fn op_make_instance(&mut self) -> RuntimeResult {
       let arg_count = self.next_byte(); // Instances can only have 255-MAX arguments
-      let maybe_class = self.peek_stack(arg_count as usize).clone();
+      let maybe_class = self.peek_stack(arg_count as usize).into_owned();
       self.create_instance(maybe_class, arg_count)
    }
 
+   /// Executes the instruction to create an instance of a class with named arguments
+   /// for its initializer. The operands have the same meaning as in `OP_FUNC_CALL_NAMED`.
+   fn op_make_instance_named(&mut self) -> RuntimeResult {
+      let arg_count = self.next_byte();
+      let named_count = self.next_byte();
+      let maybe_class = self.peek_stack((arg_count + named_count) as usize).into_owned();
+
+      let initializer = match &maybe_class {
+         Object::Class(c) => match c.borrow().members.get("init") {
+            Some(field) => (*field.value).clone(),
+            None => Object::Null,
+         },
+         _ => return self.create_instance(maybe_class, arg_count),
+      };
+
+      match self.bind_named_args(&initializer, arg_count, named_count) {
+         Ok(arg_count) => self.create_instance(maybe_class, arg_count),
+         Err(e) => e,
+      }
+   }
+
+   /// Executes the instruction to create an instance of a class with spread arguments
+   /// for its initializer. The operands have the same meaning as in `OP_FUNC_CALL_SPREAD`.
+   fn op_make_instance_spread(&mut self) -> RuntimeResult {
+      let arg_count = self.next_byte();
+
+      match self.spread_args(arg_count) {
+         Ok(arg_count) => {
+            let maybe_class = self.peek_stack(arg_count as usize).into_owned();
+            self.create_instance(maybe_class, arg_count)
+         }
+         Err(e) => e,
+      }
+   }
+
    /// Executes the instruction to get a property from an object.
    fn op_get_property(&mut self) -> RuntimeResult {
       let pos = self.get_std_or_long_operand(OpCode::GetProp);
+      let cache = self.next_short() as usize;
+
+      let value = self.pop_stack();
+
+      // Instances with the layout remembered by the cache have the property in the cached slot.
+      if let Object::Instance(x) = &value {
+         let slot = self.cached_slot(cache, &x.borrow().layout);
+
+         if let Some(slot) = slot {
+            let member = (*x.borrow().fields[slot].value).clone();
+            return self.push_instance_member(x, member);
+         }
+      }
 
       let prop_name = match self.read_constant(pos) {
-         Object::String(name) => name,
+         Object::String(name) => name.to_string(),
          _ => unreachable!("Expected String for property access name."),
       };
 
-      let value = self.pop_stack();
+      // Properties with a getter are computed by calling the getter.
+      if let Object::Instance(x) = &value {
+         match BoundMethod::find_accessor(x, "get", &prop_name) {
+            Some(Ok(getter)) => {
+               let getter = Object::BoundMethod(getter);
+               self.push_stack(getter.clone());
+               return self.call_object(getter, 0);
+            }
+            Some(Err(e)) => return e,
+            None => {}
+         }
+      }
+
       match value {
-         Object::Instance(x) => match x.borrow().get_prop(prop_name) {
-            Ok(o) => match o {
-               Object::Closure(c) => self.push_stack(Object::BoundMethod(BoundMethod {
-                  receiver: x.clone(),
-                  method: c,
-               })),
-               Object::Function(f) => self.push_stack(Object::BoundMethod(BoundMethod {
-                  receiver: x.clone(),
-                  method: ClosureObject {
-                     function: f,
-                     up_values: vec![],
-                  },
-               })),
-               _ => self.push_stack(o),
-            },
-            Err(e) => e,
-         },
-         Object::Dict(x) => match x.borrow().get(&prop_name) {
-            Some(val) => self.push_stack(val.clone()),
-            None => RuntimeResult::Error {
-               error: RuntimeErrorType::KeyError,
-               message: format!("Entry with key '{}' not found in the dictionary.", prop_name),
-            },
-         },
+         Object::Instance(x) => {
+            let member = x.borrow().get_prop(prop_name.clone());
+
+            match member {
+               Ok(member) => {
+                  self.update_cache(cache, &x.borrow().layout, &prop_name);
+                  self.push_instance_member(&x, member)
+               }
+               Err(e) => e,
+            }
+         }
+         Object::Dict(ref x) => {
+            let entry = x.borrow().get(&prop_name).cloned();
+
+            // The entries of a dictionary take precedence over the methods of the `Dict` primitive.
+            match entry {
+               Some(val) => self.push_stack(val),
+               None
+                  if self
+                     .built_in
+                     .primitives
+                     .get_prop_in_class("Dict", prop_name.clone())
+                     .is_ok() =>
+               {
+                  BuiltIn::primitive_prop(self, value.clone(), "Dict", prop_name)
+               }
+               None => RuntimeResult::Error {
+                  error: RuntimeErrorType::KeyError,
+                  message: format!("Entry with key '{}' not found in the dictionary.", prop_name),
+               },
+            }
+         }
          Object::Int(_) => BuiltIn::primitive_prop(self, value, "Int", prop_name),
          Object::String(_) => BuiltIn::primitive_prop(self, value, "String", prop_name),
+         Object::Char(_) => BuiltIn::primitive_prop(self, value, "Char", prop_name),
+         Object::Range(_) => BuiltIn::primitive_prop(self, value, "Range", prop_name),
+         Object::Iter(_) => BuiltIn::primitive_prop(self, value, "Iter", prop_name),
          Object::Array(_) => BuiltIn::primitive_prop(self, value, "Array", prop_name),
+         Object::Set(_) => BuiltIn::primitive_prop(self, value, "Set", prop_name),
+         Object::Bytes(_) => BuiltIn::primitive_prop(self, value, "Bytes", prop_name),
+         Object::Channel(_) => BuiltIn::primitive_prop(self, value, "Channel", prop_name),
+         Object::Promise(_) => BuiltIn::primitive_prop(self, value, "Promise", prop_name),
+         Object::Socket(_) => BuiltIn::primitive_prop(self, value, "Socket", prop_name),
+         Object::Regex(_) => BuiltIn::primitive_prop(self, value, "Regex", prop_name),
+         Object::DateTime(_) => BuiltIn::primitive_prop(self, value, "DateTime", prop_name),
          Object::Class(c) => match c.borrow().get_static_prop(prop_name) {
             Ok(val) => self.push_stack(val),
             Err(e) => e,
          },
-         _ => todo!("Objects of type '{}' also have properties.", value.type_name()),
+         Object::Enum(e) => match e.variant_index(&prop_name) {
+            Some(idx) => {
+               let variant = EnumVariantObject {
+                  enum_obj: e.clone(),
+                  variant: idx,
+               };
+
+               // Variants without a payload are values of the enum, rather than constructors.
+               if variant.fields().is_empty() {
+                  self.push_stack(Object::EnumValue(Rc::new(EnumValueObject {
+                     variant,
+                     payload: vec![],
+                  })))
+               } else {
+                  self.push_stack(Object::EnumVariant(variant))
+               }
+            }
+            None => RuntimeResult::Error {
+               error: RuntimeErrorType::ReferenceError,
+               message: format!("Enum '{}' has no variant '{}'.", e.name, prop_name),
+            },
+         },
+         Object::EnumValue(v) => match v.get_field(&prop_name) {
+            Some(val) => self.push_stack(val.clone()),
+            None => RuntimeResult::Error {
+               error: RuntimeErrorType::ReferenceError,
+               message: format!(
+                  "Variant '{}.{}' has no field '{}'.",
+                  v.variant.enum_obj.name,
+                  v.variant.name(),
+                  prop_name
+               ),
+            },
+         },
+         _ => RuntimeResult::Error {
+            error: RuntimeErrorType::TypeError,
+            message: format!(
+               "Cannot get property '{}' of an object of type '{}'.",
+               prop_name,
+               value.type_name()
+            ),
+         },
       }
    }
 
+   /// Executes the instruction to get a method from the superclass of the class on the TOS,
+   /// bound to the instance below the class.
+   fn op_get_super(&mut self) -> RuntimeResult {
+      let pos = self.get_std_or_long_operand(OpCode::GetSuper);
+
+      let method_name = match self.read_constant(pos) {
+         Object::String(name) => name,
+         _ => unreachable!("Expected String for super method name."),
+      };
+
+      let class = match self.pop_stack() {
+         Object::Class(c) => c,
+         _ => unreachable!("Expected Class object on TOS for super method access."),
+      };
+
+      let instance = match self.pop_stack() {
+         Object::Instance(i) => i,
+         _ => {
+            return RuntimeResult::Error {
+               error: RuntimeErrorType::InstanceError,
+               message: String::from("Cannot use 'super' outside of an instance method."),
+            }
+         }
+      };
+
+      let superclass = match &class.borrow().superclass {
+         Some(s) => s.clone(),
+         None => {
+            return RuntimeResult::Error {
+               error: RuntimeErrorType::InstanceError,
+               message: format!("Class '{}' does not have a superclass.", class.borrow().name),
+            }
+         }
+      };
+      let superclass = superclass.borrow();
+
+      let method = match superclass.members.get(&*method_name).map(|f| &*f.value) {
+         Some(Object::Closure(c)) => c.clone(),
+         Some(Object::Function(f)) => ClosureObject {
+            function: f.clone(),
+            up_values: vec![],
+         },
+         _ => {
+            return RuntimeResult::Error {
+               error: RuntimeErrorType::InstanceError,
+               message: format!(
+                  "Method '{}' not defined in superclass '{}'.",
+                  method_name, superclass.name
+               ),
+            }
+         }
+      };
+
+      self.push_stack(Object::BoundMethod(BoundMethod {
+         receiver: instance,
+         method,
+      }))
+   }
+
    /// Executes the instruction to modify a property in an object.
    fn op_set_property(&mut self) -> RuntimeResult {
       let pos = self.get_std_or_long_operand(OpCode::SetProp);
+      let cache = self.next_short() as usize;
+
+      let value = self.pop_stack();
+      let target = self.pop_stack();
+
+      // Instances with the layout remembered by the cache have the property in the cached slot.
+      if let Object::Instance(inst) = &target {
+         let slot = self.cached_slot(cache, &inst.borrow().layout);
+
+         if let Some(slot) = slot {
+            *inst.borrow_mut().fields[slot].value = value.clone();
+            return self.push_stack(value);
+         }
+      }
 
       let prop_name = match self.read_constant(pos) {
-         Object::String(name) => name,
+         Object::String(name) => name.to_string(),
          _ => unreachable!("Expected String for property assignment name."),
       };
 
-      let value = self.pop_stack();
-
-      return match self.pop_stack() {
-         Object::Instance(inst) => match inst.borrow_mut().set_prop(prop_name, value) {
-            Ok(o) => self.push_stack(o),
-            Err(e) => e,
+      return match target {
+         // Properties with a setter are modified by calling the setter.
+         Object::Instance(inst) => match BoundMethod::find_accessor(&inst, "set", &prop_name) {
+            Some(Ok(setter)) => match self.call_value(Object::BoundMethod(setter), vec![value.clone()]) {
+               Ok(_) => self.push_stack(value),
+               Err(e) => e,
+            },
+            Some(Err(e)) => e,
+            None => {
+               let result = inst.borrow_mut().set_prop(prop_name.clone(), value);
+
+               match result {
+                  Ok(o) => {
+                     self.update_cache(cache, &inst.borrow().layout, &prop_name);
+                     self.push_stack(o)
+                  }
+                  Err(e) => e,
+               }
+            }
          },
          Object::Dict(dict) => {
             dict.borrow_mut().insert(prop_name, value.clone());
             self.push_stack(value)
          }
-         _ => todo!("Other objects also have properties."),
+         Object::Class(class) => match class.borrow_mut().set_static_prop(prop_name, value) {
+            Ok(o) => self.push_stack(o),
+            Err(e) => e,
+         },
+         obj => RuntimeResult::Error {
+            error: RuntimeErrorType::TypeError,
+            message: format!(
+               "Cannot set property '{}' of an object of type '{}'.",
+               prop_name,
+               obj.type_name()
+            ),
+         },
       };
    }
 
    /// Executes the instruction to modify the value of a collection at the provided index.
    fn op_subscript_assign(&mut self) -> RuntimeResult {
-      let target = self.pop_stack();
-      let index = self.pop_stack();
       let value = self.pop_stack();
+      let index = self.pop_stack();
+      let target = self.pop_stack();
 
       match target {
+         Object::Instance(inst) => match BoundMethod::find(&inst, "__set_index__") {
+            Some(method) => match self.call_value(Object::BoundMethod(method), vec![index, value.clone()]) {
+               Ok(_) => self.push_stack(value),
+               Err(e) => e,
+            },
+            None => RuntimeResult::Error {
+               error: RuntimeErrorType::TypeError,
+               message: format!(
+                  "Objects of type '{}' do not support subscripted item reassignment.",
+                  inst.borrow().class.borrow().name
+               ),
+            },
+         },
          Object::Array(arr) => {
             let idx = match index {
                Object::Int(i) => to_bounded_index(i, arr.borrow().len()),
@@ -498,19 +1356,52 @@ impl VM {
                },
             }
          }
-         Object::Dict(dict) => match index {
-            Object::String(s) => {
-               dict.borrow_mut().insert(s, value.clone());
-               self.push_stack(value)
+         Object::Bytes(bytes) => {
+            let idx = match index {
+               Object::Int(i) => to_bounded_index(i, bytes.borrow().len()),
+               _ => {
+                  return RuntimeResult::Error {
+                     error: RuntimeErrorType::TypeError,
+                     message: format!(
+                        "Bytes reassignment subscript must be an Int. Found '{}' instead.",
+                        index.type_name()
+                     ),
+                  }
+               }
+            };
+
+            let byte = match value {
+               Object::Int(b) if (0..=255).contains(&b) => b as u8,
+               _ => {
+                  return RuntimeResult::Error {
+                     error: RuntimeErrorType::TypeError,
+                     message: format!(
+                        "Cannot store '{}' in Bytes. Expected an Int from 0 to 255.",
+                        value.as_plain_string()
+                     ),
+                  }
+               }
+            };
+
+            match idx {
+               Some(i) => {
+                  bytes.borrow_mut()[i] = byte;
+                  self.push_stack(value)
+               }
+               None => RuntimeResult::Error {
+                  error: RuntimeErrorType::IndexError,
+                  message: "Bytes index out of bounds.".to_string(),
+               },
+            }
+         }
+         Object::Dict(dict) => {
+            let inserted = dict.borrow_mut().insert_key(index.clone(), value.clone());
+
+            match inserted {
+               Ok(_) => self.push_stack(value),
+               Err(e) => e.to_runtime_error(),
             }
-            _ => RuntimeResult::Error {
-               error: RuntimeErrorType::TypeError,
-               message: format!(
-                  "Dictionary reassignment subscript must be a String. Found '{}' instead.",
-                  index.type_name()
-               ),
-            },
-         },
+         }
          _ => RuntimeResult::Error {
             error: RuntimeErrorType::TypeError,
             message: format!(
@@ -521,15 +1412,43 @@ impl VM {
       }
    }
 
+   /// Executes the instruction to push the object on the TOS into the array below it, as
+   /// done by array comprehensions to build their arrays.
+   fn op_array_push(&mut self) -> RuntimeResult {
+      let item = self.pop_stack();
+
+      match self.pop_stack() {
+         Object::Array(arr) => {
+            arr.borrow_mut().push(item);
+            RuntimeResult::Continue
+         }
+         _ => unreachable!("Expected an array below the TOS."),
+      }
+   }
+
    /// Executes the instruction to create a range object with the two objects on the TOS.
-   fn op_make_range(&mut self) -> RuntimeResult {
+   ///
+   /// # Parameters
+   /// - `inclusive`: Whether the range includes its end (e.g., `0..=10`).
+   fn op_make_range(&mut self, inclusive: bool) -> RuntimeResult {
       let right = self.pop_stack();
       let left = self.pop_stack();
 
       if left.is_int() && right.is_int() {
          let a = left.as_int().unwrap();
          let b = right.as_int().unwrap();
-         self.push_stack(Object::Range(RangeObject { min: a, max: b }))
+
+         if !inclusive {
+            return self.push_stack(Object::Range(RangeObject::new(a, b)));
+         }
+
+         match RangeObject::inclusive(a, b) {
+            Some(range) => self.push_stack(Object::Range(range)),
+            None => RuntimeResult::Error {
+               error: RuntimeErrorType::ArgumentError,
+               message: String::from("The end of an inclusive range must be within the bounds of an Int."),
+            },
+         }
       } else {
          return RuntimeResult::Error {
             error: RuntimeErrorType::TypeError,
@@ -543,55 +1462,38 @@ impl VM {
    }
 
    /// Executes the instruction to perform a unary operation with the object at the TOS.
+   /// Instances can overload the operation with a magic method (e.g., `__neg__`).
    fn unary_operation(&mut self, opr: UnaryExprType) -> RuntimeResult {
-      let val = self.pop_stack();
+      if let Some(result) = unary_magic_method(&opr).and_then(|name| self.call_magic_method(name, 0)) {
+         return result;
+      }
 
-      let result = match opr {
-         UnaryExprType::ArithmeticNeg => -val,
-         UnaryExprType::LogicNeg => Ok(Object::Bool(val.is_falsey())),
-         UnaryExprType::BitwiseNeg => !val,
-      };
+      let val = self.pop_stack();
 
-      match result {
+      match val.unary_operation(&opr) {
          Ok(r) => self.push_stack(r),
          Err(e) => e.to_runtime_error(),
       }
    }
 
    /// Executes the instruction to perform a binary operation with the two objects at the TOS.
+   /// Instances on the left-hand side can overload the operation with a magic method (e.g.,
+   /// `__add__`). If they do not, the operation raises the usual errors for its operand types.
    fn binary_operation(&mut self, opr: BinaryExprType) -> RuntimeResult {
-      let val2 = self.pop_stack();
-      let val1 = self.pop_stack();
+      if let Some(result) = binary_magic_method(&opr).and_then(|name| self.call_magic_method(name, 1)) {
+         return result;
+      }
 
-      let result = match opr {
-         BinaryExprType::Addition => val1 + val2,
-         BinaryExprType::BitwiseAND => val1 & val2,
-         BinaryExprType::BitwiseOR => val1 | val2,
-         BinaryExprType::BitwiseShiftLeft => val1 << val2,
-         BinaryExprType::BitwiseShiftRight => val1 >> val2,
-         BinaryExprType::BitwiseXOR => val1 ^ val2,
-         BinaryExprType::Division => val1 / val2,
-         BinaryExprType::Expo => val1.pow(val2),
-         BinaryExprType::LogicEQ => Ok(Object::Bool(val1 == val2)),
-         BinaryExprType::LogicGreaterThan => val1.gt(val2),
-         BinaryExprType::LogicGreaterThanEQ => val1.gteq(val2),
-         BinaryExprType::LogicLessThan => val1.lt(val2),
-         BinaryExprType::LogicLessThanEQ => val1.lteq(val2),
-         BinaryExprType::LogicNotEQ => Ok(Object::Bool(val1 != val2)),
-         BinaryExprType::Minus => val1 - val2,
-         BinaryExprType::Modulus => val1 % val2,
-         BinaryExprType::Multiplication => val1 * val2,
-         BinaryExprType::Nullish => {
-            if matches!(val1, Object::Null) {
-               Ok(val2)
-            } else {
-               Ok(val1)
-            }
+      if let BinaryExprType::LogicNotEQ = opr {
+         if let Some(result) = self.call_negated_eq() {
+            return result;
          }
-         _ => unreachable!("The other binary operations have special instruction methods."),
-      };
+      }
 
-      match result {
+      let val2 = self.pop_stack();
+      let val1 = self.pop_stack();
+
+      match val1.binary_operation(&opr, val2) {
          Ok(r) => self.push_stack(r),
          Err(e) => e.to_runtime_error(),
       }
@@ -603,19 +1505,52 @@ impl VM {
    fn op_get_iter_next_or_jump(&mut self) -> RuntimeResult {
       let jump = self.next_short() as usize;
 
-      match self.peek_stack(0) {
-         Object::Iter(i) => match get_next_in_iter(i) {
-            Ok(o) => self.push_stack(o),
-            Err(_) => {
-               self.pop_stack();
-               self.current_frame_mut().ip += jump;
-               RuntimeResult::Continue
-            }
-         },
+      let iter = match self.peek_stack(0).into_owned() {
+         Object::Iter(i) => i,
          _ => unreachable!("Expected iterable object on TOS."),
+      };
+
+      match get_next_in_iter(self, &iter) {
+         Ok(o) => self.push_stack(o),
+         Err(e) if is_end_of_iter(&e) => {
+            self.pop_stack();
+            self.current_frame_mut().ip += jump;
+            RuntimeResult::Continue
+         }
+         Err(e) => e,
       }
    }
 
+   /// Executes the instruction to unpack the array or tuple at the top of the stack into its
+   /// items. The operand is the number of items the array or tuple must have.
+   fn op_unpack(&mut self) -> RuntimeResult {
+      let count = self.next_byte() as usize;
+
+      let items = match self.pop_stack() {
+         Object::Array(arr) => arr.borrow().clone(),
+         Object::Tuple(tup) => tup.to_vec(),
+         obj => {
+            return RuntimeResult::Error {
+               error: RuntimeErrorType::TypeError,
+               message: format!("Cannot unpack object of type '{}'.", obj.type_name()),
+            }
+         }
+      };
+
+      if items.len() != count {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::TypeError,
+            message: format!("Expected {} items to unpack, found {}.", count, items.len()),
+         };
+      }
+
+      items.into_iter().for_each(|item| {
+         self.push_stack(item);
+      });
+
+      RuntimeResult::Continue
+   }
+
    /// Executes the instruction to create an array object with the top `N` stack objects.
    fn op_make_array(&mut self) -> RuntimeResult {
       // The number of values to pop from the stack. Essentially the size of the array.
@@ -626,8 +1561,24 @@ impl VM {
          arr_values.push(self.pop_stack());
       }
 
-      let arr = Rc::new(RefCell::new(arr_values));
-      self.push_stack(Object::Array(arr))
+      let arr = self.alloc_array(arr_values);
+      self.push_stack(arr)
+   }
+
+   /// Executes the instruction to build a string from the top `N` stack objects, which are
+   /// converted into strings and concatenated in the order they were pushed onto the stack.
+   fn op_build_string(&mut self) -> RuntimeResult {
+      let size = self.next_byte() as usize;
+      let mut string = String::new();
+
+      for obj in self.pop_stack_n(size).iter() {
+         match self.stringify(obj) {
+            Ok(s) => string.push_str(&s),
+            Err(e) => return e,
+         }
+      }
+
+      self.push_stack(Object::from(string))
    }
 
    /// Executes the instruction to create a tuple object with the top `N` stack objects.
@@ -648,24 +1599,29 @@ impl VM {
    fn op_make_dictionary(&mut self) -> RuntimeResult {
       // The number of values to pop from the stack. Essentially the size of the array.
       let size = self.get_std_or_long_operand(OpCode::MakeDict);
-      let mut dict: HashMap<String, Object> = HashMap::new();
+      let mut dict = DictObject::default();
 
-      for _ in 0..size {
-         let value = self.pop_stack();
-
-         match self.pop_stack() {
+      // The pairs are inserted in the order they were declared, which is the dictionary's order.
+      let mut pairs = self.pop_stack_n(size * 2).into_iter();
+      while let (Some(key), Some(value)) = (pairs.next(), pairs.next()) {
+         match key {
             Object::String(key) => {
-               dict.insert(key, value);
+               dict.insert(key.to_string(), value);
             }
             _ => unreachable!("Expected String for dictionary key."),
          }
       }
 
-      self.push_stack(Object::Dict(Rc::new(RefCell::new(dict))))
+      let dict = self.alloc_dict(dict);
+      self.push_stack(dict)
    }
 
    /// Executes the instruction to subscript and object by some index.
    fn op_subscript(&mut self) -> RuntimeResult {
+      if let Some(result) = self.call_magic_method("__index__", 1) {
+         return result;
+      }
+
       let index = self.pop_stack();
       let target = self.pop_stack();
 
@@ -675,13 +1631,44 @@ impl VM {
       }
    }
 
+   /// Executes the instruction to slice the object below the start, end, and step on the TOS.
+   /// The omitted parts of the slice are `null` on the stack.
+   fn op_slice(&mut self) -> RuntimeResult {
+      let step = self.pop_stack();
+      let end = self.pop_stack();
+      let start = self.pop_stack();
+      let target = self.pop_stack();
+
+      let mut bounds = [None; 3];
+      for (bound, obj) in bounds.iter_mut().zip([start, end, step]) {
+         *bound = match obj {
+            Object::Null => None,
+            Object::Int(i) => Some(i),
+            obj => {
+               return RuntimeResult::Error {
+                  error: RuntimeErrorType::TypeError,
+                  message: format!(
+                     "Slice indices must be of type 'Int'. Found '{}' instead.",
+                     obj.type_name()
+                  ),
+               }
+            }
+         }
+      }
+
+      match target.slice(bounds[0], bounds[1], bounds[2].unwrap_or(1)) {
+         Ok(r) => self.push_stack(r),
+         Err(e) => e.to_runtime_error(),
+      }
+   }
+
    /// Executes the instruction to get the value of a local variable.
    fn op_get_local(&mut self) -> RuntimeResult {
       // The position of the local variable's value in the stack
       let pos = self.get_std_or_long_operand(OpCode::GetLocal);
 
       let idx = self.current_frame().return_index + pos;
-      let value = self.peek_stack_abs(idx).clone();
+      let value = self.peek_stack_abs(idx).into_owned();
       self.push_stack(value)
    }
 
@@ -699,43 +1686,23 @@ impl VM {
 
    /// Executes the instruction to define a global variable with the object at the TOS.
    fn op_define_global(&mut self) -> RuntimeResult {
-      let pos = self.get_std_or_long_operand(OpCode::DefineGlobal);
-
-      // Gets the name from the pool assigns the value to the global
-      if let Object::String(name) = self.read_constant(pos) {
-         let val = self.pop_stack();
-         self.globals.insert(name, val);
-         RuntimeResult::Continue
-      } else {
-         unreachable!("Expected a String for global declaration name.");
-      }
+      let slot = self.get_std_or_long_operand(OpCode::DefineGlobal);
+      self.globals[slot] = self.pop_stack();
+      RuntimeResult::Continue
    }
 
    /// Executes the instruction to get the value of a global variable.
    fn op_get_global(&mut self) -> RuntimeResult {
-      let pos = self.get_std_or_long_operand(OpCode::GetGlobal);
-
-      // Gets the name from the pool
-      if let Object::String(name) = self.read_constant(pos) {
-         let val = self.globals.get(&name).unwrap().clone();
-         self.push_stack(val)
-      } else {
-         unreachable!("Expected a String as name of global variable access.");
-      }
+      let slot = self.get_std_or_long_operand(OpCode::GetGlobal);
+      let val = self.globals[slot].clone();
+      self.push_stack(val)
    }
 
    /// Executes the instruction to modify the value of a global variable.
    fn op_set_global(&mut self) -> RuntimeResult {
-      let pos = self.get_std_or_long_operand(OpCode::SetGlobal);
-
-      // Gets the name from the pool
-      if let Object::String(name) = self.read_constant(pos) {
-         let val = self.stack.last().unwrap().clone();
-         self.globals.insert(name, val);
-         RuntimeResult::Continue
-      } else {
-         unreachable!("Expected a String as global declaration name.");
-      }
+      let slot = self.get_std_or_long_operand(OpCode::SetGlobal);
+      self.globals[slot] = self.peek_stack(0).into_owned();
+      RuntimeResult::Continue
    }
 
    /// Executes the instruction to create an iterator object with the object at the TOS.
@@ -776,25 +1743,22 @@ impl VM {
 
       // Gets the field name string
       let field_name = match self.pop_stack() {
-         Object::String(s) => s,
+         Object::String(s) => s.to_string(),
          _ => unreachable!("Expected String on TOS for class field."),
       };
 
       // Gets the field value
       let value = Box::new(self.pop_stack());
 
-      match self.peek_stack(0).clone() {
+      match self.peek_stack(0).into_owned() {
          Object::Class(c) => {
             let mut class = c.borrow_mut();
 
-            // Gets the mutable reference to the appropriate hash_map to store the field
-            let storage = if is_static {
-               &mut class.statics
+            if is_static {
+               class.statics.insert(field_name, ClassField { value, mode });
             } else {
-               &mut class.members
-            };
-
-            storage.insert(field_name, ClassField { value, mode });
+               class.add_member(field_name, ClassField { value, mode });
+            }
          }
          _ => unreachable!("Expected Class object on TOS to bind field '{}'.", field_name),
       }
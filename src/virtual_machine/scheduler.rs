@@ -0,0 +1,517 @@
+use crate::errors::RuntimeErrorType;
+use crate::objects::channel_obj::ChannelObject;
+use crate::objects::promise_obj::{PromiseObject, PromiseState};
+use crate::objects::{Object, UpValRef};
+use crate::virtual_machine::{CallFrame, RuntimeResult, StackSlot, StackValue, VM};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// What a green thread waits for before it can continue running.
+pub(super) enum Wait {
+   /// The thread can continue right away.
+   Ready,
+   /// The thread continues once the given time is reached.
+   Sleep(Instant),
+   /// The thread continues once a value is sent through the channel, and receives the value.
+   Recv(Rc<RefCell<ChannelObject>>),
+   /// The thread continues once the promise is settled, and receives its value.
+   Promise(Promise),
+}
+
+/// An event that settles a promise once it happens, such as a timer running out. Events are
+/// polled by the scheduler whenever no green thread is ready to run, so native functions can
+/// plug new sources of events (e.g., asynchronous I/O) into the scheduler's event loop.
+pub(crate) trait Event {
+   /// Checks if the event has happened.
+   ///
+   /// # Parameters
+   /// - `now`: The time at which the event is polled, if the VM has a clock.
+   ///
+   /// # Returns
+   /// - `Option<Result<Object, (RuntimeErrorType, String)>>`: The value that the event's
+   ///   promise is resolved with, or the error it is rejected with, or `None` if the event
+   ///   has not happened.
+   fn poll(&mut self, now: Option<Instant>) -> Option<Result<Object, (RuntimeErrorType, String)>>;
+
+   /// Gets the time at which the event happens, if it is known in advance, so that the
+   /// scheduler can sleep until then instead of polling the event repeatedly.
+   fn deadline(&self) -> Option<Instant>;
+}
+
+/// A reference to the state of a promise.
+type Promise = Rc<RefCell<PromiseObject>>;
+
+/// A timer that resolves its promise with `null` once the given time is reached.
+pub(crate) struct Timer(pub(crate) Instant);
+
+impl Event for Timer {
+   fn poll(&mut self, now: Option<Instant>) -> Option<Result<Object, (RuntimeErrorType, String)>> {
+      if now.is_some_and(|now| now >= self.0) {
+         Some(Ok(Object::Null))
+      } else {
+         None
+      }
+   }
+
+   fn deadline(&self) -> Option<Instant> {
+      Some(self.0)
+   }
+}
+
+/// A green thread that is not currently running. Green threads are run by the VM one at a
+/// time, and only switch when the running thread sleeps or waits for a value from a channel.
+pub(super) struct GreenThread {
+   /// The values stack of the thread. Before the thread starts, its stack holds the
+   /// function the thread calls, followed by the arguments of the call.
+   pub(super) stack: Vec<StackValue>,
+   /// The call frames of the thread. Empty if the thread has not started yet.
+   pub(super) frames: Vec<CallFrame>,
+   /// The UpValues that captured the locals of the thread, and the stack index of their
+   /// locals. Other threads use these UpValues as if they were closed while the thread
+   /// is suspended, and they are opened again when the thread resumes.
+   pub(super) up_values: Vec<(Rc<RefCell<UpValRef>>, usize)>,
+   /// What the thread waits for before it can resume.
+   pub(super) wait: Wait,
+   /// Whether this is the main thread of the program, which runs the program's main function.
+   is_main: bool,
+   /// The promise of the async function call that the thread runs, if any, which is settled
+   /// with the result of the call once the thread ends.
+   pub(super) promise: Option<Promise>,
+}
+
+impl GreenThread {
+   /// Checks if the thread can resume at the given time.
+   fn is_ready(&self, now: Option<Instant>) -> bool {
+      match &self.wait {
+         Wait::Ready => true,
+         Wait::Sleep(until) => now.is_some_and(|now| now >= *until),
+         Wait::Recv(channel) => !channel.borrow().is_empty(),
+         Wait::Promise(promise) => promise.borrow().is_settled(),
+      }
+   }
+}
+
+/// The cooperative scheduler of the green threads spawned by a program.
+#[derive(Default)]
+pub(super) struct Scheduler {
+   /// The threads waiting to run, in the order in which they are resumed.
+   pub(super) threads: VecDeque<GreenThread>,
+   /// What the running thread waits for before it can continue. Set by the native functions
+   /// that suspend the running thread (e.g., `sleep(...)`).
+   pending: Option<Wait>,
+   /// Whether the running thread is the main thread of the program.
+   running_main: bool,
+   /// The promise of the async function call that the running thread runs, if any.
+   pub(super) running_promise: Option<Promise>,
+   /// The pending events, and the promises they settle once they happen.
+   pub(super) events: Vec<(Promise, Box<dyn Event>)>,
+   /// The stack of the main thread after the program's main function has ended, which is
+   /// restored once the other threads end.
+   pub(super) main_stack: Option<Vec<StackValue>>,
+}
+
+impl Scheduler {
+   /// Discards the green threads of the previous program, before a new program runs.
+   pub(super) fn reset(&mut self) {
+      *self = Scheduler {
+         running_main: true,
+         ..Default::default()
+      };
+   }
+
+   /// Checks if there are green threads waiting to run.
+   pub(super) fn has_threads(&self) -> bool {
+      !self.threads.is_empty()
+   }
+
+   /// Settles the promises of the events that have happened, and stops tracking those events.
+   fn poll_events(&mut self, now: Option<Instant>) {
+      self.events.retain_mut(|(promise, event)| match event.poll(now) {
+         Some(Ok(value)) => {
+            promise.borrow_mut().resolve(value);
+            false
+         }
+         Some(Err((error, message))) => {
+            promise.borrow_mut().reject(error, message);
+            false
+         }
+         None => true,
+      });
+   }
+}
+
+impl VM {
+   /// Spawns a green thread that calls a function with the given arguments. The thread
+   /// starts running once the running thread sleeps, waits for a channel, or ends.
+   ///
+   /// # Parameters
+   /// - `callee`: The function called by the thread.
+   /// - `args`: The arguments of the call.
+   pub(crate) fn spawn_thread(&mut self, callee: Object, args: Vec<Object>) {
+      let mut stack = vec![StackValue::from_object(callee)];
+      stack.extend(args.into_iter().map(StackValue::from_object));
+
+      self.scheduler.threads.push_back(GreenThread {
+         stack,
+         frames: vec![],
+         up_values: vec![],
+         wait: Wait::Ready,
+         is_main: false,
+         promise: None,
+      });
+   }
+
+   /// Creates a promise that is settled by an event once it happens.
+   ///
+   /// # Parameters
+   /// - `event`: The event that settles the promise.
+   pub(crate) fn promise_event(&mut self, event: Box<dyn Event>) -> Object {
+      let promise = self.alloc_promise();
+      self.scheduler.events.push((promise.clone(), event));
+      Object::Promise(promise)
+   }
+
+   /// Executes the instruction at the start of an async function, which moves the function's
+   /// call frame into a new green thread, and returns a promise of the call's result to the
+   /// caller. The body of the function runs once the running thread sleeps, waits, or ends.
+   pub(super) fn op_async(&mut self) -> RuntimeResult {
+      let frame = self.frames.pop().unwrap();
+      let stack = self.stack.split_off(frame.return_index);
+      let is_deferred = frame.is_deferred;
+
+      let promise = self.alloc_promise();
+
+      // The stack of the new thread starts at the function's frame, so the frame is rebased.
+      self.scheduler.threads.push_back(GreenThread {
+         stack,
+         frames: vec![CallFrame {
+            return_index: 0,
+            is_deferred: false,
+            ..frame
+         }],
+         up_values: vec![],
+         wait: Wait::Ready,
+         is_main: false,
+         promise: Some(promise.clone()),
+      });
+
+      // The value of a deferred call is discarded, like when the call returns.
+      if is_deferred {
+         return self.resume_after_deferred();
+      }
+
+      self.push_stack(Object::Promise(promise))
+   }
+
+   /// Executes the instruction of an `await` expression. If the value at the top of the stack
+   /// is a pending promise, the running green thread waits until the promise is settled.
+   /// Awaiting a value that is not a promise results in the value itself.
+   pub(super) fn op_await(&mut self) -> RuntimeResult {
+      let promise = match self.pop_stack() {
+         Object::Promise(p) => p,
+         value => return self.push_stack(value),
+      };
+
+      let settled = match &promise.borrow().state {
+         PromiseState::Pending => None,
+         PromiseState::Resolved(value) => Some(Ok(value.clone())),
+         PromiseState::Rejected(error, message) => Some(Err((error.clone(), message.clone()))),
+      };
+
+      match settled {
+         Some(Ok(value)) => return self.push_stack(value),
+         Some(Err((error, message))) => return RuntimeResult::Error { error, message },
+         None => {}
+      }
+
+      if self.native_calls > 0 {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::DeadlockError,
+            message: String::from(
+               "Cannot await a pending promise inside a function called by a native function.",
+            ),
+         };
+      }
+
+      // The placeholder is replaced by the value of the promise once the thread resumes.
+      self.scheduler.pending = Some(Wait::Promise(promise));
+      self.suspend_with(Object::Null)
+   }
+
+   /// Pauses the running green thread for the given duration, so that other threads can run,
+   /// and pushes `null` onto the stack. Inside a function called by a native function, where
+   /// threads cannot switch, the whole VM sleeps instead.
+   ///
+   /// # Parameters
+   /// - `duration`: How long the thread sleeps.
+   pub(crate) fn sleep_thread(&mut self, duration: Duration) -> RuntimeResult {
+      let now = match self.now() {
+         Ok(now) => now,
+         Err(e) => return e,
+      };
+
+      if self.native_calls > 0 {
+         thread::sleep(duration);
+         return self.push_stack(Object::Null);
+      }
+
+      self.scheduler.pending = Some(Wait::Sleep(now + duration));
+      self.suspend_with(Object::Null)
+   }
+
+   /// Receives a value from a channel, and pushes it onto the stack. If the channel is empty,
+   /// the running green thread waits until another thread sends a value through the channel.
+   ///
+   /// # Parameters
+   /// - `channel`: The channel to receive a value from.
+   pub(crate) fn recv_from_channel(&mut self, channel: Rc<RefCell<ChannelObject>>) -> RuntimeResult {
+      if let Some(value) = channel.borrow_mut().recv() {
+         return self.push_stack(value);
+      }
+
+      if self.native_calls > 0 {
+         return RuntimeResult::Error {
+            error: RuntimeErrorType::DeadlockError,
+            message: String::from(
+               "Cannot wait for a value from an empty channel inside a function called by a native function.",
+            ),
+         };
+      }
+
+      // The placeholder is replaced by the received value once the thread resumes.
+      self.scheduler.pending = Some(Wait::Recv(channel));
+      self.suspend_with(Object::Null)
+   }
+
+   /// Pushes the result of a native function that suspends the running green thread.
+   ///
+   /// # Parameters
+   /// - `result`: The result of the native function.
+   fn suspend_with(&mut self, result: Object) -> RuntimeResult {
+      match self.push_stack(result) {
+         RuntimeResult::Continue => RuntimeResult::Suspend,
+         error => error,
+      }
+   }
+
+   /// Suspends the running green thread, which waits for what was requested by the native
+   /// function that suspended it, and resumes the next thread that is ready to run.
+   pub(super) fn switch_thread(&mut self) -> RuntimeResult {
+      let wait = self.scheduler.pending.take().unwrap_or(Wait::Ready);
+      let thread = self.suspend_thread(wait);
+      self.scheduler.threads.push_back(thread);
+
+      self.resume_next_thread()
+   }
+
+   /// Ends the running green thread once its function has returned, and resumes the next
+   /// thread that is ready to run.
+   pub(super) fn end_thread(&mut self) -> RuntimeResult {
+      // The value returned by the thread's function resolves the promise of the async call
+      // run by the thread, if any, and is discarded otherwise.
+      if let Some(promise) = self.scheduler.running_promise.take() {
+         let value = self.stack.last().map_or(Object::Null, |v| v.to_object());
+         promise.borrow_mut().resolve(value);
+      }
+
+      self.scheduler.pending = None;
+      self.close_up_values_from(0);
+      self.stack.clear();
+
+      self.resume_next_thread()
+   }
+
+   /// Ends the running green thread after a runtime error that was not caught by the thread,
+   /// if the thread runs an async function call, rejecting the promise of the call with the
+   /// error. The errors of other threads end the program instead.
+   ///
+   /// # Parameters
+   /// - `error`: The type of runtime error.
+   /// - `message`: The error message.
+   ///
+   /// # Returns
+   /// - `Result<RuntimeResult, RuntimeResult>`: The result of resuming the next thread, or
+   ///   the error itself if the running thread does not run an async function call.
+   pub(super) fn reject_thread(
+      &mut self,
+      error: RuntimeErrorType,
+      message: String,
+   ) -> Result<RuntimeResult, RuntimeResult> {
+      let promise = match self.scheduler.running_promise.take() {
+         Some(p) => p,
+         None => return Err(RuntimeResult::Error { error, message }),
+      };
+
+      promise.borrow_mut().reject(error, message);
+      self.frames.clear();
+
+      Ok(self.end_thread())
+   }
+
+   /// Ends the main thread once the program's main function has ended, and runs the other
+   /// green threads until they end. The stack of the main thread is kept aside, so that the
+   /// value of the program's last expression can still be read after the program ends.
+   pub(super) fn end_main_thread(&mut self) -> RuntimeResult {
+      self.close_up_values_from(0);
+      self.scheduler.main_stack = Some(std::mem::take(&mut self.stack));
+
+      self.resume_next_thread()
+   }
+
+   /// Moves the state of the running green thread out of the VM.
+   ///
+   /// # Parameters
+   /// - `wait`: What the thread waits for before it can resume.
+   fn suspend_thread(&mut self, wait: Wait) -> GreenThread {
+      let stack = std::mem::take(&mut self.stack);
+
+      // The thread's open UpValues are closed, because the stack indices they
+      // point to are only valid while the thread's stack is in the VM.
+      let up_values = std::mem::take(&mut self.up_values)
+         .into_iter()
+         .filter_map(|u| {
+            let idx = match *u.borrow() {
+               UpValRef::Open(i) => i,
+               UpValRef::Closed(_) => return None,
+            };
+
+            u.replace(UpValRef::Closed(stack[idx].to_object()));
+            Some((u, idx))
+         })
+         .collect();
+
+      GreenThread {
+         stack,
+         frames: std::mem::take(&mut self.frames),
+         up_values,
+         wait,
+         is_main: self.scheduler.running_main,
+         promise: self.scheduler.running_promise.take(),
+      }
+   }
+
+   /// Resumes the first green thread that is ready to run, sleeping until one of the threads
+   /// wakes up or one of the pending events happens if needed. This is the event loop of the
+   /// VM. Once the main thread has ended, the program ends when every other thread has ended,
+   /// or when the remaining threads wait for channels or promises that cannot be settled anymore.
+   fn resume_next_thread(&mut self) -> RuntimeResult {
+      loop {
+         // Without a clock, there are no sleeping threads or timers to wait for.
+         let now = self.now().ok();
+         self.scheduler.poll_events(now);
+
+         if let Some(idx) = self.scheduler.threads.iter().position(|t| t.is_ready(now)) {
+            let thread = self.scheduler.threads.remove(idx).unwrap();
+            return self.resume_thread(thread);
+         }
+
+         let sleeps = self.scheduler.threads.iter().filter_map(|t| match t.wait {
+            Wait::Sleep(until) => Some(until),
+            _ => None,
+         });
+         let deadlines = self.scheduler.events.iter().filter_map(|(_, e)| e.deadline());
+
+         if let (Some(now), Some(until)) = (now, sleeps.chain(deadlines).min()) {
+            thread::sleep(until.saturating_duration_since(now));
+            continue;
+         }
+
+         // The events that do not know when they happen are polled periodically.
+         if !self.scheduler.events.is_empty() {
+            if now.is_some() {
+               thread::sleep(Duration::from_millis(1));
+            }
+            continue;
+         }
+
+         // Every remaining thread waits for a channel or a promise, so none of them can ever resume.
+         return match self.scheduler.threads.iter().position(|t| t.is_main) {
+            Some(idx) => {
+               let mut main = self.scheduler.threads.remove(idx).unwrap();
+               let waited = match std::mem::replace(&mut main.wait, Wait::Ready) {
+                  Wait::Promise(_) => "Cannot await a promise that is never settled.",
+                  _ => "Cannot receive a value from an empty channel.",
+               };
+               self.resume_thread(main);
+
+               RuntimeResult::Error {
+                  error: RuntimeErrorType::DeadlockError,
+                  message: format!(
+                     "{} Every green thread is waiting for a channel or a promise.",
+                     waited
+                  ),
+               }
+            }
+            None => {
+               self.scheduler.threads.clear();
+               self.end_program()
+            }
+         };
+      }
+   }
+
+   /// Moves the state of a suspended green thread into the VM, and starts the thread if it
+   /// has not started yet.
+   fn resume_thread(&mut self, thread: GreenThread) -> RuntimeResult {
+      self.stack = thread.stack;
+      self.frames = thread.frames;
+      self.scheduler.running_main = thread.is_main;
+      self.scheduler.running_promise = thread.promise;
+
+      for (u, idx) in thread.up_values {
+         if let UpValRef::Closed(value) = u.replace(UpValRef::Open(idx)) {
+            self.stack[idx] = StackValue::from_object(value);
+         }
+
+         self.up_values.push(u);
+      }
+
+      // The placeholder pushed while the thread waited is replaced by the received value.
+      let top = self.stack.len().saturating_sub(1);
+      match thread.wait {
+         Wait::Recv(channel) => {
+            if let Some(value) = channel.borrow_mut().recv() {
+               self.stack[top] = StackValue::from_object(value);
+            }
+         }
+         Wait::Promise(promise) => match &promise.borrow().state {
+            PromiseState::Resolved(value) => self.stack[top] = StackValue::from_object(value.clone()),
+            PromiseState::Rejected(error, message) => {
+               return RuntimeResult::Error {
+                  error: error.clone(),
+                  message: message.clone(),
+               }
+            }
+            PromiseState::Pending => {}
+         },
+         _ => {}
+      }
+
+      if !self.frames.is_empty() {
+         return RuntimeResult::Continue;
+      }
+
+      let callee = self.stack[0].to_object();
+      let arg_count = (self.stack.len() - 1) as u8;
+
+      match self.call_object(callee, arg_count) {
+         // Calls to native functions return right away, which ends the thread.
+         RuntimeResult::Continue | RuntimeResult::Suspend if self.frames.is_empty() => self.end_thread(),
+         result => result,
+      }
+   }
+
+   /// Ends the program once every green thread has ended, restoring the stack of the main thread.
+   fn end_program(&mut self) -> RuntimeResult {
+      if let Some(stack) = self.scheduler.main_stack.take() {
+         self.stack = stack;
+      }
+
+      self.scheduler.running_main = true;
+      self.scheduler.events.clear();
+      RuntimeResult::EndOK
+   }
+}
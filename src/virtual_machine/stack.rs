@@ -0,0 +1,56 @@
+use crate::objects::Object;
+use std::borrow::Cow;
+
+/// The representation of the objects stored in the VM's values stack. By default, objects are
+/// stored as they are. With the `nan_boxing` feature, they are stored as 8-byte NaN-boxed words.
+#[cfg(not(feature = "nan_boxing"))]
+pub type StackValue = Object;
+#[cfg(feature = "nan_boxing")]
+pub type StackValue = crate::objects::nan_box::NanBox;
+
+/// Converts the values in the VM's stack into Hinton objects.
+pub trait StackSlot: Clone {
+   /// Stores an object in a stack slot.
+   fn from_object(obj: Object) -> Self;
+
+   /// Gets the object stored in this slot.
+   fn as_object(&self) -> Cow<'_, Object>;
+
+   /// Gets the object stored in this slot, consuming the slot.
+   fn into_object(self) -> Object;
+
+   /// Gets a copy of the object stored in this slot.
+   fn to_object(&self) -> Object {
+      self.as_object().into_owned()
+   }
+}
+
+#[cfg(not(feature = "nan_boxing"))]
+impl StackSlot for Object {
+   fn from_object(obj: Object) -> Self {
+      obj
+   }
+
+   fn as_object(&self) -> Cow<'_, Object> {
+      Cow::Borrowed(self)
+   }
+
+   fn into_object(self) -> Object {
+      self
+   }
+}
+
+#[cfg(feature = "nan_boxing")]
+impl StackSlot for StackValue {
+   fn from_object(obj: Object) -> Self {
+      StackValue::from(obj)
+   }
+
+   fn as_object(&self) -> Cow<'_, Object> {
+      self.decode()
+   }
+
+   fn into_object(self) -> Object {
+      self.into_decoded()
+   }
+}
@@ -0,0 +1,125 @@
+use crate::core::bytecode::OpCode;
+use crate::core::disassembler::disassemble_instruction;
+use crate::errors::paint;
+use crate::virtual_machine::{StackSlot, VM};
+
+/// Determines which of the executed instructions are printed by the VM's execution trace.
+#[derive(Clone, PartialEq, Debug)]
+pub enum TraceMode {
+   /// Every executed instruction.
+   All,
+   /// Only the instructions that call a function, create an instance, or return from a function.
+   Calls,
+   /// Only the instructions executed by the functions with the given name.
+   Function(String),
+}
+
+impl TraceMode {
+   /// Gets the trace mode with the given name, as passed to the `--trace=<mode>` flag. Names
+   /// other than `all` and `calls` select the instructions of the function with that name.
+   ///
+   /// # Parameters
+   /// - `name`: The name of the trace mode.
+   pub fn from_name(name: &str) -> Self {
+      match name {
+         "" | "all" => TraceMode::All,
+         "calls" => TraceMode::Calls,
+         function => TraceMode::Function(function.to_string()),
+      }
+   }
+
+   /// Checks if an instruction is printed by the execution trace in this mode.
+   ///
+   /// # Parameters
+   /// - `function`: The name of the function executing the instruction.
+   /// - `instruction`: The instruction.
+   pub fn includes(&self, function: &str, instruction: &OpCode) -> bool {
+      match self {
+         TraceMode::All => true,
+         TraceMode::Calls => matches!(
+            instruction,
+            OpCode::FuncCall
+               | OpCode::FuncCallNamed
+               | OpCode::FuncCallSpread
+               | OpCode::TailCall
+               | OpCode::MakeInstance
+               | OpCode::MakeInstanceNamed
+               | OpCode::MakeInstanceSpread
+               | OpCode::Return
+         ),
+         TraceMode::Function(name) => name == function,
+      }
+   }
+}
+
+/// Prints the instructions executed by the VM, along with the values in the stack slots of
+/// the current call frame. Useful for debugging the bytecode generated by the compiler.
+pub struct Tracer {
+   mode: TraceMode,
+   /// The names of the native functions and primitive classes, used to disassemble the
+   /// instructions that load them. They are looked up when the first instruction is traced,
+   /// so that they include the natives registered after the tracer was attached.
+   names: Option<(Vec<String>, Vec<String>)>,
+}
+
+impl Tracer {
+   /// Creates a new tracer.
+   ///
+   /// # Parameters
+   /// - `mode`: Which of the executed instructions are printed.
+   pub fn new(mode: TraceMode) -> Self {
+      Tracer { mode, names: None }
+   }
+}
+
+impl VM {
+   /// Prints the next instruction to be executed, if it is included in the execution trace.
+   ///
+   /// # Parameters
+   /// - `instruction`: The instruction about to be executed.
+   pub(super) fn trace_instruction(&mut self, instruction: &OpCode) {
+      let mut tracer = match self.tracer.take() {
+         Some(t) => t,
+         None => return,
+      };
+
+      let frame = self.current_frame();
+      let function = frame.closure.function.borrow();
+
+      if tracer.mode.includes(&function.name, instruction) {
+         if tracer.names.is_none() {
+            tracer.names = Some((
+               self.built_in.natives.get_names(),
+               self.built_in.primitives.get_names(),
+            ));
+         }
+
+         let (natives, primitives) = tracer.names.as_ref().unwrap();
+         let offset = frame.ip - 1;
+         let (text, _) = disassemble_instruction(&function.chunk, offset, natives, primitives);
+         let indent = "  ".repeat(self.frames.len() - 1);
+
+         let slots: Vec<String> = self.stack[frame.return_index..]
+            .iter()
+            .map(|slot| format!("{}", slot.as_object()))
+            .collect();
+
+         eprintln!(
+            "{}{} {:>05} (line {}) {}",
+            indent,
+            function.name,
+            offset,
+            function.chunk.get_line_info(offset).0,
+            text
+         );
+         eprintln!(
+            "{}{}",
+            indent,
+            paint(&format!("   stack: [{}]", slots.join(", ")))
+         );
+      }
+
+      drop(function);
+      self.tracer = Some(tracer);
+   }
+}
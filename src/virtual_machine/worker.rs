@@ -0,0 +1,232 @@
+use crate::errors::{HintonError, ObjectOprErrType};
+use crate::objects::bigint::BigInt;
+use crate::objects::dict_obj::DictObject;
+use crate::objects::set_obj::SetObject;
+use crate::objects::Object;
+use crate::virtual_machine::{VmConfig, VM};
+use std::cell::RefCell;
+use std::convert::TryFrom;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+
+/// The maximum depth of the collections nested inside a message. Collections that
+/// contain themselves cannot be sent, and are reported once they reach this depth.
+const MAX_MESSAGE_DEPTH: usize = 256;
+
+/// A Hinton value that can be sent between threads. The objects of a VM are reference-counted
+/// with `Rc`, so they cannot leave the thread of their VM; instead, they are copied into a
+/// message, which the receiving VM converts back into new objects. Only plain data can be
+/// copied into a message (functions, classes, instances, and iterators cannot).
+#[derive(Clone, Debug, PartialEq)]
+pub enum Message {
+   Array(Vec<Message>),
+   BigInt(BigInt),
+   Bool(bool),
+   Bytes(Vec<u8>),
+   Char(char),
+   Dict(Vec<(Message, Message)>),
+   Float(f64),
+   Int(i64),
+   Null,
+   Set(Vec<Message>),
+   String(String),
+   Tuple(Vec<Message>),
+}
+
+impl Message {
+   /// Copies an object into a message.
+   ///
+   /// # Parameters
+   /// - `obj`: The object to copy.
+   /// - `depth`: The number of collections that contain the object.
+   fn from_object(obj: &Object, depth: usize) -> Result<Message, ObjectOprErrType> {
+      if depth > MAX_MESSAGE_DEPTH {
+         return Err(ObjectOprErrType::TypeError(String::from(
+            "Collections nested this deeply (or that contain themselves) cannot be sent.",
+         )));
+      }
+
+      let items = |items: &mut dyn Iterator<Item = &Object>| {
+         items
+            .map(|o| Message::from_object(o, depth + 1))
+            .collect::<Result<Vec<_>, _>>()
+      };
+
+      Ok(match obj {
+         Object::Array(a) => Message::Array(items(&mut a.borrow().iter())?),
+         Object::BigInt(b) => Message::BigInt((**b).clone()),
+         Object::Bool(b) => Message::Bool(*b),
+         Object::Bytes(b) => Message::Bytes(b.borrow().clone()),
+         Object::Char(c) => Message::Char(*c),
+         Object::Dict(d) => Message::Dict(
+            d.borrow()
+               .iter()
+               .map(|(k, v)| {
+                  Ok((
+                     Message::from_object(k, depth + 1)?,
+                     Message::from_object(v, depth + 1)?,
+                  ))
+               })
+               .collect::<Result<Vec<_>, _>>()?,
+         ),
+         Object::Float(f) => Message::Float(*f),
+         Object::Int(i) => Message::Int(*i),
+         Object::Null => Message::Null,
+         Object::Set(s) => Message::Set(items(&mut s.borrow().iter())?),
+         Object::String(s) => Message::String(s.to_string()),
+         Object::Tuple(t) => Message::Tuple(items(&mut t.iter())?),
+         _ => {
+            return Err(ObjectOprErrType::TypeError(format!(
+               "Objects of type '{}' cannot be sent to another VM.",
+               obj.type_name()
+            )))
+         }
+      })
+   }
+}
+
+impl TryFrom<&Object> for Message {
+   type Error = ObjectOprErrType;
+
+   fn try_from(o: &Object) -> Result<Self, Self::Error> {
+      Message::from_object(o, 0)
+   }
+}
+
+impl From<Message> for Object {
+   fn from(m: Message) -> Self {
+      let objects = |items: Vec<Message>| items.into_iter().map(Object::from);
+
+      match m {
+         Message::Array(a) => Object::Array(Rc::new(RefCell::new(objects(a).collect()))),
+         Message::BigInt(b) => Object::from(b),
+         Message::Bool(b) => Object::Bool(b),
+         Message::Bytes(b) => Object::from(b),
+         Message::Char(c) => Object::Char(c),
+         Message::Dict(entries) => {
+            let mut dict = DictObject::default();
+
+            // The keys were hashable when they were sent, so they still are.
+            for (k, v) in entries {
+               let _ = dict.insert_key(Object::from(k), Object::from(v));
+            }
+
+            Object::Dict(Rc::new(RefCell::new(dict)))
+         }
+         Message::Float(f) => Object::Float(f),
+         Message::Int(i) => Object::Int(i),
+         Message::Null => Object::Null,
+         Message::Set(s) => match SetObject::from_items(objects(s)) {
+            Ok(set) => Object::Set(Rc::new(RefCell::new(set))),
+            Err(_) => unreachable!("The items of a set are hashable."),
+         },
+         Message::String(s) => Object::from(s),
+         Message::Tuple(t) => Object::Tuple(Rc::new(objects(t).collect())),
+      }
+   }
+}
+
+/// A Hinton program that runs in its own VM, on a separate thread. The host and the program
+/// communicate by passing messages: the program receives the messages sent by the host with
+/// the `receive_message()` native function, and sends messages to the host with the
+/// `post_message(value)` native function. Each worker has its own globals, heap, and limits,
+/// so several workers can run programs concurrently without sharing any state.
+///
+/// # Examples
+/// ```
+/// let worker = Worker::spawn("while true { post_message(receive_message() * 2); }");
+/// worker.send(Message::Int(21));
+/// assert_eq!(worker.recv(), Some(Message::Int(42)));
+/// ```
+pub struct Worker {
+   /// Sends messages to the program.
+   inbox: Sender<Message>,
+   /// Receives the messages posted by the program.
+   outbox: Receiver<Message>,
+   /// The thread running the program, which returns the result of the program.
+   thread: JoinHandle<Result<Message, HintonError>>,
+}
+
+impl Worker {
+   /// Starts running a program in a new VM, on a new thread.
+   ///
+   /// # Parameters
+   /// - `source`: The source text of the program.
+   pub fn spawn(source: &str) -> Self {
+      Worker::spawn_with_config(source, VmConfig::default())
+   }
+
+   /// Starts running a program in a new VM with the given limits, on a new thread.
+   ///
+   /// # Parameters
+   /// - `source`: The source text of the program.
+   /// - `config`: The limits of the VM that runs the program.
+   pub fn spawn_with_config(source: &str, config: VmConfig) -> Self {
+      let (inbox, inbox_receiver) = channel::<Message>();
+      let (outbox_sender, outbox) = channel::<Message>();
+      let source = source.to_string();
+
+      let thread = thread::spawn(move || {
+         // The VM is created on the worker's thread, since its objects cannot leave the thread.
+         let mut vm = VM::new(PathBuf::new()).with_config(config);
+
+         vm.register_native("post_message", 1, move |args| {
+            let message = Message::try_from(&args[0])?;
+
+            // The message is dropped if the host is no longer listening.
+            let _ = outbox_sender.send(message);
+            Ok(Object::Null)
+         });
+
+         // Once the host stops sending messages, the program receives `null`.
+         vm.register_native("receive_message", 0, move |_| {
+            Ok(inbox_receiver.recv().map(Object::from).unwrap_or(Object::Null))
+         });
+
+         let value = vm.eval(&source)?;
+         Ok(Message::try_from(&value).unwrap_or(Message::Null))
+      });
+
+      Worker {
+         inbox,
+         outbox,
+         thread,
+      }
+   }
+
+   /// Sends a message to the program.
+   ///
+   /// # Returns
+   /// - `bool`: Whether the message was sent, which fails if the program has already ended.
+   pub fn send(&self, message: Message) -> bool {
+      self.inbox.send(message).is_ok()
+   }
+
+   /// Waits for the next message posted by the program.
+   ///
+   /// # Returns
+   /// - `Option<Message>`: The message, or `None` if the program ended without posting more
+   ///   messages.
+   pub fn recv(&self) -> Option<Message> {
+      self.outbox.recv().ok()
+   }
+
+   /// Waits for the program to end, and gets its result. The messages posted by the program
+   /// that were not received are discarded.
+   ///
+   /// # Returns
+   /// - `Result<Message, HintonError>`: The value of the expression statement at the end of the
+   ///   program (or `null` if the program does not end with one, or if its value cannot be
+   ///   sent), or the error that stopped the program.
+   pub fn join(self) -> Result<Message, HintonError> {
+      // Closes the inbox, so that a program waiting for a message receives `null` and can end.
+      drop(self.inbox);
+
+      match self.thread.join() {
+         Ok(result) => result,
+         Err(panic) => std::panic::resume_unwind(panic),
+      }
+   }
+}
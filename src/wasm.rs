@@ -0,0 +1,30 @@
+//! The JavaScript API of Hinton, which is available when the crate is compiled to WebAssembly
+//! (e.g., with `wasm-pack build --target web`), so that Hinton programs can run in a browser.
+//! The diagnostics are not colored on this target, and the natives that access the host system
+//! (e.g., `File.read(...)`) raise an `IOError`. The target has no clock, so the natives that read
+//! the time (e.g., `clock()` or `sleep(...)`) raise an `IOError` as well.
+
+use wasm_bindgen::prelude::*;
+
+/// The result of running a program with `run(source)`.
+#[wasm_bindgen(getter_with_clone)]
+pub struct RunResult {
+   /// The output of the program (e.g., the text printed with `print(...)`).
+   pub output: String,
+   /// The errors reported while the program ran.
+   pub errors: String,
+}
+
+/// Runs the source text of a program, and returns its output and errors.
+///
+/// # Parameters
+/// - `source`: The source text of the program.
+#[wasm_bindgen]
+pub fn run(source: &str) -> RunResult {
+   let captured = crate::run_captured(source);
+
+   RunResult {
+      output: captured.output,
+      errors: captured.errors,
+   }
+}
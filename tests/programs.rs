@@ -0,0 +1,186 @@
+//! Runs every Hinton program in the `tests/programs/` directory with the `hinton` executable,
+//! and compares its output with the expectations written in the program's comments:
+//! - `// expect: <text>`: The next line printed to the standard output is `<text>`.
+//! - `// expect error: <text>`: The next lines printed to the standard error include a line
+//!   that contains `<text>` (errors are also printed with snippets and stack traces, which
+//!   are not compared).
+//! - `// expect exit: <code>`: The program exits with the given code (by default, `0`).
+//!
+//! Every mismatch of every program is reported before the test fails.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The directory of the programs, relative to the root of the crate.
+const PROGRAMS_DIR: &str = "tests/programs";
+
+/// The output expected from a program.
+#[derive(Default)]
+struct Expectations {
+   /// The lines expected in the standard output.
+   stdout: Vec<String>,
+   /// The text of the lines expected in the standard error, in order.
+   stderr: Vec<String>,
+   /// The expected exit code.
+   exit_code: i32,
+}
+
+impl Expectations {
+   /// Reads the expectations from the comments of a program.
+   ///
+   /// # Parameters
+   /// - `source`: The source text of the program.
+   fn parse(source: &str) -> Result<Self, String> {
+      let mut expectations = Expectations::default();
+
+      for (idx, line) in source.lines().enumerate() {
+         let comment = match line.find("// expect") {
+            Some(pos) => &line[pos + 3..],
+            None => continue,
+         };
+
+         // The space after the colon is optional, so that empty lines can be expected.
+         let value = |prefix: &str| {
+            comment
+               .strip_prefix(prefix)
+               .map(|t| t.strip_prefix(' ').unwrap_or(t))
+         };
+
+         if let Some(text) = value("expect:") {
+            expectations.stdout.push(text.to_string());
+         } else if let Some(text) = value("expect error:") {
+            expectations.stderr.push(text.to_string());
+         } else if let Some(code) = value("expect exit:") {
+            expectations.exit_code = code
+               .trim()
+               .parse()
+               .map_err(|_| format!("line {}: invalid exit code '{}'", idx + 1, code))?;
+         } else {
+            return Err(format!("line {}: unknown expectation '{}'", idx + 1, comment));
+         }
+      }
+
+      Ok(expectations)
+   }
+}
+
+/// Finds the `.ht` files in a directory and its subdirectories, in alphabetical order.
+fn collect_programs(dir: &Path) -> Vec<PathBuf> {
+   let mut entries: Vec<PathBuf> = fs::read_dir(dir)
+      .unwrap_or_else(|e| panic!("Could not read '{}': {}", dir.display(), e))
+      .filter_map(|e| e.ok().map(|e| e.path()))
+      .collect();
+   entries.sort();
+
+   entries
+      .into_iter()
+      .flat_map(|path| {
+         if path.is_dir() {
+            collect_programs(&path)
+         } else if path.extension().is_some_and(|ext| ext == "ht") {
+            vec![path]
+         } else {
+            vec![]
+         }
+      })
+      .collect()
+}
+
+/// Formats the differences between the expected and the actual lines of an output stream.
+fn diff_lines(expected: &[String], actual: &[&str]) -> String {
+   let mut diff = String::new();
+
+   for idx in 0..expected.len().max(actual.len()) {
+      match (expected.get(idx), actual.get(idx)) {
+         (Some(e), Some(a)) if e == a => diff.push_str(&format!("     {}\n", a)),
+         (e, a) => {
+            if let Some(e) = e {
+               diff.push_str(&format!("   - {}\n", e));
+            }
+            if let Some(a) = a {
+               diff.push_str(&format!("   + {}\n", a));
+            }
+         }
+      }
+   }
+
+   diff
+}
+
+/// Runs a program and compares its output with its expectations.
+///
+/// # Returns
+/// - `Result<(), String>`: The description of the mismatches, if any.
+fn check_program(path: &Path) -> Result<(), String> {
+   let source = fs::read_to_string(path).map_err(|e| format!("could not read the file: {}", e))?;
+   let expected = Expectations::parse(&source)?;
+
+   let output = Command::new(env!("CARGO_BIN_EXE_hinton"))
+      .arg("--no-color")
+      .arg(path)
+      .current_dir(path.parent().unwrap())
+      .output()
+      .map_err(|e| format!("could not run the program: {}", e))?;
+
+   let stdout = String::from_utf8_lossy(&output.stdout);
+   let stderr = String::from_utf8_lossy(&output.stderr);
+   let mut failures = vec![];
+
+   let stdout_lines: Vec<&str> = stdout.lines().collect();
+   if stdout_lines != expected.stdout {
+      failures.push(format!(
+         "unexpected stdout (- expected, + actual):\n{}",
+         diff_lines(&expected.stdout, &stdout_lines)
+      ));
+   }
+
+   // The expected errors must appear in order, but other lines may be printed in between.
+   let mut stderr_lines = stderr.lines();
+   for text in expected.stderr.iter() {
+      if !stderr_lines.any(|line| line.contains(text.as_str())) {
+         failures.push(format!("missing error '{}' in stderr:\n{}", text, stderr));
+         break;
+      }
+   }
+
+   match output.status.code() {
+      Some(code) if code == expected.exit_code => {}
+      code => failures.push(format!(
+         "expected exit code {}, got {:?}\n{}",
+         expected.exit_code, code, stderr
+      )),
+   }
+
+   if failures.is_empty() {
+      Ok(())
+   } else {
+      Err(failures.join("\n"))
+   }
+}
+
+#[test]
+fn programs_produce_their_expected_output() {
+   let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(PROGRAMS_DIR);
+   let programs = collect_programs(&dir);
+   assert!(!programs.is_empty(), "No programs found in '{}'.", dir.display());
+
+   let failures: Vec<String> = programs
+      .iter()
+      .filter_map(|path| {
+         let name = path.strip_prefix(&dir).unwrap_or(path).display();
+         check_program(path)
+            .err()
+            .map(|e| format!("--- {} ---\n{}", name, e))
+      })
+      .collect();
+
+   if !failures.is_empty() {
+      panic!(
+         "{} of {} programs failed:\n\n{}",
+         failures.len(),
+         programs.len(),
+         failures.join("\n\n")
+      );
+   }
+}